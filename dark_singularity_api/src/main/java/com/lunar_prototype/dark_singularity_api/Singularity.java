@@ -5,6 +5,8 @@ import java.io.InputStream;
 import java.nio.file.Files;
 import java.nio.file.Path;
 import java.nio.file.StandardCopyOption;
+import java.util.LinkedHashMap;
+import java.util.Map;
 import java.util.concurrent.atomic.AtomicBoolean;
 
 /**
@@ -76,27 +78,162 @@ public class Singularity implements AutoCloseable {
     public static final int IDX_TACTICAL = 2;
     public static final int IDX_REFLEX = 3;
 
+    /** Condition id for rules that apply regardless of the active conditions (see {@link #registerHamiltonianRules}). */
+    public static final int ALWAYS_CONDITION = -1;
+
+    // --- Operators for composite rule conditions (see addCompositeRule) ---
+    public static final int COND_ALL = -1;
+    public static final int COND_ANY = -2;
+    public static final int COND_NOT = -3;
+
+    // --- Tags for rule template action expressions (see addRuleTemplate) ---
+    public static final int EXPR_CONST = 0;
+    public static final int EXPR_STATE = 1;
+    public static final int EXPR_ADD = 2;
+    public static final int EXPR_SUB = 3;
+    public static final int EXPR_MUL = 4;
+    public static final int EXPR_DIV = 5;
+    public static final int EXPR_MOD = 6;
+
+    // --- Node activation functions (see setNodeActivation) ---
+    public static final int ACTIVATION_LINEAR = 0;
+    public static final int ACTIVATION_SIGMOID = 1;
+    public static final int ACTIVATION_TANH = 2;
+    public static final int ACTIVATION_RELU_CAP = 3;
+
+    // --- Personality presets (see setPersonality) ---
+    public static final int PERSONALITY_AGGRESSIVE = 0;
+    public static final int PERSONALITY_CAUTIOUS = 1;
+    public static final int PERSONALITY_ADAPTIVE = 2;
+    public static final int PERSONALITY_BERSERKER = 3;
+
     // --- Native Methods ---
     private native long initNativeSingularity(int stateSize, int[] categorySizes);
     private native void destroyNativeSingularity(long handle);
     private native int selectActionNative(long handle, float[] inputs);
+    private native int selectActionExNative(long handle, float[] inputs, int category, boolean vectorMode);
     private native int[] selectActionsNative(long handle, float[] inputs);
     private native void learnNative(long handle, float reward);
     private native float getSystemTemperature(long handle);
     private native float getActionScoreNative(long handle, int action_idx);
+    private native int getActionScoresNative(long handle, float[] out);
     private native float getFrustration(long handle);
     private native float getAdrenaline(long handle);
     private native void setNeuronStateNative(long handle, int idx, float state);
     private native float[] getNeuronStates(long handle);
+    private native int getNodeIndexNative(long handle, String name);
+    private native String exportTopologyDotNative(long handle);
+    private native int addNodeNative(long handle, String name, float decay);
+    private native boolean removeNodeNative(long handle, int idx);
+    private native void setRefractoryNative(long handle, int period, float spikeThreshold);
+    private native boolean setNodeActivationNative(long handle, int idx, int kind, float cap);
+    private native boolean addNodeCouplingNative(long handle, String node, int start, int count, float gain);
+    private native void clearNodeCouplingsNative(long handle);
+    private native float[] getNodeHistoryNative(long handle, int idx);
+    private native float[] getNodeHistoryStatsNative(long handle, int idx);
+    private native boolean bindSensorNative(long handle, String signal, String node, float gain, float offset);
+    private native void feedSensorsNative(long handle, int[] nameHashes, float[] values, float urgency);
+    private native void enableNeuromodulatorsNative(long handle);
+    private native float[] getNeuromodulatorsNative(long handle);
+    private native boolean setPersonalityNative(long handle, int personality);
     private native void setExplorationBetaNative(long handle, float beta);
     private native float getExplorationBetaNative(long handle);
     private native int generateVisualSnapshotNative(long handle, String path);
     private native int saveNativeModel(long handle, String path);
     private native int loadNativeModel(long handle, String path);
     private native void setActiveConditionsNative(long handle, int[] conditionIds);
+    private native void setConditionLevelsNative(long handle, int[] conditionIds, float[] levels);
     private native void bootstrapNative(long handle, int[] conditionIndices, int[] actionIndices, float[] strengths);
     private native void observeExpertNative(long handle, int stateIdx, int[] expertActions, float strength);
     private native void suppressExpertNative(long handle, int[] badActions, float strength);
+    private native float[] getPenaltiesNative(long handle, int stateIdx);
+    private native void setPenaltyNative(long handle, int stateIdx, int actionIdx, float value);
+    private native void observeOpponentNative(long handle, int stateIdx, int opponentAction);
+    private native float getDecisionConfidence(long handle);
+    private native float getDecisionUncertainty(long handle);
+    private native float[] evaluateActionsNative(long handle, int stateIdx);
+    private native int[] simulateRulesNative(long handle, int[] conditionIds, int stateIdx);
+    private native void queueLearnNative(long handle, float reward);
+    private native int drainLearnQueueNative(long handle, int maxItems);
+    private native int getLearnQueueDepth(long handle);
+    private native void setLearnQueuePolicyNative(long handle, int policy, int capacity);
+    private native void startLearnWorkerNative(long handle);
+    private native void stopLearnWorkerNative(long handle);
+    private native void awaitLearnQueueNative(long handle);
+    private native int loadShadowModelNative(long handle, String path, int reportAfter);
+    private native float[] getShadowReportNative(long handle);
+    private native boolean promoteShadowNative(long handle);
+    private native void detachShadowNative(long handle);
+    private native void setInterventionLevelNative(long handle, float level);
+    private native float[] getTemperatureTraceNative(long handle);
+    private static native long initHashedSingularity(int[] categorySizes);
+    private static native long initNamedNodesSingularity(int stateSize, int[] categorySizes, String[] nodeNames, float[] nodeDecays);
+    private native int[] selectActionsIdNative(long handle, long stateId);
+    private native int[] selectActionsFeaturesNative(long handle, long[] featureIds, float[] values);
+    private native void setTileCoderNative(long handle, float[] mins, float[] maxs, int tilesPerDim, int tilings);
+    private native int[] selectActionsContinuousNative(long handle, float[] inputs);
+    private native void setNStepNative(long handle, int n);
+    private native void learnTrajectoryNative(long handle, int[] states, int[] actions, float[] importance, float reward);
+    private native void learnBatchNative(long handle, int[] states, int[] actions, float[] rewards);
+    private native void startRecordingNative(long handle);
+    private native int stopRecordingNative(long handle, String path);
+    private native int[] replayEpisodeNative(long handle, String path);
+    private native void setExplorationPolicyNative(long handle, int policy);
+    private native void setIntegratorNative(long handle, int integrator);
+    private native void setCouplingNative(long handle, int coupling);
+    private native void setGravityParamsNative(long handle, float rewardThreshold, float increment, float cap, float failureErosion, float hawkingDecay);
+    private native void setFieldPrecisionNative(long handle, int precision);
+    private native void entangleWithNative(long handle, long otherHandle, int[] actions, float strength);
+    private native long scheduleActionFieldNative(long handle, int[] actions, float strength, int duration, int decay, float halfLife);
+    private native boolean cancelScheduledFieldNative(long handle, long id);
+    private native boolean addCompositeRuleNative(long handle, int[] condition, int action, float strength);
+    private native boolean addPrioritizedRuleNative(long handle, int[] condition, int action, float strength, int priority);
+    private native void setRuleConflictPolicyNative(long handle, int policy);
+    private native int removeRuleNative(long handle, int[] condition, int action);
+    private native int updateRuleStrengthNative(long handle, int[] condition, int action, float strength);
+    private native void clearRulesNative(long handle);
+    private native int applyKnowledgePriorsNative(long handle);
+    private native boolean addRangeRuleNative(long handle, int[] condition, int start, int count, float strength, int priority);
+    private native boolean addCategoryRuleNative(long handle, int[] condition, int category, float strength, int priority);
+    private native boolean addCategoryScopedRuleNative(long handle, int[] condition, int category, int start, int count, float strength, int priority);
+    private native boolean addRuleTemplateNative(long handle, int[] condition, int[] expr, float strength);
+    private native void setRuleLifecycleNative(long handle, float autoDecay, long ttl, long maxUses, float minStrength);
+    private native int loadKnowledgeNative(long handle, String path);
+    private native int saveKnowledgeNative(long handle, String path);
+    private native int extractPolicyNative(long handle, float threshold, String path);
+    private native void setScoreTopKNative(long handle, int k);
+    private native void setInterferenceScoringNative(long handle, float weight);
+    private native int[] selectActionsWithinNative(long handle, int stateIdx, long budgetMicros);
+    private native boolean wasLastDecisionDegradedNative(long handle);
+    private native float[] getStatsNative(long handle);
+    private native float[] getLastRuleActivationsNative(long handle);
+    private native int addMemoryBankNative(long handle, String label);
+    private native boolean switchMemoryBankNative(long handle, int bank);
+    private native void setMemoryBankRoutingNative(long handle, int policy);
+    private native float[] getBankResonancesNative(long handle, int stateIdx);
+    private native void setExplorationNoiseModeNative(long handle, int mode);
+    private native void setSpectrumNative(long handle, int preset, float a, float b);
+    private native boolean setFrequenciesNative(long handle, float[] frequencies);
+    private native float[] getGravityStatsNative(long handle, float threshold);
+    private native float getGravityForActionNative(long handle, int action);
+    private native void erodeGravityNative(long handle, int action, float amount);
+    private native float getPhaseCoherenceNative(long handle, int action);
+    private native void setStateCodingNative(long handle, int coding);
+    private native boolean setActionWeightsNative(long handle, float[] weights);
+    private native void setAutoGrowNative(long handle, float snr, int interval, int maxDim);
+    private native float getRetrievalSnrNative(long handle, int stateIdx);
+    private native void setConsolidationNative(long handle, int capacity, int interval, float decay);
+    private native int consolidateMemoryNative(long handle);
+    private native void setPlasticityNative(long handle, float rate, float minWeight, float maxWeight);
+    private native float[] recallStateNative(long handle, int stateIdx);
+    private native void imprintMemoryNative(long handle, int stateIdx, float reward, String label);
+    private native String getResonantMemoriesNative(long handle, float minResonance);
+    private native void setMemoryHalfLifeNative(long handle, float halfLife);
+    private native float[] exportThetaNative(long handle);
+    private native boolean importThetaNative(long handle, float[] theta);
+    private native long openWaveViewNative(long handle);
+    private static native float[] readWaveViewNative(long view);
+    private static native void closeWaveViewNative(long view);
 
     public Singularity(int stateSize, int... categorySizes) {
         if (categorySizes == null || categorySizes.length == 0) {
@@ -108,6 +245,54 @@ public class Singularity implements AutoCloseable {
         }
     }
 
+    private Singularity(long handle) {
+        if (handle == 0) {
+            throw new IllegalStateException("Failed to initialize native Singularity instance.");
+        }
+        this.handle = handle;
+    }
+
+    /**
+     * Creates an instance without a dense state space.
+     * States are identified by arbitrary 64-bit ids (or feature vectors) that are hashed
+     * directly into the wave field's injection patterns.
+     */
+    public static Singularity createHashed(int... categorySizes) {
+        if (categorySizes == null || categorySizes.length == 0) {
+            throw new IllegalArgumentException("At least one action category must be defined.");
+        }
+        return new Singularity(initHashedSingularity(categorySizes));
+    }
+
+    /**
+     * Creates an instance with a custom set of named emotional nodes.
+     * Nodes named "aggression", "fear", "tactical" and "reflex" drive scoring and topology;
+     * when one of them is missing its contribution is treated as zero.
+     */
+    public static Singularity createWithNodes(int stateSize, int[] categorySizes, String[] nodeNames, float[] nodeDecays) {
+        if (categorySizes == null || categorySizes.length == 0) {
+            throw new IllegalArgumentException("At least one action category must be defined.");
+        }
+        if (nodeNames == null || nodeDecays == null || nodeNames.length != nodeDecays.length) {
+            throw new IllegalArgumentException("nodeNames and nodeDecays must have the same length.");
+        }
+        return new Singularity(initNamedNodesSingularity(stateSize, categorySizes, nodeNames, nodeDecays));
+    }
+
+    /**
+     * Creates an instance with a personality preset applied.
+     *
+     * @param personality one of the {@code PERSONALITY_*} constants
+     */
+    public static Singularity createWithPersonality(int stateSize, int personality, int... categorySizes) {
+        Singularity singularity = new Singularity(stateSize, categorySizes);
+        if (!singularity.setPersonality(personality)) {
+            singularity.close();
+            throw new IllegalArgumentException("Unknown personality: " + personality);
+        }
+        return singularity;
+    }
+
     private void checkClosed() {
         if (closed.get()) throw new IllegalStateException("Singularity instance is already closed.");
     }
@@ -117,6 +302,16 @@ public class Singularity implements AutoCloseable {
         return selectActionNative(handle, inputs);
     }
 
+    /**
+     * Single-action compatible selection with a category selector.
+     * When vectorMode is true, inputs are treated as a dense feature vector (index -> weight).
+     * A negative category selects the first category.
+     */
+    public int selectAction(float[] inputs, int category, boolean vectorMode) {
+        checkClosed();
+        return selectActionExNative(handle, inputs, category, vectorMode);
+    }
+
     public int[] selectActions(float[] inputs) {
         checkClosed();
         return selectActionsNative(handle, inputs);
@@ -137,6 +332,18 @@ public class Singularity implements AutoCloseable {
         return getActionScoreNative(handle, actionIndex);
     }
 
+    /**
+     * Writes the score of every action (wave score minus fatigue) into {@code out}. Reusing the
+     * same array every tick avoids allocating on each readout.
+     *
+     * @param out destination; if shorter than the action count only the leading actions are written
+     * @return the number of scores written
+     */
+    public int getActionScores(float[] out) {
+        checkClosed();
+        return getActionScoresNative(handle, out);
+    }
+
     public float getFrustration() {
         checkClosed();
         return getFrustration(handle);
@@ -172,6 +379,170 @@ public class Singularity implements AutoCloseable {
         return getNeuronStates(handle);
     }
 
+    /**
+     * Returns the index of the named node (usable with setNeuronState / getNeuronStates), or -1 if absent.
+     */
+    public int nodeIndex(String name) {
+        checkClosed();
+        if (name == null) throw new IllegalArgumentException("name must not be null");
+        return getNodeIndexNative(handle, name);
+    }
+
+    /**
+     * Adds a new emotional node at runtime (for example a "greed" drive once an economy
+     * unlocks) without rebuilding the agent.
+     *
+     * @return the index of the new node, or -1 if a node with that name already exists
+     */
+    public int addNode(String name, float decay) {
+        checkClosed();
+        if (name == null) throw new IllegalArgumentException("name must not be null");
+        return addNodeNative(handle, name, decay);
+    }
+
+    /**
+     * Removes a node. Later nodes shift down by one and synapses are re-indexed; synapses fed
+     * by the removed node are dropped.
+     *
+     * @return false if {@code idx} is out of range
+     */
+    public boolean removeNode(int idx) {
+        checkClosed();
+        return removeNodeNative(handle, idx);
+    }
+
+    /**
+     * Sets a refractory period on every node: once a node's activation rises past
+     * {@code spikeThreshold}, it holds its state and ignores input for the next {@code period}
+     * updates. This damps oscillation between aggression and fear under alternating rewards.
+     *
+     * @param period updates to suppress after a spike; 0 disables the mechanism
+     */
+    public void setRefractory(int period, float spikeThreshold) {
+        checkClosed();
+        setRefractoryNative(handle, period, spikeThreshold);
+    }
+
+    /**
+     * Selects the response curve a node uses to turn its synaptic input into a target activation.
+     *
+     * @param kind one of the {@code ACTIVATION_*} constants
+     * @param cap the ceiling for {@link #ACTIVATION_RELU_CAP} (0 to 1); ignored by other kinds
+     * @return false if {@code idx} or {@code kind} is invalid
+     */
+    public boolean setNodeActivation(int idx, int kind, float cap) {
+        checkClosed();
+        return setNodeActivationNative(handle, idx, kind, cap);
+    }
+
+    /**
+     * Couples an emotional node to a band of actions in the wave field: the node's activation
+     * times {@code gain} is added to the penalty field over those actions' bins, damping them
+     * both in scoring and in the wave's evolution (for example, fear suppressing aggressive moves).
+     *
+     * @param start first global action index of the band
+     * @param count number of actions in the band
+     * @param gain non-negative damping per unit of activation
+     * @return false if the node is unknown, the band is empty or out of range, or gain is negative
+     */
+    public boolean addNodeCoupling(String node, int start, int count, float gain) {
+        checkClosed();
+        if (node == null) throw new IllegalArgumentException("node must not be null");
+        return addNodeCouplingNative(handle, node, start, count, gain);
+    }
+
+    /** Removes every node coupling added with {@link #addNodeCoupling}. */
+    public void clearNodeCouplings() {
+        checkClosed();
+        clearNodeCouplingsNative(handle);
+    }
+
+    /**
+     * Returns the node's recent activations, oldest first (the last 64 updates by default).
+     *
+     * @return an empty array if {@code idx} is out of range
+     */
+    public float[] getNodeHistory(int idx) {
+        checkClosed();
+        return getNodeHistoryNative(handle, idx);
+    }
+
+    /**
+     * Summarizes the node's recent activations, e.g. to flag pathological oscillation.
+     *
+     * @return [mean, variance, trend per update], or an empty array if there is no history
+     */
+    public float[] getNodeHistoryStats(int idx) {
+        checkClosed();
+        return getNodeHistoryStatsNative(handle, idx);
+    }
+
+    /**
+     * Binds a named game signal (e.g. "damage_taken") to a node's input: each time the signal is
+     * fed, {@code value * gain + offset} is added to that node's input.
+     */
+    public void bindSensor(String signal, String node, float gain, float offset) {
+        checkClosed();
+        if (signal == null || node == null) throw new IllegalArgumentException("signal and node must not be null");
+        bindSensorNative(handle, signal, node, gain, offset);
+    }
+
+    /**
+     * Updates the nodes from named signals bound with {@link #bindSensor}. Signals without a
+     * binding are ignored.
+     *
+     * @param nameHashes {@code String.hashCode()} of each signal name; precompute these once
+     * @param values signal values, parallel to {@code nameHashes}
+     */
+    public void feedSensors(int[] nameHashes, float[] values, float urgency) {
+        checkClosed();
+        if (nameHashes == null || values == null || nameHashes.length != values.length) {
+            throw new IllegalArgumentException("nameHashes and values must have the same length.");
+        }
+        feedSensorsNative(handle, nameHashes, values, urgency);
+    }
+
+    /**
+     * Enables the neuromodulator system. A dopamine-like signal tracks reward prediction error
+     * and a serotonin-like signal falls under stress (frustration and adrenaline); together they
+     * scale the wave field's learning rate, fatigue build-up and exploration noise.
+     */
+    public void enableNeuromodulators() {
+        checkClosed();
+        enableNeuromodulatorsNative(handle);
+    }
+
+    /**
+     * @return [dopamine (-1 to 1), serotonin (0 to 1), expected reward], or an empty array if
+     *         neuromodulators are not enabled
+     */
+    public float[] getNeuromodulators() {
+        checkClosed();
+        return getNeuromodulatorsNative(handle);
+    }
+
+    /**
+     * Switches the personality preset at runtime. A preset sets node base decays and states,
+     * the temperature and its schedule, and the exploration policy in one call.
+     *
+     * @param personality one of the {@code PERSONALITY_*} constants
+     * @return false if {@code personality} is unknown
+     */
+    public boolean setPersonality(int personality) {
+        checkClosed();
+        return setPersonalityNative(handle, personality);
+    }
+
+    /**
+     * Returns the emotional node graph as a GraphViz (DOT) document. Node labels carry the
+     * current activation; edges run from a synapse's source to the node it feeds and are
+     * labelled with their weight.
+     */
+    public String exportTopologyDot() {
+        checkClosed();
+        return exportTopologyDotNative(handle);
+    }
+
     public int saveModel(String path) {
         checkClosed();
         return saveNativeModel(handle, path);
@@ -182,6 +553,11 @@ public class Singularity implements AutoCloseable {
         return loadNativeModel(handle, path);
     }
 
+    /**
+     * Registers rules that push action {@code actionIndices[i]} while condition {@code conditionIds[i]}
+     * is active. Use {@link #ALWAYS_CONDITION} for baseline priors that apply regardless of the
+     * active conditions (e.g. never pick action 0), so no dummy condition flag has to be kept set.
+     */
     public void registerHamiltonianRules(int[] conditionIds, int[] actionIndices, float[] resonanceStrengths) {
         checkClosed();
         if (conditionIds == null || actionIndices == null || resonanceStrengths == null ||
@@ -191,11 +567,232 @@ public class Singularity implements AutoCloseable {
         bootstrapNative(handle, conditionIds, actionIndices, resonanceStrengths);
     }
 
+    /**
+     * Adds a rule that fires when a composite condition over the active condition ids holds.
+     * The condition is prefix-encoded: {@link #COND_ALL} or {@link #COND_ANY} followed by a child
+     * count and the children, {@link #COND_NOT} followed by one child, or a non-negative condition
+     * id. For example, "HP low and enemy near, but not ammo low" is
+     * {@code {COND_ALL, 3, HP_LOW, ENEMY_NEAR, COND_NOT, AMMO_LOW}}, and {@code {COND_ALL, 0}}
+     * always holds. A negative strength repels.
+     *
+     * @return false if the encoding is malformed (nothing is added)
+     */
+    public boolean addCompositeRule(int[] condition, int actionIndex, float strength) {
+        checkClosed();
+        if (condition == null) {
+            throw new IllegalArgumentException("condition must be non-null.");
+        }
+        return addCompositeRuleNative(handle, condition, actionIndex, strength);
+    }
+
+    /**
+     * Adds a rule with an explicit priority. The condition uses the same prefix encoding as
+     * {@link #addCompositeRule}; a single condition id is also accepted. Priorities only matter
+     * under the highest-priority conflict policy (see {@link #setRuleConflictPolicy(int)}), where
+     * e.g. a "never fire at allies" rule can't be outvoted by several weaker heuristics.
+     *
+     * @return false if the encoding is malformed (nothing is added)
+     */
+    public boolean addPrioritizedRule(int[] condition, int actionIndex, float strength, int priority) {
+        checkClosed();
+        if (condition == null) {
+            throw new IllegalArgumentException("condition must be non-null.");
+        }
+        return addPrioritizedRuleNative(handle, condition, actionIndex, strength, priority);
+    }
+
+    /**
+     * Adds a single rule that applies to the {@code count} consecutive actions starting at
+     * {@code start}, instead of registering one rule per action. The condition uses the same
+     * prefix encoding as {@link #addCompositeRule}.
+     *
+     * @return false if the encoding is malformed or the range is empty (nothing is added)
+     */
+    public boolean addRangeRule(int[] condition, int start, int count, float strength, int priority) {
+        checkClosed();
+        if (condition == null) {
+            throw new IllegalArgumentException("condition must be non-null.");
+        }
+        return addRangeRuleNative(handle, condition, start, count, strength, priority);
+    }
+
+    /**
+     * Adds a single rule that applies to every action of a category, e.g. penalize the whole
+     * attack category while a RETREAT condition is active.
+     *
+     * @return false if the encoding is malformed or the category does not exist (nothing is added)
+     */
+    public boolean addCategoryRule(int[] condition, int category, float strength, int priority) {
+        checkClosed();
+        if (condition == null) {
+            throw new IllegalArgumentException("condition must be non-null.");
+        }
+        return addCategoryRuleNative(handle, condition, category, strength, priority);
+    }
+
+    /**
+     * Adds a rule targeting {@code count} consecutive actions of one category, starting at the
+     * category-local action {@code start} (0 is the first action of that category), so callers
+     * don't have to add category offsets themselves. The condition uses the same prefix encoding as
+     * {@link #addCompositeRule}; a single condition id is also accepted.
+     *
+     * @return false if the category doesn't exist or the actions don't fit inside it
+     */
+    public boolean addCategoryScopedRule(int[] condition, int category, int start, int count, float strength, int priority) {
+        checkClosed();
+        if (condition == null) {
+            throw new IllegalArgumentException("condition must be non-null.");
+        }
+        return addCategoryScopedRuleNative(handle, condition, category, start, count, strength, priority);
+    }
+
+    /**
+     * Adds a rule template: while the condition holds, the action computed from the current state
+     * index is steered with {@code strength}. One template replaces a whole family of per-state
+     * rules, e.g. "target = state / 2 % 4" is
+     * {@code {EXPR_MOD, EXPR_DIV, EXPR_STATE, EXPR_CONST, 2, EXPR_CONST, 4}}.
+     * <p>
+     * The expression is prefix-encoded: {@link #EXPR_CONST} followed by a value, {@link #EXPR_STATE},
+     * or a binary operator ({@link #EXPR_ADD}, {@link #EXPR_SUB}, {@link #EXPR_MUL}, {@link #EXPR_DIV},
+     * {@link #EXPR_MOD}) followed by its two operands. Results outside the action range, and
+     * division by zero, steer nothing. The condition uses the same encoding as {@link #addCompositeRule}.
+     *
+     * @return false if the condition or expression is malformed
+     */
+    public boolean addRuleTemplate(int[] condition, int[] expr, float strength) {
+        checkClosed();
+        if (condition == null || expr == null) {
+            throw new IllegalArgumentException("condition and expr must be non-null.");
+        }
+        return addRuleTemplateNative(handle, condition, expr, strength);
+    }
+
+    /**
+     * Selects how rules that fire on the same action are combined: 0 = sum all strengths
+     * (default), 1 = only the highest-priority rules count (ties are summed), 2 = only the
+     * strength with the largest magnitude counts.
+     */
+    public void setRuleConflictPolicy(int policy) {
+        checkClosed();
+        setRuleConflictPolicyNative(handle, policy);
+    }
+
+    /**
+     * Removes every rule with the given condition and target action (the first action for range
+     * and category rules), e.g. to retract knowledge when the map objective changes. The condition
+     * uses the same prefix encoding as {@link #addCompositeRule}; a single condition id is also accepted.
+     *
+     * @return the number of rules removed, or -1 if the condition is malformed
+     */
+    public int removeRule(int[] condition, int actionIndex) {
+        checkClosed();
+        if (condition == null) {
+            throw new IllegalArgumentException("condition must be non-null.");
+        }
+        return removeRuleNative(handle, condition, actionIndex);
+    }
+
+    /**
+     * Sets the strength of every rule with the given condition and target action.
+     *
+     * @return the number of rules updated, or -1 if the condition is malformed
+     * @see #removeRule(int[], int)
+     */
+    public int updateRuleStrength(int[] condition, int actionIndex, float strength) {
+        checkClosed();
+        if (condition == null) {
+            throw new IllegalArgumentException("condition must be non-null.");
+        }
+        return updateRuleStrengthNative(handle, condition, actionIndex, strength);
+    }
+
+    /** Removes all Hamiltonian rules. The conflict policy and rule lifecycle settings are kept. */
+    public void clearRules() {
+        checkClosed();
+        clearRulesNative(handle);
+    }
+
+    /**
+     * One-shot warm start: bakes the current rules into the wave field as priors. The rule field is
+     * evaluated under the current condition levels; rewarded actions get their phase reference
+     * aligned and gravity added, penalized ones are anti-aligned and lose gravity. Unlike the
+     * runtime rule field, which is re-applied on every decision, these priors are gradually
+     * overwritten by learning.
+     *
+     * @return the number of actions biased
+     */
+    public int applyKnowledgePriors() {
+        checkClosed();
+        return applyKnowledgePriorsNative(handle);
+    }
+
+    /**
+     * Limits the lifetime of rules generated by {@code observeExpert}. Their strength is multiplied
+     * by {@code autoDecay} on every learning step (1.0 = no decay), and they are removed once they
+     * are older than {@code ttl} learning steps since the last demonstration, have fired on
+     * {@code maxUses} learning steps, or their strength falls below {@code minStrength}.
+     * Pass 0 for {@code ttl} or {@code maxUses} to disable that limit. Hand-registered rules never expire.
+     */
+    public void setRuleLifecycle(float autoDecay, long ttl, long maxUses, float minStrength) {
+        checkClosed();
+        setRuleLifecycleNative(handle, autoDecay, ttl, maxUses, minStrength);
+    }
+
+    /**
+     * Loads a JSON rule pack and adds its rules to the current ones; the conflict policy switches
+     * to the one in the file. Rules look like {@code {"condition_id": 0, "target_action": 1,
+     * "strength": 0.5}} or, for composite conditions, {@code {"condition": {"All": [{"Id": 0},
+     * {"Not": {"Id": 2}}]}, "target_action": 3, "strength": -1.0, "priority": 10}}.
+     *
+     * @return the number of rules added, or a negative value if the file can't be read or parsed
+     */
+    public int loadKnowledge(String path) {
+        checkClosed();
+        return loadKnowledgeNative(handle, path);
+    }
+
+    /**
+     * Writes the current rules (including crystallized ones) as a JSON rule pack that
+     * {@link #loadKnowledge(String)} can read back.
+     *
+     * @return 0 on success, negative on failure
+     */
+    public int saveKnowledge(String path) {
+        checkClosed();
+        return saveKnowledgeNative(handle, path);
+    }
+
+    /**
+     * Distills what the agent has learned into explicit rules: for every state seen so far,
+     * the action its wave field and learned rules prefer with a confidence of at least
+     * {@code threshold} (0.0-1.0) becomes a rule conditioned on that state. The rules are
+     * written as a JSON rule pack that {@link #loadKnowledge(String)} can read.
+     *
+     * @return the number of rules written, or negative on failure
+     */
+    public int extractPolicy(float threshold, String path) {
+        checkClosed();
+        return extractPolicyNative(handle, threshold, path);
+    }
+
     public void setActiveConditions(int... conditionIds) {
         checkClosed();
         setActiveConditionsNative(handle, conditionIds);
     }
 
+    /**
+     * Sets graded condition activations in [0, 1] instead of on/off flags, e.g. raise "HP low"
+     * toward 1.0 as health drops. Each rule's strength is scaled by its condition's level;
+     * composite conditions combine levels with min (all), max (any) and 1 - x (not).
+     */
+    public void setConditionLevels(int[] conditionIds, float[] levels) {
+        checkClosed();
+        if (conditionIds == null || levels == null || conditionIds.length != levels.length) {
+            throw new IllegalArgumentException("Arrays must be non-null and have the same length.");
+        }
+        setConditionLevelsNative(handle, conditionIds, levels);
+    }
+
     /**
      * Observes expert actions to perform imitation learning (IRL).
      */
@@ -212,6 +809,770 @@ public class Singularity implements AutoCloseable {
         suppressExpertNative(handle, badActions, strength);
     }
 
+    /**
+     * Returns the penalty field of a state, averaged per action.
+     */
+    public float[] getPenalties(int stateIdx) {
+        checkClosed();
+        return getPenaltiesNative(handle, stateIdx);
+    }
+
+    /**
+     * Overwrites the penalty of a specific state/action pair (e.g. to clear a poisoned penalty).
+     */
+    public void setPenalty(int stateIdx, int actionIdx, float value) {
+        checkClosed();
+        setPenaltyNative(handle, stateIdx, actionIdx, value);
+    }
+
+    /**
+     * Feeds an observed enemy action into the opponent model.
+     */
+    public void observeOpponent(int stateIdx, int opponentAction) {
+        checkClosed();
+        observeOpponentNative(handle, stateIdx, opponentAction);
+    }
+
+    /**
+     * Confidence of the last decision (0.0 - 1.0), from the score gap and resonance density.
+     */
+    public float getDecisionConfidence() {
+        checkClosed();
+        return getDecisionConfidence(handle);
+    }
+
+    /**
+     * Uncertainty derived from wave entropy (0.0 - 1.0). Fall back to scripted behavior when high.
+     */
+    public float getDecisionUncertainty() {
+        checkClosed();
+        return getDecisionUncertainty(handle);
+    }
+
+    /**
+     * Returns the score of every action for a hypothetical state without changing the learning state.
+     */
+    public float[] evaluateActions(int stateIdx) {
+        checkClosed();
+        return evaluateActionsNative(handle, stateIdx);
+    }
+
+    /**
+     * Returns the action the knowledge rules alone would choose for each category (no wave, no noise).
+     */
+    public int[] simulateRules(int[] conditionIds, int stateIdx) {
+        checkClosed();
+        return simulateRulesNative(handle, conditionIds, stateIdx);
+    }
+
+    /**
+     * Queues a learning step for the most recent decisions. The background learner started by
+     * {@link #startLearnWorker()} processes it; without one, call {@link #drainLearnQueue(int)}.
+     * With the block policy and a running learner, a full queue makes this call wait until the learner frees a slot.
+     */
+    public void queueLearn(float reward) {
+        checkClosed();
+        queueLearnNative(handle, reward);
+    }
+
+    /**
+     * Starts a background thread that learns queued steps one at a time. Every native call on this
+     * instance is synchronized, so decisions can keep running on the caller's thread in the meantime.
+     */
+    public void startLearnWorker() {
+        checkClosed();
+        startLearnWorkerNative(handle);
+    }
+
+    /**
+     * Stops the background learner after the step it is processing. Remaining steps stay queued.
+     */
+    public void stopLearnWorker() {
+        checkClosed();
+        stopLearnWorkerNative(handle);
+    }
+
+    /**
+     * Waits until the learn queue is empty. Without a running learner, processes the queue on the caller's thread.
+     */
+    public void awaitLearnQueue() {
+        checkClosed();
+        awaitLearnQueueNative(handle);
+    }
+
+    /**
+     * Processes up to maxItems queued learning steps and returns how many were processed.
+     */
+    public int drainLearnQueue(int maxItems) {
+        checkClosed();
+        return drainLearnQueueNative(handle, maxItems);
+    }
+
+    public int getLearnQueueDepth() {
+        checkClosed();
+        return getLearnQueueDepth(handle);
+    }
+
+    /**
+     * Sets the policy used when the learn queue is full (0=block, 1=drop oldest, 2=coalesce).
+     * Coalescing adds the new reward to the newest queued entry, so merged steps learn from the sum of their rewards.
+     * A non-positive capacity keeps the current capacity.
+     */
+    public void setLearnQueuePolicy(int policy, int capacity) {
+        checkClosed();
+        setLearnQueuePolicyNative(handle, policy, capacity);
+    }
+
+    /**
+     * Loads a candidate model in shadow mode. It receives the same inputs as this model,
+     * but its decisions are only logged, never executed.
+     * @return 0 on success, negative on failure
+     */
+    public int loadShadowModel(String path, int reportAfter) {
+        checkClosed();
+        return loadShadowModelNative(handle, path, reportAfter);
+    }
+
+    /**
+     * Returns [decisions, agreements, agreementRate, liveMeanReward, candidateExpectedReward, estimatedDecisions, complete],
+     * or an empty array when no shadow model is attached.
+     */
+    public float[] getShadowReport() {
+        checkClosed();
+        return getShadowReportNative(handle);
+    }
+
+    /**
+     * Replaces this model with the shadow candidate.
+     */
+    public boolean promoteShadow() {
+        checkClosed();
+        return promoteShadowNative(handle);
+    }
+
+    public void detachShadow() {
+        checkClosed();
+        detachShadowNative(handle);
+    }
+
+    /**
+     * Sets the external intervention level (0.0-1.0). Higher levels cap the maximum temperature.
+     */
+    public void setInterventionLevel(float level) {
+        checkClosed();
+        setInterventionLevelNative(handle, level);
+    }
+
+    /**
+     * Returns the latest temperature controller step as [rewardDriven, frustrationHeat, floor, ceiling, result].
+     */
+    public float[] getTemperatureTrace() {
+        checkClosed();
+        return getTemperatureTraceNative(handle);
+    }
+
+    /**
+     * Selects actions for an arbitrary 64-bit state id.
+     * On a hashed instance the id is hashed into the pattern space; otherwise it is used as a state index.
+     */
+    public int[] selectActionsById(long stateId) {
+        checkClosed();
+        return selectActionsIdNative(handle, stateId);
+    }
+
+    /**
+     * Selects actions for a sparse feature vector (featureIds[i] -> values[i]).
+     */
+    public int[] selectActionsByFeatures(long[] featureIds, float[] values) {
+        checkClosed();
+        if (featureIds.length != values.length) {
+            throw new IllegalArgumentException("featureIds and values must have the same length.");
+        }
+        return selectActionsFeaturesNative(handle, featureIds, values);
+    }
+
+    /**
+     * Configures tile coding for continuous features (distances, HP ratios, resource levels...).
+     * Each feature i is bucketed over [mins[i], maxs[i]] into tilesPerDim tiles, across
+     * several offset tilings, so nearby inputs share tiles and generalize.
+     */
+    public void setTileCoder(float[] mins, float[] maxs, int tilesPerDim, int tilings) {
+        checkClosed();
+        if (mins.length != maxs.length) {
+            throw new IllegalArgumentException("mins and maxs must have the same length.");
+        }
+        setTileCoderNative(handle, mins, maxs, tilesPerDim, tilings);
+    }
+
+    /**
+     * Selects actions from continuous features using the configured tile coder.
+     * Returns an empty array if no tile coder has been set.
+     */
+    public int[] selectActionsContinuous(float[] inputs) {
+        checkClosed();
+        return selectActionsContinuousNative(handle, inputs);
+    }
+
+    /**
+     * Limits credit assignment to the last n decisions; older decisions are credited with a
+     * value bootstrapped from the current wave scores n steps later.
+     * n <= 0 restores discounted Monte-Carlo credit over the whole history.
+     */
+    public void setNStep(int n) {
+        checkClosed();
+        setNStepNative(handle, n);
+    }
+
+    /**
+     * Learns off-policy from a stored trajectory (old saved games, expert data).
+     * actions holds one global action index per category for each step, flattened in step order.
+     * importance[i] is the ratio pi(a|s) / mu(a|s) for step i; it is clipped and scales the reward
+     * credited to that step (wave field, penalties and rules alike). The live decision history is not affected.
+     */
+    public void learnTrajectory(int[] states, int[] actions, float[] importance, float reward) {
+        checkClosed();
+        if (states.length != importance.length) {
+            throw new IllegalArgumentException("states and importance must have the same length.");
+        }
+        learnTrajectoryNative(handle, states, actions, importance, reward);
+    }
+
+    /**
+     * Learns many independent transitions at once for offline training.
+     * actions holds one global action index per category for each transition, flattened in order.
+     * Decay and wave normalization passes run once per batch instead of once per reward.
+     */
+    public void learnBatch(int[] states, int[] actions, float[] rewards) {
+        checkClosed();
+        if (states.length != rewards.length) {
+            throw new IllegalArgumentException("states and rewards must have the same length.");
+        }
+        learnBatchNative(handle, states, actions, rewards);
+    }
+
+    /**
+     * Starts recording every decision and reward (with temperature, RNG seeds and exploration state).
+     * Start on a freshly created instance so the log can be replayed deterministically.
+     * Only selectActions and learn are recorded; using other decision or learning calls while
+     * recording (vector or feature input, trajectories, batches, the learn queue, expert observation)
+     * makes the log non-replayable.
+     */
+    public void startRecording() {
+        checkClosed();
+        startRecordingNative(handle);
+    }
+
+    /**
+     * Stops recording and writes the episode log to a compact binary file.
+     * Returns 0 on success, -3 if no recording was active, -4 if unrecorded calls made the log
+     * non-replayable, other negative values on I/O errors.
+     */
+    public int stopRecording(String path) {
+        checkClosed();
+        return stopRecordingNative(handle, path);
+    }
+
+    /**
+     * Re-feeds a recorded episode into this instance (normally a fresh one).
+     * Returns [decisions, rewards, actionMismatches, firstDivergence] where firstDivergence is -1
+     * when the replay matched the recording exactly, or an empty array if the log could not be read.
+     */
+    public int[] replayEpisode(String path) {
+        checkClosed();
+        return replayEpisodeNative(handle, path);
+    }
+
+    /**
+     * Selects the exploration strategy used when picking among candidate actions:
+     * 0 = top-k softmax (default), 1 = greedy, 2 = epsilon-greedy (epsilon = exploration beta),
+     * 3 = Boltzmann softmax over all candidates, 4 = UCB (bonus coefficient = exploration beta).
+     */
+    public void setExplorationPolicy(int policy) {
+        checkClosed();
+        setExplorationPolicyNative(handle, policy);
+    }
+
+    /**
+     * Selects the integrator used to evolve the wave oscillators:
+     * 0 = explicit (default), 1 = symplectic. The symplectic integrator keeps the
+     * amplitude-phase relationship of the wave stable over long runs (thousands of steps).
+     */
+    public void setIntegrator(int integrator) {
+        checkClosed();
+        setIntegratorNative(handle, integrator);
+    }
+
+    /**
+     * Tunes how gravity wells form and evaporate. Rewards above {@code rewardThreshold} (default
+     * 1.2) add {@code increment} (default 0.1, scaled down for dimensions above 1024) to the chosen
+     * action's bins up to {@code cap} (default 1.0); negative rewards multiply them by
+     * {@code failureErosion} (default 0.8); every learning step multiplies the whole field by
+     * {@code hawkingDecay} (default 0.995). Adjust these for games whose rewards use another scale.
+     */
+    public void setGravityParams(float rewardThreshold, float increment, float cap, float failureErosion, float hawkingDecay) {
+        checkClosed();
+        setGravityParamsNative(handle, rewardThreshold, increment, cap, failureErosion, hawkingDecay);
+    }
+
+    /**
+     * Selects the coupling between neighboring wave bins: 0 = linear amplitude coupling (default),
+     * 1 = Kuramoto-style phase coupling, where neighbors pull each other's phases by
+     * theta * sin(phase difference). Use it to explore synchronization-driven decision collapse.
+     */
+    public void setCoupling(int coupling) {
+        checkClosed();
+        setCouplingNative(handle, coupling);
+    }
+
+    /**
+     * Selects how the penalty matrix and gravity field are stored: 0 = f32 (default), 1 = f16
+     * (about half the memory), 2 = per-row scaled i8 (about a quarter). Values are converted in
+     * place and dequantized on the fly; worth it for agents with tens of thousands of states.
+     */
+    public void setFieldPrecision(int precision) {
+        checkClosed();
+        setFieldPrecisionNative(handle, precision);
+    }
+
+    /**
+     * Pulls the wave bins of the given actions toward {@code other}'s wave by {@code strength}
+     * (0..1), letting squad members share hunches such as a scout's fear resonance. Call it every
+     * step; the coupling is one-way, so call it from both sides for mutual sharing. An empty array
+     * couples every action both agents have.
+     */
+    public void entangleWith(Singularity other, int[] actions, float strength) {
+        checkClosed();
+        other.checkClosed();
+        entangleWithNative(handle, other.handle, actions, strength);
+    }
+
+    /**
+     * Schedules an external field over the wave bins of the given actions for {@code duration}
+     * steps, applied automatically on every step. A positive strength excites those actions; a
+     * negative strength damps them (e.g. suppress expansion actions for 200 ticks). Decay:
+     * 0 = constant, 1 = linear fade-out, 2 = exponential with the given half-life in steps.
+     *
+     * @return an id for {@link #cancelScheduledField(long)}, or -1 if the decay is invalid
+     */
+    public long scheduleActionField(int[] actions, float strength, int duration, int decay, float halfLife) {
+        checkClosed();
+        return scheduleActionFieldNative(handle, actions, strength, duration, decay, halfLife);
+    }
+
+    /**
+     * Cancels a field scheduled by {@link #scheduleActionField}. Returns false if it already expired.
+     */
+    public boolean cancelScheduledField(long id) {
+        checkClosed();
+        return cancelScheduledFieldNative(handle, id);
+    }
+
+    /**
+     * Scores actions in two passes: a cheap band-energy estimate for every action, then exact
+     * scoring of only the {@code k} best estimates. Other actions are ranked below the candidates
+     * by their estimate. Useful for agents with hundreds of actions where only the best few matter.
+     *
+     * @param k number of actions scored exactly (per shard when sharded); 0 scores every action exactly (default)
+     */
+    public void setScoreTopK(int k) {
+        checkClosed();
+        setScoreTopKNative(handle, k);
+    }
+
+    /**
+     * Adds the interference between the active wave and the memory wave to every action score.
+     * Action bins whose phase matches a remembered good outcome score higher immediately, without
+     * waiting for the memory to flow into the wave over several steps.
+     *
+     * @param weight weight of the interference term; 0 disables it (default)
+     */
+    public void setInterferenceScoring(float weight) {
+        checkClosed();
+        setInterferenceScoringNative(handle, weight);
+    }
+
+    /**
+     * Selects actions within a time budget. When the deadline approaches, flow injection of older
+     * history entries and the wave evolution step are skipped, and scores cached from the last
+     * full decision for this state are used instead.
+     *
+     * @param stateIdx     the current state index
+     * @param budgetMicros the time budget in microseconds
+     * @return the selected action index for each category
+     * @see #wasLastDecisionDegraded()
+     */
+    public int[] selectActionsWithin(int stateIdx, long budgetMicros) {
+        checkClosed();
+        return selectActionsWithinNative(handle, stateIdx, budgetMicros);
+    }
+
+    /**
+     * Returns whether the most recent decision was made in degraded mode because of its deadline.
+     */
+    public boolean wasLastDecisionDegraded() {
+        checkClosed();
+        return wasLastDecisionDegradedNative(handle);
+    }
+
+    /**
+     * Returns runtime instrumentation.
+     * <p>
+     * Layout: for selectActions, learn and saveModel (in that order), four values each:
+     * call count, min, average and p99 latency in microseconds. These are followed by the history
+     * length {@code n}, then {@code n} wave norms and {@code n} temperatures (oldest first),
+     * one sample per learn call.
+     */
+    public float[] getStats() {
+        checkClosed();
+        return getStatsNative(handle);
+    }
+
+    /**
+     * Returns the rules that contributed to the most recent decision, as triples of
+     * (rule id, action, contribution). The rule id is the rule's index in the rule list
+     * at decision time and the contribution is its strength scaled by the condition level; the knowledge
+     * term of the score is five times the sum. Use it to check that a knowledge pack actually
+     * steers behavior.
+     */
+    public float[] getLastRuleActivations() {
+        checkClosed();
+        return getLastRuleActivationsNative(handle);
+    }
+
+    /**
+     * Adds an empty memory-wave bank (episodic memory). Bank 0 ("default") always exists.
+     *
+     * @param label a name for the bank
+     * @return the index of the new bank, or -1 on failure
+     */
+    public int addMemoryBank(String label) {
+        checkClosed();
+        return addMemoryBankNative(handle, label);
+    }
+
+    /**
+     * Makes the given bank active. Recall and imprinting use the active bank.
+     *
+     * @return false if the bank does not exist
+     */
+    public boolean switchMemoryBank(int bank) {
+        checkClosed();
+        return switchMemoryBankNative(handle, bank);
+    }
+
+    /**
+     * Sets how imprints are routed to banks.
+     *
+     * @param policy 0 = active bank only (default), 1 = by state index modulo bank count,
+     *               2 = the best-resonating bank, or the least-used bank for a new episode
+     */
+    public void setMemoryBankRouting(int policy) {
+        checkClosed();
+        setMemoryBankRoutingNative(handle, policy);
+    }
+
+    /**
+     * Returns the resonance of each bank for the given state, indexed by bank.
+     * Values are in [-1, 1]. An empty bank reports 0.
+     */
+    public float[] getBankResonances(int stateIdx) {
+        checkClosed();
+        return getBankResonancesNative(handle, stateIdx);
+    }
+
+    /**
+     * Returns the index of the bank that resonates most with the given state.
+     */
+    public int getBestMemoryBank(int stateIdx) {
+        float[] resonances = getBankResonances(stateIdx);
+        int best = 0;
+        for (int i = 1; i < resonances.length; i++) {
+            if (resonances[i] > resonances[best]) {
+                best = i;
+            }
+        }
+        return best;
+    }
+
+    /**
+     * Sets the distribution of the exploration noise injected into the wave.
+     *
+     * @param mode 0 = uniform in [-1, 1] (default), 1 = Gaussian,
+     *             2 = Ornstein-Uhlenbeck (temporally correlated across ticks),
+     *             3 = pink / 1-over-f (correlated over a wide range of timescales)
+     */
+    public void setExplorationNoiseMode(int mode) {
+        checkClosed();
+        setExplorationNoiseModeNative(handle, mode);
+    }
+
+    /**
+     * Replaces the natural frequencies of the wave bins with a preset spectrum:
+     * 0 = linear, 1 = quadratic (default), 2 = harmonic stack ({@code a} = number of harmonics),
+     * 3 = band-limited ({@code a}, {@code b} = lower and upper edge as a fraction of 2π).
+     */
+    public void setSpectrum(int preset, float a, float b) {
+        checkClosed();
+        setSpectrumNative(handle, preset, a, b);
+    }
+
+    /**
+     * Replaces the natural frequencies of the wave bins with a custom array.
+     *
+     * @return false if the length differs from the wave dimension (the shard dimension when sharded)
+     */
+    public boolean setFrequencies(float[] frequencies) {
+        checkClosed();
+        return setFrequenciesNative(handle, frequencies);
+    }
+
+    /**
+     * Returns a summary of the gravity field, which grows where strong rewards keep landing.
+     * <p>
+     * Layout: mean gravity, max gravity, and the number of bins whose gravity exceeds {@code threshold}.
+     * Gravity is in [0, 1]; a high max with few bins above threshold means the agent has
+     * collapsed onto a narrow behavior.
+     */
+    public float[] getGravityStats(float threshold) {
+        checkClosed();
+        return getGravityStatsNative(handle, threshold);
+    }
+
+    /**
+     * Returns the mean gravity over the bins of the given global action index.
+     */
+    public float getGravityForAction(int action) {
+        checkClosed();
+        return getGravityForActionNative(handle, action);
+    }
+
+    /**
+     * Lowers the gravity of the given action by {@code amount} (clamped at 0),
+     * loosening the agent's lock-in on that behavior.
+     */
+    public void erodeGravity(int action, float amount) {
+        checkClosed();
+        erodeGravityNative(handle, action, amount);
+    }
+
+    /**
+     * Returns the phase coherence (Kuramoto order parameter) over the bins of the given action.
+     * <p>
+     * Unlike the resonance density, this ignores amplitude: 1.0 means every bin shares one phase
+     * (a crystallized behavior), values near 0 mean the phases are scattered.
+     */
+    public float getPhaseCoherence(int action) {
+        checkClosed();
+        return getPhaseCoherenceNative(handle, action);
+    }
+
+    /**
+     * Selects how states are spread over the wave when injected.
+     * <p>
+     * Random and orthogonal codes reduce collisions between distinct states at the same dimension.
+     * Switch before training: a wave trained under one coding does not carry over to another.
+     *
+     * @param coding 0 = prime stride (default), 1 = random phase codes,
+     *               2 = random phase codes orthogonalized against earlier states
+     */
+    public void setStateCoding(int coding) {
+        checkClosed();
+        setStateCodingNative(handle, coding);
+    }
+
+    /**
+     * Gives each action a share of the wave proportional to its weight, so important actions get
+     * more capacity. Scoring, learning, penalties and wormholes all follow the new layout.
+     * <p>
+     * Call before training. Not available when the action space is sharded (more than 16 actions).
+     *
+     * @param weights one positive weight per global action index
+     * @return {@code false} if the weights were rejected
+     */
+    public boolean setActionWeights(float... weights) {
+        checkClosed();
+        return setActionWeightsNative(handle, weights);
+    }
+
+    /**
+     * Lets the wave grow as memories accumulate. Every {@code interval} learn calls the memory
+     * retrieval SNR of the current state is measured; when it falls below {@code snr} the wave
+     * dimension is doubled, up to {@code maxDim}. Not available when the action space is sharded.
+     *
+     * @param snr SNR threshold; 0 disables automatic growth
+     */
+    public void setAutoGrow(float snr, int interval, int maxDim) {
+        checkClosed();
+        setAutoGrowNative(handle, snr, interval, maxDim);
+    }
+
+    /**
+     * Returns the signal-to-noise ratio of retrieving the given state's memory from the
+     * memory wave. Low values mean stored memories interfere with each other.
+     */
+    public float getRetrievalSnr(int stateIdx) {
+        checkClosed();
+        return getRetrievalSnrNative(handle, stateIdx);
+    }
+
+    /**
+     * Configures memory consolidation (sleep-like replay). The strongest recently rewarded
+     * patterns, up to {@code capacity}, are kept; every {@code interval} learn calls the memory
+     * wave is weakened by {@code decay} and those patterns are imprinted again, so strong
+     * memories survive while one-off interference fades.
+     *
+     * @param interval learn calls between passes; 0 disables scheduled consolidation
+     */
+    public void setConsolidation(int capacity, int interval, float decay) {
+        checkClosed();
+        setConsolidationNative(handle, capacity, interval, decay);
+    }
+
+    /**
+     * Enables Hebbian plasticity between emotional nodes. After every node update, synapses
+     * between co-active nodes strengthen and synapses between anti-correlated nodes weaken,
+     * so the topology self-organizes over a match.
+     *
+     * @param rate learning rate; 0 disables plasticity
+     * @param minWeight lower bound for synapse weights
+     * @param maxWeight upper bound for synapse weights
+     */
+    public void setPlasticity(float rate, float minWeight, float maxWeight) {
+        checkClosed();
+        setPlasticityNative(handle, rate, minWeight, maxWeight);
+    }
+
+    /**
+     * Runs one consolidation pass immediately.
+     *
+     * @return the number of patterns replayed
+     */
+    public int consolidateMemory() {
+        checkClosed();
+        return consolidateMemoryNative(handle);
+    }
+
+    /**
+     * Asks whether this state has been seen before and what was done there, without changing
+     * the wave.
+     * <p>
+     * Layout: resonance of the recalled memory with the current wave (-1 to 1), the action with
+     * the best mean reward in this state (-1 if none was taken), then the mean reward of each
+     * global action ({@code NaN} for actions never taken here).
+     */
+    public float[] recallState(int stateIdx) {
+        checkClosed();
+        return recallStateNative(handle, stateIdx);
+    }
+
+    /**
+     * Imprints the current wave into memory under the given state.
+     *
+     * @param label a name for this memory (e.g. "won_rush_defense") so it can be reported by
+     *              {@link #getResonantMemories(float)}, or null for an unlabeled imprint
+     */
+    public void imprintMemory(int stateIdx, float reward, String label) {
+        checkClosed();
+        imprintMemoryNative(handle, stateIdx, reward, label);
+    }
+
+    /**
+     * Returns the labeled memories whose resonance with the current wave is at least
+     * {@code minResonance}, strongest first. Call it right after selecting actions to see which
+     * memories shaped the decision.
+     *
+     * @return label to resonance (-1 to 1), in descending order of resonance
+     */
+    public Map<String, Float> getResonantMemories(float minResonance) {
+        checkClosed();
+        Map<String, Float> memories = new LinkedHashMap<>();
+        String encoded = getResonantMemoriesNative(handle, minResonance);
+        if (encoded == null || encoded.isEmpty()) {
+            return memories;
+        }
+        for (String line : encoded.split("\n")) {
+            int tab = line.lastIndexOf('\t');
+            memories.put(line.substring(0, tab), Float.parseFloat(line.substring(tab + 1)));
+        }
+        return memories;
+    }
+
+    /**
+     * Sets the forgetting curve of the memory wave. Every imprint fades to half its strength
+     * after {@code halfLife} wave steps, so strategies learned early in training stop interfering
+     * with current play. Inactive memory banks do not fade.
+     *
+     * @param halfLife the half-life in wave steps (one per action selection); 0 disables forgetting
+     */
+    public void setMemoryHalfLife(float halfLife) {
+        checkClosed();
+        setMemoryHalfLifeNative(handle, halfLife);
+    }
+
+    /**
+     * Exports the learned phase-coupling structure (theta) of the wave: the per-bin phases followed
+     * by the auxiliary phases, 2 * dim values (concatenated per shard when sharded). Use it to
+     * visualize what the solidification dynamics learned or to transplant it with {@link #importTheta}.
+     */
+    public float[] exportTheta() {
+        checkClosed();
+        return exportThetaNative(handle);
+    }
+
+    /**
+     * Imports a theta exported by {@link #exportTheta()}, possibly from another agent. A theta from
+     * an agent with a different dimension is interpolated to this agent's dimension.
+     *
+     * @return false if the array is empty, has an odd length, or contains non-finite values
+     */
+    public boolean importTheta(float[] theta) {
+        checkClosed();
+        return importThetaNative(handle, theta);
+    }
+
+    /**
+     * Opens a read-only view of the wave that is refreshed after every wave step. The view can be
+     * read from any thread (visualizers, telemetry) without blocking or racing the decision loop,
+     * and stays valid after this instance is closed. Agents with more than 16 actions are sharded;
+     * their view holds every shard's wave concatenated in shard order.
+     */
+    public WaveView openWaveView() {
+        checkClosed();
+        return new WaveView(openWaveViewNative(handle));
+    }
+
+    /**
+     * A thread-safe handle on the latest published wave snapshot.
+     */
+    public static final class WaveView implements AutoCloseable {
+        private final long view;
+        private final AtomicBoolean closed = new AtomicBoolean(false);
+
+        private WaveView(long view) {
+            this.view = view;
+        }
+
+        /**
+         * Returns the latest snapshot.
+         * <p>
+         * Layout: publish count, dimension {@code n}, then {@code n} real parts, {@code n}
+         * imaginary parts and the phase-coupling array (theta) of the wave.
+         */
+        public float[] read() {
+            if (closed.get()) {
+                throw new IllegalStateException("WaveView is closed");
+            }
+            return readWaveViewNative(view);
+        }
+
+        @Override
+        public void close() {
+            if (closed.compareAndSet(false, true)) {
+                closeWaveViewNative(view);
+            }
+        }
+    }
+
     @Override
     public void close() {
         if (closed.compareAndSet(false, true)) {
@@ -0,0 +1,90 @@
+// src/core/action_layout.rs
+// 行動 → 波（およびペナルティ場）のビン範囲の対応
+// 既定は等幅で、割り切れない余りのビンは先頭の行動から 1 つずつ配る
+
+use std::ops::Range;
+
+/// 各行動が受け持つビン範囲（連続・重なりなし）
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActionLayout {
+    /// 行動 a の範囲は starts[a]..starts[a + 1]
+    starts: Vec<usize>,
+}
+
+impl ActionLayout {
+    /// dim 個のビンを action_size 個の行動に等分する
+    pub fn uniform(dim: usize, action_size: usize) -> Self {
+        Self { starts: (0..=action_size).map(|a| Self::uniform_start(dim, action_size, a)).collect() }
+    }
+
+    /// 行動ごとのビン数を直接指定する（0 幅の行動は不可）
+    pub fn from_widths(widths: &[usize]) -> Option<Self> {
+        if widths.is_empty() || widths.contains(&0) { return None; }
+        let mut starts = Vec::with_capacity(widths.len() + 1);
+        starts.push(0);
+        for w in widths { starts.push(starts.last().unwrap() + w); }
+        Some(Self { starts })
+    }
+
+    /// dim 個のビンを weights の比で配る（最大剰余法、各行動に最低 1 ビン）
+    pub fn weighted(dim: usize, weights: &[f32]) -> Option<Self> {
+        let n = weights.len();
+        if n == 0 || n > dim || weights.iter().any(|w| !w.is_finite() || *w <= 0.0) { return None; }
+        let spare = (dim - n) as f64;
+        let total: f64 = weights.iter().map(|&w| w as f64).sum();
+        let shares: Vec<f64> = weights.iter().map(|&w| w as f64 / total * spare).collect();
+        let mut widths: Vec<usize> = shares.iter().map(|s| 1 + s.floor() as usize).collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&a, &b| (shares[b] - shares[b].floor()).total_cmp(&(shares[a] - shares[a].floor())));
+        let assigned: usize = widths.iter().sum();
+        for &a in order.iter().take(dim - assigned) { widths[a] += 1; }
+        Self::from_widths(&widths)
+    }
+
+    /// uniform(dim, action_size) における行動 action の範囲（表を作らずに求める）
+    pub fn uniform_range(dim: usize, action_size: usize, action: usize) -> Range<usize> {
+        let action = action % action_size.max(1);
+        Self::uniform_start(dim, action_size, action)..Self::uniform_start(dim, action_size, action + 1)
+    }
+
+    fn uniform_start(dim: usize, action_size: usize, action: usize) -> usize {
+        let (width, extra) = (dim / action_size.max(1), dim % action_size.max(1));
+        action * width + action.min(extra)
+    }
+
+    /// 行動数
+    pub fn len(&self) -> usize {
+        self.starts.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 全行動が使うビン数
+    pub fn total_bins(&self) -> usize {
+        *self.starts.last().unwrap()
+    }
+
+    pub fn range(&self, action: usize) -> Range<usize> {
+        self.starts[action]..self.starts[action + 1]
+    }
+
+    pub fn start(&self, action: usize) -> usize {
+        self.starts[action]
+    }
+
+    pub fn width(&self, action: usize) -> usize {
+        self.starts[action + 1] - self.starts[action]
+    }
+
+    pub fn widths(&self) -> Vec<usize> {
+        self.starts.windows(2).map(|w| w[1] - w[0]).collect()
+    }
+
+    /// ビンを受け持つ行動（どの行動にも属さなければ None）
+    pub fn action_for_bin(&self, bin: usize) -> Option<usize> {
+        if bin >= self.total_bins() { return None; }
+        Some(self.starts.partition_point(|&s| s <= bin) - 1)
+    }
+}
@@ -0,0 +1,146 @@
+// src/core/archive.rs
+// 長期間アクセスのない状態の知識を圧縮してディスクへ退避するコールドストレージ
+
+use super::dsym::{self, DsymReader, DsymWriter};
+use super::knowledge::LearnedRule;
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+
+/// 1状態分の退避データ
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ArchivedState {
+    pub rules: Vec<LearnedRule>,
+    /// penalty_matrix の行（penalty_dim 要素）
+    pub penalties: Vec<f32>,
+    /// 行動ごとの (報酬合計, 回数)
+    pub outcome_stats: Vec<(f32, u32)>,
+}
+
+impl ArchivedState {
+    /// ルール・ペナルティ行・経験統計を書き出す（.dsa ファイルと .dsym のアーカイブセクションで共通）
+    pub fn write_to<W: Write>(&self, w: &mut DsymWriter<W>) -> io::Result<()> {
+        w.section(dsym::SECTION_RULES)?;
+        w.write_u32(self.rules.len() as u32)?;
+        for rule in &self.rules {
+            w.write_u32(rule.action as u32)?;
+            w.write_u32(rule.count as u32)?;
+        }
+
+        w.section(dsym::SECTION_FIELDS)?;
+        w.write_u32(self.penalties.len() as u32)?;
+        w.write_f32_slice(&self.penalties)?;
+
+        w.section(dsym::SECTION_HISTORY)?;
+        w.write_u32(self.outcome_stats.len() as u32)?;
+        for &(sum, count) in &self.outcome_stats {
+            w.write_f32(sum)?;
+            w.write_u32(count)?;
+        }
+        Ok(())
+    }
+
+    /// write_to で書いた内容を読む。復元されたルールの last_used には now が設定される
+    pub fn read_from<R: Read>(r: &mut DsymReader<R>, state_idx: usize, now: u64) -> io::Result<Self> {
+        r.expect_section(dsym::SECTION_RULES)?;
+        let rule_count = r.read_u32()? as usize;
+        let mut rules = Vec::with_capacity(rule_count.min(1024));
+        for _ in 0..rule_count {
+            let action = r.read_u32()? as usize;
+            let count = r.read_u32()? as usize;
+            rules.push(LearnedRule { state: state_idx, action, count, last_used: now });
+        }
+
+        r.expect_section(dsym::SECTION_FIELDS)?;
+        let mut penalties = vec![0.0; r.read_u32()? as usize];
+        r.read_f32_into(&mut penalties)?;
+
+        r.expect_section(dsym::SECTION_HISTORY)?;
+        let stat_count = r.read_u32()? as usize;
+        let mut outcome_stats = Vec::with_capacity(stat_count.min(1024));
+        for _ in 0..stat_count {
+            let sum = r.read_f32()?;
+            let count = r.read_u32()?;
+            outcome_stats.push((sum, count));
+        }
+        Ok(Self { rules, penalties, outcome_stats })
+    }
+}
+
+pub struct ColdArchive {
+    pub dir: PathBuf,
+    /// 現在アーカイブされている状態
+    pub archived: HashSet<usize>,
+}
+
+impl ColdArchive {
+    /// dir を開く。既にある state_*.dsa は（再起動前に退避した状態として）アーカイブ済みに数える
+    pub fn new(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        let mut archived = HashSet::new();
+        for entry in fs::read_dir(&dir)? {
+            let name = entry?.file_name();
+            let state = name.to_str()
+                .and_then(|n| n.strip_prefix("state_"))
+                .and_then(|n| n.strip_suffix(".dsa"))
+                .and_then(|n| n.parse::<usize>().ok());
+            if let Some(state) = state { archived.insert(state); }
+        }
+        Ok(Self { dir, archived })
+    }
+
+    fn path_for(&self, state_idx: usize) -> PathBuf {
+        self.dir.join(format!("state_{}.dsa", state_idx))
+    }
+
+    pub fn contains(&self, state_idx: usize) -> bool {
+        self.archived.contains(&state_idx)
+    }
+
+    /// アーカイブ済みの状態（昇順）
+    pub fn states(&self) -> Vec<usize> {
+        let mut states: Vec<usize> = self.archived.iter().copied().collect();
+        states.sort_unstable();
+        states
+    }
+
+    pub fn store(&mut self, state_idx: usize, data: &ArchivedState) -> io::Result<()> {
+        let file = File::create(self.path_for(state_idx))?;
+        let mut w = DsymWriter::new(GzEncoder::new(file, Compression::default()));
+        w.write_header(state_idx)?;
+        data.write_to(&mut w)?;
+        w.finish()?;
+
+        self.archived.insert(state_idx);
+        Ok(())
+    }
+
+    /// アーカイブの内容をファイルを残したまま読む（アーカイブされていなければ None）
+    pub fn read(&self, state_idx: usize, now: u64) -> io::Result<Option<ArchivedState>> {
+        if !self.archived.contains(&state_idx) { return Ok(None); }
+        let file = File::open(self.path_for(state_idx))?;
+        let mut no_progress = |_: u64, _: u64| {};
+        let mut r = DsymReader::open(GzDecoder::new(file), 0, &mut no_progress)?;
+        let stored_state = r.read_u32()? as usize;
+        if stored_state != state_idx {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Archive state mismatch"));
+        }
+        let data = ArchivedState::read_from(&mut r, state_idx, now)?;
+        r.finish();
+        Ok(Some(data))
+    }
+
+    /// アーカイブから取り出し、ファイルを削除する（アーカイブされていなければ None）
+    /// 復元されたルールの last_used には now が設定される
+    pub fn take(&mut self, state_idx: usize, now: u64) -> io::Result<Option<ArchivedState>> {
+        let Some(data) = self.read(state_idx, now)? else { return Ok(None); };
+        fs::remove_file(self.path_for(state_idx))?;
+        self.archived.remove(&state_idx);
+        Ok(Some(data))
+    }
+}
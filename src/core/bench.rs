@@ -0,0 +1,121 @@
+// src/core/bench.rs
+// 再利用できるマイクロベンチマーク: tests/ の println ベンチと同じ測定を関数として呼べるようにし、
+// 結果を構造体で返す（下流の環境でしきい値を assert したり、設定の比較に使ったりするためのもの）
+
+use super::mwso::MWSO;
+use super::singularity::Singularity;
+use super::stats::LatencySummary;
+use super::wave::to_wave;
+use std::time::{Duration, Instant};
+
+/// wave_step_throughput の結果
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StepThroughput {
+    pub dim: usize,
+    pub steps: usize,
+    pub elapsed: Duration,
+    pub steps_per_sec: f64,
+    /// 1 ステップ・1 ビンあたりのナノ秒
+    pub ns_per_bin: f64,
+}
+
+/// dim 次元の MWSO を steps 回 step_core させたときのスループット
+pub fn wave_step_throughput(dim: usize, steps: usize) -> StepThroughput {
+    let mut mwso = MWSO::new(dim);
+    mwso.inject_state(0, 1.0, &[]);
+    // 初回の作業領域の確保を計測に含めない
+    mwso.step_core(0.1, 0.0, 0.5, 0.5, &[]);
+
+    let start = Instant::now();
+    for i in 0..steps {
+        if i % 16 == 0 { mwso.inject_state(i, 0.5, &[]); }
+        mwso.step_core(0.1, 0.0, 0.5, 0.5, &[]);
+    }
+    let elapsed = start.elapsed();
+    let secs = elapsed.as_secs_f64().max(1e-9);
+    StepThroughput {
+        dim,
+        steps,
+        elapsed,
+        steps_per_sec: steps as f64 / secs,
+        ns_per_bin: secs * 1e9 / (steps.max(1) * dim.max(1)) as f64,
+    }
+}
+
+/// decision_latency の結果
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DecisionLatency {
+    pub state_size: usize,
+    pub action_size: usize,
+    /// 実際に使われた波動場の次元（シャード構成ではシャード 1 つ分）
+    pub dim: usize,
+    pub select_actions: LatencySummary,
+    pub learn: LatencySummary,
+    /// select_actions + learn の 1 サイクルを 1 秒に何回回せるか（平均から算出）
+    pub cycles_per_sec: f64,
+}
+
+/// state_size 状態・category_sizes の行動を持つエージェントで、select_actions と learn を iterations 回交互に呼ぶ
+pub fn decision_latency(state_size: usize, category_sizes: &[usize], iterations: usize) -> DecisionLatency {
+    let mut ai = Singularity::new(state_size, category_sizes.to_vec());
+    let states = state_size.max(1);
+    for i in 0..iterations {
+        ai.select_actions(i % states);
+        ai.learn(if i % 3 == 0 { 1.0 } else { -0.2 });
+    }
+    let report = ai.stats();
+    let cycle = report.select_actions.avg + report.learn.avg;
+    DecisionLatency {
+        state_size,
+        action_size: ai.action_size,
+        dim: ai.mwso.dim,
+        select_actions: report.select_actions,
+        learn: report.learn,
+        cycles_per_sec: if cycle.is_zero() { 0.0 } else { 1.0 / cycle.as_secs_f64() },
+    }
+}
+
+/// 記憶容量の掃引の 1 点
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CapacityPoint {
+    /// 刻み込んだパターン数
+    pub patterns: usize,
+    /// 最初に刻み込んだパターンの取り出し SNR
+    pub snr: f32,
+}
+
+/// memory_capacity_sweep の結果
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CapacitySweep {
+    pub dim: usize,
+    pub threshold: f32,
+    pub points: Vec<CapacityPoint>,
+    /// SNR が threshold 以上だった最大のパターン数（1 点も満たさなければ 0）
+    pub capacity: usize,
+}
+
+/// dim 次元の MWSO に無相関なランダム位相パターンを pattern_counts 個ずつ刻み込み、
+/// 最初のパターンの取り出し SNR（MWSO::retrieval_snr）を測る。点ごとに新しい MWSO を使う
+pub fn memory_capacity_sweep(dim: usize, pattern_counts: &[usize], threshold: f32) -> CapacitySweep {
+    let points: Vec<CapacityPoint> = pattern_counts.iter().map(|&patterns| {
+        let mut mwso = MWSO::new(dim);
+        for seed in 0..patterns {
+            set_phase_pattern(&mut mwso, seed);
+            mwso.imprint_qcel(seed, 1.0);
+        }
+        set_phase_pattern(&mut mwso, 0);
+        CapacityPoint { patterns, snr: mwso.retrieval_snr(0) }
+    }).collect();
+    let capacity = points.iter().filter(|p| p.snr >= threshold).map(|p| p.patterns).max().unwrap_or(0);
+    CapacitySweep { dim, threshold, points, capacity }
+}
+
+/// seed から決まる一様な振幅のランダム位相パターンを波に書き込む
+fn set_phase_pattern(mwso: &mut MWSO, seed: usize) {
+    for i in 0..mwso.dim {
+        let hash = (i as u64).wrapping_mul(2654435761).wrapping_add(seed as u64).wrapping_mul(2246822519);
+        let phase = (hash >> 11) as f32 / (1u64 << 53) as f32 * 2.0 * std::f32::consts::PI;
+        mwso.psi_real[i] = to_wave(phase.cos());
+        mwso.psi_imag[i] = to_wave(phase.sin());
+    }
+}
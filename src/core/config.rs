@@ -0,0 +1,225 @@
+// src/core/config.rs
+
+/// 学習キューが満杯になった時の振る舞い
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LearnQueuePolicy {
+    /// 呼び出し側で最古の学習を同期実行して空きを作る（学習の忠実度を優先）
+    Block,
+    /// 最古の学習を破棄する（フレーム時間を優先）
+    DropOldest,
+    /// 最新の学習に報酬と経験を合算する（両者の折衷）
+    /// 報酬は平均ではなく和になり、合算された 1 回の学習で履歴全体へ割り当てられる
+    Coalesce,
+}
+
+impl LearnQueuePolicy {
+    /// JNI などからの整数指定 (0=Block, 1=DropOldest, 2=Coalesce)
+    pub fn from_id(id: i32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Block),
+            1 => Some(Self::DropOldest),
+            2 => Some(Self::Coalesce),
+            _ => None,
+        }
+    }
+}
+
+/// カテゴリー内の候補スコアから行動を選ぶ探索戦略
+/// 強度パラメータを持つ戦略は Singularity::exploration_beta を使う
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExplorationPolicy {
+    /// 上位 3 候補に対する Softmax サンプリング（従来の挙動）
+    #[default]
+    TopKSoftmax,
+    /// 常に最高スコアを選ぶ
+    Greedy,
+    /// 確率 exploration_beta で一様ランダム、それ以外は最高スコア
+    EpsilonGreedy,
+    /// 全候補に対する Boltzmann Softmax（逆温度は系の温度から決まる）
+    Boltzmann,
+    /// 状態ごとの訪問回数に基づく UCB（探索係数 exploration_beta）
+    Ucb,
+}
+
+impl ExplorationPolicy {
+    /// JNI などからの整数指定 (0=TopKSoftmax, 1=Greedy, 2=EpsilonGreedy, 3=Boltzmann, 4=Ucb)
+    pub fn from_id(id: i32) -> Option<Self> {
+        match id {
+            0 => Some(Self::TopKSoftmax),
+            1 => Some(Self::Greedy),
+            2 => Some(Self::EpsilonGreedy),
+            3 => Some(Self::Boltzmann),
+            4 => Some(Self::Ucb),
+            _ => None,
+        }
+    }
+}
+
+/// MWSO の振動子（波）の時間発展に使う積分法
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Integrator {
+    /// 回転・想起ブーストの後に実部へ隣接結合を足す陽的更新（従来の挙動）
+    #[default]
+    Explicit,
+    /// 隣接結合のキックと厳密な回転を交互に行うシンプレクティック更新（長時間でも振幅と位相の関係が崩れにくい）
+    Symplectic,
+}
+
+impl Integrator {
+    /// JNI などからの整数指定 (0=Explicit, 1=Symplectic)
+    pub fn from_id(id: i32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Explicit),
+            1 => Some(Self::Symplectic),
+            _ => None,
+        }
+    }
+}
+
+/// MWSO の隣接ビン間の結合項
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Coupling {
+    /// 隣接ビンの振幅を θ 倍して足し込む線形結合（従来の挙動）
+    #[default]
+    Linear,
+    /// 隣接ビンが互いの位相を θ·sin(位相差) で引き寄せる蔵本型の結合（振幅は変えない）
+    Kuramoto,
+}
+
+impl Coupling {
+    /// JNI などからの整数指定 (0=Linear, 1=Kuramoto)
+    pub fn from_id(id: i32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Linear),
+            1 => Some(Self::Kuramoto),
+            _ => None,
+        }
+    }
+}
+
+/// MWSO の重力場（ブラックホール化）の形成と蒸発のパラメータ
+/// 報酬のスケールが違うゲームでも重力井戸を作れる（または作らない）よう、adapt の閾値と係数を外に出したもの
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MWSOConfig {
+    /// 報酬がこの値を超えた行動のビンに重力を形成する（f32::INFINITY で形成しない）
+    pub gravity_reward_threshold: f32,
+    /// 形成 1 回あたりの重力の増分（1024 次元を基準に、高次元ほど sqrt(1024 / dim) 倍に小さくなる）
+    pub gravity_increment: f32,
+    /// 重力の上限
+    pub gravity_cap: f32,
+    /// 負の報酬を受けた行動のビンの重力に掛ける係数
+    pub gravity_failure_erosion: f32,
+    /// adapt ごとに重力場全体へ掛ける蒸発係数（ホーキング放射）
+    pub hawking_decay: f32,
+}
+
+impl Default for MWSOConfig {
+    fn default() -> Self {
+        Self {
+            gravity_reward_threshold: 1.2,
+            gravity_increment: 0.1,
+            gravity_cap: 1.0,
+            gravity_failure_erosion: 0.8,
+            hawking_decay: 0.995,
+        }
+    }
+}
+
+/// 実行時に切り替え可能な動作設定
+#[derive(Clone, Debug)]
+pub struct SingularityConfig {
+    pub learn_queue_capacity: usize,
+    pub learn_queue_policy: LearnQueuePolicy,
+    /// 時間予算付きの意思決定でスコアをキャッシュしておく状態数の上限（0 でキャッシュしない）
+    pub score_cache_capacity: usize,
+    /// learned_rules の成功回数がこの値に達したらハミルトニアン・ルールへ昇格させる（0 で無効）
+    pub crystallize_threshold: usize,
+    /// 昇格したルールの強度
+    pub crystallize_strength: f32,
+    /// 昇格したルールが連続でこの回数だけ負の報酬を受けたら降格させる
+    pub demotion_failures: u32,
+    /// 温度コントローラー: フラストレーション 1.0 あたりの毎ステップ加熱量
+    pub temp_frustration_weight: f32,
+    /// 温度コントローラー: 士気が 1.0 を下回った分だけ温度の下限を引き上げる係数
+    pub temp_morale_floor_weight: f32,
+    /// 温度コントローラー: 介入レベル 1.0 で上限をこの割合だけ引き下げる
+    pub temp_intervention_cap_weight: f32,
+    pub temperature_floor: f32,
+    pub temperature_ceiling: f32,
+    /// learned_rules の上限（超えたら最も長く使われていないルールを追い出す）
+    pub learned_rule_cap: usize,
+    /// この回数の learn ごとに古いルールを掃除する（0 で無効）
+    pub rule_prune_interval: u64,
+    /// 掃除の対象になる成功回数（これ未満）
+    pub rule_prune_min_count: usize,
+    /// この learn 回数以上強化されていないルールを古いとみなす
+    pub rule_stale_after: u64,
+    /// コールドストレージ有効時、この learn 回数以上アクセスのない状態をアーカイブする
+    pub archive_after: u64,
+    /// n ステップ報酬の地平線。None なら履歴全体への割引モンテカルロ
+    pub n_step: Option<usize>,
+    /// 方策外学習の重要度重みの上限（分散の爆発を防ぐ）
+    pub importance_clip: f32,
+    pub exploration_policy: ExplorationPolicy,
+    /// この回数の learn ごとに記憶の統合を行う（0 で無効。再生するパターンは set_replay_capacity で保持させる）
+    pub consolidate_interval: u64,
+    /// 記憶の統合で記憶波全体を弱める割合
+    pub consolidate_decay: f32,
+    /// 記憶の取り出し SNR がこの値を下回ったら波動場の次元を倍にする（0 で無効、シャード構成では無効）
+    pub auto_grow_snr: f32,
+    /// この回数の learn ごとに取り出し SNR を調べる
+    pub auto_grow_interval: u64,
+    /// 自動拡張で到達できる次元の上限
+    pub auto_grow_max_dim: usize,
+    /// 波の時間発展の積分法（MWSO への反映は Singularity::set_integrator で行う）
+    pub integrator: Integrator,
+    /// ノード間シナプスのヘッブ学習率（0 で無効）
+    pub plasticity_rate: f32,
+    /// 可塑性で変化するシナプス重みの下限と上限
+    pub synapse_weight_min: f32,
+    pub synapse_weight_max: f32,
+    /// ノードの不応期（発火後に入力へ応答しない更新回数、0 で無効。反映は Singularity::set_refractory で行う）
+    pub refractory_period: u32,
+    /// 発火とみなすノード状態の閾値
+    pub spike_threshold: f32,
+    /// ノードごとに保持する活性の履歴の長さ（0 で記録しない）
+    pub node_history_len: usize,
+}
+
+impl Default for SingularityConfig {
+    fn default() -> Self {
+        Self {
+            learn_queue_capacity: 64,
+            learn_queue_policy: LearnQueuePolicy::Block,
+            score_cache_capacity: 256,
+            crystallize_threshold: 5,
+            crystallize_strength: 0.3,
+            demotion_failures: 3,
+            temp_frustration_weight: 0.05,
+            temp_morale_floor_weight: 0.1,
+            temp_intervention_cap_weight: 0.5,
+            temperature_floor: 0.01,
+            temperature_ceiling: 2.0,
+            learned_rule_cap: 1024,
+            rule_prune_interval: 100,
+            rule_prune_min_count: 2,
+            rule_stale_after: 500,
+            archive_after: 10_000,
+            n_step: None,
+            importance_clip: 1.0,
+            exploration_policy: ExplorationPolicy::TopKSoftmax,
+            consolidate_interval: 0,
+            consolidate_decay: 0.1,
+            auto_grow_snr: 0.0,
+            auto_grow_interval: 100,
+            auto_grow_max_dim: 16384,
+            integrator: Integrator::Explicit,
+            plasticity_rate: 0.0,
+            synapse_weight_min: -1.0,
+            synapse_weight_max: 3.0,
+            refractory_period: 0,
+            spike_threshold: 0.8,
+            node_history_len: 64,
+        }
+    }
+}
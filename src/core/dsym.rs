@@ -0,0 +1,308 @@
+// src/core/dsym.rs
+// .dsym (Dark Singularity Model) フォーマットのストリーミング入出力
+// v15 以降は各セクションの先頭に 4 バイトのセクションタグを置き、読み込み時に検証する
+// v16 で記憶波（PP-CEL の q_memory / q_topo、f64）のセクションを末尾に追加した
+// v17 でワームホール (entanglements) のセクションを末尾に追加した
+// v18 で FIELDS の重力場の前に波動場の次元を置いた（次元を拡張したモデルの読み込み用）
+// v19 で知識層 (Bootstrapper のルール集) のセクションを末尾に追加した
+// v20 で各ルールに減衰率・期限付きフラグ・経過学習回数・使用回数を追加した
+// v21 で各ルールに対象とする行動の数 (span) を追加した
+// v22 で知識層の末尾にルールテンプレートを追加した
+// v23 で NODES の各ノードにシナプスの一覧 (target_id, weight) を追加した
+// v24 でコールドストレージへ退避した状態のセクションを末尾に追加した
+// v25 で状態コードの種類 (StateCoding) のセクションを末尾に追加した
+// v26 で行動ごとのビン数 (ActionLayout) のセクションを末尾に追加した
+// v27 で知識層の各ルールに結晶化で追加されたかの印を追加した
+// v28 で承認済みの状態統合 (state_aliases) のセクションを末尾に追加した
+// v29 でエピソードログの意思決定に乱数と探索の状態（スカウト・各シャードのシード、探索係数）を追加した
+// v30 で NODES の各ノードに名前と活性化関数を追加した
+
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use super::wave::{Wave, from_wave, to_wave};
+
+pub const DSYM_MAGIC: &[u8; 4] = b"DSYM";
+pub const DSYM_VERSION: u32 = 30;
+/// セクションタグが導入されたバージョン
+pub const SECTION_TAG_VERSION: u32 = 15;
+/// 記憶波セクションが導入されたバージョン
+pub const MEMORY_WAVE_VERSION: u32 = 16;
+/// ワームホールのセクションが導入されたバージョン
+pub const WORMHOLE_VERSION: u32 = 17;
+/// FIELDS に波動場の次元が入ったバージョン
+pub const DIM_GROWTH_VERSION: u32 = 18;
+/// 知識層のセクションが導入されたバージョン
+pub const KNOWLEDGE_VERSION: u32 = 19;
+/// ルールの寿命（減衰・期限）が入ったバージョン
+pub const RULE_LIFECYCLE_VERSION: u32 = 20;
+/// ルールの対象範囲 (span) が入ったバージョン
+pub const RULE_SPAN_VERSION: u32 = 21;
+/// 知識層にルールテンプレートが入ったバージョン
+pub const RULE_TEMPLATE_VERSION: u32 = 22;
+/// NODES にシナプスの一覧が入ったバージョン
+pub const SYNAPSE_VERSION: u32 = 23;
+/// 退避済みの状態のセクションが導入されたバージョン
+pub const ARCHIVE_VERSION: u32 = 24;
+/// 状態コードの種類のセクションが導入されたバージョン
+pub const STATE_CODING_VERSION: u32 = 25;
+/// 行動ごとのビン数のセクションが導入されたバージョン
+pub const ACTION_LAYOUT_VERSION: u32 = 26;
+/// ルールに結晶化の印が入ったバージョン
+pub const CRYSTALLIZED_RULE_VERSION: u32 = 27;
+/// 状態統合のセクションが導入されたバージョン
+pub const STATE_ALIAS_VERSION: u32 = 28;
+/// エピソードログに探索の状態が入ったバージョン
+pub const EPISODE_EXPLORATION_VERSION: u32 = 29;
+/// NODES に名前と活性化関数が入ったバージョン
+pub const NODE_IDENTITY_VERSION: u32 = 30;
+
+pub const SECTION_EMOTION: &[u8; 4] = b"EMOT";
+pub const SECTION_FIELDS: &[u8; 4] = b"FLDS";
+pub const SECTION_HISTORY: &[u8; 4] = b"HIST";
+pub const SECTION_CATEGORIES: &[u8; 4] = b"CATS";
+pub const SECTION_NODES: &[u8; 4] = b"NODE";
+pub const SECTION_RULES: &[u8; 4] = b"RULE";
+pub const SECTION_WAVE: &[u8; 4] = b"WAVE";
+pub const SECTION_EPISODE: &[u8; 4] = b"EPIS";
+pub const SECTION_MEMORY: &[u8; 4] = b"QMEM";
+pub const SECTION_WORMHOLES: &[u8; 4] = b"WORM";
+pub const SECTION_KNOWLEDGE: &[u8; 4] = b"KNOW";
+pub const SECTION_ARCHIVE: &[u8; 4] = b"ARCH";
+pub const SECTION_STATE_CODING: &[u8; 4] = b"CODE";
+pub const SECTION_ACTION_LAYOUT: &[u8; 4] = b"LAYO";
+pub const SECTION_STATE_ALIASES: &[u8; 4] = b"ALIA";
+
+/// 進捗通知の間隔（バイト）
+const PROGRESS_INTERVAL: u64 = 1 << 20;
+/// 配列を一括で読み書きする際のチャンクサイズ（要素数）
+const CHUNK_LEN: usize = 16 * 1024;
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+pub struct DsymWriter<W: Write> {
+    inner: BufWriter<W>,
+}
+
+impl<W: Write> DsymWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner: BufWriter::new(inner) }
+    }
+
+    pub fn write_header(&mut self, state_size: usize) -> io::Result<()> {
+        self.inner.write_all(DSYM_MAGIC)?;
+        self.write_u32(DSYM_VERSION)?;
+        self.write_u32(state_size as u32)
+    }
+
+    pub fn section(&mut self, tag: &[u8; 4]) -> io::Result<()> {
+        self.inner.write_all(tag)
+    }
+
+    pub fn write_u32(&mut self, v: u32) -> io::Result<()> {
+        self.inner.write_all(&v.to_le_bytes())
+    }
+
+    pub fn write_u64(&mut self, v: u64) -> io::Result<()> {
+        self.inner.write_all(&v.to_le_bytes())
+    }
+
+    pub fn write_f32(&mut self, v: f32) -> io::Result<()> {
+        self.inner.write_all(&v.to_le_bytes())
+    }
+
+    pub fn write_i32(&mut self, v: i32) -> io::Result<()> {
+        self.inner.write_all(&v.to_le_bytes())
+    }
+
+    /// 長さ (u32) + UTF-8 バイト列
+    pub fn write_str(&mut self, v: &str) -> io::Result<()> {
+        self.write_u32(v.len() as u32)?;
+        self.inner.write_all(v.as_bytes())
+    }
+
+    pub fn write_f32_slice(&mut self, values: &[f32]) -> io::Result<()> {
+        let mut bytes = Vec::with_capacity(CHUNK_LEN.min(values.len()) * 4);
+        for chunk in values.chunks(CHUNK_LEN) {
+            bytes.clear();
+            for v in chunk { bytes.extend_from_slice(&v.to_le_bytes()); }
+            self.inner.write_all(&bytes)?;
+        }
+        Ok(())
+    }
+
+    pub fn write_f64_slice(&mut self, values: &[f64]) -> io::Result<()> {
+        let mut bytes = Vec::with_capacity(CHUNK_LEN.min(values.len()) * 8);
+        for chunk in values.chunks(CHUNK_LEN) {
+            bytes.clear();
+            for v in chunk { bytes.extend_from_slice(&v.to_le_bytes()); }
+            self.inner.write_all(&bytes)?;
+        }
+        Ok(())
+    }
+
+    /// アクティブな波を書き出す（精度に関わらずファイル上は f32）
+    pub fn write_wave_slice(&mut self, values: &[Wave]) -> io::Result<()> {
+        let mut bytes = Vec::with_capacity(CHUNK_LEN.min(values.len()) * 4);
+        for chunk in values.chunks(CHUNK_LEN) {
+            bytes.clear();
+            for &v in chunk { bytes.extend_from_slice(&from_wave(v).to_le_bytes()); }
+            self.inner.write_all(&bytes)?;
+        }
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// 全体をメモリに展開せず、先頭から順にフィールドへ直接読み込むリーダー
+pub struct DsymReader<'a, R: Read> {
+    inner: BufReader<R>,
+    pub version: u32,
+    bytes_read: u64,
+    total_bytes: u64,
+    last_reported: u64,
+    progress: &'a mut dyn FnMut(u64, u64),
+}
+
+impl<'a, R: Read> DsymReader<'a, R> {
+    /// ヘッダー (マジック・バージョン) を読み込み、リーダーを返す
+    /// total_bytes は進捗通知の分母として使われる
+    pub fn open(inner: R, total_bytes: u64, progress: &'a mut dyn FnMut(u64, u64)) -> io::Result<Self> {
+        let mut reader = Self {
+            inner: BufReader::new(inner),
+            version: 0,
+            bytes_read: 0,
+            total_bytes,
+            last_reported: 0,
+            progress,
+        };
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != DSYM_MAGIC { return Err(invalid_data("Invalid Header")); }
+        reader.version = reader.read_u32()?;
+        if reader.version > DSYM_VERSION {
+            return Err(invalid_data("Unsupported .dsym version"));
+        }
+        Ok(reader)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.inner.read_exact(buf)?;
+        self.bytes_read += buf.len() as u64;
+        if self.bytes_read - self.last_reported >= PROGRESS_INTERVAL {
+            self.report();
+        }
+        Ok(())
+    }
+
+    fn report(&mut self) {
+        self.last_reported = self.bytes_read;
+        (self.progress)(self.bytes_read, self.total_bytes);
+    }
+
+    /// セクションタグを検証する（タグ導入前のバージョンでは何もしない）
+    pub fn expect_section(&mut self, tag: &[u8; 4]) -> io::Result<()> {
+        self.report();
+        if self.version < SECTION_TAG_VERSION { return Ok(()); }
+        let mut found = [0u8; 4];
+        self.read_exact(&mut found)?;
+        if &found != tag {
+            return Err(invalid_data(&format!(
+                "Unexpected section: expected {}, found {}",
+                String::from_utf8_lossy(tag),
+                String::from_utf8_lossy(&found)
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn read_u32(&mut self) -> io::Result<u32> {
+        let mut b = [0u8; 4];
+        self.read_exact(&mut b)?;
+        Ok(u32::from_le_bytes(b))
+    }
+
+    pub fn read_u64(&mut self) -> io::Result<u64> {
+        let mut b = [0u8; 8];
+        self.read_exact(&mut b)?;
+        Ok(u64::from_le_bytes(b))
+    }
+
+    pub fn read_f32(&mut self) -> io::Result<f32> {
+        let mut b = [0u8; 4];
+        self.read_exact(&mut b)?;
+        Ok(f32::from_le_bytes(b))
+    }
+
+    pub fn read_i32(&mut self) -> io::Result<i32> {
+        let mut b = [0u8; 4];
+        self.read_exact(&mut b)?;
+        Ok(i32::from_le_bytes(b))
+    }
+
+    /// write_str で書いた文字列を読み込む
+    pub fn read_string(&mut self) -> io::Result<String> {
+        let len = self.read_u32()? as usize;
+        let mut bytes = vec![0u8; len];
+        self.read_exact(&mut bytes)?;
+        String::from_utf8(bytes).map_err(|_| invalid_data("Invalid UTF-8 string"))
+    }
+
+    /// 配列へ直接読み込む（チャンク単位で変換し、中間バッファを最小限に抑える）
+    pub fn read_f32_into(&mut self, out: &mut [f32]) -> io::Result<()> {
+        let mut bytes = vec![0u8; CHUNK_LEN.min(out.len()) * 4];
+        for chunk in out.chunks_mut(CHUNK_LEN) {
+            let buf = &mut bytes[..chunk.len() * 4];
+            self.read_exact(buf)?;
+            for (v, b) in chunk.iter_mut().zip(buf.chunks_exact(4)) {
+                *v = f32::from_le_bytes([b[0], b[1], b[2], b[3]]);
+            }
+        }
+        Ok(())
+    }
+
+    /// write_wave_slice で書いた f32 配列をアクティブな波へ読み込む
+    pub fn read_wave_into(&mut self, out: &mut [Wave]) -> io::Result<()> {
+        let mut bytes = vec![0u8; CHUNK_LEN.min(out.len()) * 4];
+        for chunk in out.chunks_mut(CHUNK_LEN) {
+            let buf = &mut bytes[..chunk.len() * 4];
+            self.read_exact(buf)?;
+            for (v, b) in chunk.iter_mut().zip(buf.chunks_exact(4)) {
+                *v = to_wave(f32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn read_f64_into(&mut self, out: &mut [f64]) -> io::Result<()> {
+        let mut bytes = vec![0u8; CHUNK_LEN.min(out.len()) * 8];
+        for chunk in out.chunks_mut(CHUNK_LEN) {
+            let buf = &mut bytes[..chunk.len() * 8];
+            self.read_exact(buf)?;
+            for (v, b) in chunk.iter_mut().zip(buf.chunks_exact(8)) {
+                *v = f64::from_le_bytes(b.try_into().unwrap());
+            }
+        }
+        Ok(())
+    }
+
+    /// 不要な f32 配列を読み飛ばす
+    pub fn skip_f32(&mut self, count: usize) -> io::Result<()> {
+        let mut bytes = vec![0u8; CHUNK_LEN.min(count) * 4];
+        let mut remaining = count;
+        while remaining > 0 {
+            let n = remaining.min(CHUNK_LEN);
+            self.read_exact(&mut bytes[..n * 4])?;
+            remaining -= n;
+        }
+        Ok(())
+    }
+
+    /// 読み込み完了を通知する
+    pub fn finish(mut self) {
+        self.report();
+    }
+}
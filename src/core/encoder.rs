@@ -0,0 +1,113 @@
+// src/core/encoder.rs
+// 外部の状態表現を MWSO の注入パターン空間 (state_idx, 重み) へ写像するエンコーダ
+
+/// 状態のエンコード方式
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum StateEncoder {
+    /// 従来どおり state_idx をそのまま使う（密な state_size が必要）
+    #[default]
+    Dense,
+    /// Feature Hashing: 任意の u64 識別子を buckets 個のパターンへ probes 回ハッシュする
+    /// 複数プローブにより、単一の衝突で2つの状態が完全に同一視されることを防ぐ
+    Hashed { buckets: usize, probes: usize },
+}
+
+/// SplitMix64 の最終化関数（偏りの少ない 64bit ハッシュ）
+fn mix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+impl StateEncoder {
+    pub fn hashed(buckets: usize) -> Self {
+        StateEncoder::Hashed { buckets: buckets.max(1), probes: 2 }
+    }
+
+    /// 1つの状態識別子を重み付きパターン群へ変換する（重みの合計は 1.0）
+    pub fn encode_id(&self, id: u64) -> Vec<(usize, f32)> {
+        self.encode_features(&[(id, 1.0)])
+    }
+
+    /// 特徴ベクトル (特徴ID, 値) を重み付きパターン群へ変換する
+    /// 同じバケットに落ちた寄与は合算され、重みは合計 1.0 に正規化される
+    pub fn encode_features(&self, features: &[(u64, f32)]) -> Vec<(usize, f32)> {
+        let mut out: Vec<(usize, f32)> = Vec::new();
+        let mut push = |idx: usize, w: f32| {
+            match out.iter_mut().find(|(i, _)| *i == idx) {
+                Some(entry) => entry.1 += w,
+                None => out.push((idx, w)),
+            }
+        };
+        match *self {
+            StateEncoder::Dense => {
+                for &(id, v) in features {
+                    if v > 0.0 { push(id as usize, v); }
+                }
+            }
+            StateEncoder::Hashed { buckets, probes } => {
+                let probes = probes.max(1);
+                for &(id, v) in features {
+                    if v <= 0.0 { continue; }
+                    let w = v / probes as f32;
+                    let mut h = id;
+                    for _ in 0..probes {
+                        h = mix64(h);
+                        push((h % buckets as u64) as usize, w);
+                    }
+                }
+            }
+        }
+
+        let total: f32 = out.iter().map(|&(_, w)| w).sum();
+        if total > 0.0 {
+            for (_, w) in out.iter_mut() { *w /= total; }
+        }
+        out
+    }
+}
+
+/// 連続特徴のタイルコーディング（粗いコーディング）
+/// 少しずつずらした複数のタイリングで入力空間を格子に分割し、各タイリングで1つずつ
+/// アクティブなタイルを得る。近い入力ほど多くのタイルを共有するため、似た状況が汎化される
+#[derive(Clone, Debug, PartialEq)]
+pub struct TileCoder {
+    /// 特徴ごとの (最小値, 最大値)。範囲外の値は端のタイルに丸められる
+    pub ranges: Vec<(f32, f32)>,
+    /// 1タイリングあたりの各次元の分割数
+    pub tiles_per_dim: usize,
+    /// 重ね合わせるタイリングの数
+    pub tilings: usize,
+}
+
+impl TileCoder {
+    pub fn new(ranges: Vec<(f32, f32)>, tiles_per_dim: usize, tilings: usize) -> Self {
+        Self { ranges, tiles_per_dim: tiles_per_dim.max(1), tilings: tilings.max(1) }
+    }
+
+    /// 各タイリングでアクティブなタイルの識別子（タイリング番号と格子座標から一意に決まる）
+    pub fn active_tiles(&self, inputs: &[f32]) -> Vec<u64> {
+        let tiles = self.tiles_per_dim as f32;
+        let side = self.tiles_per_dim as u64 + 1; // オフセットで1つはみ出す分
+        (0..self.tilings).map(|t| {
+            let mut id = t as u64;
+            for (d, &(lo, hi)) in self.ranges.iter().enumerate() {
+                let x = inputs.get(d).copied().unwrap_or(lo);
+                let span = (hi - lo).max(1e-6);
+                let norm = ((x - lo) / span).clamp(0.0, 1.0);
+                // 次元ごとに異なる量だけずらす（非対称オフセット）
+                let offset = ((t * (2 * d + 1)) % self.tilings) as f32 / self.tilings as f32;
+                let coord = (norm * tiles + offset).floor() as u64;
+                id = id.wrapping_mul(side).wrapping_add(coord.min(side - 1));
+            }
+            mix64(id ^ ((self.ranges.len() as u64) << 56))
+        }).collect()
+    }
+
+    /// アクティブなタイルを、エンコーダ経由で重み付きパターン群へ変換する
+    pub fn encode(&self, inputs: &[f32], encoder: &StateEncoder) -> Vec<(usize, f32)> {
+        let features: Vec<(u64, f32)> = self.active_tiles(inputs).into_iter().map(|id| (id, 1.0)).collect();
+        encoder.encode_features(&features)
+    }
+}
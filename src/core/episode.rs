@@ -0,0 +1,210 @@
+// src/core/episode.rs
+// 意思決定と報酬の完全なログ。バイナリに保存し、新しいインスタンスへ再投入して挙動を再現する
+
+use super::dsym::{self, DsymReader, DsymWriter};
+use super::singularity::Singularity;
+use std::fs::File;
+use std::io;
+
+/// 意思決定直前の乱数と探索の状態（再生で食い違ったらこの値へ合わせる）
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExplorationState {
+    pub rng_seed: u64,
+    pub scout_seed: u64,
+    /// シャード化された構成での各シャードの乱数シード
+    pub shard_seeds: Vec<u64>,
+    pub exploration_beta: f32,
+    pub exploration_timer: usize,
+}
+
+impl ExplorationState {
+    pub fn capture(ai: &Singularity) -> Self {
+        Self {
+            rng_seed: ai.mwso.rng_seed,
+            scout_seed: ai.scout_mwso.rng_seed,
+            shard_seeds: ai.sharded_mwso.as_ref().map(|s| s.shards.iter().map(|m| m.rng_seed).collect()).unwrap_or_default(),
+            exploration_beta: ai.exploration_beta,
+            exploration_timer: ai.exploration_timer,
+        }
+    }
+
+    pub fn apply(&self, ai: &mut Singularity) {
+        ai.mwso.rng_seed = self.rng_seed;
+        ai.scout_mwso.rng_seed = self.scout_seed;
+        if let Some(ref mut sharded) = ai.sharded_mwso {
+            for (shard, &seed) in sharded.shards.iter_mut().zip(&self.shard_seeds) { shard.rng_seed = seed; }
+        }
+        ai.exploration_beta = self.exploration_beta;
+        ai.exploration_timer = self.exploration_timer;
+    }
+}
+
+/// ログの1イベント
+#[derive(Clone, Debug, PartialEq)]
+pub enum EpisodeEvent {
+    /// select_actions の呼び出し（温度と乱数・探索の状態は意思決定直前の値）
+    Decision { state_idx: usize, actions: Vec<i32>, temperature: f32, exploration: ExplorationState },
+    /// learn の呼び出し（温度は学習直後の値）
+    Reward { reward: f32, temperature: f32 },
+}
+
+const KIND_DECISION: u32 = 0;
+const KIND_REWARD: u32 = 1;
+/// 温度の一致判定の許容誤差
+const TEMPERATURE_TOLERANCE: f32 = 1e-5;
+
+/// 再生結果
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ReplayReport {
+    pub decisions: usize,
+    pub rewards: usize,
+    /// 記録と異なる行動が選ばれた回数
+    pub action_mismatches: usize,
+    /// 行動・温度・乱数と探索の状態のいずれかが最初に食い違ったイベントの位置
+    pub first_divergence: Option<usize>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EpisodeLog {
+    pub state_size: usize,
+    pub category_sizes: Vec<usize>,
+    pub events: Vec<EpisodeEvent>,
+    /// 記録中に呼ばれた、記録されない意思決定・学習の経路（select_actions_vector・learn_trajectory など）の回数
+    pub unrecorded: u64,
+}
+
+impl EpisodeLog {
+    pub fn new(state_size: usize, category_sizes: Vec<usize>) -> Self {
+        Self { state_size, category_sizes, events: Vec::new(), unrecorded: 0 }
+    }
+
+    /// 記録されない経路が使われたログは再生しても挙動を再現できない
+    pub fn is_replayable(&self) -> bool {
+        self.unrecorded == 0
+    }
+
+    /// 記録時と同じ構成の新しいインスタンス
+    pub fn fresh_instance(&self) -> Singularity {
+        Singularity::new(self.state_size, self.category_sizes.clone())
+    }
+
+    /// 再現できないログ（is_replayable が false）は保存せずエラーを返す
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        if !self.is_replayable() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Episode used unrecorded decision or learn paths"));
+        }
+        let mut w = DsymWriter::new(File::create(path)?);
+        w.write_header(self.state_size)?;
+        w.section(dsym::SECTION_CATEGORIES)?;
+        w.write_u32(self.category_sizes.len() as u32)?;
+        for &size in &self.category_sizes { w.write_u32(size as u32)?; }
+
+        w.section(dsym::SECTION_EPISODE)?;
+        w.write_u32(self.events.len() as u32)?;
+        for event in &self.events {
+            match event {
+                EpisodeEvent::Decision { state_idx, actions, temperature, exploration } => {
+                    w.write_u32(KIND_DECISION)?;
+                    w.write_u32(*state_idx as u32)?;
+                    w.write_f32(*temperature)?;
+                    w.write_u64(exploration.rng_seed)?;
+                    w.write_u64(exploration.scout_seed)?;
+                    w.write_u32(exploration.shard_seeds.len() as u32)?;
+                    for &seed in &exploration.shard_seeds { w.write_u64(seed)?; }
+                    w.write_f32(exploration.exploration_beta)?;
+                    w.write_u32(exploration.exploration_timer as u32)?;
+                    w.write_u32(actions.len() as u32)?;
+                    for &a in actions { w.write_u32(a as u32)?; }
+                }
+                EpisodeEvent::Reward { reward, temperature } => {
+                    w.write_u32(KIND_REWARD)?;
+                    w.write_f32(*reward)?;
+                    w.write_f32(*temperature)?;
+                }
+            }
+        }
+        w.finish()
+    }
+
+    pub fn load(path: &str) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let total_bytes = file.metadata().map(|m| m.len()).unwrap_or(0);
+        let mut no_progress = |_: u64, _: u64| {};
+        let mut r = DsymReader::open(file, total_bytes, &mut no_progress)?;
+        // 乱数シードしか記録していない旧形式は再現できないため読み込まない
+        if r.version < dsym::EPISODE_EXPLORATION_VERSION {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Episode log predates exploration state; record it again"));
+        }
+        let state_size = r.read_u32()? as usize;
+
+        r.expect_section(dsym::SECTION_CATEGORIES)?;
+        let cat_count = r.read_u32()? as usize;
+        let mut category_sizes = Vec::with_capacity(cat_count);
+        for _ in 0..cat_count { category_sizes.push(r.read_u32()? as usize); }
+
+        r.expect_section(dsym::SECTION_EPISODE)?;
+        let event_count = r.read_u32()? as usize;
+        let mut events = Vec::with_capacity(event_count);
+        for _ in 0..event_count {
+            let event = match r.read_u32()? {
+                KIND_DECISION => {
+                    let state_idx = r.read_u32()? as usize;
+                    let temperature = r.read_f32()?;
+                    let rng_seed = r.read_u64()?;
+                    let scout_seed = r.read_u64()?;
+                    let shard_count = r.read_u32()? as usize;
+                    let mut shard_seeds = Vec::with_capacity(shard_count.min(1024));
+                    for _ in 0..shard_count { shard_seeds.push(r.read_u64()?); }
+                    let exploration_beta = r.read_f32()?;
+                    let exploration_timer = r.read_u32()? as usize;
+                    let exploration = ExplorationState { rng_seed, scout_seed, shard_seeds, exploration_beta, exploration_timer };
+                    let action_count = r.read_u32()? as usize;
+                    let mut actions = Vec::with_capacity(action_count);
+                    for _ in 0..action_count { actions.push(r.read_u32()? as i32); }
+                    EpisodeEvent::Decision { state_idx, actions, temperature, exploration }
+                }
+                KIND_REWARD => {
+                    let reward = r.read_f32()?;
+                    let temperature = r.read_f32()?;
+                    EpisodeEvent::Reward { reward, temperature }
+                }
+                _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "Unknown episode event")),
+            };
+            events.push(event);
+        }
+        r.finish();
+        Ok(Self { state_size, category_sizes, events, unrecorded: 0 })
+    }
+
+    /// ログを ai に再投入する。食い違いがあっても最後まで再生し、乱数と探索の状態は記録値に合わせる
+    /// 再現できないログ（is_replayable が false）は再生せず None を返す
+    pub fn replay(&self, ai: &mut Singularity) -> Option<ReplayReport> {
+        if !self.is_replayable() { return None; }
+        let mut report = ReplayReport::default();
+        for (i, event) in self.events.iter().enumerate() {
+            match event {
+                EpisodeEvent::Decision { state_idx, actions, temperature, exploration } => {
+                    if ExplorationState::capture(ai) != *exploration
+                        || (ai.system_temperature - temperature).abs() > TEMPERATURE_TOLERANCE {
+                        report.first_divergence.get_or_insert(i);
+                        exploration.apply(ai);
+                    }
+                    let chosen = ai.select_actions(*state_idx);
+                    report.decisions += 1;
+                    if chosen != *actions {
+                        report.action_mismatches += 1;
+                        report.first_divergence.get_or_insert(i);
+                    }
+                }
+                EpisodeEvent::Reward { reward, temperature } => {
+                    ai.learn(*reward);
+                    report.rewards += 1;
+                    if (ai.system_temperature - temperature).abs() > TEMPERATURE_TOLERANCE {
+                        report.first_divergence.get_or_insert(i);
+                    }
+                }
+            }
+        }
+        Some(report)
+    }
+}
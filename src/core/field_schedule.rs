@@ -0,0 +1,138 @@
+// src/core/field_schedule.rs
+// 外部場の予約注入: inject_external_state を毎ステップ手で呼ぶ代わりに、継続時間と減衰の形を付けて登録し、
+// step_core の先頭で自動的に波へ加える（例:「拡張系の行動を 200 tick 抑える」）
+
+use super::wave::{Wave, to_wave};
+
+/// 継続時間中の強度の変化
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FieldDecay {
+    /// 最後まで同じ強度
+    #[default]
+    Constant,
+    /// 終了時に 0 になるよう直線的に弱まる
+    Linear,
+    /// half_life ステップごとに半分になる
+    Exponential { half_life: f32 },
+}
+
+impl FieldDecay {
+    /// JNI などからの整数指定 (0=Constant, 1=Linear, 2=Exponential)。param は Exponential の半減期
+    pub fn from_id(id: i32, param: f32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Constant),
+            1 => Some(Self::Linear),
+            2 if param > 0.0 => Some(Self::Exponential { half_life: param }),
+            _ => None,
+        }
+    }
+
+    /// duration ステップのうち elapsed ステップ経過した時点の強度の倍率
+    fn factor(self, elapsed: u32, duration: u32) -> f32 {
+        match self {
+            Self::Constant => 1.0,
+            Self::Linear => 1.0 - elapsed as f32 / duration.max(1) as f32,
+            Self::Exponential { half_life } => 0.5f32.powf(elapsed as f32 / half_life),
+        }
+    }
+}
+
+/// 登録された外部場 1 つ
+#[derive(Clone, Debug)]
+pub struct ScheduledField {
+    pub id: u64,
+    psi_real: Vec<f32>,
+    psi_imag: Vec<f32>,
+    /// 正なら場を波へ足し込み、負なら場の振幅 × |strength| の割合でそのビンの波を減衰させる
+    pub strength: f32,
+    pub duration: u32,
+    pub elapsed: u32,
+    pub decay: FieldDecay,
+}
+
+impl ScheduledField {
+    /// 現在のステップで使う強度
+    pub fn current_strength(&self) -> f32 {
+        self.strength * self.decay.factor(self.elapsed, self.duration)
+    }
+
+    pub fn remaining(&self) -> u32 {
+        self.duration.saturating_sub(self.elapsed)
+    }
+}
+
+/// MWSO ごとの外部場の予約表
+#[derive(Clone, Debug, Default)]
+pub struct FieldScheduler {
+    fields: Vec<ScheduledField>,
+    next_id: u64,
+}
+
+impl FieldScheduler {
+    /// 外部場を duration ステップ分予約し、取り消し用の ID を返す
+    pub fn schedule(&mut self, psi_real: &[f32], psi_imag: &[f32], strength: f32, duration: u32, decay: FieldDecay) -> u64 {
+        let id = self.next_id;
+        self.schedule_as(id, psi_real, psi_imag, strength, duration, decay);
+        id
+    }
+
+    /// ID を指定して予約する（複数の MWSO にまたがる場を同じ ID で取り消せるようにするため）
+    pub fn schedule_as(&mut self, id: u64, psi_real: &[f32], psi_imag: &[f32], strength: f32, duration: u32, decay: FieldDecay) {
+        self.next_id = self.next_id.max(id + 1);
+        if duration > 0 && psi_real.len() == psi_imag.len() && strength.is_finite() {
+            self.fields.push(ScheduledField {
+                id,
+                psi_real: psi_real.to_vec(),
+                psi_imag: psi_imag.to_vec(),
+                strength,
+                duration,
+                elapsed: 0,
+                decay,
+            });
+        }
+    }
+
+    /// 取り除いたら true
+    pub fn cancel(&mut self, id: u64) -> bool {
+        let before = self.fields.len();
+        self.fields.retain(|f| f.id != id);
+        self.fields.len() != before
+    }
+
+    pub fn clear(&mut self) {
+        self.fields.clear();
+    }
+
+    pub fn fields(&self) -> &[ScheduledField] {
+        &self.fields
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// 有効な場をすべて波へ適用して 1 ステップ進め、期限の切れた場を取り除く
+    /// 波と長さが合わない場（次元の変更前に登録されたもの）は適用せずに捨てる
+    pub fn apply(&mut self, psi_real: &mut [Wave], psi_imag: &mut [Wave]) {
+        let dim = psi_real.len();
+        self.fields.retain(|f| f.psi_real.len() == dim);
+        for field in &mut self.fields {
+            let strength = field.current_strength();
+            if strength >= 0.0 {
+                for i in 0..dim {
+                    psi_real[i] += to_wave(field.psi_real[i] * strength);
+                    psi_imag[i] += to_wave(field.psi_imag[i] * strength);
+                }
+            } else {
+                for i in 0..dim {
+                    let amplitude = field.psi_real[i].hypot(field.psi_imag[i]);
+                    let keep = to_wave((1.0 + strength * amplitude).clamp(0.0, 1.0));
+                    psi_real[i] *= keep;
+                    psi_imag[i] *= keep;
+                }
+            }
+            field.elapsed += 1;
+        }
+        self.fields.retain(|f| f.elapsed < f.duration);
+    }
+}
@@ -0,0 +1,42 @@
+// src/core/hierarchy.rs
+
+/// 多解像度状態階層: 細粒度の状態（タイル）を粗粒度の領域（リージョン）に束ねる
+/// 領域は penalty_matrix 上で細粒度状態の後ろに「仮想状態」として配置され、
+/// 領域に蓄積されたペナルティやルールは未経験のタイルにも自動的に転移する
+pub struct StateHierarchy {
+    /// 細粒度状態インデックス -> 領域インデックス
+    pub region_of: Vec<usize>,
+    pub region_count: usize,
+    /// タイル自身の注入重み
+    pub fine_weight: f32,
+    /// 所属領域の注入重み（知識の転移強度）
+    pub coarse_weight: f32,
+}
+
+impl StateHierarchy {
+    pub fn new(region_of: Vec<usize>) -> Self {
+        let region_count = region_of.iter().max().map(|&r| r + 1).unwrap_or(0);
+        Self {
+            region_of,
+            region_count,
+            fine_weight: 1.0,
+            coarse_weight: 0.5,
+        }
+    }
+
+    /// 連続する region_size 個のタイルを1つの領域とみなす一様な階層
+    pub fn uniform(state_size: usize, region_size: usize) -> Self {
+        let region_size = region_size.max(1);
+        Self::new((0..state_size).map(|s| s / region_size).collect())
+    }
+
+    pub fn with_weights(mut self, fine_weight: f32, coarse_weight: f32) -> Self {
+        self.fine_weight = fine_weight;
+        self.coarse_weight = coarse_weight;
+        self
+    }
+
+    pub fn region(&self, fine_idx: usize) -> Option<usize> {
+        self.region_of.get(fine_idx).copied()
+    }
+}
@@ -1,56 +1,908 @@
 // src/core/knowledge.rs
 
+use super::singularity::Singularity;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Range;
+
 /// ハミルトニアン・ルール: 波動状態に対する「外場」としての知識
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct HamiltonianRule {
     /// 発動条件のインデックス (Java側からの指定を容易にするため ID制に)
     /// 実装例: 0=HP低, 1=敵至近, 2=弾薬少 など
+    #[serde(default = "composite_condition_id")]
     pub condition_id: i32,
     /// 誘導したいアクションのインデックス
     pub target_action: usize,
+    /// target_action から連続して対象にする行動の数（1 なら単一の行動、カテゴリー全体なども 1 ルールで表せる）
+    #[serde(default = "single_action", skip_serializing_if = "is_single_action")]
+    pub span: usize,
     /// 知識の強制力 (resonance_strength)
     /// 正の値は誘引（アトラクタ）、負の値は排斥（ペナルティ場）として機能する
     pub strength: f32,
+    /// 複合条件。Some のときは condition_id の代わりにこの式で発動を判定する
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<Condition>,
+    /// 優先度（大きいほど強い）。RuleConflictPolicy::HighestPriority で使う
+    #[serde(default)]
+    pub priority: i32,
+    /// 学習ごとに強度へ掛ける減衰率（1.0 で減衰なし）
+    #[serde(default = "no_decay")]
+    pub decay: f32,
+    /// true なら Bootstrapper::lifecycle の TTL・使用回数・最小強度で期限切れになる
+    #[serde(default)]
+    pub expires: bool,
+    /// 作成（または最後の強化）からの学習回数
+    #[serde(default)]
+    pub age: u64,
+    /// 学習時に発動していた回数
+    #[serde(default)]
+    pub uses: u64,
+    /// learned_rules の結晶化で追加されたルールか（降格時はこの印で取り除く。知識パックには書き出さない）
+    #[serde(skip)]
+    pub crystallized: bool,
+}
+
+fn no_decay() -> f32 {
+    1.0
+}
+
+fn single_action() -> usize {
+    1
+}
+
+fn is_single_action(span: &usize) -> bool {
+    *span == 1
+}
+
+impl HamiltonianRule {
+    /// action がこのルールの対象範囲に入っているか
+    pub fn targets(&self, action: usize) -> bool {
+        action >= self.target_action && action - self.target_action < self.span
+    }
+
+    /// 有効な条件フラグ群に対してこのルールが発動するか
+    pub fn is_active(&self, active_conditions: &[i32]) -> bool {
+        match &self.condition {
+            Some(condition) => condition.evaluate(active_conditions),
+            None => is_condition_active(active_conditions, self.condition_id),
+        }
+    }
+
+    /// 条件の発動レベル (0.0〜1.0) に対するこのルールの発動度合い
+    pub fn activation(&self, levels: &[(i32, f32)]) -> f32 {
+        match &self.condition {
+            Some(condition) => condition.level(levels),
+            None => condition_level(levels, self.condition_id),
+        }
+    }
+}
+
+/// levels 中の条件 ID の発動レベル（0.0〜1.0 に収める。重複していれば最大、なければ 0.0）
+pub fn condition_level(levels: &[(i32, f32)], id: i32) -> f32 {
+    if id == ALWAYS_CONDITION_ID {
+        return 1.0;
+    }
+    levels.iter()
+        .filter(|&&(c, _)| c == id)
+        .map(|&(_, level)| if level.is_nan() { 0.0 } else { level.clamp(0.0, 1.0) })
+        .fold(0.0, f32::max)
+}
+
+/// 意思決定の外場に寄与したルール 1 件分
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RuleActivation {
+    /// Bootstrapper::rules 内の位置（寄与した時点のもの）
+    pub rule: usize,
+    pub action: usize,
+    /// 強度 × 発動度合い（知識項ではこの 5 倍、合計が -0.9 を下回ると -100 の禁止になる）
+    pub contribution: f32,
+}
+
+/// 同じ行動に複数のルールが発動したときの外場の決め方
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RuleConflictPolicy {
+    /// すべての強度を足し合わせる
+    #[default]
+    Sum,
+    /// 最も優先度の高いルールだけを採用する（同じ優先度どうしは足し合わせる）
+    HighestPriority,
+    /// 絶対値の最も大きい強度だけを採用する（優先度は見ない）
+    MaxMagnitude,
+}
+
+impl RuleConflictPolicy {
+    /// JNI などからの整数指定 (0=Sum, 1=HighestPriority, 2=MaxMagnitude)
+    pub fn from_id(id: i32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Sum),
+            1 => Some(Self::HighestPriority),
+            2 => Some(Self::MaxMagnitude),
+            _ => None,
+        }
+    }
+
+    pub fn id(self) -> i32 {
+        match self {
+            Self::Sum => 0,
+            Self::HighestPriority => 1,
+            Self::MaxMagnitude => 2,
+        }
+    }
+}
+
+/// 条件フラグ群の中で id が有効か（ALWAYS_CONDITION_ID は常に有効）
+pub fn is_condition_active(active_conditions: &[i32], id: i32) -> bool {
+    id == ALWAYS_CONDITION_ID || active_conditions.contains(&id)
+}
+
+/// 条件に関わらず常に発動するルールの condition_id（「行動 0 は選ばない」などの基本的な事前知識用）
+pub const ALWAYS_CONDITION_ID: i32 = -1;
+
+/// 複合条件ルールの condition_id（単一条件の検索に引っかからないよう実在しない値にしておく）
+pub const COMPOSITE_CONDITION_ID: i32 = i32::MIN;
+
+/// 領域ルールの condition_id の基点（領域 r は REGION_CONDITION_BASE + r）
+/// 呼び出し側が割り当てる条件 ID や状態インデックスと重ならないよう、負の端の域を使う
+pub const REGION_CONDITION_BASE: i32 = i32::MIN + 1;
+
+/// 領域 region のルールの condition_id
+pub fn region_condition_id(region: usize) -> i32 {
+    REGION_CONDITION_BASE + region as i32
+}
+
+fn composite_condition_id() -> i32 {
+    COMPOSITE_CONDITION_ID
+}
+
+/// 前置記法エンコードの演算子（条件 ID は 0 以上、演算子は負の値）
+/// [OP_ALL, n, 子1, ..., 子n] / [OP_ANY, n, 子1, ..., 子n] / [OP_NOT, 子]
+/// 常に真の条件は [OP_ALL, 0]
+pub const OP_ALL: i32 = -1;
+pub const OP_ANY: i32 = -2;
+pub const OP_NOT: i32 = -3;
+
+/// ルールの発動条件式
+/// 例: All([HP_LOW, ENEMY_NEAR]) かつ Not(AMMO_LOW)
+///   = All([Id(HP_LOW), Id(ENEMY_NEAR), Not(Id(AMMO_LOW))])
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Condition {
+    /// 条件フラグが有効
+    Id(i32),
+    /// すべて成り立つ（空なら真）
+    All(Vec<Condition>),
+    /// どれかが成り立つ（空なら偽）
+    Any(Vec<Condition>),
+    /// 成り立たない
+    Not(Box<Condition>),
+}
+
+impl Condition {
+    pub fn evaluate(&self, active_conditions: &[i32]) -> bool {
+        match self {
+            Self::Id(id) => is_condition_active(active_conditions, *id),
+            Self::All(children) => children.iter().all(|c| c.evaluate(active_conditions)),
+            Self::Any(children) => children.iter().any(|c| c.evaluate(active_conditions)),
+            Self::Not(child) => !child.evaluate(active_conditions),
+        }
+    }
+
+    /// 段階的な発動レベルでの評価（ファジー論理: All=最小, Any=最大, Not=1-x）
+    /// レベルがすべて 0.0/1.0 なら evaluate と一致する
+    pub fn level(&self, levels: &[(i32, f32)]) -> f32 {
+        match self {
+            Self::Id(id) => condition_level(levels, *id),
+            Self::All(children) => children.iter().map(|c| c.level(levels)).fold(1.0, f32::min),
+            Self::Any(children) => children.iter().map(|c| c.level(levels)).fold(0.0, f32::max),
+            Self::Not(child) => 1.0 - child.level(levels),
+        }
+    }
+
+    /// 前置記法の int 配列から条件式を組み立てる。不正な列や余りがあれば None
+    pub fn decode_prefix(codes: &[i32]) -> Option<Self> {
+        let mut pos = 0;
+        let condition = Self::decode_at(codes, &mut pos)?;
+        (pos == codes.len()).then_some(condition)
+    }
+
+    fn decode_at(codes: &[i32], pos: &mut usize) -> Option<Self> {
+        let code = *codes.get(*pos)?;
+        *pos += 1;
+        match code {
+            OP_ALL | OP_ANY => {
+                let count = usize::try_from(*codes.get(*pos)?).ok()?;
+                *pos += 1;
+                // 子は最低 1 要素ずつ消費するので、残りより多い個数は不正
+                if count > codes.len() - *pos {
+                    return None;
+                }
+                let children = (0..count).map(|_| Self::decode_at(codes, pos)).collect::<Option<Vec<_>>>()?;
+                Some(if code == OP_ALL { Self::All(children) } else { Self::Any(children) })
+            }
+            OP_NOT => Some(Self::Not(Box::new(Self::decode_at(codes, pos)?))),
+            id if id >= 0 => Some(Self::Id(id)),
+            _ => None,
+        }
+    }
+
+    /// decode_prefix の逆変換
+    pub fn encode_prefix(&self) -> Vec<i32> {
+        let mut codes = Vec::new();
+        self.encode_into(&mut codes);
+        codes
+    }
+
+    fn encode_into(&self, codes: &mut Vec<i32>) {
+        match self {
+            // 演算子と衝突するので、常に真の条件は空の All として書き出す
+            Self::Id(ALWAYS_CONDITION_ID) => codes.extend([OP_ALL, 0]),
+            Self::Id(id) => codes.push(*id),
+            Self::All(children) | Self::Any(children) => {
+                codes.push(if matches!(self, Self::All(_)) { OP_ALL } else { OP_ANY });
+                codes.push(children.len() as i32);
+                for child in children {
+                    child.encode_into(codes);
+                }
+            }
+            Self::Not(child) => {
+                codes.push(OP_NOT);
+                child.encode_into(codes);
+            }
+        }
+    }
+}
+
+/// 行動式の前置記法エンコードのタグ
+/// [EXPR_CONST, 値] / [EXPR_STATE] / [EXPR_ADD など二項演算, 左, 右]
+/// 例: (状態 / 2) % 4 = [EXPR_MOD, EXPR_DIV, EXPR_STATE, EXPR_CONST, 2, EXPR_CONST, 4]
+pub const EXPR_CONST: i32 = 0;
+pub const EXPR_STATE: i32 = 1;
+pub const EXPR_ADD: i32 = 2;
+pub const EXPR_SUB: i32 = 3;
+pub const EXPR_MUL: i32 = 4;
+pub const EXPR_DIV: i32 = 5;
+pub const EXPR_MOD: i32 = 6;
+
+/// 状態から対象の行動を求める整数式（ルールテンプレート用）
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ActionExpr {
+    Const(i64),
+    /// 意思決定中の状態インデックス
+    State,
+    Add(Box<ActionExpr>, Box<ActionExpr>),
+    Sub(Box<ActionExpr>, Box<ActionExpr>),
+    Mul(Box<ActionExpr>, Box<ActionExpr>),
+    /// ユークリッド除算（0 除算は値なし）
+    Div(Box<ActionExpr>, Box<ActionExpr>),
+    /// 非負の剰余（0 除算は値なし）
+    Mod(Box<ActionExpr>, Box<ActionExpr>),
+}
+
+impl ActionExpr {
+    /// 状態 state での値。0 除算やオーバーフローは None
+    pub fn evaluate(&self, state: usize) -> Option<i64> {
+        match self {
+            Self::Const(value) => Some(*value),
+            Self::State => i64::try_from(state).ok(),
+            Self::Add(a, b) => a.evaluate(state)?.checked_add(b.evaluate(state)?),
+            Self::Sub(a, b) => a.evaluate(state)?.checked_sub(b.evaluate(state)?),
+            Self::Mul(a, b) => a.evaluate(state)?.checked_mul(b.evaluate(state)?),
+            Self::Div(a, b) => a.evaluate(state)?.checked_div_euclid(b.evaluate(state)?),
+            Self::Mod(a, b) => a.evaluate(state)?.checked_rem_euclid(b.evaluate(state)?),
+        }
+    }
+
+    /// 前置記法の int 配列から式を組み立てる。不正な列や余りがあれば None
+    pub fn decode_prefix(codes: &[i32]) -> Option<Self> {
+        let mut pos = 0;
+        let expr = Self::decode_at(codes, &mut pos)?;
+        (pos == codes.len()).then_some(expr)
+    }
+
+    fn decode_at(codes: &[i32], pos: &mut usize) -> Option<Self> {
+        let code = *codes.get(*pos)?;
+        *pos += 1;
+        let binary = match code {
+            EXPR_CONST => {
+                let value = *codes.get(*pos)?;
+                *pos += 1;
+                return Some(Self::Const(value as i64));
+            }
+            EXPR_STATE => return Some(Self::State),
+            EXPR_ADD => Self::Add,
+            EXPR_SUB => Self::Sub,
+            EXPR_MUL => Self::Mul,
+            EXPR_DIV => Self::Div,
+            EXPR_MOD => Self::Mod,
+            _ => return None,
+        };
+        let left = Self::decode_at(codes, pos)?;
+        let right = Self::decode_at(codes, pos)?;
+        Some(binary(Box::new(left), Box::new(right)))
+    }
+
+    /// decode_prefix の逆変換（int に収まらない定数は切り詰める）
+    pub fn encode_prefix(&self) -> Vec<i32> {
+        let mut codes = Vec::new();
+        self.encode_into(&mut codes);
+        codes
+    }
+
+    fn encode_into(&self, codes: &mut Vec<i32>) {
+        let (op, a, b) = match self {
+            Self::Const(value) => return codes.extend([EXPR_CONST, (*value).clamp(i32::MIN as i64, i32::MAX as i64) as i32]),
+            Self::State => return codes.push(EXPR_STATE),
+            Self::Add(a, b) => (EXPR_ADD, a, b),
+            Self::Sub(a, b) => (EXPR_SUB, a, b),
+            Self::Mul(a, b) => (EXPR_MUL, a, b),
+            Self::Div(a, b) => (EXPR_DIV, a, b),
+            Self::Mod(a, b) => (EXPR_MOD, a, b),
+        };
+        codes.push(op);
+        a.encode_into(codes);
+        b.encode_into(codes);
+    }
+}
+
+/// ルールテンプレート:「条件 condition のとき、行動 action(状態) を strength で誘導する」
+/// 線形の対応（例: 行動 = 状態 / 2）を状態ごとのルールを列挙せずに 1 つで表す
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuleTemplate {
+    pub condition: Condition,
+    pub action: ActionExpr,
+    pub strength: f32,
 }
 
+impl RuleTemplate {
+    /// 状態 state での対象の行動（行動数の範囲外なら None）
+    pub fn target(&self, state: usize, action_size: usize) -> Option<usize> {
+        usize::try_from(self.action.evaluate(state)?).ok().filter(|&a| a < action_size)
+    }
+}
+
+/// 経験から自己獲得したルール（状態で高報酬を得た行動）
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LearnedRule {
+    pub state: usize,
+    pub action: usize,
+    /// 強化された回数
+    pub count: usize,
+    /// 最後に強化された時刻（Singularity::rule_clock）
+    pub last_used: u64,
+}
+
+/// 知識層だけで評価した行動の順位（カテゴリーごと、スコア降順・同点はインデックス昇順）
+#[derive(Clone, Debug, PartialEq)]
+pub struct RankedActions {
+    /// categories[cat] = [(カテゴリー内ローカルインデックス, スコア), ...]
+    pub categories: Vec<Vec<(usize, f32)>>,
+}
+
+impl RankedActions {
+    /// カテゴリーの第1位（ローカルインデックス）
+    pub fn best(&self, category: usize) -> Option<usize> {
+        self.categories.get(category)?.first().map(|&(a, _)| a)
+    }
+}
+
+/// 期限付きルール（HamiltonianRule::expires）の寿命の設定。学習ごとに Bootstrapper::advance で適用する
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RuleLifecycle {
+    /// observe_expert が自動生成するルールに付ける減衰率（1.0 で減衰なし）
+    pub auto_decay: f32,
+    /// 作成（または最後の強化）からこの学習回数を超えたら消す
+    pub ttl: Option<u64>,
+    /// この回数発動したら消す
+    pub max_uses: Option<u64>,
+    /// 強度の絶対値がこれを下回ったら消す
+    pub min_strength: f32,
+}
+
+impl Default for RuleLifecycle {
+    fn default() -> Self {
+        Self { auto_decay: 1.0, ttl: None, max_uses: None, min_strength: 0.0 }
+    }
+}
+
+impl RuleLifecycle {
+    fn is_expired(&self, rule: &HamiltonianRule) -> bool {
+        rule.expires
+            && (self.ttl.is_some_and(|ttl| rule.age > ttl)
+                || self.max_uses.is_some_and(|max| rule.uses >= max)
+                || rule.strength.abs() < self.min_strength)
+    }
+}
+
+/// キー → Vec 内で最初に現れる位置の索引（意思決定のたびに線形探索しないためのもの）
+/// 索引した時点の Vec の長さを覚えておき、公開フィールドが外から直接書き換えられて長さが変わった場合は
+/// lookup が None を返すので、呼び出し側は線形探索に戻る
+#[derive(Clone, Debug)]
+pub(crate) struct PositionIndex<K> {
+    positions: HashMap<K, usize>,
+    len: usize,
+}
+
+impl<K> Default for PositionIndex<K> {
+    fn default() -> Self {
+        Self { positions: HashMap::new(), len: 0 }
+    }
+}
+
+impl<K: Hash + Eq> PositionIndex<K> {
+    /// keys は Vec の要素順。None の要素は索引に載せない
+    pub(crate) fn rebuild(&mut self, keys: impl Iterator<Item = Option<K>>) {
+        self.positions.clear();
+        self.len = 0;
+        for key in keys {
+            self.push(key);
+        }
+    }
+
+    /// Vec の末尾に要素を 1 つ追加した直後に呼ぶ
+    pub(crate) fn push(&mut self, key: Option<K>) {
+        if let Some(key) = key {
+            self.positions.entry(key).or_insert(self.len);
+        }
+        self.len += 1;
+    }
+
+    /// 索引が長さ len の Vec と揃っていれば Some(位置)、古ければ None
+    pub(crate) fn lookup(&self, key: &K, len: usize) -> Option<Option<usize>> {
+        (self.len == len).then(|| self.positions.get(key).copied())
+    }
+}
+
+/// Bootstrapper::find_rule 用の索引: 単一行動のルールは (condition_id, 行動) で引き、範囲ルールは位置の一覧を走査する
+#[derive(Clone, Debug, Default)]
+struct RuleIndex {
+    singles: PositionIndex<(i32, usize)>,
+    ranges: Vec<usize>,
+}
+
+impl RuleIndex {
+    fn push(&mut self, rule: &HamiltonianRule) {
+        let position = self.singles.len;
+        let key = match (&rule.condition, rule.span) {
+            (None, 1) => Some((rule.condition_id, rule.target_action)),
+            (None, _) => {
+                self.ranges.push(position);
+                None
+            }
+            _ => None,
+        };
+        self.singles.push(key);
+    }
+}
+
+/// ルール集。JSON（to_json / from_json）でファイルに書き出してバージョン管理できる
+/// 例: {"policy": "HighestPriority", "rules": [
+///        {"condition_id": 0, "target_action": 1, "strength": 0.5},
+///        {"condition": {"All": [{"Id": 0}, {"Not": {"Id": 2}}]}, "target_action": 3, "strength": -1.0, "priority": 10}]}
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Bootstrapper {
     pub rules: Vec<HamiltonianRule>,
+    /// ルールが衝突したときの解決方針
+    #[serde(default)]
+    pub policy: RuleConflictPolicy,
+    /// 期限付きルールの寿命（実行時の設定なので JSON には含めない）
+    #[serde(skip)]
+    pub lifecycle: RuleLifecycle,
+    /// 状態から対象の行動を計算するルールテンプレート
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub templates: Vec<RuleTemplate>,
+    #[serde(skip)]
+    index: RuleIndex,
+}
+
+impl PartialEq for Bootstrapper {
+    fn eq(&self, other: &Self) -> bool {
+        self.rules == other.rules && self.templates == other.templates && self.policy == other.policy && self.lifecycle == other.lifecycle
+    }
 }
 
 impl Bootstrapper {
     pub fn new() -> Self {
-        Self { rules: Vec::new() }
+        Self::default()
+    }
+
+    /// condition_id と行動で単一条件のルールを引く（rules の先頭から探したときと同じルールを返す）
+    /// 範囲ルールは action が範囲に入っていれば一致する
+    pub fn find_rule(&self, condition_id: i32, action: usize) -> Option<&HamiltonianRule> {
+        self.find_rule_position(condition_id, action).map(|p| &self.rules[p])
+    }
+
+    /// find_rule で引けるルールの rules 内の位置
+    pub fn find_rule_position(&self, condition_id: i32, action: usize) -> Option<usize> {
+        let matches = |r: &HamiltonianRule| r.condition.is_none() && r.condition_id == condition_id && r.targets(action);
+        let Some(single) = self.index.singles.lookup(&(condition_id, action), self.rules.len()) else {
+            return self.rules.iter().position(matches);
+        };
+        let range = self.index.ranges.iter().copied().find(|&p| matches(&self.rules[p]));
+        let position = match (single, range) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        match position {
+            // 要素が外から差し替えられていたら線形探索に戻る
+            Some(p) if !matches(&self.rules[p]) => self.rules.iter().position(matches),
+            p => p,
+        }
+    }
+
+    /// rules を直接書き換えた後に索引を作り直す
+    pub fn reindex(&mut self) {
+        self.index = RuleIndex::default();
+        for rule in &self.rules {
+            self.index.push(rule);
+        }
+    }
+
+    fn push_rule(&mut self, rule: HamiltonianRule) {
+        if self.index.singles.len == self.rules.len() {
+            self.index.push(&rule);
+            self.rules.push(rule);
+        } else {
+            self.rules.push(rule);
+            self.reindex();
+        }
+    }
+
+    /// 学習 ticks 回分だけルールを老化させる: 減衰を掛け、levels で発動しているルールの使用回数を数え、
+    /// 期限切れの期限付きルールを取り除く。取り除いた数を返す
+    pub fn advance(&mut self, ticks: u64, levels: &[(i32, f32)]) -> usize {
+        for rule in &mut self.rules {
+            rule.age += ticks;
+            if rule.decay != 1.0 {
+                rule.strength *= rule.decay.powi(ticks.min(i32::MAX as u64) as i32);
+            }
+            if rule.activation(levels) > 0.0 {
+                rule.uses += ticks;
+            }
+        }
+        let before = self.rules.len();
+        let lifecycle = self.lifecycle;
+        self.rules.retain(|rule| !lifecycle.is_expired(rule));
+        let removed = before - self.rules.len();
+        if removed > 0 {
+            self.reindex();
+        }
+        removed
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// JSON からルール集を読み込む。condition は単一 ID なら condition_id に正規化し、
+    /// condition_id も condition もないルールはエラーにする
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let mut bootstrapper: Self = serde_json::from_str(json)?;
+        for rule in &mut bootstrapper.rules {
+            if rule.span == 0 {
+                return Err(serde::de::Error::custom("rule span must be at least 1"));
+            }
+            match rule.condition.take() {
+                Some(Condition::Id(id)) => rule.condition_id = id,
+                Some(composite) => {
+                    rule.condition_id = COMPOSITE_CONDITION_ID;
+                    rule.condition = Some(composite);
+                }
+                None if rule.condition_id == COMPOSITE_CONDITION_ID => {
+                    return Err(serde::de::Error::custom("rule needs a condition_id or a condition"));
+                }
+                None => {}
+            }
+        }
+        bootstrapper.reindex();
+        Ok(bootstrapper)
     }
 
     pub fn add_hamiltonian_rule(&mut self, condition_id: i32, target_action: usize, strength: f32) {
-        self.rules.push(HamiltonianRule {
+        self.push_rule(HamiltonianRule {
             condition_id,
             target_action,
+            span: 1,
             strength,
+            condition: None,
+            priority: 0,
+            decay: 1.0,
+            expires: false,
+            age: 0,
+            uses: 0,
+            crystallized: false,
         });
     }
 
     pub fn add_penalty_rule(&mut self, condition_id: i32, target_action: usize, strength: f32) {
-        self.rules.push(HamiltonianRule {
+        self.push_rule(HamiltonianRule {
             condition_id,
             target_action,
+            span: 1,
             strength: -strength.abs(),
+            condition: None,
+            priority: 0,
+            decay: 1.0,
+            expires: false,
+            age: 0,
+            uses: 0,
+            crystallized: false,
         });
     }
 
+    /// 結晶化したルールを追加する（remove_crystallized_rule で、強度が書き換えられていても取り除ける）
+    pub fn add_crystallized_rule(&mut self, condition_id: i32, target_action: usize, strength: f32) {
+        self.add_hamiltonian_rule(condition_id, target_action, strength);
+        if let Some(rule) = self.rules.last_mut() {
+            rule.crystallized = true;
+        }
+    }
+
+    /// add_crystallized_rule で追加したルールを取り除く（取り除いたら true）
+    pub fn remove_crystallized_rule(&mut self, condition_id: i32, target_action: usize) -> bool {
+        let Some(pos) = self.rules.iter()
+            .position(|r| r.crystallized && r.condition_id == condition_id && r.target_action == target_action) else { return false; };
+        self.rules.remove(pos);
+        self.reindex();
+        true
+    }
+
+    /// 期限付きのルールを追加する（減衰率は lifecycle.auto_decay）。observe_expert の自動生成ルール用
+    pub fn add_expiring_rule(&mut self, condition_id: i32, target_action: usize, strength: f32) {
+        self.add_hamiltonian_rule(condition_id, target_action, strength);
+        if let Some(rule) = self.rules.last_mut() {
+            rule.decay = self.lifecycle.auto_decay;
+            rule.expires = true;
+        }
+    }
+
+    /// 条件に関わらず常に発動するルールを追加する（負の strength でその行動を常に避ける）
+    pub fn add_always_rule(&mut self, target_action: usize, strength: f32) {
+        self.add_hamiltonian_rule(ALWAYS_CONDITION_ID, target_action, strength);
+    }
+
+    /// 複合条件で発動するルールを追加する（負の strength でペナルティ場になる）
+    pub fn add_composite_rule(&mut self, condition: Condition, target_action: usize, strength: f32) {
+        self.push_rule(HamiltonianRule {
+            condition_id: COMPOSITE_CONDITION_ID,
+            target_action,
+            span: 1,
+            strength,
+            condition: Some(condition),
+            priority: 0,
+            decay: 1.0,
+            expires: false,
+            age: 0,
+            uses: 0,
+            crystallized: false,
+        });
+    }
+
+    /// 優先度付きのルールを追加する。単一の Condition::Id なら通常のルールと同じく condition_id で登録される
+    pub fn add_prioritized_rule(&mut self, condition: Condition, target_action: usize, strength: f32, priority: i32) {
+        self.add_range_rule(condition, target_action..target_action + 1, strength, priority);
+    }
+
+    /// 連続した行動の範囲をまとめて対象にするルールを追加する（例: 撤退中は攻撃カテゴリー全体を禁止）
+    /// 空の範囲なら何もしない
+    pub fn add_range_rule(&mut self, condition: Condition, actions: Range<usize>, strength: f32, priority: i32) {
+        if actions.is_empty() {
+            return;
+        }
+        let (condition_id, condition) = match condition {
+            Condition::Id(id) => (id, None),
+            composite => (COMPOSITE_CONDITION_ID, Some(composite)),
+        };
+        self.push_rule(HamiltonianRule {
+            condition_id,
+            target_action: actions.start,
+            span: actions.len(),
+            strength,
+            condition,
+            priority,
+            decay: 1.0,
+            expires: false,
+            age: 0,
+            uses: 0,
+            crystallized: false,
+        });
+    }
+
+    /// 条件 condition で target_action（範囲ルールは先頭の行動）を対象にするルールか
+    fn is_rule_for(rule: &HamiltonianRule, condition: &Condition, target_action: usize) -> bool {
+        rule.target_action == target_action
+            && match condition {
+                Condition::Id(id) => rule.condition.is_none() && rule.condition_id == *id,
+                composite => rule.condition.as_ref() == Some(composite),
+            }
+    }
+
+    /// 条件 condition で target_action を対象にするルールをすべて取り除き、取り除いた数を返す
+    /// 範囲ルールは先頭の行動で指定する
+    pub fn remove_rule(&mut self, condition: &Condition, target_action: usize) -> usize {
+        let before = self.rules.len();
+        self.rules.retain(|rule| !Self::is_rule_for(rule, condition, target_action));
+        let removed = before - self.rules.len();
+        if removed > 0 {
+            self.reindex();
+        }
+        removed
+    }
+
+    /// 条件 condition で target_action を対象にするルールの強度を strength に書き換え、書き換えた数を返す
+    pub fn update_strength(&mut self, condition: &Condition, target_action: usize, strength: f32) -> usize {
+        let mut updated = 0;
+        for rule in self.rules.iter_mut().filter(|rule| Self::is_rule_for(rule, condition, target_action)) {
+            rule.strength = strength;
+            updated += 1;
+        }
+        updated
+    }
+
+    /// すべてのルールとテンプレートを取り除く（衝突の解決方針と寿命の設定は残る）
+    pub fn clear(&mut self) {
+        self.rules.clear();
+        self.templates.clear();
+        self.reindex();
+    }
+
+    /// ルール集を波動場の事前分布として一度だけ焼き込む（ウォームスタート）
+    /// singularity の現在の条件レベルで外場を計算し、値のある行動ごとに θ と重力へ偏りをかける
+    /// 実行時の外場（毎回の意思決定で加わる知識項）とは別物で、焼き込んだ偏りはその後の学習で上書きされていく
+    /// 偏りをかけた行動の数を返す
+    pub fn apply_to(&self, singularity: &mut Singularity) -> usize {
+        let field = self.calculate_graded_resonance_field(&singularity.condition_levels, singularity.action_size);
+        let mut biased = 0;
+        for (action, strength) in field.into_iter().enumerate() {
+            let Some(strength) = strength.filter(|&s| s != 0.0) else { continue; };
+            match singularity.sharded_mwso {
+                Some(ref mut sharded) => sharded.bias_action(action, strength),
+                None => singularity.mwso.bias_action(action, singularity.action_size, strength),
+            }
+            biased += 1;
+        }
+        biased
+    }
+
+    /// 条件 condition のとき行動 action(状態) を誘導するテンプレートを追加する
+    pub fn add_template(&mut self, condition: Condition, action: ActionExpr, strength: f32) {
+        self.templates.push(RuleTemplate { condition, action, strength });
+    }
+
+    /// 発動レベル levels・状態 state でテンプレートが行動 action に与える強度の合計（発動度合いで縮める）
+    pub fn template_strength(&self, levels: &[(i32, f32)], state: usize, action: usize) -> f32 {
+        self.templates.iter()
+            .filter(|t| t.target(state, usize::MAX) == Some(action))
+            .map(|t| t.strength * t.condition.level(levels))
+            .sum()
+    }
+
     /// 現在の状況（外部から与えられた条件フラグ群）に基づき、
     /// MWSOの各アクションに対する「外場（Resonance Field）」を計算する
     /// 未定義のアクションに対しては 0.0 ではなく、None に相当する値を返せるようにし、
     /// 知識が「ない」状態と「0である」状態を区別する
     pub fn calculate_resonance_field(&self, active_conditions: &[i32], action_size: usize) -> Vec<Option<f32>> {
-        let mut field = vec![None; action_size];
+        let mut field = Vec::with_capacity(action_size);
+        self.calculate_resonance_field_into(active_conditions, action_size, &mut field);
+        field
+    }
+
+    /// calculate_resonance_field の書き込み先指定版（field は action_size 要素に作り直される）
+    pub fn calculate_resonance_field_into(&self, active_conditions: &[i32], action_size: usize, field: &mut Vec<Option<f32>>) {
+        self.resolve_into(action_size, field, |rule| if rule.is_active(active_conditions) { 1.0 } else { 0.0 });
+    }
+
+    /// 段階的な発動レベル (条件 ID, 0.0〜1.0) から外場を計算する。各ルールの強度は発動度合いで縮める
+    pub fn calculate_graded_resonance_field(&self, levels: &[(i32, f32)], action_size: usize) -> Vec<Option<f32>> {
+        let mut field = Vec::with_capacity(action_size);
+        self.calculate_graded_resonance_field_into(levels, action_size, &mut field);
+        field
+    }
+
+    pub fn calculate_graded_resonance_field_into(&self, levels: &[(i32, f32)], action_size: usize, field: &mut Vec<Option<f32>>) {
+        self.resolve_into(action_size, field, |rule| rule.activation(levels));
+    }
+
+    /// 発動レベル levels に対する外場を category_sizes のカテゴリーごとに分けて返す
+    /// i 番目の要素は category_sizes[i] 個で、添字はカテゴリー内の行動番号
+    pub fn calculate_category_fields(&self, levels: &[(i32, f32)], category_sizes: &[usize]) -> Vec<Vec<Option<f32>>> {
+        let field = self.calculate_graded_resonance_field(levels, category_sizes.iter().sum());
+        let mut offset = 0;
+        category_sizes.iter()
+            .map(|&size| {
+                let part = field[offset..offset + size].to_vec();
+                offset += size;
+                part
+            })
+            .collect()
+    }
+
+    /// calculate_graded_resonance_field の内訳: 衝突の解決方針で採用されたルールごとの寄与（強度 × 発動度合い）を
+    /// rules の順に out へ書き込む（out は作り直される）。同じ行動への寄与を足すと外場の値になる
+    pub fn rule_activations_into(&self, levels: &[(i32, f32)], action_size: usize, out: &mut Vec<RuleActivation>) {
+        out.clear();
+        let fired = || self.rules.iter().enumerate().filter_map(|(i, rule)| {
+            let end = rule.target_action.saturating_add(rule.span).min(action_size);
+            let level = rule.activation(levels);
+            (rule.target_action < end && level > 0.0).then_some((i, rule, rule.target_action..end, rule.strength * level))
+        });
+        // 行動ごとの採用基準: HighestPriority は最高の優先度、MaxMagnitude は最初に現れた絶対値最大のルール
+        let mut top_priority = Vec::new();
+        let mut strongest = Vec::new();
+        match self.policy {
+            RuleConflictPolicy::Sum => {}
+            RuleConflictPolicy::HighestPriority => {
+                top_priority = vec![i32::MIN; action_size];
+                for (_, rule, actions, _) in fired() {
+                    for action in actions {
+                        top_priority[action] = top_priority[action].max(rule.priority);
+                    }
+                }
+            }
+            RuleConflictPolicy::MaxMagnitude => {
+                strongest = vec![(usize::MAX, -1.0f32); action_size];
+                for (i, _, actions, strength) in fired() {
+                    for action in actions {
+                        if strength.abs() > strongest[action].1 {
+                            strongest[action] = (i, strength.abs());
+                        }
+                    }
+                }
+            }
+        }
+        for (i, rule, actions, strength) in fired() {
+            for action in actions {
+                let adopted = match self.policy {
+                    RuleConflictPolicy::Sum => true,
+                    RuleConflictPolicy::HighestPriority => top_priority[action] == rule.priority,
+                    RuleConflictPolicy::MaxMagnitude => strongest[action].0 == i,
+                };
+                if adopted {
+                    out.push(RuleActivation { rule: i, action, contribution: strength });
+                }
+            }
+        }
+    }
+
+    /// 発動度合い (0 なら不発) で強度を縮めたルールを、衝突の解決方針に従って行動ごとに合成する
+    fn resolve_into(&self, action_size: usize, field: &mut Vec<Option<f32>>, activation: impl Fn(&HamiltonianRule) -> f32) {
+        field.clear();
+        field.resize(action_size, None);
+        // HighestPriority のときだけ、行動ごとに採用中の優先度を覚えておく
+        let mut priorities = match self.policy {
+            RuleConflictPolicy::HighestPriority => vec![i32::MIN; action_size],
+            _ => Vec::new(),
+        };
         for rule in &self.rules {
-            if active_conditions.contains(&rule.condition_id) {
-                if rule.target_action < action_size {
-                    let current = field[rule.target_action].unwrap_or(0.0);
-                    field[rule.target_action] = Some(current + rule.strength);
+            let end = rule.target_action.saturating_add(rule.span).min(action_size);
+            if rule.target_action >= end {
+                continue;
+            }
+            let level = activation(rule);
+            if level <= 0.0 {
+                continue;
+            }
+            let strength = rule.strength * level;
+            for action in rule.target_action..end {
+                let slot = &mut field[action];
+                match self.policy {
+                    RuleConflictPolicy::Sum => *slot = Some(slot.unwrap_or(0.0) + strength),
+                    RuleConflictPolicy::HighestPriority => {
+                        let current = &mut priorities[action];
+                        if rule.priority > *current {
+                            *current = rule.priority;
+                            *slot = Some(strength);
+                        } else if rule.priority == *current {
+                            *slot = Some(slot.unwrap_or(0.0) + strength);
+                        }
+                    }
+                    RuleConflictPolicy::MaxMagnitude => {
+                        if slot.is_none_or(|s| strength.abs() > s.abs()) {
+                            *slot = Some(strength);
+                        }
+                    }
                 }
             }
         }
-        field
     }
 }
@@ -0,0 +1,194 @@
+// src/core/learn_queue.rs
+use super::config::LearnQueuePolicy;
+use super::singularity::{Experience, Singularity, VectorExperience};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+
+/// 遅延実行される1回分の学習（報酬と、その時点までの意思決定履歴）
+pub struct PendingLearn {
+    pub reward: f32,
+    pub history: VecDeque<Experience>,
+    pub vector_history: VecDeque<VectorExperience>,
+    pub last_actions: Vec<usize>,
+}
+
+/// 学習キューの診断情報
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LearnQueueStats {
+    pub depth: usize,
+    pub max_depth: usize,
+    /// Block ポリシーで呼び出し側が待たされた回数（LearnWorker が動いていなければ、空きを作るために同期実行した件数）
+    pub blocked: u64,
+    pub dropped: u64,
+    pub coalesced: u64,
+    pub processed: u64,
+}
+
+#[derive(Default)]
+pub struct LearnQueue {
+    pub pending: VecDeque<PendingLearn>,
+    pub stats: LearnQueueStats,
+}
+
+impl LearnQueue {
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    pub fn push(&mut self, item: PendingLearn) {
+        self.pending.push_back(item);
+        self.refresh_depth();
+    }
+
+    pub fn pop(&mut self) -> Option<PendingLearn> {
+        let item = self.pending.pop_front();
+        self.refresh_depth();
+        item
+    }
+
+    /// 新しい学習を最新のエントリへ合算する（エントリがなければ false）
+    /// 報酬は足し合わせる（合算した各学習の報酬の和を 1 回で与える）
+    pub fn coalesce(&mut self, item: PendingLearn, max_history: usize) -> bool {
+        let Some(last) = self.pending.back_mut() else { return false; };
+        last.reward += item.reward;
+        last.history.extend(item.history);
+        while last.history.len() > max_history { last.history.pop_front(); }
+        last.vector_history.extend(item.vector_history);
+        while last.vector_history.len() > max_history { last.vector_history.pop_front(); }
+        last.last_actions = item.last_actions;
+        self.stats.coalesced += 1;
+        true
+    }
+
+    fn refresh_depth(&mut self) {
+        self.stats.depth = self.pending.len();
+        self.stats.max_depth = self.stats.max_depth.max(self.stats.depth);
+    }
+}
+
+/// 学習キューを別スレッドで消化するワーカーと、意思決定側とで共有するエージェント
+/// 意思決定側は lock で取り出して使い、学習は queue_learn で積む。ワーカーは 1 件学習するごとにロックを手放す
+/// ワーカーが動いている間の Block ポリシーは、同期実行ではなくワーカーが空きを作るのを待つ
+pub struct LearnWorker {
+    shared: Arc<WorkerShared>,
+    /// start / stop をどのスレッドから呼んでもよいように、スレッドのハンドルも同期する
+    thread: Mutex<Option<JoinHandle<()>>>,
+}
+
+struct WorkerShared {
+    agent: Mutex<Singularity>,
+    /// キューへの積み込み・消化・停止のたびに通知する
+    changed: Condvar,
+    running: AtomicBool,
+}
+
+impl WorkerShared {
+    fn lock(&self) -> MutexGuard<'_, Singularity> {
+        // 学習中のパニックでポイズニングされても、エージェント自体は使い続けられる
+        self.agent.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn wait_while<'a>(&self, guard: MutexGuard<'a, Singularity>, condition: impl FnMut(&mut Singularity) -> bool) -> MutexGuard<'a, Singularity> {
+        self.changed.wait_while(guard, condition).unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    fn run(&self) {
+        let mut agent = self.lock();
+        while self.is_running() {
+            if agent.learn_queue.is_empty() {
+                agent = self.wait_while(agent, |a| a.learn_queue.is_empty() && self.is_running());
+                continue;
+            }
+            agent.drain_learn_queue(1);
+            self.changed.notify_all();
+            // 1 件ごとに意思決定側へロックを譲る
+            drop(agent);
+            std::thread::yield_now();
+            agent = self.lock();
+        }
+    }
+}
+
+impl LearnWorker {
+    /// ワーカーはまだ動かさない（start で開始する）
+    pub fn new(agent: Singularity) -> Self {
+        let shared = WorkerShared { agent: Mutex::new(agent), changed: Condvar::new(), running: AtomicBool::new(false) };
+        Self { shared: Arc::new(shared), thread: Mutex::new(None) }
+    }
+
+    /// 学習スレッドを開始する（動いていれば何もしない）
+    pub fn start(&self) {
+        let mut thread = self.thread.lock().unwrap_or_else(|e| e.into_inner());
+        if thread.is_some() { return; }
+        self.shared.running.store(true, Ordering::SeqCst);
+        let shared = Arc::clone(&self.shared);
+        *thread = Some(std::thread::spawn(move || shared.run()));
+    }
+
+    /// 学習スレッドを止める（処理中の 1 件は終わるまで待つ。残りはキューに残る）
+    pub fn stop(&self) {
+        let mut thread = self.thread.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(handle) = thread.take() else { return; };
+        {
+            let _agent = self.shared.lock();
+            self.shared.running.store(false, Ordering::SeqCst);
+            self.shared.changed.notify_all();
+        }
+        let _ = handle.join();
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.shared.is_running()
+    }
+
+    /// エージェントをロックする（ワーカーが学習中なら、その 1 件が終わるまで待つ）
+    pub fn lock(&self) -> MutexGuard<'_, Singularity> {
+        self.shared.lock()
+    }
+
+    /// Singularity::queue_learn と同じだが、ワーカーが動いていて Block ポリシーでキューが満杯なら、空きができるまで待つ
+    pub fn queue_learn(&self, reward: f32) {
+        let mut agent = self.lock();
+        let capacity = agent.config.learn_queue_capacity.max(1);
+        if self.shared.is_running() && agent.config.learn_queue_policy == LearnQueuePolicy::Block && agent.learn_queue.len() >= capacity {
+            agent.learn_queue.stats.blocked += 1;
+            agent = self.shared.wait_while(agent, |a| a.learn_queue.len() >= capacity && self.shared.is_running());
+        }
+        agent.queue_learn(reward);
+        self.shared.changed.notify_all();
+    }
+
+    /// キューが空になるまで待つ（ワーカーが動いていなければ、その場ですべて学習する）
+    pub fn wait_idle(&self) {
+        let agent = self.lock();
+        let mut agent = self.shared.wait_while(agent, |a| !a.learn_queue.is_empty() && self.shared.is_running());
+        agent.drain_learn_queue(usize::MAX);
+    }
+
+    /// ワーカーを止めてエージェントを取り出す（未処理の学習はキューに残る）
+    pub fn into_inner(self) -> Singularity {
+        let shared = Arc::clone(&self.shared);
+        // drop でスレッドを止めて合流させるので、共有しているのは shared だけになる
+        drop(self);
+        match Arc::try_unwrap(shared) {
+            Ok(shared) => shared.agent.into_inner().unwrap_or_else(|e| e.into_inner()),
+            Err(_) => unreachable!("the learn worker thread has been joined"),
+        }
+    }
+}
+
+impl Drop for LearnWorker {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -0,0 +1,84 @@
+// src/core/memory_bank.rs
+// ラベル付きの複数の記憶波バンク（エピソード記憶）
+// アクティブなバンクの記憶波は MWSO 本体の q_memory_* / q_topo_* が持ち、切り替え時にベクトルごと入れ替える
+
+/// Resonance ルーティングで既知のエピソードとみなす共鳴の下限
+pub const BANK_NOVELTY_THRESHOLD: f64 = 0.3;
+
+/// 記憶波バンク
+/// アクティブなバンクのベクトルは MWSO 本体に移っているため、ここでは空になっている
+#[derive(Clone, Debug, Default)]
+pub struct MemoryBank {
+    pub label: String,
+    /// このバンクへの刻み込み回数
+    pub imprints: u64,
+    pub(crate) q_memory_re: Vec<f64>,
+    pub(crate) q_memory_im: Vec<f64>,
+    pub(crate) q_topo_re: Vec<f64>,
+    pub(crate) q_topo_im: Vec<f64>,
+}
+
+impl MemoryBank {
+    /// 記憶の空なバンク
+    pub fn new(label: &str, dim: usize) -> Self {
+        Self {
+            label: label.to_string(),
+            imprints: 0,
+            q_memory_re: vec![0.0; dim],
+            q_memory_im: vec![0.0; dim],
+            q_topo_re: vec![0.0; dim],
+            q_topo_im: vec![0.0; dim],
+        }
+    }
+
+    /// 記憶波を MWSO 本体に置いたままのバンク（アクティブなバンク用）
+    pub(crate) fn detached(label: &str) -> Self {
+        Self { label: label.to_string(), ..Default::default() }
+    }
+}
+
+/// imprint_memory でラベルを付けた記憶の索引（ラベル → 刻み込んだバンクと鍵）
+/// 鍵は入力番号から決まる署名の位相なので、入力番号を覚えておけば問い合わせを作り直せる
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemoryTag {
+    /// 刻み込み先のバンク
+    pub bank: usize,
+    /// 署名（鍵）の入力番号
+    pub input_idx: usize,
+    /// このラベルで刻み込んだ回数
+    pub imprints: u32,
+    pub last_reward: f32,
+}
+
+/// 刻み込み先のバンクの選び方
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BankRoutingPolicy {
+    /// アクティブなバンクへ刻む（切り替えは呼び出し側が行う）
+    #[default]
+    Active,
+    /// 状態番号でバンクを固定する（state % バンク数）
+    ByState,
+    /// 現在の波と最も共鳴するバンクへ刻む
+    /// どのバンクとも BANK_NOVELTY_THRESHOLD 未満なら、刻み込み回数の最も少ないバンクへ
+    Resonance,
+}
+
+impl BankRoutingPolicy {
+    /// JNI などからの整数指定 (0=Active, 1=ByState, 2=Resonance)
+    pub fn from_id(id: i32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Active),
+            1 => Some(Self::ByState),
+            2 => Some(Self::Resonance),
+            _ => None,
+        }
+    }
+
+    pub fn id(self) -> i32 {
+        match self {
+            Self::Active => 0,
+            Self::ByState => 1,
+            Self::Resonance => 2,
+        }
+    }
+}
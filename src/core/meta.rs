@@ -0,0 +1,126 @@
+// src/core/meta.rs
+
+/// 自己調整対象のメタパラメータ（探索ノイズ・スミア深さ・Softmax 温度係数）
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MetaParams {
+    /// 波動場へ毎ステップ注入する探索ノイズの強度
+    pub exploration_noise: f32,
+    /// 過去状態の重畳注入 (Temporal Smearing) の減衰率
+    pub smear_decay: f32,
+    /// Top-k Softmax の逆温度係数（大きいほど貪欲）
+    pub softmax_scale: f32,
+}
+
+impl Default for MetaParams {
+    fn default() -> Self {
+        Self {
+            exploration_noise: 0.0,
+            smear_decay: 0.6,
+            softmax_scale: 2.0,
+        }
+    }
+}
+
+/// メタパラメータの組を腕とするバンディット (UCB1)
+/// 一定ステップごとに腕の報酬を評価し、成績の良い設定へ自動的に寄せていく
+#[derive(Clone)]
+pub struct MetaController {
+    pub arms: Vec<MetaParams>,
+    /// 腕ごとの報酬の移動平均
+    pub values: Vec<f32>,
+    pub pulls: Vec<u32>,
+    pub current_arm: usize,
+    /// 1つの腕を評価する学習ステップ数
+    pub evaluation_window: usize,
+    /// 移動平均の更新率（古い評価を忘れ、環境変化に追従する）
+    pub rolling_rate: f32,
+    /// UCB の探索係数
+    pub exploration_bonus: f32,
+    steps_in_arm: usize,
+    reward_sum: f32,
+    total_pulls: u32,
+}
+
+impl MetaController {
+    pub fn new(arms: Vec<MetaParams>) -> Self {
+        let arms = if arms.is_empty() { vec![MetaParams::default()] } else { arms };
+        let n = arms.len();
+        Self {
+            arms,
+            values: vec![0.0; n],
+            pulls: vec![0; n],
+            current_arm: 0,
+            evaluation_window: 20,
+            rolling_rate: 0.3,
+            exploration_bonus: 0.5,
+            steps_in_arm: 0,
+            reward_sum: 0.0,
+            total_pulls: 0,
+        }
+    }
+
+    /// 既定値を中心にした小さな腕の集合
+    pub fn with_default_arms() -> Self {
+        let base = MetaParams::default();
+        Self::new(vec![
+            base,
+            MetaParams { exploration_noise: 0.05, softmax_scale: 1.0, ..base },
+            MetaParams { softmax_scale: 4.0, ..base },
+            MetaParams { smear_decay: 0.8, ..base },
+            MetaParams { exploration_noise: 0.02, smear_decay: 0.4, ..base },
+        ])
+    }
+
+    pub fn current(&self) -> MetaParams {
+        self.arms[self.current_arm]
+    }
+
+    /// 報酬を1件記録する。評価ウィンドウが満ちたら腕を切り替え、切り替えた場合 true を返す
+    pub fn record(&mut self, reward: f32) -> bool {
+        self.reward_sum += reward;
+        self.steps_in_arm += 1;
+        if self.steps_in_arm < self.evaluation_window.max(1) { return false; }
+
+        let mean = self.reward_sum / self.steps_in_arm as f32;
+        let arm = self.current_arm;
+        self.values[arm] = if self.pulls[arm] == 0 {
+            mean
+        } else {
+            self.values[arm] + self.rolling_rate * (mean - self.values[arm])
+        };
+        self.pulls[arm] += 1;
+        self.total_pulls += 1;
+        self.steps_in_arm = 0;
+        self.reward_sum = 0.0;
+
+        let next = self.select_arm();
+        let switched = next != self.current_arm;
+        self.current_arm = next;
+        switched
+    }
+
+    /// 未評価の腕を優先し、その後は UCB1 で選択する
+    fn select_arm(&self) -> usize {
+        if let Some(untried) = self.pulls.iter().position(|&p| p == 0) {
+            return untried;
+        }
+        let ln_total = (self.total_pulls as f32).ln();
+        let mut best = 0;
+        let mut best_ucb = f32::NEG_INFINITY;
+        for (i, (&v, &p)) in self.values.iter().zip(&self.pulls).enumerate() {
+            let ucb = v + self.exploration_bonus * (2.0 * ln_total / p as f32).sqrt();
+            if ucb > best_ucb { best_ucb = ucb; best = i; }
+        }
+        best
+    }
+
+    /// 評価済みの中で最も成績の良い腕
+    pub fn best_arm(&self) -> usize {
+        let mut best = self.current_arm;
+        let mut best_v = f32::NEG_INFINITY;
+        for (i, (&v, &p)) in self.values.iter().zip(&self.pulls).enumerate() {
+            if p > 0 && v > best_v { best_v = v; best = i; }
+        }
+        best
+    }
+}
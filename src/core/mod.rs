@@ -4,3 +4,33 @@ pub mod math;
 pub mod knowledge;
 pub mod mwso;
 pub mod visualizer;
+pub mod hierarchy;
+pub mod opponent;
+pub mod dsym;
+pub mod meta;
+pub mod symmetry;
+pub mod config;
+pub mod learn_queue;
+pub mod score_cache;
+pub mod shadow;
+pub mod archive;
+pub mod encoder;
+pub mod episode;
+pub mod selfplay;
+pub mod stats;
+pub mod par;
+pub mod wave;
+pub mod memory_bank;
+pub mod noise;
+pub mod state_code;
+pub mod action_layout;
+pub mod replay;
+pub mod wave_view;
+pub mod spectrum;
+pub mod wave_delta;
+pub mod quantized;
+pub mod bench;
+pub mod field_schedule;
+pub mod sensor;
+pub mod neuromod;
+pub mod personality;
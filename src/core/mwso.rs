@@ -3,14 +3,149 @@
 
 use std::collections::HashMap;
 use std::f32::consts::PI;
+use super::action_layout::ActionLayout;
+use super::config::{Coupling, Integrator, MWSOConfig};
+use super::field_schedule::{FieldDecay, FieldScheduler};
+use super::memory_bank::{BANK_NOVELTY_THRESHOLD, BankRoutingPolicy, MemoryBank, MemoryTag};
+use super::noise::{self, ExplorationNoise, NoiseGenerator};
+use super::quantized::{FieldPrecision, QuantizedRows};
+use super::replay::ReplayBuffer;
+use super::spectrum::Spectrum;
+use super::state_code::{STATE_CODE_BINS, StateCodeTable, StateCoding};
+use super::par;
+use super::wave::{self, Wave, from_wave, to_wave, wave_from_f64, wave_to_f64};
+use super::wave_delta;
+use super::wave_view::{WaveFrame, WavePublisher, WaveView};
+
+/// step_core のチャンク幅（f32x8 = 256bit レジスタ1本分）
+/// 固定長配列の要素ごとの演算はコンパイラによって SIMD 命令へ展開される
+const LANES: usize = 8;
+
+/// 既定のワームホール数の上限（超えたら最も弱い結合から追い出す）
+pub const DEFAULT_WORMHOLE_CAP: usize = 4096;
+/// decay_wormholes でこの強度を下回った結合は取り除く
+const WORMHOLE_MIN_STRENGTH: f32 = 0.01;
+
+/// 線形合同法で seed を進め、[0, 1] の一様乱数を返す
+fn lcg_next(seed: &mut u64) -> f32 {
+    *seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+    ((*seed >> 32) as u32) as f32 / u32::MAX as f32
+}
+
+/// 重力場の要約
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GravityStats {
+    pub mean: f32,
+    pub max: f32,
+    /// 閾値を超えたビンの数
+    pub above_threshold: usize,
+}
+
+impl GravityStats {
+    /// 複数の場（シャード）の要約を、ビン数で重み付けしてまとめる
+    fn merge(parts: impl Iterator<Item = (GravityStats, usize)>) -> Self {
+        let (mut stats, mut bins) = (GravityStats::default(), 0usize);
+        for (part, n) in parts {
+            stats.mean += part.mean * n as f32;
+            stats.max = stats.max.max(part.max);
+            stats.above_threshold += part.above_threshold;
+            bins += n;
+        }
+        if bins > 0 { stats.mean /= bins as f32; }
+        stats
+    }
+}
+
+/// 正の報酬で刻み込むときの記憶波の忘却率
+const BASE_FORGET_RATE: f64 = 0.008;
+
+/// 刻み込みのたびに記憶波のノルムをこの値へ揃える
+const Q_MEMORY_NORM: f64 = 5.0;
+
+/// 忘却曲線による記憶波の減衰は、この回数の step_core ごとにまとめて適用する
+const FORGET_BATCH: u32 = 16;
+
+/// gravity_saturation でこの重力以上のビンを飽和とみなす（重力の上限は 1.0）
+pub const GRAVITY_SATURATION_LEVEL: f32 = 0.9;
+
+/// 波の状態の診断値（テレメトリやダッシュボード表示用）
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WaveDiagnostics {
+    /// 波の L2 ノルム
+    pub norm: f32,
+    /// 各ビン（固有周波数を持つモード）へのエネルギー分布の正規化エントロピー (0: 一点集中, 1: 一様)
+    pub spectral_entropy: f32,
+    /// エネルギーが最大のビン
+    pub dominant_bin: usize,
+    /// dominant_bin の固有周波数
+    pub dominant_frequency: f32,
+    /// 現在の波とアクティブな記憶波の重なりの大きさ |<q_memory|psi>| / (|q_memory| |psi|) (0.0〜1.0)
+    pub memory_resonance: f32,
+    /// 重力が GRAVITY_SATURATION_LEVEL 以上のビンの割合
+    pub gravity_saturation: f32,
+    pub entanglement_count: usize,
+}
+
+/// 記憶波への問い合わせ結果
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RecallResult {
+    /// 想起したパターンと現在の波との一致度 (-1..1)
+    pub resonance: f32,
+    /// 現在の波と逆位相のビンを落とした想起パターン
+    pub pattern_re: Vec<f32>,
+    pub pattern_im: Vec<f32>,
+}
+
+impl RecallResult {
+    /// bins に含まれる想起パターンのエネルギー
+    pub fn energy(&self, bins: std::ops::Range<usize>) -> f32 {
+        bins.filter_map(|i| Some(self.pattern_re.get(i)?.powi(2) + self.pattern_im.get(i)?.powi(2))).sum()
+    }
+}
 
+/// step_core の作業領域（毎ステップの再確保を避けるためのもので、状態ではない）
+#[derive(Clone, Default)]
+struct StepScratch {
+    recall_re: Vec<Wave>,
+    recall_im: Vec<Wave>,
+    /// 位相回転の sin / cos（ステップごとに一括で求める）
+    phase_sin: Vec<Wave>,
+    phase_cos: Vec<Wave>,
+    /// 粘性による減衰係数
+    damping: Vec<Wave>,
+    /// 回転・想起ブースト適用後、結合項を足す前の実部
+    rotated_re: Vec<Wave>,
+    /// 蔵本型結合で使う、結合前の各ビンの位相
+    phase: Vec<Wave>,
+    /// エネルギー地形に加える熱ゆらぎ用の乱数列
+    noise: Vec<f32>,
+    /// 4 のゲート係数（エネルギーの集計時に求め、正規化の書き込みで使い回す）
+    gate: Vec<Wave>,
+}
+
+impl StepScratch {
+    fn resize(&mut self, dim: usize) {
+        for buf in [&mut self.recall_re, &mut self.recall_im, &mut self.phase_sin, &mut self.phase_cos,
+                    &mut self.damping, &mut self.rotated_re, &mut self.phase, &mut self.gate] {
+            buf.resize(dim, 0.0);
+        }
+        self.noise.resize(dim, 0.0);
+    }
+}
+
+#[derive(Clone)]
 pub struct MWSO {
-    pub psi_real: Vec<f32>,
-    pub psi_imag: Vec<f32>,
-    pub theta: Vec<f32>,
+    /// アクティブな波（精度は wave::Wave、既定 f32）
+    pub psi_real: Vec<Wave>,
+    pub psi_imag: Vec<Wave>,
+    pub theta: Vec<Wave>,
     pub frequencies: Vec<f32>,
+    /// 重力場（set_gravity_precision で量子化保持に切り替えると空になり、compact_gravity が持つ）
     pub gravity_field: Vec<f32>, 
+    compact_gravity: Option<QuantizedRows>,
     pub entanglements: Vec<(usize, usize, f32)>, 
+    /// entanglements の上限
+    pub wormhole_cap: usize,
     
     // --- PP-CEL: Pure-Phase Correlated Energy Landscape ---
     pub q_memory_re: Vec<f64>,
@@ -25,15 +160,51 @@ pub struct MWSO {
     
     pub dim: usize,
     pub rng_seed: u64,
+    /// adapt の学習率倍率（予測への信頼度が低いときに慎重に学習する）
+    pub lr_scale: f32,
+    /// 記憶波バンク（index がバンク番号。アクティブなバンクの記憶波は q_memory_* / q_topo_* にある）
+    memory_banks: Vec<MemoryBank>,
+    active_bank: usize,
+    pub bank_routing: BankRoutingPolicy,
+    /// ラベル付きの刻み込みの索引
+    memory_tags: HashMap<String, MemoryTag>,
+    /// 探索ノイズの生成器（分布と、OU・ピンクノイズの tick をまたぐ状態）
+    exploration: NoiseGenerator,
+    /// inject_state の状態コード（None なら素数ストライド）
+    state_codes: Option<StateCodeTable>,
+    /// 行動 → ビン範囲（None なら等分）
+    action_layout: Option<ActionLayout>,
+    /// 記憶の統合で再刻み込みするパターン（容量 0 なら記録しない）
+    pub replay: ReplayBuffer,
+    /// 忘却曲線の半減期（step_core の回数）。この回数で刻み込んだ記憶の強さが半分になる（0 で無効）
+    pub memory_half_life: f32,
+    /// 振動子の時間発展に使う積分法
+    pub integrator: Integrator,
+    /// 隣接ビン間の結合項
+    pub coupling: Coupling,
+    /// step_core の先頭で自動的に注入される外部場の予約表
+    pub field_schedule: FieldScheduler,
+    /// 重力場の形成・蒸発のパラメータ
+    pub config: MWSOConfig,
+    /// 行動スコアに加える、現在の波と記憶波の干渉項の重み（0 で波だけを読む従来のスコア）
+    pub interference_weight: f32,
+    /// 0 より大きく行動数未満なら、帯域エネルギーによる粗い推定で上位 score_top_k 件を選び、それだけを正確に採点する
+    pub score_top_k: usize,
+    /// 上位候補の選別用（行動番号, 推定スコア）
+    top_k_scratch: Vec<(usize, f32)>,
+    /// 前回の忘却の適用から経過した step_core の回数
+    forget_steps: u32,
+    /// 他スレッドの読み手へ波のスナップショットを公開する（wave_view を呼ぶまでは無効）
+    publisher: WavePublisher,
+    scratch: StepScratch,
 }
 
 impl MWSO {
     pub fn new(dim: usize) -> Self {
         let theta_size = dim * 2;
         let mut theta = vec![0.0; theta_size];
-        let mut frequencies = vec![0.0; dim];
-        for i in 0..theta_size { theta[i] = (i as f32 * 0.1).sin() * 0.1; }
-        for i in 0..dim { frequencies[i] = (i as f32 / dim as f32).powi(2) * 2.0 * PI; }
+        for i in 0..theta_size { theta[i] = (i as Wave * 0.1).sin() * 0.1; }
+        let frequencies = Spectrum::default().frequencies(dim);
         
         let mut scramble_phases = vec![0.0; dim];
         for i in 0..dim {
@@ -62,6 +233,8 @@ impl MWSO {
             theta, 
             frequencies, 
             gravity_field: vec![0.0; dim],
+            compact_gravity: None,
+            wormhole_cap: DEFAULT_WORMHOLE_CAP.max(entanglements.len()),
             entanglements,
             q_memory_re: vec![0.0; dim],
             q_memory_im: vec![0.0; dim],
@@ -73,25 +246,310 @@ impl MWSO {
             scramble_phases,
             dim,
             rng_seed: 0xDEADBEEF,
+            lr_scale: 1.0,
+            memory_banks: vec![MemoryBank::detached("default")],
+            active_bank: 0,
+            bank_routing: BankRoutingPolicy::Active,
+            memory_tags: HashMap::new(),
+            exploration: NoiseGenerator::new(ExplorationNoise::Uniform),
+            state_codes: None,
+            action_layout: None,
+            replay: ReplayBuffer::default(),
+            memory_half_life: 0.0,
+            integrator: Integrator::default(),
+            coupling: Coupling::default(),
+            field_schedule: FieldScheduler::default(),
+            config: MWSOConfig::default(),
+            interference_weight: 0.0,
+            score_top_k: 0,
+            top_k_scratch: Vec::new(),
+            forget_steps: 0,
+            publisher: WavePublisher::default(),
+            scratch: StepScratch::default(),
+        }
+    }
+
+    /// 既存のバッファを再利用して他の MWSO の状態を丸ごと写す（ロールバック用）
+    pub fn copy_from(&mut self, other: &MWSO) {
+        self.psi_real.clone_from(&other.psi_real);
+        self.psi_imag.clone_from(&other.psi_imag);
+        self.theta.clone_from(&other.theta);
+        self.frequencies.clone_from(&other.frequencies);
+        self.gravity_field.clone_from(&other.gravity_field);
+        self.compact_gravity.clone_from(&other.compact_gravity);
+        self.entanglements.clone_from(&other.entanglements);
+        self.wormhole_cap = other.wormhole_cap;
+        self.q_memory_re.clone_from(&other.q_memory_re);
+        self.q_memory_im.clone_from(&other.q_memory_im);
+        self.q_topo_re.clone_from(&other.q_topo_re);
+        self.q_topo_im.clone_from(&other.q_topo_im);
+        self.energy_landscape.clone_from(&other.energy_landscape);
+        self.input_signature_re.clone_from(&other.input_signature_re);
+        self.input_signature_im.clone_from(&other.input_signature_im);
+        self.scramble_phases.clone_from(&other.scramble_phases);
+        self.dim = other.dim;
+        self.rng_seed = other.rng_seed;
+        self.lr_scale = other.lr_scale;
+        self.memory_banks.clone_from(&other.memory_banks);
+        self.active_bank = other.active_bank;
+        self.bank_routing = other.bank_routing;
+        self.memory_tags.clone_from(&other.memory_tags);
+        self.exploration.clone_from(&other.exploration);
+        self.state_codes.clone_from(&other.state_codes);
+        self.action_layout.clone_from(&other.action_layout);
+        self.replay.clone_from(&other.replay);
+        self.memory_half_life = other.memory_half_life;
+        self.integrator = other.integrator;
+        self.coupling = other.coupling;
+        self.field_schedule.clone_from(&other.field_schedule);
+        self.config = other.config;
+        self.interference_weight = other.interference_weight;
+        self.score_top_k = other.score_top_k;
+        self.forget_steps = other.forget_steps;
+    }
+
+    /// 固有振動数の並べ方を spectrum にした MWSO を作る（new は Spectrum::Quadratic）
+    pub fn with_spectrum(dim: usize, spectrum: Spectrum) -> Self {
+        let mut mwso = Self::new(dim);
+        mwso.set_spectrum(spectrum);
+        mwso
+    }
+
+    /// 固有振動数を任意の配列で与えて MWSO を作る（次元は frequencies.len()）
+    pub fn with_frequencies(frequencies: &[f32]) -> Self {
+        let mut mwso = Self::new(frequencies.len());
+        mwso.frequencies.copy_from_slice(frequencies);
+        mwso
+    }
+
+    /// 固有振動数をプリセットで置き換える（adapt による学習済みのずれも消える）
+    pub fn set_spectrum(&mut self, spectrum: Spectrum) {
+        self.frequencies = spectrum.frequencies(self.dim);
+    }
+
+    /// 学習された位相結合構造 theta（前半がビンごとの位相、後半が補助位相、計 2·dim 要素）を書き出す
+    pub fn export_theta(&self) -> Vec<f32> {
+        self.theta.iter().map(|&t| from_wave(t)).collect()
+    }
+
+    /// export_theta で書き出した theta を読み込む（他のエージェントからの移植用）
+    /// 次元の違うエージェントのものは前半・後半をそれぞれ環状に補間して写す
+    /// 長さが奇数・空・有限でない値を含む場合は何もせず false を返す
+    pub fn import_theta(&mut self, theta: &[f32]) -> bool {
+        if theta.is_empty() || !theta.len().is_multiple_of(2) || theta.iter().any(|t| !t.is_finite()) { return false; }
+        let resample = |half: &[f32]| -> Vec<Wave> {
+            let src: Vec<f64> = half.iter().map(|&x| x as f64).collect();
+            resample_ring(&src, self.dim).into_iter().map(wave_from_f64).collect()
+        };
+        let (lo, hi) = theta.split_at(theta.len() / 2);
+        let mut imported = resample(lo);
+        imported.extend(resample(hi));
+        self.theta = imported;
+        true
+    }
+
+    /// 次元を new_dim に変える（行動空間の拡張や、次元の違うモデルの統合用）
+    /// 波・位相・重力などビンごとの場は環状に補間（縮小時は区間平均）し、ビンあたりの振幅を保つ
+    /// 記憶波は入力署名の位相を外してから補間し、新しい次元の署名で掛け直す（コードの再射影）
+    /// ワームホールは両端のビンを比例的に写し、重複と自己結合は取り除く
+    pub fn resize(&mut self, new_dim: usize) {
+        let old_dim = self.dim;
+        if new_dim == old_dim || new_dim == 0 || old_dim == 0 { return; }
+
+        let resample_wave = |v: &[Wave]| -> Vec<Wave> {
+            let src: Vec<f64> = v.iter().map(|&x| wave_to_f64(x)).collect();
+            resample_ring(&src, new_dim).into_iter().map(wave_from_f64).collect()
+        };
+        let resample_f32 = |v: &[f32]| -> Vec<f32> {
+            let src: Vec<f64> = v.iter().map(|&x| x as f64).collect();
+            resample_ring(&src, new_dim).into_iter().map(|x| x as f32).collect()
+        };
+
+        self.psi_real = resample_wave(&self.psi_real);
+        self.psi_imag = resample_wave(&self.psi_imag);
+        // theta は前半がビンごとの位相、後半がビンごとの補助位相
+        let (theta_lo, theta_hi) = self.theta.split_at(old_dim.min(self.theta.len()));
+        let mut theta = resample_wave(theta_lo);
+        theta.extend(if theta_hi.len() == old_dim { resample_wave(theta_hi) } else { vec![0.0; new_dim] });
+        self.theta = theta;
+        self.frequencies = resample_f32(&self.frequencies);
+        self.gravity_field = resample_f32(&self.gravity_field);
+        if let Some(store) = &self.compact_gravity {
+            self.compact_gravity = Some(QuantizedRows::new(store.precision(), new_dim, &resample_f32(&store.to_f32())));
+        }
+        self.energy_landscape = resample_f32(&self.energy_landscape);
+        // 入力署名は毎ステップ作り直される問い合わせなので引き継がない
+        self.input_signature_re = vec![0.0; new_dim];
+        self.input_signature_im = vec![0.0; new_dim];
+
+        let old_phases = std::mem::take(&mut self.scramble_phases);
+        // 構築時と同じ黄金比の位相列
+        self.scramble_phases = (0..new_dim).map(|i| (i as f32 * 1.618_034).rem_euclid(2.0 * PI)).collect();
+        let (mut q_re, mut q_im) = (std::mem::take(&mut self.q_memory_re), std::mem::take(&mut self.q_memory_im));
+        self.reproject_memory(&old_phases, &mut q_re, &mut q_im);
+        (self.q_memory_re, self.q_memory_im) = (q_re, q_im);
+        // q_topo は隣接ビン間の位相差の相関で、署名の位相に依らないためそのまま補間する
+        self.q_topo_re = resample_ring(&self.q_topo_re, new_dim);
+        self.q_topo_im = resample_ring(&self.q_topo_im, new_dim);
+        for bank in 0..self.memory_banks.len() {
+            if bank == self.active_bank { continue; }
+            let b = &mut self.memory_banks[bank];
+            if b.q_memory_re.len() != old_dim { continue; }
+            let (mut q_re, mut q_im) = (std::mem::take(&mut b.q_memory_re), std::mem::take(&mut b.q_memory_im));
+            let (topo_re, topo_im) = (resample_ring(&b.q_topo_re, new_dim), resample_ring(&b.q_topo_im, new_dim));
+            self.reproject_memory(&old_phases, &mut q_re, &mut q_im);
+            let b = &mut self.memory_banks[bank];
+            (b.q_memory_re, b.q_memory_im, b.q_topo_re, b.q_topo_im) = (q_re, q_im, topo_re, topo_im);
+        }
+
+        let map_bin = |i: usize| ((2 * i + 1) * new_dim / (2 * old_dim)).min(new_dim - 1);
+        let mut links: Vec<(usize, usize, f32)> = Vec::with_capacity(self.entanglements.len());
+        for &(from, to, strength) in &self.entanglements {
+            let (from, to) = (map_bin(from), map_bin(to));
+            if from == to { continue; }
+            match links.iter_mut().find(|(f, t, _)| *f == from && *t == to) {
+                // 縮小で同じ組に潰れた結合は強い方を残す
+                Some(link) => if strength.abs() > link.2.abs() { link.2 = strength; },
+                None => links.push((from, to, strength)),
+            }
+        }
+        self.entanglements = links;
+        self.enforce_wormhole_cap();
+
+        self.dim = new_dim;
+        self.exploration.reset();
+        // 保存したパターンはビンごとの位相なので、次元が変わると使えない
+        self.replay.clear();
+        if let Some(coding) = self.state_codes.as_ref().map(|t| t.coding) {
+            self.state_codes = StateCodeTable::new(coding, new_dim);
+        }
+        // 行動ごとのビン数は比率を保って配り直す
+        self.action_layout = self.action_layout.take().and_then(|layout| {
+            let weights: Vec<f32> = layout.widths().into_iter().map(|w| w as f32).collect();
+            ActionLayout::weighted(new_dim, &weights)
+        });
+    }
+
+    /// 記憶波 q (= psi * conj(署名)) から旧い署名の位相を外して new_dim へ補間し、新しい署名の位相を掛け直す
+    /// 署名は e^{i scramble_phases[i]} と入力番号だけで決まる位相の積なので、後者はそのまま保たれる
+    fn reproject_memory(&self, old_phases: &[f32], q_re: &mut Vec<f64>, q_im: &mut Vec<f64>) {
+        let new_dim = self.scramble_phases.len();
+        let (mut de_re, mut de_im) = (Vec::with_capacity(q_re.len()), Vec::with_capacity(q_re.len()));
+        for i in 0..q_re.len() {
+            let (s, c) = (old_phases[i] as f64).sin_cos();
+            de_re.push(q_re[i] * c - q_im[i] * s);
+            de_im.push(q_re[i] * s + q_im[i] * c);
+        }
+        let (de_re, de_im) = (resample_ring(&de_re, new_dim), resample_ring(&de_im, new_dim));
+        q_re.clear();
+        q_im.clear();
+        for j in 0..new_dim {
+            let (s, c) = (self.scramble_phases[j] as f64).sin_cos();
+            q_re.push(de_re[j] * c + de_im[j] * s);
+            q_im.push(de_im[j] * c - de_re[j] * s);
         }
     }
 
     pub fn next_rng(&mut self) -> f32 {
-        self.rng_seed = self.rng_seed.wrapping_mul(6364136223846793005).wrapping_add(1);
-        ((self.rng_seed >> 32) as u32) as f32 / u32::MAX as f32
+        lcg_next(&mut self.rng_seed)
     }
 
+    /// 標準正規分布に従う乱数（Box-Muller 法）
+    pub fn next_gaussian(&mut self) -> f32 {
+        noise::gaussian(&mut || self.next_rng())
+    }
+
+    /// 既に同じ組の結合があれば強度を上書きする。上限を超えたら最も弱い結合を追い出す
     pub fn add_wormhole(&mut self, from: usize, to: usize, strength: f32) {
-        if from < self.dim && to < self.dim {
-            self.entanglements.push((from, to, strength));
+        if from >= self.dim || to >= self.dim { return; }
+        if let Some(link) = self.entanglements.iter_mut().find(|(f, t, _)| *f == from && *t == to) {
+            link.2 = strength;
+            return;
+        }
+        self.entanglements.push((from, to, strength));
+        self.enforce_wormhole_cap();
+    }
+
+    /// 状態 a と b の状態コードのビンを順に双方向のワームホールで結ぶ（注入されたエネルギーを状態間で共有させる）
+    pub fn link_states(&mut self, a: usize, b: usize, strength: f32) {
+        let (code_a, code_b) = (self.state_code(a), self.state_code(b));
+        for (&(bin_a, _), &(bin_b, _)) in code_a.iter().zip(&code_b) {
+            if bin_a == bin_b { continue; }
+            self.add_wormhole(bin_a, bin_b, strength);
+            self.add_wormhole(bin_b, bin_a, strength);
+        }
+    }
+
+    /// (from, to, strength) の一覧
+    pub fn list_wormholes(&self) -> &[(usize, usize, f32)] {
+        &self.entanglements
+    }
+
+    /// 取り除いたら true
+    pub fn remove_wormhole(&mut self, from: usize, to: usize) -> bool {
+        let before = self.entanglements.len();
+        self.entanglements.retain(|&(f, t, _)| !(f == from && t == to));
+        self.entanglements.len() != before
+    }
+
+    /// 全結合の強度に factor を掛け、弱くなりすぎた結合を取り除く。取り除いた数を返す
+    pub fn decay_wormholes(&mut self, factor: f32) -> usize {
+        let before = self.entanglements.len();
+        self.entanglements.retain_mut(|(_, _, strength)| {
+            *strength *= factor;
+            strength.abs() >= WORMHOLE_MIN_STRENGTH
+        });
+        before - self.entanglements.len()
+    }
+
+    pub fn set_wormhole_cap(&mut self, cap: usize) {
+        self.wormhole_cap = cap;
+        self.enforce_wormhole_cap();
+    }
+
+    fn enforce_wormhole_cap(&mut self) {
+        let excess = self.entanglements.len().saturating_sub(self.wormhole_cap);
+        if excess == 0 { return; }
+        // 強度の絶対値が小さい順に追い出す（残る結合の順序は保つ）
+        let mut strengths: Vec<f32> = self.entanglements.iter().map(|e| e.2.abs()).collect();
+        strengths.sort_unstable_by(f32::total_cmp);
+        let threshold = strengths[excess - 1];
+        let mut to_evict = strengths[..excess].iter().filter(|&&s| s == threshold).count();
+        self.entanglements.retain(|e| {
+            let s = e.2.abs();
+            if s < threshold { return false; }
+            if s == threshold && to_evict > 0 { to_evict -= 1; return false; }
+            true
+        });
+    }
+
+    /// 他のエージェントの波を strength (0..=1) の割合で自分の波へ引き寄せる（全ビン、毎ステップ呼ぶ想定）
+    /// 片方向の結合なので、双方向に共有するには両側から呼ぶ
+    pub fn entangle_with(&mut self, other: &MWSO, strength: f32) {
+        self.entangle_bins_with(0..self.dim, other, 0..other.dim, strength);
+    }
+
+    /// 自分の bins を other の other_bins へ相対位置で対応づけ、そのビンの複素振幅へ strength の割合で近づける
+    pub fn entangle_bins_with(&mut self, bins: std::ops::Range<usize>, other: &MWSO, other_bins: std::ops::Range<usize>, strength: f32) {
+        let bins = bins.start.min(self.dim)..bins.end.min(self.dim);
+        let other_bins = other_bins.start.min(other.dim)..other_bins.end.min(other.dim);
+        if bins.is_empty() || other_bins.is_empty() { return; }
+        let k = to_wave(strength.clamp(0.0, 1.0));
+        for (n, i) in bins.clone().enumerate() {
+            let j = other_bins.start + n * other_bins.len() / bins.len();
+            self.psi_real[i] += (other.psi_real[j] - self.psi_real[i]) * k;
+            self.psi_imag[i] += (other.psi_imag[j] - self.psi_imag[i]) * k;
         }
     }
 
     /// PP-CEL: Pure-Phase Correlated Energy Landscape Imprinting.
     /// Uses pure phase correlations weighted by reward (alpha) with normalization.
     pub fn imprint_qcel(&mut self, input_idx: usize, reward: f32) {
+        self.route_imprint(input_idx);
+        self.memory_banks[self.active_bank].imprints += 1;
+
         let alpha = reward as f64;
-        let base_lambda = 0.008; // Slightly higher base forgetting
+        let base_lambda = BASE_FORGET_RATE; // Slightly higher base forgetting
         
         // 失敗時は報酬の大きさに応じて忘却を加速（最大 0.2 まで）
         let lambda = if reward < 0.0 { 
@@ -103,41 +561,41 @@ impl MWSO {
         let offset = (input_idx as f32 * 1.618).rem_euclid(2.0 * PI);
         let spread = 2;
 
+        // リプレイ用のパターンも記憶波と同じ率で忘れる。失敗したパターンは再生しない
+        self.replay.fade(1.0 - lambda as f32);
+        if reward > 0.0 {
+            let (psi_real, psi_imag) = (&self.psi_real, &self.psi_imag);
+            self.replay.record(self.active_bank, input_idx, reward, || {
+                psi_real.iter().zip(psi_imag).map(|(&re, &im)| from_wave(im.atan2(re))).collect()
+            });
+        } else if reward < 0.0 {
+            self.replay.remove(self.active_bank, input_idx);
+        }
+
+        // 正規化用のエネルギーは更新と同じループで積算し、正規化のための走査を省く
+        let (mut memory_energy_sq, mut topo_energy_sq) = (0.0f64, 0.0f64);
         for i in 0..self.dim {
             let next_i = (i + 1) % self.dim;
             
             // --- Pointwise correlation ---
-            let psi_re = self.psi_real[i] as f64;
-            let psi_im = self.psi_imag[i] as f64;
+            let psi_re = wave_to_f64(self.psi_real[i]);
+            let psi_im = wave_to_f64(self.psi_imag[i]);
             let psi_mag = (psi_re.powi(2) + psi_im.powi(2)).sqrt() + 1e-9;
             let u_psi_re = psi_re / psi_mag;
             let u_psi_im = psi_im / psi_mag;
 
-            // Use same spread as in set_input_query
-            let mut sig_re = 0.0f64;
-            let mut sig_im = 0.0f64;
-            for j in 0..spread {
-                let idx_offset = (offset + j as f32 * 0.05).rem_euclid(2.0 * PI);
-                let weight = 1.0 / (j + 1) as f32;
-                let sig_phase = self.scramble_phases[i] + idx_offset;
-                let (s, c) = sig_phase.sin_cos();
-                sig_re += c as f64 * weight as f64;
-                sig_im += s as f64 * weight as f64;
-            }
-            // Normalize the imprinted signature
-            let sig_mag_total = (sig_re.powi(2) + sig_im.powi(2)).sqrt() + 1e-9;
-            let u_sig_re = sig_re / sig_mag_total;
-            let u_sig_im = sig_im / sig_mag_total;
+            let (u_sig_re, u_sig_im) = self.imprint_signature(i, offset);
 
             let corr_re = u_psi_re * u_sig_re + u_psi_im * u_sig_im;
             let corr_im = u_psi_im * u_sig_re - u_psi_re * u_sig_im;
             
             self.q_memory_re[i] = self.q_memory_re[i] * (1.0 - lambda) + corr_re * alpha / dim_norm;
             self.q_memory_im[i] = self.q_memory_im[i] * (1.0 - lambda) + corr_im * alpha / dim_norm;
+            memory_energy_sq += self.q_memory_re[i].powi(2) + self.q_memory_im[i].powi(2);
 
             // --- Topological Gradient correlation ---
-            let psi_re_next = self.psi_real[next_i] as f64;
-            let psi_im_next = self.psi_imag[next_i] as f64;
+            let psi_re_next = wave_to_f64(self.psi_real[next_i]);
+            let psi_im_next = wave_to_f64(self.psi_imag[next_i]);
             let psi_mag_next = (psi_re_next.powi(2) + psi_im_next.powi(2)).sqrt() + 1e-9;
             let u_psi_re_next = psi_re_next / psi_mag_next;
             let u_psi_im_next = psi_im_next / psi_mag_next;
@@ -166,41 +624,224 @@ impl MWSO {
 
             self.q_topo_re[i] = self.q_topo_re[i] * (1.0 - lambda) + topo_re * alpha / dim_norm;
             self.q_topo_im[i] = self.q_topo_im[i] * (1.0 - lambda) + topo_im * alpha / dim_norm;
+            topo_energy_sq += self.q_topo_re[i].powi(2) + self.q_topo_im[i].powi(2);
 
             // Immediate potential demolition if reward is bad
             if reward < -0.1 {
                 self.energy_landscape[i] *= 0.8; // Moderate demolition
-                self.gravity_field[i] *= 0.8;
+                self.set_gravity(i, self.gravity(i) * 0.8);
             }
         }
 
         // Keep memories bounded
-        self.normalize_q_memory(5.0);
-        self.normalize_q_topo(3.0);
+        Self::rescale(&mut self.q_memory_re, &mut self.q_memory_im, memory_energy_sq, Q_MEMORY_NORM);
+        Self::rescale(&mut self.q_topo_re, &mut self.q_topo_im, topo_energy_sq, 3.0);
     }
 
-    fn normalize_q_topo(&mut self, target_norm: f64) {
-        let mut total_energy_sq = 0.0;
-        for i in 0..self.dim { total_energy_sq += self.q_topo_re[i].powi(2) + self.q_topo_im[i].powi(2); }
-        let norm = total_energy_sq.sqrt();
-        if norm > 1e-12 {
-            let factor = target_norm / norm;
-            for i in 0..self.dim { self.q_topo_re[i] *= factor; self.q_topo_im[i] *= factor; }
+    /// 刻み込みに使う入力署名の i 番目（単位複素数）。offset は入力番号から決まる位相
+    fn imprint_signature(&self, i: usize, offset: f32) -> (f64, f64) {
+        // Use same spread as in set_input_query
+        let spread = 2;
+        let mut sig_re = 0.0f64;
+        let mut sig_im = 0.0f64;
+        for j in 0..spread {
+            let idx_offset = (offset + j as f32 * 0.05).rem_euclid(2.0 * PI);
+            let weight = 1.0 / (j + 1) as f32;
+            let sig_phase = self.scramble_phases[i] + idx_offset;
+            let (s, c) = sig_phase.sin_cos();
+            sig_re += c as f64 * weight as f64;
+            sig_im += s as f64 * weight as f64;
+        }
+        // Normalize the imprinted signature
+        let sig_mag_total = (sig_re.powi(2) + sig_im.powi(2)).sqrt() + 1e-9;
+        (sig_re / sig_mag_total, sig_im / sig_mag_total)
+    }
+
+    /// 空の記憶波バンクを追加し、そのバンク番号を返す
+    pub fn add_memory_bank(&mut self, label: &str) -> usize {
+        self.memory_banks.push(MemoryBank::new(label, self.dim));
+        self.memory_banks.len() - 1
+    }
+
+    pub fn memory_banks(&self) -> &[MemoryBank] {
+        &self.memory_banks
+    }
+
+    pub fn active_memory_bank(&self) -> usize {
+        self.active_bank
+    }
+
+    pub fn find_memory_bank(&self, label: &str) -> Option<usize> {
+        self.memory_banks.iter().position(|b| b.label == label)
+    }
+
+    /// アクティブなバンクを切り替える（以後の想起と刻み込みはこのバンクに対して行われる）
+    pub fn switch_memory_bank(&mut self, bank: usize) -> bool {
+        if bank >= self.memory_banks.len() { return false; }
+        if bank != self.active_bank {
+            // 現在の記憶波をバンクへ戻し、切り替え先の記憶波を本体へ移す
+            self.swap_bank_vectors(self.active_bank);
+            self.swap_bank_vectors(bank);
+            self.active_bank = bank;
         }
+        true
+    }
+
+    /// バンク bank の記憶波 [q_memory_re, q_memory_im, q_topo_re, q_topo_im]
+    pub fn bank_memory(&self, bank: usize) -> [&[f64]; 4] {
+        if bank == self.active_bank {
+            [&self.q_memory_re, &self.q_memory_im, &self.q_topo_re, &self.q_topo_im]
+        } else {
+            let b = &self.memory_banks[bank];
+            [&b.q_memory_re, &b.q_memory_im, &b.q_topo_re, &b.q_topo_im]
+        }
+    }
+
+    /// バンク一式を置き換える（各バンクの記憶波は dim 要素であること）
+    pub fn restore_memory_banks(&mut self, banks: Vec<MemoryBank>, active: usize) {
+        if banks.is_empty() { return; }
+        self.memory_banks = banks;
+        self.active_bank = active.min(self.memory_banks.len() - 1);
+        self.swap_bank_vectors(self.active_bank);
+        let b = &mut self.memory_banks[self.active_bank];
+        let label = std::mem::take(&mut b.label);
+        *b = MemoryBank { imprints: b.imprints, ..MemoryBank::detached(&label) };
+        let count = self.memory_banks.len();
+        self.memory_tags.retain(|_, tag| tag.bank < count);
+    }
+
+    /// 記憶波を空の既定バンク1つだけの状態に戻す
+    pub fn clear_memory_banks(&mut self) {
+        self.restore_memory_banks(vec![MemoryBank::new("default", self.dim)], 0);
+        self.memory_tags.clear();
+    }
+
+    fn swap_bank_vectors(&mut self, bank: usize) {
+        let b = &mut self.memory_banks[bank];
+        std::mem::swap(&mut self.q_memory_re, &mut b.q_memory_re);
+        std::mem::swap(&mut self.q_memory_im, &mut b.q_memory_im);
+        std::mem::swap(&mut self.q_topo_re, &mut b.q_topo_re);
+        std::mem::swap(&mut self.q_topo_im, &mut b.q_topo_im);
+    }
+
+    /// 入力 input_idx で各バンクに問い合わせたときの共鳴 (-1..1)
+    /// 想起される波と現在の波との一致度で、記憶が空のバンクは 0
+    pub fn bank_resonances(&self, input_idx: usize) -> Vec<f64> {
+        (0..self.memory_banks.len()).map(|bank| {
+            let (mem_re, mem_im) = if bank == self.active_bank {
+                (&self.q_memory_re, &self.q_memory_im)
+            } else {
+                (&self.memory_banks[bank].q_memory_re, &self.memory_banks[bank].q_memory_im)
+            };
+            self.memory_resonance(mem_re, mem_im, input_idx)
+        }).collect()
+    }
+
+    /// 最も共鳴するバンクの番号と共鳴
+    pub fn best_memory_bank(&self, input_idx: usize) -> (usize, f64) {
+        self.bank_resonances(input_idx).into_iter().enumerate()
+            .fold((self.active_bank, f64::NEG_INFINITY), |best, (bank, r)| if r > best.1 { (bank, r) } else { best })
+    }
+
+    /// imprint_qcel に加え、label があれば刻み込み先のバンクと鍵を索引に記録する
+    /// 同じラベルで刻み直すと索引は最新の刻み込みを指す
+    pub fn imprint_memory(&mut self, input_idx: usize, reward: f32, label: Option<&str>) {
+        self.imprint_qcel(input_idx, reward);
+        let Some(label) = label else { return };
+        let bank = self.active_bank;
+        let tag = self.memory_tags.entry(label.to_string())
+            .or_insert(MemoryTag { bank, input_idx, imprints: 0, last_reward: 0.0 });
+        *tag = MemoryTag { bank, input_idx, imprints: tag.imprints + 1, last_reward: reward };
+    }
+
+    pub fn memory_tags(&self) -> &HashMap<String, MemoryTag> {
+        &self.memory_tags
+    }
+
+    /// ラベルを索引から外す（記憶波そのものは変えない）
+    pub fn remove_memory_tag(&mut self, label: &str) -> bool {
+        self.memory_tags.remove(label).is_some()
+    }
+
+    /// ラベル付きの記憶それぞれを鍵で想起したときの、現在の波との共鳴 (-1..1)。共鳴の高い順
+    pub fn tag_resonances(&self) -> Vec<(String, f32)> {
+        let mut resonances: Vec<(String, f32)> = self.memory_tags.iter()
+            .filter(|(_, tag)| tag.bank < self.memory_banks.len())
+            .map(|(label, tag)| {
+                let [mem_re, mem_im, _, _] = self.bank_memory(tag.bank);
+                (label.clone(), self.memory_resonance(mem_re, mem_im, tag.input_idx) as f32)
+            })
+            .collect();
+        resonances.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        resonances
+    }
+
+    fn memory_resonance(&self, mem_re: &[f64], mem_im: &[f64], input_idx: usize) -> f64 {
+        let offset = (input_idx as f32 * 1.618).rem_euclid(2.0 * PI);
+        let (mut dot, mut mem_sq, mut psi_sq) = (0.0, 0.0, 0.0);
+        for i in 0..self.dim {
+            let (sig_re, sig_im) = self.imprint_signature(i, offset);
+            // 想起: 記憶波に入力署名を掛けると、刻んだときの波の位相が戻る
+            let rec_re = mem_re[i] * sig_re - mem_im[i] * sig_im;
+            let rec_im = mem_re[i] * sig_im + mem_im[i] * sig_re;
+            let (psi_re, psi_im) = (wave_to_f64(self.psi_real[i]), wave_to_f64(self.psi_imag[i]));
+            dot += rec_re * psi_re + rec_im * psi_im;
+            mem_sq += mem_re[i].powi(2) + mem_im[i].powi(2);
+            psi_sq += psi_re.powi(2) + psi_im.powi(2);
+        }
+        if mem_sq < 1e-18 || psi_sq < 1e-18 { return 0.0; }
+        dot / (mem_sq.sqrt() * psi_sq.sqrt())
+    }
+
+    /// bank_routing に従って刻み込み先のバンクをアクティブにする
+    fn route_imprint(&mut self, input_idx: usize) {
+        let count = self.memory_banks.len();
+        if count < 2 { return; }
+        let bank = match self.bank_routing {
+            BankRoutingPolicy::Active => return,
+            BankRoutingPolicy::ByState => input_idx % count,
+            BankRoutingPolicy::Resonance => {
+                let (best, resonance) = self.best_memory_bank(input_idx);
+                if resonance >= BANK_NOVELTY_THRESHOLD {
+                    best
+                } else {
+                    // 新しいエピソードは最も使われていないバンクへ
+                    (0..count).min_by_key(|&b| self.memory_banks[b].imprints).unwrap_or(best)
+                }
+            }
+        };
+        self.switch_memory_bank(bank);
     }
 
     fn normalize_q_memory(&mut self, target_norm: f64) {
-        let mut total_energy_sq = 0.0;
-        for i in 0..self.dim { total_energy_sq += self.q_memory_re[i].powi(2) + self.q_memory_im[i].powi(2); }
-        let norm = total_energy_sq.sqrt();
+        let total_energy_sq = self.q_memory_re.iter().zip(&self.q_memory_im).map(|(re, im)| re.powi(2) + im.powi(2)).sum();
+        Self::rescale(&mut self.q_memory_re, &mut self.q_memory_im, total_energy_sq, target_norm);
+    }
+
+    /// 二乗ノルムが energy_sq と分かっている記憶波 (re, im) を target_norm に揃える
+    fn rescale(re: &mut [f64], im: &mut [f64], energy_sq: f64, target_norm: f64) {
+        let norm = energy_sq.sqrt();
         if norm > 1e-12 {
             let factor = target_norm / norm;
-            for i in 0..self.dim { self.q_memory_re[i] *= factor; self.q_memory_im[i] *= factor; }
+            for (r, i) in re.iter_mut().zip(im.iter_mut()) { *r *= factor; *i *= factor; }
         }
     }
 
     pub fn inject_state(&mut self, state_idx: usize, strength: f32, penalty_field: &[f32]) {
         if state_idx >= self.dim { return; }
+        if let Some(ref mut table) = self.state_codes {
+            let (bins, phases) = table.code(state_idx);
+            for (&idx, &phase_offset) in bins.iter().zip(&phases) {
+                let penalty = penalty_field.get(idx).cloned().unwrap_or(0.0);
+                let resistance = (-penalty * 2.0).exp();
+
+                let phase_filter = self.theta[idx].cos() + to_wave(phase_offset);
+                let drive = to_wave(strength) * (1.5 + phase_filter.cos()) * to_wave(resistance);
+                self.psi_real[idx] += drive;
+                self.psi_imag[idx] += drive * phase_filter.sin();
+            }
+            return;
+        }
         let primes = [31, 37, 41, 43, 47, 53, 59, 61, 67, 71];
         let stride = primes[state_idx % primes.len()];
         let phase_offset = (state_idx as f32 * 1.618).rem_euclid(2.0 * PI);
@@ -211,13 +852,57 @@ impl MWSO {
             let penalty = penalty_field.get(idx).cloned().unwrap_or(0.0);
             let resistance = (-penalty * 2.0).exp(); 
             
-            let phase_filter = self.theta[idx].cos() + phase_offset;
-            let drive = strength * (1.5 + phase_filter.cos()) * resistance;
+            let phase_filter = self.theta[idx].cos() + to_wave(phase_offset);
+            let drive = to_wave(strength) * (1.5 + phase_filter.cos()) * to_wave(resistance);
             self.psi_real[idx] += drive;
             self.psi_imag[idx] += drive * phase_filter.sin();
         }
     }
 
+    /// inject_state の状態コードを切り替える（既存の波はそのままなので、学習済みのモデルでは再学習が必要）
+    pub fn set_state_coding(&mut self, coding: StateCoding) {
+        if self.state_coding() == coding { return; }
+        self.state_codes = StateCodeTable::new(coding, self.dim);
+    }
+
+    pub fn state_coding(&self) -> StateCoding {
+        self.state_codes.as_ref().map_or(StateCoding::PrimeStride, |table| table.coding)
+    }
+
+    /// inject_state が状態 state_idx に使う (ビン, 位相) の組
+    pub fn state_code(&mut self, state_idx: usize) -> Vec<(usize, f32)> {
+        if state_idx >= self.dim { return Vec::new(); }
+        if let Some(ref mut table) = self.state_codes {
+            let (bins, phases) = table.code(state_idx);
+            return bins.into_iter().zip(phases).collect();
+        }
+        let primes = [31, 37, 41, 43, 47, 53, 59, 61, 67, 71];
+        let stride = primes[state_idx % primes.len()];
+        let phase_offset = (state_idx as f32 * 1.618).rem_euclid(2.0 * PI);
+        (0..STATE_CODE_BINS).map(|i| ((state_idx + i * stride) % self.dim, phase_offset)).collect()
+    }
+
+    /// 行動 → ビン範囲の対応を指定する（None で等分に戻す）
+    /// 全行動のビン数の合計が dim を超える場合は何もせず false を返す
+    pub fn set_action_layout(&mut self, layout: Option<ActionLayout>) -> bool {
+        if layout.as_ref().is_some_and(|l| l.is_empty() || l.total_bins() > self.dim) { return false; }
+        self.action_layout = layout;
+        true
+    }
+
+    pub fn action_layout(&self) -> Option<&ActionLayout> {
+        self.action_layout.as_ref()
+    }
+
+    /// 行動 action_idx（action_size 個中）が受け持つビン範囲
+    /// action_layout が同じ行動数で指定されていればそれを、そうでなければ等分を使う
+    pub fn action_bins(&self, action_idx: usize, action_size: usize) -> std::ops::Range<usize> {
+        match self.action_layout {
+            Some(ref layout) if layout.len() == action_size => layout.range(action_idx % action_size),
+            _ => ActionLayout::uniform_range(self.dim, action_size, action_idx),
+        }
+    }
+
     /// Injects a vector-based state (weighted multiple indices).
     /// This allows for simultaneous multi-feature injection.
     pub fn inject_vector_state(&mut self, state_weights: &[(usize, f32)], total_strength: f32, penalty_field: &[f32]) {
@@ -281,14 +966,80 @@ impl MWSO {
         }
     }
 
+    /// PP-CEL Retrieval (Phase-Gated Key Matching) の1要素分
+    /// 各要素は現在の波と記憶だけを読むので、要素間で独立に評価できる
+    fn recall_at(&self, i: usize, gate_power: f32, base_assoc_strength: f32, dim_scale: f32) -> (Wave, Wave) {
+        let next_i = (i + 1) % self.dim;
+
+        // --- Hybrid Query (External Input + Internal Wave State) ---
+        let psi_re = wave_to_f64(self.psi_real[i]);
+        let psi_im = wave_to_f64(self.psi_imag[i]);
+        let psi_mag_sq = psi_re.powi(2) + psi_im.powi(2) + 1e-12;
+        let psi_mag = psi_mag_sq.sqrt();
+        
+        // 入力信号が弱い場所ほど、自己連想（穴埋め）を強める
+        let sig_strength = (self.input_signature_re[i].powi(2) + self.input_signature_im[i].powi(2)).sqrt();
+        let local_assoc = base_assoc_strength * (1.2 - sig_strength).clamp(0.2, 1.2);
+
+        let query_re = self.input_signature_re[i] as f64 + (psi_re / psi_mag) * local_assoc as f64;
+        let query_im = self.input_signature_im[i] as f64 + (psi_im / psi_mag) * local_assoc as f64;
+
+        let q_mag = (query_re.powi(2) + query_im.powi(2)).sqrt() + 1e-9;
+        let u_q_re = query_re / q_mag;
+        let u_q_im = query_im / q_mag;
+
+        // 1. Pointwise Recall
+        let rec_re = self.q_memory_re[i] * u_q_re - self.q_memory_im[i] * u_q_im;
+        let rec_im = self.q_memory_re[i] * u_q_im + self.q_memory_im[i] * u_q_re;
+
+        // 2. Topological Shape Matching
+        let query_re_next = self.input_signature_re[next_i] as f64 + (wave_to_f64(self.psi_real[next_i]) / dim_scale as f64) * local_assoc as f64;
+        let query_im_next = self.input_signature_im[next_i] as f64 + (wave_to_f64(self.psi_imag[next_i]) / dim_scale as f64) * local_assoc as f64;
+        let q_mag_next = (query_re_next.powi(2) + query_im_next.powi(2)).sqrt() + 1e-9;
+        
+        let d_q_re = u_q_re * (query_re_next / q_mag_next) + u_q_im * (query_im_next / q_mag_next);
+        let d_q_im = u_q_im * (query_re_next / q_mag_next) - u_q_re * (query_im_next / q_mag_next);
+
+        let topo_match = (self.q_topo_re[i] * d_q_re + self.q_topo_im[i] * d_q_im).max(0.0);
+        let shape_coherence = (topo_match as f32 * 2.5).clamp(0.5, 2.5);
+
+        // Soft-Gate
+        let corr_strength = (rec_re.powi(2) + rec_im.powi(2)).sqrt();
+        let mut gate = (corr_strength * shape_coherence as f64).powf(gate_power as f64).clamp(0.0, 2.0);
+
+        // --- Phase Coherence Guard & Resonance ---
+        let alignment = (psi_re * rec_re + psi_im * rec_im) / (psi_mag * corr_strength + 1e-12);
+        
+        let mut resonance_gain = 1.0;
+        if alignment < -0.3 {
+            // 逆位相なら大幅に減衰（干渉防止）
+            gate *= (1.0 + alignment).max(0.0); 
+        } else if alignment > 0.6 {
+            // 強烈な共鳴：位相が一致しているなら、想起強度を非線形に増幅 (Similarity Resonance)
+            resonance_gain = 1.0 + (alignment as f32 - 0.6).powi(2) * 5.0;
+        }
+
+        (wave_from_f64(rec_re * gate * resonance_gain as f64), wave_from_f64(rec_im * gate * resonance_gain as f64))
+    }
+
+    /// 波を dt だけ時間発展させる。penalty_field はこの MWSO のビンごとの現在のペナルティ場で、
+    /// ポテンシャルを持ち上げ粘性を増やして波を減衰させる（要素が足りないビンはペナルティ 0 とみなす）
+    /// dim が par::PARALLEL_MIN_DIM 以上かつ parallel 機能が有効なら、要素ごとの処理を rayon で分割する
     pub fn step_core(&mut self, dt: f32, speed_boost: f32, focus_factor: f32, system_temp: f32, penalty_field: &[f32]) {
         let solidification = 0.9999 - (0.0005 * (1.0 - focus_factor));
         let effective_dt = dt * (1.0 + speed_boost);
         let dim_scale = (self.dim as f32).sqrt();
+        let dim = self.dim;
+        let parallel = par::enabled(dim);
+
+        // --- 0. Scheduled External Fields ---
+        if !self.field_schedule.is_empty() {
+            self.field_schedule.apply(&mut self.psi_real, &mut self.psi_imag);
+        }
 
         // --- 1. PP-CEL Retrieval (Phase-Gated Key Matching) ---
-        let mut recall_re = vec![0.0; self.dim];
-        let mut recall_im = vec![0.0; self.dim];
+        let mut scratch = std::mem::take(&mut self.scratch);
+        scratch.resize(dim);
         
         // Soft Gate exponent based on temperature (High temp = more inclusive)
         let gate_power = (1.8 - system_temp * 0.8).clamp(1.0, 3.0);
@@ -297,67 +1048,20 @@ impl MWSO {
         // 温度が高い時は弱めるだけでなく、入力が不確かな場所ほど強く働くように動的制御
         let base_assoc_strength = 0.4 * focus_factor * (1.0 - (system_temp * 0.5).min(0.8));
 
-        for i in 0..self.dim {
-            let next_i = (i + 1) % self.dim;
-
-            // --- Hybrid Query (External Input + Internal Wave State) ---
-            let psi_re = self.psi_real[i] as f64;
-            let psi_im = self.psi_imag[i] as f64;
-            let psi_mag_sq = psi_re.powi(2) + psi_im.powi(2) + 1e-12;
-            let psi_mag = psi_mag_sq.sqrt();
-            
-            // 入力信号が弱い場所ほど、自己連想（穴埋め）を強める
-            let sig_strength = (self.input_signature_re[i].powi(2) + self.input_signature_im[i].powi(2)).sqrt();
-            let local_assoc = base_assoc_strength * (1.2 - sig_strength).clamp(0.2, 1.2);
-
-            let query_re = self.input_signature_re[i] as f64 + (psi_re / psi_mag) * local_assoc as f64;
-            let query_im = self.input_signature_im[i] as f64 + (psi_im / psi_mag) * local_assoc as f64;
-
-            let q_mag = (query_re.powi(2) + query_im.powi(2)).sqrt() + 1e-9;
-            let u_q_re = query_re / q_mag;
-            let u_q_im = query_im / q_mag;
-
-            // 1. Pointwise Recall
-            let rec_re = self.q_memory_re[i] * u_q_re - self.q_memory_im[i] * u_q_im;
-            let rec_im = self.q_memory_re[i] * u_q_im + self.q_memory_im[i] * u_q_re;
-
-            // 2. Topological Shape Matching
-            let query_re_next = self.input_signature_re[next_i] as f64 + (self.psi_real[next_i] as f64 / dim_scale as f64) * local_assoc as f64;
-            let query_im_next = self.input_signature_im[next_i] as f64 + (self.psi_imag[next_i] as f64 / dim_scale as f64) * local_assoc as f64;
-            let q_mag_next = (query_re_next.powi(2) + query_im_next.powi(2)).sqrt() + 1e-9;
-            
-            let d_q_re = u_q_re * (query_re_next / q_mag_next) + u_q_im * (query_im_next / q_mag_next);
-            let d_q_im = u_q_im * (query_re_next / q_mag_next) - u_q_re * (query_im_next / q_mag_next);
-
-            let topo_match = (self.q_topo_re[i] * d_q_re + self.q_topo_im[i] * d_q_im).max(0.0);
-            let shape_coherence = (topo_match as f32 * 2.5).clamp(0.5, 2.5);
-
-            // Soft-Gate
-            let corr_strength = (rec_re.powi(2) + rec_im.powi(2)).sqrt();
-            let mut gate = (corr_strength * shape_coherence as f64).powf(gate_power as f64).clamp(0.0, 2.0);
-
-            // --- Phase Coherence Guard & Resonance ---
-            let alignment = (psi_re * rec_re + psi_im * rec_im) / (psi_mag * corr_strength + 1e-12);
-            
-            let mut resonance_gain = 1.0;
-            if alignment < -0.3 {
-                // 逆位相なら大幅に減衰（干渉防止）
-                gate *= (1.0 + alignment).max(0.0); 
-            } else if alignment > 0.6 {
-                // 強烈な共鳴：位相が一致しているなら、想起強度を非線形に増幅 (Similarity Resonance)
-                resonance_gain = 1.0 + (alignment as f32 - 0.6).powi(2) * 5.0;
-            }
-
-            recall_re[i] = (rec_re * gate * resonance_gain as f64) as f32;
-            recall_im[i] = (rec_im * gate * resonance_gain as f64) as f32;
-        }
+        par::for_each2(parallel, &mut scratch.recall_re[..dim], &mut scratch.recall_im[..dim], |i, re, im| {
+            (*re, *im) = self.recall_at(i, gate_power, base_assoc_strength, dim_scale);
+        });
 
         // --- 2. Dynamic Energy Landscape (V) ---
         let thermal_noise = (system_temp * 0.25).max(0.01);
         let smoothing = (system_temp * 0.3).clamp(0.05, 0.9);
 
-        for i in 0..self.dim {
-            let recall_intensity = (recall_re[i].powi(2) + recall_im[i].powi(2)).sqrt();
+        // 熱ゆらぎの乱数は先に逐次で引いておく（並列化の有無で乱数の消費順を変えない）
+        for n in &mut scratch.noise[..dim] { *n = self.next_rng(); }
+
+        let StepScratch { recall_re, recall_im, noise, .. } = &scratch;
+        par::for_each(parallel, &mut self.energy_landscape[..dim], |i, v| {
+            let recall_intensity = from_wave((recall_re[i].powi(2) + recall_im[i].powi(2)).sqrt());
             let penalty = penalty_field.get(i).cloned().unwrap_or(0.0);
             
             // --- Input-Memory Cross Resonance ---
@@ -369,67 +1073,221 @@ impl MWSO {
             let input_mem_match = (sig_re * mem_re + sig_im * mem_im).max(0.0);
             let cross_resonance = (input_mem_match as f32 * 3.0).min(5.0);
 
-            let target_v = -recall_intensity * (5.0 + cross_resonance) * focus_factor + penalty * 6.0 + (self.next_rng() - 0.5) * thermal_noise;
-            self.energy_landscape[i] = self.energy_landscape[i] * smoothing + target_v * (1.0 - smoothing);
-        }
+            let target_v = -recall_intensity * (5.0 + cross_resonance) * focus_factor + penalty * 6.0 + (noise[i] - 0.5) * thermal_noise;
+            *v = *v * smoothing + target_v * (1.0 - smoothing);
+        });
 
         // --- 3. Wave Evolution ---
-        for i in 0..self.dim {
-            self.theta[i] *= solidification;
-            self.theta[i + self.dim] *= solidification;
-
-            let (re, im) = (self.psi_real[i], self.psi_imag[i]);
-            let v = self.energy_landscape[i];
-            let phase_shift = (self.frequencies[i] + v) * effective_dt;
-            let (sin_w, cos_w) = phase_shift.sin_cos();   
-            
-            let mut new_re = re * cos_w - im * sin_w;
-            let mut new_im = re * sin_w + im * cos_w;
-
-            // 波の直接加算（boost）は控えめにし、ポテンシャルによる誘導をメインにする (2.5 -> 0.8)
-            let recall_boost = (0.8 + focus_factor * 0.5) * (1.0 / (system_temp + 0.1));
-            new_re += recall_re[i] * recall_boost * effective_dt;
-            new_im += recall_im[i] * recall_boost * effective_dt;
-
-            let neighbor_re = self.psi_real[(i + 1) % self.dim] + self.psi_real[if i == 0 { self.dim - 1 } else { i - 1 }];
-            let coupling = self.theta[i] * neighbor_re / dim_scale;
-            
-            self.psi_real[i] = new_re + coupling * effective_dt;
-            self.psi_imag[i] = new_im;
-
+        // 3a. 位相回転の sin/cos と粘性減衰を先に一括で求める（sin_cos はベクトル化されないためここで済ませる）
+        let StepScratch { phase_sin, phase_cos, damping, .. } = &mut scratch;
+        par::for_each3(parallel, &mut phase_sin[..dim], &mut phase_cos[..dim], &mut damping[..dim], |i, sin_w, cos_w, damp| {
+            let phase_shift = (to_wave(self.frequencies[i]) + to_wave(self.energy_landscape[i])) * to_wave(effective_dt);
+            (*sin_w, *cos_w) = phase_shift.sin_cos();
             let penalty_val = penalty_field.get(i).cloned().unwrap_or(0.0);
             let viscosity = 0.015 * (1.0 + penalty_val);
-            self.psi_real[i] *= (1.0 - viscosity * effective_dt).max(0.0);
-            self.psi_imag[i] *= (1.0 - viscosity * effective_dt).max(0.0);
+            *damp = to_wave((1.0 - viscosity * effective_dt).max(0.0));
+        });
+
+        // 3b. 要素ごとに独立な部分（θ の固化・回転・想起ブースト・虚部の減衰）を LANES 幅のチャンクで処理する
+        // 波の直接加算（boost）は控えめにし、ポテンシャルによる誘導をメインにする (2.5 -> 0.8)
+        let recall_boost = (0.8 + focus_factor * 0.5) * (1.0 / (system_temp + 0.1));
+        let solidification = to_wave(solidification);
+        par::for_each(parallel, &mut self.theta, |_, t| *t *= solidification);
+        let gain = (to_wave(recall_boost), to_wave(effective_dt));
+        match self.integrator {
+            Integrator::Explicit => self.evolve_explicit(&mut scratch, gain, to_wave(dim_scale)),
+            Integrator::Symplectic => self.evolve_symplectic(&scratch, gain, to_wave(dim_scale)),
         }
+        if self.coupling == Coupling::Kuramoto {
+            self.kuramoto_pull(&mut scratch, gain.1);
+        }
+        let StepScratch { recall_re, recall_im, .. } = &scratch;
 
         // Gravity field (now derived from recall and psi coincidence)
-        for i in 0..self.dim {
-            let coincidence = (self.psi_real[i] * recall_re[i] + self.psi_imag[i] * recall_im[i]).max(0.0);
-            self.gravity_field[i] = self.gravity_field[i] * 0.98 + coincidence * 0.02;
+        let coincidence = |i: usize| from_wave((self.psi_real[i] * recall_re[i] + self.psi_imag[i] * recall_im[i]).max(0.0));
+        match self.compact_gravity.as_mut() {
+            None => par::for_each(parallel, &mut self.gravity_field[..dim], |i, g| {
+                *g = *g * 0.98 + coincidence(i) * 0.02;
+            }),
+            Some(store) => {
+                for i in 0..dim {
+                    store.set(i, store.get(i) * 0.98 + coincidence(i) * 0.02);
+                }
+            }
         }
 
         // --- 4. Boltzmann-like Multimodal Gating ---
         // Allow multiple solution peaks to coexist based on temperature.
-        let mut total_e = 0.0;
-        for i in 0..self.dim { total_e += self.psi_real[i].powi(2) + self.psi_imag[i].powi(2); }
-        let avg_e = total_e / self.dim as f32;
+        let total_e = par::sum(parallel, dim, |i| self.psi_real[i].powi(2) + self.psi_imag[i].powi(2));
+        let avg_e = total_e / self.dim as Wave;
         
         // Beta: Inverse temperature. High temp = low beta = uniform gating.
-        let beta = (1.5 / (system_temp + 0.5)).clamp(0.5, 3.0);
+        let beta = to_wave((1.5 / (system_temp + 0.5)).clamp(0.5, 3.0));
+
+        // Soft gating: allow multiple peaks that are above avg_e.
+        let gate = |e: Wave| (e / (avg_e + 1e-6)).powf(beta).clamp(0.1, 4.0);
+
+        // ゲート後のエネルギーは Σ e·gate² なので、ゲートを書き込む前に求めてゲートと正規化を1回の書き込みにまとめる
+        // ゲート（powf）はビンごとに 1 回だけ求めて作業領域に置き、書き込みで使い回す
+        let (re, im) = (&self.psi_real, &self.psi_imag);
+        par::for_each(parallel, &mut scratch.gate[..dim], |i, g| *g = gate(re[i].powi(2) + im[i].powi(2)));
+        let gates = &scratch.gate;
+        let gated_energy_sq = par::sum(parallel, dim, |i| {
+            (re[i].powi(2) + im[i].powi(2)) * gates[i].powi(2)
+        });
+        let target_norm = to_wave(1.0 + (system_temp * 0.5).min(1.5));
+        let norm = gated_energy_sq.sqrt();
+        let factor = if norm > 1e-6 { target_norm / norm } else { 1.0 };
+        par::for_each2(parallel, &mut self.psi_real[..dim], &mut self.psi_imag[..dim], |i, re, im| {
+            let scale = gates[i] * factor;
+            *re *= scale;
+            *im *= scale;
+        });
+        self.scratch = scratch;
 
-        for i in 0..self.dim {
-            let e = self.psi_real[i].powi(2) + self.psi_imag[i].powi(2);
-            let ratio = e / (avg_e + 1e-6);
-            
-            // Soft gating: allow multiple peaks that are above avg_e.
-            let gate = ratio.powf(beta).clamp(0.1, 4.0);
-            self.psi_real[i] *= gate;
-            self.psi_imag[i] *= gate;
+        if self.memory_half_life > 0.0 {
+            self.forget_steps += 1;
+            if self.forget_steps >= FORGET_BATCH {
+                self.forget(self.forget_steps as f32);
+                self.forget_steps = 0;
+            }
         }
+        self.publish_wave();
+    }
+
+    /// 3b/3c の従来の陽的更新: 回転・想起ブースト・虚部の減衰の後、実部に隣接結合を逐次に足して減衰させる
+    fn evolve_explicit(&mut self, scratch: &mut StepScratch, gain: (Wave, Wave), dim_scale: Wave) {
+        let dim = self.dim;
+        let parallel = par::enabled(dim);
+        // 蔵本型結合では振幅の線形結合を足さない
+        let coupling_dt = if self.coupling == Coupling::Linear { gain.1 } else { 0.0 };
+        let StepScratch { recall_re, recall_im, phase_sin, phase_cos, damping, rotated_re, .. } = scratch;
+        let re = &self.psi_real[..dim];
+        par::for_each_block2(parallel, &mut self.psi_imag[..dim], &mut rotated_re[..dim], |start, im, out| {
+            let range = start..start + im.len();
+            rotate_block(
+                (&re[range.clone()], im),
+                (&phase_sin[range.clone()], &phase_cos[range.clone()]),
+                (&recall_re[range.clone()], &recall_im[range.clone()]),
+                &damping[range],
+                out,
+                gain,
+            );
+        });
 
-        let target_norm = 1.0 + (system_temp * 0.5).min(1.5);
-        self.normalize(target_norm);
+        // 3c. 実部の隣接結合は更新済みの左隣を参照する逐次依存があるため、ブロック内はスカラーで順に適用する
+        // 並列時はブロック境界の左隣・右隣として更新前の値を受け渡す（境界交換）
+        let theta = &self.theta[..dim];
+        par::for_each_ring_block(parallel, &mut self.psi_real[..dim], |start, block, prev, next| {
+            let range = start..start + block.len();
+            couple_block(
+                block, prev, next,
+                (&theta[range.clone()], &rotated_re[range.clone()], &damping[range]),
+                (dim_scale, coupling_dt),
+            );
+        });
+    }
+
+    /// シンプレクティック（保存系部分の相空間体積を保つ）更新。実部を座標・虚部を運動量とみなし、
+    /// 隣接結合の半キック → 位相回転（厳密） → 半キック の Strang 分割で進める
+    /// 想起ブーストと粘性は保存系の外側の項として最後に掛け、減衰は実部・虚部に同じ係数で掛けて位相を保つ
+    fn evolve_symplectic(&mut self, scratch: &StepScratch, (recall_boost, effective_dt): (Wave, Wave), dim_scale: Wave) {
+        let dim = self.dim;
+        let parallel = par::enabled(dim);
+        let StepScratch { recall_re, recall_im, phase_sin, phase_cos, damping, .. } = scratch;
+        let half_kick = effective_dt * 0.5 / dim_scale;
+
+        let linear = self.coupling == Coupling::Linear;
+        if linear { self.coupling_kick(parallel, half_kick); }
+        par::for_each2(parallel, &mut self.psi_real[..dim], &mut self.psi_imag[..dim], |i, re, im| {
+            (*re, *im) = (*re * phase_cos[i] - *im * phase_sin[i], *re * phase_sin[i] + *im * phase_cos[i]);
+        });
+        if linear { self.coupling_kick(parallel, half_kick); }
+
+        let forcing = recall_boost * effective_dt;
+        par::for_each2(parallel, &mut self.psi_real[..dim], &mut self.psi_imag[..dim], |i, re, im| {
+            *re = (*re + recall_re[i] * forcing) * damping[i];
+            *im = (*im + recall_im[i] * forcing) * damping[i];
+        });
+    }
+
+    /// 虚部に隣接結合の力を k だけ加える（実部のみに依存するため厳密に解けるシアー写像）
+    /// 結合係数は隣り合う θ の平均で、対称なのでハミルトン流になる
+    fn coupling_kick(&mut self, parallel: bool, k: Wave) {
+        let dim = self.dim;
+        let (re, theta) = (&self.psi_real[..dim], &self.theta[..dim]);
+        par::for_each(parallel, &mut self.psi_imag[..dim], |i, im| {
+            let (l, r) = ((i + dim - 1) % dim, (i + 1) % dim);
+            let force = (theta[l] + theta[i]) * 0.5 * re[l] + (theta[i] + theta[r]) * 0.5 * re[r];
+            *im += force * k;
+        });
+    }
+
+    /// 蔵本型結合: 各ビンの位相を dt·θ_i·Σ sin(φ_j - φ_i)（j は左右の隣）だけ回す
+    /// 位相はすべて更新前の値から求め、振幅は変えない。θ が正なら同期、負なら反同期へ引く
+    fn kuramoto_pull(&mut self, scratch: &mut StepScratch, effective_dt: Wave) {
+        let dim = self.dim;
+        let parallel = par::enabled(dim);
+        let (re, im) = (&self.psi_real[..dim], &self.psi_imag[..dim]);
+        par::for_each(parallel, &mut scratch.phase[..dim], |i, p| *p = im[i].atan2(re[i]));
+
+        let (phase, theta) = (&scratch.phase[..dim], &self.theta[..dim]);
+        par::for_each2(parallel, &mut self.psi_real[..dim], &mut self.psi_imag[..dim], |i, re, im| {
+            let (l, r) = ((i + dim - 1) % dim, (i + 1) % dim);
+            let pull = theta[i] * ((phase[l] - phase[i]).sin() + (phase[r] - phase[i]).sin()) * effective_dt;
+            let (s, c) = pull.sin_cos();
+            (*re, *im) = (*re * c - *im * s, *re * s + *im * c);
+        });
+    }
+
+    /// 波の読み取り専用スナップショットを別スレッドから読むためのハンドル
+    /// 初回の呼び出しで公開を有効にし、以後は step_core のたびに最新のフレームへ差し替える
+    /// 返したハンドル（とその複製）がすべて破棄されると、次の step_core で公開を止める
+    pub fn wave_view(&mut self) -> WaveView {
+        let first = !self.publisher.is_enabled();
+        let view = self.publisher.view();
+        if first { self.publish_wave(); }
+        view
+    }
+
+    /// 現在の波を読み手へ公開する（step_core 以外で波を書き換えた後に呼ぶ）
+    pub fn publish_wave(&mut self) {
+        self.publisher.publish(&[&self.psi_real], &[&self.psi_imag], &[&self.theta]);
+    }
+
+    /// prev（受信側が持っているフレーム）から現在の波 (psi) への量子化差分。ストリーミング・複製用
+    /// 受信側は wave_delta::decode_delta か apply_delta で復元する（連続して流すなら wave_delta::WaveDeltaStream）
+    pub fn encode_delta(&self, prev: &WaveFrame) -> Vec<u8> {
+        wave_delta::encode_delta(prev, &self.psi_real, &self.psi_imag, prev.step + 1)
+    }
+
+    /// encode_delta の出力を自分の波に適用する（複製側の MWSO 用）。形式や次元が合わなければ何もせず false
+    pub fn apply_delta(&mut self, bytes: &[u8]) -> bool {
+        let current = WaveFrame {
+            step: 0,
+            psi_real: std::mem::take(&mut self.psi_real),
+            psi_imag: std::mem::take(&mut self.psi_imag),
+            theta: Vec::new(),
+        };
+        let decoded = wave_delta::decode_delta(&current, bytes).filter(|f| f.dim() == self.dim);
+        let applied = decoded.is_some();
+        let frame = decoded.unwrap_or(current);
+        self.psi_real = frame.psi_real;
+        self.psi_imag = frame.psi_imag;
+        applied
+    }
+
+    /// 忘却曲線を steps 回の step_core 分だけ進める: アクティブなバンクの記憶波とリプレイ用パターンの強度を
+    /// 0.5^(steps / memory_half_life) 倍にする。刻み込みは正規化されるため、古い刻み込みほど新しいものに比べて弱くなる
+    /// 非アクティブなバンクは凍結されたエピソードとして扱い、減衰させない
+    pub fn forget(&mut self, steps: f32) {
+        if self.memory_half_life <= 0.0 || steps <= 0.0 { return; }
+        let keep = 0.5f64.powf(steps as f64 / self.memory_half_life as f64);
+        for v in self.q_memory_re.iter_mut().chain(self.q_memory_im.iter_mut())
+            .chain(self.q_topo_re.iter_mut()).chain(self.q_topo_im.iter_mut()) {
+            *v *= keep;
+        }
+        self.replay.fade(keep as f32);
     }
 
     /// Sets the current input query signature for Q-CEL retrieval.
@@ -464,59 +1322,128 @@ impl MWSO {
         }
     }
 
-    fn normalize(&mut self, target_norm: f32) {
-        let mut total_energy_sq = 0.0;
-        for i in 0..self.dim { total_energy_sq += self.psi_real[i].powi(2) + self.psi_imag[i].powi(2); }
-        let norm = total_energy_sq.sqrt();
-        if norm > 1e-6 {
-            let factor = target_norm / norm;
-            for i in 0..self.dim { self.psi_real[i] *= factor; self.psi_imag[i] *= factor; }
+    pub fn get_action_scores(&mut self, offset: usize, size: usize, exploration_noise: f32, penalty_field: &[f32]) -> Vec<f32> {
+        let _ = exploration_noise;
+        let mut scores = vec![0.0; size];
+        self.get_action_scores_into(offset, penalty_field, &mut scores);
+        scores
+    }
+
+    /// get_action_scores の書き込み先指定版。scores.len() 個の行動のスコアを呼び出し側のバッファへ直接書き込む
+    /// score_top_k が有効なら、上位候補以外には正確なスコアの代わりに候補の最低点を下回る推定値が入る
+    pub fn get_action_scores_into(&mut self, offset: usize, penalty_field: &[f32], scores: &mut [f32]) {
+        let size = scores.len();
+        let k = self.score_top_k;
+        if k == 0 || k >= size {
+            self.exact_scores_into(offset, penalty_field, scores);
+            return;
+        }
+
+        // 1. 粗い推定: 帯域エネルギーから求めた波動スコアの上界（sin/cos を使わない）
+        par::for_each(par::enabled(self.dim), scores, |i, score| {
+            let bins = self.action_bins(i, size);
+            *score = self.band_estimate((offset + bins.start) % self.dim, bins.len(), penalty_field);
+        });
+
+        // 2. 推定の上位 k 件だけを正確に採点する
+        let mut ranked = std::mem::take(&mut self.top_k_scratch);
+        ranked.clear();
+        ranked.extend(scores.iter().copied().enumerate());
+        ranked.select_nth_unstable_by(k - 1, |a, b| b.1.total_cmp(&a.1));
+        let mut floor = f32::INFINITY;
+        for &(i, _) in &ranked[..k] {
+            let bins = self.action_bins(i, size);
+            scores[i] = self.action_score((offset + bins.start) % self.dim, bins.len(), penalty_field);
+            floor = floor.min(scores[i]);
         }
+        // 候補外は推定の順序を保ったまま、候補の最低点より下に置く
+        let below = floor - (floor.abs() + 1.0) * 1e-6;
+        for &(i, estimate) in &ranked[k..] { scores[i] = estimate.min(below); }
+        self.top_k_scratch = ranked;
     }
 
-    pub fn get_action_scores(&mut self, offset: usize, size: usize, exploration_noise: f32, penalty_field: &[f32]) -> Vec<f32> {
-        let bin_per_action = self.dim / size;
-        let mut scores = Vec::with_capacity(size);
-        for i in 0..size {
-            let mut score = 0.0;
-            let center_idx = (offset + i * bin_per_action) % self.dim;
-            let mut total_penalty = 0.0;
-
-            for j in 0..bin_per_action { 
-                let idx = (center_idx + j) % self.dim;
-                let (re, im) = (self.psi_real[idx], self.psi_imag[idx]);
-                score += (re.powi(2) + im.powi(2)).sqrt() * (im.atan2(re) - self.theta[idx]).cos();
-                total_penalty += penalty_field.get(idx).cloned().unwrap_or(0.0);
-            }
+    fn exact_scores_into(&self, offset: usize, penalty_field: &[f32], scores: &mut [f32]) {
+        let size = scores.len();
+        par::for_each(par::enabled(self.dim), scores, |i, score| {
+            let bins = self.action_bins(i, size);
+            *score = self.action_score((offset + bins.start) % self.dim, bins.len(), penalty_field);
+        });
+    }
 
-            score -= total_penalty * 0.5;
-            
-            // Scaled Score Normalization (similar to Transformer's 1/sqrt(d))
-            // Prevents score explosion as the number of bins increases.
-            score /= (bin_per_action as f32).sqrt();
-            
-            // Linear score. Noise/Jitter is now handled at the decision level (Top-k Softmax).
-            scores.push(score);
+    /// action_score の粗い推定。Σ|ψ|cos(φ-θ) ≤ √(n·Σ|ψ|²) なので、記憶との干渉項を除いた正確なスコアの上界になる
+    fn band_estimate(&self, center_idx: usize, bin_per_action: usize, penalty_field: &[f32]) -> f32 {
+        let mut energy: Wave = 0.0;
+        let mut total_penalty = 0.0;
+        for j in 0..bin_per_action {
+            let idx = (center_idx + j) % self.dim;
+            energy += self.psi_real[idx].powi(2) + self.psi_imag[idx].powi(2);
+            total_penalty += penalty_field.get(idx).cloned().unwrap_or(0.0);
         }
-        scores
+        // (√(n·E) - penalty·0.5) / √n
+        from_wave(energy).sqrt() - total_penalty * 0.5 / (bin_per_action as f32).sqrt()
+    }
+
+    /// 行動 action_idx (size 個中) のスコア。1 行動だけ読むときに全行動を計算しないためのもの
+    pub fn get_action_score(&self, action_idx: usize, size: usize, penalty_field: &[f32]) -> f32 {
+        let bins = self.action_bins(action_idx, size);
+        self.action_score(bins.start % self.dim, bins.len(), penalty_field)
+    }
+
+    /// center_idx から bin_per_action 個のビンにわたる1行動分のスコア
+    fn action_score(&self, center_idx: usize, bin_per_action: usize, penalty_field: &[f32]) -> f32 {
+        let mut wave_score: Wave = 0.0;
+        let mut total_penalty = 0.0;
+
+        for j in 0..bin_per_action { 
+            let idx = (center_idx + j) % self.dim;
+            let (re, im) = (self.psi_real[idx], self.psi_imag[idx]);
+            wave_score += (re.powi(2) + im.powi(2)).sqrt() * (im.atan2(re) - self.theta[idx]).cos();
+            total_penalty += penalty_field.get(idx).cloned().unwrap_or(0.0);
+        }
+
+        let mut score = from_wave(wave_score);
+        if self.interference_weight != 0.0 {
+            let cross: f64 = (0..bin_per_action).map(|j| self.memory_cross_term((center_idx + j) % self.dim)).sum();
+            score += self.interference_weight * cross as f32;
+        }
+        score -= total_penalty * 0.5;
+        
+        // Scaled Score Normalization (similar to Transformer's 1/sqrt(d))
+        // Prevents score explosion as the number of bins increases.
+        score /= (bin_per_action as f32).sqrt();
+        
+        // Linear score. Noise/Jitter is now handled at the decision level (Top-k Softmax).
+        score
+    }
+
+    /// ビン idx における現在の波と記憶波（現在の入力署名で取り出したもの）の干渉項 Re(ψ·conj(m))
+    /// 強め合う（良い結果として刻まれた位相と揃う）と正、弱め合うと負。1 ビンの波の振幅と同程度の大きさに揃える
+    fn memory_cross_term(&self, idx: usize) -> f64 {
+        let (sig_re, sig_im) = (self.input_signature_re[idx] as f64, self.input_signature_im[idx] as f64);
+        let sig_mag = (sig_re.powi(2) + sig_im.powi(2)).sqrt();
+        if sig_mag < 1e-9 { return 0.0; }
+        let (u_re, u_im) = (sig_re / sig_mag, sig_im / sig_mag);
+        let mem_re = self.q_memory_re[idx] * u_re - self.q_memory_im[idx] * u_im;
+        let mem_im = self.q_memory_re[idx] * u_im + self.q_memory_im[idx] * u_re;
+        let cross = wave_to_f64(self.psi_real[idx]) * mem_re + wave_to_f64(self.psi_imag[idx]) * mem_im;
+        cross * (self.dim as f64).sqrt() / Q_MEMORY_NORM
     }
 
     pub fn adapt(&mut self, state_idx: usize, reward: f32, last_actions: &[usize], system_temp: f32, action_size: usize) {
         // 高次元ほど学習を慎重に（勾配爆発的な位相変化を防ぐ）
         let dim_factor = (1024.0 / self.dim as f32).sqrt().min(1.0);
         let annealing = (system_temp * 0.5).clamp(0.1, 1.0);
-        let base_lr = 1.2 * annealing * dim_factor; 
-        let bin_per_action = self.dim / action_size;
+        let base_lr = 1.2 * annealing * dim_factor * self.lr_scale;
         let t_len = self.theta.len();
+        let cfg = self.config;
 
         for &action_idx in last_actions {
-            let base_idx = action_idx * bin_per_action;
+            let bins = self.action_bins(action_idx, action_size);
 
-            if reward > 1.2 {
+            if reward > cfg.gravity_reward_threshold {
                 // 強力な報酬：重力場を形成（ブラックホール化）
-                for j in 0..bin_per_action {
-                    let idx = (base_idx + j) % self.dim;
-                    self.gravity_field[idx] = (self.gravity_field[idx] + 0.1 * dim_factor).min(1.0);
+                for idx in bins.clone() {
+                    self.set_gravity(idx, (self.gravity(idx) + cfg.gravity_increment * dim_factor).min(cfg.gravity_cap));
                 }
             }
             
@@ -530,32 +1457,29 @@ impl MWSO {
             self.imprint_qcel(state_idx, reward * fidelity as f32);
 
             if reward < 0.0 {
-                for j in 0..bin_per_action {
-                    let idx = (base_idx + j) % self.dim;
+                for idx in bins {
                     self.frequencies[idx] = (self.frequencies[idx] + 0.001).clamp(0.0, 2.0 * PI);
-                    self.gravity_field[idx] *= 0.8; // 失敗は重力を弱める
+                    self.set_gravity(idx, self.gravity(idx) * cfg.gravity_failure_erosion); // 失敗は重力を弱める
                 }
             }
             for neighborhood in -1..=1 {
                 let weight = if neighborhood == 0 { 1.0 } else { 0.1 }; // Restore to 0.1
                 let target_action = (action_idx as i32 + neighborhood).rem_euclid(action_size as i32) as usize;
                 let lr = base_lr * weight;
-                let n_base = target_action * bin_per_action;
-                for j in 0..bin_per_action {
-                    let idx = (n_base + j) % self.dim;
+                for idx in self.action_bins(target_action, action_size) {
                     let current_phase = self.psi_imag[idx].atan2(self.psi_real[idx]);
-                    let target_phase = if reward > 0.0 { 0.0 } else { PI };
+                    let target_phase = if reward > 0.0 { 0.0 } else { wave::PI };
                     let phase_diff_sin = (target_phase - current_phase).sin();
                     
                     // 重力が強い場所は、位相が「固定」されやすくなる
                     // Reduce inertia effect for better high-dim adaptation (Improvement 1)
-                    let gravity_inertia = 1.0 - self.gravity_field[idx] * 0.2;
-                    self.theta[idx] = (self.theta[idx] + phase_diff_sin * lr * gravity_inertia).clamp(-PI, PI);
+                    let gravity_inertia = 1.0 - self.gravity(idx) * 0.2;
+                    self.theta[idx] = (self.theta[idx] + phase_diff_sin * to_wave(lr) * to_wave(gravity_inertia)).clamp(-wave::PI, wave::PI);
                     
                     if reward > 0.0 {
                         let (sin_p, cos_p) = current_phase.sin_cos();
-                        self.psi_real[idx] += 3.0 * reward * cos_p * dim_factor;
-                        self.psi_imag[idx] += 3.0 * reward * sin_p * dim_factor;
+                        self.psi_real[idx] += 3.0 * to_wave(reward) * cos_p * to_wave(dim_factor);
+                        self.psi_imag[idx] += 3.0 * to_wave(reward) * sin_p * to_wave(dim_factor);
                         self.theta[(idx + self.dim) % t_len] = 1.0; 
                     }
                 }
@@ -564,78 +1488,181 @@ impl MWSO {
 
         // ホーキング放射（重力場の自然蒸発）
         // Faster evaporation for fluid adaptation (Improvement 1)
-        for g in &mut self.gravity_field { *g *= 0.995; }
+        match self.compact_gravity.as_mut() {
+            None => for g in &mut self.gravity_field { *g *= cfg.hawking_decay; },
+            Some(store) => store.scale_row(0, cfg.hawking_decay),
+        }
     }
 
     /// 行動から動機を逆算するための位相アライメント
     pub fn align_to_action(&mut self, action_idx: usize, strength: f32, action_size: usize) {
-        let bin_per_action = self.dim / action_size;
-        let base_idx = (action_idx * bin_per_action) % self.dim;
         let lr = 0.5 * strength;
 
-        for j in 0..bin_per_action {
-            let idx = (base_idx + j) % self.dim;
+        for idx in self.action_bins(action_idx, action_size) {
             let current_phase = self.psi_imag[idx].atan2(self.psi_real[idx]);
             let target_phase = 0.0;
             let phase_diff_sin = (target_phase - current_phase).sin();
-            self.theta[idx] = (self.theta[idx] + phase_diff_sin * lr).clamp(-PI, PI);
-            self.psi_real[idx] += 0.2 * strength;
-            self.gravity_field[idx] = (self.gravity_field[idx] + 0.01 * strength).min(0.5);
+            self.theta[idx] = (self.theta[idx] + phase_diff_sin * to_wave(lr)).clamp(-wave::PI, wave::PI);
+            self.psi_real[idx] += to_wave(0.2 * strength);
+            self.set_gravity(idx, (self.gravity(idx) + 0.01 * strength).min(0.5));
         }
     }
 
     /// 負のフィードバックに基づき、行動を抑制するための逆方向アライメント
     pub fn suppress_action(&mut self, action_idx: usize, strength: f32, action_size: usize) {
-        let bin_per_action = self.dim / action_size;
-        let base_idx = (action_idx * bin_per_action) % self.dim;
         let lr = 0.5 * strength;
 
-        for j in 0..bin_per_action {
-            let idx = (base_idx + j) % self.dim;
+        for idx in self.action_bins(action_idx, action_size) {
             let current_phase = self.psi_imag[idx].atan2(self.psi_real[idx]);
             // 逆位相である PI をターゲットにする
-            let target_phase = PI;
+            let target_phase = wave::PI;
             let phase_diff_sin = (target_phase - current_phase).sin();
-            self.theta[idx] = (self.theta[idx] + phase_diff_sin * lr).clamp(-PI, PI);
+            self.theta[idx] = (self.theta[idx] + phase_diff_sin * to_wave(lr)).clamp(-wave::PI, wave::PI);
             
             // 波動の振幅を減衰させる
-            self.psi_real[idx] *= 1.0 - (0.1 * strength);
-            self.psi_imag[idx] *= 1.0 - (0.1 * strength);
+            self.psi_real[idx] *= to_wave(1.0 - (0.1 * strength));
+            self.psi_imag[idx] *= to_wave(1.0 - (0.1 * strength));
 
             // 重力場を弱める
-            self.gravity_field[idx] = (self.gravity_field[idx] - 0.02 * strength).max(0.0);
+            self.set_gravity(idx, (self.gravity(idx) - 0.02 * strength).max(0.0));
         }
     }
 
-    pub fn inject_exploration_noise(&mut self, strength: f32) {
-        for i in 0..self.dim {
-            let noise = (self.next_rng() - 0.5) * 2.0;
-            self.psi_real[i] += noise * strength;
+    /// 重力場全体の平均・最大値と、threshold を超えたビンの数
+    pub fn gravity_stats(&self, threshold: f32) -> GravityStats {
+        let field = self.gravity_values();
+        if field.is_empty() { return GravityStats::default(); }
+        GravityStats {
+            mean: field.iter().sum::<f32>() / field.len() as f32,
+            max: field.iter().cloned().fold(0.0, f32::max),
+            above_threshold: field.iter().filter(|&&g| g > threshold).count(),
         }
     }
 
+    /// アクションの担当ビンにかかる重力の平均
+    pub fn gravity_for_action(&self, action_idx: usize, action_size: usize) -> f32 {
+        let bins = self.action_bins(action_idx, action_size);
+        if bins.is_empty() { return 0.0; }
+        let width = bins.len();
+        bins.map(|idx| self.gravity(idx)).sum::<f32>() / width as f32
+    }
+
+    /// アクションの担当ビンの重力を amount だけ削る（ブラックホール化した行動の不安定化）
+    pub fn erode_gravity(&mut self, action_idx: usize, amount: f32, action_size: usize) {
+        for idx in self.action_bins(action_idx, action_size) {
+            self.set_gravity(idx, (self.gravity(idx) - amount).max(0.0));
+        }
+    }
+
+    /// 行動の担当ビンに事前知識の偏りをかける（学習前のウォームスタート用）
+    /// 正の strength は位相の基準 θ を波の位相へ寄せて重力を足し、負なら逆位相へ寄せて重力を削る（|strength| は 1 で頭打ち）
+    pub fn bias_action(&mut self, action_idx: usize, action_size: usize, strength: f32) {
+        let weight = strength.abs().min(1.0);
+        let cap = self.config.gravity_cap;
+        for idx in self.action_bins(action_idx, action_size) {
+            let phase = self.psi_imag[idx].atan2(self.psi_real[idx]);
+            let target = if strength > 0.0 { phase } else { phase + wave::PI };
+            let diff = target - self.theta[idx];
+            let wrapped = diff.sin().atan2(diff.cos());
+            self.theta[idx] = (self.theta[idx] + wrapped * to_wave(weight)).clamp(-wave::PI, wave::PI);
+            let gravity = self.gravity(idx);
+            self.set_gravity(idx, if strength > 0.0 { (gravity + weight * cap).min(cap) } else { gravity * (1.0 - weight) });
+        }
+    }
+
+    /// ビン idx の重力（量子化保持中はその場で f32 に戻す。範囲外は 0）
+    pub fn gravity(&self, idx: usize) -> f32 {
+        match &self.compact_gravity {
+            Some(store) => store.get(idx),
+            None => self.gravity_field.get(idx).copied().unwrap_or(0.0),
+        }
+    }
+
+    fn set_gravity(&mut self, idx: usize, value: f32) {
+        match self.compact_gravity.as_mut() {
+            Some(store) => store.set(idx, value),
+            None => if let Some(g) = self.gravity_field.get_mut(idx) { *g = value; },
+        }
+    }
+
+    /// 重力場全体（量子化保持中は f32 に戻した複製）
+    pub fn gravity_values(&self) -> std::borrow::Cow<'_, [f32]> {
+        match &self.compact_gravity {
+            Some(store) => store.to_f32().into(),
+            None => (&self.gravity_field[..]).into(),
+        }
+    }
+
+    /// 重力場全体を values で置き換える（長さが dim と違えば何もしない）
+    pub fn set_gravity_values(&mut self, values: &[f32]) {
+        if values.len() != self.dim { return; }
+        match self.compact_gravity.as_mut() {
+            Some(store) => store.write_row(0, values),
+            None => self.gravity_field.copy_from_slice(values),
+        }
+    }
+
+    pub fn gravity_precision(&self) -> FieldPrecision {
+        self.compact_gravity.as_ref().map_or(FieldPrecision::F32, QuantizedRows::precision)
+    }
+
+    /// 重力場を保持する精度を切り替える。F32 以外では gravity_field は空になり、読み書きは gravity() 経由になる
+    pub fn set_gravity_precision(&mut self, precision: FieldPrecision) {
+        let values = match self.compact_gravity.take() {
+            Some(store) => store.to_f32(),
+            None => std::mem::take(&mut self.gravity_field),
+        };
+        match precision {
+            FieldPrecision::F32 => self.gravity_field = values,
+            _ => self.compact_gravity = Some(QuantizedRows::new(precision, self.dim, &values)),
+        }
+    }
+
+    /// 探索ノイズの生成器から次の tick のノイズを引き、strength 倍して psi_real に加える
+    pub fn inject_exploration_noise(&mut self, strength: f32) {
+        let mut scratch = std::mem::take(&mut self.scratch);
+        scratch.noise.resize(self.dim, 0.0);
+        let noise = &mut scratch.noise[..self.dim];
+        let seed = &mut self.rng_seed;
+        self.exploration.next_into(noise, &mut || lcg_next(seed));
+        for (psi, &x) in self.psi_real.iter_mut().zip(noise.iter()) { *psi += to_wave(x * strength); }
+        self.scratch = scratch;
+    }
+
+    pub fn exploration_noise(&self) -> ExplorationNoise {
+        self.exploration.mode()
+    }
+
+    /// 探索ノイズの分布を切り替える（OU・ピンクノイズの状態はリセットされる）
+    pub fn set_exploration_noise(&mut self, mode: ExplorationNoise) {
+        self.exploration.set_mode(mode);
+    }
+
     /// 特定のアクション領域（Bin）にエネルギーを集中照射し、探索を促す
     pub fn illuminate_bin(&mut self, action_idx: usize, action_size: usize, strength: f32) {
-        let bin_per_action = self.dim / action_size;
-        let start_idx = (action_idx * bin_per_action) % self.dim;
-        
-        for i in 0..bin_per_action {
-            let idx = (start_idx + i) % self.dim;
+        for idx in self.action_bins(action_idx, action_size) {
             let noise = (self.next_rng() - 0.5) * 0.2;
             // 位相をある程度揃えて注入することで、ノイズよりも強い「指向性」を持たせる
-            self.psi_real[idx] += (1.0 + noise) * strength;
-            self.psi_imag[idx] += noise * strength;
+            self.psi_real[idx] += to_wave((1.0 + noise) * strength);
+            self.psi_imag[idx] += to_wave(noise * strength);
         }
     }
 
     pub fn inject_external_state(&mut self, psi_real: &[f32], psi_imag: &[f32], strength: f32) {
         if psi_real.len() != self.dim || psi_imag.len() != self.dim { return; }
         for i in 0..self.dim {
-            self.psi_real[i] += psi_real[i] * strength;
-            self.psi_imag[i] += psi_imag[i] * strength;
+            self.psi_real[i] += to_wave(psi_real[i] * strength);
+            self.psi_imag[i] += to_wave(psi_imag[i] * strength);
         }
     }
 
+    /// inject_external_state と同じ場を duration ステップの間 step_core ごとに自動で注入する
+    /// strength が負なら場の振幅に比例してそのビンの波を減衰させる。次元が合わなければ None
+    pub fn schedule_external_state(&mut self, psi_real: &[f32], psi_imag: &[f32], strength: f32, duration: u32, decay: FieldDecay) -> Option<u64> {
+        if psi_real.len() != self.dim || psi_imag.len() != self.dim { return None; }
+        Some(self.field_schedule.schedule(psi_real, psi_imag, strength, duration, decay))
+    }
+
     pub fn calculate_rhyd(&self) -> f32 {
         let mut rd = 0.0;
         let mut active_components = 0.0;
@@ -647,7 +1674,179 @@ impl MWSO {
                 active_components += 1.0;
             }
         }
-        rd * (active_components / self.dim as f32) * 100.0
+        from_wave(rd * (active_components / self.dim as Wave) * 100.0)
+    }
+
+    /// offset から size 個のビンの位相の揃い具合（蔵本の秩序変数 R = |Σ e^{iφ}| / size）
+    /// 振幅を含まない純粋な位相の指標で、1 に近いほど結晶化している。ほぼ静止したビンは数えない
+    pub fn phase_coherence(&self, offset: usize, size: usize) -> f32 {
+        let (mut sum_cos, mut sum_sin, mut count): (Wave, Wave, usize) = (0.0, 0.0, 0);
+        for j in 0..size.min(self.dim) {
+            let idx = (offset + j) % self.dim;
+            let (re, im) = (self.psi_real[idx], self.psi_imag[idx]);
+            if re * re + im * im <= 1e-12 { continue; }
+            let (sin_p, cos_p) = im.atan2(re).sin_cos();
+            sum_cos += cos_p;
+            sum_sin += sin_p;
+            count += 1;
+        }
+        if count == 0 { return 0.0; }
+        from_wave((sum_cos * sum_cos + sum_sin * sum_sin).sqrt() / count as Wave)
+    }
+
+    /// 行動 action_idx の担当ビンの位相コヒーレンス
+    pub fn action_phase_coherence(&self, action_idx: usize, action_size: usize) -> f32 {
+        let bins = self.action_bins(action_idx, action_size);
+        self.phase_coherence(bins.start, bins.len())
+    }
+
+    /// 波の状態の診断値をまとめて求める
+    pub fn diagnostics(&self) -> WaveDiagnostics {
+        let mut energy_sum: Wave = 0.0;
+        let (mut dominant_bin, mut dominant_energy): (usize, Wave) = (0, 0.0);
+        let (mut dot_re, mut dot_im, mut mem_sq) = (0.0f64, 0.0f64, 0.0f64);
+        for i in 0..self.dim {
+            let (re, im) = (self.psi_real[i], self.psi_imag[i]);
+            let e = re * re + im * im;
+            energy_sum += e;
+            if e > dominant_energy { dominant_bin = i; dominant_energy = e; }
+            // conj(q_memory) * psi
+            let (m_re, m_im) = (self.q_memory_re[i], self.q_memory_im[i]);
+            let (p_re, p_im) = (wave_to_f64(re), wave_to_f64(im));
+            dot_re += m_re * p_re + m_im * p_im;
+            dot_im += m_re * p_im - m_im * p_re;
+            mem_sq += m_re * m_re + m_im * m_im;
+        }
+        let psi_sq = wave_to_f64(energy_sum);
+        let memory_resonance = if mem_sq < 1e-18 || psi_sq < 1e-18 {
+            0.0
+        } else {
+            ((dot_re * dot_re + dot_im * dot_im).sqrt() / (mem_sq.sqrt() * psi_sq.sqrt())) as f32
+        };
+        let saturated = self.gravity_stats(GRAVITY_SATURATION_LEVEL).above_threshold;
+
+        WaveDiagnostics {
+            norm: from_wave(energy_sum.sqrt()),
+            spectral_entropy: self.wave_entropy(),
+            dominant_bin,
+            dominant_frequency: self.frequencies.get(dominant_bin).cloned().unwrap_or(0.0),
+            memory_resonance,
+            gravity_saturation: if self.dim > 0 { saturated as f32 / self.dim as f32 } else { 0.0 },
+            entanglement_count: self.entanglements.len(),
+        }
+    }
+
+    /// 入力 input_idx を刻み込むときの鍵（署名）。recall の問い合わせに使う
+    pub fn state_query(&self, input_idx: usize) -> (Vec<f32>, Vec<f32>) {
+        let offset = (input_idx as f32 * 1.618).rem_euclid(2.0 * PI);
+        (0..self.dim).map(|i| {
+            let (re, im) = self.imprint_signature(i, offset);
+            (re as f32, im as f32)
+        }).unzip()
+    }
+
+    /// 鍵 (query_re, query_im) で記憶波に問い合わせる（波の状態は変えない）
+    /// 想起パターンは アクティブな記憶波 × 鍵 で、recall_at と同じく現在の波と逆位相のビンは減衰させ、
+    /// 強く共鳴するビンは増幅する。resonance は想起パターン全体と現在の波との一致度 (-1..1)
+    pub fn recall(&self, query_re: &[f32], query_im: &[f32]) -> RecallResult {
+        let dim = self.dim.min(query_re.len()).min(query_im.len());
+        let (mut pattern_re, mut pattern_im) = (vec![0.0; self.dim], vec![0.0; self.dim]);
+        let (mut dot, mut rec_sq, mut psi_sq) = (0.0f64, 0.0f64, 0.0f64);
+        for i in 0..dim {
+            let (q_re, q_im) = (query_re[i] as f64, query_im[i] as f64);
+            let q_mag = (q_re * q_re + q_im * q_im).sqrt();
+            if q_mag < 1e-12 { continue; }
+            let (m_re, m_im) = (self.q_memory_re[i], self.q_memory_im[i]);
+            let rec_re = (m_re * q_re - m_im * q_im) / q_mag;
+            let rec_im = (m_re * q_im + m_im * q_re) / q_mag;
+
+            let (psi_re, psi_im) = (wave_to_f64(self.psi_real[i]), wave_to_f64(self.psi_imag[i]));
+            let d = psi_re * rec_re + psi_im * rec_im;
+            let (r_sq, p_sq) = (rec_re * rec_re + rec_im * rec_im, psi_re * psi_re + psi_im * psi_im);
+            dot += d;
+            rec_sq += r_sq;
+            psi_sq += p_sq;
+
+            let alignment = d / ((r_sq * p_sq).sqrt() + 1e-12);
+            let gain = if alignment < -0.3 {
+                (1.0 + alignment).max(0.0)
+            } else if alignment > 0.6 {
+                1.0 + (alignment - 0.6).powi(2) * 5.0
+            } else {
+                1.0
+            };
+            pattern_re[i] = (rec_re * gain) as f32;
+            pattern_im[i] = (rec_im * gain) as f32;
+        }
+        let resonance = if rec_sq < 1e-18 || psi_sq < 1e-18 { 0.0 } else { dot / (rec_sq.sqrt() * psi_sq.sqrt()) };
+        RecallResult { resonance: resonance as f32, pattern_re, pattern_im }
+    }
+
+    /// 記憶の統合（睡眠時のリプレイ）: 記憶波全体を decay だけ弱めてから、
+    /// アクティブなバンクのリプレイ用パターンを強度に比例して刻み直す
+    /// 繰り返し刻まれた強いパターンは残り、一度きりの弱い干渉は薄れる。再生したパターン数を返す
+    pub fn consolidate(&mut self, decay: f32) -> usize {
+        let bank = self.active_bank;
+        let replayed = self.replay.traces().iter().filter(|t| t.bank == bank).count();
+        if replayed == 0 { return 0; }
+        let keep = (1.0 - decay.clamp(0.0, 1.0)) as f64;
+        for v in self.q_memory_re.iter_mut().chain(self.q_memory_im.iter_mut()) { *v *= keep; }
+
+        let dim_norm = (self.dim as f64).sqrt();
+        for trace in self.replay.traces().iter().filter(|t| t.bank == bank) {
+            let offset = (trace.input_idx as f32 * 1.618).rem_euclid(2.0 * PI);
+            let alpha = trace.strength as f64 / dim_norm;
+            for i in 0..self.dim {
+                let (p_sin, p_cos) = trace.phases[i].sin_cos();
+                let (sig_re, sig_im) = self.imprint_signature(i, offset);
+                // imprint_qcel と同じく パターン × conj(署名)
+                self.q_memory_re[i] += (p_cos as f64 * sig_re + p_sin as f64 * sig_im) * alpha;
+                self.q_memory_im[i] += (p_sin as f64 * sig_re - p_cos as f64 * sig_im) * alpha;
+            }
+        }
+        self.normalize_q_memory(Q_MEMORY_NORM);
+        replayed
+    }
+
+    /// 入力 input_idx の記憶を取り出したときの SNR（scaling_laws_bench の干渉 SNR と同じ定義）
+    /// 現在の波の各ビンの位相を取り出し対象のパターンとみなし、想起成分との内積を信号、
+    /// 記憶波の全エネルギーから信号を除いた分をアクティブなバンクの刻み込み回数で割ったものを雑音とする
+    pub fn retrieval_snr(&self, input_idx: usize) -> f32 {
+        if self.dim == 0 { return 0.0; }
+        let offset = (input_idx as f32 * 1.618).rem_euclid(2.0 * PI);
+        let inv_sqrt_dim = 1.0 / (self.dim as f64).sqrt();
+        let (mut s_re, mut total) = (0.0f64, 0.0f64);
+        for i in 0..self.dim {
+            let (q_re, q_im) = (self.q_memory_re[i], self.q_memory_im[i]);
+            total += q_re * q_re + q_im * q_im;
+            let psi_re = wave_to_f64(self.psi_real[i]);
+            let psi_im = wave_to_f64(self.psi_imag[i]);
+            let psi_mag = (psi_re.powi(2) + psi_im.powi(2)).sqrt() + 1e-9;
+            let (sig_re, sig_im) = self.imprint_signature(i, offset);
+            // 想起 = 記憶 × 署名
+            let rec_re = q_re * sig_re - q_im * sig_im;
+            let rec_im = q_re * sig_im + q_im * sig_re;
+            s_re += (psi_re * rec_re + psi_im * rec_im) / psi_mag * inv_sqrt_dim;
+        }
+        let signal_sq = s_re * s_re;
+        // 忘却により実効的に残っているのは直近 1 / BASE_FORGET_RATE 回分程度
+        let imprints = self.memory_banks[self.active_bank].imprints as f64;
+        let patterns = imprints.clamp(1.0, 1.0 / BASE_FORGET_RATE);
+        let noise_sq = (total - signal_sq).max(0.0) / patterns;
+        if noise_sq < 1e-10 { return 100.0; }
+        (signal_sq / noise_sq).sqrt() as f32
+    }
+
+    /// 波動エネルギー分布の正規化シャノンエントロピー (0: 一点集中, 1: 完全に一様)
+    pub fn wave_entropy(&self) -> f32 {
+        let total: Wave = (0..self.dim).map(|i| self.psi_real[i].powi(2) + self.psi_imag[i].powi(2)).sum();
+        if total <= 1e-12 || self.dim < 2 { return 1.0; }
+        let mut h = 0.0;
+        for i in 0..self.dim {
+            let p = (self.psi_real[i].powi(2) + self.psi_imag[i].powi(2)) / total;
+            if p > 1e-12 { h -= p * p.ln(); }
+        }
+        from_wave(h / (self.dim as Wave).ln())
     }
 
     pub fn calculate_ipr(&self) -> f32 {
@@ -660,16 +1859,128 @@ impl MWSO {
         }
         // 正規化してD不変にする
         if norm_sq > 1e-10 {
-            ipr / (norm_sq * norm_sq) * self.dim as f32
+            from_wave(ipr / (norm_sq * norm_sq) * self.dim as Wave)
         } else {
             0.0
         }
     }
 }
 
+/// N 要素分の位相回転と想起ブーストをまとめて適用する（N = LANES ならベクトル化される）
+/// 虚部は減衰まで適用して書き戻し、実部は結合項を足す前の値を rotated_re に書く
+/// 各スライスはちょうど N 要素であること
+#[inline(always)]
+fn rotate_lanes<const N: usize>(
+    psi: (&[Wave], &mut [Wave]),
+    phase: (&[Wave], &[Wave]),
+    recall: (&[Wave], &[Wave]),
+    damping: &[Wave],
+    rotated_re: &mut [Wave],
+    (recall_boost, effective_dt): (Wave, Wave),
+) {
+    let re: &[Wave; N] = psi.0.try_into().unwrap();
+    let im: &mut [Wave; N] = psi.1.try_into().unwrap();
+    let sin_w: &[Wave; N] = phase.0.try_into().unwrap();
+    let cos_w: &[Wave; N] = phase.1.try_into().unwrap();
+    let rec_re: &[Wave; N] = recall.0.try_into().unwrap();
+    let rec_im: &[Wave; N] = recall.1.try_into().unwrap();
+    let damping: &[Wave; N] = damping.try_into().unwrap();
+    let out_re: &mut [Wave; N] = rotated_re.try_into().unwrap();
+    for l in 0..N {
+        out_re[l] = re[l] * cos_w[l] - im[l] * sin_w[l] + rec_re[l] * recall_boost * effective_dt;
+        let new_im = re[l] * sin_w[l] + im[l] * cos_w[l] + rec_im[l] * recall_boost * effective_dt;
+        im[l] = new_im * damping[l];
+    }
+}
+
+/// 任意長のブロックを LANES 幅のチャンクと端数に分けて rotate_lanes を適用する
+fn rotate_block(
+    psi: (&[Wave], &mut [Wave]),
+    phase: (&[Wave], &[Wave]),
+    recall: (&[Wave], &[Wave]),
+    damping: &[Wave],
+    rotated_re: &mut [Wave],
+    gain: (Wave, Wave),
+) {
+    let chunked = damping.len() - damping.len() % LANES;
+    let (re_body, re_tail) = psi.0.split_at(chunked);
+    let (im_body, im_tail) = psi.1.split_at_mut(chunked);
+    let (sin_body, sin_tail) = phase.0.split_at(chunked);
+    let (cos_body, cos_tail) = phase.1.split_at(chunked);
+    let (rre_body, rre_tail) = recall.0.split_at(chunked);
+    let (rim_body, rim_tail) = recall.1.split_at(chunked);
+    let (damp_body, damp_tail) = damping.split_at(chunked);
+    let (out_body, out_tail) = rotated_re.split_at_mut(chunked);
+    for (((((((re, im), sin_w), cos_w), rec_re), rec_im), damping), out) in re_body.chunks_exact(LANES)
+        .zip(im_body.chunks_exact_mut(LANES))
+        .zip(sin_body.chunks_exact(LANES)).zip(cos_body.chunks_exact(LANES))
+        .zip(rre_body.chunks_exact(LANES)).zip(rim_body.chunks_exact(LANES))
+        .zip(damp_body.chunks_exact(LANES)).zip(out_body.chunks_exact_mut(LANES)) {
+        rotate_lanes::<LANES>((re, im), (sin_w, cos_w), (rec_re, rec_im), damping, out, gain);
+    }
+    // 端数はスカラーで処理する
+    for i in 0..re_tail.len() {
+        rotate_lanes::<1>(
+            (&re_tail[i..=i], &mut im_tail[i..=i]),
+            (&sin_tail[i..=i], &cos_tail[i..=i]),
+            (&rre_tail[i..=i], &rim_tail[i..=i]),
+            &damp_tail[i..=i],
+            &mut out_tail[i..=i],
+            gain,
+        );
+    }
+}
+
+/// 実部の隣接結合をブロックの先頭から順に適用する（左隣はレジスタに持ち回す）
+/// prev はブロック先頭の左隣、next は末尾の右隣で、None なら更新後のブロック先頭（環状の折り返し）を使う
+fn couple_block(
+    psi: &mut [Wave],
+    mut prev: Wave,
+    next: Option<Wave>,
+    (theta, rotated_re, damping): (&[Wave], &[Wave], &[Wave]),
+    (dim_scale, effective_dt): (Wave, Wave),
+) {
+    let len = psi.len();
+    for i in 0..len {
+        let right = if i + 1 == len { next.unwrap_or(psi[0]) } else { psi[i + 1] };
+        let coupling = theta[i] * (right + prev) / dim_scale;
+        prev = (rotated_re[i] + coupling * effective_dt) * damping[i];
+        psi[i] = prev;
+    }
+}
+
+/// 環状に並んだ値を new_len 個に写す（拡大は線形補間、縮小は区間平均）
+fn resample_ring(src: &[f64], new_len: usize) -> Vec<f64> {
+    let n = src.len();
+    if n == 0 { return vec![0.0; new_len]; }
+    if new_len == n { return src.to_vec(); }
+    let scale = n as f64 / new_len as f64;
+    if new_len > n {
+        (0..new_len).map(|j| {
+            let x = ((j as f64 + 0.5) * scale - 0.5).rem_euclid(n as f64);
+            let i = x.floor() as usize % n;
+            let t = x - x.floor();
+            src[i] * (1.0 - t) + src[(i + 1) % n] * t
+        }).collect()
+    } else {
+        (0..new_len).map(|j| {
+            let (lo, hi) = (j as f64 * scale, (j + 1) as f64 * scale);
+            let mut acc = 0.0;
+            let mut i = lo.floor() as usize;
+            while (i as f64) < hi {
+                let overlap = (hi.min(i as f64 + 1.0) - lo.max(i as f64)).max(0.0);
+                acc += src[i.min(n - 1)] * overlap;
+                i += 1;
+            }
+            acc / scale
+        }).collect()
+    }
+}
+
 /// 複数の1024次元MWSOシャードの直和空間
 /// H_total = H_0 ⊕ H_1 ⊕ ... ⊕ H_n
 /// 計算量O(1024)×シャード数、表現能力はシャード数×1024
+#[derive(Clone)]
 pub struct ShardedMWSO {
     pub shards: Vec<MWSO>,
     pub shard_dim: usize,       // 各シャードの次元（固定1024）
@@ -679,6 +1990,11 @@ pub struct ShardedMWSO {
     pub inter_shard_tunnels: HashMap<(usize, usize, usize, usize), f32>,
     // 状態とシャードの親和性 (state_idx -> shard_affinities)
     pub state_affinities: HashMap<usize, Vec<f32>>,
+    /// シャード単位の作業領域（毎ステップの再確保を避けるためのもので、状態ではない）
+    local_penalty: Vec<f32>,
+    shard_weights: Vec<f32>,
+    /// 全シャードの波を連結して他スレッドの読み手へ公開する（wave_view を呼ぶまでは無効）
+    publisher: WavePublisher,
 }
 
 impl ShardedMWSO {
@@ -698,9 +2014,41 @@ impl ShardedMWSO {
             actions_per_shard,
             inter_shard_tunnels: HashMap::new(),
             state_affinities: HashMap::new(),
+            local_penalty: vec![0.0; shard_dim],
+            shard_weights: Vec::with_capacity(num_shards),
+            publisher: WavePublisher::default(),
+        }
+    }
+
+    /// penalty_field のうち shard_idx が担当する範囲を、シャードローカルなペナルティ場として local に書き込む
+    fn fill_local_penalty(&self, shard_idx: usize, penalty_field: &[f32], local: &mut Vec<f32>) {
+        let bin_per_action = self.shard_dim / self.actions_per_shard;
+        let action_start = shard_idx * self.actions_per_shard;
+        let action_end = (action_start + self.actions_per_shard).min(self.total_action_size);
+        let slice_start = action_start * bin_per_action;
+        let slice_end = action_end * bin_per_action;
+
+        local.clear();
+        local.resize(self.shard_dim, 0.0);
+        if slice_end > slice_start && slice_end <= penalty_field.len() {
+            let relevant_slice = &penalty_field[slice_start..slice_end];
+            local[..relevant_slice.len()].copy_from_slice(relevant_slice);
         }
     }
  
+    pub fn copy_from(&mut self, other: &ShardedMWSO) {
+        if self.shards.len() == other.shards.len() {
+            for (shard, src) in self.shards.iter_mut().zip(&other.shards) { shard.copy_from(src); }
+        } else {
+            self.shards.clone_from(&other.shards);
+        }
+        self.shard_dim = other.shard_dim;
+        self.total_action_size = other.total_action_size;
+        self.actions_per_shard = other.actions_per_shard;
+        self.inter_shard_tunnels.clone_from(&other.inter_shard_tunnels);
+        self.state_affinities.clone_from(&other.state_affinities);
+    }
+
     /// 新設：シャード間の情報伝達トンネルを追加・強化する
     pub fn add_or_strengthen_tunnel(&mut self, from_shard_idx: usize, to_shard_idx: usize, from_state_idx: usize, local_to_action: usize, strength: f32) {
         if from_shard_idx >= self.shards.len() || to_shard_idx >= self.shards.len() || from_shard_idx == to_shard_idx {
@@ -727,29 +2075,34 @@ impl ShardedMWSO {
     }
 
     pub fn get_action_scores(&mut self, penalty_field: &[f32]) -> Vec<f32> {
-        let mut scores = Vec::with_capacity(self.total_action_size);
-        let bin_per_action = self.shard_dim / self.actions_per_shard;
+        let mut scores = vec![0.0; self.total_action_size];
+        self.get_action_scores_into(penalty_field, &mut scores);
+        scores
+    }
+
+    /// get_action_scores の書き込み先指定版。scores は total_action_size 要素で、各シャードが担当範囲へ直接書き込む
+    pub fn get_action_scores_into(&mut self, penalty_field: &[f32], scores: &mut [f32]) {
+        let mut local_penalty = std::mem::take(&mut self.local_penalty);
 
         for shard_idx in 0..self.shards.len() {
             let action_start = shard_idx * self.actions_per_shard;
             let action_end = (action_start + self.actions_per_shard).min(self.total_action_size);
-            let local_size = action_end - action_start;
 
-            let slice_start = action_start * bin_per_action;
-            let slice_end = action_end * bin_per_action;
-            
-            let mut local_penalty = vec![0.0f32; self.shard_dim];
-            if slice_end > slice_start && slice_end <= penalty_field.len() {
-                 let relevant_slice = &penalty_field[slice_start..slice_end];
-                 let local_penalty_len = relevant_slice.len();
-                 local_penalty[..local_penalty_len].copy_from_slice(relevant_slice);
-            }
-
-            let shard_scores = self.shards[shard_idx]
-                .get_action_scores(0, local_size, 0.0, &local_penalty);
-            scores.extend_from_slice(&shard_scores);
+            self.fill_local_penalty(shard_idx, penalty_field, &mut local_penalty);
+            self.shards[shard_idx].get_action_scores_into(0, &local_penalty, &mut scores[action_start..action_end]);
         }
-        scores
+        self.local_penalty = local_penalty;
+    }
+
+    /// 行動 action_idx 1 つだけのスコア（担当シャードだけを読む）
+    pub fn get_action_score(&mut self, action_idx: usize, penalty_field: &[f32]) -> f32 {
+        let (shard_idx, local_action) = self.shard_for_action(action_idx);
+        let local_size = (self.total_action_size - shard_idx * self.actions_per_shard).min(self.actions_per_shard);
+        let mut local_penalty = std::mem::take(&mut self.local_penalty);
+        self.fill_local_penalty(shard_idx, penalty_field, &mut local_penalty);
+        let score = self.shards[shard_idx].get_action_score(local_action, local_size, &local_penalty);
+        self.local_penalty = local_penalty;
+        score
     }
  
     pub fn inject_state(&mut self, state_idx: usize, strength: f32, system_temp: f32, penalty_field: &[f32]) {
@@ -765,7 +2118,9 @@ impl ShardedMWSO {
 
         // 2. 温度に応じた探索・分配重みの計算
         // 高温時は全シャードに均等に近く、低温時は親和性の高いシャードに集中させる
-        let mut weights = vec![0.0; num_shards];
+        let mut weights = std::mem::take(&mut self.shard_weights);
+        weights.clear();
+        weights.resize(num_shards, 0.0);
         let mut total_weight = 0.0;
         
         // 温度が高いほど(2.0に近い)、base_prob が高くなり、全シャードが均等に選ばれやすくなる
@@ -784,10 +2139,9 @@ impl ShardedMWSO {
         }
 
         // 3. 各シャードに重みに応じた強度で注入
-        let bin_per_action = self.shard_dim / self.actions_per_shard;
-        
         // 動的なノイズカット閾値（シャード数に応じた平均の半分以下なら切る）
         let cutoff_threshold = (1.0 / num_shards as f32) * 0.5 * (1.0 - system_temp).max(0.1);
+        let mut local_penalty = std::mem::take(&mut self.local_penalty);
 
         for shard_idx in 0..num_shards {
             let shard_weight = weights[shard_idx];
@@ -795,18 +2149,7 @@ impl ShardedMWSO {
             // 閾値未満のシャードには状態を注入しない（無駄な波を立てない）
             if shard_weight < cutoff_threshold { continue; }
 
-            let action_start = shard_idx * self.actions_per_shard;
-            let action_end = (action_start + self.actions_per_shard).min(self.total_action_size);
-
-            let slice_start = action_start * bin_per_action;
-            let slice_end = action_end * bin_per_action;
-
-            let mut local_penalty = vec![0.0f32; self.shard_dim];
-            if slice_end > slice_start && slice_end <= penalty_field.len() {
-                let relevant_slice = &penalty_field[slice_start..slice_end];
-                let local_penalty_len = relevant_slice.len();
-                local_penalty[..local_penalty_len].copy_from_slice(relevant_slice);
-            }
+            self.fill_local_penalty(shard_idx, penalty_field, &mut local_penalty);
 
             let shard = &mut self.shards[shard_idx];
             // Q-CEL: Set the input query for this shard
@@ -814,6 +2157,8 @@ impl ShardedMWSO {
             // Also inject some energy into the state representation
             shard.inject_state(state_idx % shard.dim, strength * shard_weight, &local_penalty);
         }
+        self.local_penalty = local_penalty;
+        self.shard_weights = weights;
     }
 
     /// Injects a vector-based state (weighted multiple features) into the sharded system.
@@ -866,26 +2211,15 @@ impl ShardedMWSO {
         }
     }
  
+    /// penalty_field は全行動ぶんのペナルティ場で、各シャードには担当範囲を切り出して渡す
     pub fn step_core(&mut self, dt: f32, speed_boost: f32, focus_factor: f32, system_temp: f32, penalty_field: &[f32]) {
-        let bin_per_action = self.shard_dim / self.actions_per_shard;
-
         // 1. 各シャードを独立して時間発展させる
-        for (shard_idx, shard) in &mut self.shards.iter_mut().enumerate() {
-            let action_start = shard_idx * self.actions_per_shard;
-            let action_end = (action_start + self.actions_per_shard).min(self.total_action_size);
-
-            let slice_start = action_start * bin_per_action;
-            let slice_end = action_end * bin_per_action;
-            
-            let mut local_penalty = vec![0.0f32; self.shard_dim];
-            if slice_end > slice_start && slice_end <= penalty_field.len() {
-                 let relevant_slice = &penalty_field[slice_start..slice_end];
-                 let local_penalty_len = relevant_slice.len();
-                 local_penalty[..local_penalty_len].copy_from_slice(relevant_slice);
-            }
-
-            shard.step_core(dt, speed_boost, focus_factor, system_temp, &local_penalty);
+        let mut local_penalty = std::mem::take(&mut self.local_penalty);
+        for shard_idx in 0..self.shards.len() {
+            self.fill_local_penalty(shard_idx, penalty_field, &mut local_penalty);
+            self.shards[shard_idx].step_core(dt, speed_boost, focus_factor, system_temp, &local_penalty);
         }
+        self.local_penalty = local_penalty;
 
         // 2. シャード間トンネルでエネルギーを交換する
         let effective_dt = dt * (1.0 + speed_boost);
@@ -896,8 +2230,8 @@ impl ShardedMWSO {
                 (shard_from.psi_real[from_bin], shard_from.psi_imag[from_bin])
             };
             
-            let delta_re = from_psi_re * strength * effective_dt;
-            let delta_im = from_psi_im * strength * effective_dt;
+            let delta_re = from_psi_re * to_wave(strength) * to_wave(effective_dt);
+            let delta_im = from_psi_im * to_wave(strength) * to_wave(effective_dt);
 
             // エネルギーを得る側（to）
             let shard_to = &mut self.shards[to_shard];
@@ -910,6 +2244,25 @@ impl ShardedMWSO {
             *strength *= 0.995;
             *strength > 0.01
         });
+        self.publish_wave();
+    }
+
+    /// 全シャードの波をシャード順に連結したスナップショットを別スレッドから読むためのハンドル
+    /// 公開はトンネルによるエネルギー交換まで済んだ step_core の最後に行う（MWSO::wave_view と同じく、読み手がいなくなれば止まる）
+    pub fn wave_view(&mut self) -> WaveView {
+        let first = !self.publisher.is_enabled();
+        let view = self.publisher.view();
+        if first { self.publish_wave(); }
+        view
+    }
+
+    /// 現在の全シャードの波を読み手へ公開する
+    pub fn publish_wave(&mut self) {
+        if !self.publisher.is_enabled() { return; }
+        let psi_real: Vec<&[Wave]> = self.shards.iter().map(|s| s.psi_real.as_slice()).collect();
+        let psi_imag: Vec<&[Wave]> = self.shards.iter().map(|s| s.psi_imag.as_slice()).collect();
+        let theta: Vec<&[Wave]> = self.shards.iter().map(|s| s.theta.as_slice()).collect();
+        self.publisher.publish(&psi_real, &psi_imag, &theta);
     }
  
     pub fn adapt(&mut self, state_idx: usize, reward: f32, last_actions: &[usize], system_temp: f32) {
@@ -942,6 +2295,12 @@ impl ShardedMWSO {
         }
     }
 
+    /// 行動を受け持つシャードで MWSO::bias_action をかける
+    pub fn bias_action(&mut self, action_idx: usize, strength: f32) {
+        let (shard_idx, local_action) = self.shard_for_action(action_idx);
+        self.shards[shard_idx].bias_action(local_action, self.actions_per_shard, strength);
+    }
+
     /// Adapts the sharded system using a vector of states.
     pub fn adapt_vector(&mut self, state_weights: &[(usize, f32)], reward: f32, last_actions: &[usize], system_temp: f32) {
         for &action_idx in last_actions {
@@ -981,10 +2340,19 @@ impl ShardedMWSO {
         self.shards.iter().map(|s| s.calculate_ipr()).sum::<f32>() / self.shards.len() as f32
     }
  
+    pub fn action_phase_coherence(&self, action_idx: usize) -> f32 {
+        let (shard_idx, local_action) = self.shard_for_action(action_idx);
+        self.shards[shard_idx].action_phase_coherence(local_action, self.actions_per_shard)
+    }
+
     pub fn calculate_rhyd(&self) -> f32 {
         self.shards.iter().map(|s| s.calculate_rhyd()).sum::<f32>() / self.shards.len() as f32
     }
  
+    pub fn wave_entropy(&self) -> f32 {
+        self.shards.iter().map(|s| s.wave_entropy()).sum::<f32>() / self.shards.len() as f32
+    }
+
     pub fn num_shards(&self) -> usize {
         self.shards.len()
     }
@@ -1016,6 +2384,20 @@ impl ShardedMWSO {
         );
     }
 
+    pub fn gravity_stats(&self, threshold: f32) -> GravityStats {
+        GravityStats::merge(self.shards.iter().map(|shard| (shard.gravity_stats(threshold), shard.dim)))
+    }
+
+    pub fn gravity_for_action(&self, action_idx: usize) -> f32 {
+        let (shard_idx, local_action) = self.shard_for_action(action_idx);
+        self.shards[shard_idx].gravity_for_action(local_action, self.actions_per_shard)
+    }
+
+    pub fn erode_gravity(&mut self, action_idx: usize, amount: f32) {
+        let (shard_idx, local_action) = self.shard_for_action(action_idx);
+        self.shards[shard_idx].erode_gravity(local_action, amount, self.actions_per_shard);
+    }
+
     pub fn inject_state_for_action(&mut self, state_idx: usize, action_idx: usize, strength: f32, penalty_field: &[f32]) {
         let (shard_idx, _) = self.shard_for_action(action_idx);
         
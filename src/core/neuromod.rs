@@ -0,0 +1,56 @@
+// src/core/neuromod.rs
+// 神経修飾物質: 報酬予測誤差で動くドーパミンと、ストレスで下がるセロトニンの 2 つのスカラーが
+// 波動場の学習率・疲労の蓄積・探索ノイズをまとめて調整する（既定値ではすべて 1.0 倍）
+
+/// 期待報酬を報酬へ寄せる割合
+const EXPECTATION_RATE: f32 = 0.1;
+/// 1 回の learn でドーパミンが新しい予測誤差へ寄る割合
+const DOPAMINE_RATE: f32 = 0.3;
+/// 1 回の learn でセロトニンが (1 - ストレス) へ寄る割合
+const SEROTONIN_RATE: f32 = 0.1;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Neuromodulators {
+    /// 報酬予測誤差の平滑値 [-1, 1]（正なら期待以上）
+    pub dopamine: f32,
+    /// 落ち着き [0, 1]（0.5 が平常、ストレスが続くと下がる）
+    pub serotonin: f32,
+    /// 報酬の期待値（予測誤差の基準）
+    pub expected_reward: f32,
+}
+
+impl Default for Neuromodulators {
+    fn default() -> Self {
+        Self { dopamine: 0.0, serotonin: 0.5, expected_reward: 0.0 }
+    }
+}
+
+impl Neuromodulators {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 報酬とストレス [0, 1] で更新し、今回の報酬予測誤差を返す
+    pub fn update(&mut self, reward: f32, stress: f32) -> f32 {
+        let error = reward - self.expected_reward;
+        self.expected_reward += EXPECTATION_RATE * error;
+        self.dopamine += DOPAMINE_RATE * (error.tanh() - self.dopamine);
+        self.serotonin += SEROTONIN_RATE * ((1.0 - stress.clamp(0.0, 1.0)) - self.serotonin);
+        error
+    }
+
+    /// 波動場の学習率の倍率: 予想外の結果（正負とも）ほど速く学ぶ [1, 2]
+    pub fn learning_rate_scale(&self) -> f32 {
+        1.0 + self.dopamine.abs()
+    }
+
+    /// 失敗時の疲労の蓄積の倍率: セロトニンが低い（ストレス下）ほど同じ手に早く飽きる [0.5, 1.5]
+    pub fn fatigue_scale(&self) -> f32 {
+        1.5 - self.serotonin
+    }
+
+    /// 探索ノイズの倍率: 期待を下回っている間は探索を増やし、上回っている間は抑える [0, 2]
+    pub fn exploration_scale(&self) -> f32 {
+        1.0 - self.dopamine
+    }
+}
@@ -1,26 +1,182 @@
 // のロジックを移植
+use std::collections::VecDeque;
+
+#[derive(Clone)]
 pub struct Synapse {
     pub target_id: usize, // インデックスによる直接参照
     pub weight: f32,
 }
 
+/// 既定の感情ノード名（Singularity はこの名前でノードを引いて意思決定・トポロジーに使う）
+pub const NODE_AGGRESSION: &str = "aggression";
+pub const NODE_FEAR: &str = "fear";
+pub const NODE_TACTICAL: &str = "tactical";
+pub const NODE_REFLEX: &str = "reflex";
+
+/// ノードの活性で波動場の行動帯域を弱める結合（例: 恐怖が攻撃的な行動のビンを全体的に減衰させる）
+/// 活性 × gain が、対象行動のビンのペナルティ場に加わる
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeCoupling {
+    pub node: String,
+    pub actions: std::ops::Range<usize>,
+    pub gain: f32,
+}
+
+/// ノード活性の履歴の要約
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NodeHistoryStats {
+    pub mean: f32,
+    pub variance: f32,
+    /// 最小二乗で求めた 1 更新あたりの傾き（正なら上昇傾向）
+    pub trend: f32,
+}
+
+/// Sigmoid の傾き（入力 0.5 を中心に 0〜1 をほぼ覆う）
+pub const SIGMOID_GAIN: f32 = 8.0;
+
+/// ノードの応答曲線（シナプス入力を目標状態に写す関数。更新後の状態は従来どおり [0, 1] に収める）
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Activation {
+    /// 入力をそのまま使う（従来の線形な混合。目標は範囲外も許し、更新後にクランプする）
+    #[default]
+    Linear,
+    /// 入力 0.5 を中心とするロジスティック関数（弱い入力を無視し、強い入力で飽和する）
+    Sigmoid,
+    /// tanh を入力 1.0 で 1.0 になるよう正規化したもの（弱い入力に敏感に反応する）
+    Tanh,
+    /// 0 から上限までの ReLU（上限で頭打ちになる衝動）
+    ReluCap(f32),
+}
+
+impl Activation {
+    /// JNI などからの整数指定 (0=Linear, 1=Sigmoid, 2=Tanh, 3=ReluCap)。cap は ReluCap の上限
+    pub fn from_id(id: i32, cap: f32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Linear),
+            1 => Some(Self::Sigmoid),
+            2 => Some(Self::Tanh),
+            3 => Some(Self::ReluCap(cap.clamp(0.0, 1.0))),
+            _ => None,
+        }
+    }
+
+    /// from_id の逆（ReluCap 以外の cap は 0.0）
+    pub fn to_id(self) -> (i32, f32) {
+        match self {
+            Self::Linear => (0, 0.0),
+            Self::Sigmoid => (1, 0.0),
+            Self::Tanh => (2, 0.0),
+            Self::ReluCap(cap) => (3, cap),
+        }
+    }
+
+    pub fn apply(self, x: f32) -> f32 {
+        match self {
+            Self::Linear => x,
+            Self::Sigmoid => 1.0 / (1.0 + (-SIGMOID_GAIN * (x - 0.5)).exp()),
+            Self::Tanh => (x.tanh() / 1.0f32.tanh()).clamp(0.0, 1.0),
+            Self::ReluCap(cap) => x.clamp(0.0, cap),
+        }
+    }
+}
+
+/// ノード構成の 1 要素（名前・基本減衰率・応答曲線）
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeSpec {
+    pub name: String,
+    pub decay: f32,
+    pub activation: Activation,
+}
+
+impl NodeSpec {
+    pub fn new(name: &str, decay: f32) -> Self {
+        Self { name: name.to_string(), decay, activation: Activation::Linear }
+    }
+
+    pub fn with_activation(mut self, activation: Activation) -> Self {
+        self.activation = activation;
+        self
+    }
+
+    /// 既定の 4 ノード（攻撃性・恐怖・戦術・反射）
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new(NODE_AGGRESSION, 0.5),
+            Self::new(NODE_FEAR, 0.4),
+            Self::new(NODE_TACTICAL, 0.3),
+            Self::new(NODE_REFLEX, 0.3),
+        ]
+    }
+}
+
+#[derive(Clone)]
 pub struct Node {
+    /// ノード名（Singularity::node_index で引く）
+    pub name: String,
     pub state: f32,
     pub base_decay: f32,
     pub synapses: Vec<Synapse>,
+    pub activation: Activation,
+    /// 発火（state が spike_threshold を下から越える）後に入力へ応答しない更新回数（0 で無効）
+    pub refractory_period: u32,
+    pub spike_threshold: f32,
+    /// 不応期の残り更新回数
+    pub refractory_left: u32,
+    /// 直近の活性（古い順）
+    pub history: VecDeque<f32>,
 }
 
 impl Node {
     pub fn new(initial_decay: f32) -> Self {
+        Self::named("", initial_decay)
+    }
+
+    pub fn named(name: &str, initial_decay: f32) -> Self {
         Self {
+            name: name.to_string(),
             state: 0.0,
             base_decay: initial_decay,
             synapses: Vec::new(),
+            activation: Activation::Linear,
+            refractory_period: 0,
+            spike_threshold: 0.8,
+            refractory_left: 0,
+            history: VecDeque::new(),
         }
     }
 
+    /// 現在の活性を履歴に積む（capacity を超えた古いものは捨てる。0 なら記録しない）
+    pub fn record_history(&mut self, capacity: usize) {
+        if capacity == 0 { return; }
+        while self.history.len() >= capacity { self.history.pop_front(); }
+        self.history.push_back(self.state);
+    }
+
+    /// 履歴の平均・分散・傾向（履歴が空なら None）
+    pub fn history_stats(&self) -> Option<NodeHistoryStats> {
+        let n = self.history.len();
+        if n == 0 { return None; }
+        let mean = self.history.iter().sum::<f32>() / n as f32;
+        let variance = self.history.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / n as f32;
+        // x = 0..n の平均は (n - 1) / 2
+        let x_mean = (n - 1) as f32 / 2.0;
+        let (mut sxy, mut sxx) = (0.0, 0.0);
+        for (i, v) in self.history.iter().enumerate() {
+            let dx = i as f32 - x_mean;
+            sxy += dx * (v - mean);
+            sxx += dx * dx;
+        }
+        let trend = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+        Some(NodeHistoryStats { mean, variance, trend })
+    }
+
     /// [TQH Update] システム温度を考慮した更新ロジック
     pub fn update(&mut self, input: f32, urgency: f32, system_temp: f32, node_states: &[f32]) {
+        // 不応期の間は状態を保持し、交互の報酬による攻撃性と恐怖の振動を抑える
+        if self.refractory_left > 0 {
+            self.refractory_left -= 1;
+            return;
+        }
         let mut synaptic_input = input;
     
         // シナプス入力の計算 (node_states からインデックスで取得)
@@ -32,19 +188,100 @@ impl Node {
         
         // 自己回帰的な特性の付与
         synaptic_input += self.state * 0.1;
-    
+        self.integrate(synaptic_input, urgency, system_temp);
+    }
+
+    /// 合計済みのシナプス入力で状態を進める（不応期の判定は呼び出し側で済ませる）
+    fn integrate(&mut self, synaptic_input: f32, urgency: f32, system_temp: f32) {
+        let previous = self.state;
+
         // TQH: 温度による流動性(alpha)の計算
         let thermal_effect = (system_temp * 0.4).max(0.0);
         let alpha = (self.base_decay + (urgency * (1.0 - self.base_decay)) + thermal_effect)
             .clamp(0.01, 1.0);
     
         // 状態の更新
-        self.state += alpha * (synaptic_input - self.state);
+        self.state += alpha * (self.activation.apply(synaptic_input) - self.state);
         self.state = self.state.clamp(0.0, 1.0);
+
+        if self.refractory_period > 0 && previous < self.spike_threshold && self.state >= self.spike_threshold {
+            self.refractory_left = self.refractory_period;
+        }
+    }
+
+    /// ヘッブ則による可塑性: 共に活性なノードとの結合を強め、逆相関なら弱める
+    /// Δw = rate * (s_i s_j - (s_i - s_j)^2)（共に静止しているときは変化しない）
+    /// 共活性のノードとの間にまだ結合が無ければ新たに張る
+    pub fn apply_plasticity(&mut self, own_idx: usize, node_states: &[f32], rate: f32, min_weight: f32, max_weight: f32) {
+        for (target_id, &other) in node_states.iter().enumerate() {
+            if target_id == own_idx { continue; }
+            let delta = rate * (self.state * other - (self.state - other).powi(2));
+            if let Some(synapse) = self.synapses.iter_mut().find(|s| s.target_id == target_id) {
+                synapse.weight = (synapse.weight + delta).clamp(min_weight, max_weight);
+            } else if delta > 0.0 {
+                self.synapses.push(Synapse { target_id, weight: delta.clamp(min_weight, max_weight) });
+            }
+        }
     }
 
     pub fn apply_inhibition(&mut self, dampening_factor: f32) {
         self.state -= self.state * dampening_factor;
         self.state = self.state.max(0.0);
     }
-}
\ No newline at end of file
+}
+/// ノード群の一括更新用の作業領域
+/// 状態とシナプスを連続した配列（CSR）へ詰め直し、全ノードのシナプス入力を 1 パスで計算する
+/// バッファは使い回すので、ノード数が数百でも更新ごとの確保は発生しない
+#[derive(Clone, Debug, Default)]
+pub struct NodeBatch {
+    states: Vec<f32>,
+    /// ノード i のシナプスは targets/weights の offsets[i]..offsets[i + 1]
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Vec<f32>,
+    synaptic_input: Vec<f32>,
+}
+
+impl NodeBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 全ノードを同期的に更新する（各ノードに Node::update を呼ぶのと同じ結果）
+    /// inputs が短い場合、足りないノードの外部入力は 0
+    pub fn update(&mut self, nodes: &mut [Node], inputs: &[f32], urgency: f32, system_temp: f32) {
+        self.states.clear();
+        self.offsets.clear();
+        self.targets.clear();
+        self.weights.clear();
+        self.offsets.push(0);
+        for node in nodes.iter() {
+            self.states.push(node.state);
+            for synapse in &node.synapses {
+                self.targets.push(synapse.target_id);
+                self.weights.push(synapse.weight);
+            }
+            self.offsets.push(self.targets.len());
+        }
+
+        let states = &self.states;
+        self.synaptic_input.clear();
+        self.synaptic_input.extend(self.offsets.windows(2).enumerate().map(|(i, range)| {
+            let mut sum = inputs.get(i).copied().unwrap_or(0.0);
+            for (&target, &weight) in self.targets[range[0]..range[1]].iter().zip(&self.weights[range[0]..range[1]]) {
+                if let Some(&state) = states.get(target) {
+                    sum += state * weight;
+                }
+            }
+            sum + states[i] * 0.1
+        }));
+
+        for (node, &synaptic_input) in nodes.iter_mut().zip(&self.synaptic_input) {
+            if node.refractory_left > 0 {
+                node.refractory_left -= 1;
+                continue;
+            }
+            node.integrate(synaptic_input, urgency, system_temp);
+        }
+    }
+}
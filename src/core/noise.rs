@@ -0,0 +1,136 @@
+// src/core/noise.rs
+// 探索ノイズの分布（一様・ガウス・Ornstein-Uhlenbeck 過程・ピンクノイズ）と、
+// tick をまたいで状態を持つ生成器（相関のあるノイズで数 tick 続く一貫した探りを生む）
+
+/// OrnsteinUhlenbeck で 1 回の注入ごとに平均 0 へ引き戻す割合
+/// 小さいほどノイズが tick をまたいで長く相関する
+pub const OU_REVERSION: f32 = 0.15;
+
+/// inject_exploration_noise が psi_real に加えるノイズの分布
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExplorationNoise {
+    /// [-1, 1] の一様分布
+    #[default]
+    Uniform,
+    /// 標準正規分布
+    Gaussian,
+    /// 定常分散 1 の Ornstein-Uhlenbeck 過程（前回の注入と時間的に相関する）
+    OrnsteinUhlenbeck,
+    /// 分散 1 の 1/f ノイズ（数 tick から数百 tick までの幅広い時間スケールで相関する）
+    Pink,
+}
+
+impl ExplorationNoise {
+    /// JNI などからの整数指定 (0=Uniform, 1=Gaussian, 2=OrnsteinUhlenbeck)
+    pub fn from_id(id: i32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Uniform),
+            1 => Some(Self::Gaussian),
+            2 => Some(Self::OrnsteinUhlenbeck),
+            3 => Some(Self::Pink),
+            _ => None,
+        }
+    }
+
+    pub fn id(self) -> i32 {
+        match self {
+            Self::Uniform => 0,
+            Self::Gaussian => 1,
+            Self::OrnsteinUhlenbeck => 2,
+            Self::Pink => 3,
+        }
+    }
+}
+
+/// ピンクノイズのフィルタ（Paul Kellet の 3 極近似）: b_k = a_k b_k + c_k w, 出力 = Σ b_k + PINK_DIRECT w
+const PINK_POLES: [f32; 3] = [0.99765, 0.96300, 0.57000];
+const PINK_GAINS: [f32; 3] = [0.0990460, 0.2965164, 1.0526913];
+const PINK_DIRECT: f32 = 0.1848;
+/// 白色雑音の入力に対する出力の定常分散 (≈ 8.8745) を 1 にする係数
+const PINK_NORM: f32 = 0.3356815;
+/// 状態 (b_0, b_1, b_2) の定常分布の共分散 c_i c_j / (1 - a_i a_j) のコレスキー因子（初回を定常分布から始めるため）
+const PINK_INIT: [[f32; 3]; 3] = [
+    [1.4455839, 0.0, 0.0],
+    [0.517438, 0.9709725, 0.0],
+    [0.1672151, 0.6235454, 1.1066635],
+];
+
+/// 一様乱数 [0, 1) から標準正規分布の値を作る（Box-Muller 法、一様乱数を 2 つ使う）
+pub fn gaussian(uniform: &mut impl FnMut() -> f32) -> f32 {
+    // u1 = 0 で ln が発散しないよう (0, 1] に写す
+    let u1 = (1.0 - uniform()).max(f32::MIN_POSITIVE);
+    let u2 = uniform();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos()
+}
+
+/// 要素ごとに独立な探索ノイズの時系列を作る生成器
+/// OU・ピンクノイズは要素ごとの状態を tick をまたいで持ち、要素数が変わると定常分布から始め直す
+#[derive(Clone, Debug, Default)]
+pub struct NoiseGenerator {
+    mode: ExplorationNoise,
+    /// OU: 要素ごとの値 / ピンク: 要素ごとの (b_0, b_1, b_2)
+    state: Vec<f32>,
+}
+
+impl NoiseGenerator {
+    pub fn new(mode: ExplorationNoise) -> Self {
+        Self { mode, state: Vec::new() }
+    }
+
+    pub fn mode(&self) -> ExplorationNoise {
+        self.mode
+    }
+
+    /// 分布を切り替える（溜まっている状態は捨てる）
+    pub fn set_mode(&mut self, mode: ExplorationNoise) {
+        self.mode = mode;
+        self.reset();
+    }
+
+    pub fn reset(&mut self) {
+        self.state.clear();
+    }
+
+    /// out の各要素に次の tick のノイズを書く。uniform は [0, 1) の一様乱数
+    pub fn next_into(&mut self, out: &mut [f32], uniform: &mut impl FnMut() -> f32) {
+        match self.mode {
+            ExplorationNoise::Uniform => {
+                for x in out.iter_mut() { *x = (uniform() - 0.5) * 2.0; }
+            }
+            ExplorationNoise::Gaussian => {
+                for x in out.iter_mut() { *x = gaussian(uniform); }
+            }
+            ExplorationNoise::OrnsteinUhlenbeck => {
+                // x' = (1 - k) x + sqrt(1 - (1 - k)^2) N(0, 1) で、定常分散を 1 に保つ
+                let decay = 1.0 - OU_REVERSION;
+                let diffusion = (1.0 - decay * decay).sqrt();
+                if self.state.len() != out.len() {
+                    // 初回は定常分布から始める
+                    self.state.clear();
+                    self.state.extend((0..out.len()).map(|_| gaussian(uniform)));
+                } else {
+                    for x in self.state.iter_mut() { *x = decay * *x + diffusion * gaussian(uniform); }
+                }
+                out.copy_from_slice(&self.state);
+            }
+            ExplorationNoise::Pink => {
+                if self.state.len() != out.len() * 3 {
+                    self.state.clear();
+                    for _ in 0..out.len() {
+                        let g = [gaussian(uniform), gaussian(uniform), gaussian(uniform)];
+                        self.state.extend(PINK_INIT.iter().map(|row| row[0] * g[0] + row[1] * g[1] + row[2] * g[2]));
+                    }
+                }
+                for (x, b) in out.iter_mut().zip(self.state.chunks_exact_mut(3)) {
+                    let w = gaussian(uniform);
+                    let mut sum = PINK_DIRECT * w;
+                    for k in 0..3 {
+                        b[k] = PINK_POLES[k] * b[k] + PINK_GAINS[k] * w;
+                        sum += b[k];
+                    }
+                    *x = sum * PINK_NORM;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+// src/core/opponent.rs
+use std::collections::HashMap;
+
+/// 相手モデル: 観測した敵の (状態, 行動) ペアから敵の行動分布を推定し、
+/// 「どの敵行動に対して自分のどの行動が報われたか」を共鳴統計として保持する
+#[derive(Clone)]
+pub struct OpponentModel {
+    pub opponent_action_size: usize,
+    pub action_size: usize,
+    /// state_idx -> 敵行動ごとの観測頻度（指数減衰付き）
+    pub frequencies: HashMap<usize, Vec<f32>>,
+    /// [敵行動 * action_size + 自分の行動] -> 報酬の移動平均（共鳴）
+    pub resonance: Vec<f32>,
+    pub frequency_decay: f32,
+    pub learning_rate: f32,
+    /// get_best_in_range に加算する予測応答項の重み
+    pub weight: f32,
+}
+
+impl OpponentModel {
+    pub fn new(opponent_action_size: usize, action_size: usize) -> Self {
+        Self {
+            opponent_action_size,
+            action_size,
+            frequencies: HashMap::new(),
+            resonance: vec![0.0; opponent_action_size * action_size],
+            frequency_decay: 0.95,
+            learning_rate: 0.2,
+            weight: 1.0,
+        }
+    }
+
+    /// 敵の行動を1件観測する
+    pub fn observe(&mut self, state_idx: usize, opponent_action: usize) {
+        if opponent_action >= self.opponent_action_size { return; }
+        let size = self.opponent_action_size;
+        let freq = self.frequencies.entry(state_idx).or_insert_with(|| vec![0.0; size]);
+        for f in freq.iter_mut() { *f *= self.frequency_decay; }
+        freq[opponent_action] += 1.0;
+    }
+
+    /// 状態における敵行動の予測分布（未観測なら None）
+    pub fn predict(&self, state_idx: usize) -> Option<Vec<f32>> {
+        let freq = self.frequencies.get(&state_idx)?;
+        let total: f32 = freq.iter().sum();
+        if total <= 1e-6 { return None; }
+        Some(freq.iter().map(|f| f / total).collect())
+    }
+
+    /// 予測される敵の応答を踏まえた、自分の各行動への期待共鳴
+    pub fn expected_response(&self, state_idx: usize) -> Option<Vec<f32>> {
+        let dist = self.predict(state_idx)?;
+        let mut response = vec![0.0; self.action_size];
+        for (opp, &p) in dist.iter().enumerate() {
+            if p < 1e-3 { continue; }
+            let row = &self.resonance[opp * self.action_size..(opp + 1) * self.action_size];
+            for (r, &v) in response.iter_mut().zip(row) {
+                *r += p * v * self.weight;
+            }
+        }
+        Some(response)
+    }
+
+    /// 報酬を、予測された敵行動と自分の行動の組み合わせへ配分する
+    pub fn reinforce(&mut self, state_idx: usize, own_actions: &[usize], reward: f32) {
+        let Some(dist) = self.predict(state_idx) else { return; };
+        for &action in own_actions {
+            if action >= self.action_size { continue; }
+            for (opp, &p) in dist.iter().enumerate() {
+                let cell = &mut self.resonance[opp * self.action_size + action];
+                *cell += self.learning_rate * p * (reward - *cell);
+            }
+        }
+    }
+}
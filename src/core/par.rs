@@ -0,0 +1,97 @@
+// src/core/par.rs
+// parallel 機能が有効かつ次元が十分大きいときだけ、要素ごとの処理を rayon で分割するヘルパー
+// 無効なときは同じクロージャを先頭から順に呼ぶだけなので、結果は従来の逐次ループとビット単位で一致する
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::ops::Add;
+
+/// この次元以上で並列化する（小さい次元ではスレッド間の受け渡しの方が高くつく）
+pub const PARALLEL_MIN_DIM: usize = 8192;
+/// 並列化時に1タスクが受け持つ要素数（step_core のチャンク幅の倍数）
+pub const BLOCK: usize = 2048;
+
+pub fn enabled(dim: usize) -> bool {
+    cfg!(feature = "parallel") && dim >= PARALLEL_MIN_DIM
+}
+
+/// 各要素 a[i] に f(i, &mut a[i]) を適用する
+pub fn for_each<A: Send>(parallel: bool, a: &mut [A], f: impl Fn(usize, &mut A) + Sync + Send) {
+    #[cfg(feature = "parallel")]
+    if parallel {
+        a.par_iter_mut().enumerate().for_each(|(i, x)| f(i, x));
+        return;
+    }
+    let _ = parallel;
+    for (i, x) in a.iter_mut().enumerate() { f(i, x); }
+}
+
+/// 各要素の組 (a[i], b[i]) に f(i, &mut a[i], &mut b[i]) を適用する
+pub fn for_each2<A: Send, B: Send>(parallel: bool, a: &mut [A], b: &mut [B], f: impl Fn(usize, &mut A, &mut B) + Sync + Send) {
+    #[cfg(feature = "parallel")]
+    if parallel {
+        a.par_iter_mut().zip(b.par_iter_mut()).enumerate().for_each(|(i, (x, y))| f(i, x, y));
+        return;
+    }
+    let _ = parallel;
+    for (i, (x, y)) in a.iter_mut().zip(b.iter_mut()).enumerate() { f(i, x, y); }
+}
+
+/// 各要素の組 (a[i], b[i], c[i]) に f を適用する
+pub fn for_each3<A: Send, B: Send, C: Send>(parallel: bool, a: &mut [A], b: &mut [B], c: &mut [C], f: impl Fn(usize, &mut A, &mut B, &mut C) + Sync + Send) {
+    #[cfg(feature = "parallel")]
+    if parallel {
+        a.par_iter_mut().zip(b.par_iter_mut()).zip(c.par_iter_mut()).enumerate()
+            .for_each(|(i, ((x, y), z))| f(i, x, y, z));
+        return;
+    }
+    let _ = parallel;
+    for (i, ((x, y), z)) in a.iter_mut().zip(b.iter_mut()).zip(c.iter_mut()).enumerate() { f(i, x, y, z); }
+}
+
+/// BLOCK 要素ごとのブロックに f(先頭インデックス, &mut a[block], &mut b[block]) を適用する
+/// 逐次時は全体を1ブロックとして1回だけ呼ぶ
+pub fn for_each_block2<A: Send, B: Send>(parallel: bool, a: &mut [A], b: &mut [B], f: impl Fn(usize, &mut [A], &mut [B]) + Sync + Send) {
+    #[cfg(feature = "parallel")]
+    if parallel {
+        a.par_chunks_mut(BLOCK).zip(b.par_chunks_mut(BLOCK)).enumerate()
+            .for_each(|(k, (x, y))| f(k * BLOCK, x, y));
+        return;
+    }
+    let _ = parallel;
+    f(0, a, b);
+}
+
+/// 環状配列を BLOCK 要素ごとのブロックに分け、f(先頭インデックス, ブロック, 左隣, 右隣) を適用する
+/// 並列時の左隣・右隣は更新前の値（境界交換）で、ブロック内の逐次依存だけが保たれる
+/// 逐次時は全体を1ブロックとし、左隣は更新前の末尾、右隣は None（更新後の先頭を使う）で呼ぶ
+pub fn for_each_ring_block<T: Copy + Send + Sync>(parallel: bool, a: &mut [T], f: impl Fn(usize, &mut [T], T, Option<T>) + Sync + Send) {
+    let n = a.len();
+    if n == 0 { return; }
+    #[cfg(feature = "parallel")]
+    if parallel {
+        let boundaries: Vec<(T, T)> = (0..n.div_ceil(BLOCK))
+            .map(|k| (a[(k * BLOCK + n - 1) % n], a[((k + 1) * BLOCK).min(n) % n]))
+            .collect();
+        a.par_chunks_mut(BLOCK).zip(boundaries.par_iter()).enumerate()
+            .for_each(|(k, (block, &(prev, next)))| f(k * BLOCK, block, prev, Some(next)));
+        return;
+    }
+    let _ = parallel;
+    let prev = a[n - 1];
+    f(0, a, prev, None);
+}
+
+/// f(0) + f(1) + ... + f(n - 1)
+/// 並列時もブロックごとの部分和を順に足すため、スケジューリングに依らず結果は決定的
+pub fn sum<T: Copy + Default + Add<Output = T> + Send>(parallel: bool, n: usize, f: impl Fn(usize) -> T + Sync + Send) -> T {
+    #[cfg(feature = "parallel")]
+    if parallel {
+        let partials: Vec<T> = (0..n.div_ceil(BLOCK)).into_par_iter()
+            .map(|k| (k * BLOCK..((k + 1) * BLOCK).min(n)).map(&f).fold(T::default(), |acc, x| acc + x))
+            .collect();
+        return partials.into_iter().fold(T::default(), |acc, x| acc + x);
+    }
+    let _ = parallel;
+    (0..n).map(f).fold(T::default(), |acc, x| acc + x)
+}
@@ -0,0 +1,88 @@
+// src/core/personality.rs
+// 性格プリセット: ノードの減衰率・初期状態、温度のスケジュール、探索戦略をひとまとめにしたもの
+// 同じバイナリから性格の違う AI を並べるために、生成時にも実行中にも一括で切り替えられる
+
+use super::config::ExplorationPolicy;
+use super::node::{NODE_AGGRESSION, NODE_FEAR, NODE_REFLEX, NODE_TACTICAL};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Personality {
+    /// 攻撃性が高く、温度もやや高めで押し切る
+    Aggressive,
+    /// 恐怖と戦術が強く、低温で手堅く最善手を選ぶ
+    Cautious,
+    /// 既定の調整（Singularity::new と同じ）
+    #[default]
+    Adaptive,
+    /// 攻撃性と反射だけで動き、高温のまま無秩序に暴れる
+    Berserker,
+}
+
+/// 性格プリセットが設定する値
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PersonalityProfile {
+    /// (ノード名, 基本減衰率, 初期状態)
+    pub nodes: [(&'static str, f32, f32); 4],
+    /// 切り替え時の系の温度
+    pub temperature: f32,
+    pub temperature_floor: f32,
+    pub temperature_ceiling: f32,
+    /// フラストレーション 1.0 あたりの毎ステップ加熱量
+    pub temp_frustration_weight: f32,
+    pub exploration_policy: ExplorationPolicy,
+    pub exploration_beta: f32,
+}
+
+impl Personality {
+    /// JNI などからの整数指定 (0=Aggressive, 1=Cautious, 2=Adaptive, 3=Berserker)
+    pub fn from_id(id: i32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Aggressive),
+            1 => Some(Self::Cautious),
+            2 => Some(Self::Adaptive),
+            3 => Some(Self::Berserker),
+            _ => None,
+        }
+    }
+
+    pub fn profile(self) -> PersonalityProfile {
+        match self {
+            Self::Aggressive => PersonalityProfile {
+                nodes: [(NODE_AGGRESSION, 0.6, 0.6), (NODE_FEAR, 0.3, 0.0), (NODE_TACTICAL, 0.3, 0.2), (NODE_REFLEX, 0.4, 0.3)],
+                temperature: 0.6,
+                temperature_floor: 0.05,
+                temperature_ceiling: 2.0,
+                temp_frustration_weight: 0.08,
+                exploration_policy: ExplorationPolicy::TopKSoftmax,
+                exploration_beta: 0.1,
+            },
+            Self::Cautious => PersonalityProfile {
+                nodes: [(NODE_AGGRESSION, 0.3, 0.0), (NODE_FEAR, 0.5, 0.4), (NODE_TACTICAL, 0.5, 0.6), (NODE_REFLEX, 0.3, 0.1)],
+                temperature: 0.3,
+                temperature_floor: 0.01,
+                temperature_ceiling: 1.0,
+                temp_frustration_weight: 0.03,
+                exploration_policy: ExplorationPolicy::Greedy,
+                exploration_beta: 0.1,
+            },
+            Self::Adaptive => PersonalityProfile {
+                nodes: [(NODE_AGGRESSION, 0.5, 0.0), (NODE_FEAR, 0.4, 0.0), (NODE_TACTICAL, 0.3, 0.0), (NODE_REFLEX, 0.3, 0.0)],
+                temperature: 0.5,
+                temperature_floor: 0.01,
+                temperature_ceiling: 2.0,
+                temp_frustration_weight: 0.05,
+                exploration_policy: ExplorationPolicy::TopKSoftmax,
+                exploration_beta: 0.1,
+            },
+            Self::Berserker => PersonalityProfile {
+                nodes: [(NODE_AGGRESSION, 0.8, 1.0), (NODE_FEAR, 0.1, 0.0), (NODE_TACTICAL, 0.1, 0.0), (NODE_REFLEX, 0.7, 0.8)],
+                temperature: 1.2,
+                temperature_floor: 0.3,
+                temperature_ceiling: 2.0,
+                temp_frustration_weight: 0.15,
+                exploration_policy: ExplorationPolicy::EpsilonGreedy,
+                exploration_beta: 0.2,
+            },
+        }
+    }
+}
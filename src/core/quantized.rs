@@ -0,0 +1,257 @@
+// src/core/quantized.rs
+// ペナルティ場・重力場を f32 より小さい型で保持するための行単位の量子化ストア
+// 保持する精度だけを落とし、読み出しのたびに f32 へ戻して計算する
+// 19683 状態 × 1024 次元のペナルティ行列は f32 で約 80 MB、F16 で半分、I8 で約 1/4 になる
+
+/// 場を保持する精度
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FieldPrecision {
+    /// f32 のまま保持する（従来の挙動）
+    #[default]
+    F32,
+    /// 半精度浮動小数（相対誤差 約 0.05%）
+    F16,
+    /// 行ごとの刻み × i8（誤差は行の最大絶対値の 1/254 以内）
+    I8,
+}
+
+impl FieldPrecision {
+    /// JNI などからの整数指定 (0=F32, 1=F16, 2=I8)
+    pub fn from_id(id: i32) -> Option<Self> {
+        match id {
+            0 => Some(Self::F32),
+            1 => Some(Self::F16),
+            2 => Some(Self::I8),
+            _ => None,
+        }
+    }
+}
+
+/// row_len 要素ずつの行に分けた f32 の表を、指定の精度で保持する
+#[derive(Clone, Debug, Default)]
+pub struct QuantizedRows {
+    precision: FieldPrecision,
+    row_len: usize,
+    full: Vec<f32>,
+    half: Vec<u16>,
+    bytes: Vec<i8>,
+    /// I8 の行ごとの刻み（復号値 = bytes × scale）
+    scales: Vec<f32>,
+}
+
+impl QuantizedRows {
+    /// values を row_len 要素ずつの行として量子化する（端数の要素は 0 で埋めた 1 行になる）
+    pub fn new(precision: FieldPrecision, row_len: usize, values: &[f32]) -> Self {
+        let row_len = row_len.max(1);
+        let rows = values.len().div_ceil(row_len);
+        let mut store = Self { precision, row_len, ..Self::default() };
+        store.resize_rows(rows);
+        for (row, chunk) in values.chunks(row_len).enumerate() {
+            store.write_row(row, chunk);
+        }
+        store
+    }
+
+    pub fn precision(&self) -> FieldPrecision {
+        self.precision
+    }
+
+    pub fn row_len(&self) -> usize {
+        self.row_len
+    }
+
+    /// 保持している要素数
+    pub fn len(&self) -> usize {
+        match self.precision {
+            FieldPrecision::F32 => self.full.len(),
+            FieldPrecision::F16 => self.half.len(),
+            FieldPrecision::I8 => self.bytes.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn rows(&self) -> usize {
+        self.len() / self.row_len
+    }
+
+    /// 要素が実際に使っているヒープのバイト数
+    pub fn heap_bytes(&self) -> usize {
+        self.full.len() * 4 + self.half.len() * 2 + self.bytes.len() + self.scales.len() * 4
+    }
+
+    /// i 番目の要素（範囲外は 0）
+    pub fn get(&self, i: usize) -> f32 {
+        match self.precision {
+            FieldPrecision::F32 => self.full.get(i).copied().unwrap_or(0.0),
+            FieldPrecision::F16 => self.half.get(i).map_or(0.0, |&h| f16_to_f32(h)),
+            FieldPrecision::I8 => self.bytes.get(i).map_or(0.0, |&q| q as f32 * self.scales[i / self.row_len]),
+        }
+    }
+
+    /// i 番目の要素を書き換える。I8 で行の刻みに収まらない値は、行全体を刻み直してから書く
+    pub fn set(&mut self, i: usize, value: f32) {
+        if i >= self.len() { return; }
+        match self.precision {
+            FieldPrecision::F32 => self.full[i] = value,
+            FieldPrecision::F16 => self.half[i] = f32_to_f16(value),
+            FieldPrecision::I8 => {
+                let row = i / self.row_len;
+                if !value.is_finite() { return; }
+                if value.abs() > self.scales[row] * 127.0 {
+                    self.rescale_row(row, value.abs() / 127.0);
+                }
+                self.bytes[i] = quantize_i8(value, self.scales[row]);
+            }
+        }
+    }
+
+    /// 行 row の全要素を factor 倍する（I8 は刻みを掛けるだけで済む）
+    pub fn scale_row(&mut self, row: usize, factor: f32) {
+        let range = self.row_range(row);
+        match self.precision {
+            FieldPrecision::F32 => {
+                if let Some(values) = self.full.get_mut(range) {
+                    for v in values { *v *= factor; }
+                }
+            }
+            FieldPrecision::F16 => {
+                if let Some(values) = self.half.get_mut(range) {
+                    for h in values { *h = f32_to_f16(f16_to_f32(*h) * factor); }
+                }
+            }
+            FieldPrecision::I8 => {
+                let Some(scale) = self.scales.get_mut(row) else { return };
+                if factor < 0.0 {
+                    for q in &mut self.bytes[range] { *q = -*q; }
+                }
+                *scale *= factor.abs();
+            }
+        }
+    }
+
+    /// out[j] += 行 row の j 番目 × weight
+    pub fn accumulate_row(&self, row: usize, weight: f32, out: &mut [f32]) {
+        let range = self.row_range(row);
+        match self.precision {
+            FieldPrecision::F32 => {
+                let Some(values) = self.full.get(range) else { return };
+                for (o, &v) in out.iter_mut().zip(values) { *o += v * weight; }
+            }
+            FieldPrecision::F16 => {
+                let Some(values) = self.half.get(range) else { return };
+                for (o, &h) in out.iter_mut().zip(values) { *o += f16_to_f32(h) * weight; }
+            }
+            FieldPrecision::I8 => {
+                let Some(values) = self.bytes.get(range) else { return };
+                let step = self.scales[row] * weight;
+                for (o, &q) in out.iter_mut().zip(values) { *o += q as f32 * step; }
+            }
+        }
+    }
+
+    /// 行 row を f32 に戻して out の末尾に足す
+    pub fn extend_row(&self, row: usize, out: &mut Vec<f32>) {
+        let start = out.len();
+        out.resize(start + self.row_len, 0.0);
+        self.accumulate_row(row, 1.0, &mut out[start..]);
+    }
+
+    /// 行 row を values で置き換える（I8 は行の最大絶対値から刻みを決め直す）
+    pub fn write_row(&mut self, row: usize, values: &[f32]) {
+        let range = self.row_range(row);
+        if range.end > self.len() { return; }
+        match self.precision {
+            FieldPrecision::F32 => {
+                for (v, &x) in self.full[range].iter_mut().zip(values) { *v = x; }
+            }
+            FieldPrecision::F16 => {
+                for (h, &x) in self.half[range].iter_mut().zip(values) { *h = f32_to_f16(x); }
+            }
+            FieldPrecision::I8 => {
+                let max = values.iter().filter(|v| v.is_finite()).fold(0.0f32, |m, v| m.max(v.abs()));
+                let scale = max / 127.0;
+                self.scales[row] = scale;
+                for (q, &x) in self.bytes[range].iter_mut().zip(values) {
+                    *q = if x.is_finite() { quantize_i8(x, scale) } else { 0 };
+                }
+            }
+        }
+    }
+
+    /// 行数を変える（増えた行は 0）
+    pub fn resize_rows(&mut self, rows: usize) {
+        let len = rows * self.row_len;
+        match self.precision {
+            FieldPrecision::F32 => self.full.resize(len, 0.0),
+            FieldPrecision::F16 => self.half.resize(len, 0),
+            FieldPrecision::I8 => {
+                self.bytes.resize(len, 0);
+                self.scales.resize(rows, 0.0);
+            }
+        }
+    }
+
+    /// 全要素を f32 に戻す
+    pub fn to_f32(&self) -> Vec<f32> {
+        (0..self.len()).map(|i| self.get(i)).collect()
+    }
+
+    fn row_range(&self, row: usize) -> std::ops::Range<usize> {
+        row * self.row_len..(row + 1) * self.row_len
+    }
+
+    /// 行 row の刻みを scale に変え、既存の値を刻み直す
+    fn rescale_row(&mut self, row: usize, scale: f32) {
+        let old = self.scales[row];
+        self.scales[row] = scale;
+        let range = self.row_range(row);
+        for q in &mut self.bytes[range] {
+            *q = quantize_i8(*q as f32 * old, scale);
+        }
+    }
+}
+
+fn quantize_i8(value: f32, scale: f32) -> i8 {
+    if scale > 0.0 { (value / scale).round().clamp(-127.0, 127.0) as i8 } else { 0 }
+}
+
+/// f32 を IEEE 754 半精度のビット列へ（最近接偶数丸め、範囲外は ±∞）
+pub fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let mant = bits & 0x7f_ffff;
+    if exp == 0xff {
+        return sign | 0x7c00 | if mant != 0 { 0x200 } else { 0 };
+    }
+    let e = exp - 127 + 15;
+    if e >= 0x1f { return sign | 0x7c00; }
+    let (half, rem, halfway) = if e <= 0 {
+        // 半精度の非正規数（2^-24 刻み）
+        if e < -10 { return sign; }
+        let m = mant | 0x80_0000;
+        let shift = (14 - e) as u32;
+        (m >> shift, m & ((1 << shift) - 1), 1 << (shift - 1))
+    } else {
+        (((e as u32) << 10) | (mant >> 13), mant & 0x1fff, 0x1000)
+    };
+    // 繰り上がりで指数が溢れると ∞ になる
+    let round = rem > halfway || (rem == halfway && half & 1 == 1);
+    sign | (half + round as u32) as u16
+}
+
+/// IEEE 754 半精度のビット列を f32 へ
+pub fn f16_to_f32(half: u16) -> f32 {
+    let sign = if half & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exp = ((half >> 10) & 0x1f) as u32;
+    let mant = (half & 0x3ff) as u32;
+    match exp {
+        0 => sign * mant as f32 / 16_777_216.0,
+        0x1f if mant == 0 => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => f32::from_bits(((half as u32 & 0x8000) << 16) | ((exp + 112) << 23) | (mant << 13)),
+    }
+}
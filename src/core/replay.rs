@@ -0,0 +1,81 @@
+// src/core/replay.rs
+// 記憶の統合（睡眠時のリプレイ）用に、最近強く刻み込んだパターンを保持するバッファ
+// 強度は刻み込みごとに記憶波と同じ率で減衰させ、記憶波に残っている重みの目安として使う
+
+/// 刻み込んだ波のパターン 1 つ
+#[derive(Clone, Debug)]
+pub struct ReplayTrace {
+    /// 刻み込み先のバンク
+    pub bank: usize,
+    /// 入力（状態）番号
+    pub input_idx: usize,
+    pub strength: f32,
+    /// 刻み込み時の各ビンの位相
+    pub phases: Vec<f32>,
+}
+
+/// 強度の大きい順に capacity 個までのパターンを保持する（capacity 0 で記録しない）
+#[derive(Clone, Debug, Default)]
+pub struct ReplayBuffer {
+    pub capacity: usize,
+    traces: Vec<ReplayTrace>,
+}
+
+impl ReplayBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, traces: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.traces.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.traces.is_empty()
+    }
+
+    pub fn traces(&self) -> &[ReplayTrace] {
+        &self.traces
+    }
+
+    /// パターンを記録する。同じ (bank, input_idx) があれば位相を置き換えて強度を足し、
+    /// 満杯なら最も弱いパターンより強いときだけ入れ替える
+    pub fn record(&mut self, bank: usize, input_idx: usize, strength: f32, phases: impl FnOnce() -> Vec<f32>) {
+        if self.capacity == 0 || strength <= 0.0 { return; }
+        if let Some(trace) = self.traces.iter_mut().find(|t| t.bank == bank && t.input_idx == input_idx) {
+            trace.strength += strength;
+            trace.phases = phases();
+            return;
+        }
+        let trace = ReplayTrace { bank, input_idx, strength, phases: phases() };
+        if self.traces.len() < self.capacity {
+            self.traces.push(trace);
+        } else if let Some(weakest) = self.traces.iter_mut().min_by(|a, b| a.strength.total_cmp(&b.strength))
+            && weakest.strength < strength {
+            *weakest = trace;
+        }
+    }
+
+    /// (bank, input_idx) のパターンを捨てる
+    pub fn remove(&mut self, bank: usize, input_idx: usize) {
+        self.traces.retain(|t| t.bank != bank || t.input_idx != input_idx);
+    }
+
+    /// 全パターンの強度に factor を掛ける
+    pub fn fade(&mut self, factor: f32) {
+        for t in &mut self.traces { t.strength *= factor; }
+    }
+
+    /// 容量を変える（溢れた分は弱い順に捨てる）
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        if self.traces.len() > capacity {
+            self.traces.sort_by(|a, b| b.strength.total_cmp(&a.strength));
+            self.traces.truncate(capacity);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.traces.clear();
+    }
+}
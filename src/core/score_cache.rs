@@ -0,0 +1,67 @@
+// src/core/score_cache.rs
+use std::collections::HashMap;
+
+/// 時間予算付きの意思決定で最後に計算した、状態ごとの波動スコア（締め切り直前のフォールバック用）
+/// 容量を超えたら最も長く使われていない状態から追い出す。学習でその状態の波が変わったら remove で捨てる
+#[derive(Clone, Debug, Default)]
+pub struct ScoreCache {
+    /// state_idx -> (行動ごとのスコア, 最後に使った時刻)
+    entries: HashMap<usize, (Vec<f32>, u64)>,
+    clock: u64,
+}
+
+impl ScoreCache {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains_key(&self, state_idx: &usize) -> bool {
+        self.entries.contains_key(state_idx)
+    }
+
+    /// 使用時刻を更新せずに読む
+    pub fn get(&self, state_idx: &usize) -> Option<&[f32]> {
+        self.entries.get(state_idx).map(|(scores, _)| scores.as_slice())
+    }
+
+    /// フォールバックとして読み、最近使ったものとして記録する
+    pub fn touch(&mut self, state_idx: usize) -> Option<&[f32]> {
+        self.clock += 1;
+        let clock = self.clock;
+        self.entries.get_mut(&state_idx).map(|(scores, used)| {
+            *used = clock;
+            scores.as_slice()
+        })
+    }
+
+    /// state_idx のスコア列（なければ長さ len のゼロ列を作る）を書き換え用に返す
+    /// 新しく作る場合は、容量 capacity に収まるまで最も古い状態を追い出す（capacity が 0 なら None）
+    pub fn entry(&mut self, state_idx: usize, len: usize, capacity: usize) -> Option<&mut [f32]> {
+        if capacity == 0 {
+            self.entries.clear();
+            return None;
+        }
+        self.clock += 1;
+        if !self.entries.contains_key(&state_idx) {
+            while self.entries.len() >= capacity {
+                let Some(&oldest) = self.entries.iter().min_by_key(|(_, (_, used))| *used).map(|(state, _)| state) else { break; };
+                self.entries.remove(&oldest);
+            }
+        }
+        let (scores, used) = self.entries.entry(state_idx).or_insert_with(|| (vec![0.0; len], 0));
+        *used = self.clock;
+        Some(scores.as_mut_slice())
+    }
+
+    pub fn remove(&mut self, state_idx: usize) {
+        self.entries.remove(&state_idx);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
@@ -0,0 +1,152 @@
+// src/core/selfplay.rs
+// 2つの Singularity を対戦させる自己対戦アリーナ
+
+use super::singularity::Singularity;
+
+/// 1手の結果
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StepOutcome {
+    /// 対局続行
+    Continue,
+    /// 指定したプレイヤー (0 / 1) の勝ち
+    Win(usize),
+    Draw,
+    /// 手番のプレイヤーが反則した（反則負け）
+    Invalid,
+}
+
+/// 2人対戦の環境（盤面ゲームなど）
+pub trait Environment {
+    /// 新しい対局を始める
+    fn reset(&mut self);
+    /// 手番のプレイヤー (0 / 1)
+    fn current_player(&self) -> usize;
+    /// player 視点の状態インデックス
+    fn state_index(&self, player: usize) -> usize;
+    /// 意思決定前に設定するアクティブ条件（既定は条件なし）
+    fn active_conditions(&self, _player: usize) -> Vec<i32> {
+        Vec::new()
+    }
+    /// 手番のプレイヤーの行動を適用する
+    fn step(&mut self, actions: &[i32]) -> StepOutcome;
+}
+
+/// 結果ごとの報酬
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ArenaRewards {
+    pub win: f32,
+    pub loss: f32,
+    pub draw: f32,
+    /// 反則したプレイヤーにのみ与えられる
+    pub invalid: f32,
+}
+
+impl Default for ArenaRewards {
+    fn default() -> Self {
+        Self { win: 2.0, loss: -2.0, draw: 0.5, invalid: -5.0 }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ArenaReport {
+    pub matches: usize,
+    pub wins: [usize; 2],
+    pub draws: usize,
+    /// 反則負けの回数（wins には相手の勝ちとして含まれる）
+    pub invalid: [usize; 2],
+}
+
+impl ArenaReport {
+    pub fn win_rate(&self, player: usize) -> f32 {
+        if self.matches == 0 { return 0.0; }
+        self.wins[player] as f32 / self.matches as f32
+    }
+
+    pub fn draw_rate(&self) -> f32 {
+        if self.matches == 0 { return 0.0; }
+        self.draws as f32 / self.matches as f32
+    }
+}
+
+pub struct Arena {
+    pub rewards: ArenaRewards,
+    /// 1対局あたりの最大手数（超えたら引き分け）
+    pub max_turns: usize,
+    /// この対局数ごとに記憶波動を交配する（None なら交配しない）
+    pub cross_pollinate_every: Option<usize>,
+    /// 交配時に相手の記憶波動を混ぜる割合
+    pub cross_pollination_rate: f32,
+}
+
+impl Default for Arena {
+    fn default() -> Self {
+        Self {
+            rewards: ArenaRewards::default(),
+            max_turns: 1000,
+            cross_pollinate_every: None,
+            cross_pollination_rate: 0.1,
+        }
+    }
+}
+
+impl Arena {
+    /// 2体を matches 回対戦させる（players[0] が環境のプレイヤー 0 を担当する）
+    pub fn run<E: Environment>(&self, env: &mut E, players: [&mut Singularity; 2], matches: usize) -> ArenaReport {
+        let [a, b] = players;
+        let mut report = ArenaReport::default();
+        for m in 1..=matches {
+            env.reset();
+            let mut turns = 0;
+            loop {
+                let turn = env.current_player().min(1);
+                let ai: &mut Singularity = if turn == 0 { &mut *a } else { &mut *b };
+                ai.set_active_conditions(&env.active_conditions(turn));
+                let actions = ai.select_actions(env.state_index(turn));
+                turns += 1;
+
+                match env.step(&actions) {
+                    StepOutcome::Continue if turns < self.max_turns => continue,
+                    StepOutcome::Continue | StepOutcome::Draw => {
+                        a.learn(self.rewards.draw);
+                        b.learn(self.rewards.draw);
+                        report.draws += 1;
+                    }
+                    StepOutcome::Win(winner) => {
+                        let winner = winner.min(1);
+                        let (win_ai, lose_ai) = if winner == 0 { (&mut *a, &mut *b) } else { (&mut *b, &mut *a) };
+                        win_ai.learn(self.rewards.win);
+                        lose_ai.learn(self.rewards.loss);
+                        report.wins[winner] += 1;
+                    }
+                    StepOutcome::Invalid => {
+                        let offender: &mut Singularity = if turn == 0 { &mut *a } else { &mut *b };
+                        offender.learn(self.rewards.invalid);
+                        report.invalid[turn] += 1;
+                        report.wins[1 - turn] += 1;
+                    }
+                }
+                break;
+            }
+            report.matches += 1;
+
+            if let Some(every) = self.cross_pollinate_every
+                && m.is_multiple_of(every) {
+                cross_pollinate(a, b, self.cross_pollination_rate);
+            }
+        }
+        report
+    }
+}
+
+/// 2体の記憶波動 (q_memory) を相互に混ぜ合わせる（次元が異なれば何もしない）
+pub fn cross_pollinate(a: &mut Singularity, b: &mut Singularity, rate: f32) {
+    if a.mwso.dim != b.mwso.dim { return; }
+    let rate = rate.clamp(0.0, 0.5) as f64;
+    let pairs = a.mwso.q_memory_re.iter_mut().zip(b.mwso.q_memory_re.iter_mut())
+        .chain(a.mwso.q_memory_im.iter_mut().zip(b.mwso.q_memory_im.iter_mut()));
+    for (x, y) in pairs {
+        let (ox, oy) = (*x, *y);
+        *x = ox * (1.0 - rate) + oy * rate;
+        *y = oy * (1.0 - rate) + ox * rate;
+    }
+}
@@ -0,0 +1,61 @@
+// src/core/sensor.rs
+// 名前付きのゲーム信号（"damage_taken" など）をノード入力へ写すセンサー層
+// 位置で並べた入力配列の代わりに、信号名とノード名の対応・ゲイン・オフセットで入力を組み立てる
+
+/// 信号 1 つとノード 1 つの対応: 入力 = 値 × gain + offset
+#[derive(Clone, Debug, PartialEq)]
+pub struct SensorBinding {
+    pub signal: String,
+    /// 信号名のハッシュ（Java の String.hashCode と同じ値）
+    pub hash: i32,
+    pub node: String,
+    pub gain: f32,
+    pub offset: f32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SensorMap {
+    pub bindings: Vec<SensorBinding>,
+}
+
+impl SensorMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Java の String.hashCode と同じハッシュ（JNI 側は毎 tick 文字列を渡さずに済む）
+    pub fn hash_name(name: &str) -> i32 {
+        name.encode_utf16().fold(0i32, |h, c| h.wrapping_mul(31).wrapping_add(c as i32))
+    }
+
+    /// 信号をノードへ結びつける（同じ信号を複数のノードへ、複数の信号を同じノードへ結んでもよい）
+    pub fn bind(&mut self, signal: &str, node: &str, gain: f32, offset: f32) {
+        self.bindings.push(SensorBinding { signal: signal.to_string(), hash: Self::hash_name(signal), node: node.to_string(), gain, offset });
+    }
+
+    /// 信号の対応をすべて外し、外した数を返す
+    pub fn unbind(&mut self, signal: &str) -> usize {
+        let before = self.bindings.len();
+        self.bindings.retain(|b| b.signal != signal);
+        before - self.bindings.len()
+    }
+
+    /// 名前付きの信号からノード入力を組み立てる。node_index はノード名 -> インデックス
+    /// 届いていない信号・存在しないノードへの対応は寄与しない
+    pub fn inputs(&self, signals: &[(&str, f32)], node_count: usize, node_index: impl Fn(&str) -> Option<usize>) -> Vec<f32> {
+        let hashed: Vec<(i32, f32)> = signals.iter().map(|&(name, value)| (Self::hash_name(name), value)).collect();
+        self.inputs_hashed(&hashed, node_count, node_index)
+    }
+
+    /// inputs のハッシュ版（JNI から信号名のハッシュと値で渡す）
+    pub fn inputs_hashed(&self, signals: &[(i32, f32)], node_count: usize, node_index: impl Fn(&str) -> Option<usize>) -> Vec<f32> {
+        let mut inputs = vec![0.0; node_count];
+        for binding in &self.bindings {
+            let Some(node) = node_index(&binding.node).filter(|&idx| idx < node_count) else { continue };
+            for &(_, value) in signals.iter().filter(|&&(hash, _)| hash == binding.hash) {
+                inputs[node] += value * binding.gain + binding.offset;
+            }
+        }
+        inputs
+    }
+}
@@ -0,0 +1,105 @@
+// src/core/shadow.rs
+use super::singularity::Singularity;
+use std::collections::VecDeque;
+
+/// シャドウモードで記録された1回分の意思決定
+#[derive(Clone, Debug)]
+pub struct ShadowDecision {
+    pub state_idx: usize,
+    pub live_actions: Vec<usize>,
+    pub candidate_actions: Vec<usize>,
+    /// ライブモデルの行動に対して実際に得られた報酬（学習前は None）
+    pub reward: Option<f32>,
+    /// 候補モデルの行動に対する期待報酬（一致時は実報酬、不一致時は経験統計からの推定）
+    pub candidate_expected: Option<f32>,
+}
+
+/// 一致率と期待報酬のレポート
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ShadowReport {
+    pub decisions: usize,
+    pub agreements: usize,
+    pub agreement_rate: f32,
+    pub live_mean_reward: f32,
+    pub candidate_expected_reward: f32,
+    /// 期待報酬を算出できた意思決定の数
+    pub estimated_decisions: usize,
+    /// 指定した意思決定数に達したか
+    pub complete: bool,
+}
+
+/// 候補モデルにライブモデルと同じ入力を与え、その仮想的な意思決定を記録する（実行はしない）
+pub struct ShadowMode {
+    pub candidate: Singularity,
+    /// この回数の意思決定でレポートが完了する
+    pub report_after: usize,
+    pub log: VecDeque<ShadowDecision>,
+    decisions: usize,
+    agreements: usize,
+    reward_sum: f32,
+    rewarded: usize,
+    expected_sum: f32,
+    estimated: usize,
+}
+
+impl ShadowMode {
+    pub fn new(candidate: Singularity, report_after: usize) -> Self {
+        Self {
+            candidate,
+            report_after: report_after.max(1),
+            log: VecDeque::new(),
+            decisions: 0,
+            agreements: 0,
+            reward_sum: 0.0,
+            rewarded: 0,
+            expected_sum: 0.0,
+            estimated: 0,
+        }
+    }
+
+    pub fn record_decision(&mut self, state_idx: usize, live_actions: &[usize], candidate_actions: Vec<usize>) {
+        self.decisions += 1;
+        if live_actions == candidate_actions.as_slice() { self.agreements += 1; }
+        self.log.push_back(ShadowDecision {
+            state_idx,
+            live_actions: live_actions.to_vec(),
+            candidate_actions,
+            reward: None,
+            candidate_expected: None,
+        });
+        while self.log.len() > self.report_after { self.log.pop_front(); }
+    }
+
+    /// 報酬未確定の意思決定に報酬を割り当てる
+    /// estimate(state, action) はライブ側の経験統計から候補行動の平均報酬を推定する
+    pub fn record_reward(&mut self, reward: f32, estimate: impl Fn(usize, usize) -> Option<f32>) {
+        for d in self.log.iter_mut().rev() {
+            if d.reward.is_some() { break; }
+            d.reward = Some(reward);
+            self.reward_sum += reward;
+            self.rewarded += 1;
+
+            d.candidate_expected = if d.candidate_actions == d.live_actions {
+                Some(reward)
+            } else {
+                d.candidate_actions.first().and_then(|&a| estimate(d.state_idx, a))
+            };
+            if let Some(e) = d.candidate_expected {
+                self.expected_sum += e;
+                self.estimated += 1;
+            }
+        }
+    }
+
+    pub fn report(&self) -> ShadowReport {
+        ShadowReport {
+            decisions: self.decisions,
+            agreements: self.agreements,
+            agreement_rate: if self.decisions > 0 { self.agreements as f32 / self.decisions as f32 } else { 0.0 },
+            live_mean_reward: if self.rewarded > 0 { self.reward_sum / self.rewarded as f32 } else { 0.0 },
+            candidate_expected_reward: if self.estimated > 0 { self.expected_sum / self.estimated as f32 } else { 0.0 },
+            estimated_decisions: self.estimated,
+            complete: self.decisions >= self.report_after,
+        }
+    }
+}
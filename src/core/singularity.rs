@@ -1,9 +1,40 @@
-use super::node::Node;
-use super::mwso::MWSO;
+use super::node::{Activation, Node, NodeBatch, NodeCoupling, NodeHistoryStats, NodeSpec, NODE_AGGRESSION, NODE_FEAR, NODE_REFLEX, NODE_TACTICAL};
+use super::action_layout::ActionLayout;
+use super::mwso::{GravityStats, MWSO, RecallResult};
 use super::mwso::ShardedMWSO;
+use super::wave_view::WaveView;
+use super::hierarchy::StateHierarchy;
+use super::opponent::OpponentModel;
+use super::meta::{MetaController, MetaParams};
+use super::config::{Coupling, ExplorationPolicy, Integrator, LearnQueuePolicy, MWSOConfig, SingularityConfig};
+use super::learn_queue::{LearnQueue, LearnQueueStats, PendingLearn};
+use super::score_cache::ScoreCache;
+use super::shadow::{ShadowMode, ShadowReport};
+use super::archive::{ArchivedState, ColdArchive};
+use super::encoder::{StateEncoder, TileCoder};
+use super::episode::{EpisodeEvent, EpisodeLog, ExplorationState};
+use super::stats::{RuntimeStats, StatsReport};
+use super::knowledge::{ActionExpr, Bootstrapper, Condition, HamiltonianRule, LearnedRule, PositionIndex, RankedActions, RuleActivation, RuleConflictPolicy, region_condition_id};
+use super::symmetry::{SymmetryAnalyzer, SymmetryKind, SymmetryProposal};
+use super::dsym::{self, DsymReader, DsymWriter};
+use super::memory_bank::{BankRoutingPolicy, MemoryBank};
+use super::noise::ExplorationNoise;
+use super::sensor::SensorMap;
+use super::neuromod::Neuromodulators;
+use super::personality::Personality;
+use super::field_schedule::FieldDecay;
+use super::quantized::{FieldPrecision, QuantizedRows};
+use super::spectrum::Spectrum;
+use super::state_code::StateCoding;
+use super::wave::from_wave;
 use std::fs::File;
-use std::io::{self, Read, Write};
-use std::collections::VecDeque;
+use std::io;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// learn 1回あたりのペナルティ行列の減衰率
+const PENALTY_DECAY: f32 = 0.995;
 
 #[derive(Clone, Debug)]
 pub struct Experience {
@@ -11,28 +42,180 @@ pub struct Experience {
     pub actions: Vec<usize>,
 }
 
+/// 保存済み軌跡の1ステップ
+#[derive(Clone, Debug)]
+pub struct TrajectoryStep {
+    pub state_idx: usize,
+    pub actions: Vec<usize>,
+    /// 重要度重み π(a|s) / μ(a|s)（現在の方策と記録時の方策の確率比）
+    pub importance: f32,
+}
+
 #[derive(Clone, Debug)]
 pub struct VectorExperience {
     pub state_weights: Vec<(usize, f32)>,
     pub actions: Vec<usize>,
 }
 
+/// 意思決定スコアの内訳（get_best_in_range で合算される各項）
+#[derive(Clone, Debug)]
+pub struct ScoreBreakdown {
+    pub category: usize,
+    /// 全体アクションインデックス
+    pub action: usize,
+    pub wave: f32,
+    pub fatigue_penalty: f32,
+    pub knowledge: f32,
+    pub learned_rule: f32,
+    pub neuron: f32,
+    pub momentum: f32,
+    pub opponent: f32,
+    pub morale: f32,
+    pub total: f32,
+    /// 実際に選択されたアクションか
+    pub chosen: bool,
+}
+
+/// 温度コントローラーの1ステップ分の内訳
+#[derive(Clone, Copy, Debug)]
+pub struct TemperatureTrace {
+    /// 報酬のみで決まる温度
+    pub reward_driven: f32,
+    /// フラストレーションによる加熱
+    pub frustration_heat: f32,
+    /// 士気による下限
+    pub floor: f32,
+    /// 介入レベルによる上限
+    pub ceiling: f32,
+    pub result: f32,
+}
+
+/// 1 回の意思決定で知識層に寄与したルール
+#[derive(Clone, Debug, Default)]
+pub struct RuleAudit {
+    pub state_idx: usize,
+    /// 条件による外場（衝突の解決後）、状態のルール、領域のルールの順
+    pub activations: Vec<RuleActivation>,
+}
+
+/// recall_state の結果
+#[derive(Clone, Debug, Default)]
+pub struct StateRecall {
+    /// 状態の鍵で記憶波に問い合わせた結果（シャード構成では最も共鳴したシャードのもの）
+    pub wave: RecallResult,
+    /// 行動ごとの (平均報酬, 回数)。この状態で過去に取った行動の実績
+    pub actions: Vec<(f32, u32)>,
+}
+
+impl StateRecall {
+    /// 過去に取った行動のうち平均報酬が最も高いもの
+    pub fn best_action(&self) -> Option<usize> {
+        self.actions.iter().enumerate()
+            .filter(|(_, a)| a.1 > 0)
+            .max_by(|a, b| a.1.0.total_cmp(&b.1.0))
+            .map(|(action, _)| action)
+    }
+}
+
+/// select_actions の作業領域（呼び出しごとの再確保を避けるためのもので、状態ではない）
+#[derive(Clone, Debug, Default)]
+struct SelectScratch {
+    penalty_field: Vec<f32>,
+    cached_wave: Vec<f32>,
+    /// スカウト（128 次元）へ写したペナルティ場
+    scout_penalty: Vec<f32>,
+    scout_scores: Vec<f32>,
+    ranking: RankingScratch,
+}
+
+/// get_best_in_range の作業領域
+#[derive(Clone, Debug, Default)]
+struct RankingScratch {
+    resonance: Vec<Option<f32>>,
+    all_scores: Vec<f32>,
+    mwso_scores: Vec<f32>,
+    ranked: Vec<(usize, f32)>,
+}
+
+/// checkpoint() が保存する可変状態一式（先読み探索のロールバック用）
+#[derive(Clone)]
+pub struct StateSnapshot {
+    nodes: Vec<Node>,
+    mwso: MWSO,
+    scout_mwso: MWSO,
+    sharded_mwso: Option<ShardedMWSO>,
+    penalty_matrix: Vec<f32>,
+    compact_penalties: Option<QuantizedRows>,
+    penalty_dim: usize,
+    penalty_clock: u64,
+    penalty_row_clock: Vec<u64>,
+    system_temperature: f32,
+    last_topology_update_temp: f32,
+    adrenaline: f32,
+    frustration: f32,
+    failure_streaks: HashMap<usize, u32>,
+    velocity_trust: f32,
+    fatigue_map: Vec<f32>,
+    morale: f32,
+    patience: f32,
+    reward_streak: i32,
+    stalemate_streak: u32,
+    last_actions: Vec<usize>,
+    last_state_idx: usize,
+    action_momentum: Vec<f32>,
+    input_history: VecDeque<usize>,
+    history: VecDeque<Experience>,
+    vector_history: VecDeque<VectorExperience>,
+    learned_rules: Vec<LearnedRule>,
+    rule_clock: u64,
+    bootstrapper: Bootstrapper,
+    crystallized: HashMap<(usize, usize), u32>,
+    action_visits: HashMap<usize, Vec<u32>>,
+    neuromodulators: Option<Neuromodulators>,
+    opponent_model: Option<OpponentModel>,
+    meta_params: MetaParams,
+    meta_controller: Option<MetaController>,
+    temperature_trace: VecDeque<TemperatureTrace>,
+    rule_audit: VecDeque<RuleAudit>,
+    state_last_active: HashMap<usize, u64>,
+    state_aliases: HashMap<usize, usize>,
+    symmetry: SymmetryAnalyzer,
+    last_region: Option<usize>,
+    exploration_timer: usize,
+    current_focus_action: usize,
+}
+
 pub struct Singularity {
+    pub config: SingularityConfig,
     pub nodes: Vec<Node>,
     pub mwso: MWSO,
     pub scout_mwso: MWSO, // 低次元スカウト (128次元固定)
     pub sharded_mwso: Option<ShardedMWSO>,
     pub bootstrapper: crate::core::knowledge::Bootstrapper,
     pub active_conditions: Vec<i32>, 
+    /// 条件ごとの発動レベル (条件 ID, 0.0〜1.0)。知識層の外場はこれで計算する
+    /// set_active_conditions / set_condition_levels が active_conditions と揃えて更新する
+    pub condition_levels: Vec<(i32, f32)>,
     pub system_temperature: f32,
     pub temperature_locked: bool,
+    /// 外部（監督側）からの介入レベル 0.0〜1.0。高いほど温度の上限が下がる
+    pub intervention_level: f32,
+    pub temperature_trace: VecDeque<TemperatureTrace>,
+    /// 直近 64 回の意思決定で発動したルール（ルール集が実際に行動を動かしているかの監査用）
+    pub rule_audit: VecDeque<RuleAudit>,
     pub last_topology_update_temp: f32,
     pub adrenaline: f32,
     pub frustration: f32,
+    /// state_idx -> 連続失敗回数（フラストレーションの蓄積源）
+    pub failure_streaks: HashMap<usize, u32>,
     pub velocity_trust: f32,
     pub fatigue_map: Vec<f32>,
     pub morale: f32,
     pub patience: f32,
+    /// 正: 連勝数 / 負: 連敗数
+    pub reward_streak: i32,
+    /// 勝敗のつかない結果の連続回数
+    pub stalemate_streak: u32,
     pub category_sizes: Vec<usize>, 
     pub action_size: usize,    
     pub state_size: usize,
@@ -44,23 +227,83 @@ pub struct Singularity {
     pub history: VecDeque<Experience>,
     pub vector_history: VecDeque<VectorExperience>,
     pub max_history: usize,
-    pub learned_rules: Vec<(usize, usize, usize)>, 
+    pub learned_rules: Vec<LearnedRule>,
+    /// (state, action) -> learned_rules の位置
+    learned_index: PositionIndex<(usize, usize)>,
+    /// learn の呼び出し回数（ルールの鮮度の基準）
+    pub rule_clock: u64,
+    /// ハミルトニアン・ルールへ昇格済みの (state, action) -> 連続失敗回数
+    pub crystallized: HashMap<(usize, usize), u32>,
+    /// 行ごとに遅延減衰されるため、行全体を直接読む場合は先に settle_penalties を呼ぶこと
+    /// set_penalty_precision で量子化保持に切り替えると空になり、compact_penalties が持つ
     pub penalty_matrix: Vec<f32>, 
+    compact_penalties: Option<QuantizedRows>,
+    /// ペナルティ減衰の累積ステップ数（learn ごとに 1 進む）
+    pub penalty_clock: u64,
+    /// 各行に減衰を最後に反映した時点の penalty_clock
+    pub penalty_row_clock: Vec<u64>,
+    pub hierarchy: Option<StateHierarchy>,
+    /// 感情ノードから波動場の行動帯域への結合
+    pub node_couplings: Vec<NodeCoupling>,
+    /// 名前付きのゲーム信号 -> ノード入力の対応
+    pub sensors: SensorMap,
+    pub last_region: Option<usize>,
+    pub opponent_model: Option<OpponentModel>,
+    /// 学習率・疲労・探索ノイズをまとめて調整する神経修飾物質（None なら従来どおり固定）
+    pub neuromodulators: Option<Neuromodulators>,
+    /// 最後に適用した性格プリセット
+    pub personality: Personality,
+    pub symmetry: SymmetryAnalyzer,
+    /// 統合された状態 -> 代表状態
+    pub state_aliases: HashMap<usize, usize>,
+    pub meta_params: MetaParams,
+    pub meta_controller: Option<MetaController>,
+    pub learn_queue: LearnQueue,
+    /// シャドウモードで評価中の候補モデル
+    pub shadow: Option<Box<ShadowMode>>,
+    /// state_idx -> 最後にアクセスされた rule_clock
+    pub state_last_active: HashMap<usize, u64>,
+    pub cold_archive: Option<ColdArchive>,
+    /// 学習・意思決定の途中で起きたコールドストレージの読み書きの失敗回数（呼び出し元へ返せないため数える）
+    pub archive_errors: u64,
+    pub encoder: StateEncoder,
+    pub tile_coder: Option<TileCoder>,
+    /// 記録中のエピソードログ（select_actions / learn を記録し、それ以外の意思決定・学習の経路は回数だけ数える）
+    pub episode_log: Option<EpisodeLog>,
+    /// UCB 用: state_idx -> 行動ごとの選択回数
+    pub action_visits: HashMap<usize, Vec<u32>>,
 
     pub empty_penalty: Vec<f32>,
     pub exploration_beta: f32,    
     pub exploration_timer: usize,
     pub current_focus_action: usize,
-
-    pub idx_aggression: usize,
-    pub idx_fear: usize,
-    pub idx_tactical: usize,
-    pub idx_reflex: usize,
+    pub last_score_gap: Option<f32>,
+    pub last_explanation: Vec<ScoreBreakdown>,
+    scratch: SelectScratch,
+    /// ノードの一括更新用の作業領域
+    node_batch: NodeBatch,
+    /// 時間予算付きの意思決定で最後に計算した、状態ごとの波動スコア（締め切り直前のフォールバック用）
+    pub score_cache: ScoreCache,
+    /// 直前の意思決定が締め切りのために縮退モードで行われたか
+    pub last_decision_degraded: bool,
+    /// schedule_action_field が次に割り当てる ID（シャードをまたぐ場を同じ ID で取り消すため）
+    next_field_id: u64,
+    /// 呼び出しごとの計測（&self の save_to_file からも記録するため Mutex で包む）
+    stats: Mutex<RuntimeStats>,
 }
 
 impl Singularity {
     pub fn new(state_size: usize, category_sizes: Vec<usize>) -> Self {
-        let nodes = vec![Node::new(0.5), Node::new(0.4), Node::new(0.3), Node::new(0.3)];
+        Self::with_nodes(state_size, category_sizes, &NodeSpec::defaults())
+    }
+
+    /// 任意の名前付きノード構成で生成する（既定名のノードが無い場合、その寄与は 0 として扱う）
+    pub fn with_nodes(state_size: usize, category_sizes: Vec<usize>, node_specs: &[NodeSpec]) -> Self {
+        let nodes: Vec<Node> = node_specs.iter().map(|spec| {
+            let mut node = Node::named(&spec.name, spec.decay);
+            node.activation = spec.activation;
+            node
+        }).collect();
         let total_action_size: usize = category_sizes.iter().sum();
 
         let shard_threshold = 16; // 16アクション以上はシャード化
@@ -75,6 +318,7 @@ impl Singularity {
         };
         
         Self {
+            config: SingularityConfig::default(),
             nodes,
             mwso: MWSO::new(required_dim),
             scout_mwso: MWSO::new(128), // 常に高速な128次元で探索を回す
@@ -85,15 +329,22 @@ impl Singularity {
             },
             bootstrapper: crate::core::knowledge::Bootstrapper::new(),
             active_conditions: Vec::new(),
+            condition_levels: Vec::new(),
             system_temperature: 0.5,
             temperature_locked: false,
+            intervention_level: 0.0,
+            temperature_trace: VecDeque::with_capacity(64),
+            rule_audit: VecDeque::with_capacity(64),
             last_topology_update_temp: -1.0,
             adrenaline: 0.0,
             frustration: 0.0,
+            failure_streaks: HashMap::new(),
             velocity_trust: 1.0,
             fatigue_map: vec![0.0; total_action_size],
             morale: 1.0,
             patience: 1.0,
+            reward_streak: 0,
+            stalemate_streak: 0,
             category_sizes: category_sizes.clone(),
             action_size: total_action_size,
             state_size,
@@ -106,58 +357,419 @@ impl Singularity {
             vector_history: VecDeque::with_capacity(32),
             max_history: 15,
             learned_rules: Vec::new(),
+            learned_index: PositionIndex::default(),
+            rule_clock: 0,
+            crystallized: HashMap::new(),
             penalty_matrix: vec![0.0; state_size * penalty_dim],
+            compact_penalties: None,
+            penalty_clock: 0,
+            penalty_row_clock: vec![0; state_size],
+            hierarchy: None,
+            node_couplings: Vec::new(),
+            sensors: SensorMap::new(),
+            last_region: None,
+            opponent_model: None,
+            neuromodulators: None,
+            personality: Personality::Adaptive,
+            symmetry: SymmetryAnalyzer::new(total_action_size),
+            state_aliases: HashMap::new(),
+            meta_params: MetaParams::default(),
+            meta_controller: None,
+            learn_queue: LearnQueue::default(),
+            shadow: None,
+            state_last_active: HashMap::new(),
+            cold_archive: None,
+            archive_errors: 0,
+            encoder: StateEncoder::Dense,
+            tile_coder: None,
+            episode_log: None,
+            action_visits: HashMap::new(),
             empty_penalty: vec![0.0; penalty_dim],
             exploration_beta: 0.1, 
             exploration_timer: 0,
             current_focus_action: 0,
-            idx_aggression: 0,
-            idx_fear: 1,
-            idx_tactical: 2,
-            idx_reflex: 3,
+            last_score_gap: None,
+            last_explanation: Vec::new(),
+            scratch: SelectScratch::default(),
+            node_batch: NodeBatch::new(),
+            score_cache: ScoreCache::default(),
+            last_decision_degraded: false,
+            next_field_id: 0,
+            stats: Mutex::new(RuntimeStats::default()),
+        }
+    }
+
+    /// 名前からノードのインデックスを引く
+    pub fn node_index(&self, name: &str) -> Option<usize> {
+        self.nodes.iter().position(|n| n.name == name)
+    }
+
+    /// 実行中にノードを追加し、そのインデックスを返す（同じ名前のノードが既にあれば None）
+    pub fn add_node(&mut self, name: &str, decay: f32) -> Option<usize> {
+        if !name.is_empty() && self.node_index(name).is_some() { return None; }
+        let mut node = Node::named(name, decay);
+        node.refractory_period = self.config.refractory_period;
+        node.spike_threshold = self.config.spike_threshold;
+        self.nodes.push(node);
+        Some(self.nodes.len() - 1)
+    }
+
+    /// ノードの応答曲線を切り替える（範囲外なら false）
+    pub fn set_node_activation(&mut self, idx: usize, activation: Activation) -> bool {
+        match self.nodes.get_mut(idx) {
+            Some(node) => { node.activation = activation; true }
+            None => false,
+        }
+    }
+
+    /// 全ノードの不応期を設定する（period が 0 なら無効。以後 add_node で加えたノードにも適用される）
+    pub fn set_refractory(&mut self, period: u32, spike_threshold: f32) {
+        self.config.refractory_period = period;
+        self.config.spike_threshold = spike_threshold;
+        for node in &mut self.nodes {
+            node.refractory_period = period;
+            node.spike_threshold = spike_threshold;
+            if period == 0 { node.refractory_left = 0; }
+        }
+    }
+
+    /// ノードを取り除き、残りのシナプスの入力元インデックスを詰め直す
+    /// 取り除いたノードから入力を受けていたシナプスも削除する
+    pub fn remove_node(&mut self, idx: usize) -> bool {
+        if idx >= self.nodes.len() { return false; }
+        self.nodes.remove(idx);
+        for node in &mut self.nodes {
+            node.synapses.retain(|s| s.target_id != idx);
+            for synapse in &mut node.synapses {
+                if synapse.target_id > idx { synapse.target_id -= 1; }
+            }
         }
+        true
+    }
+
+    /// 名前付きノードの現在の状態（存在しなければ 0.0）
+    pub fn node_state(&self, name: &str) -> f32 {
+        self.node_index(name).map_or(0.0, |idx| self.nodes[idx].state)
     }
 
     pub fn set_active_conditions(&mut self, conditions: &[i32]) {
         self.active_conditions = conditions.to_vec();
+        self.condition_levels = conditions.iter().map(|&id| (id, 1.0)).collect();
     }
 
-    pub fn select_actions_vector(&mut self, state_weights: &[(usize, f32)]) -> Vec<i32> {
-        let speed_boost = (self.adrenaline * 0.5).clamp(0.0, 1.0);
-        let focus_factor = (self.nodes[self.idx_tactical].state * 0.5).clamp(0.0, 1.0);
+    /// 条件を段階的な発動レベル (0.0〜1.0) で与える（例: HP が減るほど「HP低」を 1.0 に近づける）
+    /// ルールの強度はレベルに比例し、レベルが 0 より大きい条件は active_conditions にも入る
+    pub fn set_condition_levels(&mut self, levels: &[(i32, f32)]) {
+        self.condition_levels = levels.to_vec();
+        self.active_conditions = levels.iter().filter(|&&(_, level)| level > 0.0).map(|&(id, _)| id).collect();
+    }
+
+    /// 密な state_size を持たない Feature Hashing モードで生成する
+    /// 状態は MWSO の注入パターン空間（波動場の次元）へ直接ハッシュされる
+    pub fn new_hashed(category_sizes: Vec<usize>) -> Self {
+        let mut ai = Self::new(0, category_sizes);
+        let buckets = ai.mwso.dim;
+        ai.state_size = buckets;
+        ai.penalty_matrix = vec![0.0; buckets * ai.penalty_dim];
+        ai.penalty_row_clock = vec![0; buckets];
+        ai.encoder = StateEncoder::hashed(buckets);
+        ai
+    }
+
+    /// 任意の u64 状態識別子で意思決定する（エンコーダ経由）
+    pub fn select_actions_id(&mut self, state_id: u64) -> Vec<i32> {
+        match self.encoder {
+            StateEncoder::Dense => self.select_actions(state_id as usize),
+            _ => {
+                let weights = self.encoder.encode_id(state_id);
+                self.select_actions_vector(&weights)
+            }
+        }
+    }
+
+    /// 特徴ベクトル (特徴ID, 値) で意思決定する（エンコーダ経由）
+    pub fn select_actions_features(&mut self, features: &[(u64, f32)]) -> Vec<i32> {
+        let weights = self.encoder.encode_features(features);
+        self.select_actions_vector(&weights)
+    }
+
+    /// 連続特徴 (距離・HP比・資源量など) のタイルコーディングを設定する
+    pub fn set_tile_coder(&mut self, coder: TileCoder) {
+        self.tile_coder = Some(coder);
+    }
+
+    /// 連続特徴から重なり合うタイル群を注入して意思決定する
+    /// Dense モードではタイルを state_size 内へハッシュする。タイルコーダ未設定なら None
+    pub fn select_actions_continuous(&mut self, inputs: &[f32]) -> Option<Vec<i32>> {
+        let coder = self.tile_coder.as_ref()?;
+        let encoder = match self.encoder {
+            StateEncoder::Dense => StateEncoder::Hashed { buckets: self.state_size.clamp(1, self.mwso.dim), probes: 1 },
+            e => e,
+        };
+        let weights = coder.encode(inputs, &encoder);
+        Some(self.select_actions_vector(&weights))
+    }
+
+    /// 多解像度の状態階層を設定する
+    /// 領域ごとのペナルティ行を penalty_matrix の末尾（state_size 以降）に確保する
+    pub fn set_state_hierarchy(&mut self, hierarchy: StateHierarchy) {
+        let rows = self.state_size + hierarchy.region_count;
+        self.settle_penalties();
+        match self.compact_penalties.as_mut() {
+            Some(store) => {
+                store.resize_rows(self.state_size);
+                store.resize_rows(rows);
+            }
+            None => {
+                self.penalty_matrix.truncate(self.state_size * self.penalty_dim);
+                self.penalty_matrix.resize(rows * self.penalty_dim, 0.0);
+            }
+        }
+        self.penalty_row_clock.resize(rows, self.penalty_clock);
+        self.hierarchy = Some(hierarchy);
+    }
+
+    /// 領域を表す仮想状態インデックス（ペナルティ行として使用）
+    pub fn region_state_idx(&self, region: usize) -> usize {
+        self.state_size + region
+    }
+
+    /// 領域レベルのハミルトニアン・ルールを登録する（同じ領域の全タイルに転移する）
+    pub fn add_region_rule(&mut self, region: usize, target_action: usize, strength: f32) {
+        self.bootstrapper.add_hamiltonian_rule(region_condition_id(region), target_action, strength);
+    }
+
+    /// penalty_matrix が保持している行数（細粒度状態 + 領域）
+    fn penalty_rows(&self) -> usize {
+        match &self.compact_penalties {
+            Some(store) => store.rows().max(1),
+            None => (self.penalty_matrix.len() / self.penalty_dim).max(1),
+        }
+    }
+
+    /// ペナルティ行列の要素数（量子化保持中も f32 換算の要素数）
+    fn penalty_len(&self) -> usize {
+        self.compact_penalties.as_ref().map_or(self.penalty_matrix.len(), QuantizedRows::len)
+    }
+
+    /// cells の各ペナルティを f で書き換える（量子化保持中は 1 要素ずつ戻して書き直す）
+    fn update_penalty_cells(&mut self, cells: std::ops::Range<usize>, f: impl Fn(f32) -> f32) {
+        let end = cells.end.min(self.penalty_len());
+        let cells = cells.start.min(end)..end;
+        match self.compact_penalties.as_mut() {
+            Some(store) => {
+                for i in cells { store.set(i, f(store.get(i))); }
+            }
+            None => {
+                for p in &mut self.penalty_matrix[cells] { *p = f(*p); }
+            }
+        }
+    }
+
+    /// 行 row を f32 で取り出す（遅延減衰は反映しない）
+    fn penalty_row_values(&self, row: usize) -> Vec<f32> {
+        let dim = self.penalty_dim;
+        match &self.compact_penalties {
+            Some(store) => {
+                let mut values = Vec::with_capacity(dim);
+                store.extend_row(row, &mut values);
+                values
+            }
+            None => self.penalty_matrix[row * dim..(row + 1) * dim].to_vec(),
+        }
+    }
+
+    fn write_penalty_row(&mut self, row: usize, values: &[f32]) {
+        let dim = self.penalty_dim;
+        match self.compact_penalties.as_mut() {
+            Some(store) => store.write_row(row, values),
+            None => self.penalty_matrix[row * dim..(row + 1) * dim].copy_from_slice(values),
+        }
+    }
+
+    pub fn penalty_precision(&self) -> FieldPrecision {
+        self.compact_penalties.as_ref().map_or(FieldPrecision::F32, QuantizedRows::precision)
+    }
+
+    /// ペナルティ行列と重力場を保持する精度を切り替える（既存の値はそのまま変換する）
+    /// F16 でメモリは約半分、I8 で約 1/4。F32 以外では penalty_matrix・gravity_field は空になる
+    pub fn set_field_precision(&mut self, precision: FieldPrecision) {
+        self.set_penalty_storage(precision);
+        self.mwso.set_gravity_precision(precision);
+        self.scout_mwso.set_gravity_precision(precision);
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            for shard in &mut sharded.shards { shard.set_gravity_precision(precision); }
+        }
+    }
+
+    fn set_penalty_storage(&mut self, precision: FieldPrecision) {
+        let values = match self.compact_penalties.take() {
+            Some(store) => store.to_f32(),
+            None => std::mem::take(&mut self.penalty_matrix),
+        };
+        match precision {
+            FieldPrecision::F32 => self.penalty_matrix = values,
+            _ => self.compact_penalties = Some(QuantizedRows::new(precision, self.penalty_dim, &values)),
+        }
+    }
+
+    /// ペナルティ行列が実際に使っているバイト数
+    pub fn penalty_bytes(&self) -> usize {
+        self.compact_penalties.as_ref().map_or(self.penalty_matrix.len() * 4, QuantizedRows::heap_bytes)
+    }
+
+    /// 行 row にまだ反映していない減衰係数（0.995 の経過ステップ乗）
+    fn pending_penalty_decay(&self, row: usize) -> f32 {
+        let last = self.penalty_row_clock.get(row).copied().unwrap_or(self.penalty_clock);
+        match self.penalty_clock.saturating_sub(last) {
+            0 => 1.0,
+            // 0.995^5000 は f32 で 0 に潰れるため、それ以上は計算しない
+            dt if dt > 5000 => 0.0,
+            dt => PENALTY_DECAY.powi(dt as i32),
+        }
+    }
+
+    /// 行 row に溜まった減衰をまとめて反映する（行を書き換える前に必ず呼ぶ）
+    fn settle_penalty_row(&mut self, row: usize) {
+        let factor = self.pending_penalty_decay(row);
+        let Some(clock) = self.penalty_row_clock.get_mut(row) else { return; };
+        *clock = self.penalty_clock;
+        if factor == 1.0 { return; }
+        let dim = self.penalty_dim;
+        if let Some(store) = self.compact_penalties.as_mut() {
+            store.scale_row(row, factor);
+        } else if let Some(values) = self.penalty_matrix.get_mut(row * dim..(row + 1) * dim) {
+            for p in values { *p *= factor; }
+        }
+    }
+
+    /// 全行の遅延減衰を反映する（penalty_matrix を丸ごと読む・保存する前に呼ぶ）
+    pub fn settle_penalties(&mut self) {
+        for row in 0..self.penalty_row_clock.len() {
+            self.settle_penalty_row(row);
+        }
+    }
+
+    /// 知識ルールの禁止（負の共鳴）を、該当する行動のビンへペナルティとして加える
+    fn add_knowledge_penalties(&self, field: &mut [f32], resonance: &[Option<f32>]) {
+        for (action_idx, strength) in resonance.iter().enumerate() {
+            let Some(strength) = *strength else { continue };
+            if strength >= 0.0 { continue; }
+            let p_val = strength.abs() * 50.0; // ペナルティ強度を増幅して注入
+            for idx in self.penalty_bins(action_idx) {
+                if let Some(p) = field.get_mut(idx) { *p += p_val; }
+            }
+        }
+    }
+
+    /// 感情ノードの活性を、結合した行動帯域のペナルティとして場に加える
+    fn add_emotion_penalties(&self, field: &mut [f32]) {
+        for coupling in &self.node_couplings {
+            let p_val = self.node_state(&coupling.node) * coupling.gain;
+            if p_val <= 0.0 { continue; }
+            for action_idx in coupling.actions.clone() {
+                for idx in self.penalty_bins(action_idx) {
+                    if let Some(p) = field.get_mut(idx) { *p += p_val; }
+                }
+            }
+        }
+    }
+
+    /// 感情ノードと行動帯域を結合する（ノードが無い・範囲が空か行動数を超える・gain が負なら false）
+    pub fn add_node_coupling(&mut self, node: &str, actions: std::ops::Range<usize>, gain: f32) -> bool {
+        if self.node_index(node).is_none() || actions.is_empty() || actions.end > self.action_size || gain < 0.0 {
+            return false;
+        }
+        self.node_couplings.push(NodeCoupling { node: node.to_string(), actions, gain });
+        true
+    }
+
+    /// 直前の状態 (last_state_idx) の現在のペナルティ場。遅延減衰と知識ルールの禁止を反映する
+    /// 行動選択以外で波を時間発展させるときも、この場で波を減衰させる
+    pub fn live_penalty_field(&self) -> Vec<f32> {
+        let mut field = self.accumulate_penalty_field(&[(self.last_state_idx, 1.0)]);
+        let resonance = self.bootstrapper.calculate_graded_resonance_field(&self.condition_levels, self.action_size);
+        self.add_knowledge_penalties(&mut field, &resonance);
+        self.add_emotion_penalties(&mut field);
+        field
+    }
+
+    /// 行動ごとの平均ペナルティを、スカウト（低次元）の同じ行動のビンへ写す
+    fn fill_scout_penalty(&self, field: &[f32], out: &mut Vec<f32>) {
+        out.clear();
+        out.resize(self.scout_mwso.dim, 0.0);
+        for action in 0..self.action_size {
+            let Some(values) = field.get(self.penalty_bins(action)) else { continue };
+            if values.is_empty() { continue; }
+            let mean = values.iter().sum::<f32>() / values.len() as f32;
+            for idx in self.scout_mwso.action_bins(action, self.action_size) {
+                if let Some(p) = out.get_mut(idx) { *p = mean; }
+            }
+        }
+    }
 
+    /// 重み付き状態群からペナルティ場を合成する
+    pub fn accumulate_penalty_field(&self, state_weights: &[(usize, f32)]) -> Vec<f32> {
         let total_dim = self.penalty_dim;
-        let mut current_penalty_field = vec![0.0; total_dim];
-        
-        // Accumulate penalties from all weighted states
+        let rows = self.penalty_rows();
+        let mut field = vec![0.0; total_dim];
         for &(idx, w) in state_weights {
             if w < 0.001 { continue; }
-            let start = (idx % self.state_size) * total_dim;
-            if start + total_dim <= self.penalty_matrix.len() {
+            let row = idx % rows;
+            let start = row * total_dim;
+            let decay = self.pending_penalty_decay(row);
+            if let Some(store) = &self.compact_penalties {
+                store.accumulate_row(row, decay * w, &mut field);
+            } else if start + total_dim <= self.penalty_matrix.len() {
                 let state_penalty = &self.penalty_matrix[start..start + total_dim];
-                for i in 0..total_dim {
-                    current_penalty_field[i] += state_penalty[i] * w;
+                for (f, &p) in field.iter_mut().zip(state_penalty) {
+                    *f += p * decay * w;
                 }
             }
         }
+        field
+    }
 
-        // --- Knowledge-based Penalty Injection ---
-        let bin_per_action = self.mwso.dim / self.action_size;
-        let active_resonance = self.bootstrapper.calculate_resonance_field(&self.active_conditions, self.action_size);
-        for (action_idx, strength_opt) in active_resonance.iter().enumerate() {
-            if let Some(strength) = strength_opt {
-                if *strength < 0.0 {
-                    let p_val = strength.abs() * 50.0;
-                    let b_start = action_idx * bin_per_action;
-                    for j in 0..bin_per_action {
-                        if b_start + j < current_penalty_field.len() {
-                            current_penalty_field[b_start + j] += p_val;
-                        }
-                    }
-                }
-            }
+    /// タイル（細粒度）と所属領域（粗粒度）を異なる重みで同時に注入して意思決定する
+    /// 階層が未設定なら通常の select_actions と同じ
+    pub fn select_actions_hierarchical(&mut self, fine_idx: usize) -> Vec<i32> {
+        let (region, fine_w, coarse_w) = match &self.hierarchy {
+            Some(h) => match h.region(fine_idx) {
+                Some(r) => (r, h.fine_weight, h.coarse_weight),
+                None => return self.select_actions(fine_idx),
+            },
+            None => return self.select_actions(fine_idx),
+        };
+
+        let state_weights = [(fine_idx, fine_w), (self.region_state_idx(region), coarse_w)];
+        self.last_state_idx = fine_idx;
+        self.last_region = Some(region);
+        let results = self.select_actions_vector(&state_weights);
+        self.last_region = None;
+        results
+    }
+
+    pub fn select_actions_vector(&mut self, state_weights: &[(usize, f32)]) -> Vec<i32> {
+        self.note_unrecorded();
+        self.last_score_gap = None;
+        self.last_explanation.clear();
+        let speed_boost = (self.adrenaline * 0.5).clamp(0.0, 1.0);
+        let focus_factor = (self.node_state(NODE_TACTICAL) * 0.5).clamp(0.0, 1.0);
+
+        let state_weights: Vec<(usize, f32)> = state_weights.iter().map(|&(idx, w)| (self.canonical_state(idx), w)).collect();
+        let state_weights = state_weights.as_slice();
+        for &(idx, w) in state_weights {
+            if w >= 0.05 { self.touch_state(idx); }
         }
 
+        // Accumulate penalties from all weighted states
+        let mut current_penalty_field = self.accumulate_penalty_field(state_weights);
+
+        // --- Knowledge-based Penalty Injection ---
+        let active_resonance = self.bootstrapper.calculate_graded_resonance_field(&self.condition_levels, self.action_size);
+        self.add_knowledge_penalties(&mut current_penalty_field, &active_resonance);
+        self.add_emotion_penalties(&mut current_penalty_field);
+
         // --- Vector State Injection ---
         if let Some(ref mut sharded) = self.sharded_mwso {
             sharded.inject_vector_state(state_weights, 1.0, self.system_temperature, &current_penalty_field);
@@ -168,13 +780,15 @@ impl Singularity {
 
         // --- Scout Scouting ---
         let scout_temp = (self.system_temperature + 0.5).clamp(0.8, 1.5);
+        let mut scout_penalty = Vec::new();
+        self.fill_scout_penalty(&current_penalty_field, &mut scout_penalty);
         for &(idx, w) in state_weights {
             if w > 0.1 {
-                self.scout_mwso.inject_state(idx % 128, w, &vec![0.0; 128]);
+                self.scout_mwso.inject_state(idx % 128, w, &scout_penalty);
             }
         }
-        self.scout_mwso.step_core(0.1, speed_boost, focus_factor, scout_temp, &vec![0.0; 128]);
-        let scout_scores = self.scout_mwso.get_action_scores(0, self.action_size, 0.0, &vec![0.0; 128]);
+        self.scout_mwso.step_core(0.1, speed_boost, focus_factor, scout_temp, &scout_penalty);
+        let scout_scores = self.scout_mwso.get_action_scores(0, self.action_size, 0.0, &scout_penalty);
         let mut best_scout_action = 0;
         let mut max_scout_s = -f32::INFINITY;
         for (i, &s) in scout_scores.iter().enumerate() {
@@ -190,6 +804,7 @@ impl Singularity {
             }
         }
 
+        self.apply_exploration_noise();
         if let Some(ref mut sharded) = self.sharded_mwso {
             sharded.step_core(0.1, speed_boost, focus_factor, self.system_temperature, &current_penalty_field);
         } else {
@@ -198,13 +813,16 @@ impl Singularity {
 
         let mut results = Vec::with_capacity(self.category_sizes.len());
         let mut current_offset = 0;
-        let cat_sizes = self.category_sizes.clone();
-        for (cat_idx, &size) in cat_sizes.iter().enumerate() {
-            let best_idx = self.get_best_in_range(current_offset, size, &current_penalty_field);
+        let mut ranking = std::mem::take(&mut self.scratch.ranking);
+        for cat_idx in 0..self.category_sizes.len() {
+            let size = self.category_sizes[cat_idx];
+            let best_idx = self.get_best_in_range(current_offset, size, &current_penalty_field, None, &mut ranking);
             self.last_actions[cat_idx] = current_offset + best_idx;
             results.push(best_idx as i32);
             current_offset += size;
         }
+        self.scratch.ranking = ranking;
+        self.record_rule_activations();
 
         self.vector_history.push_back(VectorExperience {
             state_weights: state_weights.to_vec(),
@@ -212,57 +830,94 @@ impl Singularity {
         });
         if self.vector_history.len() > self.max_history { self.vector_history.pop_front(); }
 
+        let dominant = state_weights.iter()
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map_or(0, |&(idx, _)| idx);
+        self.run_shadow(dominant, |candidate| { candidate.select_actions_vector(state_weights); });
+
         results
     }
 
     pub fn select_actions(&mut self, state_idx: usize) -> Vec<i32> {
+        let mut results = Vec::with_capacity(self.category_sizes.len());
+        self.select_actions_into(state_idx, &mut results);
+        results
+    }
+
+    /// select_actions の書き込み先指定版（results は上書きされる）
+    /// 内部の作業バッファを使い回すため、ウォームアップ後の呼び出しではヒープ確保を行わない
+    pub fn select_actions_into(&mut self, state_idx: usize, results: &mut Vec<i32>) {
+        self.select_actions_until(state_idx, results, None);
+    }
+
+    /// 時間予算付きの意思決定（戻り値の bool は縮退モードで決定したかどうか）
+    /// 予算の 3/4 を過ぎると古い履歴の流れ注入を打ち切り、波動場の時間発展も省略して
+    /// この状態で前回計算したスコア（なければ現在の波動場）から選ぶ
+    pub fn select_actions_within(&mut self, state_idx: usize, budget: Duration) -> (Vec<i32>, bool) {
+        let deadline = Instant::now() + budget * 3 / 4;
+        let mut results = Vec::with_capacity(self.category_sizes.len());
+        let degraded = self.select_actions_until(state_idx, &mut results, Some(deadline));
+        (results, degraded)
+    }
+
+    /// deadline を指定した場合は締め切り直前に処理を省略し、省略したかどうかを返す
+    fn select_actions_until(&mut self, state_idx: usize, results: &mut Vec<i32>, deadline: Option<Instant>) -> bool {
+        let started = Instant::now();
+        let past_deadline = || deadline.is_some_and(|d| Instant::now() >= d);
+        let mut degraded = false;
+        let raw_state_idx = state_idx;
+        let log_state = self.episode_log.as_ref().map(|_| (self.system_temperature, ExplorationState::capture(self)));
+        let state_idx = self.canonical_state(state_idx);
+        self.touch_state(state_idx);
         self.last_state_idx = state_idx;
+        self.last_score_gap = None;
+        self.last_explanation.clear();
         let speed_boost = (self.adrenaline * 0.5).clamp(0.0, 1.0);
-        let focus_factor = (self.nodes[self.idx_tactical].state * 0.5).clamp(0.0, 1.0);
+        let focus_factor = (self.node_state(NODE_TACTICAL) * 0.5).clamp(0.0, 1.0);
 
         let total_dim = self.penalty_dim;
         
         let start = state_idx * total_dim;
-        let mut current_penalty_field = self.penalty_matrix[start..start + total_dim].to_vec();
+        self.settle_penalty_row(state_idx);
+        let mut scratch = std::mem::take(&mut self.scratch);
+        let current_penalty_field = &mut scratch.penalty_field;
+        current_penalty_field.clear();
+        match &self.compact_penalties {
+            Some(store) => store.extend_row(state_idx, current_penalty_field),
+            None => current_penalty_field.extend_from_slice(&self.penalty_matrix[start..start + total_dim]),
+        }
 
         // --- Knowledge-based Penalty Injection ---
-        let bin_per_action = self.mwso.dim / self.action_size;
-        let active_resonance = self.bootstrapper.calculate_resonance_field(&self.active_conditions, self.action_size);
-        for (action_idx, strength_opt) in active_resonance.iter().enumerate() {
-            if let Some(strength) = strength_opt {
-                if *strength < 0.0 {
-                    let p_val = strength.abs() * 50.0; // ペナルティ強度を増幅して注入
-                    let b_start = action_idx * bin_per_action;
-                    for j in 0..bin_per_action {
-                        if b_start + j < current_penalty_field.len() {
-                            current_penalty_field[b_start + j] += p_val;
-                        }
-                    }
-                }
-            }
-        }
+        let active_resonance = &mut scratch.ranking.resonance;
+        self.bootstrapper.calculate_graded_resonance_field_into(&self.condition_levels, self.action_size, active_resonance);
+        self.add_knowledge_penalties(current_penalty_field, active_resonance);
+        self.add_emotion_penalties(current_penalty_field);
+
+        let current_penalty_field: &[f32] = &scratch.penalty_field;
 
         // --- Flow Injection (Temporal Smearing) ---
         // 現在の状態を 1.0 で注入
         if let Some(ref mut sharded) = self.sharded_mwso {
-            sharded.inject_state(state_idx, 1.0, self.system_temperature, &current_penalty_field);
+            sharded.inject_state(state_idx, 1.0, self.system_temperature, current_penalty_field);
         } else {
             self.mwso.set_input_query(state_idx, 1.0);
-            self.mwso.inject_state(state_idx, 1.0, &current_penalty_field);
+            self.mwso.inject_state(state_idx, 1.0, current_penalty_field);
         }
         
         // 過去の状態を減衰させながら重畳注入（流れを形成）
         // 2048次元設定では、履歴エネルギーを強めに維持(0.4 -> 0.6)してパスを形成する
-        let mut decay = 0.6;
+        let smear_decay = self.meta_params.smear_decay;
+        let mut decay = smear_decay;
         for &prev_idx in self.input_history.iter().rev() {
-        if let Some(ref mut sharded) = self.sharded_mwso {
+            if past_deadline() { degraded = true; break; }
+            if let Some(ref mut sharded) = self.sharded_mwso {
                 // 全シャードに注入するが強度を弱める
-                sharded.inject_state(prev_idx, decay * 0.5, self.system_temperature, &current_penalty_field);
+                sharded.inject_state(prev_idx, decay * 0.5, self.system_temperature, current_penalty_field);
             } else {
                 self.mwso.set_input_query(prev_idx, decay);
-                self.mwso.inject_state(prev_idx, decay, &current_penalty_field);
+                self.mwso.inject_state(prev_idx, decay, current_penalty_field);
             }
-            decay *= 0.6;
+            decay *= smear_decay;
             if decay < 0.1 { break; }
         }
         
@@ -271,169 +926,896 @@ impl Singularity {
         if self.input_history.len() > 4 { self.input_history.pop_front(); }
         // ------------------------------------------
 
-        // --- Scout Scouting (Low-Resolution Broad Search) ---
-        // 常に高温で回して広域的な「アタリ」を探る
-        let scout_temp = (self.system_temperature + 0.5).clamp(0.8, 1.5);
-        self.scout_mwso.inject_state(state_idx % 128, 1.0, &vec![0.0; 128]);
-        self.scout_mwso.step_core(0.1, speed_boost, focus_factor, scout_temp, &vec![0.0; 128]);
+        // 締め切り直前なら時間発展を省略し、前回のスコアで代用する
+        let skip_dynamics = past_deadline();
+        if skip_dynamics {
+            degraded = true;
+        } else {
+            // --- Scout Scouting (Low-Resolution Broad Search) ---
+            // 常に高温で回して広域的な「アタリ」を探る
+            let scout_temp = (self.system_temperature + 0.5).clamp(0.8, 1.5);
+            self.fill_scout_penalty(current_penalty_field, &mut scratch.scout_penalty);
+            let scout_penalty: &[f32] = &scratch.scout_penalty;
+            self.scout_mwso.inject_state(state_idx % 128, 1.0, scout_penalty);
+            self.scout_mwso.step_core(0.1, speed_boost, focus_factor, scout_temp, scout_penalty);
         
-        // スカウトから「粗い」最良アクションを取得
-        let scout_scores = self.scout_mwso.get_action_scores(0, self.action_size, 0.0, &vec![0.0; 128]);
-        let mut best_scout_action = 0;
-        let mut max_scout_s = -f32::INFINITY;
-        for (i, &s) in scout_scores.iter().enumerate() {
-            if s > max_scout_s { max_scout_s = s; best_scout_action = i; }
-        }
+            // スカウトから「粗い」最良アクションを取得
+            scratch.scout_scores.resize(self.action_size, 0.0);
+            self.scout_mwso.get_action_scores_into(0, scout_penalty, &mut scratch.scout_scores);
+            let mut best_scout_action = 0;
+            let mut max_scout_s = -f32::INFINITY;
+            for (i, &s) in scratch.scout_scores.iter().enumerate() {
+                if s > max_scout_s { max_scout_s = s; best_scout_action = i; }
+            }
 
-        // --- Neighborhood Wave Effect Feedback (Scout -> Main) ---
-        // スカウトが選んだアクションとその近傍に、Main側でエネルギーを照射する
-        let irradiate_strength = 0.15 * self.system_temperature;
-        if irradiate_strength > 0.01 {
-            // 中心 (100%)
+            // --- Neighborhood Wave Effect Feedback (Scout -> Main) ---
+            // スカウトが選んだアクションとその近傍に、Main側でエネルギーを照射する
+            let irradiate_strength = 0.15 * self.system_temperature;
+            if irradiate_strength > 0.01 {
+                // 中心 (100%)
+                if let Some(ref mut sharded) = self.sharded_mwso {
+                    sharded.illuminate_bin(best_scout_action, irradiate_strength);
+                    // 左右近傍 (40% 強度で波及)
+                    let left = (best_scout_action as i32 - 1).rem_euclid(self.action_size as i32) as usize;
+                    let right = (best_scout_action as i32 + 1).rem_euclid(self.action_size as i32) as usize;
+                    sharded.illuminate_bin(left, irradiate_strength * 0.4);
+                    sharded.illuminate_bin(right, irradiate_strength * 0.4);
+                } else {
+                    self.mwso.illuminate_bin(best_scout_action, self.action_size, irradiate_strength);
+                    let left = (best_scout_action as i32 - 1).rem_euclid(self.action_size as i32) as usize;
+                    let right = (best_scout_action as i32 + 1).rem_euclid(self.action_size as i32) as usize;
+                    self.mwso.illuminate_bin(left, self.action_size, irradiate_strength * 0.4);
+                    self.mwso.illuminate_bin(right, self.action_size, irradiate_strength * 0.4);
+                }
+            }
+
+            self.apply_exploration_noise();
             if let Some(ref mut sharded) = self.sharded_mwso {
-                sharded.illuminate_bin(best_scout_action, irradiate_strength);
-                // 左右近傍 (40% 強度で波及)
-                let left = (best_scout_action as i32 - 1).rem_euclid(self.action_size as i32) as usize;
-                let right = (best_scout_action as i32 + 1).rem_euclid(self.action_size as i32) as usize;
-                sharded.illuminate_bin(left, irradiate_strength * 0.4);
-                sharded.illuminate_bin(right, irradiate_strength * 0.4);
+                sharded.step_core(0.1, speed_boost, focus_factor, self.system_temperature, current_penalty_field);
             } else {
-                self.mwso.illuminate_bin(best_scout_action, self.action_size, irradiate_strength);
-                let left = (best_scout_action as i32 - 1).rem_euclid(self.action_size as i32) as usize;
-                let right = (best_scout_action as i32 + 1).rem_euclid(self.action_size as i32) as usize;
-                self.mwso.illuminate_bin(left, self.action_size, irradiate_strength * 0.4);
-                self.mwso.illuminate_bin(right, self.action_size, irradiate_strength * 0.4);
+                self.mwso.step_core(0.1, speed_boost, focus_factor, self.system_temperature, current_penalty_field);
             }
         }
 
-        if let Some(ref mut sharded) = self.sharded_mwso {
-            sharded.step_core(0.1, speed_boost, focus_factor, self.system_temperature, &current_penalty_field);
-        } else {
-            self.mwso.step_core(0.1, speed_boost, focus_factor, self.system_temperature, &current_penalty_field);
-        }
+        let cached = skip_dynamics && match self.score_cache.touch(state_idx) {
+            Some(wave) => {
+                scratch.cached_wave.clear();
+                scratch.cached_wave.extend_from_slice(wave);
+                true
+            }
+            None => false,
+        };
 
-        let mut results = Vec::with_capacity(self.category_sizes.len());
+        results.clear();
         let mut current_offset = 0;
-        let cat_sizes = self.category_sizes.clone();
-
-        for (cat_idx, &size) in cat_sizes.iter().enumerate() {
-            let best_idx = self.get_best_in_range(current_offset, size, &current_penalty_field);
+        for cat_idx in 0..self.category_sizes.len() {
+            let size = self.category_sizes[cat_idx];
+            let cached_wave = cached.then(|| &scratch.cached_wave[current_offset..current_offset + size]);
+            let best_idx = self.get_best_in_range(current_offset, size, current_penalty_field, cached_wave, &mut scratch.ranking);
+            if deadline.is_some() && !skip_dynamics {
+                let capacity = self.config.score_cache_capacity;
+                if let Some(wave) = self.score_cache.entry(state_idx, self.action_size, capacity) {
+                    let fresh = &scratch.ranking.mwso_scores;
+                    let n = fresh.len().min(size);
+                    wave[current_offset..current_offset + n].copy_from_slice(&fresh[..n]);
+                }
+            }
             self.last_actions[cat_idx] = current_offset + best_idx;
             results.push(best_idx as i32);
             current_offset += size;
         }
+        self.scratch = scratch;
+        self.record_rule_activations();
 
-        self.history.push_back(Experience {
-            state_idx,
-            actions: self.last_actions.clone(),
-        });
+        // 履歴が満杯なら、押し出される経験の actions バッファを再利用する
+        let mut actions = if self.history.len() >= self.max_history {
+            self.history.pop_front().map(|exp| exp.actions).unwrap_or_default()
+        } else {
+            Vec::with_capacity(self.last_actions.len())
+        };
+        actions.clear();
+        actions.extend_from_slice(&self.last_actions);
+        self.history.push_back(Experience { state_idx, actions });
         if self.history.len() > self.max_history {
             self.history.pop_front();
         }
 
-        results
+        self.run_shadow(state_idx, |candidate| { candidate.select_actions(state_idx); });
+
+        if let (Some(log), Some((temperature, exploration))) = (self.episode_log.as_mut(), log_state) {
+            log.events.push(EpisodeEvent::Decision {
+                state_idx: raw_state_idx,
+                actions: results.clone(),
+                temperature,
+                exploration,
+            });
+        }
+        self.last_decision_degraded = degraded;
+        self.stats_mut().select_actions.record(started.elapsed());
+        degraded
     }
 
-    pub fn generate_visual_snapshot(&self, path: &str) -> bool {
-        super::visualizer::Visualizer::render_wave_snapshot(&self.mwso, path).is_ok()
+    /// シャドウ候補に同じ入力を与え、その仮想的な意思決定を記録する
+    fn run_shadow(&mut self, state_idx: usize, decide: impl FnOnce(&mut Singularity)) {
+        let Some(mut shadow) = self.shadow.take() else { return; };
+        decide(&mut shadow.candidate);
+        let candidate_actions = shadow.candidate.last_actions.clone();
+        shadow.record_decision(state_idx, &self.last_actions, candidate_actions);
+        self.shadow = Some(shadow);
     }
 
-    fn get_best_in_range(&mut self, offset: usize, size: usize, penalty_field: &[f32]) -> usize {
-        let mwso_scores = if let Some(ref mut sharded) = self.sharded_mwso {
-            // 1. シャード全体から全アクションのスコアを一気に取得
-            // ※この内部で各シャードの get_action_scores が並列（または順次）に走る
-            let all_scores = sharded.get_action_scores(penalty_field);
-            
-            // 2. 必要な範囲（カテゴリ）だけを切り出す
-            // offset と size が total_dim (2048) を超えないよう安全にスライス
-            let end = (offset + size).min(all_scores.len());
-            all_scores[offset..end].to_vec()
-        } else {
-            // 従来の 1024次元単体モード
-            self.mwso.get_action_scores(offset, size, 0.0, penalty_field)
-        };
-        let active_resonance = self.bootstrapper.calculate_resonance_field(&self.active_conditions, self.action_size);
+    /// 候補モデルをシャドウモードで接続する（カテゴリー構成が異なる場合は false）
+    /// report_after 回の意思決定で一致率・期待報酬のレポートが完了する
+    pub fn attach_shadow(&mut self, candidate: Singularity, report_after: usize) -> bool {
+        if candidate.category_sizes != self.category_sizes { return false; }
+        self.shadow = Some(Box::new(ShadowMode::new(candidate, report_after)));
+        true
+    }
 
-        let mut candidate_scores = Vec::with_capacity(size);
+    /// .dsym ファイルから候補モデルを読み込み、シャドウモードで接続する
+    pub fn load_shadow_from_file(&mut self, path: &str, report_after: usize) -> io::Result<()> {
+        let mut candidate = Singularity::new(self.state_size, self.category_sizes.clone());
+        candidate.load_from_file(path)?;
+        self.attach_shadow(candidate, report_after);
+        Ok(())
+    }
 
-        for i in 0..size {
-            let mut knowledge_field = 0.0;
-            if let Some(s) = active_resonance[offset + i] {
-                if s < -0.9 { knowledge_field = -100.0; } 
-                else { knowledge_field = s * 5.0; }
-            }
-            
-            let mwso_component = mwso_scores[i];
-            let internal_field = self.learned_rules.iter()
-                .find(|r| r.0 == self.last_state_idx && r.1 == offset + i)
-                .map(|r| (r.2 as f32 * 1.0).min(5.0)).unwrap_or(0.0);
+    pub fn shadow_report(&self) -> Option<ShadowReport> {
+        self.shadow.as_ref().map(|s| s.report())
+    }
 
-            if let Some(rule) = self.bootstrapper.rules.iter().find(|r| r.condition_id == self.last_state_idx as i32 && r.target_action == offset + i) {
-                knowledge_field += rule.strength * 5.0;
-            }
+    pub fn detach_shadow(&mut self) -> Option<Singularity> {
+        self.shadow.take().map(|s| s.candidate)
+    }
 
-            let neuron_boost = match i {
-                0 => self.nodes[self.idx_aggression].state * 0.5,
-                1 => self.nodes[self.idx_fear].state * 0.3,
-                _ => 0.0,
-            };
-            
-            let momentum_boost = self.action_momentum[offset + i] * 1.0;
-            let fatigue_penalty = self.fatigue_map[offset + i] * 2.0;
-            
-            let total_score = mwso_component + internal_field + knowledge_field + neuron_boost + momentum_boost - fatigue_penalty + (self.morale * 0.1);
-            candidate_scores.push((i, total_score));
+    /// 候補モデルをライブに昇格させる（現在のライブモデルの状態は破棄される）
+    pub fn promote_shadow(&mut self) -> bool {
+        match self.detach_shadow() {
+            Some(candidate) => { *self = candidate; true }
+            None => false,
         }
+    }
 
-        // --- Top-k Softmax Sampling ---
-        // 1. Sort by score descending
-        candidate_scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    /// 今回の探索ノイズの強さ（メタパラメータ + 忍耐の消耗分、神経修飾物質で増減）
+    fn exploration_noise(&self) -> f32 {
+        let noise = self.meta_params.exploration_noise + (1.0 - self.patience).max(0.0) * 0.05;
+        noise * self.neuromodulators.map_or(1.0, |n| n.exploration_scale())
+    }
 
-        // 2. Take Top-k (k=3 or size if smaller)
-        let k = 3.min(size);
-        let top_k = &candidate_scores[..k];
+    /// メタパラメータの探索ノイズに、忍耐の消耗分（膠着が続くほど大きい）を上乗せして注入する
+    fn apply_exploration_noise(&mut self) {
+        let noise = self.exploration_noise();
+        if noise <= 0.0 { return; }
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            for shard in &mut sharded.shards { shard.inject_exploration_noise(noise); }
+        } else {
+            self.mwso.inject_exploration_noise(noise);
+        }
+    }
 
-        // 3. Compute Softmax probabilities over Top-k
-        // Probability depends on inverse temperature
-        let beta = (1.0 / self.system_temperature.max(0.05)) * 2.0;
-        let mut probs = Vec::with_capacity(k);
-        let max_s = top_k[0].1;
-        let mut sum_exp = 0.0;
+    /// メタパラメータの自己調整を有効化する（以後 learn ごとに報酬が評価される）
+    pub fn enable_meta_controller(&mut self, controller: MetaController) {
+        self.meta_params = controller.current();
+        self.meta_controller = Some(controller);
+    }
 
-        for &(_, s) in top_k {
-            let p = ((s - max_s) * beta).exp(); // subtract max for numerical stability
-            probs.push(p);
-            sum_exp += p;
+    /// 自己調整を止め、最も成績の良かった設定に固定する
+    pub fn disable_meta_controller(&mut self) {
+        if let Some(meta) = self.meta_controller.take() {
+            self.meta_params = meta.arms[meta.best_arm()];
         }
+    }
 
-        // 4. Weighted Random Sample from Top-k
-        let mut r = self.mwso.next_rng() * sum_exp;
-        for i in 0..k {
-            r -= probs[i];
-            if r <= 0.0 {
-                return top_k[i].0;
-            }
-        }
-        top_k[0].0
+    /// 反実仮想評価: state_idx で select_actions を走らせた場合の全アクションのスコアを返す
+    /// 波動場の複製の上で評価し self は読むだけなので、何度呼んでも副作用はない
+    pub fn evaluate_actions(&self, state_idx: usize) -> Vec<f32> {
+        self.evaluate_breakdown(state_idx).iter().map(|b| b.total).collect()
     }
 
-    pub fn learn_vector(&mut self, reward: f32) {
-        let mut discount = 1.0;
-        let gamma = 0.9;
+    /// evaluate_actions の内訳版（全アクションの ScoreBreakdown）
+    /// select_actions と同じ手順（状態の注入・流れ・スカウトの照射・探索ノイズ・時間発展）を波動場の複製に施して採点する。
+    /// 統計・アクセス時刻・アーカイブ・ペナルティの減衰時計・スコアキャッシュには触れない
+    fn evaluate_breakdown(&self, state_idx: usize) -> Vec<ScoreBreakdown> {
+        let state_idx = self.canonical_state(state_idx);
+        let temp = self.system_temperature;
+        let speed_boost = (self.adrenaline * 0.5).clamp(0.0, 1.0);
+        let focus_factor = (self.node_state(NODE_TACTICAL) * 0.5).clamp(0.0, 1.0);
 
-        let history_clone = self.vector_history.clone();
-        for exp in history_clone.iter().rev() {
-            let discounted_reward = reward * discount;
-            if let Some(ref mut sharded) = self.sharded_mwso {
-                sharded.adapt_vector(&exp.state_weights, discounted_reward, &exp.actions, self.system_temperature);
-            } else {
-                self.mwso.imprint_vector_qcel(&exp.state_weights, discounted_reward);
-                for &(state_idx, w) in &exp.state_weights {
-                    if w > 0.01 {
-                        self.mwso.adapt(state_idx, discounted_reward * w, &exp.actions, self.system_temperature, self.action_size);
-                    }
-                }
-            }
+        let mut field = self.accumulate_penalty_field(&[(state_idx, 1.0)]);
+        let resonance = self.bootstrapper.calculate_graded_resonance_field(&self.condition_levels, self.action_size);
+        self.add_knowledge_penalties(&mut field, &resonance);
+        self.add_emotion_penalties(&mut field);
+
+        let mut mwso = self.mwso.clone();
+        let mut scout = self.scout_mwso.clone();
+        let mut sharded = self.sharded_mwso.clone();
+
+        let mut inject = |idx: usize, strength: f32, sharded_strength: f32| match sharded.as_mut() {
+            Some(sharded) => sharded.inject_state(idx, sharded_strength, temp, &field),
+            None => {
+                mwso.set_input_query(idx, strength);
+                mwso.inject_state(idx, strength, &field);
+            }
+        };
+        inject(state_idx, 1.0, 1.0);
+        let smear_decay = self.meta_params.smear_decay;
+        let mut decay = smear_decay;
+        for &prev_idx in self.input_history.iter().rev() {
+            inject(prev_idx, decay, decay * 0.5);
+            decay *= smear_decay;
+            if decay < 0.1 { break; }
+        }
+
+        let scout_temp = (temp + 0.5).clamp(0.8, 1.5);
+        let mut scout_penalty = Vec::new();
+        self.fill_scout_penalty(&field, &mut scout_penalty);
+        scout.inject_state(state_idx % 128, 1.0, &scout_penalty);
+        scout.step_core(0.1, speed_boost, focus_factor, scout_temp, &scout_penalty);
+        let mut scout_scores = vec![0.0; self.action_size];
+        scout.get_action_scores_into(0, &scout_penalty, &mut scout_scores);
+        let mut best_scout_action = 0;
+        let mut max_scout_s = -f32::INFINITY;
+        for (i, &s) in scout_scores.iter().enumerate() {
+            if s > max_scout_s { max_scout_s = s; best_scout_action = i; }
+        }
+
+        let irradiate_strength = 0.15 * temp;
+        if irradiate_strength > 0.01 {
+            let left = (best_scout_action as i32 - 1).rem_euclid(self.action_size as i32) as usize;
+            let right = (best_scout_action as i32 + 1).rem_euclid(self.action_size as i32) as usize;
+            for (action, strength) in [(best_scout_action, irradiate_strength), (left, irradiate_strength * 0.4), (right, irradiate_strength * 0.4)] {
+                match sharded.as_mut() {
+                    Some(sharded) => sharded.illuminate_bin(action, strength),
+                    None => mwso.illuminate_bin(action, self.action_size, strength),
+                }
+            }
+        }
+
+        let noise = self.exploration_noise();
+        match sharded.as_mut() {
+            Some(sharded) => {
+                if noise > 0.0 {
+                    for shard in &mut sharded.shards { shard.inject_exploration_noise(noise); }
+                }
+                sharded.step_core(0.1, speed_boost, focus_factor, temp, &field);
+            }
+            None => {
+                if noise > 0.0 { mwso.inject_exploration_noise(noise); }
+                mwso.step_core(0.1, speed_boost, focus_factor, temp, &field);
+            }
+        }
+
+        let mut all_scores = Vec::new();
+        if let Some(sharded) = sharded.as_mut() {
+            all_scores.resize(sharded.total_action_size, 0.0);
+            sharded.get_action_scores_into(&field, &mut all_scores);
+        }
+        let mut breakdown = Vec::with_capacity(self.action_size);
+        let mut scores = Vec::new();
+        let mut offset = 0;
+        for &size in &self.category_sizes {
+            if sharded.is_some() {
+                let end = (offset + size).min(all_scores.len());
+                scores.clear();
+                scores.extend_from_slice(&all_scores[offset..end]);
+            } else {
+                scores.resize(size, 0.0);
+                mwso.get_action_scores_into(offset, &field, &mut scores);
+            }
+            self.score_candidates(state_idx, None, offset, &scores, &resonance, &mut breakdown);
+            offset += size;
+        }
+        breakdown
+    }
+
+    /// 現在の可変状態（波動場・ペナルティ・感情・履歴・ルール・統計）のスナップショットを取る
+    pub fn checkpoint(&self) -> StateSnapshot {
+        StateSnapshot {
+            nodes: self.nodes.clone(),
+            mwso: self.mwso.clone(),
+            scout_mwso: self.scout_mwso.clone(),
+            sharded_mwso: self.sharded_mwso.clone(),
+            penalty_matrix: self.penalty_matrix.clone(),
+            compact_penalties: self.compact_penalties.clone(),
+            penalty_dim: self.penalty_dim,
+            penalty_clock: self.penalty_clock,
+            penalty_row_clock: self.penalty_row_clock.clone(),
+            system_temperature: self.system_temperature,
+            last_topology_update_temp: self.last_topology_update_temp,
+            adrenaline: self.adrenaline,
+            frustration: self.frustration,
+            failure_streaks: self.failure_streaks.clone(),
+            velocity_trust: self.velocity_trust,
+            fatigue_map: self.fatigue_map.clone(),
+            morale: self.morale,
+            patience: self.patience,
+            reward_streak: self.reward_streak,
+            stalemate_streak: self.stalemate_streak,
+            last_actions: self.last_actions.clone(),
+            last_state_idx: self.last_state_idx,
+            action_momentum: self.action_momentum.clone(),
+            input_history: self.input_history.clone(),
+            history: self.history.clone(),
+            vector_history: self.vector_history.clone(),
+            learned_rules: self.learned_rules.clone(),
+            rule_clock: self.rule_clock,
+            bootstrapper: self.bootstrapper.clone(),
+            crystallized: self.crystallized.clone(),
+            action_visits: self.action_visits.clone(),
+            neuromodulators: self.neuromodulators,
+            opponent_model: self.opponent_model.clone(),
+            meta_params: self.meta_params,
+            meta_controller: self.meta_controller.clone(),
+            temperature_trace: self.temperature_trace.clone(),
+            rule_audit: self.rule_audit.clone(),
+            state_last_active: self.state_last_active.clone(),
+            state_aliases: self.state_aliases.clone(),
+            symmetry: self.symmetry.clone(),
+            last_region: self.last_region,
+            exploration_timer: self.exploration_timer,
+            current_focus_action: self.current_focus_action,
+        }
+    }
+
+    /// スナップショット時点の状態へ巻き戻す
+    /// 既存のバッファを再利用して上書きするため、探索中の繰り返しロールバックでも再確保は起きない
+    pub fn restore(&mut self, snapshot: &StateSnapshot) {
+        self.nodes.clone_from(&snapshot.nodes);
+        self.mwso.copy_from(&snapshot.mwso);
+        self.scout_mwso.copy_from(&snapshot.scout_mwso);
+        match (&mut self.sharded_mwso, &snapshot.sharded_mwso) {
+            (Some(sharded), Some(src)) => sharded.copy_from(src),
+            (current, src) => *current = src.clone(),
+        }
+        self.penalty_matrix.clone_from(&snapshot.penalty_matrix);
+        self.compact_penalties.clone_from(&snapshot.compact_penalties);
+        if self.penalty_dim != snapshot.penalty_dim {
+            // スナップショット後に次元が拡張されていた
+            self.penalty_dim = snapshot.penalty_dim;
+            self.empty_penalty = vec![0.0; snapshot.penalty_dim];
+        }
+        self.penalty_clock = snapshot.penalty_clock;
+        self.penalty_row_clock.clone_from(&snapshot.penalty_row_clock);
+        self.system_temperature = snapshot.system_temperature;
+        self.last_topology_update_temp = snapshot.last_topology_update_temp;
+        self.adrenaline = snapshot.adrenaline;
+        self.frustration = snapshot.frustration;
+        self.failure_streaks.clone_from(&snapshot.failure_streaks);
+        self.velocity_trust = snapshot.velocity_trust;
+        self.fatigue_map.clone_from(&snapshot.fatigue_map);
+        self.morale = snapshot.morale;
+        self.patience = snapshot.patience;
+        self.reward_streak = snapshot.reward_streak;
+        self.stalemate_streak = snapshot.stalemate_streak;
+        self.last_actions.clone_from(&snapshot.last_actions);
+        self.last_state_idx = snapshot.last_state_idx;
+        self.action_momentum.clone_from(&snapshot.action_momentum);
+        self.input_history.clone_from(&snapshot.input_history);
+        self.history.clone_from(&snapshot.history);
+        self.vector_history.clone_from(&snapshot.vector_history);
+        self.learned_rules.clone_from(&snapshot.learned_rules);
+        self.reindex_learned_rules();
+        self.rule_clock = snapshot.rule_clock;
+        self.bootstrapper.clone_from(&snapshot.bootstrapper);
+        self.crystallized.clone_from(&snapshot.crystallized);
+        self.action_visits.clone_from(&snapshot.action_visits);
+        self.neuromodulators = snapshot.neuromodulators;
+        self.opponent_model.clone_from(&snapshot.opponent_model);
+        self.meta_params = snapshot.meta_params;
+        self.meta_controller.clone_from(&snapshot.meta_controller);
+        self.temperature_trace.clone_from(&snapshot.temperature_trace);
+        self.rule_audit.clone_from(&snapshot.rule_audit);
+        self.state_last_active.clone_from(&snapshot.state_last_active);
+        self.state_aliases.clone_from(&snapshot.state_aliases);
+        self.symmetry.clone_from(&snapshot.symmetry);
+        // キャッシュしたスコアは巻き戻した波とは合わないので捨てる
+        self.score_cache.clear();
+        self.last_region = snapshot.last_region;
+        self.exploration_timer = snapshot.exploration_timer;
+        self.current_focus_action = snapshot.current_focus_action;
+        self.last_score_gap = None;
+        self.last_explanation.clear();
+    }
+
+    /// 旧単一アクション API 互換の選択
+    /// feature_vector が false なら inputs[0] を状態インデックスとして扱い、
+    /// true なら inputs 全体を密な特徴ベクトル（index -> 重み）として select_actions_vector に渡す
+    /// category が範囲外・未指定の場合は最初のカテゴリーを返す
+    pub fn select_action_compat(&mut self, inputs: &[f32], category: Option<usize>, feature_vector: bool) -> i32 {
+        let actions = if feature_vector {
+            let state_weights: Vec<(usize, f32)> = inputs.iter().enumerate()
+                .filter(|&(_, &w)| w != 0.0)
+                .map(|(i, &w)| (i, w))
+                .collect();
+            self.select_actions_vector(&state_weights)
+        } else {
+            let state_idx = inputs.first().map_or(0, |&v| v as usize);
+            self.select_actions(state_idx)
+        };
+        let cat = category.filter(|&c| c < actions.len()).unwrap_or(0);
+        actions.get(cat).copied().unwrap_or(0)
+    }
+
+    pub fn generate_visual_snapshot(&self, path: &str) -> bool {
+        super::visualizer::Visualizer::render_wave_snapshot(&self.mwso, path).is_ok()
+    }
+
+    /// 位相結合構造 theta を書き出す（シャード構成では各シャードの theta を順に連結したもの）
+    pub fn export_theta(&self) -> Vec<f32> {
+        match self.sharded_mwso {
+            Some(ref sharded) => sharded.shards.iter().flat_map(|s| s.export_theta()).collect(),
+            None => self.mwso.export_theta(),
+        }
+    }
+
+    /// export_theta で書き出した theta を読み込む。シャード構成ではシャード数で等分して各シャードへ読み込む
+    pub fn import_theta(&mut self, theta: &[f32]) -> bool {
+        match self.sharded_mwso {
+            Some(ref mut sharded) => {
+                let n = sharded.shards.len();
+                if n == 0 || !theta.len().is_multiple_of(n) { return false; }
+                let chunk = theta.len() / n;
+                if chunk == 0 || !chunk.is_multiple_of(2) || theta.iter().any(|t| !t.is_finite()) { return false; }
+                for (shard, part) in sharded.shards.iter_mut().zip(theta.chunks(chunk)) {
+                    shard.import_theta(part);
+                }
+                true
+            }
+            None => self.mwso.import_theta(theta),
+        }
+    }
+
+    /// 意思決定に使っている波のスナップショットを別スレッドから読むためのハンドル（可視化・テレメトリ用）
+    /// シャード構成では全シャードの波をシャード順に連結したフレームになる
+    pub fn wave_view(&mut self) -> WaveView {
+        match self.sharded_mwso {
+            Some(ref mut sharded) => sharded.wave_view(),
+            None => self.mwso.wave_view(),
+        }
+    }
+
+    /// cached_wave が与えられた場合は波動場を読まず、そのスコアを波動成分として使う
+    fn get_best_in_range(&mut self, offset: usize, size: usize, penalty_field: &[f32], cached_wave: Option<&[f32]>, scratch: &mut RankingScratch) -> usize {
+        if let Some(wave) = cached_wave {
+            scratch.mwso_scores.clear();
+            scratch.mwso_scores.extend_from_slice(wave);
+        } else if let Some(ref mut sharded) = self.sharded_mwso {
+            // 1. シャード全体から全アクションのスコアを一気に取得
+            // ※この内部で各シャードの get_action_scores が並列（または順次）に走る
+            scratch.all_scores.resize(sharded.total_action_size, 0.0);
+            sharded.get_action_scores_into(penalty_field, &mut scratch.all_scores);
+            
+            // 2. 必要な範囲（カテゴリ）だけを切り出す
+            // offset と size が total_dim (2048) を超えないよう安全にスライス
+            let end = (offset + size).min(scratch.all_scores.len());
+            scratch.mwso_scores.clear();
+            scratch.mwso_scores.extend_from_slice(&scratch.all_scores[offset..end]);
+        } else {
+            // 従来の 1024次元単体モード
+            scratch.mwso_scores.resize(size, 0.0);
+            self.mwso.get_action_scores_into(offset, penalty_field, &mut scratch.mwso_scores);
+        }
+        self.bootstrapper.calculate_graded_resonance_field_into(&self.condition_levels, self.action_size, &mut scratch.resonance);
+
+        // 説明用の内訳は last_explanation へ直接書き込む
+        let explanation_start = self.last_explanation.len();
+        let mut explanation = std::mem::take(&mut self.last_explanation);
+        self.score_candidates(self.last_state_idx, self.last_region, offset, &scratch.mwso_scores, &scratch.resonance, &mut explanation);
+        let candidate_scores = &mut scratch.ranked;
+        candidate_scores.clear();
+        candidate_scores.extend(explanation[explanation_start..].iter().enumerate().map(|(i, b)| (i, b.total)));
+        self.last_explanation = explanation;
+
+        // --- 探索戦略に基づく選択 ---
+        // 1. Sort by score descending
+        candidate_scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        // 確信度用: 1位と2位のスコア差（全カテゴリー中で最も僅差のものを保持）
+        let gap = if size > 1 { candidate_scores[0].1 - candidate_scores[1].1 } else { f32::INFINITY };
+        self.last_score_gap = Some(self.last_score_gap.map_or(gap, |g| g.min(gap)));
+
+        let chosen = match self.config.exploration_policy {
+            ExplorationPolicy::TopKSoftmax => self.sample_softmax(&candidate_scores[..3.min(size)]),
+            ExplorationPolicy::Greedy => candidate_scores[0].0,
+            ExplorationPolicy::EpsilonGreedy => {
+                if self.mwso.next_rng() < self.exploration_beta.clamp(0.0, 1.0) {
+                    ((self.mwso.next_rng() * size as f32) as usize).min(size - 1)
+                } else {
+                    candidate_scores[0].0
+                }
+            }
+            ExplorationPolicy::Boltzmann => self.sample_softmax(candidate_scores),
+            ExplorationPolicy::Ucb => self.select_ucb(offset, candidate_scores),
+        };
+        if self.config.exploration_policy == ExplorationPolicy::Ucb {
+            let action_size = self.action_size;
+            let visits = self.action_visits.entry(self.last_state_idx).or_insert_with(|| vec![0; action_size]);
+            visits[offset + chosen] += 1;
+        }
+
+        self.last_explanation[explanation_start + chosen].chosen = true;
+        chosen
+    }
+
+    /// スコア降順の候補群に対する Softmax サンプリング
+    /// 逆温度は系の温度とメタパラメータの softmax_scale から決まる
+    fn sample_softmax(&mut self, ranked: &[(usize, f32)]) -> usize {
+        let beta = (1.0 / self.system_temperature.max(0.05)) * self.meta_params.softmax_scale;
+        let max_s = ranked[0].1;
+        let weight = |s: f32| ((s - max_s) * beta).exp(); // subtract max for numerical stability
+        let sum_exp: f32 = ranked.iter().map(|&(_, s)| weight(s)).sum();
+
+        let mut r = self.mwso.next_rng() * sum_exp;
+        for &(idx, s) in ranked {
+            r -= weight(s);
+            if r <= 0.0 { return idx; }
+        }
+        ranked[0].0
+    }
+
+    /// UCB: スコア + exploration_beta * sqrt(ln(N) / (n + 1))（訪問回数は状態ごと）
+    fn select_ucb(&self, offset: usize, ranked: &[(usize, f32)]) -> usize {
+        let visits = self.action_visits.get(&self.last_state_idx);
+        let count = |i: usize| visits.map_or(0, |v| v[offset + i]) as f32;
+        let total: f32 = ranked.iter().map(|&(i, _)| count(i)).sum();
+        let ln_total = (total + 1.0).ln();
+        let mut best = ranked[0].0;
+        let mut best_ucb = f32::NEG_INFINITY;
+        for &(i, s) in ranked {
+            let ucb = s + self.exploration_beta * (ln_total / (count(i) + 1.0)).sqrt();
+            if ucb > best_ucb { best_ucb = ucb; best = i; }
+        }
+        best
+    }
+
+    /// get_best_in_range で合算される各項を、カテゴリー内の全候補について算出する
+    /// 結果は breakdowns の末尾に追記される
+    /// region は階層的な意思決定で参照する領域（それ以外は None）
+    fn score_candidates(&self, state_idx: usize, region: Option<usize>, offset: usize, mwso_scores: &[f32], active_resonance: &[Option<f32>], breakdowns: &mut Vec<ScoreBreakdown>) {
+        let opponent_response = self.opponent_model.as_ref().and_then(|m| m.expected_response(state_idx));
+        let category = self.category_of(offset);
+
+        for (i, &mwso_component) in mwso_scores.iter().enumerate() {
+            let mut knowledge_field = self.knowledge_score(active_resonance, &self.condition_levels, state_idx, offset + i);
+            let internal_field = self.learned_rule_score(state_idx, offset + i);
+
+            // 領域レベルの知識（階層的な意思決定の間のみ）
+            if let (Some(region), Some(h)) = (region, &self.hierarchy)
+                && let Some(rule) = self.bootstrapper.find_rule(region_condition_id(region), offset + i) {
+                knowledge_field += rule.strength * 5.0 * h.coarse_weight;
+            }
+
+            let neuron_boost = match i {
+                0 => self.node_state(NODE_AGGRESSION) * 0.5,
+                1 => self.node_state(NODE_FEAR) * 0.3,
+                _ => 0.0,
+            };
+            
+            // 予測が外れ続けている間は慣性に頼らない
+            let momentum_boost = self.action_momentum[offset + i] * self.velocity_trust;
+            let fatigue_penalty = self.fatigue_map[offset + i] * 2.0;
+            let opponent_term = opponent_response.as_ref().and_then(|r| r.get(offset + i).copied()).unwrap_or(0.0);
+            // 士気が高いほど、勢いのある行動をさらに押し出す
+            let morale_term = self.morale * 0.1 * (1.0 + 0.25 * self.action_momentum[offset + i]);
+            
+            let total_score = mwso_component + internal_field + knowledge_field + neuron_boost + momentum_boost - fatigue_penalty + opponent_term + morale_term;
+            breakdowns.push(ScoreBreakdown {
+                category,
+                action: offset + i,
+                wave: mwso_component,
+                fatigue_penalty,
+                knowledge: knowledge_field,
+                learned_rule: internal_field,
+                neuron: neuron_boost,
+                momentum: momentum_boost,
+                opponent: opponent_term,
+                morale: morale_term,
+                total: total_score,
+                chosen: false,
+            });
+        }
+    }
+
+    /// 統合済みの状態は代表状態へ読み替える
+    pub fn canonical_state(&self, state_idx: usize) -> usize {
+        self.state_aliases.get(&state_idx).copied().unwrap_or(state_idx)
+    }
+
+    /// 経験ログから等価な状態を検出し、統合・ワームホールの提案を返す（この時点では何も変更しない）
+    pub fn propose_symmetries(&self) -> Vec<SymmetryProposal> {
+        self.symmetry.propose()
+    }
+
+    /// 承認された提案を適用する
+    /// Merge: 代表状態以外を代表状態へのエイリアスにする
+    /// Wormhole: 状態コードのビン同士をエンタングルさせ、注入されたエネルギーを共有させる
+    pub fn apply_symmetry(&mut self, proposal: &SymmetryProposal, wormhole_strength: f32) {
+        let Some((&representative, rest)) = proposal.states.split_first() else { return; };
+        match proposal.kind {
+            SymmetryKind::Merge => {
+                let representative = self.canonical_state(representative);
+                for &state in rest {
+                    if state != representative { self.state_aliases.insert(state, representative); }
+                }
+            }
+            SymmetryKind::Wormhole => {
+                for &state in rest {
+                    if let Some(ref mut sharded) = self.sharded_mwso {
+                        for shard in &mut sharded.shards { shard.link_states(representative, state, wormhole_strength); }
+                    } else {
+                        self.mwso.link_states(representative, state, wormhole_strength);
+                    }
+                }
+            }
+        }
+    }
+
+    /// 外部条件による外場と、状態に紐づくルール・テンプレートから求めた知識項
+    fn knowledge_score(&self, active_resonance: &[Option<f32>], levels: &[(i32, f32)], state_idx: usize, action: usize) -> f32 {
+        let mut knowledge_field = 0.0;
+        if let Some(s) = active_resonance[action] {
+            if s < -0.9 { knowledge_field = -100.0; }
+            else { knowledge_field = s * 5.0; }
+        }
+        if let Some(rule) = self.bootstrapper.find_rule(state_idx as i32, action) {
+            knowledge_field += rule.strength * 5.0;
+        }
+        if !self.bootstrapper.templates.is_empty() {
+            knowledge_field += self.bootstrapper.template_strength(levels, state_idx, action) * 5.0;
+        }
+        knowledge_field
+    }
+
+    /// 自己獲得ルール（learned_rules）による加点
+    fn learned_rule_score(&self, state_idx: usize, action: usize) -> f32 {
+        self.learned_rule_position(state_idx, action)
+            .map(|i| (self.learned_rules[i].count as f32 * 1.0).min(5.0)).unwrap_or(0.0)
+    }
+
+    /// (state, action) の自己獲得ルールの位置（索引が古ければ線形探索）
+    fn learned_rule_position(&self, state: usize, action: usize) -> Option<usize> {
+        let matches = |r: &LearnedRule| r.state == state && r.action == action;
+        match self.learned_index.lookup(&(state, action), self.learned_rules.len()) {
+            Some(Some(i)) if matches(&self.learned_rules[i]) => Some(i),
+            Some(None) => None,
+            _ => self.learned_rules.iter().position(matches),
+        }
+    }
+
+    /// learned_rules を直接書き換えた後に索引を作り直す
+    pub fn reindex_learned_rules(&mut self) {
+        self.learned_index.rebuild(self.learned_rules.iter().map(|r| Some((r.state, r.action))));
+    }
+
+    /// JSON のルールパック（Bootstrapper::to_json の形式）を読み込み、既存のルールに追加する
+    /// 衝突の解決方針はファイルのものに切り替わる。追加したルール数（テンプレートを含む）を返す
+    pub fn load_knowledge(&mut self, path: &str) -> io::Result<usize> {
+        let json = std::fs::read_to_string(path)?;
+        let pack = Bootstrapper::from_json(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let added = pack.rules.len() + pack.templates.len();
+        self.bootstrapper.rules.extend(pack.rules);
+        self.bootstrapper.templates.extend(pack.templates);
+        self.bootstrapper.reindex();
+        self.bootstrapper.policy = pack.policy;
+        Ok(added)
+    }
+
+    /// 現在のルール集（結晶化したルールを含む）を JSON で書き出す
+    pub fn save_knowledge(&self, path: &str) -> io::Result<()> {
+        let json = self.bootstrapper.to_json().map_err(io::Error::other)?;
+        std::fs::write(path, json)
+    }
+
+    /// 知識層のみ（波動・ノイズ・感情なし）で各カテゴリーの行動を順位付けする
+    /// 学習中の脳に読み込む前に、ドクトリン（ルール集）を決定論的に検証するためのもの
+    pub fn simulate_rules(&self, conditions: &[i32], state_idx: usize) -> RankedActions {
+        let resonance = self.bootstrapper.calculate_resonance_field(conditions, self.action_size);
+        let levels: Vec<(i32, f32)> = conditions.iter().map(|&id| (id, 1.0)).collect();
+        let mut categories = Vec::with_capacity(self.category_sizes.len());
+        let mut offset = 0;
+        for &size in &self.category_sizes {
+            let mut ranked: Vec<(usize, f32)> = (0..size)
+                .map(|i| {
+                    let action = offset + i;
+                    (i, self.knowledge_score(&resonance, &levels, state_idx, action) + self.learned_rule_score(state_idx, action))
+                })
+                .collect();
+            ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+            categories.push(ranked);
+            offset += size;
+        }
+        RankedActions { categories }
+    }
+
+    /// 全体アクションオフセットが属するカテゴリー番号
+    /// カテゴリーに属する行動（全体インデックス）の範囲
+    pub fn category_actions(&self, category: usize) -> Option<std::ops::Range<usize>> {
+        let size = *self.category_sizes.get(category)?;
+        let start: usize = self.category_sizes[..category].iter().sum();
+        Some(start..start + size)
+    }
+
+    /// カテゴリーの全行動を対象にするルールを 1 つ追加する（例: 撤退条件で攻撃カテゴリー全体を禁止）
+    /// カテゴリーが存在しなければ false
+    pub fn add_category_rule(&mut self, condition: Condition, category: usize, strength: f32, priority: i32) -> bool {
+        let Some(&size) = self.category_sizes.get(category) else { return false; };
+        self.add_category_scoped_rule(condition, category, 0..size, strength, priority)
+    }
+
+    /// カテゴリー内の行動番号 local_actions を対象にするルールを追加する（全体のオフセットは自動で足される）
+    /// カテゴリーが存在しない、範囲が空、またはカテゴリーをはみ出す場合は何もせず false
+    pub fn add_category_scoped_rule(&mut self, condition: Condition, category: usize, local_actions: std::ops::Range<usize>, strength: f32, priority: i32) -> bool {
+        let Some(actions) = self.category_actions(category) else { return false; };
+        if local_actions.is_empty() || local_actions.end > actions.len() { return false; }
+        self.bootstrapper.add_range_rule(condition, actions.start + local_actions.start..actions.start + local_actions.end, strength, priority);
+        true
+    }
+
+    /// 現在の条件レベルに対する外場をカテゴリーごとに返す（category_sizes と同じ並びで、添字はカテゴリー内の行動番号）
+    pub fn category_resonance_fields(&self) -> Vec<Vec<Option<f32>>> {
+        self.bootstrapper.calculate_category_fields(&self.condition_levels, &self.category_sizes)
+    }
+
+    /// 逆抽出: これまでに経験した状態ごとに、波動場（重力を含む）と自己獲得ルールが学んだ選好を読み出し、
+    /// 確信度が threshold 以上の「状態 → 行動」を condition_id = 状態のルールとして書き出す
+    /// 確信度はカテゴリー内の softmax 確率で、ルールの強度にもなる。実績（平均報酬が正、または learned_rule）のない行動は出さない
+    /// 波動場の複製の上で評価するため self は変更しない
+    pub fn extract_policy(&self, threshold: f32) -> Bootstrapper {
+        let mut states: Vec<usize> = self.symmetry.stats.keys().copied()
+            .chain(self.learned_rules.iter().map(|r| r.state))
+            .map(|s| self.canonical_state(s))
+            .collect();
+        states.sort_unstable();
+        states.dedup();
+
+        let mut policy = Bootstrapper::new();
+        for state in states {
+            let breakdown = self.evaluate_breakdown(state);
+            let outcomes = self.symmetry.stats.get(&state);
+            let proven = |action: usize| {
+                outcomes.and_then(|row| row.get(action)).is_some_and(|&(sum, count)| count > 0 && sum > 0.0)
+                    || self.learned_rule_position(state, action).is_some()
+            };
+            for actions in (0..self.category_sizes.len()).filter_map(|c| self.category_actions(c)) {
+                let Some(candidates) = breakdown.get(actions.clone()) else { continue; };
+                let learned: Vec<f32> = candidates.iter().map(|b| b.wave + b.learned_rule).collect();
+                let max = learned.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+                let total: f32 = learned.iter().map(|&v| (v - max).exp()).sum();
+                let Some(best) = learned.iter().position(|&v| v == max) else { continue; };
+                let confidence = 1.0 / total;
+                let action = actions.start + best;
+                if confidence >= threshold && proven(action) {
+                    policy.add_hamiltonian_rule(state as i32, action, confidence);
+                }
+            }
+        }
+        policy
+    }
+
+    fn category_of(&self, offset: usize) -> usize {
+        let mut end = 0;
+        for (cat_idx, &size) in self.category_sizes.iter().enumerate() {
+            end += size;
+            if offset < end { return cat_idx; }
+        }
+        self.category_sizes.len().saturating_sub(1)
+    }
+
+    /// 直近の意思決定で知識層に寄与したルール（ルール ID = bootstrapper.rules 内の位置と、行動ごとの寄与）
+    pub fn last_rule_activations(&self) -> &[RuleActivation] {
+        self.rule_audit.back().map_or(&[], |audit| &audit.activations)
+    }
+
+    /// 意思決定で知識層に寄与したルールを rule_audit へ記録する（score_candidates と同じ状態・領域で引く）
+    fn record_rule_activations(&mut self) {
+        let mut activations = if self.rule_audit.len() >= 64 {
+            self.rule_audit.pop_front().map(|audit| audit.activations).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        self.bootstrapper.rule_activations_into(&self.condition_levels, self.action_size, &mut activations);
+        let state_id = self.last_state_idx as i32;
+        let region = self.last_region.zip(self.hierarchy.as_ref()).map(|(r, h)| (region_condition_id(r), h.coarse_weight));
+        for action in 0..self.action_size {
+            if let Some(rule) = self.bootstrapper.find_rule_position(state_id, action) {
+                activations.push(RuleActivation { rule, action, contribution: self.bootstrapper.rules[rule].strength });
+            }
+            let region_rule = region.and_then(|(id, weight)| self.bootstrapper.find_rule_position(id, action).map(|rule| (rule, weight)));
+            if let Some((rule, weight)) = region_rule {
+                activations.push(RuleActivation { rule, action, contribution: self.bootstrapper.rules[rule].strength * weight });
+            }
+        }
+        self.rule_audit.push_back(RuleAudit { state_idx: self.last_state_idx, activations });
+    }
+
+    /// 直近の意思決定における各候補アクションのスコア内訳
+    /// total = wave + learned_rule + knowledge + neuron + momentum - fatigue_penalty + opponent + morale
+    pub fn explain_last_decision(&self) -> &[ScoreBreakdown] {
+        &self.last_explanation
+    }
+
+    /// 波動場の学習率を一時的に factor 倍する（factor == 1.0 なら何もしない）
+    /// 戻り値は restore_wave_lr に渡す元の学習率 [mwso, scout, shards...]
+    fn scale_wave_lr(&mut self, factor: f32) -> Option<Vec<f32>> {
+        if factor == 1.0 { return None; }
+        let mut saved = vec![self.mwso.lr_scale, self.scout_mwso.lr_scale];
+        self.mwso.lr_scale *= factor;
+        self.scout_mwso.lr_scale *= factor;
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            for shard in &mut sharded.shards {
+                saved.push(shard.lr_scale);
+                shard.lr_scale *= factor;
+            }
+        }
+        Some(saved)
+    }
+
+    fn restore_wave_lr(&mut self, saved: &[f32]) {
+        self.mwso.lr_scale = saved[0];
+        self.scout_mwso.lr_scale = saved[1];
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            for (shard, &lr) in sharded.shards.iter_mut().zip(&saved[2..]) { shard.lr_scale = lr; }
+        }
+    }
+
+    /// 波動スコアから推定した状態価値（最大スコアを [-1, 1] に制限したもの）
+    fn bootstrap_value(&self, state_idx: usize) -> f32 {
+        self.evaluate_actions(state_idx).into_iter()
+            .fold(f32::NEG_INFINITY, f32::max)
+            .clamp(-1.0, 1.0)
+    }
+
+    /// 新しい順に並んだ経験の状態列に対して、各経験へ割り当てる報酬を計算する
+    /// n_step 未設定なら割引モンテカルロ（reward * γ^k）。
+    /// 設定時は直近 n ステップまでを実報酬で、それより古い経験は n ステップ先の状態価値で
+    /// ブートストラップする（γ^n * V(s_{t+n})）
+    fn step_credits(&self, reward: f32, states: &[usize], gamma: f32) -> Vec<f32> {
+        let n = match self.config.n_step {
+            Some(n) if n < states.len() => n.max(1),
+            _ => return (0..states.len()).map(|k| reward * gamma.powi(k as i32)).collect(),
+        };
+        let tail_discount = gamma.powi(n as i32);
+        let mut values: HashMap<usize, f32> = HashMap::new();
+        (0..states.len()).map(|k| {
+            if k < n { return reward * gamma.powi(k as i32); }
+            let later = states[k - n];
+            let v = match values.get(&later) {
+                Some(&v) => v,
+                None => {
+                    let v = self.bootstrap_value(later);
+                    values.insert(later, v);
+                    v
+                }
+            };
+            tail_discount * v
+        }).collect()
+    }
+
+    pub fn learn_vector(&mut self, reward: f32) {
+        self.note_unrecorded();
+        let mut discount = 1.0;
+        let gamma = 0.9;
+
+        let history_clone = self.vector_history.clone();
+        let states: Vec<usize> = history_clone.iter().rev()
+            .map(|exp| exp.state_weights.iter()
+                .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+                .map_or(0, |&(idx, _)| idx))
+            .collect();
+        let credits = self.step_credits(reward, &states, gamma);
+        for (k, exp) in history_clone.iter().rev().enumerate() {
+            let discounted_reward = credits[k];
+            for &(state_idx, _) in &exp.state_weights { self.score_cache.remove(state_idx); }
+            if let Some(ref mut sharded) = self.sharded_mwso {
+                sharded.adapt_vector(&exp.state_weights, discounted_reward, &exp.actions, self.system_temperature);
+            } else {
+                self.mwso.imprint_vector_qcel(&exp.state_weights, discounted_reward);
+                for &(state_idx, w) in &exp.state_weights {
+                    if w > 0.01 {
+                        self.mwso.adapt(state_idx, discounted_reward * w, &exp.actions, self.system_temperature, self.action_size);
+                    }
+                }
+            }
 
             // Scout MWSO (use strongest feature for simplicity)
             if let Some(strongest) = exp.state_weights.iter().max_by(|a, b| a.1.partial_cmp(&b.1).unwrap()) {
@@ -442,23 +1824,23 @@ impl Singularity {
 
             // Update Penalty Matrix for each weighted state
             let penalty_dim = self.penalty_dim;
-            let bin_per_action = penalty_dim / self.action_size;
             let dim_stability = (1024.0 / self.mwso.dim as f32).sqrt().min(1.0);
+            let penalty_rows = self.penalty_rows();
 
             for &(state_idx, w) in &exp.state_weights {
                 if w < 0.05 { continue; }
+                self.settle_penalty_row(state_idx % penalty_rows);
                 for &action_idx in &exp.actions {
-                    let start = (state_idx % self.state_size) * penalty_dim + action_idx * bin_per_action;
-                    if start + bin_per_action <= self.penalty_matrix.len() {
+                    let bins = self.penalty_bins(action_idx);
+                    let row_start = (state_idx % penalty_rows) * penalty_dim;
+                    let cells = row_start + bins.start..row_start + bins.end;
+                    if cells.end <= self.penalty_len() {
                         if discounted_reward > 1.2 {
-                            for j in 0..bin_per_action { 
-                                self.penalty_matrix[start + j] *= 1.0 - (0.5 * w * (0.5 + 0.4 * (1.0 - dim_stability))); 
-                            }
+                            let factor = 1.0 - (0.5 * w * (0.5 + 0.4 * (1.0 - dim_stability)));
+                            self.update_penalty_cells(cells, |p| p * factor);
                         } else if discounted_reward < 0.0 {
                             let p_add = (discounted_reward.abs() * 2.0 * dim_stability * w).min(10.0);
-                            for j in 0..bin_per_action { 
-                                self.penalty_matrix[start + j] = (self.penalty_matrix[start + j] + p_add).min(10.0); 
-                            }
+                            self.update_penalty_cells(cells, |p| (p + p_add).min(10.0));
                         }
                     }
                 }
@@ -470,87 +1852,141 @@ impl Singularity {
     }
 
     pub fn learn(&mut self, reward: f32) {
-        // Handle vector-based history first
-        if !self.vector_history.is_empty() {
-            self.learn_vector(reward);
-            self.vector_history.clear();
+        let started = Instant::now();
+        self.learn_from_history(reward);
+        if let Some(ref mut log) = self.episode_log {
+            log.events.push(EpisodeEvent::Reward { reward, temperature: self.system_temperature });
         }
+        let (wave_norm, temperature) = (self.wave_norm(), self.system_temperature);
+        let mut stats = self.stats_mut();
+        stats.learn.record(started.elapsed());
+        stats.record_state(wave_norm, temperature);
+    }
+
+    /// 呼び出し回数・レイテンシ・波動ノルムと温度の推移
+    pub fn stats(&self) -> StatsReport {
+        self.stats_mut().report()
+    }
+
+    fn stats_mut(&self) -> MutexGuard<'_, RuntimeStats> {
+        // 計測値しか持たないため、ポイズニングされていても中身をそのまま使う
+        self.stats.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 波動場全体のノルム（シャード化されている場合は全シャードの合計）
+    pub fn wave_norm(&self) -> f32 {
+        let energy = |m: &MWSO| -> f32 { from_wave(m.psi_real.iter().zip(&m.psi_imag).map(|(re, im)| re * re + im * im).sum()) };
+        match self.sharded_mwso {
+            Some(ref sharded) => sharded.shards.iter().map(energy).sum::<f32>().sqrt(),
+            None => energy(&self.mwso).sqrt(),
+        }
+    }
+
+    /// 記録中に、エピソードログに残らない意思決定・学習の経路が呼ばれたことを数える（そのログは再生できなくなる）
+    fn note_unrecorded(&mut self) {
+        if let Some(ref mut log) = self.episode_log { log.unrecorded += 1; }
+    }
+
+    /// エピソードの記録を開始する（記録中のログは破棄される）
+    pub fn start_recording(&mut self) {
+        self.episode_log = Some(EpisodeLog::new(self.state_size, self.category_sizes.clone()));
+    }
+
+    /// 記録を終了し、ログを返す
+    pub fn stop_recording(&mut self) -> Option<EpisodeLog> {
+        self.episode_log.take()
+    }
+
+    /// 保存済みの軌跡（古い対局・エキスパートデータ）から方策外学習する
+    /// 各ステップの importance（π/μ）は config.importance_clip で打ち切られ、そのステップの報酬に掛けられる（波動場・ペナルティ・ルールのすべてに効く）
+    /// 行動が空・範囲外の行動や状態を含むステップは読み飛ばす。
+    /// max_history より長い軌跡は新しい側から max_history 個ずつの窓に分けて学習し、古い窓の終端報酬は直後の窓の先頭状態の価値で置き換える。
+    /// 更新するのは波動場・ペナルティ・ルールだけで、進行中の履歴・慣性・感情・温度・メタコントローラーは影響を受けない
+    pub fn learn_trajectory(&mut self, trajectory: &[TrajectoryStep], reward: f32) {
+        self.note_unrecorded();
+        let steps: Vec<&TrajectoryStep> = trajectory.iter().filter(|s| self.is_valid_experience(s.state_idx, &s.actions)).collect();
+        if steps.is_empty() { return; }
 
-        let mut discount = 1.0;
         let gamma = 0.9;
+        let mut terminal = reward;
+        for window in steps.rchunks(self.max_history.max(1)) {
+            self.learn_offline_window(window, terminal, gamma);
+            terminal = gamma * self.bootstrap_value(window[0].state_idx);
+        }
+    }
 
-        let history_clone = self.history.clone();
-        for exp in history_clone.iter().rev() {
-            let discounted_reward = reward * discount;
-            if let Some(ref mut sharded) = self.sharded_mwso {
-                sharded.adapt(exp.state_idx, discounted_reward, &exp.actions, self.system_temperature);
-
-                // シャード間トンネルの学習
-                if discounted_reward > 0.1 && !sharded.shards.is_empty() {
-                    let state_shard_idx = exp.state_idx % sharded.shards.len();
-                    for &action_idx in &exp.actions {
-                        let (action_shard_idx, local_action) = sharded.shard_for_action(action_idx);
-                        if state_shard_idx != action_shard_idx {
-                            // 状態とアクションの担当シャードが違う場合、トンネルを強化
-                            let strength = (0.05 * discounted_reward).min(0.1);
-                            sharded.add_or_strengthen_tunnel(state_shard_idx, action_shard_idx, exp.state_idx, local_action, strength);
-                        }
-                    }
-                }
-            } else {
-                self.mwso.adapt(exp.state_idx, discounted_reward, &exp.actions, self.system_temperature, self.action_size);
-            }
+    /// 方策外の 1 窓分（古い順）を、波動場・ペナルティ・ルールにだけ反映する
+    fn learn_offline_window(&mut self, window: &[&TrajectoryStep], reward: f32, gamma: f32) {
+        for s in window { self.touch_state(s.state_idx); }
+        let clip = self.config.importance_clip;
+        let states: Vec<usize> = window.iter().rev().map(|s| s.state_idx).collect();
+        let credits = self.step_credits(reward, &states, gamma);
+        let mut discount = 1.0;
+        for (k, s) in window.iter().rev().enumerate() {
+            let exp = Experience { state_idx: s.state_idx, actions: s.actions.clone() };
+            self.apply_experience(&exp, credits[k], discount, s.importance.clamp(0.0, clip));
+            discount *= gamma;
+        }
+        self.penalty_clock += 1;
+        self.advance_rule_clock(1);
+    }
 
-            // Scout MWSOにも報酬を反映 (低次元での大まかな傾向学習)
-            self.scout_mwso.adapt(exp.state_idx % 128, discounted_reward, &exp.actions, self.system_temperature, self.action_size);
+    /// 外部から与えられた経験が学習できる形か（行動が 1 つ以上あり、状態と行動がすべて範囲内）
+    fn is_valid_experience(&self, state_idx: usize, actions: &[usize]) -> bool {
+        !actions.is_empty() && state_idx < self.state_size && actions.iter().all(|&a| a < self.action_size)
+    }
 
-            if self.active_conditions.is_empty() {
-                let state = exp.state_idx;
-                let action = exp.actions[0];
-                let dim_stability = (1024.0 / self.mwso.dim as f32).sqrt().min(1.0);
+    fn learn_from_history(&mut self, reward: f32) {
+        // 感情の帰属先となる状態（直近の決定の状態、ベクトル入力なら最大重みの状態）
+        let emotion_state = match (self.history.back(), self.vector_history.back()) {
+            (Some(exp), _) => exp.state_idx,
+            (None, Some(vexp)) => vexp.state_weights.iter()
+                .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+                .map_or(self.last_state_idx, |&(idx, _)| idx),
+            (None, None) => self.last_state_idx,
+        };
 
-                if discounted_reward > 1.2 {
-                    if let Some(rule) = self.learned_rules.iter_mut().find(|r| r.0 == state && r.1 == action) {
-                        rule.2 += 1;
-                    } else {
-                        self.learned_rules.push((state, action, 1));
-                    }
-                    let penalty_dim = self.penalty_dim;
-                    let bin_per_action = penalty_dim / self.action_size;
-                    let start = state * penalty_dim + action * bin_per_action;
-                    // 成功時にペナルティを消す力も次元数で調整
-                    for j in 0..bin_per_action { self.penalty_matrix[start + j] *= 0.5 + 0.4 * (1.0 - dim_stability); }
-                } else if discounted_reward < 0.0 {
-                    let penalty_dim = self.penalty_dim;
-                    let bin_per_action = penalty_dim / self.action_size;
-                    let start = state * penalty_dim + action * bin_per_action;
-                    for j in 0..bin_per_action { 
-                        // 失敗時のペナルティ注入を次元数に応じて薄める
-                        let p_add = (discounted_reward.abs() * 2.0 * dim_stability).min(10.0);
-                        self.penalty_matrix[start + j] = (self.penalty_matrix[start + j] + p_add).min(10.0); 
-                    }
-                }
-            }
+        // 学習対象の状態をアクティブとして扱う（アーカイブ済みなら先に復元）
+        let touched: Vec<usize> = self.history.iter().map(|e| e.state_idx).collect();
+        for state in touched { self.touch_state(state); }
 
-            // --- ここから自動IRL注入ロジック ---
-            const HIGH_REWARD_THRESHOLD: f32 = 1.0;
-            const LOW_REWARD_THRESHOLD: f32 = -0.5;
+        self.update_velocity_trust(emotion_state, reward);
 
-            if discounted_reward > HIGH_REWARD_THRESHOLD {
-                // 高報酬: エキスパート行動と見なして observe_expert で自己強化
-                let strength = (discounted_reward - HIGH_REWARD_THRESHOLD) * 0.2;
-                self.observe_expert(exp.state_idx, &exp.actions, strength.clamp(0.0, 0.5));
-            } else if discounted_reward < LOW_REWARD_THRESHOLD {
-                // 低報酬: アンチエキスパート行動と見なして suppress_expert で自己抑制
-                let strength = (discounted_reward.abs() - LOW_REWARD_THRESHOLD.abs()) * 0.2;
-                self.suppress_expert(&exp.actions, strength.clamp(0.0, 0.5));
+        // 神経修飾物質: 報酬予測誤差とストレス（苛立ち・興奮）で更新し、今回の学習率を決める
+        let stress = ((self.frustration + self.adrenaline) * 0.5).clamp(0.0, 1.0);
+        let saved_lr = match self.neuromodulators.as_mut() {
+            Some(n) => {
+                n.update(reward, stress);
+                let scale = n.learning_rate_scale();
+                self.scale_wave_lr(scale)
             }
-            // --- 自動IRL注入ここまで ---
+            None => None,
+        };
 
-            for &idx in &exp.actions {
-                if discounted_reward < 0.0 { self.fatigue_map[idx] = (self.fatigue_map[idx] + 0.2 * discount).min(1.0); }
-                else { self.fatigue_map[idx] = (self.fatigue_map[idx] - 0.3 * discount).max(0.0); }
-            }
+        // シャドウ候補の意思決定に、ライブの経験統計から期待報酬を割り当てる
+        let stats = &self.symmetry.stats;
+        if let Some(ref mut shadow) = self.shadow {
+            shadow.record_reward(reward, |state, action| {
+                let &(sum, count) = stats.get(&state)?.get(action)?;
+                if count == 0 { None } else { Some(sum / count as f32) }
+            });
+        }
+
+        // Handle vector-based history first
+        if !self.vector_history.is_empty() {
+            self.learn_vector(reward);
+            self.vector_history.clear();
+        }
+
+        let mut discount = 1.0;
+        let gamma = 0.9;
+
+        let history_clone = self.history.clone();
+        let states: Vec<usize> = history_clone.iter().rev().map(|exp| exp.state_idx).collect();
+        let credits = self.step_credits(reward, &states, gamma);
+        for (k, exp) in history_clone.iter().rev().enumerate() {
+            let discounted_reward = credits[k];
+            self.apply_experience(exp, discounted_reward, discount, 1.0);
 
             discount *= gamma;
             if discount < 0.01 { break; }
@@ -569,11 +2005,478 @@ impl Singularity {
         // 慣性の自然減衰
         for m in &mut self.action_momentum { *m *= 0.95; }
 
-        for p in &mut self.penalty_matrix { *p *= 0.995; }
+        // ペナルティ行列の減衰は時計を進めるだけにし、各行の次回アクセス時に反映する
+        if let Some(saved) = saved_lr { self.restore_wave_lr(&saved); }
+
+        self.penalty_clock += 1;
         for f in &mut self.fatigue_map { *f *= 0.98; }
 
+        self.advance_rule_clock(1);
+
+        self.update_emotions(emotion_state, reward);
         self.digest_experience(reward.abs(), reward, if reward < 0.0 { reward.abs() } else { 0.0 });
         self.history.clear();
+
+        // メタコントローラー: 報酬を評価し、必要なら次の設定へ切り替える
+        if let Some(ref mut meta) = self.meta_controller {
+            meta.record(reward);
+            self.meta_params = meta.current();
+        }
+
+        let interval = self.config.consolidate_interval;
+        if interval > 0 && self.rule_clock.is_multiple_of(interval) {
+            self.consolidate_memory();
+        }
+        self.maybe_grow_dimension(emotion_state);
+    }
+
+    /// オフライン学習用のバッチ API: 遷移 (state_idx, actions, reward) ごとに報酬をそのまま反映し、
+    /// ペナルティ・疲労・慣性の減衰と波動場の消化（正規化）はバッチ全体で1回だけ行う
+    /// 進行中の意思決定とは無関係なため、履歴・感情・メタコントローラーには影響しない
+    /// 行動が空・範囲外の行動や状態を含む遷移は読み飛ばす
+    pub fn learn_batch(&mut self, batch: &[(usize, Vec<usize>, f32)]) {
+        self.note_unrecorded();
+        let mut reward_sum = 0.0;
+        let mut applied = 0usize;
+        for (state_idx, actions, reward) in batch {
+            if !self.is_valid_experience(*state_idx, actions) { continue; }
+            self.touch_state(*state_idx);
+            let exp = Experience { state_idx: *state_idx, actions: actions.clone() };
+            self.apply_experience(&exp, *reward, 1.0, 1.0);
+            reward_sum += reward;
+            applied += 1;
+        }
+        if applied == 0 { return; }
+
+        // 1遷移ごとの減衰を n 回分まとめて掛ける
+        let n = applied as i32;
+        let momentum_decay = 0.95f32.powi(n);
+        let fatigue_decay = 0.98f32.powi(n);
+        for m in &mut self.action_momentum { *m *= momentum_decay; }
+        self.penalty_clock += applied as u64;
+        for f in &mut self.fatigue_map { *f *= fatigue_decay; }
+
+        self.advance_rule_clock(applied as u64);
+
+        let mean_reward = reward_sum / applied as f32;
+        self.digest_experience(mean_reward.abs(), mean_reward, if mean_reward < 0.0 { mean_reward.abs() } else { 0.0 });
+    }
+
+    /// 学習ステップの時計を進め、剪定間隔をまたいだらルールの剪定とアーカイブを行う
+    /// 知識ルールの減衰と期限切れもここで進める
+    fn advance_rule_clock(&mut self, ticks: u64) {
+        let before = self.rule_clock;
+        self.rule_clock += ticks;
+        self.bootstrapper.advance(ticks, &self.condition_levels);
+        let interval = self.config.rule_prune_interval;
+        if interval > 0 && before / interval != self.rule_clock / interval {
+            self.prune_learned_rules();
+            if self.cold_archive.is_some() && self.archive_inactive_states().is_err() {
+                self.archive_errors += 1;
+            }
+        }
+    }
+
+    /// 1件の経験を、割り当てられた報酬で波動場・ペナルティ・ルール・疲労へ反映する
+    /// weight は報酬に掛かる重要度重み（0 以下ならこの経験からは何も学ばない）
+    fn apply_experience(&mut self, exp: &Experience, discounted_reward: f32, discount: f32, weight: f32) {
+        // 以降の更新はすべて重み付きの報酬を使う
+        if weight <= 0.0 { return; }
+        let discounted_reward = discounted_reward * weight;
+        // この状態の波が変わるので、締め切り用にキャッシュしたスコアは使えなくなる
+        self.score_cache.remove(exp.state_idx);
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            sharded.adapt(exp.state_idx, discounted_reward, &exp.actions, self.system_temperature);
+
+            // シャード間トンネルの学習
+            if discounted_reward > 0.1 && !sharded.shards.is_empty() {
+                let state_shard_idx = exp.state_idx % sharded.shards.len();
+                for &action_idx in &exp.actions {
+                    let (action_shard_idx, local_action) = sharded.shard_for_action(action_idx);
+                    if state_shard_idx != action_shard_idx {
+                        // 状態とアクションの担当シャードが違う場合、トンネルを強化
+                        let strength = (0.05 * discounted_reward).min(0.1);
+                        sharded.add_or_strengthen_tunnel(state_shard_idx, action_shard_idx, exp.state_idx, local_action, strength);
+                    }
+                }
+            }
+        } else {
+            self.mwso.adapt(exp.state_idx, discounted_reward, &exp.actions, self.system_temperature, self.action_size);
+        }
+
+        // Scout MWSOにも報酬を反映 (低次元での大まかな傾向学習)
+        self.scout_mwso.adapt(exp.state_idx % 128, discounted_reward, &exp.actions, self.system_temperature, self.action_size);
+
+        for &action in &exp.actions {
+            self.symmetry.record(exp.state_idx, action, discounted_reward);
+        }
+
+        // 相手モデル: 予測された敵行動に対する自分の行動の有効性を更新
+        if let Some(ref mut model) = self.opponent_model {
+            model.reinforce(exp.state_idx, &exp.actions, discounted_reward);
+        }
+
+        if self.active_conditions.is_empty() {
+            let state = exp.state_idx;
+            let action = exp.actions[0];
+            let dim_stability = (1024.0 / self.mwso.dim as f32).sqrt().min(1.0);
+            self.settle_penalty_row(state);
+
+            if discounted_reward > 1.2 {
+                self.reinforce_learned_rule(state, action);
+                let cells = self.penalty_cells(state, action);
+                // 成功時にペナルティを消す力も次元数で調整
+                self.update_penalty_cells(cells, |p| p * (0.5 + 0.4 * (1.0 - dim_stability)));
+            } else if discounted_reward < 0.0 {
+                let cells = self.penalty_cells(state, action);
+                // 失敗時のペナルティ注入を次元数に応じて薄める
+                let p_add = (discounted_reward.abs() * 2.0 * dim_stability).min(10.0);
+                self.update_penalty_cells(cells, |p| (p + p_add).min(10.0));
+            }
+        }
+
+        self.update_crystallization(exp.state_idx, exp.actions[0], discounted_reward);
+
+        // --- ここから自動IRL注入ロジック ---
+        const HIGH_REWARD_THRESHOLD: f32 = 1.0;
+        const LOW_REWARD_THRESHOLD: f32 = -0.5;
+
+        if discounted_reward > HIGH_REWARD_THRESHOLD {
+            // 高報酬: エキスパート行動と見なして observe_expert で自己強化
+            let strength = (discounted_reward - HIGH_REWARD_THRESHOLD) * 0.2;
+            self.imitate_expert(exp.state_idx, &exp.actions, strength.clamp(0.0, 0.5));
+        } else if discounted_reward < LOW_REWARD_THRESHOLD {
+            // 低報酬: アンチエキスパート行動と見なして suppress_expert で自己抑制
+            let strength = (discounted_reward.abs() - LOW_REWARD_THRESHOLD.abs()) * 0.2;
+            self.inhibit_actions(&exp.actions, strength.clamp(0.0, 0.5));
+        }
+        // --- 自動IRL注入ここまで ---
+
+        let fatigue_scale = self.neuromodulators.map_or(1.0, |n| n.fatigue_scale());
+        for &idx in &exp.actions {
+            if discounted_reward < 0.0 { self.fatigue_map[idx] = (self.fatigue_map[idx] + 0.2 * discount * fatigue_scale).min(1.0); }
+            else { self.fatigue_map[idx] = (self.fatigue_map[idx] - 0.3 * discount).max(0.0); }
+        }
+    }
+
+    /// 結晶化: 十分に確信された learned_rule を状態条件のハミルトニアン・ルールへ昇格させ、
+    /// 条件駆動の意思決定やモデル統合でも失われないようにする。負の報酬が続けば降格する
+    fn update_crystallization(&mut self, state: usize, action: usize, reward: f32) {
+        let key = (state, action);
+        if let Some(failures) = self.crystallized.get_mut(&key) {
+            if reward < 0.0 {
+                *failures += 1;
+                if *failures >= self.config.demotion_failures.max(1) {
+                    self.demote_rule(state, action);
+                }
+            } else if reward > 0.0 {
+                *failures = 0;
+            }
+            return;
+        }
+
+        let threshold = self.config.crystallize_threshold;
+        if threshold == 0 || reward <= 0.0 { return; }
+        let confident = self.learned_rule_position(state, action).is_some_and(|i| self.learned_rules[i].count >= threshold);
+        if confident {
+            self.bootstrapper.add_crystallized_rule(state as i32, action, self.config.crystallize_strength);
+            self.crystallized.insert(key, 0);
+        }
+    }
+
+    fn demote_rule(&mut self, state: usize, action: usize) {
+        self.crystallized.remove(&(state, action));
+        self.bootstrapper.remove_crystallized_rule(state as i32, action);
+        self.learned_rules.retain(|r| !(r.state == state && r.action == action));
+        self.reindex_learned_rules();
+    }
+
+    /// ルールを強化する。新規追加で上限を超える場合は最も長く強化されていないルールを追い出す（結晶化済みは後回し）
+    fn reinforce_learned_rule(&mut self, state: usize, action: usize) {
+        let now = self.rule_clock;
+        if let Some(i) = self.learned_rule_position(state, action) {
+            let rule = &mut self.learned_rules[i];
+            rule.count += 1;
+            rule.last_used = now;
+            return;
+        }
+        let cap = self.config.learned_rule_cap.max(1);
+        while self.learned_rules.len() >= cap {
+            let crystallized = &self.crystallized;
+            let victim = self.learned_rules.iter().enumerate()
+                .min_by_key(|(_, r)| (crystallized.contains_key(&(r.state, r.action)), r.last_used))
+                .map(|(i, _)| i);
+            match victim {
+                Some(i) => {
+                    self.learned_rules.swap_remove(i);
+                    self.reindex_learned_rules();
+                }
+                None => break,
+            }
+        }
+        if self.learned_index.lookup(&(state, action), self.learned_rules.len()).is_none() {
+            self.reindex_learned_rules();
+        }
+        self.learned_rules.push(LearnedRule { state, action, count: 1, last_used: now });
+        self.learned_index.push(Some((state, action)));
+    }
+
+    /// 成功回数が少なく、長く強化されていないルールを忘れる
+    pub fn prune_learned_rules(&mut self) -> usize {
+        let now = self.rule_clock;
+        let min_count = self.config.rule_prune_min_count;
+        let stale_after = self.config.rule_stale_after;
+        let before = self.learned_rules.len();
+        self.learned_rules.retain(|r| r.count >= min_count || now.saturating_sub(r.last_used) < stale_after);
+        let removed = before - self.learned_rules.len();
+        if removed > 0 { self.reindex_learned_rules(); }
+        removed
+    }
+
+    /// コールドストレージを有効化する（dir は存在しなければ作成される）
+    pub fn enable_cold_archive(&mut self, dir: &str) -> io::Result<()> {
+        self.cold_archive = Some(ColdArchive::new(dir)?);
+        Ok(())
+    }
+
+    /// 状態へのアクセスを記録し、アーカイブ済みであれば透過的に復元する
+    fn touch_state(&mut self, state_idx: usize) {
+        self.state_last_active.insert(state_idx, self.rule_clock);
+        if self.cold_archive.as_ref().is_some_and(|a| a.contains(state_idx))
+            && self.restore_archived_state(state_idx).is_err() {
+            self.archive_errors += 1;
+        }
+    }
+
+    /// config.archive_after 以上アクセスのない状態のルール・ペナルティ・経験統計をディスクへ退避する
+    pub fn archive_inactive_states(&mut self) -> io::Result<usize> {
+        if self.cold_archive.is_none() { return Ok(0); }
+        let now = self.rule_clock;
+        let horizon = self.config.archive_after;
+        let mut inactive: Vec<usize> = self.state_last_active.iter()
+            .filter(|&(_, &t)| now.saturating_sub(t) >= horizon)
+            .map(|(&s, _)| s)
+            .collect();
+        inactive.sort_unstable();
+
+        let penalty_dim = self.penalty_dim;
+        let penalty_rows = self.penalty_rows();
+        let mut archived = 0;
+        for state in inactive {
+            self.state_last_active.remove(&state);
+            self.settle_penalty_row(state);
+            let rules: Vec<LearnedRule> = self.learned_rules.iter().filter(|r| r.state == state).copied().collect();
+            let penalties = if state < penalty_rows {
+                self.penalty_row_values(state)
+            } else {
+                Vec::new()
+            };
+            let outcome_stats = self.symmetry.stats.get(&state).cloned().unwrap_or_default();
+            if rules.is_empty() && outcome_stats.is_empty() && penalties.iter().all(|&p| p == 0.0) { continue; }
+
+            let data = ArchivedState { rules, penalties, outcome_stats };
+            if let Some(ref mut archive) = self.cold_archive {
+                archive.store(state, &data)?;
+            }
+            self.learned_rules.retain(|r| r.state != state);
+            if !data.penalties.is_empty() {
+                self.write_penalty_row(state, &vec![0.0; penalty_dim]);
+            }
+            self.symmetry.stats.remove(&state);
+            archived += 1;
+        }
+        self.reindex_learned_rules();
+        Ok(archived)
+    }
+
+    /// アーカイブ済みの状態を読み戻す（アーカイブされていなければ false）
+    pub fn restore_archived_state(&mut self, state_idx: usize) -> io::Result<bool> {
+        let now = self.rule_clock;
+        let Some(archive) = self.cold_archive.as_mut() else { return Ok(false); };
+        let Some(data) = archive.take(state_idx, now)? else { return Ok(false); };
+        self.merge_archived_state(state_idx, data);
+        Ok(true)
+    }
+
+    /// 退避データをルール・ペナルティ行・経験統計へ書き戻す
+    fn merge_archived_state(&mut self, state_idx: usize, data: ArchivedState) {
+        self.learned_rules.extend(data.rules);
+        self.reindex_learned_rules();
+        let penalty_dim = self.penalty_dim;
+        if data.penalties.len() == penalty_dim && state_idx < self.penalty_rows() {
+            self.write_penalty_row(state_idx, &data.penalties);
+            self.penalty_row_clock[state_idx] = self.penalty_clock;
+        }
+        if !data.outcome_stats.is_empty() {
+            self.symmetry.stats.insert(state_idx, data.outcome_stats);
+        }
+    }
+
+    /// 読み込み直後など、閾値を超えているが未昇格の learned_rules をまとめて昇格させる
+    pub fn recrystallize(&mut self) {
+        let threshold = self.config.crystallize_threshold;
+        if threshold == 0 { return; }
+        let candidates: Vec<(usize, usize)> = self.learned_rules.iter()
+            .filter(|r| r.count >= threshold && !self.crystallized.contains_key(&(r.state, r.action)))
+            .map(|r| (r.state, r.action))
+            .collect();
+        for (state, action) in candidates {
+            self.bootstrapper.add_crystallized_rule(state as i32, action, self.config.crystallize_strength);
+            self.crystallized.insert((state, action), 0);
+        }
+    }
+
+    /// 予測への信頼度: 過去の (状態, 行動) の平均報酬から期待した符号と、実際の報酬の符号の一致率（移動平均）
+    /// 信頼度に応じて波動場の学習率と慣性ブーストを絞り、外れが続いた後は慎重に振る舞う
+    fn update_velocity_trust(&mut self, state_idx: usize, reward: f32) {
+        if let Some(row) = self.symmetry.stats.get(&state_idx) {
+            for &action in &self.last_actions {
+                let Some(&(sum, count)) = row.get(action) else { continue; };
+                if count == 0 { continue; }
+                let expected_positive = sum / count as f32 > 0.0;
+                let hit = if expected_positive == (reward > 0.0) { 1.0 } else { 0.0 };
+                self.velocity_trust = self.velocity_trust * 0.9 + hit * 0.1;
+            }
+        }
+        self.velocity_trust = self.velocity_trust.clamp(0.0, 1.0);
+
+        let lr_scale = 0.5 + 0.5 * self.velocity_trust;
+        self.mwso.lr_scale = lr_scale;
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            for shard in &mut sharded.shards { shard.lr_scale = lr_scale; }
+        }
+    }
+
+    /// 複合温度コントローラー: 報酬で決まった温度に、苛立ちによる加熱・士気による下限・介入レベルによる上限を重ねる
+    fn apply_emotional_temperature(&mut self) {
+        let cfg = &self.config;
+        let reward_driven = self.system_temperature;
+        let frustration_heat = self.frustration * cfg.temp_frustration_weight;
+        let floor = cfg.temperature_floor + (1.0 - self.morale).max(0.0) * cfg.temp_morale_floor_weight;
+        let ceiling = (cfg.temperature_ceiling * (1.0 - self.intervention_level.clamp(0.0, 1.0) * cfg.temp_intervention_cap_weight)).max(floor);
+        let result = (reward_driven + frustration_heat).clamp(floor, ceiling);
+        self.system_temperature = result;
+
+        self.temperature_trace.push_back(TemperatureTrace { reward_driven, frustration_heat, floor, ceiling, result });
+        if self.temperature_trace.len() > 64 { self.temperature_trace.pop_front(); }
+    }
+
+    pub fn set_intervention_level(&mut self, level: f32) {
+        self.intervention_level = level.clamp(0.0, 1.0);
+    }
+
+    /// 感情ダイナミクス
+    /// adrenaline: 緊急度（報酬の大きさ）と被ダメージ（負の報酬）で急上昇し、速やかに減衰する
+    /// frustration: 同じ状態での連続失敗で蓄積し、成功でほどける。ゆっくり減衰する
+    pub fn update_emotions(&mut self, state_idx: usize, reward: f32) {
+        let urgency = (reward.abs() * 0.5).min(1.0);
+        let damage = (-reward).max(0.0);
+        self.adrenaline = (self.adrenaline * 0.85 + urgency * 0.2 + damage * 0.3).clamp(0.0, 1.0);
+
+        if reward < 0.0 {
+            let streak = self.failure_streaks.entry(state_idx).or_insert(0);
+            *streak += 1;
+            // 同じ状態で失敗を繰り返すほど苛立ちは加速する
+            let gain = 0.05 * (*streak as f32).min(5.0) * damage.min(2.0);
+            self.frustration = (self.frustration * 0.95 + gain).clamp(0.0, 1.0);
+        } else {
+            self.failure_streaks.remove(&state_idx);
+            let relief = if reward > 0.0 { 0.8 } else { 0.95 };
+            self.frustration = (self.frustration * relief).clamp(0.0, 1.0);
+        }
+
+        self.update_morale(reward);
+    }
+
+    /// 士気: 連勝で上がり連敗で下がる（連続するほど変化が大きい）。平常時は 1.0 へ緩やかに戻る
+    /// 忍耐: 勝敗のつかない結果が続くと削られ、決着がつくと回復する
+    fn update_morale(&mut self, reward: f32) {
+        const DECISIVE: f32 = 0.1;
+        if reward > DECISIVE {
+            self.reward_streak = self.reward_streak.max(0) + 1;
+            self.morale += 0.05 * self.reward_streak.min(5) as f32;
+        } else if reward < -DECISIVE {
+            self.reward_streak = self.reward_streak.min(0) - 1;
+            self.morale -= 0.05 * (-self.reward_streak).min(5) as f32;
+        }
+        self.morale = (self.morale + (1.0 - self.morale) * 0.02).clamp(0.0, 2.0);
+
+        if reward.abs() <= DECISIVE {
+            self.stalemate_streak += 1;
+            self.patience *= 1.0 - 0.02 * (self.stalemate_streak.min(10) as f32);
+        } else {
+            self.stalemate_streak = 0;
+            self.patience += (1.0 - self.patience) * 0.3;
+        }
+        self.patience = self.patience.clamp(0.0, 1.0);
+    }
+
+    /// 直近の意思決定履歴ごと学習をキューに積む（実際の学習は drain_learn_queue か LearnWorker の学習スレッドで行う）
+    /// キューが満杯の場合は config.learn_queue_policy に従う
+    pub fn queue_learn(&mut self, reward: f32) {
+        self.note_unrecorded();
+        let item = PendingLearn {
+            reward,
+            history: std::mem::take(&mut self.history),
+            vector_history: std::mem::take(&mut self.vector_history),
+            last_actions: self.last_actions.clone(),
+        };
+
+        let capacity = self.config.learn_queue_capacity.max(1);
+        if self.learn_queue.len() >= capacity {
+            match self.config.learn_queue_policy {
+                LearnQueuePolicy::Block => {
+                    while self.learn_queue.len() >= capacity {
+                        self.learn_queue.stats.blocked += 1;
+                        self.process_one_queued();
+                    }
+                }
+                LearnQueuePolicy::DropOldest => {
+                    while self.learn_queue.len() >= capacity {
+                        self.learn_queue.pop();
+                        self.learn_queue.stats.dropped += 1;
+                    }
+                }
+                LearnQueuePolicy::Coalesce => {
+                    let max_history = self.max_history;
+                    self.learn_queue.coalesce(item, max_history);
+                    return;
+                }
+            }
+        }
+        self.learn_queue.push(item);
+    }
+
+    /// キューに積まれた学習を最大 max_items 件実行し、実行件数を返す
+    pub fn drain_learn_queue(&mut self, max_items: usize) -> usize {
+        self.note_unrecorded();
+        let mut processed = 0;
+        while processed < max_items && self.process_one_queued() {
+            processed += 1;
+        }
+        processed
+    }
+
+    fn process_one_queued(&mut self) -> bool {
+        let Some(item) = self.learn_queue.pop() else { return false; };
+        // 現在進行中の意思決定履歴を退避し、キューに積まれた時点の履歴で学習する
+        let current_history = std::mem::replace(&mut self.history, item.history);
+        let current_vector_history = std::mem::replace(&mut self.vector_history, item.vector_history);
+        let current_actions = std::mem::replace(&mut self.last_actions, item.last_actions);
+        self.learn(item.reward);
+        self.history = current_history;
+        self.vector_history = current_vector_history;
+        self.last_actions = current_actions;
+        self.learn_queue.stats.processed += 1;
+        true
+    }
+
+    pub fn learn_queue_depth(&self) -> usize {
+        self.learn_queue.len()
+    }
+
+    pub fn learn_queue_stats(&self) -> LearnQueueStats {
+        self.learn_queue.stats
     }
 
     pub fn digest_experience(&mut self, td_error: f32, reward: f32, penalty: f32) {
@@ -613,26 +2516,29 @@ impl Singularity {
                 let heating = (td_error * 0.3 / dim_inertia).min(1.0) * confidence_guard; 
                 self.system_temperature = (self.system_temperature + heating).min(2.0);
             }
+            self.apply_emotional_temperature();
         }
 
         let urgency = ((reward + penalty) * 5.0).min(1.0);
         
+        let penalty_field = self.live_penalty_field();
         match &mut self.sharded_mwso {
             Some(sharded) => {
-                sharded.inject_state(0, reward, self.system_temperature, &self.empty_penalty);
-                sharded.inject_state(1, -penalty, self.system_temperature, &self.empty_penalty);
-                sharded.step_core(0.05, 0.0, 0.0, self.system_temperature, &self.empty_penalty);
+                sharded.inject_state(0, reward, self.system_temperature, &penalty_field);
+                sharded.inject_state(1, -penalty, self.system_temperature, &penalty_field);
+                sharded.step_core(0.05, 0.0, 0.0, self.system_temperature, &penalty_field);
             },
             None => {
                 // In non-sharded mode, mwso.dim and penalty_dim are the same.
-                self.mwso.inject_state(0, reward, &self.empty_penalty);
-                self.mwso.inject_state(1, -penalty, &self.empty_penalty);
-                self.mwso.step_core(0.05, 0.0, 0.0, self.system_temperature, &self.empty_penalty);
+                self.mwso.inject_state(0, reward, &penalty_field);
+                self.mwso.inject_state(1, -penalty, &penalty_field);
+                self.mwso.step_core(0.05, 0.0, 0.0, self.system_temperature, &penalty_field);
             }
         }
 
-        let current_states: Vec<f32> = self.nodes.iter().map(|n| n.state).collect();
-        for node in &mut self.nodes { node.update(0.0, urgency, self.system_temperature, &current_states); }
+        self.node_batch.update(&mut self.nodes, &[], urgency, self.system_temperature);
+        self.apply_plasticity();
+        self.record_node_history();
 
         if urgency > 0.5 || (self.system_temperature - self.last_topology_update_temp).abs() > 0.05 {
             self.reshape_topology();
@@ -641,18 +2547,42 @@ impl Singularity {
 
     pub fn reshape_topology(&mut self) {
         self.last_topology_update_temp = self.system_temperature;
-        let arousal = (self.nodes[self.idx_aggression].state + self.adrenaline).clamp(0.0, 2.0);
-        let tactical_focus = self.nodes[self.idx_tactical].state;
+        // 苛立ちは覚醒を押し上げ、同時に戦術的な抑制を弱める
+        let arousal = (self.node_state(NODE_AGGRESSION) + self.adrenaline + self.frustration * 0.5).clamp(0.0, 2.0);
+        let tactical_focus = self.node_state(NODE_TACTICAL);
         let temp = self.system_temperature;
+        let composure = 1.0 - self.frustration * 0.5;
 
-        self.update_connection(self.idx_tactical, self.idx_reflex, (1.0 - temp).clamp(0.0, 1.0) * (1.0 + tactical_focus));
-        self.update_connection(self.idx_aggression, self.idx_reflex, arousal * 1.5);
-        self.update_connection(self.idx_fear, self.idx_reflex, self.nodes[self.idx_fear].state * 2.0);
+        self.update_connection(NODE_TACTICAL, NODE_REFLEX, (1.0 - temp).clamp(0.0, 1.0) * (1.0 + tactical_focus) * composure);
+        self.update_connection(NODE_AGGRESSION, NODE_REFLEX, arousal * 1.5);
+        self.update_connection(NODE_FEAR, NODE_REFLEX, self.node_state(NODE_FEAR) * 2.0);
 
         self.apply_elastic_fatigue();
     }
 
-    fn update_connection(&mut self, from: usize, to: usize, weight: f32) {
+    /// ノードの結合を GraphViz (DOT) で書き出す
+    /// ノードのラベルは名前と現在の活性、辺はシナプスの入力元 -> 受け手で、重みの符号で色、絶対値で太さを変える
+    pub fn export_topology_dot(&self) -> String {
+        let mut lines = vec!["digraph topology {".to_string(), "    node [shape=circle, style=filled, fillcolor=\"0 0 1\"];".to_string()];
+        for (i, node) in self.nodes.iter().enumerate() {
+            let name = if node.name.is_empty() { format!("node{}", i) } else { node.name.replace('"', "\\\"") };
+            // 活性が高いほど赤く塗る（HSV の彩度）
+            lines.push(format!("    n{} [label=\"{}\\n{:.3}\", fillcolor=\"0 {:.3} 1\"];", i, name, node.state, node.state.clamp(0.0, 1.0)));
+        }
+        for (i, node) in self.nodes.iter().enumerate() {
+            for synapse in &node.synapses {
+                let color = if synapse.weight < 0.0 { "blue" } else { "black" };
+                let penwidth = (synapse.weight.abs() * 2.0).clamp(0.5, 6.0);
+                lines.push(format!("    n{} -> n{} [label=\"{:.3}\", color={}, penwidth={:.2}];", synapse.target_id, i, synapse.weight, color, penwidth));
+            }
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    /// 名前で指定したノード間の結合を更新する（どちらかが構成に無ければ何もしない）
+    fn update_connection(&mut self, from: &str, to: &str, weight: f32) {
+        let (Some(from), Some(to)) = (self.node_index(from), self.node_index(to)) else { return };
         if let Some(node) = self.nodes.get_mut(from) {
             if let Some(synapse) = node.synapses.iter_mut().find(|s| s.target_id == to) { synapse.weight = weight; }
             else { node.synapses.push(super::node::Synapse { target_id: to, weight }); }
@@ -670,11 +2600,48 @@ impl Singularity {
     }
 
     pub fn update_all_nodes(&mut self, input_signals: &[f32], urgency: f32) {
-        self.mwso.step_core(0.1, 0.0, 0.0, self.system_temperature, &vec![0.0; self.mwso.dim]);
-        let current_states: Vec<f32> = self.nodes.iter().map(|n| n.state).collect();
+        let penalty_field = self.live_penalty_field();
+        self.mwso.step_core(0.1, 0.0, 0.0, self.system_temperature, &penalty_field);
+        self.node_batch.update(&mut self.nodes, input_signals, urgency, self.system_temperature);
+        self.apply_plasticity();
+        self.record_node_history();
+    }
+
+    /// 名前付きの信号をセンサー対応でノード入力に写し、ノードを更新する
+    pub fn feed_sensors(&mut self, signals: &[(&str, f32)], urgency: f32) {
+        let inputs = self.sensors.inputs(signals, self.nodes.len(), |name| self.node_index(name));
+        self.update_all_nodes(&inputs, urgency);
+    }
+
+    /// feed_sensors のハッシュ版（信号名は SensorMap::hash_name で引く）
+    pub fn feed_sensors_hashed(&mut self, signals: &[(i32, f32)], urgency: f32) {
+        let inputs = self.sensors.inputs_hashed(signals, self.nodes.len(), |name| self.node_index(name));
+        self.update_all_nodes(&inputs, urgency);
+    }
+
+    fn record_node_history(&mut self) {
+        let capacity = self.config.node_history_len;
+        for node in &mut self.nodes { node.record_history(capacity); }
+    }
+
+    /// ノードの直近の活性（古い順。範囲外なら空）
+    pub fn node_history(&self, idx: usize) -> Vec<f32> {
+        self.nodes.get(idx).map(|n| n.history.iter().copied().collect()).unwrap_or_default()
+    }
+
+    /// ノードの活性履歴の平均・分散・傾向（範囲外か履歴が空なら None）
+    pub fn node_history_stats(&self, idx: usize) -> Option<NodeHistoryStats> {
+        self.nodes.get(idx).and_then(|n| n.history_stats())
+    }
+
+    /// 更新後のノード状態でシナプスをヘッブ則に従って自己組織化させる（plasticity_rate が 0 なら何もしない）
+    pub fn apply_plasticity(&mut self) {
+        let rate = self.config.plasticity_rate;
+        if rate <= 0.0 { return; }
+        let (min_weight, max_weight) = (self.config.synapse_weight_min, self.config.synapse_weight_max);
+        let states: Vec<f32> = self.nodes.iter().map(|n| n.state).collect();
         for (i, node) in self.nodes.iter_mut().enumerate() {
-            let input = input_signals.get(i).cloned().unwrap_or(0.0);
-            node.update(input, urgency, self.system_temperature, &current_states);
+            node.apply_plasticity(i, &states, rate, min_weight, max_weight);
         }
     }
 
@@ -690,6 +2657,16 @@ impl Singularity {
         }
     }
 
+    /// アクションの担当ビンの位相コヒーレンス（蔵本の秩序変数, 0.0〜1.0）
+    /// Rhyd と違い振幅を含まないため、位相の結晶化だけを見るのに使う
+    pub fn phase_coherence(&self, action: usize) -> f32 {
+        if action >= self.action_size { return 0.0; }
+        match self.sharded_mwso {
+            Some(ref sharded) => sharded.action_phase_coherence(action),
+            None => self.mwso.action_phase_coherence(action, self.action_size),
+        }
+    }
+
     pub fn calculate_current_ipr(&self) -> f32 {
         if let Some(ref sharded) = self.sharded_mwso {
             sharded.calculate_ipr()
@@ -698,9 +2675,35 @@ impl Singularity {
         }
     }
 
+    /// 直近の意思決定の確信度 (0.0〜1.0)
+    /// 1位と2位のスコア差と、現在の共鳴密度(Rhyd)から算出する。未決定なら 0.0
+    pub fn decision_confidence(&self) -> f32 {
+        let Some(gap) = self.last_score_gap else { return 0.0; };
+        let gap_term = 1.0 - (-gap.max(0.0) * 2.0).exp();
+        let rhyd = self.get_resonance_density();
+        let resonance_term = rhyd / (rhyd + 5.0);
+        (gap_term * (0.5 + 0.5 * resonance_term)).clamp(0.0, 1.0)
+    }
+
+    /// 波動エントロピーに基づく不確実性 (0.0〜1.0)
+    /// 高い時は呼び出し側でスクリプト挙動へフォールバックすることを想定
+    pub fn decision_uncertainty(&self) -> f32 {
+        if let Some(ref sharded) = self.sharded_mwso {
+            sharded.wave_entropy()
+        } else {
+            self.mwso.wave_entropy()
+        }
+    }
+
     /// 逆強化学習: 行動から動機を逆算する
     /// エキスパートの行動を観測し、それを引き起こす「ハミルトニアン場（動機）」を内省的に生成する
     pub fn observe_expert(&mut self, state_idx: usize, expert_actions: &[usize], strength: f32) {
+        self.note_unrecorded();
+        self.imitate_expert(state_idx, expert_actions, strength);
+    }
+
+    /// observe_expert の本体（learn の自動IRL注入からも使う）
+    fn imitate_expert(&mut self, state_idx: usize, expert_actions: &[usize], strength: f32) {
         // 1. 位相の同調（模倣位相ロック）
         for &action in expert_actions {
             if let Some(ref mut sharded) = self.sharded_mwso {
@@ -716,21 +2719,17 @@ impl Singularity {
             for &action in expert_actions {
                 // すでに類似のルールがあるか確認し、あれば強化、なければ新設
                 if let Some(rule) = self.bootstrapper.rules.iter_mut()
-                    .find(|r| r.condition_id == state_idx as i32 && r.target_action == action) {
+                    .find(|r| r.condition_id == state_idx as i32 && r.target_action == action && r.span == 1) {
                     rule.strength = (rule.strength + 0.1 * strength).min(10.0);
+                    rule.age = 0;
                 } else {
-                    self.bootstrapper.add_hamiltonian_rule(state_idx as i32, action, 0.5 * strength);
+                    self.bootstrapper.add_expiring_rule(state_idx as i32, action, 0.5 * strength);
                 }
 
                 // 観測された状態・行動ペアに対するペナルティを劇的に減少させる
-                let penalty_dim = self.penalty_matrix.len() / self.state_size;
-                let bin_per_action = penalty_dim / self.action_size;
-                let start = state_idx * self.penalty_dim + action * bin_per_action;
-                for j in 0..bin_per_action {
-                    if start + j < self.penalty_matrix.len() {
-                        self.penalty_matrix[start + j] *= 0.5;
-                    }
-                }
+                self.settle_penalty_row(state_idx);
+                let cells = self.penalty_cells(state_idx, action);
+                self.update_penalty_cells(cells, |p| p * 0.5);
             }
         }
 
@@ -746,6 +2745,12 @@ impl Singularity {
 
     /// 逆強化学習: 負のフィードバックから行動を抑制する
     pub fn suppress_expert(&mut self, bad_actions: &[usize], strength: f32) {
+        self.note_unrecorded();
+        self.inhibit_actions(bad_actions, strength);
+    }
+
+    /// suppress_expert の本体（learn の自動IRL注入からも使う）
+    fn inhibit_actions(&mut self, bad_actions: &[usize], strength: f32) {
         for &action in bad_actions {
             if let Some(ref mut sharded) = self.sharded_mwso {
                 sharded.suppress_action(action, strength);
@@ -755,141 +2760,977 @@ impl Singularity {
         }
     }
 
+    /// 重力場の要約（シャーディング時は全シャードをまとめる）
+    pub fn gravity_stats(&self, threshold: f32) -> GravityStats {
+        match self.sharded_mwso {
+            Some(ref sharded) => sharded.gravity_stats(threshold),
+            None => self.mwso.gravity_stats(threshold),
+        }
+    }
+
+    /// アクションにかかる重力（担当ビンの平均）
+    pub fn get_gravity_for_action(&self, action: usize) -> f32 {
+        if action >= self.action_size { return 0.0; }
+        match self.sharded_mwso {
+            Some(ref sharded) => sharded.gravity_for_action(action),
+            None => self.mwso.gravity_for_action(action, self.action_size),
+        }
+    }
+
+    /// アクションの重力を削り、ブラックホール化した行動を不安定化する
+    pub fn erode_gravity(&mut self, action: usize, amount: f32) {
+        if action >= self.action_size { return; }
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            sharded.erode_gravity(action, amount);
+        } else {
+            self.mwso.erode_gravity(action, amount, self.action_size);
+        }
+    }
+
+    /// 性格プリセットを適用した状態で生成する
+    pub fn with_personality(state_size: usize, category_sizes: Vec<usize>, personality: Personality) -> Self {
+        let mut singularity = Self::new(state_size, category_sizes);
+        singularity.set_personality(personality);
+        singularity
+    }
+
+    /// 性格プリセットを実行中に切り替える（ノードの減衰率と状態・温度・探索戦略を一括で設定する）
+    /// プリセットの既定名のノードが構成に無ければ、そのノードの設定は飛ばす
+    pub fn set_personality(&mut self, personality: Personality) {
+        let profile = personality.profile();
+        for (name, decay, state) in profile.nodes {
+            if let Some(idx) = self.node_index(name) {
+                self.nodes[idx].base_decay = decay;
+                self.nodes[idx].state = state;
+            }
+        }
+        self.config.temperature_floor = profile.temperature_floor;
+        self.config.temperature_ceiling = profile.temperature_ceiling;
+        self.config.temp_frustration_weight = profile.temp_frustration_weight;
+        self.system_temperature = profile.temperature.clamp(profile.temperature_floor, profile.temperature_ceiling);
+        self.config.exploration_policy = profile.exploration_policy;
+        self.exploration_beta = profile.exploration_beta;
+        self.personality = personality;
+        // 次の learn で新しい感情状態に合わせて結合を組み直させる
+        self.last_topology_update_temp = -1.0;
+    }
+
+    /// 神経修飾物質による学習率・疲労・探索ノイズの調整を有効化する（平常値から始める）
+    pub fn enable_neuromodulators(&mut self) {
+        self.neuromodulators = Some(Neuromodulators::new());
+    }
+
+    /// 敵の行動空間が自分と異なる場合に、相手モデルを明示的に初期化する
+    pub fn enable_opponent_model(&mut self, opponent_action_size: usize) {
+        self.opponent_model = Some(OpponentModel::new(opponent_action_size, self.action_size));
+    }
+
+    /// 敵の (状態, 行動) を観測し、相手モデルへ取り込む
+    /// モデルが未初期化なら自分と同じ行動空間を持つ相手として生成する
+    pub fn observe_opponent(&mut self, state_idx: usize, opponent_action: usize) {
+        let action_size = self.action_size;
+        self.opponent_model
+            .get_or_insert_with(|| OpponentModel::new(action_size, action_size))
+            .observe(state_idx, opponent_action);
+    }
+
+    /// ペナルティ場の1行のうち、行動 action が受け持つ範囲
+    /// 非シャード時は MWSO と同じ対応を使い、シャード時はペナルティ場を等分する
+    fn penalty_bins(&self, action: usize) -> std::ops::Range<usize> {
+        match self.sharded_mwso {
+            Some(_) => ActionLayout::uniform_range(self.penalty_dim, self.action_size, action),
+            None => self.mwso.action_bins(action, self.action_size),
+        }
+    }
+
+    /// penalty_matrix 上の (状態, 行動) の範囲
+    fn penalty_cells(&self, state_idx: usize, action: usize) -> std::ops::Range<usize> {
+        let bins = self.penalty_bins(action);
+        let row_start = state_idx * self.penalty_dim;
+        row_start + bins.start..row_start + bins.end
+    }
+
+    /// 行動ごとのビン数を重みの比で割り当てる（重要な行動により多くの波の容量を与える）
+    /// 波・スコア・ペナルティ・ワームホールのすべてがこの対応を使う
+    /// シャード化された構成では各シャードの行動数が固定のため使えず、false を返す
+    /// 学習済みの波は旧い対応に合わせて形成されているため、切り替えは学習の開始前に行う
+    pub fn set_action_weights(&mut self, weights: &[f32]) -> bool {
+        if self.sharded_mwso.is_some() || weights.len() != self.action_size { return false; }
+        match ActionLayout::weighted(self.mwso.dim, weights) {
+            Some(layout) => self.mwso.set_action_layout(Some(layout)),
+            None => false,
+        }
+    }
+
+    /// 行動ごとのビン数（set_action_weights を使っていなければ等分）
+    pub fn action_widths(&self) -> Vec<usize> {
+        (0..self.action_size).map(|a| self.penalty_bins(a).len()).collect()
+    }
+
+    /// 状態 state_idx を見たことがあるか、そのとき何をしたかを問い合わせる（波の状態は変えない）
+    pub fn recall_state(&self, state_idx: usize) -> StateRecall {
+        let wave = match self.sharded_mwso {
+            Some(ref sharded) => sharded.shards.iter()
+                .map(|shard| {
+                    let (query_re, query_im) = shard.state_query(state_idx);
+                    shard.recall(&query_re, &query_im)
+                })
+                .max_by(|a, b| a.resonance.total_cmp(&b.resonance))
+                .unwrap_or_default(),
+            None => {
+                let (query_re, query_im) = self.mwso.state_query(state_idx);
+                self.mwso.recall(&query_re, &query_im)
+            }
+        };
+        let actions = self.symmetry.stats.get(&state_idx)
+            .map(|row| row.iter().map(|&(sum, count)| (if count > 0 { sum / count as f32 } else { 0.0 }, count)).collect())
+            .unwrap_or_else(|| vec![(0.0, 0); self.action_size]);
+        StateRecall { wave, actions }
+    }
+
+    /// 現在の波で状態 state_idx を記憶波へ刻み込む。label を付けると resonant_memories で報告される
+    /// シャード構成では全シャードに刻む
+    pub fn imprint_memory(&mut self, state_idx: usize, reward: f32, label: Option<&str>) {
+        self.note_unrecorded();
+        match self.sharded_mwso {
+            Some(ref mut sharded) => {
+                for shard in &mut sharded.shards { shard.imprint_memory(state_idx, reward, label); }
+            }
+            None => self.mwso.imprint_memory(state_idx, reward, label),
+        }
+    }
+
+    /// 現在の波と min_resonance 以上に共鳴しているラベル付きの記憶（共鳴の高い順）
+    /// 行動選択の直後に呼ぶと、その判断でどの記憶が響いていたかが分かる
+    /// シャード構成ではラベルごとに最も共鳴したシャードの値
+    pub fn resonant_memories(&self, min_resonance: f32) -> Vec<(String, f32)> {
+        let mut resonances = match self.sharded_mwso {
+            Some(ref sharded) => {
+                let mut best: HashMap<String, f32> = HashMap::new();
+                for (label, r) in sharded.shards.iter().flat_map(|shard| shard.tag_resonances()) {
+                    let entry = best.entry(label).or_insert(f32::NEG_INFINITY);
+                    *entry = entry.max(r);
+                }
+                let mut merged: Vec<(String, f32)> = best.into_iter().collect();
+                merged.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                merged
+            }
+            None => self.mwso.tag_resonances(),
+        };
+        resonances.retain(|(_, r)| *r >= min_resonance);
+        resonances
+    }
+
+    /// 状態 state_idx の記憶の取り出し SNR（シャード構成では各シャードの最小値）
+    pub fn retrieval_snr(&self, state_idx: usize) -> f32 {
+        match self.sharded_mwso {
+            Some(ref sharded) => sharded.shards.iter()
+                .map(|shard| shard.retrieval_snr(state_idx))
+                .fold(f32::INFINITY, f32::min),
+            None => self.mwso.retrieval_snr(state_idx),
+        }
+    }
+
+    /// 波動場の次元を new_dim へ広げる（シャード構成・縮小は不可）
+    /// 波と記憶は MWSO::resize で移し、ペナルティ場は行動ごとの平均を新しい範囲へ広げる
+    pub fn grow_dimension(&mut self, new_dim: usize) -> bool {
+        if self.sharded_mwso.is_some() || new_dim <= self.mwso.dim { return false; }
+        self.settle_penalties();
+        // 行の組み替えは f32 で行い、最後に元の精度へ戻す
+        let precision = self.penalty_precision();
+        self.set_penalty_storage(FieldPrecision::F32);
+        let rows = self.penalty_rows();
+        let old_dim = self.penalty_dim;
+        let old_bins: Vec<_> = (0..self.action_size).map(|a| self.penalty_bins(a)).collect();
+
+        self.mwso.resize(new_dim);
+        let mut matrix = vec![0.0; rows * new_dim];
+        for row in 0..rows {
+            let old_row = &self.penalty_matrix[row * old_dim..(row + 1) * old_dim];
+            let new_row = &mut matrix[row * new_dim..(row + 1) * new_dim];
+            for (a, bins) in old_bins.iter().enumerate() {
+                let mean = old_row[bins.clone()].iter().sum::<f32>() / bins.len().max(1) as f32;
+                new_row[self.mwso.action_bins(a, self.action_size)].fill(mean);
+            }
+        }
+        self.penalty_matrix = matrix;
+        self.penalty_dim = new_dim;
+        self.empty_penalty = vec![0.0; new_dim];
+        self.set_penalty_storage(precision);
+        true
+    }
+
+    /// auto_grow_interval 回の learn ごとに取り出し SNR を調べ、閾値を下回っていれば次元を倍にする
+    fn maybe_grow_dimension(&mut self, state_idx: usize) {
+        let cfg = &self.config;
+        if cfg.auto_grow_snr <= 0.0 || cfg.auto_grow_interval == 0 || !self.rule_clock.is_multiple_of(cfg.auto_grow_interval) {
+            return;
+        }
+        let new_dim = (self.mwso.dim * 2).min(cfg.auto_grow_max_dim);
+        if self.sharded_mwso.is_some() || new_dim <= self.mwso.dim { return; }
+        if self.mwso.retrieval_snr(state_idx) < cfg.auto_grow_snr {
+            self.grow_dimension(new_dim);
+        }
+    }
+
+    /// 指定状態のペナルティ場をアクション単位（各ビンの平均）で取得する
+    pub fn get_penalties(&self, state_idx: usize) -> Vec<f32> {
+        if state_idx >= self.penalty_rows() { return Vec::new(); }
+        let row = self.penalty_row_values(state_idx);
+        let decay = self.pending_penalty_decay(state_idx);
+        (0..self.action_size)
+            .map(|a| {
+                let bins = &row[self.penalty_bins(a)];
+                bins.iter().sum::<f32>() * decay / bins.len().max(1) as f32
+            })
+            .collect()
+    }
+
+    /// 指定状態・アクションのペナルティを直接書き換える（シナリオ調整や汚染されたペナルティの除去用）
+    pub fn set_penalty(&mut self, state_idx: usize, action_idx: usize, value: f32) {
+        if state_idx >= self.penalty_rows() || action_idx >= self.action_size { return; }
+        let cells = self.penalty_cells(state_idx, action_idx);
+        self.settle_penalty_row(state_idx);
+        self.update_penalty_cells(cells, |_| value.max(0.0));
+    }
+
+    /// 行動の担当ビンの先頭どうしをワームホールで結ぶ
     pub fn add_wormhole(&mut self, from_action: usize, to_action: usize, strength: f32) {
-        let bin_per_action = self.mwso.dim / self.action_size;
-        let from_idx = from_action * bin_per_action;
-        let to_idx = to_action * bin_per_action;
+        let from_idx = self.mwso.action_bins(from_action, self.action_size).start;
+        let to_idx = self.mwso.action_bins(to_action, self.action_size).start;
         self.mwso.add_wormhole(from_idx, to_idx, strength);
     }
 
+    /// 他のエージェントの波のうち actions の担当ビンを、自分の同じ行動のビンへ strength の割合で引き寄せる
+    /// （分隊の仲間と「勘」を共有する。毎ステップ呼ぶ想定で、actions が空なら共通する全行動）
+    pub fn entangle_with(&mut self, other: &Singularity, actions: &[usize], strength: f32) {
+        let shared = self.action_size.min(other.action_size);
+        let all: Vec<usize>;
+        let actions = if actions.is_empty() {
+            all = (0..shared).collect();
+            &all
+        } else {
+            actions
+        };
+        for &action in actions.iter().filter(|&&a| a < shared) {
+            let (src, src_bins) = other.action_wave(action);
+            let (dst, dst_bins) = self.action_wave_mut(action);
+            dst.entangle_bins_with(dst_bins, src, src_bins, strength);
+        }
+    }
+
+    /// actions の担当ビンに振幅 1 の場を置き、duration ステップの間 step_core ごとに自動で注入する
+    /// strength が負ならそのビンの波を |strength| の割合で毎ステップ減衰させる（例: 拡張系の行動を 200 tick 抑える）
+    pub fn schedule_action_field(&mut self, actions: &[usize], strength: f32, duration: u32, decay: FieldDecay) -> u64 {
+        let id = self.next_field_id;
+        self.next_field_id += 1;
+        // 担当する MWSO（シャード）ごとに 1 つの場へまとめる
+        let mut fields: Vec<(usize, Vec<f32>)> = Vec::new();
+        for &action in actions.iter().filter(|&&a| a < self.action_size) {
+            let key = self.sharded_mwso.as_ref().map_or(0, |s| s.shard_for_action(action).0);
+            let (mwso, bins) = self.action_wave(action);
+            let slot = match fields.iter().position(|(k, _)| *k == key) {
+                Some(slot) => slot,
+                None => {
+                    fields.push((key, vec![0.0; mwso.dim]));
+                    fields.len() - 1
+                }
+            };
+            fields[slot].1[bins].fill(1.0);
+        }
+        for (key, field) in fields {
+            let imag = vec![0.0; field.len()];
+            let mwso = match self.sharded_mwso.as_mut() {
+                Some(sharded) => &mut sharded.shards[key],
+                None => &mut self.mwso,
+            };
+            mwso.field_schedule.schedule_as(id, &field, &imag, strength, duration, decay);
+        }
+        id
+    }
+
+    /// schedule_action_field で予約した場を取り消す（見つかれば true）
+    pub fn cancel_scheduled_field(&mut self, id: u64) -> bool {
+        let mut found = self.mwso.field_schedule.cancel(id);
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            for shard in &mut sharded.shards { found |= shard.field_schedule.cancel(id); }
+        }
+        found
+    }
+
+    /// 行動 action を担当する MWSO（シャード構成ではそのシャード）と、その中のビン範囲
+    fn action_wave(&self, action: usize) -> (&MWSO, std::ops::Range<usize>) {
+        match &self.sharded_mwso {
+            Some(sharded) => {
+                let (shard, local) = sharded.shard_for_action(action);
+                let shard_mwso = &sharded.shards[shard];
+                (shard_mwso, shard_mwso.action_bins(local, sharded.actions_per_shard))
+            }
+            None => (&self.mwso, self.mwso.action_bins(action, self.action_size)),
+        }
+    }
+
+    fn action_wave_mut(&mut self, action: usize) -> (&mut MWSO, std::ops::Range<usize>) {
+        match &mut self.sharded_mwso {
+            Some(sharded) => {
+                let (shard, local) = sharded.shard_for_action(action);
+                let per_shard = sharded.actions_per_shard;
+                let shard_mwso = &mut sharded.shards[shard];
+                let bins = shard_mwso.action_bins(local, per_shard);
+                (shard_mwso, bins)
+            }
+            None => {
+                let bins = self.mwso.action_bins(action, self.action_size);
+                (&mut self.mwso, bins)
+            }
+        }
+    }
+
+    /// MWSO のワームホール一覧（ビン単位の (from, to, strength)）
+    pub fn list_wormholes(&self) -> &[(usize, usize, f32)] {
+        self.mwso.list_wormholes()
+    }
+
+    /// add_wormhole で張った行動間のワームホールを取り除く
+    pub fn remove_wormhole(&mut self, from_action: usize, to_action: usize) -> bool {
+        let from_idx = self.mwso.action_bins(from_action, self.action_size).start;
+        let to_idx = self.mwso.action_bins(to_action, self.action_size).start;
+        self.mwso.remove_wormhole(from_idx, to_idx)
+    }
+
+    /// 全ワームホール（シャードを含む）を減衰させ、取り除いた数を返す
+    pub fn decay_wormholes(&mut self, factor: f32) -> usize {
+        let mut removed = self.mwso.decay_wormholes(factor);
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            removed += sharded.shards.iter_mut().map(|shard| shard.decay_wormholes(factor)).sum::<usize>();
+        }
+        removed
+    }
+
+    /// ワームホール数の上限（MWSO・各シャードごと）
+    pub fn set_wormhole_cap(&mut self, cap: usize) {
+        self.mwso.set_wormhole_cap(cap);
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            for shard in &mut sharded.shards { shard.set_wormhole_cap(cap); }
+        }
+    }
+
+    /// 状態を波へ注入するときの状態コード（MWSO・各シャード共通）
+    /// 学習済みの波は旧コードに合わせて形成されているため、切り替えは学習の開始前に行う
+    pub fn set_state_coding(&mut self, coding: StateCoding) {
+        self.mwso.set_state_coding(coding);
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            for shard in &mut sharded.shards { shard.set_state_coding(coding); }
+        }
+    }
+
+    /// 探索ノイズの分布（MWSO・各シャード共通）
+    pub fn set_exploration_noise(&mut self, mode: ExplorationNoise) {
+        self.mwso.set_exploration_noise(mode);
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            for shard in &mut sharded.shards { shard.set_exploration_noise(mode); }
+        }
+    }
+
+    /// 行動スコアに記憶波との干渉項を重み weight で加える（MWSO・各シャード共通、0 で無効）
+    pub fn set_interference_scoring(&mut self, weight: f32) {
+        self.mwso.interference_weight = weight;
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            for shard in &mut sharded.shards { shard.interference_weight = weight; }
+        }
+    }
+
+    /// 重力場の形成・蒸発のパラメータを設定する（MWSO・スカウト・各シャード共通）
+    pub fn set_mwso_config(&mut self, config: MWSOConfig) {
+        self.mwso.config = config;
+        self.scout_mwso.config = config;
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            for shard in &mut sharded.shards { shard.config = config; }
+        }
+    }
+
+    /// 隣接ビン間の結合項を切り替える（MWSO・各シャード共通）
+    pub fn set_coupling(&mut self, coupling: Coupling) {
+        self.mwso.coupling = coupling;
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            for shard in &mut sharded.shards { shard.coupling = coupling; }
+        }
+    }
+
+    /// 行動スコアを粗い推定の上位 k 件だけ正確に求める（MWSO・各シャード共通、シャードでは担当範囲ごとに k 件、0 で全件）
+    pub fn set_score_top_k(&mut self, k: usize) {
+        self.mwso.score_top_k = k;
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            for shard in &mut sharded.shards { shard.score_top_k = k; }
+        }
+    }
+
+    /// 波の時間発展の積分法を切り替える（config.integrator を更新し、MWSO・スカウト・各シャードへ反映する）
+    pub fn set_integrator(&mut self, integrator: Integrator) {
+        self.config.integrator = integrator;
+        self.mwso.integrator = integrator;
+        self.scout_mwso.integrator = integrator;
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            for shard in &mut sharded.shards { shard.integrator = integrator; }
+        }
+    }
+
+    /// 固有振動数の並べ方（MWSO・各シャード共通）
+    pub fn set_spectrum(&mut self, spectrum: Spectrum) {
+        self.mwso.set_spectrum(spectrum);
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            for shard in &mut sharded.shards { shard.set_spectrum(spectrum); }
+        }
+    }
+
+    /// 固有振動数を任意の配列で置き換える。長さは MWSO（シャード構成では各シャード）の次元と同じであること
+    pub fn set_frequencies(&mut self, frequencies: &[f32]) -> bool {
+        if frequencies.iter().any(|f| !f.is_finite()) { return false; }
+        match self.sharded_mwso {
+            Some(ref mut sharded) => {
+                if sharded.shards.iter().any(|s| s.dim != frequencies.len()) { return false; }
+                for shard in &mut sharded.shards { shard.frequencies.copy_from_slice(frequencies); }
+            }
+            None => {
+                if self.mwso.dim != frequencies.len() { return false; }
+                self.mwso.frequencies.copy_from_slice(frequencies);
+            }
+        }
+        true
+    }
+
+    /// 記憶の統合で再生するパターンの保持数（MWSO・各シャード共通、0 で記録しない）
+    pub fn set_replay_capacity(&mut self, capacity: usize) {
+        self.mwso.replay.set_capacity(capacity);
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            for shard in &mut sharded.shards { shard.replay.set_capacity(capacity); }
+        }
+    }
+
+    /// 記憶波の忘却曲線の半減期（step_core の回数、MWSO・各シャード共通、0 で無効）
+    pub fn set_memory_half_life(&mut self, half_life: f32) {
+        let half_life = half_life.max(0.0);
+        self.mwso.memory_half_life = half_life;
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            for shard in &mut sharded.shards { shard.memory_half_life = half_life; }
+        }
+    }
+
+    /// 記憶の統合を 1 回行い、再生したパターン数を返す（MWSO::consolidate）
+    pub fn consolidate_memory(&mut self) -> usize {
+        let decay = self.config.consolidate_decay;
+        let mut replayed = self.mwso.consolidate(decay);
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            replayed += sharded.shards.iter_mut().map(|shard| shard.consolidate(decay)).sum::<usize>();
+        }
+        replayed
+    }
+
     pub fn save_to_file(&self, path: &str) -> io::Result<()> {
-        let mut file = File::create(path)?;
-        file.write_all(b"DSYM")?;
-        file.write_all(&14u32.to_le_bytes())?; 
-        file.write_all(&(self.state_size as u32).to_le_bytes())?;
-        file.write_all(&self.system_temperature.to_le_bytes())?;
-        file.write_all(&(if self.temperature_locked { 1u32 } else { 0u32 }).to_le_bytes())?;
-        file.write_all(&self.adrenaline.to_le_bytes())?;
-        file.write_all(&self.frustration.to_le_bytes())?;
-        file.write_all(&self.velocity_trust.to_le_bytes())?;
-        file.write_all(&self.morale.to_le_bytes())?;
-        file.write_all(&self.patience.to_le_bytes())?;
-        file.write_all(&self.exploration_beta.to_le_bytes())?;
-        for f in &self.fatigue_map { file.write_all(&f.to_le_bytes())?; }
-        for m in &self.action_momentum { file.write_all(&m.to_le_bytes())?; }
-        for g in &self.mwso.gravity_field { file.write_all(&g.to_le_bytes())?; }
+        let started = Instant::now();
+        let result = self.write_to_file(path);
+        self.stats_mut().save_to_file.record(started.elapsed());
+        result
+    }
+
+    fn write_to_file(&self, path: &str) -> io::Result<()> {
+        let mut w = DsymWriter::new(File::create(path)?);
+        w.write_header(self.state_size)?;
+
+        w.section(dsym::SECTION_EMOTION)?;
+        w.write_f32(self.system_temperature)?;
+        w.write_u32(if self.temperature_locked { 1 } else { 0 })?;
+        w.write_f32(self.adrenaline)?;
+        w.write_f32(self.frustration)?;
+        w.write_f32(self.velocity_trust)?;
+        w.write_f32(self.morale)?;
+        w.write_f32(self.patience)?;
+        w.write_f32(self.exploration_beta)?;
+
+        w.section(dsym::SECTION_FIELDS)?;
+        w.write_f32_slice(&self.fatigue_map)?;
+        w.write_f32_slice(&self.action_momentum)?;
+        w.write_u32(self.mwso.dim as u32)?;
+        w.write_f32_slice(&self.mwso.gravity_values())?;
         
         // input_history の保存
-        file.write_all(&(self.input_history.len() as u32).to_le_bytes())?;
-        for &s in &self.input_history { file.write_all(&(s as u32).to_le_bytes())?; }
+        w.section(dsym::SECTION_HISTORY)?;
+        w.write_u32(self.input_history.len() as u32)?;
+        for &s in &self.input_history { w.write_u32(s as u32)?; }
         
-        file.write_all(&(self.category_sizes.len() as u32).to_le_bytes())?;
-        for &s in &self.category_sizes { file.write_all(&(s as u32).to_le_bytes())?; }
-        file.write_all(&(self.nodes.len() as u32).to_le_bytes())?;
+        w.section(dsym::SECTION_CATEGORIES)?;
+        w.write_u32(self.category_sizes.len() as u32)?;
+        for &s in &self.category_sizes { w.write_u32(s as u32)?; }
+
+        w.section(dsym::SECTION_NODES)?;
+        w.write_u32(self.nodes.len() as u32)?;
         for node in &self.nodes {
-            file.write_all(&node.state.to_le_bytes())?;
-            file.write_all(&node.base_decay.to_le_bytes())?;
-        }
-        file.write_all(&(self.learned_rules.len() as u32).to_le_bytes())?;
-        for &(s, a, count) in &self.learned_rules {
-            file.write_all(&(s as u32).to_le_bytes())?;
-            file.write_all(&(a as u32).to_le_bytes())?;
-            file.write_all(&(count as u32).to_le_bytes())?;
-        }
-        file.write_all(&(self.mwso.dim as u32).to_le_bytes())?;
-        for &f in &self.mwso.psi_real { file.write_all(&f.to_le_bytes())?; }
-        for &f in &self.mwso.psi_imag { file.write_all(&f.to_le_bytes())?; }
-        file.write_all(&(self.mwso.theta.len() as u32).to_le_bytes())?;
-        for &f in &self.mwso.theta { file.write_all(&f.to_le_bytes())?; }
-        Ok(())
+            w.write_f32(node.state)?;
+            w.write_f32(node.base_decay)?;
+            w.write_str(&node.name)?;
+            let (activation, cap) = node.activation.to_id();
+            w.write_i32(activation)?;
+            w.write_f32(cap)?;
+            w.write_u32(node.synapses.len() as u32)?;
+            for synapse in &node.synapses {
+                w.write_u32(synapse.target_id as u32)?;
+                w.write_f32(synapse.weight)?;
+            }
+        }
+
+        w.section(dsym::SECTION_RULES)?;
+        w.write_u32(self.learned_rules.len() as u32)?;
+        for rule in &self.learned_rules {
+            w.write_u32(rule.state as u32)?;
+            w.write_u32(rule.action as u32)?;
+            w.write_u32(rule.count as u32)?;
+        }
+
+        w.section(dsym::SECTION_WAVE)?;
+        w.write_u32(self.mwso.dim as u32)?;
+        w.write_wave_slice(&self.mwso.psi_real)?;
+        w.write_wave_slice(&self.mwso.psi_imag)?;
+        w.write_u32(self.mwso.theta.len() as u32)?;
+        w.write_wave_slice(&self.mwso.theta)?;
+
+        // 記憶波（全バンク）は f64 のまま保存する
+        w.section(dsym::SECTION_MEMORY)?;
+        w.write_u32(self.mwso.dim as u32)?;
+        let banks = self.mwso.memory_banks();
+        w.write_u32(banks.len() as u32)?;
+        w.write_u32(self.mwso.active_memory_bank() as u32)?;
+        w.write_u32(self.mwso.bank_routing.id() as u32)?;
+        for (i, bank) in banks.iter().enumerate() {
+            w.write_str(&bank.label)?;
+            w.write_u64(bank.imprints)?;
+            for values in self.mwso.bank_memory(i) { w.write_f64_slice(values)?; }
+        }
+
+        w.section(dsym::SECTION_WORMHOLES)?;
+        w.write_u32(self.mwso.wormhole_cap.min(u32::MAX as usize) as u32)?;
+        w.write_u32(self.mwso.entanglements.len() as u32)?;
+        for &(from, to, strength) in &self.mwso.entanglements {
+            w.write_u32(from as u32)?;
+            w.write_u32(to as u32)?;
+            w.write_f32(strength)?;
+        }
+
+        // 知識層: 複合条件は前置記法の int 列（長さ 0 なら condition_id だけで発動）
+        w.section(dsym::SECTION_KNOWLEDGE)?;
+        w.write_u32(self.bootstrapper.policy.id() as u32)?;
+        w.write_u32(self.bootstrapper.rules.len() as u32)?;
+        for rule in &self.bootstrapper.rules {
+            w.write_i32(rule.condition_id)?;
+            w.write_u32(rule.target_action as u32)?;
+            w.write_u32(rule.span as u32)?;
+            w.write_f32(rule.strength)?;
+            w.write_i32(rule.priority)?;
+            w.write_f32(rule.decay)?;
+            w.write_u32(if rule.expires { 1 } else { 0 })?;
+            w.write_u64(rule.age)?;
+            w.write_u64(rule.uses)?;
+            w.write_u32(if rule.crystallized { 1 } else { 0 })?;
+            let codes = rule.condition.as_ref().map(Condition::encode_prefix).unwrap_or_default();
+            w.write_u32(codes.len() as u32)?;
+            for code in codes { w.write_i32(code)?; }
+        }
+        w.write_u32(self.bootstrapper.templates.len() as u32)?;
+        for template in &self.bootstrapper.templates {
+            w.write_f32(template.strength)?;
+            for codes in [template.condition.encode_prefix(), template.action.encode_prefix()] {
+                w.write_u32(codes.len() as u32)?;
+                for code in codes { w.write_i32(code)?; }
+            }
+        }
+
+        // コールドストレージへ退避済みの状態（ディレクトリを失っても知識が残るよう本体にも含める）
+        w.section(dsym::SECTION_ARCHIVE)?;
+        let archived = self.cold_archive.as_ref().map(ColdArchive::states).unwrap_or_default();
+        w.write_u32(archived.len() as u32)?;
+        if let Some(ref archive) = self.cold_archive {
+            for state in archived {
+                let data = archive.read(state, self.rule_clock)?.unwrap_or_default();
+                w.write_u32(state as u32)?;
+                data.write_to(&mut w)?;
+            }
+        }
+
+        // 状態コード（波の形は注入時のコードに合わせて形成されているため、読み込み時に同じコードへ戻す）
+        w.section(dsym::SECTION_STATE_CODING)?;
+        w.write_i32(self.mwso.state_coding().id())?;
+
+        // 行動ごとのビン数（0 個なら等分）
+        w.section(dsym::SECTION_ACTION_LAYOUT)?;
+        let widths = self.mwso.action_layout().map(ActionLayout::widths).unwrap_or_default();
+        w.write_u32(widths.len() as u32)?;
+        for width in widths { w.write_u32(width as u32)?; }
+
+        // 承認済みの状態統合（状態 -> 代表状態、状態順）
+        w.section(dsym::SECTION_STATE_ALIASES)?;
+        let mut aliases: Vec<(usize, usize)> = self.state_aliases.iter().map(|(&s, &r)| (s, r)).collect();
+        aliases.sort_unstable();
+        w.write_u32(aliases.len() as u32)?;
+        for (state, representative) in aliases {
+            w.write_u32(state as u32)?;
+            w.write_u32(representative as u32)?;
+        }
+        w.finish()
     }
 
     pub fn load_from_file(&mut self, path: &str) -> io::Result<()> {
-        let mut file = File::open(path)?;
-        let mut buf = Vec::new();
-        file.read_to_end(&mut buf)?;
-        let mut cur = 0;
-        let read_u32 = |p: &mut usize| -> u32 { let v = u32::from_le_bytes(buf[*p..*p+4].try_into().unwrap()); *p+=4; v };
-        let read_f32 = |p: &mut usize| -> f32 { let v = f32::from_le_bytes(buf[*p..*p+4].try_into().unwrap()); *p+=4; v };
-        
-        if &buf[0..4] != b"DSYM" { return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid Header")); }
-        cur += 4;
-        let version = read_u32(&mut cur);
-        let saved_state_size = read_u32(&mut cur) as usize;
+        self.load_from_file_with_progress(path, &mut |_, _| {})
+    }
+
+    /// ストリーミング読み込み: ファイル全体をメモリに展開せず、各セクションを順に読み込む
+    /// progress には (読み込み済みバイト数, 総バイト数) がセクション境界および約1MBごとに通知される
+    pub fn load_from_file_with_progress(&mut self, path: &str, progress: &mut dyn FnMut(u64, u64)) -> io::Result<()> {
+        let file = File::open(path)?;
+        let total_bytes = file.metadata()?.len();
+        let mut r = DsymReader::open(file, total_bytes, progress)?;
+        let version = r.version;
+
+        let saved_state_size = r.read_u32()? as usize;
         if saved_state_size != self.state_size {
             return Err(io::Error::new(io::ErrorKind::InvalidData, "state_size mismatch"));
         }
 
-        self.system_temperature = read_f32(&mut cur);
+        r.expect_section(dsym::SECTION_EMOTION)?;
+        self.system_temperature = r.read_f32()?;
         if version >= 13 {
-            self.temperature_locked = read_u32(&mut cur) != 0;
+            self.temperature_locked = r.read_u32()? != 0;
         } else {
             self.temperature_locked = false;
         }
-        self.adrenaline = read_f32(&mut cur);
-        self.frustration = read_f32(&mut cur);
-        self.velocity_trust = read_f32(&mut cur);
-        self.morale = read_f32(&mut cur);
-        self.patience = read_f32(&mut cur);
-        self.exploration_beta = read_f32(&mut cur);
+        self.adrenaline = r.read_f32()?;
+        self.frustration = r.read_f32()?;
+        self.velocity_trust = r.read_f32()?;
+        self.morale = r.read_f32()?;
+        self.patience = r.read_f32()?;
+        self.exploration_beta = r.read_f32()?;
         if version < 14 {
-            let _ = read_f32(&mut cur); // Skip glutamate_buffer in old versions
+            let _ = r.read_f32()?; // Skip glutamate_buffer in old versions
         }
         
-        for f in &mut self.fatigue_map { *f = read_f32(&mut cur); }
-        for m in &mut self.action_momentum { *m = read_f32(&mut cur); }
-        for g in &mut self.mwso.gravity_field { *g = read_f32(&mut cur); }
+        r.expect_section(dsym::SECTION_FIELDS)?;
+        r.read_f32_into(&mut self.fatigue_map)?;
+        r.read_f32_into(&mut self.action_momentum)?;
+        // 次元が拡張されたモデルは、以降の波・記憶を読めるよう先に同じ次元まで広げる
+        let field_dim = if version >= dsym::DIM_GROWTH_VERSION { r.read_u32()? as usize } else { self.mwso.dim };
+        if field_dim > self.mwso.dim { self.grow_dimension(field_dim); }
+        if field_dim == self.mwso.dim {
+            let mut gravity = vec![0.0; field_dim];
+            r.read_f32_into(&mut gravity)?;
+            self.mwso.set_gravity_values(&gravity);
+        } else {
+            r.skip_f32(field_dim)?;
+        }
         
-        let in_hist_len = read_u32(&mut cur) as usize;
+        r.expect_section(dsym::SECTION_HISTORY)?;
+        let in_hist_len = r.read_u32()? as usize;
         self.input_history.clear();
         for _ in 0..in_hist_len {
-            self.input_history.push_back(read_u32(&mut cur) as usize);
+            self.input_history.push_back(r.read_u32()? as usize);
         }
         
-        let cat_len = read_u32(&mut cur) as usize;
-        for _ in 0..cat_len { let _ = read_u32(&mut cur); } // Skip category sizes for now or validate
+        r.expect_section(dsym::SECTION_CATEGORIES)?;
+        let cat_len = r.read_u32()? as usize;
+        for _ in 0..cat_len { let _ = r.read_u32()?; } // Skip category sizes for now or validate
         
-        let nodes_len = read_u32(&mut cur) as usize;
+        r.expect_section(dsym::SECTION_NODES)?;
+        let nodes_len = r.read_u32()? as usize;
         for i in 0..nodes_len {
-            if i < self.nodes.len() {
-                self.nodes[i].state = read_f32(&mut cur);
-                self.nodes[i].base_decay = read_f32(&mut cur);
+            let state = r.read_f32()?;
+            let base_decay = r.read_f32()?;
+            let identity = if version >= dsym::NODE_IDENTITY_VERSION {
+                let name = r.read_string()?;
+                let activation = r.read_i32()?;
+                let cap = r.read_f32()?;
+                Some((name, Activation::from_id(activation, cap).unwrap_or_default()))
+            } else {
+                None
+            };
+            let synapses = if version >= dsym::SYNAPSE_VERSION {
+                let count = r.read_u32()? as usize;
+                let mut synapses = Vec::with_capacity(count.min(1024));
+                for _ in 0..count {
+                    let target_id = r.read_u32()? as usize;
+                    let weight = r.read_f32()?;
+                    synapses.push(super::node::Synapse { target_id, weight });
+                }
+                Some(synapses)
             } else {
-                let _ = read_f32(&mut cur);
-                let _ = read_f32(&mut cur);
+                None
+            };
+            // 保存時にあって今の構成にないノード（add_node で加えたもの）は作り直す
+            if i >= self.nodes.len() {
+                let name = identity.as_ref().map_or("", |(name, _)| name.as_str());
+                let mut node = Node::named(name, base_decay);
+                node.refractory_period = self.config.refractory_period;
+                node.spike_threshold = self.config.spike_threshold;
+                self.nodes.push(node);
+            }
+            let node = &mut self.nodes[i];
+            node.state = state;
+            node.base_decay = base_decay;
+            if let Some((name, activation)) = identity {
+                node.name = name;
+                node.activation = activation;
             }
+            // 旧形式はシナプスを持たないので、現在のトポロジーをそのまま残す
+            if let Some(synapses) = synapses { node.synapses = synapses; }
+        }
+        // 名前の入った形式では保存時の構成がすべてなので、その後に加えたノードは取り除く
+        if version >= dsym::NODE_IDENTITY_VERSION { self.nodes.truncate(nodes_len); }
+        // 存在しないノードを指すシナプスは捨てる
+        let node_count = self.nodes.len();
+        for node in &mut self.nodes {
+            node.synapses.retain(|s| s.target_id < node_count);
         }
         
-        let rules_len = read_u32(&mut cur) as usize;
+        r.expect_section(dsym::SECTION_RULES)?;
+        let rules_len = r.read_u32()? as usize;
         self.learned_rules.clear();
         for _ in 0..rules_len {
-            let s = read_u32(&mut cur) as usize;
-            let a = read_u32(&mut cur) as usize;
-            let c = read_u32(&mut cur) as usize;
-            self.learned_rules.push((s, a, c));
+            let s = r.read_u32()? as usize;
+            let a = r.read_u32()? as usize;
+            let c = r.read_u32()? as usize;
+            self.learned_rules.push(LearnedRule { state: s, action: a, count: c, last_used: self.rule_clock });
+        }
+        self.reindex_learned_rules();
+        // 結晶化の印は読み込むルール集から作り直す。ルール集を持たない旧形式では learned_rules から昇格させ直す
+        self.crystallized.clear();
+        if version < dsym::KNOWLEDGE_VERSION {
+            self.bootstrapper.rules.retain(|rule| !rule.crystallized);
+            self.bootstrapper.reindex();
+            self.recrystallize();
         }
 
-        let mwso_dim = read_u32(&mut cur) as usize;
+        r.expect_section(dsym::SECTION_WAVE)?;
+        let mwso_dim = r.read_u32()? as usize;
         if mwso_dim == self.mwso.dim {
-            for f in &mut self.mwso.psi_real { *f = read_f32(&mut cur); }
-            for f in &mut self.mwso.psi_imag { *f = read_f32(&mut cur); }
-            let theta_len = read_u32(&mut cur) as usize;
-            for i in 0..theta_len {
-                let val = read_f32(&mut cur);
-                if i < self.mwso.theta.len() { self.mwso.theta[i] = val; }
+            r.read_wave_into(&mut self.mwso.psi_real)?;
+            r.read_wave_into(&mut self.mwso.psi_imag)?;
+            let theta_len = r.read_u32()? as usize;
+            let kept = theta_len.min(self.mwso.theta.len());
+            r.read_wave_into(&mut self.mwso.theta[..kept])?;
+            r.skip_f32(theta_len - kept)?;
+        } else {
+            // 次元の異なる波（拡張前のチェックポイントなど）は読み飛ばし、現在の波を残す
+            r.skip_f32(mwso_dim * 2)?;
+            let theta_len = r.read_u32()? as usize;
+            r.skip_f32(theta_len)?;
+        }
+
+        if version >= dsym::MEMORY_WAVE_VERSION {
+            r.expect_section(dsym::SECTION_MEMORY)?;
+            let memory_dim = r.read_u32()? as usize;
+            let bank_count = r.read_u32()? as usize;
+            let active = r.read_u32()? as usize;
+            let routing = BankRoutingPolicy::from_id(r.read_u32()? as i32).unwrap_or_default();
+            let mut banks = Vec::with_capacity(bank_count);
+            for _ in 0..bank_count {
+                let label = r.read_string()?;
+                let imprints = r.read_u64()?;
+                if memory_dim == self.mwso.dim {
+                    let mut bank = MemoryBank::new(&label, memory_dim);
+                    bank.imprints = imprints;
+                    for values in [&mut bank.q_memory_re, &mut bank.q_memory_im, &mut bank.q_topo_re, &mut bank.q_topo_im] {
+                        r.read_f64_into(values)?;
+                    }
+                    banks.push(bank);
+                } else {
+                    r.skip_f32(memory_dim * 4 * 2)?; // f64 × 4 配列分
+                }
+            }
+            if memory_dim == self.mwso.dim {
+                self.mwso.restore_memory_banks(banks, active);
+                self.mwso.bank_routing = routing;
+            }
+        } else {
+            // 記憶波を保存していなかった旧形式: 読み込んだ波と無関係な記憶が残らないよう空にする
+            self.mwso.clear_memory_banks();
+        }
+
+        // 旧形式にはワームホールが無いので、構築時の結合をそのまま使う
+        if version >= dsym::WORMHOLE_VERSION {
+            r.expect_section(dsym::SECTION_WORMHOLES)?;
+            let cap = r.read_u32()? as usize;
+            let count = r.read_u32()? as usize;
+            let mut entanglements = Vec::with_capacity(count.min(cap));
+            for _ in 0..count {
+                let from = r.read_u32()? as usize;
+                let to = r.read_u32()? as usize;
+                let strength = r.read_f32()?;
+                // 次元の異なるモデルのビンは指せないので捨てる
+                if from < self.mwso.dim && to < self.mwso.dim {
+                    entanglements.push((from, to, strength));
+                }
+            }
+            self.mwso.entanglements = entanglements;
+            self.mwso.set_wormhole_cap(cap);
+        }
+
+        // 保存時のルール集（結晶化したルールを含む）で置き換える。旧形式では登録済みのルールをそのまま使う
+        if version >= dsym::KNOWLEDGE_VERSION {
+            r.expect_section(dsym::SECTION_KNOWLEDGE)?;
+            let policy = RuleConflictPolicy::from_id(r.read_u32()? as i32).unwrap_or_default();
+            let count = r.read_u32()? as usize;
+            let mut bootstrapper = Bootstrapper::new();
+            bootstrapper.policy = policy;
+            bootstrapper.lifecycle = self.bootstrapper.lifecycle;
+            for _ in 0..count {
+                let condition_id = r.read_i32()?;
+                let target_action = r.read_u32()? as usize;
+                let span = if version >= dsym::RULE_SPAN_VERSION { r.read_u32()? as usize } else { 1 };
+                let strength = r.read_f32()?;
+                let priority = r.read_i32()?;
+                let (decay, expires, age, uses) = if version >= dsym::RULE_LIFECYCLE_VERSION {
+                    (r.read_f32()?, r.read_u32()? != 0, r.read_u64()?, r.read_u64()?)
+                } else {
+                    (1.0, false, 0, 0)
+                };
+                let crystallized = version >= dsym::CRYSTALLIZED_RULE_VERSION && r.read_u32()? != 0;
+                let len = r.read_u32()? as usize;
+                let mut codes = Vec::with_capacity(len.min(1024));
+                for _ in 0..len { codes.push(r.read_i32()?); }
+                let condition = if len == 0 {
+                    None
+                } else {
+                    Some(Condition::decode_prefix(&codes).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Invalid rule condition"))?)
+                };
+                bootstrapper.rules.push(HamiltonianRule { condition_id, target_action, span, strength, condition, priority, decay, expires, age, uses, crystallized });
+            }
+            if version >= dsym::RULE_TEMPLATE_VERSION {
+                let count = r.read_u32()? as usize;
+                for _ in 0..count {
+                    let strength = r.read_f32()?;
+                    let mut read_codes = || -> io::Result<Vec<i32>> {
+                        let len = r.read_u32()? as usize;
+                        let mut codes = Vec::with_capacity(len.min(1024));
+                        for _ in 0..len { codes.push(r.read_i32()?); }
+                        Ok(codes)
+                    };
+                    let condition = Condition::decode_prefix(&read_codes()?);
+                    let action = ActionExpr::decode_prefix(&read_codes()?);
+                    let (Some(condition), Some(action)) = (condition, action) else {
+                        return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid rule template"));
+                    };
+                    bootstrapper.add_template(condition, action, strength);
+                }
+            }
+            if version < dsym::CRYSTALLIZED_RULE_VERSION {
+                // 旧形式には印がないため、昇格の閾値を超えた (状態, 行動) の単一ルールを結晶化の強度で見分ける
+                let (threshold, strength) = (self.config.crystallize_threshold, self.config.crystallize_strength);
+                for learned in self.learned_rules.iter().filter(|l| threshold > 0 && l.count >= threshold) {
+                    if let Some(rule) = bootstrapper.rules.iter_mut().find(|r| !r.crystallized && r.condition.is_none()
+                        && r.condition_id == learned.state as i32 && r.target_action == learned.action && r.span == 1 && r.strength == strength) {
+                        rule.crystallized = true;
+                    }
+                }
+            }
+            bootstrapper.reindex();
+            self.bootstrapper = bootstrapper;
+            self.crystallized = self.bootstrapper.rules.iter()
+                .filter(|rule| rule.crystallized && rule.condition_id >= 0)
+                .map(|rule| ((rule.condition_id as usize, rule.target_action), 0))
+                .collect();
+        }
+
+        // 退避済みの状態: コールドストレージが有効なら退避先へ戻し、無効なら実行中の表へ書き戻す
+        if version >= dsym::ARCHIVE_VERSION {
+            r.expect_section(dsym::SECTION_ARCHIVE)?;
+            let count = r.read_u32()? as usize;
+            for _ in 0..count {
+                let state = r.read_u32()? as usize;
+                let data = ArchivedState::read_from(&mut r, state, self.rule_clock)?;
+                match self.cold_archive.as_mut() {
+                    Some(archive) if archive.contains(state) => {}
+                    Some(archive) => archive.store(state, &data)?,
+                    None => self.merge_archived_state(state, data),
+                }
+            }
+        }
+
+        if version >= dsym::STATE_CODING_VERSION {
+            r.expect_section(dsym::SECTION_STATE_CODING)?;
+            let coding = StateCoding::from_id(r.read_i32()?)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Invalid state coding"))?;
+            self.set_state_coding(coding);
+        }
+
+        if version >= dsym::ACTION_LAYOUT_VERSION {
+            r.expect_section(dsym::SECTION_ACTION_LAYOUT)?;
+            let count = r.read_u32()? as usize;
+            let mut widths = Vec::with_capacity(count);
+            for _ in 0..count { widths.push(r.read_u32()? as usize); }
+            let layout = if count == 0 {
+                None
+            } else {
+                let layout = ActionLayout::from_widths(&widths)
+                    .filter(|l| l.len() == self.action_size)
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Invalid action layout"))?;
+                Some(layout)
+            };
+            if !self.mwso.set_action_layout(layout) {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "Action layout exceeds wave dimension"));
+            }
+        }
+
+        if version >= dsym::STATE_ALIAS_VERSION {
+            r.expect_section(dsym::SECTION_STATE_ALIASES)?;
+            let count = r.read_u32()? as usize;
+            self.state_aliases.clear();
+            for _ in 0..count {
+                let state = r.read_u32()? as usize;
+                let representative = r.read_u32()? as usize;
+                self.state_aliases.insert(state, representative);
             }
         }
+        r.finish();
 
         self.last_topology_update_temp = -1.0;
-        self.reshape_topology();
+        // シナプスを保存していない旧形式では、復元した感情状態からトポロジーを組み直す
+        if version < dsym::SYNAPSE_VERSION { self.reshape_topology(); }
         Ok(())
     }
 
     pub fn get_raw_scores(&mut self, action_size: usize) -> Vec<f32> {
         if let Some(ref mut sharded) = self.sharded_mwso {
-            sharded.get_action_scores(&vec![0.0; self.penalty_dim])
+            sharded.get_action_scores(&[])
+        } else {
+            self.mwso.get_action_scores(0, action_size, 0.0, &[])
+        }
+    }
+
+    /// 波動スコアから疲労分を引いた行動スコアを scores へ書き込む（毎ティックの読み出しで確保しない）
+    /// 書き込んだ要素数（scores.len() と action_size の小さい方）を返す
+    pub fn action_scores_into(&mut self, scores: &mut [f32]) -> usize {
+        let n = scores.len().min(self.action_size);
+        if n < self.action_size {
+            // 一部だけ読む場合は行動ごとに読む（一括読み出しは全行動分のバッファが前提）
+            for (i, s) in scores[..n].iter_mut().enumerate() { *s = self.action_score(i); }
+            return n;
+        }
+        let scores = &mut scores[..n];
+        if let Some(ref mut sharded) = self.sharded_mwso {
+            sharded.get_action_scores_into(&[], scores);
         } else {
-            self.mwso.get_action_scores(0, action_size, 0.0, &vec![0.0; self.mwso.dim])
+            self.mwso.get_action_scores_into(0, &[], scores);
+        }
+        for (s, &fatigue) in scores.iter_mut().zip(&self.fatigue_map) {
+            *s -= fatigue * 2.0;
         }
+        n
+    }
+
+    /// action_scores_into の 1 行動版。範囲外なら 0
+    pub fn action_score(&mut self, action_idx: usize) -> f32 {
+        if action_idx >= self.action_size { return 0.0; }
+        let wave_score = if let Some(ref mut sharded) = self.sharded_mwso {
+            sharded.get_action_score(action_idx, &[])
+        } else {
+            self.mwso.get_action_score(action_idx, self.action_size, &[])
+        };
+        wave_score - self.fatigue_map[action_idx] * 2.0
     }
 }
\ No newline at end of file
@@ -0,0 +1,52 @@
+// src/core/spectrum.rs
+// MWSO の固有振動数の並べ方（分散関係）のプリセット
+
+use std::f32::consts::PI;
+
+/// ビン i の固有振動数 frequencies[i] の決め方。いずれも [0, 2π] の範囲に収まる
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Spectrum {
+    /// 2π·(i / dim)
+    Linear,
+    /// 2π·(i / dim)²（従来の挙動）
+    #[default]
+    Quadratic,
+    /// 基本振動数 2π / harmonics の整数倍を、ビン順に 1 倍から harmonics 倍まで繰り返す
+    HarmonicStack { harmonics: usize },
+    /// 2π·low から 2π·high までの帯域に線形に並べる（low, high は [0, 1] の割合）
+    BandLimited { low: f32, high: f32 },
+}
+
+impl Spectrum {
+    /// JNI などからの整数指定 (0=Linear, 1=Quadratic, 2=HarmonicStack, 3=BandLimited)
+    /// HarmonicStack は a を倍音の数、BandLimited は a, b を帯域の下端・上端として使う
+    pub fn from_id(id: i32, a: f32, b: f32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Linear),
+            1 => Some(Self::Quadratic),
+            2 => Some(Self::HarmonicStack { harmonics: a.max(1.0) as usize }),
+            3 => Some(Self::BandLimited { low: a, high: b }),
+            _ => None,
+        }
+    }
+
+    /// dim ビン分の固有振動数
+    pub fn frequencies(self, dim: usize) -> Vec<f32> {
+        (0..dim).map(|i| {
+            let x = i as f32 / dim as f32;
+            match self {
+                Self::Linear => x * 2.0 * PI,
+                Self::Quadratic => x.powi(2) * 2.0 * PI,
+                Self::HarmonicStack { harmonics } => {
+                    let harmonics = harmonics.max(1);
+                    (i % harmonics + 1) as f32 * 2.0 * PI / harmonics as f32
+                }
+                Self::BandLimited { low, high } => {
+                    let low = low.clamp(0.0, 1.0);
+                    let high = high.clamp(low, 1.0);
+                    (low + (high - low) * x) * 2.0 * PI
+                }
+            }
+        }).collect()
+    }
+}
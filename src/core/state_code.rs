@@ -0,0 +1,175 @@
+// src/core/state_code.rs
+// inject_state が状態を波へ注入するときのビンと位相のパターン（状態コード）
+// 素数ストライドでは異なる状態が同じビンを踏みやすいため、擬似直交なランダム位相コードを選べるようにする
+
+use std::f32::consts::PI;
+
+/// 1 状態あたりの注入ビン数
+pub const STATE_CODE_BINS: usize = 16;
+/// Orthogonal でビンを選ぶとき、1 枠あたりに比べる候補の数（最も使われていないビンを採る）
+const BIN_CANDIDATES: usize = 4;
+
+/// 状態コードの作り方
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StateCoding {
+    /// 状態番号ごとの素数ストライドで 16 ビンを選び、全ビンに同じ位相を乗せる（従来の挙動）
+    #[default]
+    PrimeStride,
+    /// 状態ごとに擬似乱数で 16 ビンと位相を選ぶ
+    RandomPhase,
+    /// RandomPhase に加え、使用の少ないビンを優先し、先に作られたコードに対して位相を
+    /// Gram-Schmidt 法で直交化する
+    Orthogonal,
+}
+
+impl StateCoding {
+    /// JNI などからの整数指定 (0=PrimeStride, 1=RandomPhase, 2=Orthogonal)
+    pub fn from_id(id: i32) -> Option<Self> {
+        match id {
+            0 => Some(Self::PrimeStride),
+            1 => Some(Self::RandomPhase),
+            2 => Some(Self::Orthogonal),
+            _ => None,
+        }
+    }
+
+    pub fn id(self) -> i32 {
+        match self {
+            Self::PrimeStride => 0,
+            Self::RandomPhase => 1,
+            Self::Orthogonal => 2,
+        }
+    }
+}
+
+/// 状態番号 → (ビン, 位相) × STATE_CODE_BINS の表
+/// コードは状態番号の小さい順に必要な分だけ作るため、参照の順序に依らず (dim, coding) から一意に決まる
+#[derive(Clone, Debug)]
+pub struct StateCodeTable {
+    pub coding: StateCoding,
+    dim: usize,
+    /// 状態 s のコードは [s * STATE_CODE_BINS, (s + 1) * STATE_CODE_BINS)
+    bins: Vec<usize>,
+    phases: Vec<f32>,
+    /// ビンごとに、そのビンを使う状態の一覧（Orthogonal のみ）
+    bin_owners: Vec<Vec<u32>>,
+}
+
+impl StateCodeTable {
+    /// PrimeStride は表を持たないので None
+    pub fn new(coding: StateCoding, dim: usize) -> Option<Self> {
+        if coding == StateCoding::PrimeStride || dim < STATE_CODE_BINS { return None; }
+        let bin_owners = if coding == StateCoding::Orthogonal { vec![Vec::new(); dim] } else { Vec::new() };
+        Some(Self { coding, dim, bins: Vec::new(), phases: Vec::new(), bin_owners })
+    }
+
+    /// 作成済みのコード数
+    pub fn len(&self) -> usize {
+        self.bins.len() / STATE_CODE_BINS
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bins.is_empty()
+    }
+
+    /// 状態 state のビンと位相（未作成なら state までのコードを作る）
+    pub fn code(&mut self, state: usize) -> ([usize; STATE_CODE_BINS], [f32; STATE_CODE_BINS]) {
+        while self.len() <= state { self.push_next(); }
+        let range = state * STATE_CODE_BINS..(state + 1) * STATE_CODE_BINS;
+        let mut bins = [0; STATE_CODE_BINS];
+        let mut phases = [0.0; STATE_CODE_BINS];
+        bins.copy_from_slice(&self.bins[range.clone()]);
+        phases.copy_from_slice(&self.phases[range]);
+        (bins, phases)
+    }
+
+    fn push_next(&mut self) {
+        let state = self.len();
+        let mut rng = StateRng::new(state as u64, self.dim as u64);
+        let mut bins = [0usize; STATE_CODE_BINS];
+        for k in 0..STATE_CODE_BINS {
+            bins[k] = loop {
+                let bin = if self.coding == StateCoding::Orthogonal {
+                    // 候補のうち最も使われていないビン（負荷が偏らず衝突が減る）
+                    (0..BIN_CANDIDATES).map(|_| rng.below(self.dim))
+                        .min_by_key(|&b| self.bin_owners[b].len())
+                        .unwrap()
+                } else {
+                    rng.below(self.dim)
+                };
+                if !bins[..k].contains(&bin) { break bin; }
+            };
+        }
+        let mut phases = [0.0f32; STATE_CODE_BINS];
+        for p in &mut phases { *p = rng.unit() * 2.0 * PI; }
+
+        if self.coding == StateCoding::Orthogonal {
+            self.orthogonalize(&bins, &mut phases);
+            for &b in &bins { self.bin_owners[b].push(state as u32); }
+        }
+        self.bins.extend_from_slice(&bins);
+        self.phases.extend_from_slice(&phases);
+    }
+
+    /// 新しいコードから、ビンを共有する既存コードへの射影を引き、各成分の位相だけを残す
+    fn orthogonalize(&self, bins: &[usize; STATE_CODE_BINS], phases: &mut [f32; STATE_CODE_BINS]) {
+        let mut re: Vec<f32> = phases.iter().map(|p| p.cos()).collect();
+        let mut im: Vec<f32> = phases.iter().map(|p| p.sin()).collect();
+
+        let mut others: Vec<u32> = bins.iter().flat_map(|&b| self.bin_owners[b].iter().copied()).collect();
+        others.sort_unstable();
+        others.dedup();
+
+        let norm = 1.0 / STATE_CODE_BINS as f32;
+        for other in others {
+            let start = other as usize * STATE_CODE_BINS;
+            let other_bins = &self.bins[start..start + STATE_CODE_BINS];
+            let other_phases = &self.phases[start..start + STATE_CODE_BINS];
+            // 共有ビン上の (other, new) = Σ conj(e) c
+            let shared: Vec<(usize, f32)> = bins.iter().enumerate()
+                .filter_map(|(k, b)| other_bins.iter().position(|ob| ob == b).map(|j| (k, other_phases[j])))
+                .collect();
+            let (mut dot_re, mut dot_im) = (0.0, 0.0);
+            for &(k, phase) in &shared {
+                let (s, c) = phase.sin_cos();
+                dot_re += c * re[k] + s * im[k];
+                dot_im += c * im[k] - s * re[k];
+            }
+            // 両コードとも各成分の大きさは 1 なので、単位ベクトルに直すと 1 / STATE_CODE_BINS が掛かる
+            let (dot_re, dot_im) = (dot_re * norm, dot_im * norm);
+            for &(k, phase) in &shared {
+                let (s, c) = phase.sin_cos();
+                re[k] -= dot_re * c - dot_im * s;
+                im[k] -= dot_re * s + dot_im * c;
+            }
+        }
+        for k in 0..STATE_CODE_BINS {
+            if re[k] * re[k] + im[k] * im[k] > 1e-12 { phases[k] = im[k].atan2(re[k]).rem_euclid(2.0 * PI); }
+        }
+    }
+}
+
+/// 状態番号と次元から決まる擬似乱数列（splitmix64）
+struct StateRng(u64);
+
+impl StateRng {
+    fn new(state: u64, dim: u64) -> Self {
+        Self(state.wrapping_mul(0x9E3779B97F4A7C15) ^ dim.rotate_left(32) ^ 0x5DEECE66D)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (((self.next() >> 32) * n as u64) >> 32) as usize
+    }
+
+    fn unit(&mut self) -> f32 {
+        (self.next() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
@@ -0,0 +1,107 @@
+// src/core/stats.rs
+// 呼び出し回数・レイテンシと、波動ノルム・温度の推移を記録する計測モジュール
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// p99 の算出に使う直近の計測数
+const LATENCY_WINDOW: usize = 1024;
+/// 波動ノルム・温度の履歴の長さ
+const HISTORY_LEN: usize = 256;
+
+/// 1種類の呼び出しのレイテンシ記録
+#[derive(Clone, Debug, Default)]
+pub struct LatencyTracker {
+    count: u64,
+    total: Duration,
+    min: Option<Duration>,
+    max: Duration,
+    /// 直近 LATENCY_WINDOW 件のリングバッファ
+    window: Vec<Duration>,
+    cursor: usize,
+}
+
+impl LatencyTracker {
+    pub fn record(&mut self, elapsed: Duration) {
+        self.count += 1;
+        self.total += elapsed;
+        self.min = Some(self.min.map_or(elapsed, |m| m.min(elapsed)));
+        self.max = self.max.max(elapsed);
+
+        // 記録のたびに再確保しないよう、最初の記録で窓の分だけ確保する
+        if self.window.capacity() == 0 { self.window.reserve_exact(LATENCY_WINDOW); }
+        if self.window.len() < LATENCY_WINDOW {
+            self.window.push(elapsed);
+        } else {
+            self.window[self.cursor] = elapsed;
+        }
+        self.cursor = (self.cursor + 1) % LATENCY_WINDOW;
+    }
+
+    pub fn summary(&self) -> LatencySummary {
+        if self.count == 0 { return LatencySummary::default(); }
+        let mut sorted = self.window.clone();
+        sorted.sort_unstable();
+        let rank = (sorted.len() * 99).div_ceil(100).clamp(1, sorted.len());
+        LatencySummary {
+            count: self.count,
+            min: self.min.unwrap_or_default(),
+            avg: self.total / self.count.min(u32::MAX as u64) as u32,
+            p99: sorted[rank - 1],
+            max: self.max,
+        }
+    }
+}
+
+/// レイテンシの集計値（p99 は直近 LATENCY_WINDOW 件から求める）
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LatencySummary {
+    pub count: u64,
+    pub min: Duration,
+    pub avg: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RuntimeStats {
+    pub select_actions: LatencyTracker,
+    pub learn: LatencyTracker,
+    pub save_to_file: LatencyTracker,
+    /// learn ごとの波動ノルム（直近 HISTORY_LEN 件）
+    pub wave_norm: VecDeque<f32>,
+    /// learn ごとの系の温度（直近 HISTORY_LEN 件）
+    pub temperature: VecDeque<f32>,
+}
+
+impl RuntimeStats {
+    pub fn record_state(&mut self, wave_norm: f32, temperature: f32) {
+        for (history, value) in [(&mut self.wave_norm, wave_norm), (&mut self.temperature, temperature)] {
+            if history.capacity() == 0 { history.reserve_exact(HISTORY_LEN); }
+            if history.len() == HISTORY_LEN { history.pop_front(); }
+            history.push_back(value);
+        }
+    }
+
+    pub fn report(&self) -> StatsReport {
+        StatsReport {
+            select_actions: self.select_actions.summary(),
+            learn: self.learn.summary(),
+            save_to_file: self.save_to_file.summary(),
+            wave_norm: self.wave_norm.iter().copied().collect(),
+            temperature: self.temperature.iter().copied().collect(),
+        }
+    }
+}
+
+/// Singularity::stats が返す計測結果のスナップショット
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StatsReport {
+    pub select_actions: LatencySummary,
+    pub learn: LatencySummary,
+    pub save_to_file: LatencySummary,
+    /// 古い順
+    pub wave_norm: Vec<f32>,
+    /// 古い順
+    pub temperature: Vec<f32>,
+}
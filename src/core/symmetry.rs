@@ -0,0 +1,146 @@
+// src/core/symmetry.rs
+use std::collections::HashMap;
+
+/// 提案の種類
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SymmetryKind {
+    /// ほぼ同一の統計: 状態を代表状態へ統合する
+    Merge,
+    /// 類似した統計: 状態同士をワームホールで結び、経験を共有させる
+    Wormhole,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SymmetryProposal {
+    pub kind: SymmetryKind,
+    /// 対象の状態群（先頭が代表状態）
+    pub states: Vec<usize>,
+    /// 行動ごとの平均報酬の差（平均絶対誤差）
+    pub distance: f32,
+}
+
+/// 経験ログから (状態, 行動) -> 報酬統計を集計し、等価に振る舞う状態を検出する
+#[derive(Clone)]
+pub struct SymmetryAnalyzer {
+    pub action_size: usize,
+    /// state_idx -> 行動ごとの (報酬合計, 回数)
+    pub stats: HashMap<usize, Vec<(f32, u32)>>,
+    /// 比較対象とするための最低観測回数（状態あたり）
+    pub min_samples: u32,
+    /// 比較に必要な共通観測行動数
+    pub min_shared_actions: usize,
+    /// この距離未満なら統合を提案
+    pub merge_tolerance: f32,
+    /// この距離未満ならワームホールを提案
+    pub wormhole_tolerance: f32,
+}
+
+impl SymmetryAnalyzer {
+    pub fn new(action_size: usize) -> Self {
+        Self {
+            action_size,
+            stats: HashMap::new(),
+            min_samples: 4,
+            min_shared_actions: 2,
+            merge_tolerance: 0.05,
+            wormhole_tolerance: 0.2,
+        }
+    }
+
+    pub fn record(&mut self, state_idx: usize, action: usize, reward: f32) {
+        if action >= self.action_size { return; }
+        let size = self.action_size;
+        let row = self.stats.entry(state_idx).or_insert_with(|| vec![(0.0, 0); size]);
+        row[action].0 += reward;
+        row[action].1 += 1;
+    }
+
+    /// 2状態間の行動-結果統計の距離（比較不能なら None）
+    pub fn distance(&self, a: usize, b: usize) -> Option<f32> {
+        let (ra, rb) = (self.stats.get(&a)?, self.stats.get(&b)?);
+        let mut shared = 0;
+        let mut total = 0.0;
+        for (&(sa, ca), &(sb, cb)) in ra.iter().zip(rb) {
+            if ca == 0 || cb == 0 { continue; }
+            shared += 1;
+            total += (sa / ca as f32 - sb / cb as f32).abs();
+        }
+        if shared < self.min_shared_actions { return None; }
+        Some(total / shared as f32)
+    }
+
+    /// 統合（グループ内のどの 2 状態も merge_tolerance 未満のグループ）とワームホール（残りの類似ペア）を提案する
+    pub fn propose(&self) -> Vec<SymmetryProposal> {
+        let mut states: Vec<usize> = self.stats.iter()
+            .filter(|(_, row)| row.iter().map(|&(_, c)| c).sum::<u32>() >= self.min_samples)
+            .map(|(&s, _)| s)
+            .collect();
+        states.sort_unstable();
+
+        // Union-Find で統合グループを作る
+        let mut parent: Vec<usize> = (0..states.len()).collect();
+        fn find(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i { parent[i] = parent[parent[i]]; i = parent[i]; }
+            i
+        }
+
+        let mut merge_dist: HashMap<usize, f32> = HashMap::new();
+        let mut members: Vec<Vec<usize>> = (0..states.len()).map(|i| vec![i]).collect();
+        let mut wormholes = Vec::new();
+        for i in 0..states.len() {
+            for j in (i + 1)..states.len() {
+                let Some(d) = self.distance(states[i], states[j]) else { continue; };
+                let (ri, rj) = (find(&mut parent, i), find(&mut parent, j));
+                if ri == rj { continue; }
+                // 統合後のグループの直径（最も離れた 2 状態の距離）も許容範囲に収まる場合だけ統合し、推移的な連鎖を防ぐ
+                let diameter = if d < self.merge_tolerance { self.merged_diameter(&states, &members[ri], &members[rj]) } else { None };
+                if let Some(cross) = diameter.filter(|&c| c < self.merge_tolerance) {
+                    let root = ri.min(rj);
+                    let other = ri.max(rj);
+                    parent[other] = root;
+                    let moved = std::mem::take(&mut members[other]);
+                    members[root].extend(moved);
+                    let worst = merge_dist.get(&ri).copied().unwrap_or(0.0)
+                        .max(merge_dist.get(&rj).copied().unwrap_or(0.0))
+                        .max(cross);
+                    merge_dist.insert(root, worst);
+                } else if d < self.wormhole_tolerance {
+                    wormholes.push((i, j, d));
+                }
+            }
+        }
+
+        let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (i, &state) in states.iter().enumerate() {
+            let root = find(&mut parent, i);
+            groups.entry(root).or_default().push(state);
+        }
+        let mut proposals: Vec<SymmetryProposal> = groups.into_iter()
+            .filter(|(_, g)| g.len() > 1)
+            .map(|(root, g)| SymmetryProposal {
+                kind: SymmetryKind::Merge,
+                states: g,
+                distance: merge_dist.get(&root).copied().unwrap_or(0.0),
+            })
+            .collect();
+        proposals.sort_by_key(|p| p.states[0]);
+
+        // 同じグループに統合される組はワームホール不要
+        for (i, j, d) in wormholes {
+            if find(&mut parent, i) == find(&mut parent, j) { continue; }
+            proposals.push(SymmetryProposal { kind: SymmetryKind::Wormhole, states: vec![states[i], states[j]], distance: d });
+        }
+        proposals
+    }
+
+    /// 2 つのグループにまたがる組の最大距離（比較できない組があれば None）
+    fn merged_diameter(&self, states: &[usize], a: &[usize], b: &[usize]) -> Option<f32> {
+        let mut worst = 0.0f32;
+        for &i in a {
+            for &j in b {
+                worst = worst.max(self.distance(states[i], states[j])?);
+            }
+        }
+        Some(worst)
+    }
+}
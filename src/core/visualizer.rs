@@ -1,11 +1,32 @@
 use plotters::prelude::*;
 use super::mwso::MWSO;
+use super::wave::{Wave, from_wave};
+use super::wave_view::WaveFrame;
 
 pub struct Visualizer;
 
 impl Visualizer {
     /// MWSOの波動状態を3D空間にプロットし、画像として保存する
     pub fn render_wave_snapshot(mwso: &MWSO, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        // 診断値を左上に重ねる
+        let diag = mwso.diagnostics();
+        let lines = [
+            format!("norm {:.3}  entropy {:.3}", diag.norm, diag.spectral_entropy),
+            format!("dominant bin {} ({:.3} rad)", diag.dominant_bin, diag.dominant_frequency),
+            format!("memory resonance {:.3}", diag.memory_resonance),
+            format!("gravity saturation {:.1}%  wormholes {}", diag.gravity_saturation * 100.0, diag.entanglement_count),
+        ];
+        Self::render_wave(&mwso.psi_real, &mwso.psi_imag, &lines, path)
+    }
+
+    /// WaveView から読んだフレームをプロットする（意思決定ループとは別のスレッドから呼べる）
+    pub fn render_wave_frame(frame: &WaveFrame, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let norm: Wave = frame.psi_real.iter().zip(&frame.psi_imag).map(|(re, im)| re * re + im * im).sum();
+        let lines = [format!("step {}  norm {:.3}", frame.step, from_wave(norm.sqrt()))];
+        Self::render_wave(&frame.psi_real, &frame.psi_imag, &lines, path)
+    }
+
+    fn render_wave(psi_real: &[Wave], psi_imag: &[Wave], lines: &[String], path: &str) -> Result<(), Box<dyn std::error::Error>> {
         let root = BitMapBackend::new(path, (1280, 720)).into_drawing_area();
         
         // Dark Singularity スタイルの黒背景
@@ -31,8 +52,8 @@ impl Visualizer {
 
         // 波動データのプロット
         // X: Index, Y: Real, Z: Imaginary
-        let data: Vec<(f32, f32, f32)> = (0..mwso.dim)
-            .map(|i| (i as f32, mwso.psi_real[i], mwso.psi_imag[i]))
+        let data: Vec<(f32, f32, f32)> = psi_real.iter().zip(psi_imag).enumerate()
+            .map(|(i, (&re, &im))| (i as f32, from_wave(re), from_wave(im)))
             .collect();
 
         // 波動をネオンブルーの線で描画
@@ -46,6 +67,11 @@ impl Visualizer {
             Circle::new((x as f64, y as f64, z as f64), 2, Into::<ShapeStyle>::into(&CYAN).filled())
         }))?;
 
+        let style = ("sans-serif", 18).into_font().color(&RGBColor(180, 180, 180));
+        for (row, line) in lines.iter().enumerate() {
+            root.draw(&Text::new(line.as_str(), (24, 80 + row as i32 * 22), style.clone()))?;
+        }
+
         root.present()?;
         Ok(())
     }
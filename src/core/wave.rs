@@ -0,0 +1,38 @@
+// src/core/wave.rs
+// アクティブな波 (psi / theta) の数値精度
+// 既定は f32 で、`f64-wave` 機能を有効にすると f64 になる（精度による差を検証する研究用）
+// 記憶波 (q_memory / q_topo) は常に f64、エネルギー地形やペナルティなど周辺の場は常に f32
+
+#[cfg(not(feature = "f64-wave"))]
+mod precision {
+    pub type Wave = f32;
+    pub use std::f32::consts::PI;
+}
+
+#[cfg(feature = "f64-wave")]
+mod precision {
+    pub type Wave = f64;
+    pub use std::f64::consts::PI;
+}
+
+pub use precision::{Wave, PI};
+
+/// f32 の値を Wave へ（既定では恒等変換）
+#[allow(clippy::unnecessary_cast)]
+#[inline(always)]
+pub fn to_wave(x: f32) -> Wave { x as Wave }
+
+/// Wave の値を f32 へ（既定では恒等変換）
+#[allow(clippy::unnecessary_cast)]
+#[inline(always)]
+pub fn from_wave(x: Wave) -> f32 { x as f32 }
+
+/// f64 で計算した値を Wave へ
+#[allow(clippy::unnecessary_cast)]
+#[inline(always)]
+pub fn wave_from_f64(x: f64) -> Wave { x as Wave }
+
+/// Wave の値を f64 へ（記憶波との演算用）
+#[allow(clippy::unnecessary_cast)]
+#[inline(always)]
+pub fn wave_to_f64(x: Wave) -> f64 { x as f64 }
@@ -0,0 +1,166 @@
+// src/core/wave_delta.rs
+// 波 (psi) の差分圧縮: 前回のフレームとの差を 8 bit に量子化し、変化のないビンを飛ばして詰める
+// ライブ可視化やネットワーク越しの複製で、フレームごとの転送量を数 KB に抑えるためのもの
+//
+// 形式（数値はすべてリトルエンディアン）:
+//   "WDLT" | version: u8 | flags: u8 (bit0 = キーフレーム) | step: u64 | dim: u32
+//   実部・虚部それぞれ: scale: f32 | mode: u8 | 本体
+//     mode 0 (疎): 非ゼロ数 n: varint, n 個の (直前の非ゼロからの間隔: varint, 量子化値: i8)
+//     mode 1 (密): dim 個の量子化値: i8
+//   復号値 = 基準の値 + 量子化値 × scale（キーフレームの基準は 0）
+
+use super::wave::{Wave, from_wave, to_wave};
+use super::wave_view::WaveFrame;
+
+const MAGIC: &[u8; 4] = b"WDLT";
+const VERSION: u8 = 1;
+const FLAG_KEYFRAME: u8 = 1;
+const MODE_SPARSE: u8 = 0;
+const MODE_DENSE: u8 = 1;
+
+/// prev から (psi_real, psi_imag) への差分を符号化する。prev と次元が違えばキーフレーム（0 からの差分）にする
+/// 量子化誤差が積み重ならないよう、prev には受信側が持っているフレーム（decode_delta の結果）を渡すこと
+pub fn encode_delta(prev: &WaveFrame, psi_real: &[Wave], psi_imag: &[Wave], step: u64) -> Vec<u8> {
+    let dim = psi_real.len();
+    let keyframe = prev.dim() != dim || prev.psi_imag.len() != dim;
+    let mut out = Vec::with_capacity(32 + dim);
+    out.extend_from_slice(MAGIC);
+    out.push(VERSION);
+    out.push(if keyframe { FLAG_KEYFRAME } else { 0 });
+    out.extend_from_slice(&step.to_le_bytes());
+    out.extend_from_slice(&(dim as u32).to_le_bytes());
+    for (current, base) in [(psi_real, &prev.psi_real), (psi_imag, &prev.psi_imag)] {
+        let delta = |i: usize| from_wave(current[i]) - if keyframe { 0.0 } else { from_wave(base[i]) };
+        encode_component(&mut out, dim, delta);
+    }
+    out
+}
+
+/// encode_delta の出力を prev に適用したフレームを返す（theta は prev のものを引き継ぐ）
+/// 形式が壊れている、または差分の基準と prev の次元が合わない場合は None
+pub fn decode_delta(prev: &WaveFrame, bytes: &[u8]) -> Option<WaveFrame> {
+    let mut r = Reader { bytes, pos: 0 };
+    if r.take(4)? != MAGIC || r.u8()? != VERSION { return None; }
+    let keyframe = r.u8()? & FLAG_KEYFRAME != 0;
+    let step = u64::from_le_bytes(r.take(8)?.try_into().ok()?);
+    let dim = u32::from_le_bytes(r.take(4)?.try_into().ok()?) as usize;
+    if !keyframe && (prev.dim() != dim || prev.psi_imag.len() != dim) { return None; }
+
+    let mut frame = WaveFrame {
+        step,
+        psi_real: if keyframe { vec![0.0; dim] } else { prev.psi_real.clone() },
+        psi_imag: if keyframe { vec![0.0; dim] } else { prev.psi_imag.clone() },
+        theta: if prev.theta.len() == dim * 2 { prev.theta.clone() } else { Vec::new() },
+    };
+    decode_component(&mut r, &mut frame.psi_real)?;
+    decode_component(&mut r, &mut frame.psi_imag)?;
+    (r.pos == bytes.len()).then_some(frame)
+}
+
+/// 1 成分分の差分を、最大の差が ±127 に収まる刻みで量子化して書く。疎・密のうち短い方を選ぶ
+fn encode_component(out: &mut Vec<u8>, dim: usize, delta: impl Fn(usize) -> f32) {
+    let max = (0..dim).map(|i| delta(i).abs()).fold(0.0f32, f32::max);
+    let scale = if max.is_finite() && max > 0.0 { max / 127.0 } else { 0.0 };
+    out.extend_from_slice(&scale.to_le_bytes());
+    let quantize = |i: usize| if scale > 0.0 { (delta(i) / scale).round().clamp(-127.0, 127.0) as i8 } else { 0 };
+
+    let nonzero = (0..dim).filter(|&i| quantize(i) != 0).count();
+    // 疎形式は非ゼロ 1 つあたり少なくとも 2 バイト
+    if nonzero * 2 >= dim {
+        out.push(MODE_DENSE);
+        out.extend((0..dim).map(|i| quantize(i) as u8));
+    } else {
+        out.push(MODE_SPARSE);
+        write_varint(out, nonzero as u32);
+        let mut next = 0;
+        for i in 0..dim {
+            let q = quantize(i);
+            if q == 0 { continue; }
+            write_varint(out, (i - next) as u32);
+            out.push(q as u8);
+            next = i + 1;
+        }
+    }
+}
+
+fn decode_component(r: &mut Reader, values: &mut [Wave]) -> Option<()> {
+    let scale = f32::from_le_bytes(r.take(4)?.try_into().ok()?);
+    if !scale.is_finite() { return None; }
+    match r.u8()? {
+        MODE_DENSE => {
+            let quantized = r.take(values.len())?;
+            for (v, &q) in values.iter_mut().zip(quantized) {
+                *v += to_wave(q as i8 as f32 * scale);
+            }
+        }
+        MODE_SPARSE => {
+            let mut i = 0;
+            for _ in 0..r.varint()? {
+                i += r.varint()? as usize;
+                let q = r.u8()? as i8;
+                *values.get_mut(i)? += to_wave(q as f32 * scale);
+                i += 1;
+            }
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+fn write_varint(out: &mut Vec<u8>, mut v: u32) {
+    while v >= 0x80 {
+        out.push((v as u8) | 0x80);
+        v >>= 7;
+    }
+    out.push(v as u8);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        let slice = self.bytes.get(self.pos..self.pos.checked_add(n)?)?;
+        self.pos += n;
+        Some(slice)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn varint(&mut self) -> Option<u32> {
+        let mut v = 0u32;
+        for shift in (0..35).step_by(7) {
+            let b = self.u8()?;
+            v |= ((b & 0x7f) as u32).checked_shl(shift)?;
+            if b & 0x80 == 0 { return Some(v); }
+        }
+        None
+    }
+}
+
+/// 送信側の状態: 受信側が復元しているはずのフレームを持ち、次の差分をそこからの差として作る
+/// （量子化誤差はその都度次の差分に含まれるため、長く流しても受信側の波はずれていかない）
+#[derive(Clone, Debug, Default)]
+pub struct WaveDeltaStream {
+    reference: WaveFrame,
+}
+
+impl WaveDeltaStream {
+    /// 次に送る差分。最初の 1 回と次元が変わった直後はキーフレームになる
+    pub fn next(&mut self, psi_real: &[Wave], psi_imag: &[Wave]) -> Vec<u8> {
+        let bytes = encode_delta(&self.reference, psi_real, psi_imag, self.reference.step + 1);
+        if let Some(frame) = decode_delta(&self.reference, &bytes) {
+            self.reference = frame;
+        }
+        bytes
+    }
+
+    /// 受信側が持っているはずのフレーム
+    pub fn reference(&self) -> &WaveFrame {
+        &self.reference
+    }
+}
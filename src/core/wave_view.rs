@@ -0,0 +1,155 @@
+// src/core/wave_view.rs
+// 波 (psi / theta) の読み取り専用スナップショットを別スレッドへ公開するダブルバッファ
+// 書き手（意思決定ループ）は step_core の後に裏バッファへ写してから表のポインタを原子的に差し替え、
+// 読み手（可視化・テレメトリ・Java 側のゲッター）は表のフレームへの参照を受け取るだけなので、
+// 書き手と読み手はロックを取らず、互いのコピーや描画を待つこともない
+//
+// 差し替えで外れたフレームは、読み込み中の読み手がいないことを確かめるまで書き手が保持し続ける
+// （読み手は「読み込み中」の人数を増やしてからポインタを読み、参照カウントを増やしてから人数を戻す）
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use super::wave::Wave;
+
+/// ある step_core 直後の波
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WaveFrame {
+    /// 公開した回数（公開のたびに 1 増える）
+    pub step: u64,
+    pub psi_real: Vec<Wave>,
+    pub psi_imag: Vec<Wave>,
+    pub theta: Vec<Wave>,
+}
+
+impl WaveFrame {
+    pub fn dim(&self) -> usize {
+        self.psi_real.len()
+    }
+}
+
+/// 表のフレーム（Arc::into_raw したポインタ）と、ポインタを読んでいる途中の読み手の数
+#[derive(Debug)]
+struct FrameSlot {
+    current: AtomicPtr<WaveFrame>,
+    readers: AtomicUsize,
+}
+
+impl Default for FrameSlot {
+    fn default() -> Self {
+        Self {
+            current: AtomicPtr::new(Arc::into_raw(Arc::new(WaveFrame::default())).cast_mut()),
+            readers: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl FrameSlot {
+    fn load(&self) -> Arc<WaveFrame> {
+        self.readers.fetch_add(1, Ordering::SeqCst);
+        let ptr = self.current.load(Ordering::SeqCst);
+        // SAFETY: ptr は Arc::into_raw で作られ、表にある間は FrameSlot が参照を 1 つ持つ。
+        // 差し替えで外れた後も、readers が 0 になるのを書き手が確かめるまでは解放されない
+        let frame = unsafe {
+            Arc::increment_strong_count(ptr);
+            Arc::from_raw(ptr)
+        };
+        self.readers.fetch_sub(1, Ordering::SeqCst);
+        frame
+    }
+
+    /// 表を frame に差し替え、外れたフレームを返す
+    fn swap(&self, frame: Arc<WaveFrame>) -> Arc<WaveFrame> {
+        let old = self.current.swap(Arc::into_raw(frame).cast_mut(), Ordering::SeqCst);
+        // SAFETY: 表にあった間 FrameSlot が持っていた参照をそのまま引き取る
+        unsafe { Arc::from_raw(old) }
+    }
+
+    fn is_reading(&self) -> bool {
+        self.readers.load(Ordering::SeqCst) != 0
+    }
+}
+
+impl Drop for FrameSlot {
+    fn drop(&mut self) {
+        // SAFETY: 最後の所有者なので、表のフレームへの FrameSlot の参照を手放すだけでよい
+        drop(unsafe { Arc::from_raw(*self.current.get_mut()) });
+    }
+}
+
+/// 公開されたフレームを読む側のハンドル（別スレッドへ渡してよい）
+#[derive(Clone, Debug)]
+pub struct WaveView {
+    slot: Arc<FrameSlot>,
+}
+
+impl WaveView {
+    /// 最新のフレーム。返したフレームは以後の公開で書き換わらない（ロックを取らない）
+    pub fn load(&self) -> Arc<WaveFrame> {
+        self.slot.load()
+    }
+}
+
+/// MWSO 側の公開口。wave_view が一度も呼ばれていないか、読み手がすべて手放していれば何もしない
+#[derive(Debug, Default)]
+pub(crate) struct WavePublisher {
+    slot: Option<Arc<FrameSlot>>,
+    /// 裏バッファ（前回の表。読み手が手放していれば再利用する）
+    back: Option<Arc<WaveFrame>>,
+    /// 表から外れたが、読み込み中の読み手がいたためまだ手放せないフレーム
+    retired: Vec<Arc<WaveFrame>>,
+    step: u64,
+}
+
+impl Clone for WavePublisher {
+    /// 複製した MWSO は別の波を持つため、読み手は引き継がない
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl Drop for WavePublisher {
+    fn drop(&mut self) {
+        // 外れたフレームを読みかけの読み手がいれば、参照カウントを増やし終えるまで待つ（数命令で終わる）
+        if let Some(ref slot) = self.slot && !self.retired.is_empty() {
+            while slot.is_reading() { std::hint::spin_loop(); }
+        }
+    }
+}
+
+impl WavePublisher {
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.slot.is_some()
+    }
+
+    pub(crate) fn view(&mut self) -> WaveView {
+        let slot = self.slot.get_or_insert_with(Default::default);
+        WaveView { slot: Arc::clone(slot) }
+    }
+
+    /// 波を裏バッファへ写し、表と差し替える。各引数は連結して 1 本の波として公開する断片の列
+    pub(crate) fn publish(&mut self, psi_real: &[&[Wave]], psi_imag: &[&[Wave]], theta: &[&[Wave]]) {
+        let Some(ref slot) = self.slot else { return };
+        // 読み手がすべて手放したら公開をやめる（再び wave_view を呼べば再開する）
+        if Arc::strong_count(slot) == 1 {
+            self.slot = None;
+            self.back = None;
+            self.retired.clear();
+            return;
+        }
+        self.step += 1;
+        // 裏バッファをまだ読み手が保持しているなら新しく確保する
+        let mut frame = self.back.take().filter(|b| Arc::strong_count(b) == 1).unwrap_or_default();
+        let f = Arc::get_mut(&mut frame).expect("frame is not shared");
+        f.step = self.step;
+        for (dst, parts) in [(&mut f.psi_real, psi_real), (&mut f.psi_imag, psi_imag), (&mut f.theta, theta)] {
+            dst.clear();
+            for part in parts { dst.extend_from_slice(part); }
+        }
+        self.retired.push(slot.swap(frame));
+        // 差し替えの後に読み込み中の読み手がいなければ、外れたフレームを読みかけている者はいない
+        if !slot.is_reading() {
+            self.back = self.retired.pop();
+            self.retired.clear();
+        }
+    }
+}
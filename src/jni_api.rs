@@ -1,8 +1,39 @@
 // src/jni_api.rs
-use crate::core::singularity::Singularity;
+use crate::core::singularity::{Singularity, TrajectoryStep};
+use crate::core::config::{Coupling, ExplorationPolicy, Integrator, LearnQueuePolicy, MWSOConfig};
+use crate::core::encoder::TileCoder;
+use crate::core::episode::EpisodeLog;
+use crate::core::field_schedule::FieldDecay;
+use crate::core::knowledge::{ActionExpr, Condition, RuleConflictPolicy, RuleLifecycle};
+use crate::core::learn_queue::LearnWorker;
+use crate::core::memory_bank::BankRoutingPolicy;
+use crate::core::node::{Activation, NodeSpec};
+use crate::core::noise::ExplorationNoise;
+use crate::core::personality::Personality;
+use crate::core::quantized::FieldPrecision;
+use crate::core::spectrum::Spectrum;
+use crate::core::state_code::StateCoding;
+use crate::core::wave::from_wave;
+use crate::core::wave_view::WaveView;
 use jni::JNIEnv;
-use jni::objects::{JClass, JFloatArray, JIntArray, JString};
-use jni::sys::{jfloat, jfloatArray, jint, jlong, jsize, jintArray};
+use jni::objects::{JClass, JFloatArray, JIntArray, JLongArray, JObjectArray, JString, ReleaseMode};
+use jni::sys::{jboolean, jfloat, jfloatArray, jint, jlong, jsize, jintArray, jstring};
+use std::sync::MutexGuard;
+use std::time::Duration;
+
+// Java に渡すハンドルは LearnWorker へのポインタ。どのスレッドから呼ばれても、各関数はエージェントをロックしてから触る
+fn into_handle(singularity: Singularity) -> jlong {
+    Box::into_raw(Box::new(LearnWorker::new(singularity))) as jlong
+}
+
+fn worker<'a>(handle: jlong) -> &'a LearnWorker {
+    unsafe { &*(handle as *const LearnWorker) }
+}
+
+// 学習ワーカーが 1 件学習している最中なら、それが終わるまで待つ
+fn agent<'a>(handle: jlong) -> MutexGuard<'a, Singularity> {
+    worker(handle).lock()
+}
 
 // インスタンスを生成して Java にポインタ(jlong)として返す
 #[unsafe(no_mangle)]
@@ -19,8 +50,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     
     let cat_sizes: Vec<usize> = cat_buf.into_iter().map(|s| s as usize).collect();
 
-    let singularity = Box::new(Singularity::new(state_size as usize, cat_sizes));
-    Box::into_raw(singularity) as jlong
+    into_handle(Singularity::new(state_size as usize, cat_sizes))
 }
 
 // Java からもらったポインタを使って計算する
@@ -31,7 +61,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     handle: jlong,
     inputs: JFloatArray,
 ) -> jint {
-    let singularity = unsafe { &mut *(handle as *mut Singularity) };
+    let mut singularity = agent(handle);
 
     let input_vec: Vec<f32> = {
         let len = env.get_array_length(&inputs).unwrap_or(0) as usize;
@@ -47,6 +77,30 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     actions.first().cloned().unwrap_or(0) as jint
 }
 
+// 単一アクション互換 API の拡張版
+// category < 0 なら最初のカテゴリー、vectorMode なら inputs を密な特徴ベクトルとして扱う
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_selectActionExNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    inputs: JFloatArray,
+    category: jint,
+    vector_mode: jboolean,
+) -> jint {
+    let mut singularity = agent(handle);
+
+    let input_vec: Vec<f32> = {
+        let len = env.get_array_length(&inputs).unwrap_or(0) as usize;
+        let mut buf = vec![0.0f32; len];
+        env.get_float_array_region(&inputs, 0, &mut buf).unwrap_or(());
+        buf
+    };
+
+    let category = if category >= 0 { Some(category as usize) } else { None };
+    singularity.select_action_compat(&input_vec, category, vector_mode != 0) as jint
+}
+
 #[unsafe(no_mangle)]
 pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_selectActionsNative(
     env: JNIEnv,
@@ -54,7 +108,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     handle: jlong,
     inputs: JFloatArray,
 ) -> jintArray {
-    let singularity = unsafe { &mut *(handle as *mut Singularity) };
+    let mut singularity = agent(handle);
     
     let len = env.get_array_length(&inputs).unwrap_or(0) as usize;
     let mut buf = vec![0.0f32; len];
@@ -76,7 +130,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     indices: JIntArray,
     weights: JFloatArray,
 ) -> jintArray {
-    let singularity = unsafe { &mut *(handle as *mut Singularity) };
+    let mut singularity = agent(handle);
     
     let len = env.get_array_length(&indices).unwrap_or(0) as usize;
     let mut idx_buf = vec![0i32; len];
@@ -104,7 +158,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     handle: jlong,
     reward: jfloat,
 ) {
-    let singularity = unsafe { &mut *(handle as *mut Singularity) };
+    let mut singularity = agent(handle);
     // 最後に選択されたアクション群に対して報酬を適用
     singularity.learn(reward as f32);
 }
@@ -116,7 +170,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     handle: jlong,
     reward: jfloat,
 ) {
-    let singularity = unsafe { &mut *(handle as *mut Singularity) };
+    let mut singularity = agent(handle);
     singularity.learn_vector(reward as f32);
 }
 
@@ -128,8 +182,8 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
 ) {
     if handle != 0 {
         unsafe {
-            // rawポインタをBoxに戻してスコープを抜けることで自動解放
-            let _ = Box::from_raw(handle as *mut Singularity);
+            // rawポインタをBoxに戻してスコープを抜けることで自動解放（学習ワーカーも止まる）
+            let _ = Box::from_raw(handle as *mut LearnWorker);
         }
         println!("DarkSingularity memory released.");
     }
@@ -141,7 +195,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     _class: JClass,
     handle: jlong,
 ) -> jfloat {
-    let singularity = unsafe { &*(handle as *const Singularity) };
+    let singularity = agent(handle);
     singularity.system_temperature as jfloat
 }
 
@@ -152,18 +206,21 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     handle: jlong,
     action_idx: jint,
 ) -> jfloat {
-    let singularity = unsafe { &mut *(handle as *mut Singularity) };
-
-    let mwso_scores = singularity.mwso.get_action_scores(0, singularity.action_size, 0.0, &[]);
-    let idx = action_idx as usize;
+    let mut singularity = agent(handle);
+    singularity.action_score(action_idx.max(0) as usize) as jfloat
+}
 
-    if idx < mwso_scores.len() {
-        let wave_score = mwso_scores[idx];
-        let fatigue = singularity.fatigue_map[idx];
-        (wave_score - (fatigue * 2.0)) as jfloat
-    } else {
-        0.0f32
-    }
+/// 全行動のスコアを呼び出し側の float[] へ書き込み、書き込んだ要素数を返す
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getActionScoresNative(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    out: JFloatArray,
+) -> jint {
+    let mut singularity = agent(handle);
+    let Ok(mut elements) = (unsafe { env.get_array_elements(&out, ReleaseMode::CopyBack) }) else { return 0 };
+    singularity.action_scores_into(&mut elements) as jint
 }
 
 #[unsafe(no_mangle)]
@@ -172,7 +229,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     _class: JClass,
     handle: jlong,
 ) -> jfloat {
-    let singularity = unsafe { &*(handle as *const Singularity) };
+    let singularity = agent(handle);
     singularity.frustration as jfloat
 }
 
@@ -182,7 +239,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     _class: JClass,
     handle: jlong,
 ) -> jfloat {
-    let singularity = unsafe { &*(handle as *const Singularity) };
+    let singularity = agent(handle);
     singularity.adrenaline as jfloat
 }
 
@@ -193,7 +250,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     handle: jlong,
     beta: jfloat,
 ) {
-    let singularity = unsafe { &mut *(handle as *mut Singularity) };
+    let mut singularity = agent(handle);
     singularity.exploration_beta = beta as f32;
 }
 
@@ -203,7 +260,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     _class: JClass,
     handle: jlong,
 ) -> jfloat {
-    let singularity = unsafe { &*(handle as *const Singularity) };
+    let singularity = agent(handle);
     singularity.exploration_beta as jfloat
 }
 
@@ -215,7 +272,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     idx: jint,
     state: jfloat,
 ) {
-    let singularity = unsafe { &mut *(handle as *mut Singularity) };
+    let mut singularity = agent(handle);
     singularity.set_neuron_state(idx as usize, state as f32);
 }
 
@@ -225,7 +282,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     _class: JClass,
     handle: jlong,
 ) -> jfloatArray {
-    let singularity = unsafe { &*(handle as *const Singularity) };
+    let singularity = agent(handle);
     let states: Vec<f32> = singularity.nodes.iter().map(|n| n.state).collect();
 
     let output = env.new_float_array(states.len() as jsize).unwrap();
@@ -240,7 +297,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     handle: jlong,
     path: JString,
 ) -> jint {
-    let singularity = unsafe { &*(handle as *const Singularity) };
+    let singularity = agent(handle);
     let path_str: String = match env.get_string(&path) {
         Ok(s) => s.into(),
         Err(_) => return -1,
@@ -256,7 +313,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     handle: jlong,
     path: JString,
 ) -> jint {
-    let singularity = unsafe { &*(handle as *const Singularity) };
+    let singularity = agent(handle);
 
     let path_str: String = match env.get_string(&path) {
         Ok(s) => s.into(),
@@ -279,7 +336,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     handle: jlong,
     path: JString,
 ) -> jint {
-    let singularity = unsafe { &mut *(handle as *mut Singularity) };
+    let mut singularity = agent(handle);
 
     let path_str: String = match env.get_string(&path) {
         Ok(s) => s.into(),
@@ -302,7 +359,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     handle: jlong,
     condition_ids: JIntArray,
 ) {
-    let singularity = unsafe { &mut *(handle as *mut Singularity) };
+    let mut singularity = agent(handle);
     let len = env.get_array_length(&condition_ids).unwrap_or(0) as usize;
     let mut buf = vec![0i32; len];
     env.get_int_array_region(&condition_ids, 0, &mut buf).unwrap_or(());
@@ -310,6 +367,26 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     singularity.set_active_conditions(&buf);
 }
 
+// 条件を段階的な発動レベル (0.0〜1.0) で与える。ids と levels は同じ長さ（短い方に合わせる）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setConditionLevelsNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    condition_ids: JIntArray,
+    levels: JFloatArray,
+) {
+    let mut singularity = agent(handle);
+    let len = env.get_array_length(&condition_ids).unwrap_or(0).min(env.get_array_length(&levels).unwrap_or(0)) as usize;
+    let mut ids = vec![0i32; len];
+    let mut values = vec![0.0f32; len];
+    env.get_int_array_region(&condition_ids, 0, &mut ids).unwrap_or(());
+    env.get_float_array_region(&levels, 0, &mut values).unwrap_or(());
+
+    let pairs: Vec<(i32, f32)> = ids.into_iter().zip(values).collect();
+    singularity.set_condition_levels(&pairs);
+}
+
 #[unsafe(no_mangle)]
 pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_bootstrapNative(
     env: JNIEnv,
@@ -319,7 +396,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     action_indices: JIntArray,
     strengths: JFloatArray,
 ) {
-    let singularity = unsafe { &mut *(handle as *mut Singularity) };
+    let mut singularity = agent(handle);
     
     let len = env.get_array_length(&condition_indices).unwrap_or(0) as usize;
     let mut conds = vec![0i32; len];
@@ -335,6 +412,328 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     }
 }
 
+// 複合条件ルールを追加する。condition は前置記法 (-1=AND 個数 子..., -2=OR 個数 子..., -3=NOT 子, 0 以上=条件 ID)
+// [-1, 0] は常に真。列が不正なら追加せずに false
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_addCompositeRuleNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    condition: JIntArray,
+    action: jint,
+    strength: jfloat,
+) -> jboolean {
+    let mut singularity = agent(handle);
+    let len = env.get_array_length(&condition).unwrap_or(0) as usize;
+    let mut buf = vec![0i32; len];
+    env.get_int_array_region(&condition, 0, &mut buf).unwrap_or(());
+    match Condition::decode_prefix(&buf) {
+        Some(condition) if action >= 0 => {
+            singularity.bootstrapper.add_composite_rule(condition, action as usize, strength);
+            1
+        }
+        _ => 0,
+    }
+}
+
+// 優先度付きのルールを追加する。condition は addCompositeRuleNative と同じ前置記法（単一の条件 ID も可）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_addPrioritizedRuleNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    condition: JIntArray,
+    action: jint,
+    strength: jfloat,
+    priority: jint,
+) -> jboolean {
+    let mut singularity = agent(handle);
+    let len = env.get_array_length(&condition).unwrap_or(0) as usize;
+    let mut buf = vec![0i32; len];
+    env.get_int_array_region(&condition, 0, &mut buf).unwrap_or(());
+    match Condition::decode_prefix(&buf) {
+        Some(condition) if action >= 0 => {
+            singularity.bootstrapper.add_prioritized_rule(condition, action as usize, strength, priority);
+            1
+        }
+        _ => 0,
+    }
+}
+
+// 期限付きルール（observe_expert の自動生成ルール）の寿命。ttl / max_uses は 0 以下で無期限
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setRuleLifecycleNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    auto_decay: jfloat,
+    ttl: jlong,
+    max_uses: jlong,
+    min_strength: jfloat,
+) {
+    let mut singularity = agent(handle);
+    singularity.bootstrapper.lifecycle = RuleLifecycle {
+        auto_decay: auto_decay.clamp(0.0, 1.0),
+        ttl: (ttl > 0).then_some(ttl as u64),
+        max_uses: (max_uses > 0).then_some(max_uses as u64),
+        min_strength: min_strength.max(0.0),
+    };
+}
+
+// JSON のルールパックを読み込んで既存のルールに追加する。追加したルール数を返す（パス不正は -1、読み込み失敗は -2）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_loadKnowledgeNative(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    path: JString,
+) -> jint {
+    let mut singularity = agent(handle);
+
+    let path_str: String = match env.get_string(&path) {
+        Ok(s) => s.into(),
+        Err(_) => return -1,
+    };
+
+    match singularity.load_knowledge(&path_str) {
+        Ok(added) => added as jint,
+        Err(e) => {
+            println!("Error loading knowledge: {}", e);
+            -2
+        }
+    }
+}
+
+// 現在のルール集を JSON で書き出す (0=成功, -1=パス不正, -2=書き込み失敗)
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_saveKnowledgeNative(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    path: JString,
+) -> jint {
+    let singularity = agent(handle);
+
+    let path_str: String = match env.get_string(&path) {
+        Ok(s) => s.into(),
+        Err(_) => return -1,
+    };
+
+    match singularity.save_knowledge(&path_str) {
+        Ok(_) => 0,
+        Err(e) => {
+            println!("Error saving knowledge: {}", e);
+            -2
+        }
+    }
+}
+
+// 学習済みの選好を確信度 threshold 以上のルールとして抽出し、JSON のルールパックで書き出す
+// (書き出したルール数, -1=パス不正, -2=書き込み失敗)
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_extractPolicyNative(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    threshold: jfloat,
+    path: JString,
+) -> jint {
+    let singularity = agent(handle);
+
+    let path_str: String = match env.get_string(&path) {
+        Ok(s) => s.into(),
+        Err(_) => return -1,
+    };
+
+    let policy = singularity.extract_policy(threshold);
+    match policy.to_json().map_err(std::io::Error::other).and_then(|json| std::fs::write(&path_str, json)) {
+        Ok(_) => policy.rules.len() as jint,
+        Err(e) => {
+            println!("Error extracting policy: {}", e);
+            -2
+        }
+    }
+}
+
+// 連続した行動 [start, start + count) をまとめて対象にするルールを追加する。condition は前置記法
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_addRangeRuleNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    condition: JIntArray,
+    start: jint,
+    count: jint,
+    strength: jfloat,
+    priority: jint,
+) -> jboolean {
+    let mut singularity = agent(handle);
+    let len = env.get_array_length(&condition).unwrap_or(0) as usize;
+    let mut buf = vec![0i32; len];
+    env.get_int_array_region(&condition, 0, &mut buf).unwrap_or(());
+    match Condition::decode_prefix(&buf) {
+        Some(condition) if start >= 0 && count > 0 => {
+            let start = start as usize;
+            singularity.bootstrapper.add_range_rule(condition, start..start + count as usize, strength, priority);
+            1
+        }
+        _ => 0,
+    }
+}
+
+// カテゴリーの全行動を対象にするルールを追加する。condition は前置記法
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_addCategoryRuleNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    condition: JIntArray,
+    category: jint,
+    strength: jfloat,
+    priority: jint,
+) -> jboolean {
+    let mut singularity = agent(handle);
+    let len = env.get_array_length(&condition).unwrap_or(0) as usize;
+    let mut buf = vec![0i32; len];
+    env.get_int_array_region(&condition, 0, &mut buf).unwrap_or(());
+    match Condition::decode_prefix(&buf) {
+        Some(condition) if category >= 0 => singularity.add_category_rule(condition, category as usize, strength, priority) as jboolean,
+        _ => 0,
+    }
+}
+
+// カテゴリー内の行動 [start, start + count)（カテゴリー内の番号）を対象にするルールを追加する。condition は前置記法
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_addCategoryScopedRuleNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    condition: JIntArray,
+    category: jint,
+    start: jint,
+    count: jint,
+    strength: jfloat,
+    priority: jint,
+) -> jboolean {
+    let mut singularity = agent(handle);
+    let len = env.get_array_length(&condition).unwrap_or(0) as usize;
+    let mut buf = vec![0i32; len];
+    env.get_int_array_region(&condition, 0, &mut buf).unwrap_or(());
+    match Condition::decode_prefix(&buf) {
+        Some(condition) if category >= 0 && start >= 0 && count > 0 => {
+            let start = start as usize;
+            singularity.add_category_scoped_rule(condition, category as usize, start..start + count as usize, strength, priority) as jboolean
+        }
+        _ => 0,
+    }
+}
+
+// 条件のとき行動 = expr(状態) を誘導するルールテンプレートを追加する。condition と expr は前置記法
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_addRuleTemplateNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    condition: JIntArray,
+    expr: JIntArray,
+    strength: jfloat,
+) -> jboolean {
+    let mut singularity = agent(handle);
+    let read = |array: &JIntArray| {
+        let len = env.get_array_length(array).unwrap_or(0) as usize;
+        let mut buf = vec![0i32; len];
+        env.get_int_array_region(array, 0, &mut buf).unwrap_or(());
+        buf
+    };
+    let condition = Condition::decode_prefix(&read(&condition));
+    let expr = ActionExpr::decode_prefix(&read(&expr));
+    match (condition, expr) {
+        (Some(condition), Some(expr)) => {
+            singularity.bootstrapper.add_template(condition, expr, strength);
+            1
+        }
+        _ => 0,
+    }
+}
+
+// 条件と行動（範囲ルールは先頭の行動）が一致するルールを取り除き、その数を返す。condition は前置記法（不正なら -1）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_removeRuleNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    condition: JIntArray,
+    action: jint,
+) -> jint {
+    let mut singularity = agent(handle);
+    let len = env.get_array_length(&condition).unwrap_or(0) as usize;
+    let mut buf = vec![0i32; len];
+    env.get_int_array_region(&condition, 0, &mut buf).unwrap_or(());
+    match Condition::decode_prefix(&buf) {
+        Some(condition) if action >= 0 => singularity.bootstrapper.remove_rule(&condition, action as usize) as jint,
+        _ => -1,
+    }
+}
+
+// 条件と行動が一致するルールの強度を書き換え、書き換えた数を返す。condition は前置記法（不正なら -1）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_updateRuleStrengthNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    condition: JIntArray,
+    action: jint,
+    strength: jfloat,
+) -> jint {
+    let mut singularity = agent(handle);
+    let len = env.get_array_length(&condition).unwrap_or(0) as usize;
+    let mut buf = vec![0i32; len];
+    env.get_int_array_region(&condition, 0, &mut buf).unwrap_or(());
+    match Condition::decode_prefix(&buf) {
+        Some(condition) if action >= 0 => singularity.bootstrapper.update_strength(&condition, action as usize, strength) as jint,
+        _ => -1,
+    }
+}
+
+// すべてのハミルトニアン・ルールを取り除く
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_clearRulesNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) {
+    let mut singularity = agent(handle);
+    singularity.bootstrapper.clear();
+}
+
+// 現在のルール集を波動場の事前分布として一度だけ焼き込む（ウォームスタート）。偏りをかけた行動の数を返す
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_applyKnowledgePriorsNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jint {
+    let mut singularity = agent(handle);
+    let bootstrapper = std::mem::take(&mut singularity.bootstrapper);
+    let biased = bootstrapper.apply_to(&mut singularity);
+    singularity.bootstrapper = bootstrapper;
+    biased as jint
+}
+
+// ルールが衝突したときの解決方針 (0=Sum, 1=HighestPriority, 2=MaxMagnitude)
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setRuleConflictPolicyNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    policy: jint,
+) {
+    let mut singularity = agent(handle);
+    if let Some(policy) = RuleConflictPolicy::from_id(policy) {
+        singularity.bootstrapper.policy = policy;
+    }
+}
+
 #[unsafe(no_mangle)]
 pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_observeExpertNative(
     env: JNIEnv,
@@ -344,7 +743,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     expert_actions: JIntArray,
     strength: jfloat,
 ) {
-    let singularity = unsafe { &mut *(handle as *mut Singularity) };
+    let mut singularity = agent(handle);
     let len = env.get_array_length(&expert_actions).unwrap_or(0) as usize;
     let mut actions = vec![0i32; len];
     env.get_int_array_region(&expert_actions, 0, &mut actions).unwrap_or(());
@@ -361,7 +760,7 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     bad_actions: JIntArray,
     strength: jfloat,
 ) {
-    let singularity = unsafe { &mut *(handle as *mut Singularity) };
+    let mut singularity = agent(handle);
     let len = env.get_array_length(&bad_actions).unwrap_or(0) as usize;
     let mut actions = vec![0i32; len];
     env.get_int_array_region(&bad_actions, 0, &mut actions).unwrap_or(());
@@ -369,3 +768,1447 @@ pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singular
     let actions_usize: Vec<usize> = actions.into_iter().map(|a| a as usize).collect();
     singularity.suppress_expert(&actions_usize, strength as f32);
 }
+
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getPenaltiesNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    state_idx: jint,
+) -> jfloatArray {
+    let singularity = agent(handle);
+    let penalties = singularity.get_penalties(state_idx.max(0) as usize);
+
+    let output = env.new_float_array(penalties.len() as jsize).unwrap();
+    env.set_float_array_region(&output, 0, &penalties).unwrap();
+    output.into_raw()
+}
+
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setPenaltyNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    state_idx: jint,
+    action_idx: jint,
+    value: jfloat,
+) {
+    let mut singularity = agent(handle);
+    if state_idx < 0 || action_idx < 0 { return; }
+    singularity.set_penalty(state_idx as usize, action_idx as usize, value);
+}
+
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_observeOpponentNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    state_idx: jint,
+    opponent_action: jint,
+) {
+    let mut singularity = agent(handle);
+    if state_idx < 0 || opponent_action < 0 { return; }
+    singularity.observe_opponent(state_idx as usize, opponent_action as usize);
+}
+
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getDecisionConfidence(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jfloat {
+    let singularity = agent(handle);
+    singularity.decision_confidence() as jfloat
+}
+
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getDecisionUncertainty(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jfloat {
+    let singularity = agent(handle);
+    singularity.decision_uncertainty() as jfloat
+}
+
+// 副作用なしで「この状態ならどう評価するか」を問い合わせる
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_evaluateActionsNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    state_idx: jint,
+) -> jfloatArray {
+    let singularity = agent(handle);
+    let scores = singularity.evaluate_actions(state_idx.max(0) as usize);
+
+    let output = env.new_float_array(scores.len() as jsize).unwrap();
+    env.set_float_array_region(&output, 0, &scores).unwrap();
+    output.into_raw()
+}
+
+// 知識層のみで選ばれる行動（カテゴリーごとの第1位）を返す
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_simulateRulesNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    condition_ids: JIntArray,
+    state_idx: jint,
+) -> jintArray {
+    let singularity = agent(handle);
+    let len = env.get_array_length(&condition_ids).unwrap_or(0) as usize;
+    let mut buf = vec![0i32; len];
+    env.get_int_array_region(&condition_ids, 0, &mut buf).unwrap_or(());
+
+    let ranked = singularity.simulate_rules(&buf, state_idx.max(0) as usize);
+    let best: Vec<i32> = (0..ranked.categories.len())
+        .map(|cat| ranked.best(cat).map_or(-1, |a| a as i32))
+        .collect();
+
+    let output = env.new_int_array(best.len() as jsize).unwrap();
+    env.set_int_array_region(&output, 0, &best).unwrap();
+    output.into_raw()
+}
+
+// 学習をキューに積む（startLearnWorkerNative で開始した学習スレッドか、drainLearnQueueNative で消化する）
+// 学習スレッドが動いていて Block ポリシーでキューが満杯なら、空きができるまで待つ
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_queueLearnNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    reward: jfloat,
+) {
+    worker(handle).queue_learn(reward);
+}
+
+// キューを消化する学習スレッドを開始する（動いていれば何もしない）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_startLearnWorkerNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) {
+    worker(handle).start();
+}
+
+// 学習スレッドを止める（処理中の 1 件が終わるまで待つ。残りはキューに残る）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_stopLearnWorkerNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) {
+    worker(handle).stop();
+}
+
+// キューが空になるまで待つ（学習スレッドが動いていなければ、その場ですべて学習する）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_awaitLearnQueueNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) {
+    worker(handle).wait_idle();
+}
+
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_drainLearnQueueNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    max_items: jint,
+) -> jint {
+    let mut singularity = agent(handle);
+    singularity.drain_learn_queue(max_items.max(0) as usize) as jint
+}
+
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getLearnQueueDepth(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jint {
+    let singularity = agent(handle);
+    singularity.learn_queue_depth() as jint
+}
+
+// policy: 0=Block, 1=DropOldest, 2=Coalesce（範囲外なら現在のポリシーを維持）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setLearnQueuePolicyNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    policy: jint,
+    capacity: jint,
+) {
+    let mut singularity = agent(handle);
+    if let Some(policy) = LearnQueuePolicy::from_id(policy) {
+        singularity.config.learn_queue_policy = policy;
+    }
+    if capacity > 0 {
+        singularity.config.learn_queue_capacity = capacity as usize;
+    }
+}
+
+// 候補モデルをシャドウモードで読み込む（0: 成功, -1: パス不正, -2: 読み込み失敗）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_loadShadowModelNative(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    path: JString,
+    report_after: jint,
+) -> jint {
+    let mut singularity = agent(handle);
+
+    let path_str: String = match env.get_string(&path) {
+        Ok(s) => s.into(),
+        Err(_) => return -1,
+    };
+
+    match singularity.load_shadow_from_file(&path_str, report_after.max(1) as usize) {
+        Ok(_) => 0,
+        Err(e) => {
+            println!("Error loading shadow model: {}", e);
+            -2
+        }
+    }
+}
+
+// [decisions, agreements, agreementRate, liveMeanReward, candidateExpectedReward, estimatedDecisions, complete]
+// シャドウ未接続なら空配列
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getShadowReportNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jfloatArray {
+    let singularity = agent(handle);
+    let values: Vec<f32> = match singularity.shadow_report() {
+        Some(r) => vec![
+            r.decisions as f32,
+            r.agreements as f32,
+            r.agreement_rate,
+            r.live_mean_reward,
+            r.candidate_expected_reward,
+            r.estimated_decisions as f32,
+            if r.complete { 1.0 } else { 0.0 },
+        ],
+        None => Vec::new(),
+    };
+
+    let output = env.new_float_array(values.len() as jsize).unwrap();
+    env.set_float_array_region(&output, 0, &values).unwrap();
+    output.into_raw()
+}
+
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_promoteShadowNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jboolean {
+    let mut singularity = agent(handle);
+    singularity.promote_shadow() as jboolean
+}
+
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_detachShadowNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) {
+    let mut singularity = agent(handle);
+    singularity.detach_shadow();
+}
+
+// 外部からの介入レベル（0.0〜1.0）。温度の上限を引き下げる
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setInterventionLevelNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    level: jfloat,
+) {
+    let mut singularity = agent(handle);
+    singularity.set_intervention_level(level);
+}
+
+// 直近の温度制御の内訳 [rewardDriven, frustrationHeat, floor, ceiling, result]（未実行なら空配列）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getTemperatureTraceNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jfloatArray {
+    let singularity = agent(handle);
+    let values: Vec<f32> = singularity.temperature_trace.back()
+        .map(|t| vec![t.reward_driven, t.frustration_heat, t.floor, t.ceiling, t.result])
+        .unwrap_or_default();
+
+    let output = env.new_float_array(values.len() as jsize).unwrap();
+    env.set_float_array_region(&output, 0, &values).unwrap();
+    output.into_raw()
+}
+
+// 密な stateSize を持たない Feature Hashing モードのインスタンスを生成する
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_initHashedSingularity(
+    env: JNIEnv,
+    _class: JClass,
+    category_sizes: JIntArray,
+) -> jlong {
+    let len = env.get_array_length(&category_sizes).unwrap_or(0) as usize;
+    let mut cat_buf = vec![0i32; len];
+    env.get_int_array_region(&category_sizes, 0, &mut cat_buf).unwrap_or(());
+
+    let cat_sizes: Vec<usize> = cat_buf.into_iter().map(|s| s as usize).collect();
+
+    into_handle(Singularity::new_hashed(cat_sizes))
+}
+
+// 名前付きノード構成でインスタンスを生成する（名前と減衰率の長さが合わない場合は 0）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_initNamedNodesSingularity(
+    mut env: JNIEnv,
+    _class: JClass,
+    state_size: jint,
+    category_sizes: JIntArray,
+    node_names: JObjectArray,
+    node_decays: JFloatArray,
+) -> jlong {
+    let len = env.get_array_length(&category_sizes).unwrap_or(0) as usize;
+    let mut cat_buf = vec![0i32; len];
+    env.get_int_array_region(&category_sizes, 0, &mut cat_buf).unwrap_or(());
+    let cat_sizes: Vec<usize> = cat_buf.into_iter().map(|s| s as usize).collect();
+
+    let count = env.get_array_length(&node_names).unwrap_or(0);
+    let mut decays = vec![0.0f32; env.get_array_length(&node_decays).unwrap_or(0) as usize];
+    if decays.len() != count as usize { return 0; }
+    env.get_float_array_region(&node_decays, 0, &mut decays).unwrap_or(());
+
+    let mut specs = Vec::with_capacity(decays.len());
+    for (i, decay) in decays.into_iter().enumerate() {
+        let name: String = match env.get_object_array_element(&node_names, i as jsize)
+            .and_then(|obj| env.get_string(&JString::from(obj)).map(String::from))
+        {
+            Ok(name) => name,
+            Err(_) => return 0,
+        };
+        specs.push(NodeSpec::new(&name, decay));
+    }
+
+    into_handle(Singularity::with_nodes(state_size as usize, cat_sizes, &specs))
+}
+
+// 実行中にノードを追加し、そのインデックスを返す（同名のノードがある・不正な文字列なら -1）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_addNodeNative(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    name: JString,
+    decay: jfloat,
+) -> jint {
+    let mut singularity = agent(handle);
+    let name: String = match env.get_string(&name) {
+        Ok(s) => s.into(),
+        Err(_) => return -1,
+    };
+    singularity.add_node(&name, decay).map_or(-1, |idx| idx as jint)
+}
+
+// ノードを取り除く（範囲外なら false）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_removeNodeNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    idx: jint,
+) -> jboolean {
+    let mut singularity = agent(handle);
+    if idx >= 0 && singularity.remove_node(idx as usize) { 1 } else { 0 }
+}
+
+// ノードの応答曲線を切り替える（kind: 0=Linear, 1=Sigmoid, 2=Tanh, 3=ReluCap。cap は ReluCap の上限）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setNodeActivationNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    idx: jint,
+    kind: jint,
+    cap: jfloat,
+) -> jboolean {
+    let mut singularity = agent(handle);
+    match Activation::from_id(kind, cap) {
+        Some(activation) if idx >= 0 && singularity.set_node_activation(idx as usize, activation) => 1,
+        _ => 0,
+    }
+}
+
+// ノードの不応期を設定する（period: 発火後に入力へ応答しない更新回数、0 で無効）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setRefractoryNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    period: jint,
+    spike_threshold: jfloat,
+) {
+    let mut singularity = agent(handle);
+    singularity.set_refractory(period.max(0) as u32, spike_threshold);
+}
+
+// 感情ノードの活性で行動 [start, start + count) のビンを減衰させる結合を加える（不正な指定なら false）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_addNodeCouplingNative(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    node: JString,
+    start: jint,
+    count: jint,
+    gain: jfloat,
+) -> jboolean {
+    let mut singularity = agent(handle);
+    let node: String = match env.get_string(&node) {
+        Ok(s) => s.into(),
+        Err(_) => return 0,
+    };
+    if start < 0 || count <= 0 { return 0; }
+    let start = start as usize;
+    if singularity.add_node_coupling(&node, start..start + count as usize, gain) { 1 } else { 0 }
+}
+
+// 感情ノードと行動帯域の結合をすべて外す
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_clearNodeCouplingsNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) {
+    let mut singularity = agent(handle);
+    singularity.node_couplings.clear();
+}
+
+// ノードの直近の活性（古い順。範囲外なら空配列）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getNodeHistoryNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    idx: jint,
+) -> jfloatArray {
+    let singularity = agent(handle);
+    let values = if idx >= 0 { singularity.node_history(idx as usize) } else { Vec::new() };
+
+    let output = env.new_float_array(values.len() as jsize).unwrap();
+    env.set_float_array_region(&output, 0, &values).unwrap();
+    output.into_raw()
+}
+
+// ノードの活性履歴の要約 [平均, 分散, 傾向]（範囲外か履歴が空なら空配列）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getNodeHistoryStatsNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    idx: jint,
+) -> jfloatArray {
+    let singularity = agent(handle);
+    let values: Vec<f32> = (idx >= 0).then(|| singularity.node_history_stats(idx as usize)).flatten()
+        .map(|stats| vec![stats.mean, stats.variance, stats.trend])
+        .unwrap_or_default();
+
+    let output = env.new_float_array(values.len() as jsize).unwrap();
+    env.set_float_array_region(&output, 0, &values).unwrap();
+    output.into_raw()
+}
+
+// 名前付きのゲーム信号をノードへ結びつける（入力 = 値 × gain + offset。不正な文字列なら false）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_bindSensorNative(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    signal: JString,
+    node: JString,
+    gain: jfloat,
+    offset: jfloat,
+) -> jboolean {
+    let mut singularity = agent(handle);
+    let (signal, node): (String, String) = match (env.get_string(&signal), env.get_string(&node)) {
+        (Ok(signal), Ok(node)) => (signal.into(), node.into()),
+        _ => return 0,
+    };
+    singularity.sensors.bind(&signal, &node, gain, offset);
+    1
+}
+
+// 信号名のハッシュ (String.hashCode) と値の組でノードを更新する
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_feedSensorsNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    name_hashes: JIntArray,
+    values: JFloatArray,
+    urgency: jfloat,
+) {
+    let mut singularity = agent(handle);
+    let mut hashes = vec![0i32; env.get_array_length(&name_hashes).unwrap_or(0) as usize];
+    env.get_int_array_region(&name_hashes, 0, &mut hashes).unwrap_or(());
+    let mut vals = vec![0.0f32; env.get_array_length(&values).unwrap_or(0) as usize];
+    env.get_float_array_region(&values, 0, &mut vals).unwrap_or(());
+
+    let signals: Vec<(i32, f32)> = hashes.into_iter().zip(vals).collect();
+    singularity.feed_sensors_hashed(&signals, urgency);
+}
+
+// 神経修飾物質（ドーパミン・セロトニン）による学習率・疲労・探索ノイズの調整を有効化する
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_enableNeuromodulatorsNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) {
+    let mut singularity = agent(handle);
+    singularity.enable_neuromodulators();
+}
+
+// 神経修飾物質の現在値 [ドーパミン, セロトニン, 期待報酬]（無効なら空配列）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getNeuromodulatorsNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jfloatArray {
+    let singularity = agent(handle);
+    let values: Vec<f32> = singularity.neuromodulators
+        .map(|n| vec![n.dopamine, n.serotonin, n.expected_reward])
+        .unwrap_or_default();
+
+    let output = env.new_float_array(values.len() as jsize).unwrap();
+    env.set_float_array_region(&output, 0, &values).unwrap();
+    output.into_raw()
+}
+
+// 性格プリセットを切り替える（0=Aggressive, 1=Cautious, 2=Adaptive, 3=Berserker。不正な値なら false）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setPersonalityNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    personality: jint,
+) -> jboolean {
+    let mut singularity = agent(handle);
+    match Personality::from_id(personality) {
+        Some(p) => { singularity.set_personality(p); 1 }
+        None => 0,
+    }
+}
+
+// ノードの結合と活性を GraphViz (DOT) 形式の文字列で返す
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_exportTopologyDotNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jstring {
+    let singularity = agent(handle);
+    env.new_string(singularity.export_topology_dot()).map(|s| s.into_raw()).unwrap_or(std::ptr::null_mut())
+}
+
+// ノード名からインデックスを引く（存在しない・不正な文字列なら -1）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getNodeIndexNative(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    name: JString,
+) -> jint {
+    let singularity = agent(handle);
+    let name: String = match env.get_string(&name) {
+        Ok(s) => s.into(),
+        Err(_) => return -1,
+    };
+    singularity.node_index(&name).map_or(-1, |idx| idx as jint)
+}
+
+// 任意の 64bit 状態識別子で意思決定する
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_selectActionsIdNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    state_id: jlong,
+) -> jintArray {
+    let mut singularity = agent(handle);
+    let actions = singularity.select_actions_id(state_id as u64);
+
+    let output = env.new_int_array(actions.len() as jsize).unwrap();
+    env.set_int_array_region(&output, 0, &actions).unwrap();
+    output.into_raw()
+}
+
+// 特徴ベクトル (特徴ID, 値) で意思決定する
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_selectActionsFeaturesNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    feature_ids: JLongArray,
+    values: JFloatArray,
+) -> jintArray {
+    let mut singularity = agent(handle);
+
+    let len = env.get_array_length(&feature_ids).unwrap_or(0) as usize;
+    let mut id_buf = vec![0i64; len];
+    let mut value_buf = vec![0.0f32; len];
+    env.get_long_array_region(&feature_ids, 0, &mut id_buf).unwrap_or(());
+    env.get_float_array_region(&values, 0, &mut value_buf).unwrap_or(());
+
+    let features: Vec<(u64, f32)> = id_buf.into_iter().zip(value_buf)
+        .map(|(id, v)| (id as u64, v))
+        .collect();
+    let actions = singularity.select_actions_features(&features);
+
+    let output = env.new_int_array(actions.len() as jsize).unwrap();
+    env.set_int_array_region(&output, 0, &actions).unwrap();
+    output.into_raw()
+}
+
+// 連続特徴のタイルコーディングを設定する（mins/maxs は特徴ごとの範囲）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setTileCoderNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    mins: JFloatArray,
+    maxs: JFloatArray,
+    tiles_per_dim: jint,
+    tilings: jint,
+) {
+    let mut singularity = agent(handle);
+
+    let len = env.get_array_length(&mins).unwrap_or(0) as usize;
+    let mut min_buf = vec![0.0f32; len];
+    let mut max_buf = vec![0.0f32; len];
+    env.get_float_array_region(&mins, 0, &mut min_buf).unwrap_or(());
+    env.get_float_array_region(&maxs, 0, &mut max_buf).unwrap_or(());
+
+    let ranges: Vec<(f32, f32)> = min_buf.into_iter().zip(max_buf).collect();
+    singularity.set_tile_coder(TileCoder::new(ranges, tiles_per_dim.max(1) as usize, tilings.max(1) as usize));
+}
+
+// 連続特徴で意思決定する（タイルコーダ未設定なら空配列）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_selectActionsContinuousNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    inputs: JFloatArray,
+) -> jintArray {
+    let mut singularity = agent(handle);
+
+    let len = env.get_array_length(&inputs).unwrap_or(0) as usize;
+    let mut buf = vec![0.0f32; len];
+    env.get_float_array_region(&inputs, 0, &mut buf).unwrap_or(());
+
+    let actions = singularity.select_actions_continuous(&buf).unwrap_or_default();
+
+    let output = env.new_int_array(actions.len() as jsize).unwrap();
+    env.set_int_array_region(&output, 0, &actions).unwrap();
+    output.into_raw()
+}
+
+// n ステップ報酬の地平線を設定する（n <= 0 で従来の割引モンテカルロ）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setNStepNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    n: jint,
+) {
+    let mut singularity = agent(handle);
+    singularity.config.n_step = if n > 0 { Some(n as usize) } else { None };
+}
+
+// 保存済みの軌跡から方策外学習する
+// actions はステップごとにカテゴリー数ぶん並んだ平坦な配列、importance はステップごとの π/μ
+// 負の状態のステップと負の行動は取り除く（範囲外の値は learn_trajectory が読み飛ばす）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_learnTrajectoryNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    states: JIntArray,
+    actions: JIntArray,
+    importance: JFloatArray,
+    reward: jfloat,
+) {
+    let mut singularity = agent(handle);
+
+    let steps = env.get_array_length(&states).unwrap_or(0) as usize;
+    let action_len = env.get_array_length(&actions).unwrap_or(0) as usize;
+    let mut state_buf = vec![0i32; steps];
+    let mut action_buf = vec![0i32; action_len];
+    let mut weight_buf = vec![1.0f32; steps];
+    env.get_int_array_region(&states, 0, &mut state_buf).unwrap_or(());
+    env.get_int_array_region(&actions, 0, &mut action_buf).unwrap_or(());
+    env.get_float_array_region(&importance, 0, &mut weight_buf).unwrap_or(());
+
+    let per_step = singularity.category_sizes.len();
+    if steps == 0 || action_len < steps * per_step { return; }
+    let trajectory: Vec<TrajectoryStep> = (0..steps).filter(|&i| state_buf[i] >= 0).map(|i| TrajectoryStep {
+        state_idx: state_buf[i] as usize,
+        actions: action_buf[i * per_step..(i + 1) * per_step].iter().filter(|&&a| a >= 0).map(|&a| a as usize).collect(),
+        importance: weight_buf[i],
+    }).collect();
+    singularity.learn_trajectory(&trajectory, reward);
+}
+
+// オフライン学習用のバッチ API（actions はステップごとにカテゴリー数ぶん並んだ平坦な配列）
+// 負の状態の遷移と負の行動は取り除く（範囲外の値は learn_batch が読み飛ばす）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_learnBatchNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    states: JIntArray,
+    actions: JIntArray,
+    rewards: JFloatArray,
+) {
+    let mut singularity = agent(handle);
+
+    let steps = env.get_array_length(&states).unwrap_or(0) as usize;
+    let action_len = env.get_array_length(&actions).unwrap_or(0) as usize;
+    let mut state_buf = vec![0i32; steps];
+    let mut action_buf = vec![0i32; action_len];
+    let mut reward_buf = vec![0.0f32; steps];
+    env.get_int_array_region(&states, 0, &mut state_buf).unwrap_or(());
+    env.get_int_array_region(&actions, 0, &mut action_buf).unwrap_or(());
+    env.get_float_array_region(&rewards, 0, &mut reward_buf).unwrap_or(());
+
+    let per_step = singularity.category_sizes.len();
+    if steps == 0 || action_len < steps * per_step { return; }
+    let batch: Vec<(usize, Vec<usize>, f32)> = (0..steps).filter(|&i| state_buf[i] >= 0).map(|i| (
+        state_buf[i] as usize,
+        action_buf[i * per_step..(i + 1) * per_step].iter().filter(|&&a| a >= 0).map(|&a| a as usize).collect(),
+        reward_buf[i],
+    )).collect();
+    singularity.learn_batch(&batch);
+}
+
+// エピソードの記録を開始する
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_startRecordingNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) {
+    let mut singularity = agent(handle);
+    singularity.start_recording();
+}
+
+// 記録を終了し、ログをファイルへ保存する（0: 成功, -1: パス不正, -2: 書き込み失敗, -3: 記録していない,
+// -4: 記録されない意思決定・学習の経路が使われたため再現できない）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_stopRecordingNative(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    path: JString,
+) -> jint {
+    let mut singularity = agent(handle);
+
+    let path_str: String = match env.get_string(&path) {
+        Ok(s) => s.into(),
+        Err(_) => return -1,
+    };
+    let Some(log) = singularity.stop_recording() else { return -3; };
+    if !log.is_replayable() { return -4; }
+
+    match log.save(&path_str) {
+        Ok(_) => 0,
+        Err(e) => {
+            println!("Error saving episode log: {}", e);
+            -2
+        }
+    }
+}
+
+// 保存済みのログをこのインスタンスへ再投入する
+// 戻り値: [decisions, rewards, actionMismatches, firstDivergence(-1 なら一致)]（読み込み失敗・再生不可の場合は空配列）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_replayEpisodeNative(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    path: JString,
+) -> jintArray {
+    let mut singularity = agent(handle);
+
+    let values: Vec<i32> = match env.get_string(&path).map(String::from) {
+        Ok(path_str) => match EpisodeLog::load(&path_str) {
+            Ok(log) => match log.replay(&mut singularity) {
+                Some(report) => vec![
+                    report.decisions as i32,
+                    report.rewards as i32,
+                    report.action_mismatches as i32,
+                    report.first_divergence.map_or(-1, |i| i as i32),
+                ],
+                None => Vec::new(),
+            },
+            Err(e) => {
+                println!("Error loading episode log: {}", e);
+                Vec::new()
+            }
+        },
+        Err(_) => Vec::new(),
+    };
+
+    let output = env.new_int_array(values.len() as jsize).unwrap();
+    env.set_int_array_region(&output, 0, &values).unwrap();
+    output.into_raw()
+}
+
+// 探索戦略を切り替える (0=TopKSoftmax, 1=Greedy, 2=EpsilonGreedy, 3=Boltzmann, 4=Ucb)
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setExplorationPolicyNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    policy: jint,
+) {
+    let mut singularity = agent(handle);
+    if let Some(policy) = ExplorationPolicy::from_id(policy) {
+        singularity.config.exploration_policy = policy;
+    }
+}
+
+// 行動スコアに記憶波との干渉項を加える (weight = 0 で無効)
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setInterferenceScoringNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    weight: jfloat,
+) {
+    let mut singularity = agent(handle);
+    singularity.set_interference_scoring(weight);
+}
+
+// 行動スコアを粗い推定の上位 k 件だけ正確に求める (k = 0 で全件)
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setScoreTopKNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    k: jint,
+) {
+    let mut singularity = agent(handle);
+    singularity.set_score_top_k(k.max(0) as usize);
+}
+
+// 重力場の形成閾値・増分・上限・失敗時の侵食係数・蒸発係数を設定する
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setGravityParamsNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    reward_threshold: jfloat,
+    increment: jfloat,
+    cap: jfloat,
+    failure_erosion: jfloat,
+    hawking_decay: jfloat,
+) {
+    let mut singularity = agent(handle);
+    singularity.set_mwso_config(MWSOConfig {
+        gravity_reward_threshold: reward_threshold,
+        gravity_increment: increment,
+        gravity_cap: cap,
+        gravity_failure_erosion: failure_erosion.clamp(0.0, 1.0),
+        hawking_decay: hawking_decay.clamp(0.0, 1.0),
+    });
+}
+
+// 隣接ビン間の結合項を切り替える (0=Linear, 1=Kuramoto)
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setCouplingNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    coupling: jint,
+) {
+    let mut singularity = agent(handle);
+    if let Some(coupling) = Coupling::from_id(coupling) {
+        singularity.set_coupling(coupling);
+    }
+}
+
+// 別のエージェントの波の、指定した行動のビンを自分の波へ引き寄せる（空配列なら共通する全行動）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_entangleWithNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    other_handle: jlong,
+    actions: JIntArray,
+    strength: jfloat,
+) {
+    if handle == other_handle { return; }
+    // 逆向きの呼び出しと同時に走ってもデッドロックしないよう、アドレスの小さい側から先にロックする
+    let (mut singularity, other) = if handle < other_handle {
+        let singularity = agent(handle);
+        (singularity, agent(other_handle))
+    } else {
+        let other = agent(other_handle);
+        (agent(handle), other)
+    };
+    let len = env.get_array_length(&actions).unwrap_or(0) as usize;
+    let mut buf = vec![0i32; len];
+    env.get_int_array_region(&actions, 0, &mut buf).unwrap_or(());
+    let actions: Vec<usize> = buf.into_iter().filter(|&a| a >= 0).map(|a| a as usize).collect();
+    singularity.entangle_with(&other, &actions, strength);
+}
+
+// 行動の担当ビンへの外部場を duration ステップ分予約する (decay: 0=Constant, 1=Linear, 2=Exponential)
+// 取り消し用の ID を返す。decay が不正なら -1
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_scheduleActionFieldNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    actions: JIntArray,
+    strength: jfloat,
+    duration: jint,
+    decay: jint,
+    half_life: jfloat,
+) -> jlong {
+    let mut singularity = agent(handle);
+    let Some(decay) = FieldDecay::from_id(decay, half_life) else { return -1 };
+    let len = env.get_array_length(&actions).unwrap_or(0) as usize;
+    let mut buf = vec![0i32; len];
+    env.get_int_array_region(&actions, 0, &mut buf).unwrap_or(());
+    let actions: Vec<usize> = buf.into_iter().filter(|&a| a >= 0).map(|a| a as usize).collect();
+    singularity.schedule_action_field(&actions, strength, duration.max(0) as u32, decay) as jlong
+}
+
+// 予約した外部場を取り消す
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_cancelScheduledFieldNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    id: jlong,
+) -> jboolean {
+    let mut singularity = agent(handle);
+    (id >= 0 && singularity.cancel_scheduled_field(id as u64)) as jboolean
+}
+
+// ペナルティ行列と重力場を保持する精度を切り替える (0=F32, 1=F16, 2=I8)
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setFieldPrecisionNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    precision: jint,
+) {
+    let mut singularity = agent(handle);
+    if let Some(precision) = FieldPrecision::from_id(precision) {
+        singularity.set_field_precision(precision);
+    }
+}
+
+// 波の時間発展の積分法を切り替える (0=Explicit, 1=Symplectic)
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setIntegratorNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    integrator: jint,
+) {
+    let mut singularity = agent(handle);
+    if let Some(integrator) = Integrator::from_id(integrator) {
+        singularity.set_integrator(integrator);
+    }
+}
+
+// 時間予算 (マイクロ秒) 付きで行動を選択する。縮退モードだったかは wasLastDecisionDegradedNative で取得する
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_selectActionsWithinNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    state_idx: jint,
+    budget_micros: jlong,
+) -> jintArray {
+    let mut singularity = agent(handle);
+    let budget = Duration::from_micros(budget_micros.max(0) as u64);
+    let (actions, _) = singularity.select_actions_within(state_idx.max(0) as usize, budget);
+
+    let output = env.new_int_array(actions.len() as jsize).unwrap();
+    env.set_int_array_region(&output, 0, &actions).unwrap();
+    output.into_raw()
+}
+
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_wasLastDecisionDegradedNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jboolean {
+    let singularity = agent(handle);
+    singularity.last_decision_degraded as jboolean
+}
+
+// 計測値を返す: [select_actions, learn, save_to_file] の順に (回数, 最小, 平均, p99) を各4要素（時間はマイクロ秒）、
+// 続いて履歴長 n、波動ノルム n 件、温度 n 件
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getStatsNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jfloatArray {
+    let singularity = agent(handle);
+    let stats = singularity.stats();
+
+    let mut values = Vec::with_capacity(13 + stats.wave_norm.len() * 2);
+    for summary in [stats.select_actions, stats.learn, stats.save_to_file] {
+        values.push(summary.count as f32);
+        values.push(summary.min.as_secs_f32() * 1e6);
+        values.push(summary.avg.as_secs_f32() * 1e6);
+        values.push(summary.p99.as_secs_f32() * 1e6);
+    }
+    values.push(stats.wave_norm.len() as f32);
+    values.extend_from_slice(&stats.wave_norm);
+    values.extend_from_slice(&stats.temperature);
+
+    let output = env.new_float_array(values.len() as jsize).unwrap();
+    env.set_float_array_region(&output, 0, &values).unwrap();
+    output.into_raw()
+}
+
+// 直近の意思決定で知識層に寄与したルールを (ルール ID, 行動, 寄与) の 3 要素ずつ並べて返す
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getLastRuleActivationsNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jfloatArray {
+    let singularity = agent(handle);
+    let values: Vec<f32> = singularity.last_rule_activations().iter()
+        .flat_map(|a| [a.rule as f32, a.action as f32, a.contribution])
+        .collect();
+
+    let output = env.new_float_array(values.len() as jsize).unwrap();
+    env.set_float_array_region(&output, 0, &values).unwrap();
+    output.into_raw()
+}
+
+// 記憶波バンクを追加し、そのバンク番号を返す（失敗時は -1）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_addMemoryBankNative(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    label: JString,
+) -> jint {
+    let mut singularity = agent(handle);
+    let label: String = match env.get_string(&label) {
+        Ok(s) => s.into(),
+        Err(_) => return -1,
+    };
+    singularity.mwso.add_memory_bank(&label) as jint
+}
+
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_switchMemoryBankNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    bank: jint,
+) -> jboolean {
+    let mut singularity = agent(handle);
+    if bank < 0 { return 0; }
+    singularity.mwso.switch_memory_bank(bank as usize) as jboolean
+}
+
+// 刻み込み先バンクの選び方を切り替える (0=Active, 1=ByState, 2=Resonance)
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setMemoryBankRoutingNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    policy: jint,
+) {
+    let mut singularity = agent(handle);
+    if let Some(policy) = BankRoutingPolicy::from_id(policy) {
+        singularity.mwso.bank_routing = policy;
+    }
+}
+
+// 状態 stateIdx で各バンクに問い合わせたときの共鳴（バンク番号順）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getBankResonancesNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    state_idx: jint,
+) -> jfloatArray {
+    let singularity = agent(handle);
+    let resonances: Vec<f32> = singularity.mwso.bank_resonances(state_idx.max(0) as usize)
+        .into_iter().map(|r| r as f32).collect();
+
+    let output = env.new_float_array(resonances.len() as jsize).unwrap();
+    env.set_float_array_region(&output, 0, &resonances).unwrap();
+    output.into_raw()
+}
+
+// 探索ノイズの分布を切り替える (0=Uniform, 1=Gaussian, 2=OrnsteinUhlenbeck, 3=Pink)
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setExplorationNoiseModeNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    mode: jint,
+) {
+    let mut singularity = agent(handle);
+    if let Some(mode) = ExplorationNoise::from_id(mode) {
+        singularity.set_exploration_noise(mode);
+    }
+}
+
+// 固有振動数のプリセット (0=Linear, 1=Quadratic, 2=HarmonicStack(a=倍音の数), 3=BandLimited(a=下端, b=上端))
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setSpectrumNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    preset: jint,
+    a: jfloat,
+    b: jfloat,
+) {
+    let mut singularity = agent(handle);
+    if let Some(spectrum) = Spectrum::from_id(preset, a, b) {
+        singularity.set_spectrum(spectrum);
+    }
+}
+
+// 固有振動数を任意の配列で置き換える（長さが次元と違えば false）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setFrequenciesNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    frequencies: JFloatArray,
+) -> jboolean {
+    let mut singularity = agent(handle);
+    let len = env.get_array_length(&frequencies).unwrap_or(0) as usize;
+    let mut buf = vec![0.0f32; len];
+    if env.get_float_array_region(&frequencies, 0, &mut buf).is_err() { return 0; }
+    singularity.set_frequencies(&buf) as jboolean
+}
+
+// 重力場の要約 [平均, 最大, threshold を超えたビンの数]
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getGravityStatsNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    threshold: jfloat,
+) -> jfloatArray {
+    let singularity = agent(handle);
+    let stats = singularity.gravity_stats(threshold);
+    let values = [stats.mean, stats.max, stats.above_threshold as f32];
+
+    let output = env.new_float_array(values.len() as jsize).unwrap();
+    env.set_float_array_region(&output, 0, &values).unwrap();
+    output.into_raw()
+}
+
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getGravityForActionNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    action: jint,
+) -> jfloat {
+    let singularity = agent(handle);
+    if action < 0 { return 0.0; }
+    singularity.get_gravity_for_action(action as usize)
+}
+
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_erodeGravityNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    action: jint,
+    amount: jfloat,
+) {
+    let mut singularity = agent(handle);
+    if action < 0 { return; }
+    singularity.erode_gravity(action as usize, amount);
+}
+
+// アクションの担当ビンの位相コヒーレンス（蔵本の秩序変数, 0.0〜1.0）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getPhaseCoherenceNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    action: jint,
+) -> jfloat {
+    let singularity = agent(handle);
+    if action < 0 { return 0.0; }
+    singularity.phase_coherence(action as usize)
+}
+
+// 状態コードを切り替える (0=PrimeStride, 1=RandomPhase, 2=Orthogonal)
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setStateCodingNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    coding: jint,
+) {
+    let mut singularity = agent(handle);
+    if let Some(coding) = StateCoding::from_id(coding) {
+        singularity.set_state_coding(coding);
+    }
+}
+
+// 行動ごとのビン数を重みの比で割り当てる（シャード化されている・行動数と長さが違う場合は false）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setActionWeightsNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    weights: JFloatArray,
+) -> jboolean {
+    let mut singularity = agent(handle);
+
+    let len = env.get_array_length(&weights).unwrap_or(0) as usize;
+    let mut buf = vec![0.0f32; len];
+    env.get_float_array_region(&weights, 0, &mut buf).unwrap_or(());
+
+    singularity.set_action_weights(&buf) as jboolean
+}
+
+// 取り出し SNR による次元の自動拡張を設定する（snr <= 0 で無効）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setAutoGrowNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    snr: jfloat,
+    interval: jint,
+    max_dim: jint,
+) {
+    let mut singularity = agent(handle);
+    singularity.config.auto_grow_snr = snr.max(0.0);
+    singularity.config.auto_grow_interval = interval.max(0) as u64;
+    singularity.config.auto_grow_max_dim = max_dim.max(0) as usize;
+}
+
+// 状態の記憶の取り出し SNR を取得する
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getRetrievalSnrNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    state_idx: jint,
+) -> jfloat {
+    let singularity = agent(handle);
+    singularity.retrieval_snr(state_idx.max(0) as usize)
+}
+
+// 記憶の統合を設定する（capacity: 再生するパターンの保持数、interval: learn 回数ごとの実行間隔、0 で無効）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setConsolidationNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    capacity: jint,
+    interval: jint,
+    decay: jfloat,
+) {
+    let mut singularity = agent(handle);
+    singularity.set_replay_capacity(capacity.max(0) as usize);
+    singularity.config.consolidate_interval = interval.max(0) as u64;
+    singularity.config.consolidate_decay = decay.clamp(0.0, 1.0);
+}
+
+// シナプスのヘッブ可塑性を設定する（rate: 学習率、0 で無効。重みは [minWeight, maxWeight] に収める）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setPlasticityNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    rate: jfloat,
+    min_weight: jfloat,
+    max_weight: jfloat,
+) {
+    let mut singularity = agent(handle);
+    singularity.config.plasticity_rate = rate.max(0.0);
+    singularity.config.synapse_weight_min = min_weight.min(max_weight);
+    singularity.config.synapse_weight_max = max_weight.max(min_weight);
+}
+
+// 記憶の統合をその場で 1 回行い、再生したパターン数を返す
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_consolidateMemoryNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jint {
+    let mut singularity = agent(handle);
+    singularity.consolidate_memory() as jint
+}
+
+// 状態の記憶を問い合わせる: [共鳴, 最良の行動 (無ければ -1), 行動ごとの平均報酬 (未経験は NaN)...]
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_recallStateNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    state_idx: jint,
+) -> jfloatArray {
+    let singularity = agent(handle);
+    let recall = singularity.recall_state(state_idx.max(0) as usize);
+    let mut values = vec![recall.wave.resonance, recall.best_action().map_or(-1.0, |a| a as f32)];
+    values.extend(recall.actions.iter().map(|&(mean, count)| if count > 0 { mean } else { f32::NAN }));
+
+    let output = env.new_float_array(values.len() as jsize).unwrap();
+    env.set_float_array_region(&output, 0, &values).unwrap();
+    output.into_raw()
+}
+
+// 現在の波で状態を刻み込む（label が null でなければラベル付きで索引に記録する）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_imprintMemoryNative(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    state_idx: jint,
+    reward: jfloat,
+    label: JString,
+) {
+    let mut singularity = agent(handle);
+    let label: Option<String> = if label.is_null() {
+        None
+    } else {
+        match env.get_string(&label) {
+            Ok(s) => Some(s.into()),
+            Err(_) => return,
+        }
+    };
+    singularity.imprint_memory(state_idx.max(0) as usize, reward, label.as_deref());
+}
+
+// 現在の波と共鳴しているラベル付きの記憶を "ラベル\t共鳴" の行で返す（共鳴の高い順）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_getResonantMemoriesNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    min_resonance: jfloat,
+) -> jstring {
+    let singularity = agent(handle);
+    let lines: Vec<String> = singularity.resonant_memories(min_resonance).into_iter()
+        .map(|(label, r)| format!("{}\t{}", label, r))
+        .collect();
+    env.new_string(lines.join("\n")).map(|s| s.into_raw()).unwrap_or(std::ptr::null_mut())
+}
+
+// 記憶波の忘却曲線の半減期（step_core の回数、0 で無効）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_setMemoryHalfLifeNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    half_life: jfloat,
+) {
+    let mut singularity = agent(handle);
+    singularity.set_memory_half_life(half_life);
+}
+
+// 位相結合構造 theta を書き出す
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_exportThetaNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jfloatArray {
+    let singularity = agent(handle);
+    let theta = singularity.export_theta();
+
+    let output = env.new_float_array(theta.len() as jsize).unwrap();
+    env.set_float_array_region(&output, 0, &theta).unwrap();
+    output.into_raw()
+}
+
+// exportThetaNative で書き出した theta を読み込む（次元が違えば補間する）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_importThetaNative(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    theta: JFloatArray,
+) -> jboolean {
+    let mut singularity = agent(handle);
+    let len = env.get_array_length(&theta).unwrap_or(0) as usize;
+    let mut buf = vec![0.0f32; len];
+    if env.get_float_array_region(&theta, 0, &mut buf).is_err() { return 0; }
+    singularity.import_theta(&buf) as jboolean
+}
+
+// 波のスナップショットを読むハンドルを作る（closeWaveViewNative で解放する）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_openWaveViewNative(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jlong {
+    let mut singularity = agent(handle);
+    Box::into_raw(Box::new(singularity.wave_view())) as jlong
+}
+
+// 最新のフレーム [step, dim, psi_real..., psi_imag..., theta...]（Singularity には触れないため、どのスレッドから呼んでもよい）
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_readWaveViewNative(
+    env: JNIEnv,
+    _class: JClass,
+    view: jlong,
+) -> jfloatArray {
+    let view = unsafe { &*(view as *mut WaveView) };
+    let frame = view.load();
+    let mut values = Vec::with_capacity(2 + frame.psi_real.len() * 2 + frame.theta.len());
+    values.push(frame.step as f32);
+    values.push(frame.dim() as f32);
+    for part in [&frame.psi_real, &frame.psi_imag, &frame.theta] {
+        values.extend(part.iter().map(|&v| from_wave(v)));
+    }
+
+    let output = env.new_float_array(values.len() as jsize).unwrap();
+    env.set_float_array_region(&output, 0, &values).unwrap();
+    output.into_raw()
+}
+
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_lunar_1prototype_dark_1singularity_1api_Singularity_closeWaveViewNative(
+    _env: JNIEnv,
+    _class: JClass,
+    view: jlong,
+) {
+    if view != 0 {
+        unsafe {
+            let _ = Box::from_raw(view as *mut WaveView);
+        }
+    }
+}
@@ -36,6 +36,10 @@ impl PySingularity {
         self.inner.set_active_conditions(&conditions);
     }
 
+    pub fn set_condition_levels(&mut self, levels: Vec<(i32, f32)>) {
+        self.inner.set_condition_levels(&levels);
+    }
+
     pub fn observe_expert(&mut self, state_idx: usize, expert_actions: Vec<usize>, strength: f32) {
         self.inner.observe_expert(state_idx, &expert_actions, strength);
     }
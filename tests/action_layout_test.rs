@@ -0,0 +1,118 @@
+use dark_singularity::core::action_layout::ActionLayout;
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_uniform_layout_spreads_remainder() {
+    let layout = ActionLayout::uniform(1024, 7);
+    assert_eq!(layout.len(), 7);
+    assert_eq!(layout.total_bins(), 1024);
+    assert_eq!(layout.widths(), vec![147, 147, 146, 146, 146, 146, 146]);
+    for a in 0..7 {
+        assert_eq!(ActionLayout::uniform_range(1024, 7, a), layout.range(a));
+    }
+    // 割り切れる場合は従来の等幅と同じ
+    let even = ActionLayout::uniform(1024, 4);
+    assert_eq!(even.range(3), 768..1024);
+}
+
+#[test]
+fn test_weighted_layout_is_proportional_and_covers_dim() {
+    let layout = ActionLayout::weighted(1024, &[3.0, 1.0, 1.0, 1.0]).unwrap();
+    assert_eq!(layout.total_bins(), 1024);
+    let widths = layout.widths();
+    assert!((widths[0] as i64 - 511).abs() <= 1, "{:?}", widths);
+    assert!(widths.iter().all(|&w| w >= 1));
+    assert_eq!(layout.action_for_bin(0), Some(0));
+    assert_eq!(layout.action_for_bin(layout.start(1)), Some(1));
+    assert_eq!(layout.action_for_bin(1023), Some(3));
+    assert_eq!(layout.action_for_bin(1024), None);
+
+    assert!(ActionLayout::weighted(1024, &[1.0, 0.0]).is_none());
+    assert!(ActionLayout::weighted(2, &[1.0, 1.0, 1.0]).is_none());
+    assert!(ActionLayout::from_widths(&[4, 0]).is_none());
+}
+
+#[test]
+fn test_mwso_layout_drives_adapt_and_scoring() {
+    let mut mwso = MWSO::new(1024);
+    let layout = ActionLayout::from_widths(&[100, 700, 224]).unwrap();
+    assert!(mwso.set_action_layout(Some(layout.clone())));
+    assert_eq!(mwso.action_bins(1, 3), 100..800);
+    // 行動数が違う呼び出しは等分にフォールバックする
+    assert_eq!(mwso.action_bins(1, 4), 256..512);
+
+    mwso.adapt(0, 2.0, &[0], 0.5, 3);
+    let touched: Vec<usize> = (0..1024).filter(|&i| mwso.gravity_field[i] > 0.0).collect();
+    assert_eq!(touched.first(), Some(&0));
+    assert_eq!(touched.last(), Some(&99));
+
+    let scores = mwso.get_action_scores(0, 3, 0.0, &[]);
+    assert_eq!(scores.len(), 3);
+    // 同じ波でも、等分とは違う範囲で評価される
+    let mut uniform = mwso.clone();
+    uniform.set_action_layout(None);
+    assert_ne!(scores, uniform.get_action_scores(0, 3, 0.0, &[]));
+
+    let too_wide = ActionLayout::from_widths(&[1000, 100]).unwrap();
+    assert!(!mwso.set_action_layout(Some(too_wide)));
+    assert_eq!(mwso.action_layout(), Some(&layout));
+}
+
+#[test]
+fn test_singularity_action_weights() {
+    let mut ai = Singularity::new(16, vec![4]);
+    assert_eq!(ai.action_widths(), vec![256; 4]);
+    assert!(ai.set_action_weights(&[5.0, 1.0, 1.0, 1.0]));
+    let widths = ai.action_widths();
+    assert_eq!(widths.iter().sum::<usize>(), ai.mwso.dim);
+    assert!(widths[0] > widths[1] * 4);
+
+    // ペナルティは行動の範囲全体に書き込まれ、平均として読み出される
+    ai.set_penalty(3, 0, 2.0);
+    let penalties = ai.get_penalties(3);
+    assert!((penalties[0] - 2.0).abs() < 1e-6);
+    assert_eq!(penalties[1], 0.0);
+
+    // ワームホールは担当ビンの先頭どうしを結ぶ
+    ai.add_wormhole(0, 2, 0.3);
+    let start = widths[0] + widths[1];
+    assert!(ai.list_wormholes().iter().any(|&(f, t, _)| f == 0 && t == start));
+    assert!(ai.remove_wormhole(0, 2));
+
+    for i in 0..40 {
+        let actions = ai.select_actions(i % 16);
+        assert!(actions[0] < 4);
+        ai.learn(if actions[0] == 0 { 1.0 } else { -0.5 });
+    }
+    assert!(ai.wave_norm().is_finite());
+
+    assert!(!ai.set_action_weights(&[1.0, 1.0]));
+    let mut sharded = Singularity::new(16, vec![20, 20]);
+    assert!(!sharded.set_action_weights(&[1.0; 40]));
+}
+
+#[test]
+fn test_action_widths_survive_save_and_load() {
+    let mut ai = Singularity::new(16, vec![4]);
+    assert!(ai.set_action_weights(&[5.0, 1.0, 1.0, 1.0]));
+    for i in 0..20 {
+        ai.select_actions(i % 16);
+        ai.learn(1.0);
+    }
+    let path = std::env::temp_dir().join(format!("ds_action_layout_{}.dsym", std::process::id()));
+    ai.save_to_file(path.to_str().unwrap()).unwrap();
+
+    let mut loaded = Singularity::new(16, vec![4]);
+    loaded.load_from_file(path.to_str().unwrap()).unwrap();
+    assert_eq!(loaded.action_widths(), ai.action_widths());
+    assert_eq!(loaded.mwso.action_layout(), ai.mwso.action_layout());
+
+    // 等分のモデルを保存すると、重み付きのモデルへ読み込んでも等分に戻る
+    let uniform = Singularity::new(16, vec![4]);
+    uniform.save_to_file(path.to_str().unwrap()).unwrap();
+    loaded.load_from_file(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(loaded.action_widths(), vec![256; 4]);
+    assert!(loaded.mwso.action_layout().is_none());
+}
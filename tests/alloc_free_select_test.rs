@@ -0,0 +1,79 @@
+use dark_singularity::core::singularity::Singularity;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// ヒープ確保の回数を（並列実行される他のテストと混ざらないよう）スレッドごとに数えるアロケータ
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(|c| c.get())
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|c| c.set(c.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|c| c.set(c.get() + 1));
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// ウォームアップ後に select_actions_into を回したときのヒープ確保回数
+fn steady_state_allocations(ai: &mut Singularity) -> usize {
+    let mut actions = Vec::new();
+    // ウォームアップ: 作業バッファ・履歴・状態の活動記録を一通り確保させる
+    for i in 0..32 {
+        ai.select_actions_into(i % 8, &mut actions);
+    }
+
+    let before = allocations();
+    for i in 0..100 {
+        ai.select_actions_into(i % 8, &mut actions);
+    }
+    assert_eq!(actions.len(), ai.category_sizes.len());
+    allocations() - before
+}
+
+#[test]
+fn test_select_actions_into_does_not_allocate_after_warmup() {
+    let mut ai = Singularity::new(8, vec![4, 3]);
+    let allocated = steady_state_allocations(&mut ai);
+    assert_eq!(allocated, 0, "select_actions_into allocated {} times", allocated);
+}
+
+#[test]
+fn test_sharded_select_does_not_allocate_after_warmup() {
+    let mut ai = Singularity::new(8, vec![16, 16]);
+    assert!(ai.sharded_mwso.is_some());
+    let allocated = steady_state_allocations(&mut ai);
+    assert_eq!(allocated, 0, "sharded select_actions_into allocated {} times", allocated);
+}
+
+#[test]
+fn test_select_actions_into_matches_select_actions() {
+    let mut a = Singularity::new(8, vec![4, 3]);
+    let mut b = Singularity::new(8, vec![4, 3]);
+    let mut out = Vec::new();
+    for i in 0..40 {
+        let expected = a.select_actions(i % 8);
+        b.select_actions_into(i % 8, &mut out);
+        assert_eq!(out, expected, "step {}", i);
+        let reward = if i % 3 == 0 { 1.5 } else { -0.5 };
+        a.learn(reward);
+        b.learn(reward);
+    }
+}
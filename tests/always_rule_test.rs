@@ -0,0 +1,46 @@
+use dark_singularity::core::knowledge::{Bootstrapper, Condition, ALWAYS_CONDITION_ID, OP_ALL};
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_always_rule_fires_without_conditions() {
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.add_always_rule(0, -1.0);
+    bootstrapper.add_hamiltonian_rule(ALWAYS_CONDITION_ID, 2, 0.1);
+    bootstrapper.add_hamiltonian_rule(4, 2, 0.5);
+
+    assert_eq!(bootstrapper.calculate_resonance_field(&[], 3), vec![Some(-1.0), None, Some(0.1)]);
+    let field = bootstrapper.calculate_resonance_field(&[4], 3);
+    assert!((field[2].unwrap() - 0.6).abs() < 1e-6);
+    assert_eq!(bootstrapper.calculate_graded_resonance_field(&[], 3)[0], Some(-1.0), "graded levels treat it as fully active");
+}
+
+#[test]
+fn test_always_condition_inside_composites() {
+    let always = Condition::Id(ALWAYS_CONDITION_ID);
+    assert!(always.evaluate(&[]));
+    assert_eq!(always.level(&[]), 1.0);
+    assert!(!Condition::Not(Box::new(always.clone())).evaluate(&[3]));
+
+    // 演算子と衝突しないよう空の All として書き出される
+    assert_eq!(always.encode_prefix(), vec![OP_ALL, 0]);
+    let nested = Condition::Any(vec![Condition::Id(2), always]);
+    let decoded = Condition::decode_prefix(&nested.encode_prefix()).unwrap();
+    assert!(decoded.evaluate(&[]));
+}
+
+#[test]
+fn test_singularity_applies_priors_without_condition_flags() {
+    let mut ai = Singularity::new(8, vec![4]);
+    ai.bootstrapper.add_always_rule(0, -1.0);
+    ai.bootstrapper.add_always_rule(3, 0.3);
+    assert!(ai.active_conditions.is_empty());
+    ai.select_actions(2);
+    let knowledge: Vec<f32> = ai.explain_last_decision().iter().map(|b| b.knowledge).collect();
+    assert_eq!(knowledge[0], -100.0, "never pick action 0");
+    assert!((knowledge[3] - 1.5).abs() < 1e-6);
+    assert_eq!(knowledge[1], 0.0);
+
+    ai.set_active_conditions(&[5]);
+    ai.select_actions(2);
+    assert_eq!(ai.explain_last_decision()[0].knowledge, -100.0);
+}
@@ -0,0 +1,61 @@
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_batch_records_each_transition() {
+    let mut ai = Singularity::new(32, vec![3]);
+    ai.learn_batch(&[(1, vec![0], 1.0), (2, vec![2], -1.0), (1, vec![0], 0.5)]);
+
+    assert_eq!(ai.symmetry.stats[&1][0], (1.5, 2));
+    assert_eq!(ai.symmetry.stats[&2][2], (-1.0, 1));
+    assert_eq!(ai.rule_clock, 3);
+    assert!(ai.history.is_empty());
+}
+
+#[test]
+fn test_batch_decay_matches_per_transition_decay() {
+    let mut ai = Singularity::new(32, vec![3]);
+    ai.set_penalty(5, 1, 1.0);
+    let before = ai.get_penalties(5)[1];
+
+    // 状態5に触れない遷移を10件 → ペナルティは 0.995^10 だけ減衰する
+    let batch: Vec<(usize, Vec<usize>, f32)> = (0..10).map(|_| (7, vec![0], 0.0)).collect();
+    ai.learn_batch(&batch);
+    let after = ai.get_penalties(5)[1];
+    assert!((after - before * 0.995f32.powi(10)).abs() < 1e-4, "before={} after={}", before, after);
+}
+
+#[test]
+fn test_batch_training_learns_preference() {
+    let mut ai = Singularity::new(32, vec![3]);
+    let batch: Vec<(usize, Vec<usize>, f32)> = (0..60)
+        .map(|i| (4, vec![i % 3], if i % 3 == 2 { 2.0 } else { -1.0 }))
+        .collect();
+    ai.learn_batch(&batch);
+
+    let hits = (0..20).filter(|_| {
+        let a = ai.select_actions(4)[0];
+        ai.learn(0.0);
+        a == 2
+    }).count();
+    assert!(hits >= 12, "Batch-trained preference should dominate ({} / 20)", hits);
+}
+
+#[test]
+fn test_empty_batch_is_noop() {
+    let mut ai = Singularity::new(32, vec![3]);
+    ai.learn_batch(&[]);
+    ai.learn_batch(&[(3, vec![], 1.0)]);
+    assert_eq!(ai.rule_clock, 0);
+}
+
+#[test]
+fn test_out_of_range_transitions_are_skipped() {
+    let mut ai = Singularity::new(32, vec![4]);
+    ai.learn_batch(&[(3, vec![9], -1.0), (40, vec![1], -1.0), (3, vec![1, 4], -1.0)]);
+    assert_eq!(ai.rule_clock, 0);
+    assert!(ai.symmetry.stats.is_empty());
+
+    ai.learn_batch(&[(3, vec![9], -1.0), (3, vec![1], -1.0)]);
+    assert_eq!(ai.rule_clock, 1);
+    assert_eq!(ai.symmetry.stats[&3][1], (-1.0, 1));
+}
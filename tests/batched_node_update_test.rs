@@ -0,0 +1,69 @@
+use dark_singularity::core::node::{Activation, Node, NodeBatch, Synapse};
+use dark_singularity::core::singularity::Singularity;
+
+/// 再現可能な擬似乱数（xorshift）
+fn rng(seed: &mut u32) -> f32 {
+    *seed ^= *seed << 13;
+    *seed ^= *seed >> 17;
+    *seed ^= *seed << 5;
+    (*seed % 10_000) as f32 / 10_000.0
+}
+
+fn random_network(count: usize) -> Vec<Node> {
+    let mut seed = 0x9e37_79b9;
+    (0..count)
+        .map(|i| {
+            let mut node = Node::new(0.1 + rng(&mut seed) * 0.8);
+            node.state = rng(&mut seed);
+            node.activation = match i % 4 {
+                0 => Activation::Linear,
+                1 => Activation::Sigmoid,
+                2 => Activation::Tanh,
+                _ => Activation::ReluCap(0.7),
+            };
+            if i % 5 == 0 {
+                node.refractory_period = 2;
+            }
+            for _ in 0..8 {
+                let target_id = (rng(&mut seed) * count as f32) as usize;
+                node.synapses.push(Synapse { target_id, weight: rng(&mut seed) * 2.0 - 1.0 });
+            }
+            node
+        })
+        .collect()
+}
+
+#[test]
+fn test_batch_matches_per_node_update() {
+    let mut batched = random_network(300);
+    let mut reference = batched.clone();
+    let inputs: Vec<f32> = (0..250).map(|i| (i % 7) as f32 / 7.0).collect();
+    let mut batch = NodeBatch::new();
+
+    for step in 0..20 {
+        let urgency = (step % 3) as f32 * 0.3;
+        let temp = step as f32 * 0.05;
+        batch.update(&mut batched, &inputs, urgency, temp);
+
+        let states: Vec<f32> = reference.iter().map(|n| n.state).collect();
+        for (i, node) in reference.iter_mut().enumerate() {
+            node.update(inputs.get(i).copied().unwrap_or(0.0), urgency, temp, &states);
+        }
+
+        for (a, b) in batched.iter().zip(&reference) {
+            assert_eq!(a.state.to_bits(), b.state.to_bits(), "step {}", step);
+            assert_eq!(a.refractory_left, b.refractory_left);
+        }
+    }
+}
+
+#[test]
+fn test_singularity_updates_many_nodes() {
+    let mut ai = Singularity::new(8, vec![4]);
+    for i in 0..200 {
+        ai.add_node(&format!("extra{}", i), 0.3).unwrap();
+    }
+    let inputs = vec![1.0; ai.nodes.len()];
+    ai.update_all_nodes(&inputs, 0.5);
+    assert!(ai.nodes.iter().all(|n| n.state > 0.0));
+}
@@ -0,0 +1,33 @@
+use dark_singularity::core::bench::{decision_latency, memory_capacity_sweep, wave_step_throughput};
+
+#[test]
+fn test_wave_step_throughput_reports_rates() {
+    let result = wave_step_throughput(256, 50);
+    assert_eq!((result.dim, result.steps), (256, 50));
+    assert!(result.elapsed.as_nanos() > 0);
+    assert!(result.steps_per_sec > 0.0 && result.ns_per_bin > 0.0);
+    let implied = result.steps as f64 / result.elapsed.as_secs_f64();
+    assert!((implied - result.steps_per_sec).abs() / implied < 1e-6);
+}
+
+#[test]
+fn test_decision_latency_counts_every_cycle() {
+    let result = decision_latency(32, &[4, 4], 20);
+    assert_eq!(result.action_size, 8);
+    assert_eq!(result.dim, 1024);
+    assert_eq!(result.select_actions.count, 20);
+    assert_eq!(result.learn.count, 20);
+    assert!(result.select_actions.min <= result.select_actions.p99);
+    assert!(result.cycles_per_sec > 0.0);
+}
+
+#[test]
+fn test_capacity_sweep_snr_falls_with_load() {
+    let sweep = memory_capacity_sweep(512, &[1, 8, 64], 5.0);
+    assert_eq!(sweep.points.len(), 3);
+    let snr: Vec<f32> = sweep.points.iter().map(|p| p.snr).collect();
+    assert!(snr.iter().all(|s| s.is_finite() && *s > 0.0), "{snr:?}");
+    assert!(snr[2] < snr[0], "interference must lower the SNR: {snr:?}");
+    let passing = sweep.points.iter().filter(|p| p.snr >= 5.0).map(|p| p.patterns).max().unwrap_or(0);
+    assert_eq!(sweep.capacity, passing);
+}
@@ -0,0 +1,53 @@
+use dark_singularity::core::knowledge::{Bootstrapper, Condition};
+use dark_singularity::core::singularity::Singularity;
+
+const ENEMY_NEAR: i32 = 0;
+
+#[test]
+fn test_category_fields_split_the_flat_field() {
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.add_hamiltonian_rule(ENEMY_NEAR, 1, 0.5);
+    bootstrapper.add_hamiltonian_rule(ENEMY_NEAR, 4, -1.0);
+    let levels = [(ENEMY_NEAR, 1.0)];
+    let fields = bootstrapper.calculate_category_fields(&levels, &[3, 2, 2]);
+    assert_eq!(fields, vec![vec![None, Some(0.5), None], vec![None, Some(-1.0)], vec![None, None]]);
+    let flat: Vec<Option<f32>> = fields.concat();
+    assert_eq!(flat, bootstrapper.calculate_graded_resonance_field(&levels, 7));
+    assert!(bootstrapper.calculate_category_fields(&levels, &[]).is_empty());
+}
+
+#[test]
+fn test_scoped_rules_use_category_local_indices() {
+    let mut ai = Singularity::new(8, vec![3, 4]);
+    // 移動カテゴリー (1) の 0 番目 = 全体の 3 番目
+    assert!(ai.add_category_scoped_rule(Condition::Id(ENEMY_NEAR), 1, 0..1, -1.0, 0));
+    assert!(ai.add_category_scoped_rule(Condition::Id(ENEMY_NEAR), 1, 2..4, 0.4, 0));
+    assert!(ai.add_category_scoped_rule(Condition::Id(ENEMY_NEAR), 0, 2..3, 0.2, 0));
+    assert_eq!(ai.bootstrapper.rules[0].target_action, 3);
+    assert_eq!((ai.bootstrapper.rules[1].target_action, ai.bootstrapper.rules[1].span), (5, 2));
+
+    ai.set_active_conditions(&[ENEMY_NEAR]);
+    let fields = ai.category_resonance_fields();
+    assert_eq!(fields[0], vec![None, None, Some(0.2)]);
+    assert_eq!(fields[1], vec![Some(-1.0), None, Some(0.4), Some(0.4)]);
+    assert_eq!(fields.iter().map(Vec::len).collect::<Vec<_>>(), ai.category_sizes);
+
+    ai.set_active_conditions(&[]);
+    assert!(ai.category_resonance_fields().iter().flatten().all(Option::is_none));
+}
+
+#[test]
+fn test_scoped_rules_cannot_spill_into_other_categories() {
+    let mut ai = Singularity::new(8, vec![3, 4]);
+    assert!(!ai.add_category_scoped_rule(Condition::Id(ENEMY_NEAR), 0, 2..4, 1.0, 0), "would reach category 1");
+    assert!(!ai.add_category_scoped_rule(Condition::Id(ENEMY_NEAR), 1, 4..5, 1.0, 0));
+    assert!(!ai.add_category_scoped_rule(Condition::Id(ENEMY_NEAR), 2, 0..1, 1.0, 0));
+    assert!(!ai.add_category_scoped_rule(Condition::Id(ENEMY_NEAR), 1, 1..1, 1.0, 0));
+    assert!(ai.bootstrapper.rules.is_empty());
+
+    assert!(ai.add_category_rule(Condition::Id(ENEMY_NEAR), 1, -1.0, 0));
+    ai.set_active_conditions(&[ENEMY_NEAR]);
+    let fields = ai.category_resonance_fields();
+    assert!(fields[0].iter().all(Option::is_none));
+    assert!(fields[1].iter().all(|v| *v == Some(-1.0)));
+}
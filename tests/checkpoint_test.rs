@@ -0,0 +1,74 @@
+use dark_singularity::core::config::ExplorationPolicy;
+use dark_singularity::core::singularity::{Experience, Singularity};
+
+fn play(ai: &mut Singularity, steps: usize) -> Vec<Vec<i32>> {
+    (0..steps).map(|i| {
+        let actions = ai.select_actions(i % 5);
+        ai.learn(if i % 2 == 0 { 1.5 } else { -1.0 });
+        actions
+    }).collect()
+}
+
+#[test]
+fn test_restore_replays_identically() {
+    let mut ai = Singularity::new(5, vec![4, 3]);
+    play(&mut ai, 10);
+
+    let snapshot = ai.checkpoint();
+    let psi = ai.mwso.psi_real.clone();
+    let penalties = ai.penalty_matrix.clone();
+    let temp = ai.system_temperature;
+
+    let first = play(&mut ai, 8);
+    assert_ne!(ai.mwso.psi_real, psi);
+
+    ai.restore(&snapshot);
+    assert_eq!(ai.mwso.psi_real, psi);
+    assert_eq!(ai.penalty_matrix, penalties);
+    assert_eq!(ai.system_temperature, temp);
+
+    let second = play(&mut ai, 8);
+    assert_eq!(first, second, "Rolled-back brain must make the same decisions");
+}
+
+#[test]
+fn test_restore_sharded() {
+    let mut ai = Singularity::new(5, vec![20]);
+    let psi = ai.sharded_mwso.as_ref().unwrap().shards[0].psi_real.clone();
+    let snapshot = ai.checkpoint();
+    play(&mut ai, 5);
+    ai.restore(&snapshot);
+    assert!(ai.history.is_empty());
+    assert_eq!(ai.sharded_mwso.as_ref().unwrap().shards[0].psi_real, psi);
+}
+
+#[test]
+fn test_restore_undoes_crystallization_and_statistics() {
+    let mut ai = Singularity::new(6, vec![3]);
+    ai.config.crystallize_threshold = 3;
+    ai.config.exploration_policy = ExplorationPolicy::Ucb;
+    ai.enable_neuromodulators();
+    play(&mut ai, 4);
+
+    let snapshot = ai.checkpoint();
+    let rules = ai.bootstrapper.rules.len();
+    let visits = ai.action_visits.clone();
+    let modulators = ai.neuromodulators;
+    let trace = ai.temperature_trace.len();
+
+    for _ in 0..4 {
+        ai.history.push_back(Experience { state_idx: 4, actions: vec![1] });
+        ai.learn(2.0);
+    }
+    play(&mut ai, 4);
+    assert!(ai.crystallized.contains_key(&(4, 1)));
+    assert_ne!(ai.action_visits, visits);
+    assert_ne!(ai.neuromodulators, modulators);
+
+    ai.restore(&snapshot);
+    assert!(ai.crystallized.is_empty());
+    assert_eq!(ai.bootstrapper.rules.len(), rules);
+    assert_eq!(ai.action_visits, visits);
+    assert_eq!(ai.neuromodulators, modulators);
+    assert_eq!(ai.temperature_trace.len(), trace);
+}
@@ -0,0 +1,119 @@
+use dark_singularity::core::singularity::{Experience, Singularity};
+use std::path::{Path, PathBuf};
+
+fn replay(ai: &mut Singularity, state_idx: usize, action: usize, reward: f32) {
+    ai.history.push_back(Experience { state_idx, actions: vec![action] });
+    ai.learn(reward);
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ds_cold_archive_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn test_inactive_state_is_archived_and_restored() {
+    let dir = temp_dir("roundtrip");
+    let mut ai = Singularity::new(20, vec![3]);
+    ai.config.crystallize_threshold = 0;
+    ai.config.rule_prune_interval = 10;
+    ai.config.rule_stale_after = 1_000;
+    ai.config.archive_after = 5;
+    ai.enable_cold_archive(dir.to_str().unwrap()).unwrap();
+
+    replay(&mut ai, 4, 1, 2.0);
+    replay(&mut ai, 4, 1, 2.0);
+    ai.set_penalty(4, 2, 0.7);
+    let penalties_before = ai.get_penalties(4);
+    let stats_before = ai.symmetry.stats[&4].clone();
+    for _ in 0..8 { replay(&mut ai, 9, 0, 0.0); }
+
+    assert!(ai.cold_archive.as_ref().unwrap().contains(4), "State 4 should be archived after inactivity");
+    assert!(!ai.learned_rules.iter().any(|r| r.state == 4));
+    assert!(ai.get_penalties(4).iter().all(|&p| p == 0.0));
+    assert!(!ai.symmetry.stats.contains_key(&4));
+
+    // 再びアクセスされると透過的に復元される
+    ai.select_actions(4);
+    assert!(!ai.cold_archive.as_ref().unwrap().contains(4));
+    assert!(ai.learned_rules.iter().any(|r| r.state == 4 && r.action == 1 && r.count == 2));
+    // 復元後の選択でペナルティは通常どおり減衰し始める
+    assert!(ai.get_penalties(4)[2] > penalties_before[2] * 0.9);
+    assert_eq!(ai.symmetry.stats[&4], stats_before);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_active_states_are_not_archived() {
+    let dir = temp_dir("active");
+    let mut ai = Singularity::new(20, vec![3]);
+    ai.config.crystallize_threshold = 0;
+    ai.config.archive_after = 5;
+    ai.enable_cold_archive(dir.to_str().unwrap()).unwrap();
+
+    for _ in 0..10 { replay(&mut ai, 3, 2, 2.0); }
+    assert_eq!(ai.archive_inactive_states().unwrap(), 0);
+    assert!(ai.learned_rules.iter().any(|r| r.state == 3));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// 状態 4 のルールを退避した状態の AI
+fn archived(dir: &Path) -> Singularity {
+    let mut ai = Singularity::new(20, vec![3]);
+    ai.config.crystallize_threshold = 0;
+    ai.config.rule_prune_interval = 10;
+    ai.config.rule_stale_after = 1_000;
+    ai.config.archive_after = 5;
+    ai.enable_cold_archive(dir.to_str().unwrap()).unwrap();
+    replay(&mut ai, 4, 1, 2.0);
+    replay(&mut ai, 4, 1, 2.0);
+    for _ in 0..8 { replay(&mut ai, 9, 0, 0.0); }
+    assert!(ai.cold_archive.as_ref().unwrap().contains(4));
+    ai
+}
+
+#[test]
+fn test_reopened_archive_finds_existing_states() {
+    let dir = temp_dir("reopen");
+    let _ = archived(&dir);
+
+    // 再起動後に同じディレクトリを開けば、退避済みの状態をそのまま復元できる
+    let mut restarted = Singularity::new(20, vec![3]);
+    restarted.enable_cold_archive(dir.to_str().unwrap()).unwrap();
+    assert!(restarted.cold_archive.as_ref().unwrap().contains(4));
+    assert!(restarted.restore_archived_state(4).unwrap());
+    assert!(restarted.learned_rules.iter().any(|r| r.state == 4 && r.action == 1 && r.count == 2));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_archived_states_survive_save_and_load() {
+    let dir = temp_dir("save");
+    let ai = archived(&dir);
+    let path = std::env::temp_dir().join(format!("ds_cold_archive_save_{}.dsym", std::process::id()));
+    ai.save_to_file(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    // コールドストレージ無しで読み込むと、退避していた知識は実行中の表へ戻る
+    let mut loaded = Singularity::new(20, vec![3]);
+    loaded.load_from_file(path.to_str().unwrap()).unwrap();
+    assert!(loaded.learned_rules.iter().any(|r| r.state == 4 && r.action == 1 && r.count == 2));
+    assert!(loaded.symmetry.stats.contains_key(&4));
+
+    // 別のディレクトリのコールドストレージを有効にして読み込むと、退避先へ戻る
+    let other = temp_dir("save_other");
+    let mut archiving = Singularity::new(20, vec![3]);
+    archiving.enable_cold_archive(other.to_str().unwrap()).unwrap();
+    archiving.load_from_file(path.to_str().unwrap()).unwrap();
+    assert!(archiving.cold_archive.as_ref().unwrap().contains(4));
+    assert!(!archiving.learned_rules.iter().any(|r| r.state == 4));
+    assert!(archiving.restore_archived_state(4).unwrap());
+    assert!(archiving.learned_rules.iter().any(|r| r.state == 4 && r.count == 2));
+
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_dir_all(&other);
+}
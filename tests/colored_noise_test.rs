@@ -0,0 +1,98 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::noise::{ExplorationNoise, NoiseGenerator};
+use dark_singularity::core::singularity::Singularity;
+
+/// 要素ごとの系列を ticks 回引いた [tick][要素] の表
+fn series(mode: ExplorationNoise, width: usize, ticks: usize) -> Vec<Vec<f32>> {
+    let mut generator = NoiseGenerator::new(mode);
+    let mut seed = 12345u64;
+    let mut uniform = || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+        ((seed >> 32) as u32) as f32 / u32::MAX as f32
+    };
+    (0..ticks).map(|_| {
+        let mut out = vec![0.0; width];
+        generator.next_into(&mut out, &mut uniform);
+        out
+    }).collect()
+}
+
+/// tick 間隔 lag の自己相関（要素全体で平均、分散 1 を仮定しない）
+fn autocorrelation(s: &[Vec<f32>], lag: usize) -> f32 {
+    let (mut num, mut den) = (0.0f64, 0.0f64);
+    for t in 0..s.len() - lag {
+        for (a, b) in s[t].iter().zip(&s[t + lag]) {
+            num += (*a * *b) as f64;
+        }
+    }
+    for row in s {
+        for a in row { den += (*a * *a) as f64; }
+    }
+    (num / (s.len() - lag) as f64 / (den / s.len() as f64)) as f32
+}
+
+fn variance(s: &[Vec<f32>]) -> f32 {
+    let n: usize = s.iter().map(|r| r.len()).sum();
+    s.iter().flatten().map(|x| x * x).sum::<f32>() / n as f32
+}
+
+#[test]
+fn test_pink_noise_is_stationary_with_unit_variance() {
+    let s = series(ExplorationNoise::Pink, 2048, 40);
+    assert!((variance(&s[..1]) - 1.0).abs() < 0.15, "first tick starts from the stationary distribution");
+    assert!((variance(&s[30..]) - 1.0).abs() < 0.15);
+    assert!(s.iter().flatten().all(|x| x.is_finite()));
+}
+
+#[test]
+fn test_pink_noise_correlates_over_long_horizons() {
+    let pink = series(ExplorationNoise::Pink, 512, 200);
+    let ou = series(ExplorationNoise::OrnsteinUhlenbeck, 512, 200);
+    let white = series(ExplorationNoise::Gaussian, 512, 200);
+
+    assert!(autocorrelation(&white, 1).abs() < 0.05);
+    // OU は指数的に相関を失うが、1/f ノイズは長い間隔でも相関が残る
+    let (pink_far, ou_far) = (autocorrelation(&pink, 40), autocorrelation(&ou, 40));
+    assert!(ou_far.abs() < 0.05, "ou {ou_far}");
+    assert!(pink_far > 0.15, "pink {pink_far}");
+    assert!(autocorrelation(&pink, 1) > pink_far);
+}
+
+#[test]
+fn test_generator_restarts_when_width_changes() {
+    let mut generator = NoiseGenerator::new(ExplorationNoise::Pink);
+    let mut seed = 1u64;
+    let mut uniform = || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+        ((seed >> 32) as u32) as f32 / u32::MAX as f32
+    };
+    let mut wide = vec![0.0; 16];
+    generator.next_into(&mut wide, &mut uniform);
+    let mut narrow = vec![0.0; 4];
+    generator.next_into(&mut narrow, &mut uniform);
+    assert!(narrow.iter().all(|x| x.is_finite() && *x != 0.0));
+    generator.set_mode(ExplorationNoise::Uniform);
+    assert_eq!(generator.mode(), ExplorationNoise::Uniform);
+}
+
+#[test]
+fn test_mwso_pink_noise_is_reproducible_and_survives_copy() {
+    let mut a = MWSO::new(128);
+    a.set_exploration_noise(ExplorationNoise::Pink);
+    assert_eq!(a.exploration_noise(), ExplorationNoise::Pink);
+    a.inject_exploration_noise(0.1);
+
+    let mut b = MWSO::new(128);
+    b.copy_from(&a);
+    a.inject_exploration_noise(0.1);
+    b.inject_exploration_noise(0.1);
+    assert_eq!(a.psi_real, b.psi_real, "the generator state is part of the copied state");
+
+    let mut ai = Singularity::new(16, vec![4, 3]);
+    ai.set_exploration_noise(ExplorationNoise::Pink);
+    for i in 0..30 {
+        ai.select_actions(i % 16);
+        ai.learn(if i % 3 == 0 { 1.0 } else { -0.2 });
+    }
+    assert!(ai.wave_norm().is_finite());
+}
@@ -0,0 +1,27 @@
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_compat_category_selector() {
+    let mut ai = Singularity::new(10, vec![4, 3]);
+    for _ in 0..20 {
+        let a = ai.select_action_compat(&[2.0], Some(1), false);
+        assert!((0..3).contains(&a));
+        assert_eq!(ai.last_actions[1], 4 + a as usize);
+        ai.learn(0.1);
+    }
+
+    // 範囲外のカテゴリーは最初のカテゴリーにフォールバック
+    let a = ai.select_action_compat(&[2.0], Some(9), false);
+    assert_eq!(ai.last_actions[0], a as usize);
+}
+
+#[test]
+fn test_compat_feature_vector_mode() {
+    let mut ai = Singularity::new(10, vec![4]);
+    let a = ai.select_action_compat(&[0.0, 0.7, 0.0, 0.3], None, true);
+    assert!((0..4).contains(&a));
+
+    let exp = ai.vector_history.back().expect("Vector mode should record a vector experience");
+    assert_eq!(exp.state_weights, vec![(1, 0.7), (3, 0.3)]);
+    assert!(ai.history.is_empty());
+}
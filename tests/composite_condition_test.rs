@@ -0,0 +1,71 @@
+use dark_singularity::core::knowledge::{Bootstrapper, Condition, OP_ALL, OP_ANY, OP_NOT};
+use dark_singularity::core::singularity::Singularity;
+
+const HP_LOW: i32 = 0;
+const ENEMY_NEAR: i32 = 1;
+const AMMO_LOW: i32 = 2;
+
+fn retreat_condition() -> Condition {
+    Condition::All(vec![
+        Condition::Id(HP_LOW),
+        Condition::Id(ENEMY_NEAR),
+        Condition::Not(Box::new(Condition::Id(AMMO_LOW))),
+    ])
+}
+
+#[test]
+fn test_condition_evaluation() {
+    let c = retreat_condition();
+    assert!(c.evaluate(&[HP_LOW, ENEMY_NEAR]));
+    assert!(!c.evaluate(&[HP_LOW, ENEMY_NEAR, AMMO_LOW]));
+    assert!(!c.evaluate(&[HP_LOW]));
+
+    let either = Condition::Any(vec![Condition::Id(HP_LOW), Condition::Id(AMMO_LOW)]);
+    assert!(either.evaluate(&[AMMO_LOW]));
+    assert!(!either.evaluate(&[ENEMY_NEAR]));
+    assert!(Condition::All(vec![]).evaluate(&[]));
+    assert!(!Condition::Any(vec![]).evaluate(&[HP_LOW]));
+}
+
+#[test]
+fn test_prefix_encoding_round_trip() {
+    let codes = [OP_ALL, 3, HP_LOW, ENEMY_NEAR, OP_NOT, AMMO_LOW];
+    let c = Condition::decode_prefix(&codes).unwrap();
+    assert_eq!(c, retreat_condition());
+    assert_eq!(c.encode_prefix(), codes.to_vec());
+
+    let nested = Condition::Any(vec![Condition::Not(Box::new(retreat_condition())), Condition::Id(7)]);
+    assert_eq!(Condition::decode_prefix(&nested.encode_prefix()), Some(nested));
+    assert_eq!(Condition::decode_prefix(&[5]), Some(Condition::Id(5)));
+
+    assert_eq!(Condition::decode_prefix(&[]), None);
+    assert_eq!(Condition::decode_prefix(&[OP_ALL, 2, HP_LOW]), None, "missing child");
+    assert_eq!(Condition::decode_prefix(&[OP_ANY, -1, HP_LOW]), None, "negative count");
+    assert_eq!(Condition::decode_prefix(&[OP_ALL, i32::MAX]), None, "count beyond the input");
+    assert_eq!(Condition::decode_prefix(&[HP_LOW, ENEMY_NEAR]), None, "trailing codes");
+    assert_eq!(Condition::decode_prefix(&[OP_NOT]), None);
+    assert_eq!(Condition::decode_prefix(&[-9]), None, "unknown operator");
+}
+
+#[test]
+fn test_resonance_field_uses_composite_rules() {
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.add_hamiltonian_rule(HP_LOW, 0, 0.5);
+    bootstrapper.add_composite_rule(retreat_condition(), 1, 2.0);
+    bootstrapper.add_composite_rule(Condition::Id(AMMO_LOW), 1, -1.0);
+
+    let field = bootstrapper.calculate_resonance_field(&[HP_LOW, ENEMY_NEAR], 3);
+    assert_eq!(field, vec![Some(0.5), Some(2.0), None]);
+    let field = bootstrapper.calculate_resonance_field(&[HP_LOW, ENEMY_NEAR, AMMO_LOW], 3);
+    assert_eq!(field, vec![Some(0.5), Some(-1.0), None]);
+}
+
+#[test]
+fn test_composite_rule_drives_simulated_ranking() {
+    let mut ai = Singularity::new(8, vec![3]);
+    ai.bootstrapper.add_composite_rule(retreat_condition(), 2, 3.0);
+    assert_eq!(ai.simulate_rules(&[HP_LOW, ENEMY_NEAR], 0).best(0), Some(2));
+    assert_ne!(ai.simulate_rules(&[HP_LOW, ENEMY_NEAR, AMMO_LOW], 0).best(0), Some(2));
+    // 単一条件の検索（condition_id による照合）には複合ルールは現れない
+    assert!(ai.bootstrapper.rules.iter().all(|r| r.condition_id < 0));
+}
@@ -0,0 +1,64 @@
+use dark_singularity::core::knowledge::{Bootstrapper, Condition, RuleConflictPolicy};
+use dark_singularity::core::singularity::Singularity;
+
+const HP_LOW: i32 = 0;
+const ENEMY_NEAR: i32 = 1;
+const RETREAT: usize = 2;
+
+#[test]
+fn test_rule_strength_scales_with_level() {
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.add_hamiltonian_rule(HP_LOW, RETREAT, 2.0);
+    let strengths: Vec<f32> = [0.0, 0.25, 0.5, 1.0, 3.0]
+        .iter()
+        .map(|&level| bootstrapper.calculate_graded_resonance_field(&[(HP_LOW, level)], 3)[RETREAT].unwrap_or(0.0))
+        .collect();
+    assert_eq!(strengths, vec![0.0, 0.5, 1.0, 2.0, 2.0], "levels are clamped to [0, 1]");
+    assert_eq!(bootstrapper.calculate_graded_resonance_field(&[(HP_LOW, 0.0)], 3)[RETREAT], None, "a zero level does not fire");
+}
+
+#[test]
+fn test_graded_matches_boolean_for_full_levels() {
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.add_hamiltonian_rule(HP_LOW, 0, 0.5);
+    bootstrapper.add_penalty_rule(ENEMY_NEAR, 1, 1.0);
+    bootstrapper.add_composite_rule(Condition::All(vec![Condition::Id(HP_LOW), Condition::Not(Box::new(Condition::Id(ENEMY_NEAR)))]), RETREAT, 1.0);
+    for policy in [RuleConflictPolicy::Sum, RuleConflictPolicy::HighestPriority, RuleConflictPolicy::MaxMagnitude] {
+        bootstrapper.policy = policy;
+        for active in [vec![], vec![HP_LOW], vec![ENEMY_NEAR], vec![HP_LOW, ENEMY_NEAR]] {
+            let levels: Vec<(i32, f32)> = active.iter().map(|&id| (id, 1.0)).collect();
+            assert_eq!(
+                bootstrapper.calculate_graded_resonance_field(&levels, 3),
+                bootstrapper.calculate_resonance_field(&active, 3),
+            );
+        }
+    }
+}
+
+#[test]
+fn test_composite_levels_use_fuzzy_logic() {
+    let levels = [(HP_LOW, 0.8), (ENEMY_NEAR, 0.3)];
+    let hp = Condition::Id(HP_LOW);
+    let enemy = Condition::Id(ENEMY_NEAR);
+    assert!((Condition::All(vec![hp.clone(), enemy.clone()]).level(&levels) - 0.3).abs() < 1e-6);
+    assert!((Condition::Any(vec![hp.clone(), enemy.clone()]).level(&levels) - 0.8).abs() < 1e-6);
+    assert!((Condition::Not(Box::new(enemy)).level(&levels) - 0.7).abs() < 1e-6);
+    assert_eq!(Condition::Id(9).level(&levels), 0.0);
+}
+
+#[test]
+fn test_singularity_levels_drive_knowledge_smoothly() {
+    let mut ai = Singularity::new(8, vec![4]);
+    ai.bootstrapper.add_hamiltonian_rule(HP_LOW, RETREAT, 1.0);
+
+    ai.set_condition_levels(&[(HP_LOW, 0.4), (ENEMY_NEAR, 0.0)]);
+    assert_eq!(ai.active_conditions, vec![HP_LOW], "only conditions above zero count as active");
+    let weak = ai.evaluate_actions(5)[RETREAT];
+    ai.set_condition_levels(&[(HP_LOW, 0.9)]);
+    let strong = ai.evaluate_actions(5)[RETREAT];
+    assert!(strong > weak, "{weak} -> {strong}");
+
+    ai.set_active_conditions(&[HP_LOW]);
+    assert_eq!(ai.condition_levels, vec![(HP_LOW, 1.0)]);
+    assert!(ai.evaluate_actions(5)[RETREAT] > strong);
+}
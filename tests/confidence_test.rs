@@ -0,0 +1,25 @@
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_confidence_bounds_and_growth() {
+    let mut ai = Singularity::new(10, vec![5]);
+    assert_eq!(ai.decision_confidence(), 0.0, "No decision yet");
+
+    ai.select_actions(0);
+    let initial = ai.decision_confidence();
+    assert!((0.0..=1.0).contains(&initial));
+
+    let uncertainty = ai.decision_uncertainty();
+    assert!((0.0..=1.0).contains(&uncertainty));
+
+    for _ in 0..20 {
+        ai.select_actions(0);
+        ai.learn(1.5);
+    }
+    ai.select_actions(0);
+    let trained = ai.decision_confidence();
+    println!("confidence: {:.3} -> {:.3}, uncertainty: {:.3} -> {:.3}",
+        initial, trained, uncertainty, ai.decision_uncertainty());
+    assert!((0.0..=1.0).contains(&trained));
+    assert!(trained > initial, "Confidence should grow after consistent reinforcement");
+}
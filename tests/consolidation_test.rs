@@ -0,0 +1,93 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::replay::ReplayBuffer;
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::wave::to_wave;
+
+fn set_pattern(mwso: &mut MWSO, seed: usize) {
+    for i in 0..mwso.dim {
+        let phase = ((i * 2654435761 + seed * 40503) % 6283) as f32 / 1000.0;
+        mwso.psi_real[i] = to_wave(phase.cos());
+        mwso.psi_imag[i] = to_wave(phase.sin());
+    }
+}
+
+/// 強いパターン 0 を刻んだ後、弱いパターンを大量に刻み、途中で統合するかどうかで比べる
+fn snr_of_strong_pattern(consolidate_every: Option<usize>) -> f32 {
+    let mut mwso = MWSO::new(1024);
+    mwso.replay.set_capacity(4);
+    set_pattern(&mut mwso, 0);
+    mwso.imprint_qcel(0, 2.0);
+    for s in 1..150 {
+        set_pattern(&mut mwso, s);
+        mwso.imprint_qcel(s, 0.3);
+        if consolidate_every.is_some_and(|n| s % n == 0) {
+            mwso.consolidate(0.2);
+        }
+    }
+    set_pattern(&mut mwso, 0);
+    mwso.retrieval_snr(0)
+}
+
+#[test]
+fn test_consolidation_preserves_strong_memory() {
+    let plain = snr_of_strong_pattern(None);
+    let consolidated = snr_of_strong_pattern(Some(25));
+    assert!(consolidated > plain * 1.5, "replay must protect the strong pattern: {} vs {}", plain, consolidated);
+}
+
+#[test]
+fn test_replay_buffer_keeps_strongest() {
+    let mut buffer = ReplayBuffer::new(2);
+    buffer.record(0, 1, 0.5, || vec![0.0]);
+    buffer.record(0, 2, 1.0, || vec![0.0]);
+    buffer.record(0, 3, 0.2, || vec![0.0]);
+    assert_eq!(buffer.len(), 2);
+    assert!(buffer.traces().iter().all(|t| t.input_idx != 3), "weaker pattern must not evict");
+
+    buffer.record(0, 3, 0.8, || vec![0.0]);
+    let mut kept: Vec<usize> = buffer.traces().iter().map(|t| t.input_idx).collect();
+    kept.sort();
+    assert_eq!(kept, vec![2, 3]);
+
+    buffer.record(0, 2, 0.5, || vec![1.0]);
+    let trace = buffer.traces().iter().find(|t| t.input_idx == 2).unwrap();
+    assert!((trace.strength - 1.5).abs() < 1e-6);
+    assert_eq!(trace.phases, vec![1.0]);
+
+    buffer.remove(0, 2);
+    assert_eq!(buffer.len(), 1);
+    assert!(ReplayBuffer::default().is_empty());
+}
+
+#[test]
+fn test_failed_patterns_are_not_replayed() {
+    let mut mwso = MWSO::new(1024);
+    mwso.replay.set_capacity(8);
+    set_pattern(&mut mwso, 3);
+    mwso.imprint_qcel(3, 1.0);
+    mwso.imprint_qcel(4, 1.0);
+    assert_eq!(mwso.replay.len(), 2);
+    mwso.imprint_qcel(3, -1.0);
+    assert_eq!(mwso.replay.len(), 1);
+    assert_eq!(mwso.replay.traces()[0].input_idx, 4);
+}
+
+#[test]
+fn test_scheduled_consolidation_in_learn() {
+    let mut ai = Singularity::new(16, vec![4]);
+    assert_eq!(ai.consolidate_memory(), 0, "nothing is recorded by default");
+
+    ai.set_replay_capacity(8);
+    ai.config.consolidate_interval = 5;
+    for i in 0..20 {
+        ai.select_actions(i % 16);
+        ai.learn(1.0);
+    }
+    assert!(!ai.mwso.replay.is_empty());
+    assert!(ai.mwso.replay.len() <= 8);
+    assert!(ai.consolidate_memory() > 0);
+    assert!(ai.mwso.q_memory_re.iter().all(|v| v.is_finite()));
+
+    ai.set_replay_capacity(2);
+    assert_eq!(ai.mwso.replay.len(), 2);
+}
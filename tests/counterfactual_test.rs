@@ -0,0 +1,71 @@
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_evaluate_actions_has_no_side_effects() {
+    let mut ai = Singularity::new(10, vec![4, 3]);
+    for step in 0..5 {
+        ai.select_actions(step);
+        ai.learn(0.3);
+    }
+    ai.select_actions(7);
+
+    let psi_before = ai.mwso.psi_real.clone();
+    let rng_before = ai.mwso.rng_seed;
+    let history_before: Vec<usize> = ai.history.iter().map(|e| e.state_idx).collect();
+    let input_history_before = ai.input_history.clone();
+    let last_actions_before = ai.last_actions.clone();
+
+    let first = ai.evaluate_actions(3);
+    let second = ai.evaluate_actions(3);
+    assert_eq!(first.len(), 7);
+    assert_eq!(first, second, "Repeated hypothetical queries must be identical");
+
+    assert_eq!(ai.mwso.psi_real, psi_before);
+    assert_eq!(ai.mwso.rng_seed, rng_before);
+    assert_eq!(ai.history.iter().map(|e| e.state_idx).collect::<Vec<_>>(), history_before);
+    assert_eq!(ai.input_history, input_history_before);
+    assert_eq!(ai.last_actions, last_actions_before);
+}
+
+#[test]
+fn test_evaluate_actions_sharded() {
+    let ai = Singularity::new(10, vec![20]);
+    let psi_before = ai.sharded_mwso.as_ref().unwrap().shards[0].psi_real.clone();
+    let scores = ai.evaluate_actions(2);
+    assert_eq!(scores.len(), 20);
+    assert_eq!(ai.sharded_mwso.as_ref().unwrap().shards[0].psi_real, psi_before);
+    assert!(ai.history.is_empty());
+}
+
+#[test]
+fn test_evaluate_actions_leaves_bookkeeping_untouched() {
+    let mut ai = Singularity::new(10, vec![4]);
+    for step in 0..6 {
+        ai.select_actions(step % 3);
+        ai.learn(-0.5);
+    }
+    let selects = ai.stats().select_actions.count;
+    let last_active = ai.state_last_active.clone();
+    let row_clock = ai.penalty_row_clock.clone();
+
+    // 状態 8 は未訪問。評価してもアクセスとしては数えず、ペナルティの減衰も反映しない
+    ai.evaluate_actions(8);
+    ai.evaluate_actions(1);
+    assert_eq!(ai.stats().select_actions.count, selects);
+    assert_eq!(ai.state_last_active, last_active);
+    assert_eq!(ai.penalty_row_clock, row_clock);
+    assert!(ai.score_cache.is_empty());
+}
+
+#[test]
+fn test_evaluate_actions_matches_the_decision() {
+    let mut ai = Singularity::new(10, vec![4, 3]);
+    for step in 0..5 {
+        ai.select_actions(step);
+        ai.learn(0.3);
+    }
+    let scores = ai.evaluate_actions(2);
+    ai.select_actions(2);
+    let explained: Vec<f32> = ai.explain_last_decision().iter().map(|b| b.total).collect();
+    assert_eq!(scores, explained, "Hypothetical scores must match a real decision from the same state");
+}
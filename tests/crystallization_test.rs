@@ -0,0 +1,91 @@
+use dark_singularity::core::knowledge::Condition;
+use dark_singularity::core::singularity::{Experience, Singularity};
+
+fn replay(ai: &mut Singularity, state_idx: usize, action: usize, reward: f32) {
+    ai.history.push_back(Experience { state_idx, actions: vec![action] });
+    ai.learn(reward);
+}
+
+#[test]
+fn test_confident_rule_is_promoted_and_demoted() {
+    let mut ai = Singularity::new(6, vec![3]);
+    ai.config.crystallize_threshold = 3;
+
+    for _ in 0..2 { replay(&mut ai, 4, 1, 2.0); }
+    assert!(ai.crystallized.is_empty());
+    replay(&mut ai, 4, 1, 2.0);
+    assert!(ai.crystallized.contains_key(&(4, 1)));
+    assert!(ai.bootstrapper.rules.iter().any(|r| r.condition_id == 4 && r.target_action == 1));
+
+    // 条件駆動の経路でも知識として効く
+    ai.set_active_conditions(&[99]);
+    assert_eq!(ai.simulate_rules(&[99], 4).best(0), Some(1));
+
+    // 負の報酬が続くと降格
+    for _ in 0..3 { replay(&mut ai, 4, 1, -1.0); }
+    assert!(!ai.crystallized.contains_key(&(4, 1)));
+    assert!(!ai.bootstrapper.rules.iter().any(|r| r.condition_id == 4 && r.target_action == 1));
+    assert!(!ai.learned_rules.iter().any(|r| r.state == 4 && r.action == 1));
+}
+
+#[test]
+fn test_crystallized_rules_restored_after_load() {
+    let path = "test_crystallize.dsym";
+    let mut ai = Singularity::new(6, vec![3]);
+    ai.config.crystallize_threshold = 2;
+    for _ in 0..2 { replay(&mut ai, 3, 2, 2.0); }
+    ai.save_to_file(path).unwrap();
+
+    let mut loaded = Singularity::new(6, vec![3]);
+    loaded.config.crystallize_threshold = 2;
+    loaded.load_from_file(path).unwrap();
+    std::fs::remove_file(path).ok();
+    assert!(loaded.crystallized.contains_key(&(3, 2)));
+    assert_eq!(loaded.bootstrapper.rules.len(), 1);
+}
+
+#[test]
+fn test_demotion_removes_the_crystallized_rule_itself() {
+    let path = std::env::temp_dir().join(format!("ds_crystallize_demote_{}.dsym", std::process::id()));
+    let mut ai = Singularity::new(6, vec![3]);
+    ai.config.crystallize_threshold = 2;
+    // 同じ (状態, 行動) に対する利用者のルール（結晶化と同じ強度）
+    ai.bootstrapper.add_hamiltonian_rule(4, 1, ai.config.crystallize_strength);
+    for _ in 0..2 { replay(&mut ai, 4, 1, 2.0); }
+    assert!(ai.crystallized.contains_key(&(4, 1)));
+    assert_eq!(ai.bootstrapper.rules.len(), 2);
+
+    // 強度を書き換えても、保存・読み込みを挟んでも、降格で取り除かれるのは結晶化したルールだけ
+    assert_eq!(ai.bootstrapper.update_strength(&Condition::Id(4), 1, 0.3), 2);
+    ai.save_to_file(path.to_str().unwrap()).unwrap();
+    let mut loaded = Singularity::new(6, vec![3]);
+    loaded.config.crystallize_threshold = 2;
+    loaded.load_from_file(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(loaded.bootstrapper.rules.iter().filter(|r| r.crystallized).count(), 1);
+
+    for _ in 0..3 { replay(&mut loaded, 4, 1, -1.0); }
+    assert!(!loaded.crystallized.contains_key(&(4, 1)));
+    assert_eq!(loaded.bootstrapper.rules.len(), 1);
+    assert!(!loaded.bootstrapper.rules[0].crystallized);
+}
+
+#[test]
+fn test_load_replaces_live_crystallized_keys() {
+    let path = std::env::temp_dir().join(format!("ds_crystallize_reload_{}.dsym", std::process::id()));
+    let mut saved = Singularity::new(6, vec![3]);
+    saved.config.crystallize_threshold = 2;
+    for _ in 0..2 { replay(&mut saved, 3, 2, 2.0); }
+    saved.save_to_file(path.to_str().unwrap()).unwrap();
+
+    // 読み込み先が自分で結晶化させていた (状態, 行動) は、ファイルのルール集に置き換わった後まで残らない
+    let mut loaded = Singularity::new(6, vec![3]);
+    loaded.config.crystallize_threshold = 2;
+    for _ in 0..2 { replay(&mut loaded, 5, 0, 2.0); }
+    assert!(loaded.crystallized.contains_key(&(5, 0)));
+    loaded.load_from_file(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(loaded.crystallized.keys().copied().collect::<Vec<_>>(), vec![(3, 2)]);
+    assert!(loaded.bootstrapper.rules.iter().all(|r| r.condition_id == 3 && r.target_action == 2));
+}
@@ -0,0 +1,97 @@
+use dark_singularity::core::config::ExplorationPolicy;
+use dark_singularity::core::singularity::Singularity;
+use std::time::Duration;
+
+#[test]
+fn test_generous_budget_is_not_degraded() {
+    let mut ai = Singularity::new(16, vec![4, 3]);
+    for i in 0..10 {
+        let (actions, degraded) = ai.select_actions_within(i % 16, Duration::from_secs(5));
+        assert_eq!(actions.len(), 2);
+        assert!(!degraded);
+        assert!(!ai.last_decision_degraded);
+        ai.learn(0.5);
+    }
+    ai.select_actions_within(3, Duration::from_secs(5));
+    assert!(ai.score_cache.contains_key(&3));
+}
+
+#[test]
+fn test_zero_budget_falls_back_to_cached_scores() {
+    let mut ai = Singularity::new(16, vec![4, 3]);
+    ai.config.exploration_policy = ExplorationPolicy::Greedy;
+    ai.select_actions_within(5, Duration::from_secs(5));
+    ai.learn(0.0);
+    ai.select_actions_within(5, Duration::from_secs(5));
+    let cached = ai.score_cache.get(&5).unwrap().to_vec();
+    let psi = ai.mwso.psi_real.clone();
+
+    let (actions, degraded) = ai.select_actions_within(5, Duration::ZERO);
+    assert!(degraded);
+    assert!(ai.last_decision_degraded);
+    assert_eq!(actions.len(), 2);
+
+    // 波動成分はキャッシュから取られ、キャッシュ自体は更新されない
+    let waves: Vec<f32> = ai.last_explanation.iter().map(|b| b.wave).collect();
+    assert_eq!(waves, cached);
+    assert_eq!(ai.score_cache.get(&5).unwrap(), cached.as_slice());
+
+    // 時間発展を省略しても、現在の状態の注入だけは行われる
+    assert_ne!(ai.mwso.psi_real, psi);
+}
+
+#[test]
+fn test_zero_budget_without_cache_still_decides() {
+    let mut ai = Singularity::new(16, vec![4, 3]);
+    let (actions, degraded) = ai.select_actions_within(2, Duration::ZERO);
+    assert!(degraded);
+    assert!(actions[0] < 4 && actions[1] < 3);
+    assert!(ai.score_cache.is_empty());
+
+    // 通常の select_actions は縮退しない
+    ai.select_actions(2);
+    assert!(!ai.last_decision_degraded);
+}
+
+#[test]
+fn test_learning_invalidates_cached_scores() {
+    let mut ai = Singularity::new(16, vec![4, 3]);
+    ai.select_actions_within(5, Duration::from_secs(5));
+    ai.select_actions_within(6, Duration::from_secs(5));
+    assert_eq!(ai.score_cache.len(), 2);
+    ai.learn(1.0);
+    // 学習した状態のスコアだけが捨てられる
+    assert!(ai.score_cache.is_empty());
+
+    ai.select_actions_within(7, Duration::from_secs(5));
+    ai.learn_batch(&[(9, vec![0, 4], 1.0)]);
+    assert!(ai.score_cache.contains_key(&7));
+}
+
+#[test]
+fn test_cache_keeps_most_recently_used_states() {
+    let mut ai = Singularity::new(16, vec![4, 3]);
+    ai.config.score_cache_capacity = 2;
+    ai.select_actions_within(1, Duration::from_secs(5));
+    ai.select_actions_within(2, Duration::from_secs(5));
+    // 状態 1 をフォールバックとして使うと、次に追い出されるのは状態 2
+    ai.select_actions_within(1, Duration::ZERO);
+    ai.select_actions_within(3, Duration::from_secs(5));
+    assert_eq!(ai.score_cache.len(), 2);
+    assert!(ai.score_cache.contains_key(&1));
+    assert!(!ai.score_cache.contains_key(&2));
+
+    ai.config.score_cache_capacity = 0;
+    ai.select_actions_within(4, Duration::from_secs(5));
+    assert!(ai.score_cache.is_empty());
+}
+
+#[test]
+fn test_checkpoint_restore_drops_cached_scores() {
+    let mut ai = Singularity::new(16, vec![4, 3]);
+    ai.select_actions_within(5, Duration::from_secs(5));
+    let snapshot = ai.checkpoint();
+    ai.select_actions_within(6, Duration::from_secs(5));
+    ai.restore(&snapshot);
+    assert!(ai.score_cache.is_empty());
+}
@@ -0,0 +1,152 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::wave::{Wave, from_wave, to_wave};
+
+fn trained() -> Singularity {
+    let mut ai = Singularity::new(16, vec![4]);
+    for i in 0..30 {
+        ai.select_actions(i % 16);
+        ai.learn(if i % 3 == 0 { 1.5 } else { -0.5 });
+    }
+    ai
+}
+
+#[test]
+fn test_retrieval_snr_drops_as_memories_accumulate() {
+    let mut mwso = MWSO::new(1024);
+    assert_eq!(mwso.retrieval_snr(0), 100.0, "empty memory has no interference");
+
+    let set_pattern = |mwso: &mut MWSO, seed: usize| {
+        for i in 0..mwso.dim {
+            let phase = ((i * 2654435761 + seed * 40503) % 6283) as f32 / 1000.0;
+            mwso.psi_real[i] = to_wave(phase.cos());
+            mwso.psi_imag[i] = to_wave(phase.sin());
+        }
+    };
+    set_pattern(&mut mwso, 0);
+    mwso.imprint_qcel(0, 1.0);
+    let single = mwso.retrieval_snr(0);
+
+    for s in 1..60 {
+        set_pattern(&mut mwso, s);
+        mwso.imprint_qcel(s, 1.0);
+    }
+    set_pattern(&mut mwso, 0);
+    let crowded = mwso.retrieval_snr(0);
+    assert!(crowded.is_finite() && crowded > 0.0);
+    assert!(crowded < single, "interference must lower the SNR: {} -> {}", single, crowded);
+}
+
+#[test]
+fn test_grow_dimension_keeps_penalties() {
+    let mut ai = trained();
+    ai.set_penalty(2, 1, 0.7);
+    ai.set_penalty(5, 3, 0.3);
+    let before: Vec<Vec<f32>> = (0..16).map(|s| ai.get_penalties(s)).collect();
+    let old_dim = ai.mwso.dim;
+
+    assert!(!ai.grow_dimension(old_dim), "growth must increase the dimension");
+    assert!(ai.grow_dimension(old_dim * 2));
+    assert_eq!(ai.mwso.dim, old_dim * 2);
+    assert_eq!(ai.mwso.psi_real.len(), old_dim * 2);
+    assert_eq!(ai.empty_penalty.len(), old_dim * 2);
+    for (s, row) in before.iter().enumerate() {
+        for (a, (&p, q)) in row.iter().zip(ai.get_penalties(s)).enumerate() {
+            assert!((p - q).abs() < 1e-5, "penalty ({}, {}) changed: {} -> {}", s, a, p, q);
+        }
+    }
+
+    for i in 0..20 {
+        let actions = ai.select_actions(i % 16);
+        assert!(actions.iter().all(|&a| (0..4).contains(&a)));
+        ai.learn(0.5);
+    }
+}
+
+#[test]
+fn test_grow_dimension_rejected_when_sharded() {
+    let mut ai = Singularity::new(8, vec![20]);
+    let dim = ai.mwso.dim;
+    assert!(!ai.grow_dimension(dim * 2));
+    assert_eq!(ai.mwso.dim, dim);
+}
+
+#[test]
+fn test_auto_grow_doubles_up_to_cap() {
+    let mut ai = Singularity::new(16, vec![4]);
+    let start = ai.mwso.dim;
+    ai.config.auto_grow_snr = f32::MAX;
+    ai.config.auto_grow_interval = 1;
+    ai.config.auto_grow_max_dim = start * 4;
+    for i in 0..5 {
+        ai.select_actions(i);
+        ai.learn(1.0);
+    }
+    assert_eq!(ai.mwso.dim, start * 4, "growth must stop at auto_grow_max_dim");
+
+    let mut idle = Singularity::new(16, vec![4]);
+    for i in 0..5 {
+        idle.select_actions(i);
+        idle.learn(1.0);
+    }
+    assert_eq!(idle.mwso.dim, start, "auto growth is disabled by default");
+}
+
+#[test]
+fn test_grown_model_reloads_into_fresh_instance() {
+    let mut ai = trained();
+    let dim = ai.mwso.dim * 2;
+    ai.grow_dimension(dim);
+    ai.select_actions(3);
+    ai.learn(1.0);
+
+    let path = std::env::temp_dir().join("ds_dimension_growth_test.dsym");
+    ai.save_to_file(path.to_str().unwrap()).unwrap();
+    let mut loaded = Singularity::new(16, vec![4]);
+    loaded.load_from_file(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(loaded.mwso.dim, dim);
+    // ファイル形式は f32 なので、f64 モードでは f32 に丸めた値が戻る
+    let rounded: Vec<Wave> = ai.mwso.psi_real.iter().map(|&v| to_wave(from_wave(v))).collect();
+    assert_eq!(loaded.mwso.psi_real, rounded);
+    assert_eq!(loaded.mwso.gravity_field, ai.mwso.gravity_field);
+    assert_eq!(loaded.mwso.q_memory_re, ai.mwso.q_memory_re);
+}
+
+#[test]
+fn test_pre_growth_checkpoint_loads_into_grown_instance() {
+    let ai = trained();
+    let path = std::env::temp_dir().join("ds_dimension_growth_pre_test.dsym");
+    ai.save_to_file(path.to_str().unwrap()).unwrap();
+
+    let mut grown = trained();
+    let dim = grown.mwso.dim * 2;
+    grown.grow_dimension(dim);
+    let psi = grown.mwso.psi_real.clone();
+    let result = grown.load_from_file(path.to_str().unwrap());
+    let _ = std::fs::remove_file(&path);
+
+    // 次元の違う波は読み飛ばし、後続のセクションまで読み切る
+    result.unwrap();
+    assert_eq!(grown.mwso.dim, dim);
+    assert_eq!(grown.mwso.psi_real, psi);
+    assert_eq!(grown.mwso.entanglements.len(), ai.mwso.entanglements.len());
+    assert_eq!(grown.bootstrapper.rules.len(), ai.bootstrapper.rules.len());
+    grown.select_actions(2);
+    grown.learn(1.0);
+}
+
+#[test]
+fn test_restore_rolls_back_growth() {
+    let mut ai = trained();
+    let dim = ai.mwso.dim;
+    let snapshot = ai.checkpoint();
+    ai.grow_dimension(dim * 2);
+    ai.restore(&snapshot);
+
+    assert_eq!(ai.mwso.dim, dim);
+    assert_eq!(ai.empty_penalty.len(), dim);
+    ai.select_actions(1);
+    ai.learn(-0.5);
+}
@@ -0,0 +1,116 @@
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_repeated_failures_build_frustration() {
+    let mut ai = Singularity::new(5, vec![4]);
+    assert_eq!(ai.frustration, 0.0);
+    assert_eq!(ai.adrenaline, 0.0);
+
+    let mut previous = 0.0;
+    for _ in 0..5 {
+        ai.select_actions(2);
+        ai.learn(-1.0);
+        assert!(ai.frustration > previous, "Frustration should accumulate on repeated failures");
+        previous = ai.frustration;
+    }
+    assert_eq!(ai.failure_streaks.get(&2), Some(&5));
+    assert!(ai.adrenaline > 0.3, "Damage should raise adrenaline: {}", ai.adrenaline);
+
+    // 成功で連続失敗はリセットされ、感情は減衰する
+    ai.select_actions(2);
+    ai.learn(1.0);
+    assert!(!ai.failure_streaks.contains_key(&2));
+    assert!(ai.frustration < previous);
+
+    for _ in 0..60 {
+        ai.select_actions(0);
+        ai.learn(0.0);
+    }
+    assert!(ai.adrenaline < 0.05);
+    assert!(ai.frustration < 0.05);
+}
+
+#[test]
+fn test_frustration_scattered_failures_are_milder() {
+    let mut focused = Singularity::new(5, vec![4]);
+    let mut scattered = Singularity::new(5, vec![4]);
+    for i in 0..5 {
+        focused.select_actions(1);
+        focused.learn(-1.0);
+        scattered.select_actions(i);
+        scattered.learn(-1.0);
+    }
+    assert!(focused.frustration > scattered.frustration);
+}
+
+#[test]
+fn test_morale_follows_win_and_loss_streaks() {
+    let mut ai = Singularity::new(5, vec![4]);
+    for _ in 0..5 {
+        ai.select_actions(0);
+        ai.learn(1.0);
+    }
+    assert_eq!(ai.reward_streak, 5);
+    let high = ai.morale;
+    assert!(high > 1.3, "Win streak should lift morale: {}", high);
+
+    for _ in 0..5 {
+        ai.select_actions(0);
+        ai.learn(-1.0);
+    }
+    assert_eq!(ai.reward_streak, -5);
+    assert!(ai.morale < 1.0, "Loss streak should sink morale: {}", ai.morale);
+}
+
+#[test]
+fn test_morale_only_tilts_momentum() {
+    let mut ai = Singularity::new(5, vec![4]);
+    for _ in 0..8 {
+        ai.select_actions(0);
+        ai.learn(1.0);
+    }
+    ai.select_actions(0);
+    assert!(ai.action_momentum.iter().any(|&m| m > 0.5));
+
+    // 勢いは運動量の項で既に加点されているので、士気による増幅は勢いの 1/4 に抑える
+    // 勢いが上限 2.0 でも士気項は 士気 × 0.1 × 1.5 までに収まり、連勝中に同じ手へ固着しない
+    for b in ai.explain_last_decision() {
+        assert!(b.morale <= ai.morale * 0.15 + 1e-6, "Morale term should stay a tilt: {}", b.morale);
+    }
+}
+
+#[test]
+fn test_stalemates_erode_patience() {
+    let mut ai = Singularity::new(5, vec![4]);
+    for _ in 0..10 {
+        ai.select_actions(1);
+        ai.learn(0.0);
+    }
+    assert_eq!(ai.stalemate_streak, 10);
+    let worn = ai.patience;
+    assert!(worn < 0.5, "Repeated stalemates should wear patience down: {}", worn);
+
+    ai.select_actions(1);
+    ai.learn(1.0);
+    assert_eq!(ai.stalemate_streak, 0);
+    assert!(ai.patience > worn);
+}
+
+#[test]
+fn test_velocity_trust_drops_after_wrong_predictions() {
+    let mut ai = Singularity::new(5, vec![1]);
+    for _ in 0..10 {
+        ai.select_actions(3);
+        ai.learn(1.0);
+    }
+    assert!(ai.velocity_trust > 0.99, "Consistent outcomes keep trust high: {}", ai.velocity_trust);
+    assert!((ai.mwso.lr_scale - 1.0).abs() < 0.01);
+
+    // 成功を期待していた状態で失敗が続く
+    for _ in 0..5 {
+        ai.select_actions(3);
+        ai.learn(-1.0);
+    }
+    assert!(ai.velocity_trust < 0.7, "Wrong predictions should erode trust: {}", ai.velocity_trust);
+    assert!(ai.mwso.lr_scale < 0.85);
+}
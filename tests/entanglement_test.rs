@@ -0,0 +1,77 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::wave::from_wave;
+
+fn distance(a: &MWSO, b: &MWSO, bins: std::ops::Range<usize>) -> f32 {
+    bins.map(|i| from_wave((a.psi_real[i] - b.psi_real[i]).powi(2) + (a.psi_imag[i] - b.psi_imag[i]).powi(2))).sum()
+}
+
+#[test]
+fn test_entangle_pulls_toward_other_wave() {
+    let mut a = MWSO::new(64);
+    let mut b = MWSO::new(64);
+    a.inject_state(1, 1.0, &[]);
+    b.inject_state(9, 1.0, &[]);
+    let before = distance(&a, &b, 0..64);
+    let source = b.psi_real.clone();
+
+    a.entangle_with(&b, 0.25);
+    let after = distance(&a, &b, 0..64);
+    assert!((after - before * 0.5625).abs() < before * 1e-3, "distance shrinks by (1 - k)^2: {before} -> {after}");
+    assert_eq!(b.psi_real, source, "the source wave is untouched");
+
+    a.entangle_with(&b, 1.0);
+    assert!(distance(&a, &b, 0..64) < 1e-10);
+}
+
+#[test]
+fn test_entangle_bins_only_touches_selected_range() {
+    let mut a = MWSO::new(64);
+    let mut b = MWSO::new(128);
+    a.inject_state(2, 1.0, &[]);
+    b.inject_state(5, 1.0, &[]);
+    let untouched = a.psi_real[32..].to_vec();
+    a.entangle_bins_with(0..32, &b, 0..64, 1.0);
+    assert_eq!(a.psi_real[32..].to_vec(), untouched);
+    for n in 0..32 {
+        assert!((a.psi_real[n] - b.psi_real[n * 2]).abs() < 1e-6, "bins map by relative position");
+    }
+    a.entangle_bins_with(60..90, &b, 200..300, 1.0);
+    a.entangle_bins_with(0..0, &b, 0..64, 1.0);
+}
+
+#[test]
+fn test_singularity_shares_selected_action_bins() {
+    for sizes in [vec![4], vec![12, 8]] {
+        let mut scout = Singularity::new(16, sizes.clone());
+        let mut defender = Singularity::new(16, sizes.clone());
+        scout.select_actions(3);
+        defender.select_actions(11);
+
+        let fear = 1;
+        let action_size = defender.action_size;
+        let before = defender.get_raw_scores(action_size);
+        let target = scout.get_raw_scores(action_size);
+        for _ in 0..40 {
+            defender.entangle_with(&scout, &[fear], 0.5);
+        }
+        let after = defender.get_raw_scores(action_size);
+        assert!((after[fear] - target[fear]).abs() < 1e-3, "{sizes:?}: {} vs {}", after[fear], target[fear]);
+        assert!((after[0] - before[0]).abs() < 1e-6, "{sizes:?}: other actions keep their own wave");
+    }
+}
+
+#[test]
+fn test_empty_action_list_entangles_every_shared_action() {
+    let mut a = Singularity::new(8, vec![4]);
+    let mut b = Singularity::new(8, vec![4]);
+    a.select_actions(0);
+    b.select_actions(5);
+    for _ in 0..40 {
+        a.entangle_with(&b, &[], 0.5);
+    }
+    let (sa, sb) = (a.get_raw_scores(4), b.get_raw_scores(4));
+    for i in 0..4 {
+        assert!((sa[i] - sb[i]).abs() < 0.05, "action {i}: {} vs {}", sa[i], sb[i]);
+    }
+}
@@ -0,0 +1,81 @@
+use dark_singularity::core::episode::{EpisodeEvent, EpisodeLog};
+use dark_singularity::core::singularity::Singularity;
+
+fn record_episode() -> EpisodeLog {
+    let mut ai = Singularity::new(64, vec![3, 2]);
+    ai.start_recording();
+    for i in 0..40 {
+        let state = (i * 7) % 64;
+        let actions = ai.select_actions(state);
+        ai.learn(if actions[0] == (state % 3) as i32 { 1.0 } else { -0.4 });
+    }
+    ai.stop_recording().unwrap()
+}
+
+#[test]
+fn test_recording_captures_decisions_and_rewards() {
+    let log = record_episode();
+    assert_eq!(log.events.len(), 80);
+    assert!(matches!(log.events[0], EpisodeEvent::Decision { state_idx: 0, .. }));
+    assert!(matches!(log.events[1], EpisodeEvent::Reward { .. }));
+}
+
+#[test]
+fn test_save_load_roundtrip() {
+    let log = record_episode();
+    let path = std::env::temp_dir().join(format!("ds_episode_{}.dsep", std::process::id()));
+    let path = path.to_str().unwrap();
+    log.save(path).unwrap();
+    let loaded = EpisodeLog::load(path).unwrap();
+    let _ = std::fs::remove_file(path);
+    assert_eq!(loaded, log);
+}
+
+#[test]
+fn test_replay_into_fresh_instance_is_deterministic() {
+    let log = record_episode();
+    let mut fresh = log.fresh_instance();
+    let report = log.replay(&mut fresh).unwrap();
+    assert_eq!(report.decisions, 40);
+    assert_eq!(report.rewards, 40);
+    assert_eq!(report.action_mismatches, 0);
+    assert_eq!(report.first_divergence, None);
+}
+
+#[test]
+fn test_replay_reports_divergence() {
+    let log = record_episode();
+    let mut other = log.fresh_instance();
+    other.system_temperature = 1.5;
+    let report = log.replay(&mut other).unwrap();
+    assert_eq!(report.first_divergence, Some(0));
+}
+
+#[test]
+fn test_replay_resyncs_exploration_state() {
+    let log = record_episode();
+    let mut other = log.fresh_instance();
+    other.scout_mwso.rng_seed ^= 0x1234;
+    other.exploration_beta += 0.3;
+    let report = log.replay(&mut other).unwrap();
+    // 最初の意思決定で食い違いを検出し、記録された乱数・探索の状態へ合わせてから再生する
+    assert_eq!(report.first_divergence, Some(0));
+    assert_eq!(report.action_mismatches, 0);
+}
+
+#[test]
+fn test_unrecorded_paths_make_the_log_unreplayable() {
+    let mut ai = Singularity::new(64, vec![3, 2]);
+    ai.start_recording();
+    ai.select_actions(3);
+    ai.learn(1.0);
+    ai.select_actions_vector(&[(5, 1.0), (9, 0.5)]);
+    ai.learn(-0.5);
+    let log = ai.stop_recording().unwrap();
+    assert!(!log.is_replayable());
+    assert!(log.replay(&mut log.fresh_instance()).is_none());
+
+    let path = std::env::temp_dir().join(format!("ds_episode_unrecorded_{}.dsep", std::process::id()));
+    assert!(log.save(path.to_str().unwrap()).is_err());
+    assert!(!path.exists());
+}
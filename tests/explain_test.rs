@@ -0,0 +1,26 @@
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_explain_last_decision_matches_selection() {
+    let mut ai = Singularity::new(10, vec![4, 3]);
+    ai.bootstrapper.add_hamiltonian_rule(2, 1, 0.5);
+    ai.set_active_conditions(&[2]);
+
+    let actions = ai.select_actions(5);
+    let explanation = ai.explain_last_decision();
+    assert_eq!(explanation.len(), 7, "Every candidate of every category is explained");
+
+    let mut offset = 0;
+    for (cat, &size) in [4usize, 3].iter().enumerate() {
+        let chosen: Vec<_> = explanation.iter().filter(|b| b.category == cat && b.chosen).collect();
+        assert_eq!(chosen.len(), 1);
+        assert_eq!(chosen[0].action, offset + actions[cat] as usize);
+        offset += size;
+    }
+
+    for b in explanation {
+        let sum = b.wave + b.learned_rule + b.knowledge + b.neuron + b.momentum - b.fatigue_penalty + b.opponent + b.morale;
+        assert!((sum - b.total).abs() < 1e-4);
+    }
+    assert!((explanation[1].knowledge - 2.5).abs() < 1e-4, "Hamiltonian field shows up in the knowledge term");
+}
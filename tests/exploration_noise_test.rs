@@ -0,0 +1,93 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::noise::ExplorationNoise;
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::wave::from_wave;
+
+/// 1 回の注入で psi_real に加わったノイズ（strength = 1）
+fn injected(mwso: &mut MWSO) -> Vec<f32> {
+    let before: Vec<f32> = mwso.psi_real.iter().map(|&v| from_wave(v)).collect();
+    mwso.inject_exploration_noise(1.0);
+    mwso.psi_real.iter().zip(&before).map(|(&v, b)| from_wave(v) - b).collect()
+}
+
+fn mean_var(xs: &[f32]) -> (f32, f32) {
+    let n = xs.len() as f32;
+    let mean = xs.iter().sum::<f32>() / n;
+    (mean, xs.iter().map(|x| (x - mean).powi(2)).sum::<f32>() / n)
+}
+
+#[test]
+fn test_uniform_noise_is_centered_and_nonzero_everywhere() {
+    let mut mwso = MWSO::new(4096);
+    let noise = injected(&mut mwso);
+    let (mean, var) = mean_var(&noise);
+    assert!(mean.abs() < 0.05, "mean {}", mean);
+    // [-1, 1] の一様分布の分散は 1/3
+    assert!((var - 1.0 / 3.0).abs() < 0.03, "var {}", var);
+    assert!(noise[0] != 0.0);
+    assert!(noise.iter().all(|x| x.abs() <= 1.0 + 1e-4));
+}
+
+#[test]
+fn test_gaussian_noise_has_unit_variance() {
+    let mut mwso = MWSO::new(4096);
+    mwso.set_exploration_noise(ExplorationNoise::Gaussian);
+    let noise = injected(&mut mwso);
+    let (mean, var) = mean_var(&noise);
+    assert!(mean.abs() < 0.06, "mean {}", mean);
+    assert!((var - 1.0).abs() < 0.1, "var {}", var);
+    assert!(noise.iter().all(|x| x.is_finite()));
+}
+
+#[test]
+fn test_ou_noise_is_correlated_across_ticks() {
+    let mut mwso = MWSO::new(4096);
+    mwso.set_exploration_noise(ExplorationNoise::OrnsteinUhlenbeck);
+    let first = injected(&mut mwso);
+    let second = injected(&mut mwso);
+    let (_, var) = mean_var(&second);
+    assert!((var - 1.0).abs() < 0.15, "var {}", var);
+    let corr = first.iter().zip(&second).map(|(a, b)| a * b).sum::<f32>() / first.len() as f32;
+    // 1 - OU_REVERSION = 0.85 程度の自己相関を持つ
+    assert!(corr > 0.7, "corr {}", corr);
+
+    let mut gaussian = MWSO::new(4096);
+    gaussian.set_exploration_noise(ExplorationNoise::Gaussian);
+    let (a, b) = (injected(&mut gaussian), injected(&mut gaussian));
+    let corr = a.iter().zip(&b).map(|(a, b)| a * b).sum::<f32>() / a.len() as f32;
+    assert!(corr.abs() < 0.1, "corr {}", corr);
+}
+
+#[test]
+fn test_noise_is_reproducible_from_seed() {
+    for mode in [ExplorationNoise::Uniform, ExplorationNoise::Gaussian, ExplorationNoise::OrnsteinUhlenbeck] {
+        let mut a = MWSO::new(256);
+        let mut b = MWSO::new(256);
+        a.set_exploration_noise(mode);
+        b.set_exploration_noise(mode);
+        for _ in 0..3 {
+            assert_eq!(injected(&mut a), injected(&mut b));
+        }
+    }
+}
+
+#[test]
+fn test_mode_ids_round_trip() {
+    for id in 0..4 {
+        assert_eq!(ExplorationNoise::from_id(id).unwrap().id(), id);
+    }
+    assert_eq!(ExplorationNoise::from_id(4), None);
+    assert_eq!(ExplorationNoise::default(), ExplorationNoise::Uniform);
+}
+
+#[test]
+fn test_singularity_runs_with_ou_noise() {
+    let mut ai = Singularity::new(16, vec![4, 3]);
+    ai.set_exploration_noise(ExplorationNoise::OrnsteinUhlenbeck);
+    for i in 0..50 {
+        let actions = ai.select_actions(i % 16);
+        assert_eq!(actions.len(), 2);
+        ai.learn(if i % 3 == 0 { 1.0 } else { -0.2 });
+    }
+    assert!(ai.wave_norm().is_finite());
+}
@@ -0,0 +1,62 @@
+use dark_singularity::core::config::ExplorationPolicy;
+use dark_singularity::core::singularity::Singularity;
+
+fn train(ai: &mut Singularity, state: usize, best: i32) {
+    for _ in 0..40 {
+        let a = ai.select_actions(state)[0];
+        ai.learn(if a == best { 1.5 } else { -0.5 });
+    }
+}
+
+fn distinct_choices(ai: &mut Singularity, state: usize, n: usize) -> usize {
+    let mut seen = std::collections::HashSet::new();
+    for _ in 0..n {
+        seen.insert(ai.select_actions(state)[0]);
+        ai.history.clear();
+    }
+    seen.len()
+}
+
+#[test]
+fn test_default_policy_is_top_k_softmax() {
+    assert_eq!(Singularity::new(8, vec![4]).config.exploration_policy, ExplorationPolicy::TopKSoftmax);
+    assert_eq!(ExplorationPolicy::from_id(4), Some(ExplorationPolicy::Ucb));
+    assert_eq!(ExplorationPolicy::from_id(9), None);
+}
+
+#[test]
+fn test_greedy_always_picks_top_candidate() {
+    let mut ai = Singularity::new(8, vec![4]);
+    train(&mut ai, 2, 1);
+    ai.config.exploration_policy = ExplorationPolicy::Greedy;
+    for _ in 0..20 {
+        ai.select_actions(2);
+        let top = ai.explain_last_decision().iter()
+            .max_by(|a, b| a.total.partial_cmp(&b.total).unwrap())
+            .unwrap().action;
+        let chosen = ai.explain_last_decision().iter().find(|b| b.chosen).unwrap().action;
+        assert_eq!(chosen, top);
+        ai.history.clear();
+    }
+}
+
+#[test]
+fn test_epsilon_greedy_uses_exploration_beta() {
+    let mut ai = Singularity::new(8, vec![6]);
+    train(&mut ai, 2, 1);
+    ai.config.exploration_policy = ExplorationPolicy::EpsilonGreedy;
+
+    ai.exploration_beta = 0.0;
+    assert_eq!(distinct_choices(&mut ai, 2, 30), 1);
+    ai.exploration_beta = 1.0;
+    assert!(distinct_choices(&mut ai, 2, 60) >= 4, "Full epsilon should explore uniformly");
+}
+
+#[test]
+fn test_ucb_tries_unvisited_actions() {
+    let mut ai = Singularity::new(8, vec![5]);
+    ai.config.exploration_policy = ExplorationPolicy::Ucb;
+    ai.exploration_beta = 100.0; // 訪問回数の項が支配的になる
+    assert_eq!(distinct_choices(&mut ai, 3, 5), 5);
+    assert!(ai.action_visits[&3].iter().all(|&v| v == 1));
+}
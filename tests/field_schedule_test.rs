@@ -0,0 +1,80 @@
+use dark_singularity::core::field_schedule::{FieldDecay, FieldScheduler};
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::wave::from_wave;
+
+#[test]
+fn test_scheduler_applies_decay_profile_and_expires() {
+    let mut scheduler = FieldScheduler::default();
+    let field = vec![1.0; 4];
+    let zero = vec![0.0; 4];
+    let linear = scheduler.schedule(&field, &zero, 1.0, 4, FieldDecay::Linear);
+    let mut re = vec![0.0; 4];
+    let mut im = vec![0.0; 4];
+    let mut added = Vec::new();
+    for _ in 0..6 {
+        let before = from_wave(re[0]);
+        scheduler.apply(&mut re, &mut im);
+        added.push(from_wave(re[0]) - before);
+    }
+    assert_eq!(added, vec![1.0, 0.75, 0.5, 0.25, 0.0, 0.0]);
+    assert!(scheduler.is_empty(), "expired fields are dropped");
+    assert!(!scheduler.cancel(linear));
+
+    let exp = scheduler.schedule(&field, &zero, 2.0, 100, FieldDecay::Exponential { half_life: 2.0 });
+    assert_eq!(scheduler.fields()[0].current_strength(), 2.0);
+    scheduler.apply(&mut re, &mut im);
+    scheduler.apply(&mut re, &mut im);
+    assert!((scheduler.fields()[0].current_strength() - 1.0).abs() < 1e-6);
+    assert_eq!(scheduler.fields()[0].remaining(), 98);
+    assert!(scheduler.cancel(exp));
+}
+
+#[test]
+fn test_negative_strength_damps_selected_bins() {
+    let mut scheduler = FieldScheduler::default();
+    let mask = vec![1.0, 1.0, 0.0, 0.0];
+    scheduler.schedule(&mask, &[0.0; 4], -0.5, 3, FieldDecay::Constant);
+    let mut re = vec![1.0; 4];
+    let mut im = vec![1.0; 4];
+    scheduler.apply(&mut re, &mut im);
+    assert_eq!(from_wave(re[0]), 0.5);
+    assert_eq!(from_wave(im[1]), 0.5);
+    assert_eq!(from_wave(re[3]), 1.0);
+}
+
+#[test]
+fn test_step_core_matches_manual_injection() {
+    let mut scheduled = MWSO::new(64);
+    let mut manual = MWSO::new(64);
+    let field_re: Vec<f32> = (0..64).map(|i| (i as f32 * 0.3).cos()).collect();
+    let field_im: Vec<f32> = (0..64).map(|i| (i as f32 * 0.3).sin()).collect();
+    assert!(scheduled.schedule_external_state(&field_re[..10], &field_im, 0.2, 3, FieldDecay::Constant).is_none());
+    scheduled.schedule_external_state(&field_re, &field_im, 0.2, 3, FieldDecay::Constant).unwrap();
+
+    for step in 0..5 {
+        if step < 3 { manual.inject_external_state(&field_re, &field_im, 0.2); }
+        manual.step_core(0.1, 0.0, 0.5, 0.5, &[]);
+        scheduled.step_core(0.1, 0.0, 0.5, 0.5, &[]);
+    }
+    assert_eq!(scheduled.psi_real, manual.psi_real);
+    assert_eq!(scheduled.psi_imag, manual.psi_imag);
+    assert!(scheduled.field_schedule.is_empty());
+}
+
+#[test]
+fn test_singularity_suppresses_actions_for_duration() {
+    for sizes in [vec![4], vec![12, 8]] {
+        let mut ai = Singularity::new(8, sizes.clone());
+        let mut baseline = Singularity::new(8, sizes.clone());
+        let id = ai.schedule_action_field(&[1, 3], -0.9, 200, FieldDecay::Constant);
+        for i in 0..5 {
+            ai.select_actions(i);
+            baseline.select_actions(i);
+        }
+        let (scores, base) = (ai.get_raw_scores(ai.action_size), baseline.get_raw_scores(ai.action_size));
+        assert!(scores[1] < base[1] && scores[3] < base[3], "{sizes:?}: {scores:?} vs {base:?}");
+        assert!(ai.cancel_scheduled_field(id));
+        assert!(!ai.cancel_scheduled_field(id));
+    }
+}
@@ -0,0 +1,73 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::wave::{Wave, to_wave};
+
+fn set_wave(mwso: &mut MWSO, seed: usize) {
+    for i in 0..mwso.dim {
+        let phase = ((i * 7919 + seed * 104729) % 997) as f32 * 0.37;
+        mwso.psi_real[i] = to_wave(phase.cos()) / (mwso.dim as Wave).sqrt();
+        mwso.psi_imag[i] = to_wave(phase.sin()) / (mwso.dim as Wave).sqrt();
+    }
+}
+
+fn memory_norm(mwso: &MWSO) -> f64 {
+    mwso.q_memory_re.iter().zip(&mwso.q_memory_im).map(|(re, im)| re * re + im * im).sum::<f64>().sqrt()
+}
+
+#[test]
+fn test_forget_halves_memory_after_half_life() {
+    let mut mwso = MWSO::new(256);
+    set_wave(&mut mwso, 1);
+    mwso.imprint_qcel(3, 1.0);
+    let before = memory_norm(&mwso);
+
+    mwso.forget(100.0);
+    assert_eq!(memory_norm(&mwso), before, "forgetting is disabled by default");
+
+    mwso.memory_half_life = 100.0;
+    mwso.forget(100.0);
+    assert!((memory_norm(&mwso) / before - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn test_step_core_applies_forgetting_in_batches() {
+    let mut mwso = MWSO::new(128);
+    set_wave(&mut mwso, 2);
+    mwso.imprint_qcel(5, 1.0);
+    mwso.memory_half_life = 32.0;
+    let before = memory_norm(&mwso);
+    let penalty = vec![0.0; 128];
+
+    for _ in 0..15 { mwso.step_core(0.1, 0.0, 1.0, 0.5, &penalty); }
+    assert_eq!(memory_norm(&mwso), before, "decay waits for a full batch");
+    for _ in 0..49 { mwso.step_core(0.1, 0.0, 1.0, 0.5, &penalty); }
+    assert!((memory_norm(&mwso) / before - 0.25).abs() < 1e-4, "two half-lives leave a quarter");
+}
+
+#[test]
+fn test_old_imprints_lose_to_recent_ones() {
+    let run = |half_life: f32| {
+        let mut mwso = MWSO::new(512);
+        mwso.memory_half_life = half_life;
+        set_wave(&mut mwso, 1);
+        mwso.imprint_qcel(3, 1.0);
+        mwso.forget(200.0);
+        set_wave(&mut mwso, 2);
+        mwso.imprint_qcel(3, 1.0);
+        set_wave(&mut mwso, 1);
+        mwso.bank_resonances(3)[0]
+    };
+    let remembered = run(0.0);
+    let forgotten = run(50.0);
+    assert!(forgotten < remembered * 0.5, "old strategy resonance {} vs {}", forgotten, remembered);
+}
+
+#[test]
+fn test_singularity_sets_half_life_on_every_shard() {
+    let mut ai = Singularity::new(8, vec![20]);
+    ai.set_memory_half_life(64.0);
+    assert_eq!(ai.mwso.memory_half_life, 64.0);
+    assert!(ai.sharded_mwso.as_ref().unwrap().shards.iter().all(|s| s.memory_half_life == 64.0));
+    ai.set_memory_half_life(-1.0);
+    assert_eq!(ai.mwso.memory_half_life, 0.0);
+}
@@ -0,0 +1,64 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_gravity_stats_summarize_field() {
+    let mut mwso = MWSO::new(1024);
+    mwso.gravity_field[0] = 0.9;
+    mwso.gravity_field[1] = 0.6;
+    mwso.gravity_field[2] = 0.1;
+    let stats = mwso.gravity_stats(0.5);
+    assert_eq!(stats.max, 0.9);
+    assert_eq!(stats.above_threshold, 2);
+    assert!((stats.mean - 1.6 / 1024.0).abs() < 1e-5);
+}
+
+#[test]
+fn test_erode_gravity_targets_only_that_action() {
+    let mut mwso = MWSO::new(1024);
+    // 4 アクション × 256 ビン
+    for g in &mut mwso.gravity_field { *g = 0.8; }
+    mwso.erode_gravity(1, 0.3, 4);
+    assert!((mwso.gravity_for_action(0, 4) - 0.8).abs() < 1e-5);
+    assert!((mwso.gravity_for_action(1, 4) - 0.5).abs() < 1e-5);
+    assert!((mwso.gravity_for_action(2, 4) - 0.8).abs() < 1e-5);
+
+    // 0 未満にはならない
+    mwso.erode_gravity(1, 5.0, 4);
+    assert_eq!(mwso.gravity_for_action(1, 4), 0.0);
+}
+
+#[test]
+fn test_strong_rewards_black_hole_and_erosion_releases() {
+    let mut ai = Singularity::new(8, vec![4]);
+    for _ in 0..30 {
+        ai.select_actions(0);
+        ai.learn(2.0);
+    }
+    let favored = ai.last_actions[0];
+    let before = ai.get_gravity_for_action(favored);
+    assert!(before > 0.0);
+    assert!(ai.gravity_stats(0.0).max >= before);
+
+    ai.erode_gravity(favored, 1.0);
+    assert_eq!(ai.get_gravity_for_action(favored), 0.0);
+    // 範囲外のアクションは無視される
+    ai.erode_gravity(99, 1.0);
+    assert_eq!(ai.get_gravity_for_action(99), 0.0);
+}
+
+#[test]
+fn test_sharded_gravity_api() {
+    let mut ai = Singularity::new(8, vec![20, 20]);
+    let sharded = ai.sharded_mwso.as_mut().expect("40 actions should be sharded");
+    for shard in &mut sharded.shards {
+        for g in &mut shard.gravity_field { *g = 0.7; }
+    }
+    let stats = ai.gravity_stats(0.5);
+    assert!((stats.mean - 0.7).abs() < 1e-5);
+    assert_eq!(stats.above_threshold, 3 * 1024);
+
+    ai.erode_gravity(25, 0.7);
+    assert_eq!(ai.get_gravity_for_action(25), 0.0);
+    assert!((ai.get_gravity_for_action(24) - 0.7).abs() < 1e-5);
+}
@@ -0,0 +1,69 @@
+use dark_singularity::core::config::MWSOConfig;
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_defaults_match_previous_constants() {
+    let mut configured = MWSO::new(64);
+    configured.config = MWSOConfig::default();
+    let mut plain = MWSO::new(64);
+    for (reward, action) in [(1.5, 1), (-0.5, 1), (2.0, 3)] {
+        configured.adapt(0, reward, &[action], 0.5, 8);
+        plain.adapt(0, reward, &[action], 0.5, 8);
+    }
+    assert_eq!(configured.gravity_field, plain.gravity_field);
+    // 1.5 > 1.2 で形成され、失敗で 0.8 倍（刻み込みの取り壊しでさらに 0.8 倍）、各 adapt で 0.995 倍
+    let expected = 0.1 * 0.995 * 0.8 * 0.8 * 0.995 * 0.995;
+    assert!((plain.gravity_for_action(1, 8) - expected).abs() < 1e-6);
+}
+
+#[test]
+fn test_threshold_controls_gravity_formation() {
+    // 報酬のスケールが 0..1 のゲームでは既定の閾値では重力が形成されない
+    let mut default = MWSO::new(64);
+    default.adapt(0, 0.9, &[2], 0.5, 8);
+    assert_eq!(default.gravity_for_action(2, 8), 0.0);
+
+    let mut tuned = MWSO::new(64);
+    tuned.config.gravity_reward_threshold = 0.5;
+    tuned.config.gravity_increment = 0.4;
+    tuned.config.gravity_cap = 0.6;
+    tuned.config.hawking_decay = 1.0;
+    tuned.adapt(0, 0.9, &[2], 0.5, 8);
+    assert!((tuned.gravity_for_action(2, 8) - 0.4).abs() < 1e-6);
+    tuned.adapt(0, 0.9, &[2], 0.5, 8);
+    assert!((tuned.gravity_for_action(2, 8) - 0.6).abs() < 1e-6, "capped");
+
+    tuned.config.gravity_failure_erosion = 0.5;
+    tuned.adapt(0, -1.0, &[2], 0.5, 8);
+    // 刻み込みの取り壊し (0.8 倍) の後に侵食係数が掛かる
+    assert!((tuned.gravity_for_action(2, 8) - 0.6 * 0.8 * 0.5).abs() < 1e-6);
+
+    tuned.config.gravity_reward_threshold = f32::INFINITY;
+    tuned.adapt(0, 100.0, &[5], 0.5, 8);
+    assert_eq!(tuned.gravity_for_action(5, 8), 0.0);
+}
+
+#[test]
+fn test_evaporation_rate() {
+    let mut mwso = MWSO::new(32);
+    for g in &mut mwso.gravity_field { *g = 1.0; }
+    mwso.config.hawking_decay = 0.5;
+    mwso.adapt(0, 0.0, &[], 0.5, 4);
+    assert!(mwso.gravity_field.iter().all(|&g| (g - 0.5).abs() < 1e-6));
+}
+
+#[test]
+fn test_singularity_applies_config_to_every_shard() {
+    let mut ai = Singularity::new(8, vec![12, 8]);
+    let config = MWSOConfig { gravity_reward_threshold: 0.2, ..MWSOConfig::default() };
+    ai.set_mwso_config(config);
+    assert_eq!(ai.mwso.config, config);
+    assert_eq!(ai.scout_mwso.config, config);
+    let sharded = ai.sharded_mwso.as_ref().unwrap();
+    assert!(sharded.shards.iter().all(|s| s.config == config));
+
+    ai.select_actions(1);
+    ai.learn(0.5);
+    assert!(ai.gravity_stats(0.0).above_threshold > 0, "a 0.5 reward now forms gravity");
+}
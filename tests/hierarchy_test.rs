@@ -0,0 +1,59 @@
+use dark_singularity::core::hierarchy::StateHierarchy;
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_region_penalty_transfers_to_unseen_tile() {
+    let mut ai = Singularity::new(16, vec![4]);
+    ai.set_state_hierarchy(StateHierarchy::uniform(16, 4));
+
+    // タイル0（領域0）でのみ失敗を経験させる
+    for _ in 0..5 {
+        ai.select_actions_hierarchical(0);
+        ai.learn(-1.0);
+    }
+
+    // 未経験のタイル1は自身のペナルティ行を持たない
+    let own_field = ai.accumulate_penalty_field(&[(1, 1.0)]);
+    assert!(own_field.iter().all(|&p| p == 0.0));
+
+    // 所属領域を介すとタイル0の失敗経験が転移している
+    let region_idx = ai.region_state_idx(0);
+    let region_field = ai.accumulate_penalty_field(&[(1, 1.0), (region_idx, 0.5)]);
+    assert!(region_field.iter().sum::<f32>() > 0.0, "Region-level penalty should transfer to tile 1");
+
+    // 別領域のタイルには影響しない
+    let other_region = ai.region_state_idx(1);
+    let other_field = ai.accumulate_penalty_field(&[(5, 1.0), (other_region, 0.5)]);
+    assert!(other_field.iter().all(|&p| p == 0.0));
+}
+
+#[test]
+fn test_region_rule_applies_to_all_tiles() {
+    let mut ai = Singularity::new(16, vec![4]);
+    ai.set_state_hierarchy(StateHierarchy::uniform(16, 4));
+    ai.add_region_rule(2, 3, 0.95);
+
+    // 領域2に属するタイル (8..12) はすべて action 3 に誘導される
+    for tile in 8..12 {
+        let actions = ai.select_actions_hierarchical(tile);
+        assert_eq!(actions[0], 3, "Tile {} should follow the region rule", tile);
+    }
+}
+
+#[test]
+fn test_region_rules_do_not_collide_with_condition_ids() {
+    let mut ai = Singularity::new(16, vec![4]);
+    ai.set_state_hierarchy(StateHierarchy::uniform(16, 4));
+    ai.add_region_rule(2, 3, 0.95);
+    let caller_id = ai.region_state_idx(2) as i32;
+    assert_ne!(ai.bootstrapper.rules[0].condition_id, caller_id);
+
+    // 呼び出し側が同じ値の条件 ID を使っても、領域ルールは発動しない
+    assert_ne!(ai.simulate_rules(&[caller_id], 0).best(0), Some(3));
+
+    // 呼び出し側のルールは領域ルールとして扱われない
+    ai.bootstrapper.add_hamiltonian_rule(caller_id, 1, 0.95);
+    for tile in 8..12 {
+        assert_eq!(ai.select_actions_hierarchical(tile)[0], 3, "Tile {} should follow only the region rule", tile);
+    }
+}
@@ -0,0 +1,64 @@
+use dark_singularity::core::config::Integrator;
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::wave::from_wave;
+
+fn wave_norm(mwso: &MWSO) -> f32 {
+    mwso.psi_real.iter().zip(&mwso.psi_imag).map(|(&r, &i)| from_wave(r * r + i * i)).sum::<f32>().sqrt()
+}
+
+fn run(integrator: Integrator, steps: usize) -> MWSO {
+    let mut mwso = MWSO::new(256);
+    mwso.integrator = integrator;
+    for t in 0..steps {
+        if t % 50 == 0 {
+            mwso.inject_state(t / 50 % 7, 1.0, &[]);
+            mwso.imprint_qcel(t / 50 % 7, 0.5);
+        }
+        mwso.step_core(0.1, 0.0, 0.5, 0.5, &[]);
+    }
+    mwso
+}
+
+#[test]
+fn test_default_integrator_is_explicit() {
+    assert_eq!(MWSO::new(64).integrator, Integrator::Explicit);
+    assert_eq!(Integrator::from_id(1), Some(Integrator::Symplectic));
+    assert_eq!(Integrator::from_id(2), None);
+}
+
+#[test]
+fn test_symplectic_stays_stable_over_long_horizon() {
+    let mwso = run(Integrator::Symplectic, 5000);
+    assert!(mwso.psi_real.iter().chain(&mwso.psi_imag).all(|v| v.is_finite()));
+    assert!((wave_norm(&mwso) - 1.25).abs() < 1e-3);
+
+    let explicit = run(Integrator::Explicit, 200);
+    let symplectic = run(Integrator::Symplectic, 200);
+    assert_ne!(explicit.psi_real, symplectic.psi_real, "the integrator changes the update");
+}
+
+#[test]
+fn test_clone_keeps_integrator() {
+    let mut mwso = MWSO::new(64);
+    mwso.integrator = Integrator::Symplectic;
+    let mut other = MWSO::new(64);
+    other.copy_from(&mwso);
+    assert_eq!(other.integrator, Integrator::Symplectic);
+}
+
+#[test]
+fn test_set_integrator_reaches_every_operator() {
+    let mut ai = Singularity::new(8, vec![20]);
+    ai.set_integrator(Integrator::Symplectic);
+    assert_eq!(ai.config.integrator, Integrator::Symplectic);
+    assert_eq!(ai.mwso.integrator, Integrator::Symplectic);
+    assert_eq!(ai.scout_mwso.integrator, Integrator::Symplectic);
+    assert!(ai.sharded_mwso.as_ref().unwrap().shards.iter().all(|s| s.integrator == Integrator::Symplectic));
+
+    for i in 0..30 {
+        let actions = ai.select_actions(i % 8);
+        assert!((actions[0] as usize) < 20);
+        ai.learn(if i % 2 == 0 { 1.0 } else { -0.5 });
+    }
+}
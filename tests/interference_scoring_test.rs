@@ -0,0 +1,80 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::wave::{Wave, to_wave};
+
+fn set_wave(mwso: &mut MWSO, seed: usize) {
+    for i in 0..mwso.dim {
+        let phase = ((i * 7919 + seed * 104729) % 997) as f32 * 0.37;
+        mwso.psi_real[i] = to_wave(phase.cos()) / (mwso.dim as Wave).sqrt();
+        mwso.psi_imag[i] = to_wave(phase.sin()) / (mwso.dim as Wave).sqrt();
+    }
+}
+
+fn interference(mwso: &mut MWSO, size: usize) -> Vec<f32> {
+    mwso.interference_weight = 0.0;
+    let plain = mwso.get_action_scores(0, size, 0.0, &[]);
+    mwso.interference_weight = 1.0;
+    let with = mwso.get_action_scores(0, size, 0.0, &[]);
+    with.iter().zip(&plain).map(|(w, p)| w - p).collect()
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let mut mwso = MWSO::new(256);
+    set_wave(&mut mwso, 1);
+    mwso.set_input_query(3, 1.0);
+    mwso.imprint_qcel(3, 1.0);
+    assert_eq!(mwso.interference_weight, 0.0);
+    let before = mwso.get_action_scores(0, 8, 0.0, &[]);
+    mwso.interference_weight = 0.0;
+    assert_eq!(mwso.get_action_scores(0, 8, 0.0, &[]), before);
+}
+
+#[test]
+fn test_remembered_phase_raises_scores_and_anti_phase_lowers_them() {
+    let mut mwso = MWSO::new(256);
+    set_wave(&mut mwso, 1);
+    mwso.set_input_query(3, 1.0);
+    mwso.imprint_qcel(3, 1.0);
+
+    // 行動 1 (ビン 32..64) の位相だけ反転させる
+    for i in 32..64 {
+        mwso.psi_real[i] = -mwso.psi_real[i];
+        mwso.psi_imag[i] = -mwso.psi_imag[i];
+    }
+    let delta = interference(&mut mwso, 8);
+    assert!(delta[0] > 0.1, "constructive interference {:?}", delta);
+    assert!(delta[1] < -0.1, "destructive interference {:?}", delta);
+
+    // 記憶と無関係な位相の波ではほぼ干渉しない
+    for i in 0..mwso.dim {
+        let phase = ((i * i * 31) % 1009) as f32 * 1.1;
+        mwso.psi_real[i] = to_wave(phase.cos()) / (mwso.dim as Wave).sqrt();
+        mwso.psi_imag[i] = to_wave(phase.sin()) / (mwso.dim as Wave).sqrt();
+    }
+    let unrelated = interference(&mut mwso, 8);
+    assert!(unrelated.iter().all(|d| d.abs() < delta[0] * 0.5), "{:?}", unrelated);
+}
+
+#[test]
+fn test_bad_outcome_interferes_destructively() {
+    let mut mwso = MWSO::new(256);
+    set_wave(&mut mwso, 1);
+    mwso.set_input_query(5, 1.0);
+    mwso.imprint_qcel(5, -1.0);
+    assert!(interference(&mut mwso, 4).iter().all(|&d| d < 0.0));
+}
+
+#[test]
+fn test_singularity_sets_weight_on_every_shard() {
+    let mut ai = Singularity::new(8, vec![20]);
+    ai.set_interference_scoring(0.5);
+    assert!(ai.sharded_mwso.as_ref().unwrap().shards.iter().all(|s| s.interference_weight == 0.5));
+    for i in 0..20 {
+        ai.select_actions(i % 8);
+        ai.learn(1.0);
+    }
+    let mut scores = vec![0.0; ai.action_size];
+    ai.action_scores_into(&mut scores);
+    assert!(scores.iter().all(|s| s.is_finite()));
+}
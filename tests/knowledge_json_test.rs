@@ -0,0 +1,74 @@
+use dark_singularity::core::knowledge::{Bootstrapper, Condition, RuleConflictPolicy, COMPOSITE_CONDITION_ID};
+use dark_singularity::core::singularity::Singularity;
+
+const PACK: &str = r#"{
+    "policy": "HighestPriority",
+    "rules": [
+        {"condition_id": 0, "target_action": 1, "strength": 0.5},
+        {"condition": {"All": [{"Id": 0}, {"Not": {"Id": 2}}]}, "target_action": 2, "strength": -1.0, "priority": 10},
+        {"condition": {"Id": 3}, "target_action": 0, "strength": 0.8}
+    ]
+}"#;
+
+#[test]
+fn test_from_json_reads_a_rule_pack() {
+    let pack = Bootstrapper::from_json(PACK).unwrap();
+    assert_eq!(pack.policy, RuleConflictPolicy::HighestPriority);
+    assert_eq!(pack.rules.len(), 3);
+    assert_eq!((pack.rules[0].condition_id, pack.rules[0].priority), (0, 0));
+    assert_eq!(pack.rules[1].condition_id, COMPOSITE_CONDITION_ID);
+    assert_eq!(pack.rules[1].priority, 10);
+    assert!(pack.rules[1].is_active(&[0]) && !pack.rules[1].is_active(&[0, 2]));
+    assert_eq!(pack.rules[2].condition_id, 3, "a single id is normalized to condition_id");
+    assert!(pack.rules[2].condition.is_none());
+
+    let minimal = Bootstrapper::from_json(r#"{"rules": []}"#).unwrap();
+    assert_eq!(minimal.policy, RuleConflictPolicy::Sum);
+}
+
+#[test]
+fn test_json_round_trip() {
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.add_hamiltonian_rule(4, 1, 0.3);
+    bootstrapper.add_penalty_rule(5, 2, 0.7);
+    bootstrapper.add_prioritized_rule(Condition::Any(vec![Condition::Id(1), Condition::Id(9)]), 0, 1.5, -2);
+    bootstrapper.policy = RuleConflictPolicy::MaxMagnitude;
+    let json = bootstrapper.to_json().unwrap();
+    assert_eq!(Bootstrapper::from_json(&json).unwrap(), bootstrapper);
+}
+
+#[test]
+fn test_invalid_packs_are_rejected() {
+    assert!(Bootstrapper::from_json("not json").is_err());
+    assert!(Bootstrapper::from_json(r#"{"rules": [{"target_action": 0, "strength": 1.0}]}"#).is_err(), "no condition");
+    assert!(Bootstrapper::from_json(r#"{"rules": [], "policy": "Vote"}"#).is_err());
+}
+
+#[test]
+fn test_singularity_loads_and_saves_knowledge() {
+    let dir = std::env::temp_dir();
+    let pack_path = dir.join("ds_knowledge_pack_test.json");
+    let saved_path = dir.join("ds_knowledge_saved_test.json");
+    std::fs::write(&pack_path, PACK).unwrap();
+
+    let mut ai = Singularity::new(8, vec![3]);
+    ai.bootstrapper.add_hamiltonian_rule(7, 0, 0.1);
+    assert_eq!(ai.load_knowledge(pack_path.to_str().unwrap()).unwrap(), 3);
+    assert_eq!(ai.bootstrapper.rules.len(), 4, "rules are added to the existing ones");
+    assert_eq!(ai.bootstrapper.policy, RuleConflictPolicy::HighestPriority);
+    assert_eq!(ai.simulate_rules(&[0], 5).best(0), Some(1));
+
+    ai.save_knowledge(saved_path.to_str().unwrap()).unwrap();
+    let mut other = Singularity::new(8, vec![3]);
+    assert_eq!(other.load_knowledge(saved_path.to_str().unwrap()).unwrap(), 4);
+    assert_eq!(other.bootstrapper, ai.bootstrapper);
+
+    let missing = dir.join("ds_knowledge_missing_test.json");
+    assert!(other.load_knowledge(missing.to_str().unwrap()).is_err());
+    std::fs::write(&pack_path, "{").unwrap();
+    assert_eq!(other.load_knowledge(pack_path.to_str().unwrap()).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(other.bootstrapper.rules.len(), 4, "a failed load leaves the rules untouched");
+
+    let _ = std::fs::remove_file(pack_path);
+    let _ = std::fs::remove_file(saved_path);
+}
@@ -0,0 +1,59 @@
+use dark_singularity::core::knowledge::{Condition, RuleConflictPolicy};
+use dark_singularity::core::singularity::{Experience, Singularity};
+
+#[test]
+fn test_rules_survive_save_and_load() {
+    let path = std::env::temp_dir().join("ds_knowledge_persistence_test.dsym");
+    let path = path.to_str().unwrap();
+
+    let mut ai = Singularity::new(8, vec![4]);
+    ai.bootstrapper.add_hamiltonian_rule(0, 1, 0.7);
+    ai.bootstrapper.add_penalty_rule(2, 3, 0.4);
+    ai.bootstrapper.add_prioritized_rule(
+        Condition::All(vec![Condition::Id(1), Condition::Not(Box::new(Condition::Id(2)))]),
+        2,
+        1.5,
+        7,
+    );
+    ai.bootstrapper.policy = RuleConflictPolicy::HighestPriority;
+    ai.observe_expert(5, &[0], 1.0);
+    assert_eq!(ai.bootstrapper.rules.len(), 4);
+    ai.save_to_file(path).unwrap();
+
+    let mut loaded = Singularity::new(8, vec![4]);
+    loaded.load_from_file(path).unwrap();
+    assert_eq!(loaded.bootstrapper, ai.bootstrapper);
+    assert_eq!(loaded.simulate_rules(&[1], 6), ai.simulate_rules(&[1], 6));
+
+    // 構築時に同じルールを登録してから読み込んでも重複しない
+    let mut preloaded = Singularity::new(8, vec![4]);
+    preloaded.bootstrapper.add_hamiltonian_rule(0, 1, 0.7);
+    preloaded.load_from_file(path).unwrap();
+    assert_eq!(preloaded.bootstrapper.rules.len(), 4);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_crystallized_rules_are_not_duplicated_on_load() {
+    let path = std::env::temp_dir().join("ds_knowledge_crystallized_test.dsym");
+    let path = path.to_str().unwrap();
+
+    let mut ai = Singularity::new(6, vec![3]);
+    ai.config.crystallize_threshold = 2;
+    for _ in 0..2 {
+        ai.history.push_back(Experience { state_idx: 4, actions: vec![1] });
+        ai.learn(2.0);
+    }
+    assert!(ai.crystallized.contains_key(&(4, 1)));
+    ai.save_to_file(path).unwrap();
+
+    let mut loaded = Singularity::new(6, vec![3]);
+    loaded.config.crystallize_threshold = 2;
+    loaded.load_from_file(path).unwrap();
+    assert!(loaded.crystallized.contains_key(&(4, 1)));
+    let count = loaded.bootstrapper.rules.iter().filter(|r| r.condition_id == 4 && r.target_action == 1).count();
+    assert_eq!(count, 1);
+
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,61 @@
+use dark_singularity::core::knowledge::Bootstrapper;
+use dark_singularity::core::singularity::Singularity;
+
+const ENEMY_NEAR: i32 = 3;
+
+fn wave_scores(ai: &Singularity) -> Vec<f32> {
+    (0..ai.action_size).map(|a| ai.mwso.get_action_score(a, ai.action_size, &[])).collect()
+}
+
+#[test]
+fn test_priors_bias_theta_and_gravity() {
+    let mut ai = Singularity::new(8, vec![4]);
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.add_always_rule(2, 1.0);
+    bootstrapper.add_always_rule(0, -1.0);
+    let before = wave_scores(&ai);
+
+    assert_eq!(bootstrapper.apply_to(&mut ai), 2);
+    let after = wave_scores(&ai);
+    assert!(after[2] >= before[2]);
+    assert!(after[0] < 0.0 && after[0] < before[0]);
+    assert_eq!(after[1], before[1], "actions without rules are untouched");
+    assert!(ai.mwso.gravity_for_action(2, 4) > ai.mwso.gravity_for_action(1, 4));
+    assert!(ai.bootstrapper.rules.is_empty(), "priors do not install the runtime field");
+
+    // 外場なしでも事前分布だけで避けるようになる
+    for step in 0..20 {
+        assert_ne!(ai.select_actions(step % 8)[0], 0);
+        ai.learn(0.0);
+    }
+}
+
+#[test]
+fn test_priors_follow_current_condition_levels() {
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.add_penalty_rule(ENEMY_NEAR, 1, 1.0);
+    bootstrapper.add_hamiltonian_rule(ENEMY_NEAR, 3, 0.0);
+
+    let mut idle = Singularity::new(8, vec![4]);
+    let theta = idle.export_theta();
+    assert_eq!(bootstrapper.apply_to(&mut idle), 0);
+    assert_eq!(idle.export_theta(), theta);
+
+    let mut alert = Singularity::new(8, vec![4]);
+    alert.set_condition_levels(&[(ENEMY_NEAR, 0.5)]);
+    assert_eq!(bootstrapper.apply_to(&mut alert), 1, "zero-strength fields are skipped");
+    assert!(wave_scores(&alert)[1] < wave_scores(&idle)[1]);
+}
+
+#[test]
+fn test_priors_reach_sharded_fields() {
+    let mut ai = Singularity::new(8, vec![20, 12]);
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.add_always_rule(25, -1.0);
+    let (shard, local) = ai.sharded_mwso.as_ref().unwrap().shard_for_action(25);
+    let per_shard = ai.sharded_mwso.as_ref().unwrap().actions_per_shard;
+    let before = ai.sharded_mwso.as_ref().unwrap().shards[shard].get_action_score(local, per_shard, &[]);
+    assert_eq!(bootstrapper.apply_to(&mut ai), 1);
+    let after = ai.sharded_mwso.as_ref().unwrap().shards[shard].get_action_score(local, per_shard, &[]);
+    assert!(after < before, "{before} -> {after}");
+}
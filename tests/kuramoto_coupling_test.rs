@@ -0,0 +1,71 @@
+use dark_singularity::core::config::{Coupling, Integrator};
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::wave::{Wave, from_wave, to_wave};
+
+/// 隣接ビンの位相差の cos の平均（1 で完全同期）
+fn neighbor_coherence(mwso: &MWSO) -> f32 {
+    let n = mwso.dim;
+    (0..n).map(|i| {
+        let j = (i + 1) % n;
+        let a = mwso.psi_imag[i].atan2(mwso.psi_real[i]);
+        let b = mwso.psi_imag[j].atan2(mwso.psi_real[j]);
+        from_wave((b - a).cos())
+    }).sum::<f32>() / n as f32
+}
+
+fn run(coupling: Coupling, integrator: Integrator) -> MWSO {
+    let mut mwso = MWSO::with_frequencies(&[1.0; 64]);
+    mwso.coupling = coupling;
+    mwso.integrator = integrator;
+    for (i, t) in mwso.theta.iter_mut().enumerate() { *t = if i < 64 { to_wave(2.0) } else { 0.0 }; }
+    for i in 0..64 {
+        let phase = ((i * 37) % 64) as f32 * 0.7;
+        mwso.psi_real[i] = to_wave(phase.cos()) / (64.0 as Wave).sqrt();
+        mwso.psi_imag[i] = to_wave(phase.sin()) / (64.0 as Wave).sqrt();
+    }
+    for _ in 0..300 { mwso.step_core(0.1, 0.0, 0.5, 0.5, &[]); }
+    mwso
+}
+
+#[test]
+fn test_kuramoto_coupling_synchronizes_neighbors() {
+    for integrator in [Integrator::Explicit, Integrator::Symplectic] {
+        let linear = run(Coupling::Linear, integrator);
+        let kuramoto = run(Coupling::Kuramoto, integrator);
+        assert!(kuramoto.psi_real.iter().chain(&kuramoto.psi_imag).all(|v| v.is_finite()));
+        let (lc, kc) = (neighbor_coherence(&linear), neighbor_coherence(&kuramoto));
+        assert!(kc > 0.9, "{:?}: kuramoto coherence {}", integrator, kc);
+        assert!(kc > lc, "{:?}: kuramoto {} vs linear {}", integrator, kc, lc);
+    }
+}
+
+#[test]
+fn test_negative_theta_pulls_towards_anti_phase() {
+    let mut mwso = MWSO::with_frequencies(&[1.0; 32]);
+    mwso.coupling = Coupling::Kuramoto;
+    for t in mwso.theta.iter_mut() { *t = to_wave(-2.0); }
+    for i in 0..32 {
+        let phase = i as f32 * 0.1;
+        mwso.psi_real[i] = to_wave(phase.cos()) / (32.0 as Wave).sqrt();
+        mwso.psi_imag[i] = to_wave(phase.sin()) / (32.0 as Wave).sqrt();
+    }
+    let before = neighbor_coherence(&mwso);
+    for _ in 0..300 { mwso.step_core(0.1, 0.0, 0.5, 0.5, &[]); }
+    assert!(neighbor_coherence(&mwso) < before - 0.2);
+}
+
+#[test]
+fn test_singularity_sets_coupling_per_instance() {
+    let mut a = Singularity::new(8, vec![20]);
+    let b = Singularity::new(8, vec![20]);
+    assert_eq!(Coupling::from_id(1), Some(Coupling::Kuramoto));
+    a.set_coupling(Coupling::Kuramoto);
+    assert_eq!(a.mwso.coupling, Coupling::Kuramoto);
+    assert!(a.sharded_mwso.as_ref().unwrap().shards.iter().all(|s| s.coupling == Coupling::Kuramoto));
+    assert_eq!(b.mwso.coupling, Coupling::Linear);
+    for i in 0..20 {
+        a.select_actions(i % 8);
+        a.learn(1.0);
+    }
+}
@@ -0,0 +1,46 @@
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_untouched_rows_are_not_rewritten() {
+    let mut ai = Singularity::new(64, vec![4]);
+    ai.set_penalty(10, 2, 2.0);
+    let stored = ai.penalty_matrix.clone();
+
+    for _ in 0..20 {
+        ai.select_actions(3);
+        ai.learn(-0.5);
+    }
+
+    // 触れていない行は書き換わらず、読み出し時に減衰が反映される
+    let row = 10 * ai.penalty_dim..11 * ai.penalty_dim;
+    assert_eq!(ai.penalty_matrix[row.clone()], stored[row]);
+    let expected = 2.0 * 0.995f32.powi(20);
+    assert!((ai.get_penalties(10)[2] - expected).abs() < 1e-4, "got {}", ai.get_penalties(10)[2]);
+}
+
+#[test]
+fn test_lazy_decay_matches_eager_decay() {
+    let mut ai = Singularity::new(8, vec![3]);
+    let mut eager = [0.0f32; 8];
+    ai.set_penalty(1, 0, 4.0);
+    eager[1] = 4.0;
+
+    for step in 0..30 {
+        // 一部の行だけを時々書き換え、それ以外は減衰のみ
+        if step % 7 == 0 {
+            ai.set_penalty(2, 0, 1.0);
+            eager[2] = 1.0;
+        }
+        ai.learn(0.0);
+        for p in &mut eager { *p *= 0.995; }
+    }
+
+    for (state, &expected) in eager.iter().enumerate() {
+        let got = ai.get_penalties(state)[0];
+        assert!((got - expected).abs() < 1e-4, "state {}: lazy={} eager={}", state, got, expected);
+    }
+
+    ai.settle_penalties();
+    let dim = ai.penalty_dim;
+    assert!((ai.penalty_matrix[dim] - eager[1]).abs() < 1e-4);
+}
@@ -0,0 +1,109 @@
+use dark_singularity::core::config::LearnQueuePolicy;
+use dark_singularity::core::learn_queue::LearnWorker;
+use dark_singularity::core::singularity::Singularity;
+
+fn fill(ai: &mut Singularity, n: usize) {
+    for i in 0..n {
+        ai.select_actions(i % 4);
+        ai.queue_learn(1.0);
+    }
+}
+
+#[test]
+fn test_queue_defers_learning() {
+    let mut ai = Singularity::new(4, vec![3]);
+    fill(&mut ai, 3);
+    assert_eq!(ai.learn_queue_depth(), 3);
+    assert!(ai.history.is_empty(), "Queued decisions are moved out of the live history");
+
+    // 処理中の意思決定は退避・復元される
+    ai.select_actions(2);
+    let live_actions = ai.last_actions.clone();
+    assert_eq!(ai.drain_learn_queue(2), 2);
+    assert_eq!(ai.learn_queue_depth(), 1);
+    assert_eq!(ai.history.len(), 1);
+    assert_eq!(ai.last_actions, live_actions);
+
+    assert_eq!(ai.drain_learn_queue(10), 1);
+    let stats = ai.learn_queue_stats();
+    assert_eq!(stats.processed, 3);
+    assert_eq!(stats.max_depth, 3);
+}
+
+#[test]
+fn test_backpressure_policies() {
+    let mut block = Singularity::new(4, vec![3]);
+    block.config.learn_queue_capacity = 2;
+    fill(&mut block, 5);
+    assert_eq!(block.learn_queue_depth(), 2);
+    assert_eq!(block.learn_queue_stats().blocked, 3);
+    assert_eq!(block.learn_queue_stats().processed, 3);
+
+    let mut drop = Singularity::new(4, vec![3]);
+    drop.config.learn_queue_capacity = 2;
+    drop.config.learn_queue_policy = LearnQueuePolicy::DropOldest;
+    fill(&mut drop, 5);
+    assert_eq!(drop.learn_queue_depth(), 2);
+    assert_eq!(drop.learn_queue_stats().dropped, 3);
+
+    let mut coalesce = Singularity::new(4, vec![3]);
+    coalesce.config.learn_queue_capacity = 2;
+    coalesce.config.learn_queue_policy = LearnQueuePolicy::Coalesce;
+    fill(&mut coalesce, 5);
+    assert_eq!(coalesce.learn_queue_depth(), 2);
+    assert_eq!(coalesce.learn_queue_stats().coalesced, 3);
+    // 合算した 4 回分の報酬は平均ではなく和になる
+    let merged = coalesce.learn_queue.pending.back().unwrap();
+    assert!((merged.reward - 4.0).abs() < 1e-6);
+    assert_eq!(merged.history.len(), 4);
+}
+
+#[test]
+fn test_worker_consumes_queue_in_background() {
+    let worker = LearnWorker::new(Singularity::new(4, vec![3]));
+    worker.start();
+    for i in 0..20 {
+        worker.lock().select_actions(i % 4);
+        worker.queue_learn(1.0);
+    }
+    worker.wait_idle();
+    let ai = worker.into_inner();
+    assert_eq!(ai.learn_queue_depth(), 0);
+    assert_eq!(ai.learn_queue_stats().processed, 20);
+    assert!(!ai.symmetry.stats.is_empty());
+}
+
+#[test]
+fn test_block_policy_waits_for_worker() {
+    let mut ai = Singularity::new(4, vec![3]);
+    ai.config.learn_queue_capacity = 1;
+    let worker = LearnWorker::new(ai);
+    worker.start();
+    for i in 0..30 {
+        worker.lock().select_actions(i % 4);
+        worker.queue_learn(0.5);
+        assert!(worker.lock().learn_queue_depth() <= 1);
+    }
+    worker.wait_idle();
+    // 待たされた呼び出しでも学習は失われず、すべてワーカーが処理する
+    let stats = worker.lock().learn_queue_stats();
+    assert_eq!(stats.processed, 30);
+    assert_eq!(stats.dropped, 0);
+    assert_eq!(stats.max_depth, 1);
+}
+
+#[test]
+fn test_stopped_worker_leaves_queue_for_caller() {
+    let worker = LearnWorker::new(Singularity::new(4, vec![3]));
+    worker.start();
+    worker.stop();
+    assert!(!worker.is_running());
+    for i in 0..3 {
+        worker.lock().select_actions(i);
+        worker.queue_learn(1.0);
+    }
+    assert_eq!(worker.lock().learn_queue_depth(), 3);
+    // 学習スレッドがなければ、その場で消化する
+    worker.wait_idle();
+    assert_eq!(worker.lock().learn_queue_stats().processed, 3);
+}
@@ -0,0 +1,81 @@
+use dark_singularity::core::memory_bank::BankRoutingPolicy;
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::wave::{Wave, to_wave};
+
+/// 位相パターン seed の波をセットする
+fn set_wave(mwso: &mut MWSO, seed: usize) {
+    for i in 0..mwso.dim {
+        let phase = ((i * 7919 + seed * 104729) % 997) as f32 * 0.37;
+        mwso.psi_real[i] = to_wave(phase.cos()) / (mwso.dim as Wave).sqrt();
+        mwso.psi_imag[i] = to_wave(phase.sin()) / (mwso.dim as Wave).sqrt();
+    }
+}
+
+#[test]
+fn test_best_bank_reports_where_episode_was_imprinted() {
+    let mut mwso = MWSO::new(256);
+    let other = mwso.add_memory_bank("other");
+    assert_eq!(mwso.find_memory_bank("other"), Some(other));
+
+    set_wave(&mut mwso, 1);
+    mwso.imprint_qcel(3, 1.0);
+    assert!(mwso.switch_memory_bank(other));
+    set_wave(&mut mwso, 2);
+    mwso.imprint_qcel(11, 1.0);
+
+    set_wave(&mut mwso, 1);
+    assert_eq!(mwso.best_memory_bank(3).0, 0);
+    set_wave(&mut mwso, 2);
+    let (bank, resonance) = mwso.best_memory_bank(11);
+    assert_eq!(bank, other);
+    assert!(resonance > 0.5, "Resonance with the imprinting bank was {}", resonance);
+    assert_eq!(mwso.memory_banks()[0].imprints, 1);
+    assert_eq!(mwso.memory_banks()[other].imprints, 1);
+}
+
+#[test]
+fn test_switching_banks_preserves_memories() {
+    let mut mwso = MWSO::new(128);
+    let second = mwso.add_memory_bank("second");
+    set_wave(&mut mwso, 5);
+    mwso.imprint_qcel(2, 1.0);
+    let first_memory = mwso.q_memory_re.clone();
+
+    mwso.switch_memory_bank(second);
+    assert!(mwso.q_memory_re.iter().all(|&v| v == 0.0), "A new bank starts empty");
+    mwso.imprint_qcel(2, -0.5);
+
+    mwso.switch_memory_bank(0);
+    assert_eq!(mwso.q_memory_re, first_memory);
+    assert!(!mwso.switch_memory_bank(5));
+}
+
+#[test]
+fn test_resonance_routing_separates_episodes() {
+    let mut mwso = MWSO::new(256);
+    mwso.add_memory_bank("b");
+    mwso.bank_routing = BankRoutingPolicy::Resonance;
+
+    // 既知のエピソードは同じバンクへ、新しいエピソードは空いているバンクへ
+    set_wave(&mut mwso, 1);
+    mwso.imprint_qcel(3, 1.0);
+    let first = mwso.active_memory_bank();
+    set_wave(&mut mwso, 1);
+    mwso.imprint_qcel(3, 1.0);
+    assert_eq!(mwso.active_memory_bank(), first);
+
+    set_wave(&mut mwso, 2);
+    mwso.imprint_qcel(11, 1.0);
+    assert_ne!(mwso.active_memory_bank(), first);
+}
+
+#[test]
+fn test_by_state_routing() {
+    let mut mwso = MWSO::new(64);
+    mwso.add_memory_bank("odd");
+    mwso.bank_routing = BankRoutingPolicy::ByState;
+    mwso.imprint_qcel(5, 1.0);
+    assert_eq!(mwso.active_memory_bank(), 1);
+    mwso.imprint_qcel(4, 1.0);
+    assert_eq!(mwso.active_memory_bank(), 0);
+}
@@ -0,0 +1,77 @@
+use dark_singularity::core::memory_bank::BankRoutingPolicy;
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::wave::from_wave;
+
+fn trained() -> Singularity {
+    let mut ai = Singularity::new(16, vec![4]);
+    for i in 0..30 {
+        ai.select_actions(i % 16);
+        ai.learn(if i % 3 == 0 { 1.5 } else { -0.5 });
+    }
+    ai
+}
+
+#[test]
+fn test_memory_wave_survives_reload() {
+    let mut ai = trained();
+    let episodic = ai.mwso.add_memory_bank("episodic");
+    ai.mwso.bank_routing = BankRoutingPolicy::ByState;
+    for i in 0..10 {
+        ai.select_actions(i);
+        ai.learn(1.0);
+    }
+    assert!(ai.mwso.q_memory_re.iter().any(|&v| v != 0.0));
+
+    let path = std::env::temp_dir().join("ds_memory_persistence_test.dsym");
+    ai.save_to_file(path.to_str().unwrap()).unwrap();
+    let mut loaded = Singularity::new(16, vec![4]);
+    loaded.load_from_file(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(loaded.mwso.active_memory_bank(), ai.mwso.active_memory_bank());
+    assert_eq!(loaded.mwso.bank_routing, BankRoutingPolicy::ByState);
+    assert_eq!(loaded.mwso.memory_banks().len(), 2);
+    assert_eq!(loaded.mwso.memory_banks()[episodic].label, "episodic");
+    for bank in 0..2 {
+        assert_eq!(loaded.mwso.memory_banks()[bank].imprints, ai.mwso.memory_banks()[bank].imprints);
+        assert_eq!(loaded.mwso.bank_memory(bank), ai.mwso.bank_memory(bank), "bank {} must round-trip bit-exactly", bank);
+    }
+}
+
+#[test]
+fn test_old_format_without_memory_section_loads_with_empty_memory() {
+    let ai = trained();
+    let path = std::env::temp_dir().join("ds_memory_persistence_v15.dsym");
+    ai.save_to_file(path.to_str().unwrap()).unwrap();
+
+    // v15 形式を再現する: 末尾の記憶波セクションと FIELDS の次元を落とし、バージョンを 15 にする
+    let mut bytes = std::fs::read(&path).unwrap();
+    let memory_at = bytes.windows(4).rposition(|w| w == b"QMEM").unwrap();
+    bytes.truncate(memory_at);
+    let fields_at = bytes.windows(4).position(|w| w == b"FLDS").unwrap();
+    let dim_at = fields_at + 4 + (ai.fatigue_map.len() + ai.action_momentum.len()) * 4;
+    bytes.drain(dim_at..dim_at + 4);
+    // NODES の各ノードから名前・活性化関数とシナプスの一覧を落とす
+    let nodes_at = bytes.windows(4).position(|w| w == b"NODE").unwrap() + 4;
+    let read_u32 = |bytes: &[u8], at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap()) as usize;
+    let mut at = nodes_at + 4;
+    for _ in 0..read_u32(&bytes, nodes_at) {
+        at += 8;
+        let name_len = read_u32(&bytes, at);
+        bytes.drain(at..at + 4 + name_len + 8);
+        let synapses = read_u32(&bytes, at);
+        bytes.drain(at..at + 4 + synapses * 8);
+    }
+    bytes[4..8].copy_from_slice(&15u32.to_le_bytes());
+    std::fs::write(&path, &bytes).unwrap();
+
+    let mut loaded = trained();
+    loaded.mwso.add_memory_bank("stale");
+    loaded.load_from_file(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    let as_f32 = |ai: &Singularity| -> Vec<f32> { ai.mwso.psi_real.iter().map(|&v| from_wave(v)).collect() };
+    assert_eq!(as_f32(&loaded), as_f32(&ai));
+    assert_eq!(loaded.mwso.memory_banks().len(), 1);
+    assert!(loaded.mwso.q_memory_re.iter().chain(&loaded.mwso.q_topo_re).all(|&v| v == 0.0));
+}
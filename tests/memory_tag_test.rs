@@ -0,0 +1,68 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::wave::{Wave, to_wave};
+
+fn set_wave(mwso: &mut MWSO, seed: usize) {
+    for i in 0..mwso.dim {
+        let phase = ((i * 7919 + seed * 104729) % 997) as f32 * 0.37;
+        mwso.psi_real[i] = to_wave(phase.cos()) / (mwso.dim as Wave).sqrt();
+        mwso.psi_imag[i] = to_wave(phase.sin()) / (mwso.dim as Wave).sqrt();
+    }
+}
+
+#[test]
+fn test_tag_resonances_report_matching_memory() {
+    let mut mwso = MWSO::new(512);
+    set_wave(&mut mwso, 1);
+    mwso.imprint_memory(3, 1.0, Some("won_rush_defense"));
+    set_wave(&mut mwso, 2);
+    mwso.imprint_memory(9, 1.0, Some("lost_flank"));
+    mwso.imprint_memory(5, 0.5, None);
+    assert_eq!(mwso.memory_tags().len(), 2);
+    assert_eq!(mwso.memory_tags()["won_rush_defense"].input_idx, 3);
+
+    set_wave(&mut mwso, 1);
+    let resonances = mwso.tag_resonances();
+    assert_eq!(resonances[0].0, "won_rush_defense");
+    assert!(resonances[0].1 > 0.3, "resonance {:?}", resonances);
+
+    set_wave(&mut mwso, 2);
+    assert_eq!(mwso.tag_resonances()[0].0, "lost_flank");
+
+    assert!(mwso.remove_memory_tag("lost_flank"));
+    assert!(!mwso.remove_memory_tag("lost_flank"));
+    assert_eq!(mwso.tag_resonances().len(), 1);
+}
+
+#[test]
+fn test_tags_follow_their_bank() {
+    let mut mwso = MWSO::new(256);
+    let other = mwso.add_memory_bank("other");
+    set_wave(&mut mwso, 4);
+    mwso.imprint_memory(2, 1.0, Some("opening"));
+    mwso.switch_memory_bank(other);
+    mwso.imprint_memory(2, 1.0, Some("opening"));
+    let tag = &mwso.memory_tags()["opening"];
+    assert_eq!((tag.bank, tag.imprints), (other, 2));
+
+    mwso.clear_memory_banks();
+    assert!(mwso.memory_tags().is_empty());
+}
+
+#[test]
+fn test_resonant_memories_after_decision() {
+    let mut ai = Singularity::new(16, vec![4]);
+    ai.select_actions(4);
+    ai.imprint_memory(4, 1.0, Some("seen_4"));
+    let memories = ai.resonant_memories(-1.0);
+    assert_eq!(memories.len(), 1);
+    assert_eq!(memories[0].0, "seen_4");
+    assert!(ai.resonant_memories(1.1).is_empty());
+
+    let mut sharded = Singularity::new(8, vec![20]);
+    sharded.select_actions(1);
+    sharded.imprint_memory(1, 1.0, Some("sharded"));
+    let memories = sharded.resonant_memories(-1.0);
+    assert_eq!(memories.len(), 1);
+    assert!(memories[0].1.is_finite());
+}
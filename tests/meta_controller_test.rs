@@ -0,0 +1,43 @@
+use dark_singularity::core::meta::{MetaController, MetaParams};
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_bandit_converges_to_best_arm() {
+    let mut meta = MetaController::with_default_arms();
+    meta.evaluation_window = 5;
+    let best = 2;
+
+    let mut pulls_of_best_late = 0;
+    for round in 0..200 {
+        let reward = if meta.current_arm == best { 1.0 } else { 0.2 };
+        for _ in 0..meta.evaluation_window {
+            meta.record(reward);
+        }
+        if round >= 100 && meta.current_arm == best { pulls_of_best_late += 1; }
+    }
+
+    assert_eq!(meta.best_arm(), best);
+    assert!(pulls_of_best_late > 60, "Best arm should dominate late rounds: {}", pulls_of_best_late);
+}
+
+#[test]
+fn test_singularity_applies_meta_params() {
+    let mut ai = Singularity::new(4, vec![4]);
+    assert_eq!(ai.meta_params, MetaParams::default());
+
+    let mut meta = MetaController::with_default_arms();
+    meta.evaluation_window = 2;
+    ai.enable_meta_controller(meta);
+
+    let mut seen = vec![ai.meta_params];
+    for step in 0..20 {
+        ai.select_actions(step % 4);
+        ai.learn(0.5);
+        if !seen.contains(&ai.meta_params) { seen.push(ai.meta_params); }
+    }
+    assert!(seen.len() > 1, "Controller should try other settings during a session");
+
+    ai.disable_meta_controller();
+    assert!(ai.meta_controller.is_none());
+    ai.select_actions(0);
+}
@@ -0,0 +1,100 @@
+use dark_singularity::core::action_layout::ActionLayout;
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::state_code::StateCoding;
+use dark_singularity::core::wave::{from_wave, to_wave};
+
+fn smooth_wave(dim: usize) -> MWSO {
+    let mut mwso = MWSO::new(dim);
+    for i in 0..dim {
+        let phase = i as f32 / dim as f32 * 2.0 * std::f32::consts::PI * 3.0;
+        mwso.psi_real[i] = to_wave(phase.cos());
+        mwso.psi_imag[i] = to_wave(phase.sin());
+    }
+    mwso
+}
+
+fn assert_lengths(mwso: &MWSO, dim: usize) {
+    assert_eq!(mwso.dim, dim);
+    for len in [mwso.psi_real.len(), mwso.psi_imag.len(), mwso.q_memory_re.len(), mwso.q_memory_im.len(),
+                mwso.q_topo_re.len(), mwso.q_topo_im.len(), mwso.gravity_field.len(), mwso.frequencies.len(),
+                mwso.energy_landscape.len(), mwso.input_signature_re.len(), mwso.scramble_phases.len()] {
+        assert_eq!(len, dim);
+    }
+    assert_eq!(mwso.theta.len(), dim * 2);
+    assert!(mwso.entanglements.iter().all(|&(f, t, _)| f < dim && t < dim && f != t));
+}
+
+#[test]
+fn test_resize_preserves_per_bin_fields() {
+    for new_dim in [2048, 512, 1500] {
+        let mut mwso = MWSO::new(1024);
+        for g in &mut mwso.gravity_field { *g = 0.4; }
+        mwso.resize(new_dim);
+        assert_lengths(&mwso, new_dim);
+        assert!(mwso.psi_real.iter().all(|&v| (from_wave(v) - 0.01).abs() < 1e-6));
+        assert!(mwso.gravity_field.iter().all(|&g| (g - 0.4).abs() < 1e-6));
+        mwso.step_core(0.1, 0.0, 0.0, 0.5, &[]);
+        assert!(mwso.psi_real.iter().all(|v| v.is_finite()));
+    }
+}
+
+#[test]
+fn test_resize_keeps_memory_recall_for_imprinted_state() {
+    let mut mwso = smooth_wave(1024);
+    for _ in 0..5 { mwso.imprint_qcel(5, 1.0); }
+    let before = mwso.bank_resonances(5)[0];
+    assert!(before > 0.5, "before {}", before);
+
+    for new_dim in [2048, 512] {
+        let mut resized = mwso.clone();
+        resized.resize(new_dim);
+        let target = smooth_wave(new_dim);
+        resized.psi_real = target.psi_real.clone();
+        resized.psi_imag = target.psi_imag.clone();
+        let kept = resized.bank_resonances(5)[0];
+        // 状態 7 の署名は状態 5 とほぼ逆位相なので、位相が保たれていれば負の共鳴になる
+        let opposite = resized.bank_resonances(7)[0];
+        assert!(kept > 0.8 * before, "dim {} kept {} before {}", new_dim, kept, before);
+        assert!(opposite < -0.5, "dim {} opposite {}", new_dim, opposite);
+    }
+}
+
+#[test]
+fn test_resize_remaps_wormholes_and_banks() {
+    let mut mwso = MWSO::new(1024);
+    mwso.entanglements.clear();
+    mwso.add_wormhole(100, 500, 0.3);
+    mwso.add_wormhole(101, 500, 0.7);
+    mwso.add_memory_bank("other");
+    mwso.switch_memory_bank(1);
+    mwso.imprint_qcel(3, 1.0);
+    mwso.switch_memory_bank(0);
+
+    let mut up = mwso.clone();
+    up.resize(2048);
+    assert_lengths(&up, 2048);
+    assert_eq!(up.entanglements.len(), 2);
+    assert!(up.entanglements.iter().any(|&(f, t, _)| f == 201 && t == 1001));
+    assert!(up.switch_memory_bank(1));
+    assert_eq!(up.q_memory_re.len(), 2048);
+    assert!(up.q_memory_re.iter().any(|&v| v != 0.0));
+
+    // 縮小で同じ組に潰れた結合は強い方が残る
+    let mut down = mwso.clone();
+    down.resize(256);
+    assert_lengths(&down, 256);
+    assert_eq!(down.entanglements, vec![(25, 125, 0.7)]);
+}
+
+#[test]
+fn test_resize_rescales_layout_and_codes() {
+    let mut mwso = MWSO::new(1024);
+    mwso.set_action_layout(ActionLayout::from_widths(&[512, 256, 256]));
+    mwso.set_state_coding(StateCoding::Orthogonal);
+    mwso.resize(2048);
+    assert_eq!(mwso.action_layout().unwrap().widths(), vec![1024, 512, 512]);
+    assert_eq!(mwso.state_coding(), StateCoding::Orthogonal);
+    assert!(mwso.state_code(2000).iter().all(|&(bin, _)| bin < 2048));
+    mwso.inject_state(2000, 1.0, &[]);
+    mwso.adapt(2000, 1.5, &[1], 0.5, 3);
+}
@@ -1,4 +1,5 @@
 use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::wave::{Wave, from_wave};
 
 #[test]
 fn test_mwso_influence() {
@@ -41,3 +42,49 @@ fn test_mwso_wave_evolution() {
     }
     assert!(changed, "Wave state should evolve after input");
 }
+
+#[test]
+fn test_step_core_handles_non_lane_multiple_dims() {
+    use dark_singularity::core::mwso::MWSO;
+
+    // 1027 = 8 * 128 + 3: 端数の3要素はスカラー経路で処理される
+    let mut mwso = MWSO::new(1027);
+    mwso.inject_state(1025, 1.0, &[]);
+    let tail_before: Vec<Wave> = mwso.psi_imag[1024..].to_vec();
+    let penalty = vec![0.2; 1027];
+    for _ in 0..5 {
+        mwso.step_core(0.1, 0.0, 0.5, 0.5, &penalty);
+    }
+
+    assert!(mwso.psi_real.iter().chain(&mwso.psi_imag).all(|v| v.is_finite()));
+    assert_ne!(mwso.psi_imag[1024..], tail_before[..], "Tail bins must evolve too");
+    let norm = from_wave(mwso.psi_real.iter().zip(&mwso.psi_imag).map(|(re, im)| re * re + im * im).sum::<Wave>().sqrt());
+    assert!((norm - 1.25).abs() < 1e-3, "step_core should normalize to the target norm, got {}", norm);
+}
+
+#[test]
+fn test_high_dim_step_core_is_deterministic() {
+    use dark_singularity::core::mwso::MWSO;
+
+    // 8195 = 2048 * 4 + 3: parallel 機能が有効なら並列経路（最後のブロックは端数）を通る
+    let run = || {
+        let mut mwso = MWSO::new(8195);
+        mwso.inject_state(8193, 1.0, &[]);
+        let penalty = vec![0.1; 8195];
+        for _ in 0..3 {
+            mwso.step_core(0.1, 0.0, 0.5, 0.5, &penalty);
+        }
+        let scores = mwso.get_action_scores(0, 7, 0.0, &penalty);
+        (mwso, scores)
+    };
+    let (a, scores_a) = run();
+    let (b, scores_b) = run();
+
+    assert!(a.psi_real.iter().chain(&a.psi_imag).all(|v| v.is_finite()));
+    assert_eq!(a.psi_real, b.psi_real, "High-dim evolution must not depend on scheduling");
+    assert_eq!(a.psi_imag, b.psi_imag);
+    assert_eq!(scores_a, scores_b);
+    assert_eq!(scores_a.len(), 7);
+    let norm = from_wave(a.psi_real.iter().zip(&a.psi_imag).map(|(re, im)| re * re + im * im).sum::<Wave>().sqrt());
+    assert!((norm - 1.25).abs() < 1e-3, "step_core should normalize to the target norm, got {}", norm);
+}
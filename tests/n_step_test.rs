@@ -0,0 +1,50 @@
+use dark_singularity::core::singularity::{Experience, Singularity};
+
+fn play_episode(ai: &mut Singularity, states: &[usize], reward: f32) {
+    for &s in states {
+        ai.history.push_back(Experience { state_idx: s, actions: vec![1] });
+    }
+    ai.learn(reward);
+}
+
+#[test]
+fn test_monte_carlo_credits_whole_history_by_default() {
+    let mut ai = Singularity::new(32, vec![3]);
+    assert_eq!(ai.config.n_step, None);
+    play_episode(&mut ai, &[1, 2, 3, 4, 5, 6], 1.0);
+
+    // 最も古い状態にも割引された実報酬が届く（0.9^5）
+    let (sum, count) = ai.symmetry.stats[&1][1];
+    assert_eq!(count, 1);
+    assert!((sum - 0.9f32.powi(5)).abs() < 1e-4, "sum={}", sum);
+}
+
+#[test]
+fn test_n_step_bootstraps_older_experiences() {
+    let mut ai = Singularity::new(32, vec![3]);
+    ai.config.n_step = Some(2);
+    play_episode(&mut ai, &[1, 2, 3, 4, 5, 6], 1.0);
+
+    // 直近 2 ステップは実報酬
+    assert!((ai.symmetry.stats[&6][1].0 - 1.0).abs() < 1e-4);
+    assert!((ai.symmetry.stats[&5][1].0 - 0.9).abs() < 1e-4);
+
+    // それより古い経験は γ^n * V（|V| <= 1）で、実報酬の連鎖からは切り離される
+    for s in 1..=4 {
+        let credit = ai.symmetry.stats[&s][1].0;
+        assert!(credit.abs() <= 0.81 + 1e-4, "state {} credit {}", s, credit);
+    }
+}
+
+#[test]
+fn test_n_step_bootstrap_has_no_side_effects_on_history() {
+    let mut ai = Singularity::new(32, vec![3]);
+    ai.config.n_step = Some(1);
+    for _ in 0..5 {
+        ai.select_actions(3);
+        ai.select_actions(7);
+        ai.select_actions(9);
+        ai.learn(0.5);
+        assert!(ai.history.is_empty());
+    }
+}
@@ -0,0 +1,54 @@
+use dark_singularity::core::node::{NodeSpec, NODE_AGGRESSION, NODE_FEAR, NODE_REFLEX, NODE_TACTICAL};
+use dark_singularity::core::singularity::Singularity;
+
+fn synapse_weight(ai: &Singularity, from: &str, to: &str) -> Option<f32> {
+    let to = ai.node_index(to)?;
+    ai.nodes[ai.node_index(from)?].synapses.iter().find(|s| s.target_id == to).map(|s| s.weight)
+}
+
+#[test]
+fn test_default_nodes_are_named() {
+    let ai = Singularity::new(8, vec![4]);
+    let names: Vec<&str> = ai.nodes.iter().map(|n| n.name.as_str()).collect();
+    assert_eq!(names, vec![NODE_AGGRESSION, NODE_FEAR, NODE_TACTICAL, NODE_REFLEX]);
+    assert_eq!(ai.node_index(NODE_FEAR), Some(1));
+    assert_eq!(ai.nodes[1].base_decay, 0.4);
+    assert_eq!(ai.node_index("curiosity"), None);
+    assert_eq!(ai.node_state("curiosity"), 0.0);
+}
+
+#[test]
+fn test_custom_layout_is_resolved_by_name() {
+    let specs = vec![
+        NodeSpec::new("curiosity", 0.2),
+        NodeSpec::new(NODE_REFLEX, 0.3),
+        NodeSpec::new(NODE_FEAR, 0.4),
+        NodeSpec::new(NODE_TACTICAL, 0.3),
+        NodeSpec::new(NODE_AGGRESSION, 0.5),
+    ];
+    let mut ai = Singularity::with_nodes(8, vec![4], &specs);
+    assert_eq!(ai.nodes.len(), 5);
+    assert_eq!(ai.node_index(NODE_AGGRESSION), Some(4));
+
+    ai.set_neuron_state(ai.node_index(NODE_FEAR).unwrap(), 0.5);
+    assert_eq!(ai.node_state(NODE_FEAR), 0.5);
+    ai.reshape_topology();
+    assert_eq!(synapse_weight(&ai, NODE_FEAR, NODE_REFLEX), Some(1.0));
+    assert!(synapse_weight(&ai, NODE_TACTICAL, NODE_REFLEX).is_some());
+    assert!(ai.nodes[0].synapses.is_empty(), "unnamed roles are left alone");
+}
+
+#[test]
+fn test_missing_roles_are_skipped() {
+    let mut ai = Singularity::with_nodes(8, vec![4], &[NodeSpec::new(NODE_FEAR, 0.4), NodeSpec::new("morale", 0.1)]);
+    ai.set_neuron_state(0, 1.0);
+    // reflex が無いので結合は張られない
+    ai.reshape_topology();
+    assert!(ai.nodes.iter().all(|n| n.synapses.is_empty()));
+
+    for step in 0..20 {
+        ai.select_actions(step % 8);
+        ai.learn(if step % 2 == 0 { 1.0 } else { -1.0 });
+    }
+    assert_eq!(ai.node_state(NODE_AGGRESSION), 0.0);
+}
@@ -0,0 +1,89 @@
+use dark_singularity::core::node::{Activation, Synapse, NODE_AGGRESSION, NODE_FEAR, NODE_REFLEX, NODE_TACTICAL};
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_add_node_introduces_a_new_drive() {
+    let mut ai = Singularity::new(8, vec![4]);
+    assert_eq!(ai.add_node("greed", 0.2), Some(4));
+    assert_eq!(ai.add_node("greed", 0.5), None, "names stay unique");
+    assert_eq!(ai.node_index("greed"), Some(4));
+    assert_eq!(ai.nodes[4].base_decay, 0.2);
+
+    ai.update_all_nodes(&[0.0, 0.0, 0.0, 0.0, 1.0], 0.0);
+    assert!(ai.node_state("greed") > 0.0);
+    for step in 0..10 {
+        ai.select_actions(step % 8);
+        ai.learn(1.0);
+    }
+    assert_eq!(ai.nodes.len(), 5);
+}
+
+#[test]
+fn test_remove_node_reindexes_synapses() {
+    let mut ai = Singularity::new(8, vec![4]);
+    ai.set_neuron_state(ai.node_index(NODE_AGGRESSION).unwrap(), 0.6);
+    ai.set_neuron_state(ai.node_index(NODE_FEAR).unwrap(), 0.5);
+    ai.reshape_topology();
+    let fear_weight = ai.nodes[ai.node_index(NODE_FEAR).unwrap()].synapses[0].weight;
+
+    // tactical (2) を取り除くと reflex は 3 -> 2 に詰まる
+    assert!(ai.remove_node(ai.node_index(NODE_TACTICAL).unwrap()));
+    assert!(!ai.remove_node(10));
+    let reflex = ai.node_index(NODE_REFLEX).unwrap();
+    assert_eq!(reflex, 2);
+    let fear = &ai.nodes[ai.node_index(NODE_FEAR).unwrap()];
+    assert_eq!(fear.synapses.len(), 1);
+    assert_eq!((fear.synapses[0].target_id, fear.synapses[0].weight), (reflex, fear_weight));
+
+    // reflex を取り除くと、そこから入力を受けていたシナプスも消える
+    assert!(ai.remove_node(reflex));
+    assert!(ai.nodes.iter().all(|n| n.synapses.is_empty()));
+    ai.reshape_topology();
+    assert!(ai.nodes.iter().all(|n| n.synapses.iter().all(|s| s.target_id < ai.nodes.len())));
+}
+
+#[test]
+fn test_added_nodes_survive_reload() {
+    let path = std::env::temp_dir().join(format!("ds_neurogenesis_reload_{}.dsym", std::process::id()));
+    let path = path.to_str().unwrap();
+    let mut ai = Singularity::new(8, vec![4]);
+    let greed = ai.add_node("greed", 0.2).unwrap();
+    ai.set_node_activation(greed, Activation::ReluCap(0.6));
+    ai.set_neuron_state(greed, 0.4);
+    ai.nodes[greed].synapses.push(Synapse { target_id: 0, weight: 0.7 });
+    ai.save_to_file(path).unwrap();
+
+    // 読み込み先に無いノードは名前・減衰率・活性化関数ごと作り直される
+    let mut loaded = Singularity::new(8, vec![4]);
+    loaded.load_from_file(path).unwrap();
+    let _ = std::fs::remove_file(path);
+    assert_eq!(loaded.nodes.len(), 5);
+    assert_eq!(loaded.node_index("greed"), Some(greed));
+    let node = &loaded.nodes[greed];
+    assert_eq!((node.base_decay, node.state, node.activation), (0.2, 0.4, Activation::ReluCap(0.6)));
+    assert_eq!(node.synapses.len(), 1);
+    assert!(loaded.export_topology_dot().contains("greed"));
+}
+
+#[test]
+fn test_reload_drops_nodes_and_synapses_missing_from_file() {
+    let path = std::env::temp_dir().join(format!("ds_neurogenesis_trim_{}.dsym", std::process::id()));
+    let path = path.to_str().unwrap();
+    let mut ai = Singularity::new(8, vec![4]);
+    assert!(ai.remove_node(ai.node_index(NODE_TACTICAL).unwrap()));
+    ai.save_to_file(path).unwrap();
+
+    let mut loaded = Singularity::new(8, vec![4]);
+    loaded.add_node("greed", 0.2);
+    loaded.set_neuron_state(ai.node_index(NODE_FEAR).unwrap(), 1.0);
+    loaded.reshape_topology();
+    loaded.load_from_file(path).unwrap();
+    let _ = std::fs::remove_file(path);
+
+    let names: Vec<&str> = loaded.nodes.iter().map(|n| n.name.as_str()).collect();
+    let saved: Vec<&str> = ai.nodes.iter().map(|n| n.name.as_str()).collect();
+    assert_eq!(names, saved);
+    let count = loaded.nodes.len();
+    assert!(loaded.nodes.iter().all(|n| n.synapses.iter().all(|s| s.target_id < count)));
+    assert!(!loaded.export_topology_dot().contains(&format!("n{}", count)));
+}
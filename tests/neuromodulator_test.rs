@@ -0,0 +1,61 @@
+use dark_singularity::core::neuromod::Neuromodulators;
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_baseline_is_neutral() {
+    let n = Neuromodulators::new();
+    assert_eq!(n.learning_rate_scale(), 1.0);
+    assert_eq!(n.fatigue_scale(), 1.0);
+    assert_eq!(n.exploration_scale(), 1.0);
+}
+
+#[test]
+fn test_prediction_error_drives_dopamine() {
+    let mut n = Neuromodulators::new();
+    assert_eq!(n.update(1.0, 0.5), 1.0);
+    assert!(n.dopamine > 0.0);
+    assert!(n.exploration_scale() < 1.0, "better than expected: exploit");
+    assert!(n.learning_rate_scale() > 1.0);
+
+    // 同じ報酬が続けば予想どおりになり、ドーパミンは平常へ戻る
+    for _ in 0..200 { n.update(1.0, 0.5); }
+    assert!((n.expected_reward - 1.0).abs() < 1e-3);
+    assert!(n.dopamine.abs() < 1e-3);
+
+    n.update(-1.0, 0.5);
+    assert!(n.dopamine < 0.0);
+    assert!(n.exploration_scale() > 1.0, "worse than expected: explore");
+}
+
+#[test]
+fn test_stress_depletes_serotonin() {
+    let mut n = Neuromodulators::new();
+    for _ in 0..100 { n.update(0.0, 1.0); }
+    assert!(n.serotonin < 0.01);
+    assert!(n.fatigue_scale() > 1.4);
+    for _ in 0..100 { n.update(0.0, 0.0); }
+    assert!(n.serotonin > 0.99);
+    assert!(n.fatigue_scale() < 0.6);
+}
+
+#[test]
+fn test_modulators_shape_learning() {
+    let run = |enable: bool| {
+        let mut ai = Singularity::new(8, vec![4]);
+        if enable { ai.enable_neuromodulators(); }
+        for _ in 0..10 {
+            ai.select_actions(3);
+            ai.learn(-1.0);
+        }
+        ai
+    };
+    let plain = run(false);
+    let modulated = run(true);
+    assert!(plain.neuromodulators.is_none());
+    let n = modulated.neuromodulators.unwrap();
+    assert!(n.dopamine < 0.0 && n.expected_reward < 0.0);
+    assert!(n.serotonin != 0.5);
+    // 学習率は学習の間だけ変わり、元に戻る
+    assert_eq!(modulated.mwso.lr_scale, plain.mwso.lr_scale);
+    assert_ne!(modulated.fatigue_map, plain.fatigue_map);
+}
@@ -0,0 +1,53 @@
+use dark_singularity::core::node::{Activation, Node, NodeSpec, NODE_AGGRESSION, NODE_FEAR};
+use dark_singularity::core::singularity::Singularity;
+
+fn settle(activation: Activation, input: f32) -> f32 {
+    let mut node = Node::new(1.0);
+    node.activation = activation;
+    node.update(input, 0.0, 0.0, &[]);
+    node.state
+}
+
+#[test]
+fn test_response_curves() {
+    assert_eq!(Activation::Linear.apply(1.5), 1.5);
+    assert!((Activation::Sigmoid.apply(0.5) - 0.5).abs() < 1e-6);
+    assert!(Activation::Sigmoid.apply(0.1) < 0.05, "weak input is ignored");
+    assert!(Activation::Sigmoid.apply(0.9) > 0.95);
+    assert!((Activation::Tanh.apply(1.0) - 1.0).abs() < 1e-6);
+    assert!(Activation::Tanh.apply(0.3) > 0.3, "weak input is amplified");
+    assert_eq!(Activation::Tanh.apply(-1.0), 0.0);
+    assert_eq!(Activation::ReluCap(0.6).apply(0.9), 0.6);
+    assert_eq!(Activation::ReluCap(0.6).apply(-0.2), 0.0);
+
+    assert_eq!(Activation::from_id(3, 2.0), Some(Activation::ReluCap(1.0)));
+    assert_eq!(Activation::from_id(4, 0.0), None);
+}
+
+#[test]
+fn test_linear_keeps_the_original_update() {
+    // 線形では従来どおり範囲外の入力も混ぜてから [0, 1] に収める
+    let mut node = Node::new(0.5);
+    node.update(1.5, 0.0, 0.0, &[]);
+    assert!((node.state - 0.75).abs() < 1e-6);
+    assert_eq!(settle(Activation::Linear, 0.4), 0.4);
+    assert!(settle(Activation::Sigmoid, 0.4) < 0.4);
+    assert_eq!(settle(Activation::ReluCap(0.2), 0.4), 0.2);
+}
+
+#[test]
+fn test_activation_is_configured_per_node() {
+    let specs = vec![
+        NodeSpec::new(NODE_AGGRESSION, 1.0).with_activation(Activation::ReluCap(0.5)),
+        NodeSpec::new(NODE_FEAR, 1.0).with_activation(Activation::Sigmoid),
+    ];
+    let mut ai = Singularity::with_nodes(8, vec![4], &specs);
+    ai.update_all_nodes(&[1.0, 0.2], 0.0);
+    assert_eq!(ai.node_state(NODE_AGGRESSION), 0.5);
+    assert!(ai.node_state(NODE_FEAR) < 0.1);
+
+    assert!(ai.set_node_activation(1, Activation::Linear));
+    assert!(!ai.set_node_activation(2, Activation::Linear));
+    assert_eq!(ai.nodes[1].activation, Activation::Linear);
+    assert_eq!(NodeSpec::new("greed", 0.2).activation, Activation::Linear);
+}
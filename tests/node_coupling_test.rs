@@ -0,0 +1,52 @@
+use dark_singularity::core::node::{NODE_AGGRESSION, NODE_FEAR};
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_coupling_validation() {
+    let mut ai = Singularity::new(8, vec![4, 4]);
+    assert!(ai.add_node_coupling(NODE_FEAR, 0..2, 1.0));
+    assert!(!ai.add_node_coupling("greed", 0..2, 1.0), "unknown node");
+    assert!(!ai.add_node_coupling(NODE_FEAR, 2..2, 1.0));
+    assert!(!ai.add_node_coupling(NODE_FEAR, 6..9, 1.0), "beyond the action space");
+    assert!(!ai.add_node_coupling(NODE_FEAR, 0..2, -1.0));
+    assert_eq!(ai.node_couplings.len(), 1);
+}
+
+#[test]
+fn test_fear_damps_coupled_bins_only() {
+    let mut ai = Singularity::new(8, vec![4, 4]);
+    // 攻撃的な行動は両カテゴリーの先頭 2 つ
+    assert!(ai.add_node_coupling(NODE_FEAR, 0..2, 2.0));
+    assert!(ai.add_node_coupling(NODE_FEAR, 4..6, 2.0));
+    let calm = ai.live_penalty_field();
+    assert!(calm.iter().all(|&p| p == 0.0), "a silent node exerts no field");
+
+    ai.set_neuron_state(ai.node_index(NODE_FEAR).unwrap(), 0.5);
+    let afraid = ai.live_penalty_field();
+    let per_action = afraid.len() / 8;
+    for action in 0..8 {
+        let bins = &afraid[action * per_action..(action + 1) * per_action];
+        let expected = if action % 4 < 2 { 1.0 } else { 0.0 };
+        assert!(bins.iter().all(|&p| p == expected), "action {action}");
+    }
+}
+
+#[test]
+fn test_coupling_steers_decisions_through_the_wave() {
+    let mut ai = Singularity::new(8, vec![4]);
+    let fear = ai.node_index(NODE_FEAR).unwrap();
+    assert!(ai.add_node_coupling(NODE_FEAR, 0..2, 50.0));
+    ai.set_neuron_state(fear, 1.0);
+    for step in 0..20 {
+        let action = ai.select_actions(step % 8)[0];
+        assert!(action >= 2, "step {step}: damped action {action} was chosen");
+        ai.set_neuron_state(fear, 1.0);
+    }
+    let breakdown = ai.evaluate_actions(3);
+    assert!(breakdown[0] < breakdown[2] && breakdown[1] < breakdown[3]);
+
+    // 結合していないノードは場に影響しない
+    ai.node_couplings.clear();
+    ai.set_neuron_state(ai.node_index(NODE_AGGRESSION).unwrap(), 1.0);
+    assert!(ai.live_penalty_field().iter().all(|&p| p == 0.0));
+}
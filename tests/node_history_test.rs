@@ -0,0 +1,62 @@
+use dark_singularity::core::node::{Node, NODE_AGGRESSION, NODE_FEAR};
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_history_is_a_bounded_ring() {
+    let mut node = Node::new(0.5);
+    assert_eq!(node.history_stats(), None);
+    for i in 0..10 {
+        node.state = i as f32 / 10.0;
+        node.record_history(4);
+    }
+    let history: Vec<f32> = node.history.iter().copied().collect();
+    assert_eq!(history, vec![0.6, 0.7, 0.8, 0.9]);
+    node.record_history(0);
+    assert_eq!(node.history.len(), 4, "capacity 0 records nothing");
+}
+
+#[test]
+fn test_stats_capture_mean_variance_and_trend() {
+    let mut rising = Node::new(0.5);
+    for v in [0.1, 0.2, 0.3, 0.4] {
+        rising.state = v;
+        rising.record_history(8);
+    }
+    let stats = rising.history_stats().unwrap();
+    assert!((stats.mean - 0.25).abs() < 1e-6);
+    assert!((stats.variance - 0.0125).abs() < 1e-6);
+    assert!((stats.trend - 0.1).abs() < 1e-6);
+
+    let mut flat = Node::new(0.5);
+    flat.state = 0.7;
+    flat.record_history(8);
+    assert_eq!(flat.history_stats().unwrap().trend, 0.0);
+}
+
+#[test]
+fn test_oscillation_shows_up_as_variance() {
+    let mut ai = Singularity::new(8, vec![4]);
+    let aggression = ai.node_index(NODE_AGGRESSION).unwrap();
+    let fear = ai.node_index(NODE_FEAR).unwrap();
+    for step in 0..100 {
+        let mut inputs = [0.0; 4];
+        inputs[aggression] = 0.6;
+        inputs[fear] = if step % 2 == 0 { 1.0 } else { 0.0 };
+        ai.update_all_nodes(&inputs, 0.0);
+    }
+    assert_eq!(ai.node_history(fear).len(), ai.config.node_history_len);
+    assert_eq!(*ai.node_history(fear).last().unwrap(), ai.nodes[fear].state);
+    let steady = ai.node_history_stats(aggression).unwrap();
+    let swinging = ai.node_history_stats(fear).unwrap();
+    assert!(swinging.variance > steady.variance * 10.0, "{swinging:?} vs {steady:?}");
+    assert!(steady.trend.abs() < 1e-3);
+
+    // 学習による更新も記録される
+    let before = ai.node_history(aggression);
+    ai.config.node_history_len = 128;
+    ai.select_actions(1);
+    ai.learn(1.0);
+    assert_eq!(ai.node_history(aggression).len(), before.len() + 1);
+    assert!(ai.node_history(9).is_empty());
+    assert_eq!(ai.node_history_stats(9), None);
+}
@@ -0,0 +1,32 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::wave::from_wave;
+
+fn norm(re: &[f64], im: &[f64]) -> f64 {
+    re.iter().zip(im).map(|(r, i)| r * r + i * i).sum::<f64>().sqrt()
+}
+
+fn wave_norm(mwso: &MWSO) -> f32 {
+    mwso.psi_real.iter().zip(&mwso.psi_imag).map(|(&r, &i)| from_wave(r * r + i * i)).sum::<f32>().sqrt()
+}
+
+#[test]
+fn test_step_core_normalizes_gated_wave_to_temperature_target() {
+    let mut mwso = MWSO::new(512);
+    mwso.inject_state(7, 2.0, &[]);
+    for (temp, target) in [(0.2, 1.1), (1.0, 1.5), (4.0, 2.5)] {
+        mwso.step_core(0.1, 0.0, 0.5, temp, &[]);
+        assert!((wave_norm(&mwso) - target).abs() < 1e-3, "temp {} norm {}", temp, wave_norm(&mwso));
+    }
+}
+
+#[test]
+fn test_imprint_keeps_memory_and_topology_norms() {
+    let mut mwso = MWSO::new(256);
+    for i in 0..5 {
+        mwso.inject_state(i, 1.0, &[]);
+        mwso.step_core(0.1, 0.0, 0.5, 0.5, &[]);
+        mwso.imprint_qcel(i, if i % 2 == 0 { 1.0 } else { -0.5 });
+        assert!((norm(&mwso.q_memory_re, &mwso.q_memory_im) - 5.0).abs() < 1e-9);
+        assert!((norm(&mwso.q_topo_re, &mwso.q_topo_im) - 3.0).abs() < 1e-9);
+    }
+}
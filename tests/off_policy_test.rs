@@ -0,0 +1,128 @@
+use dark_singularity::core::singularity::{Experience, Singularity, TrajectoryStep};
+use dark_singularity::core::wave::from_wave;
+
+fn step(state_idx: usize, action: usize, importance: f32) -> TrajectoryStep {
+    TrajectoryStep { state_idx, actions: vec![action], importance }
+}
+
+/// 行動1の帯域の波動振幅
+fn action_band_after(importance: f32) -> Vec<f32> {
+    let mut ai = Singularity::new(32, vec![3]);
+    ai.learn_trajectory(&[step(4, 1, importance)], 1.0);
+    let bins = ai.mwso.dim / ai.action_size;
+    (bins..2 * bins).map(|i| from_wave(ai.mwso.psi_real[i].hypot(ai.mwso.psi_imag[i]))).collect()
+}
+
+/// 重要度 0（波動場への適応なし）の場合との差
+fn adaptation(importance: f32) -> f32 {
+    let baseline = action_band_after(0.0);
+    action_band_after(importance).iter().zip(&baseline).map(|(a, b)| (a - b).abs()).sum()
+}
+
+#[test]
+fn test_importance_scales_adaptation() {
+    assert_eq!(adaptation(0.0), 0.0);
+    let half = adaptation(0.5);
+    let full = adaptation(1.0);
+    assert!(half > 0.0);
+    assert!(half < full, "half={} full={}", half, full);
+    // 上限 (importance_clip = 1.0) を超える重みは打ち切られる
+    assert_eq!(adaptation(5.0), full);
+}
+
+#[test]
+fn test_replay_preserves_live_history_and_lr() {
+    let mut ai = Singularity::new(32, vec![3]);
+    ai.history.push_back(Experience { state_idx: 9, actions: vec![0] });
+    ai.learn_trajectory(&[step(4, 1, 0.3), step(5, 2, 0.3)], 1.0);
+
+    assert_eq!(ai.history.len(), 1);
+    assert_eq!(ai.history[0].state_idx, 9);
+    assert!(ai.symmetry.stats.contains_key(&4));
+    assert!(!ai.symmetry.stats.contains_key(&9));
+    assert_eq!(ai.scout_mwso.lr_scale, 1.0);
+}
+
+/// 負の報酬で学習したときの (ペナルティの増分, 対称性統計の報酬合計)
+fn failure_effect(importance: f32) -> (f32, f32) {
+    let mut ai = Singularity::new(32, vec![3]);
+    ai.learn_trajectory(&[step(4, 1, importance)], -1.0);
+    let penalty: f32 = ai.penalty_matrix.iter().sum();
+    let recorded = ai.symmetry.stats.get(&4).map_or(0.0, |s| s[1].0);
+    (penalty, recorded)
+}
+
+#[test]
+fn test_importance_is_applied_once_and_linearly() {
+    let (half_penalty, half_reward) = failure_effect(0.5);
+    let (full_penalty, full_reward) = failure_effect(1.0);
+    assert!(full_penalty > 0.0);
+    assert!((half_penalty * 2.0 - full_penalty).abs() < 1e-4, "half={} full={}", half_penalty, full_penalty);
+    assert!((half_reward * 2.0 - full_reward).abs() < 1e-6, "half={} full={}", half_reward, full_reward);
+}
+
+#[test]
+fn test_zero_importance_changes_nothing() {
+    // 重み 0 のステップを足しても、足さない場合と同じ学習結果になる
+    let mut with_zero = Singularity::new(32, vec![3]);
+    with_zero.learn_trajectory(&[step(4, 1, 0.0), step(7, 2, 1.0)], -1.0);
+    let mut without = Singularity::new(32, vec![3]);
+    without.learn_trajectory(&[step(7, 2, 1.0)], -1.0);
+
+    assert_eq!(with_zero.mwso.psi_real, without.mwso.psi_real);
+    assert_eq!(with_zero.mwso.theta, without.mwso.theta);
+    assert_eq!(with_zero.penalty_matrix, without.penalty_matrix);
+    assert_eq!(with_zero.fatigue_map, without.fatigue_map);
+    assert!(!with_zero.symmetry.stats.contains_key(&4));
+    assert!(!with_zero.learned_rules.iter().any(|r| r.state == 4));
+}
+
+#[test]
+fn test_malformed_steps_are_skipped() {
+    let mut ai = Singularity::new(32, vec![3]);
+    let bad = [
+        TrajectoryStep { state_idx: 4, actions: vec![], importance: 1.0 },
+        step(4, 7, 1.0),
+        step(99, 1, 1.0),
+    ];
+    ai.learn_trajectory(&bad, -1.0);
+    assert!(ai.symmetry.stats.is_empty());
+
+    // 正しいステップだけが学習される
+    let mut mixed = Singularity::new(32, vec![3]);
+    mixed.learn_trajectory(&[bad[0].clone(), step(5, 2, 1.0), bad[1].clone()], -1.0);
+    let mut clean = Singularity::new(32, vec![3]);
+    clean.learn_trajectory(&[step(5, 2, 1.0)], -1.0);
+    assert_eq!(mixed.penalty_matrix, clean.penalty_matrix);
+    assert!(mixed.symmetry.stats.contains_key(&5));
+}
+
+#[test]
+fn test_replay_leaves_live_control_state_alone() {
+    let mut ai = Singularity::new(32, vec![3]);
+    ai.last_actions = vec![0];
+    ai.action_momentum = vec![0.5, 0.5, 0.5];
+    let temperature = ai.system_temperature;
+    let (trust, frustration, morale, adrenaline) = (ai.velocity_trust, ai.frustration, ai.morale, ai.adrenaline);
+
+    ai.learn_trajectory(&[step(4, 1, 1.0), step(5, 2, 1.0)], 1.0);
+    ai.learn_trajectory(&[step(6, 0, 1.0)], -1.0);
+
+    assert_eq!(ai.last_actions, vec![0]);
+    assert_eq!(ai.action_momentum, vec![0.5, 0.5, 0.5]);
+    assert_eq!(ai.system_temperature, temperature);
+    assert_eq!((ai.velocity_trust, ai.frustration, ai.morale, ai.adrenaline), (trust, frustration, morale, adrenaline));
+}
+
+#[test]
+fn test_long_trajectory_is_learned_in_windows() {
+    let mut ai = Singularity::new(64, vec![3]);
+    let window = ai.max_history;
+    // max_history の 3 倍の長さ: 最も古いステップも切り捨てられずに学習される
+    let trajectory: Vec<TrajectoryStep> = (0..window * 3).map(|i| step(i, i % 3, 1.0)).collect();
+    ai.learn_trajectory(&trajectory, -1.0);
+
+    for s in &trajectory {
+        assert!(ai.symmetry.stats.contains_key(&s.state_idx), "state {} was not learned", s.state_idx);
+    }
+}
@@ -0,0 +1,36 @@
+use dark_singularity::core::opponent::OpponentModel;
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_opponent_prediction_and_response() {
+    let mut model = OpponentModel::new(3, 4);
+    assert!(model.predict(0).is_none());
+
+    for _ in 0..10 { model.observe(0, 1); }
+    model.observe(0, 2);
+
+    let dist = model.predict(0).unwrap();
+    assert!(dist[1] > dist[2] && dist[2] > dist[0]);
+
+    // 敵が行動1を取りがちな状態で、自分の行動3が報われた
+    for _ in 0..10 { model.reinforce(0, &[3], 1.0); }
+    model.reinforce(0, &[0], -1.0);
+
+    let response = model.expected_response(0).unwrap();
+    assert!(response[3] > 0.5);
+    assert!(response[0] < 0.0);
+}
+
+#[test]
+fn test_observe_opponent_feeds_learning() {
+    let mut ai = Singularity::new(8, vec![4]);
+    for _ in 0..5 { ai.observe_opponent(2, 1); }
+    assert!(ai.opponent_model.is_some());
+
+    for _ in 0..5 {
+        ai.select_actions(2);
+        ai.learn(1.0);
+    }
+    let response = ai.opponent_model.as_ref().unwrap().expected_response(2).unwrap();
+    assert!(response.iter().any(|&r| r > 0.0));
+}
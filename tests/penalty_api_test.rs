@@ -0,0 +1,66 @@
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_get_and_set_penalty() {
+    let mut ai = Singularity::new(8, vec![4]);
+    assert_eq!(ai.get_penalties(3), vec![0.0; 4]);
+
+    ai.set_penalty(3, 2, 7.5);
+    let penalties = ai.get_penalties(3);
+    assert!((penalties[2] - 7.5).abs() < 1e-6);
+    assert_eq!(penalties[0], 0.0);
+
+    // 範囲外は無視される
+    ai.set_penalty(100, 0, 1.0);
+    assert!(ai.get_penalties(100).is_empty());
+}
+
+#[test]
+fn test_clear_poisoned_penalty() {
+    let mut ai = Singularity::new(8, vec![4]);
+    for _ in 0..5 {
+        ai.select_actions(1);
+        ai.learn(-1.0);
+    }
+    let poisoned = ai.get_penalties(1);
+    let (action, _) = poisoned.iter().enumerate()
+        .max_by(|a, b| a.1.partial_cmp(b.1).unwrap()).unwrap();
+    assert!(poisoned[action] > 0.0);
+
+    ai.set_penalty(1, action, 0.0);
+    assert_eq!(ai.get_penalties(1)[action], 0.0);
+}
+
+#[test]
+fn test_live_penalty_field_tracks_last_state_and_rules() {
+    let mut ai = Singularity::new(8, vec![4]);
+    ai.select_actions(3);
+    ai.set_penalty(3, 1, 2.0);
+    ai.bootstrapper.add_hamiltonian_rule(7, 2, -0.5);
+    ai.set_active_conditions(&[7]);
+
+    let field = ai.live_penalty_field();
+    let widths = ai.action_widths();
+    let bin_of = |action: usize| widths[..action].iter().sum::<usize>();
+    assert_eq!(field.len(), ai.penalty_dim);
+    assert!(field[bin_of(1)] >= 2.0);
+    assert!(field[bin_of(2)] >= 25.0, "rule prohibitions join the live field");
+    assert_eq!(field[bin_of(0)], 0.0);
+}
+
+#[test]
+fn test_wave_evolution_outside_selection_is_damped_by_penalty() {
+    let energy = |penalized: bool| {
+        let mut ai = Singularity::new(8, vec![4]);
+        ai.last_state_idx = 2;
+        if penalized { ai.set_penalty(2, 1, 50.0); }
+        for _ in 0..5 { ai.update_all_nodes(&[], 0.0); }
+        let widths = ai.action_widths();
+        let start = widths[0];
+        (start..start + widths[1])
+            .map(|i| (ai.mwso.psi_real[i].powi(2) + ai.mwso.psi_imag[i].powi(2)) as f32)
+            .sum::<f32>()
+    };
+    let (free, damped) = (energy(false), energy(true));
+    assert!(damped < free, "penalized bins must lose energy: {} vs {}", damped, free);
+}
@@ -0,0 +1,51 @@
+use dark_singularity::core::config::ExplorationPolicy;
+use dark_singularity::core::node::{NodeSpec, NODE_AGGRESSION, NODE_FEAR, NODE_REFLEX};
+use dark_singularity::core::personality::Personality;
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_adaptive_matches_the_default_tuning() {
+    let plain = Singularity::new(8, vec![4]);
+    let adaptive = Singularity::with_personality(8, vec![4], Personality::Adaptive);
+    let decays = |ai: &Singularity| ai.nodes.iter().map(|n| (n.base_decay, n.state)).collect::<Vec<_>>();
+    assert_eq!(decays(&adaptive), decays(&plain));
+    assert_eq!(adaptive.system_temperature, plain.system_temperature);
+    assert_eq!(adaptive.config.temperature_floor, plain.config.temperature_floor);
+    assert_eq!(adaptive.config.temperature_ceiling, plain.config.temperature_ceiling);
+    assert_eq!(adaptive.config.temp_frustration_weight, plain.config.temp_frustration_weight);
+    assert_eq!(adaptive.config.exploration_policy, plain.config.exploration_policy);
+    assert_eq!(adaptive.exploration_beta, plain.exploration_beta);
+}
+
+#[test]
+fn test_presets_configure_everything_at_once() {
+    let mut ai = Singularity::with_personality(8, vec![4], Personality::Berserker);
+    assert_eq!(ai.personality, Personality::Berserker);
+    assert_eq!(ai.node_state(NODE_AGGRESSION), 1.0);
+    assert_eq!(ai.nodes[ai.node_index(NODE_REFLEX).unwrap()].base_decay, 0.7);
+    assert_eq!(ai.system_temperature, 1.2);
+    assert_eq!(ai.config.exploration_policy, ExplorationPolicy::EpsilonGreedy);
+
+    // 実行中に切り替える
+    ai.set_personality(Personality::Cautious);
+    assert_eq!(ai.node_state(NODE_AGGRESSION), 0.0);
+    assert_eq!(ai.node_state(NODE_FEAR), 0.4);
+    assert_eq!(ai.system_temperature, 0.3);
+    assert_eq!(ai.config.temperature_ceiling, 1.0);
+    assert_eq!(ai.config.exploration_policy, ExplorationPolicy::Greedy);
+    for step in 0..20 {
+        ai.select_actions(step % 8);
+        ai.learn(if step % 3 == 0 { -1.0 } else { 0.5 });
+        assert!(ai.system_temperature <= 1.0 + 1e-6);
+    }
+}
+
+#[test]
+fn test_presets_skip_missing_nodes() {
+    let mut ai = Singularity::with_nodes(8, vec![4], &[NodeSpec::new("greed", 0.2), NodeSpec::new(NODE_FEAR, 0.4)]);
+    ai.set_personality(Personality::Aggressive);
+    assert_eq!(ai.nodes[0].base_decay, 0.2);
+    assert_eq!(ai.nodes[1].base_decay, 0.3);
+    assert_eq!(Personality::from_id(3), Some(Personality::Berserker));
+    assert_eq!(Personality::from_id(4), None);
+}
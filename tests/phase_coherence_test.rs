@@ -0,0 +1,51 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::wave::{Wave, to_wave};
+
+#[test]
+fn test_aligned_phases_are_fully_coherent() {
+    let mut mwso = MWSO::new(1024);
+    for i in 0..256 {
+        // 振幅がばらばらでも位相が揃っていれば R = 1
+        let amp = to_wave(0.1 + i as f32 * 0.01);
+        mwso.psi_real[i] = amp * (0.7 as Wave).cos();
+        mwso.psi_imag[i] = amp * (0.7 as Wave).sin();
+    }
+    assert!((mwso.phase_coherence(0, 256) - 1.0).abs() < 1e-4);
+    assert!((mwso.action_phase_coherence(0, 4) - 1.0).abs() < 1e-4);
+}
+
+#[test]
+fn test_uniformly_spread_phases_are_incoherent() {
+    let mut mwso = MWSO::new(1024);
+    for i in 0..256 {
+        let phase = i as Wave / 256.0 * 2.0 * dark_singularity::core::wave::PI;
+        mwso.psi_real[i] = phase.cos();
+        mwso.psi_imag[i] = phase.sin();
+    }
+    assert!(mwso.phase_coherence(0, 256) < 1e-3);
+}
+
+#[test]
+fn test_silent_bins_report_zero() {
+    let mut mwso = MWSO::new(1024);
+    for v in &mut mwso.psi_real { *v = 0.0; }
+    assert_eq!(mwso.phase_coherence(0, 1024), 0.0);
+    assert_eq!(mwso.phase_coherence(0, 0), 0.0);
+}
+
+#[test]
+fn test_singularity_phase_coherence_in_range() {
+    for categories in [vec![4, 3], vec![20, 20]] {
+        let mut ai = Singularity::new(16, categories);
+        for i in 0..30 {
+            ai.select_actions(i % 16);
+            ai.learn(1.0);
+        }
+        for action in 0..ai.action_size {
+            let r = ai.phase_coherence(action);
+            assert!((0.0..=1.0 + 1e-4).contains(&r), "action {} R {}", action, r);
+        }
+        assert_eq!(ai.phase_coherence(ai.action_size), 0.0);
+    }
+}
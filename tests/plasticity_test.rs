@@ -0,0 +1,66 @@
+use dark_singularity::core::node::{Node, Synapse, NODE_AGGRESSION, NODE_FEAR, NODE_TACTICAL};
+use dark_singularity::core::singularity::Singularity;
+
+fn weight(node: &Node, target_id: usize) -> Option<f32> {
+    node.synapses.iter().find(|s| s.target_id == target_id).map(|s| s.weight)
+}
+
+#[test]
+fn test_hebbian_rule_strengthens_and_weakens() {
+    let states = [1.0, 1.0, 0.0, 0.0];
+    let mut active = Node::new(0.5);
+    active.state = 1.0;
+    active.apply_plasticity(0, &states, 0.1, -1.0, 3.0);
+    assert_eq!(weight(&active, 1), Some(0.1), "co-active nodes are wired together");
+    assert_eq!(weight(&active, 2), None, "anti-correlated pairs never sprout a synapse");
+    assert_eq!(weight(&active, 0), None, "no self-connection");
+
+    active.synapses.push(Synapse { target_id: 2, weight: 0.5 });
+    active.apply_plasticity(0, &states, 0.1, -1.0, 3.0);
+    assert!((weight(&active, 2).unwrap() - 0.4).abs() < 1e-6);
+    assert!((weight(&active, 1).unwrap() - 0.2).abs() < 1e-6);
+
+    let mut silent = Node::new(0.5);
+    silent.apply_plasticity(3, &states, 0.1, -1.0, 3.0);
+    assert!(silent.synapses.is_empty());
+}
+
+#[test]
+fn test_weights_stay_within_bounds() {
+    let mut node = Node::new(0.5);
+    node.state = 1.0;
+    for _ in 0..100 {
+        node.apply_plasticity(0, &[1.0, 1.0, 0.0], 0.5, -0.2, 1.0);
+    }
+    assert_eq!(weight(&node, 1), Some(1.0));
+
+    node.synapses.push(Synapse { target_id: 2, weight: 0.0 });
+    for _ in 0..100 {
+        node.apply_plasticity(0, &[1.0, 1.0, 0.0], 0.5, -0.2, 1.0);
+    }
+    assert_eq!(weight(&node, 2), Some(-0.2));
+}
+
+#[test]
+fn test_topology_self_organizes_when_enabled() {
+    let mut ai = Singularity::new(8, vec![4]);
+    let aggression = ai.node_index(NODE_AGGRESSION).unwrap();
+    let fear = ai.node_index(NODE_FEAR).unwrap();
+    let tactical = ai.node_index(NODE_TACTICAL).unwrap();
+
+    // 既定では無効なので結合は変わらない
+    ai.update_all_nodes(&[1.0, 1.0, 0.0, 0.0], 0.0);
+    assert!(ai.nodes.iter().all(|n| n.synapses.is_empty()));
+
+    ai.config.plasticity_rate = 0.05;
+    let mut inputs = [0.0; 4];
+    inputs[aggression] = 1.0;
+    inputs[fear] = 1.0;
+    for _ in 0..20 {
+        ai.update_all_nodes(&inputs, 0.0);
+    }
+    let link = weight(&ai.nodes[aggression], fear).unwrap();
+    assert!(link > 0.5, "co-active nodes bond: {link}");
+    assert!(weight(&ai.nodes[aggression], tactical).is_none_or(|w| w <= 0.0));
+    assert!(ai.nodes.iter().flat_map(|n| &n.synapses).all(|s| (-1.0..=3.0).contains(&s.weight)));
+}
@@ -0,0 +1,69 @@
+use dark_singularity::core::knowledge::Bootstrapper;
+use dark_singularity::core::singularity::{Experience, Singularity};
+
+const STATES: usize = 4;
+
+/// 状態 s では行動 s だけが報われる環境で学習させる
+fn trained() -> Singularity {
+    let mut ai = Singularity::new(16, vec![4]);
+    for step in 0..400 {
+        let state = step % STATES;
+        let action = ai.select_actions(state)[0] as usize;
+        ai.learn(if action == state { 2.0 } else { -1.0 });
+    }
+    for state in 0..STATES {
+        for _ in 0..5 {
+            ai.history.push_back(Experience { state_idx: state, actions: vec![state] });
+            ai.learn(2.0);
+        }
+    }
+    ai
+}
+
+#[test]
+fn test_extracted_rules_map_states_to_rewarded_actions() {
+    let ai = trained();
+    let policy = ai.extract_policy(0.0);
+    assert!(!policy.rules.is_empty());
+    for rule in &policy.rules {
+        assert!(rule.condition.is_none() && rule.span == 1);
+        assert!(rule.strength > 0.0 && rule.strength <= 1.0, "strength is the confidence");
+        assert!((rule.condition_id as usize) < STATES);
+    }
+    let correct = policy.rules.iter().filter(|r| r.target_action == r.condition_id as usize).count();
+    assert!(correct * 4 >= policy.rules.len() * 3, "{correct} / {}", policy.rules.len());
+    assert_eq!(policy.rules.iter().filter(|r| r.condition_id == 0).count(), 1, "one rule per state and category");
+}
+
+#[test]
+fn test_threshold_filters_and_state_is_untouched() {
+    let ai = trained();
+    let theta = ai.export_theta();
+    let history = ai.history.len();
+    let all = ai.extract_policy(0.0);
+    assert_eq!(ai.export_theta(), theta);
+    assert_eq!(ai.history.len(), history);
+    assert!(ai.extract_policy(1.01).rules.is_empty());
+
+    let cut = all.rules.iter().map(|r| r.strength).fold(f32::INFINITY, f32::min);
+    let strict = ai.extract_policy(cut + 1e-6);
+    assert!(strict.rules.iter().all(|r| r.strength > cut));
+    assert_eq!(strict.rules.len(), all.rules.iter().filter(|r| r.strength > cut).count());
+}
+
+#[test]
+fn test_extracted_policy_round_trips_as_rule_pack() {
+    let ai = trained();
+    let policy = ai.extract_policy(0.0);
+    let restored = Bootstrapper::from_json(&policy.to_json().unwrap()).unwrap();
+    assert_eq!(restored, policy);
+
+    // 未経験の脳でも抽出したルールで同じ行動を知識層が推す
+    let mut fresh = Singularity::new(16, vec![4]);
+    fresh.bootstrapper = restored;
+    for rule in &policy.rules {
+        let ranked = fresh.simulate_rules(&[], rule.condition_id as usize);
+        assert_eq!(ranked.categories[0][0].0, rule.target_action);
+    }
+    assert!(Singularity::new(16, vec![4]).extract_policy(0.0).rules.is_empty(), "nothing to extract before any experience");
+}
@@ -0,0 +1,116 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::quantized::{FieldPrecision, QuantizedRows, f16_to_f32, f32_to_f16};
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_f16_round_trip() {
+    for v in [0.0f32, 1.0, -2.5, 0.1, 9.99, 65504.0, 1e-6, -3.0e-5] {
+        let back = f16_to_f32(f32_to_f16(v));
+        assert!((back - v).abs() <= v.abs() * 1e-3 + 6e-8, "{v} -> {back}");
+    }
+    assert_eq!(f16_to_f32(f32_to_f16(1e6)), f32::INFINITY);
+    assert!(f16_to_f32(f32_to_f16(f32::NAN)).is_nan());
+}
+
+#[test]
+fn test_rows_track_f32_within_precision() {
+    let values: Vec<f32> = (0..64).map(|i| (i as f32 * 0.37).sin() * 5.0).collect();
+    for (precision, tol) in [(FieldPrecision::F32, 0.0), (FieldPrecision::F16, 5e-3), (FieldPrecision::I8, 5.0 / 254.0 + 1e-6)] {
+        let mut rows = QuantizedRows::new(precision, 16, &values);
+        assert_eq!(rows.rows(), 4);
+        for (i, &v) in values.iter().enumerate() {
+            assert!((rows.get(i) - v).abs() <= tol, "{precision:?} [{i}]");
+        }
+
+        // 刻みを超える値を書いても行全体が刻み直される
+        rows.set(3, 40.0);
+        assert!((rows.get(3) - 40.0).abs() <= 40.0 / 254.0 + 0.05);
+        rows.scale_row(0, 0.5);
+        assert!((rows.get(3) - 20.0).abs() <= 40.0 / 254.0 + 0.05);
+
+        let mut field = vec![1.0; 16];
+        rows.accumulate_row(1, 2.0, &mut field);
+        assert!((field[0] - (1.0 + values[16] * 2.0)).abs() <= tol * 2.0 + 1e-6);
+    }
+}
+
+#[test]
+fn test_quantized_storage_shrinks_penalties() {
+    let mut ai = Singularity::new(243, vec![8]);
+    let full = ai.penalty_bytes();
+    ai.set_field_precision(FieldPrecision::F16);
+    assert_eq!(ai.penalty_precision(), FieldPrecision::F16);
+    assert!(ai.penalty_matrix.is_empty());
+    assert!(ai.penalty_bytes() * 2 <= full);
+    ai.set_field_precision(FieldPrecision::I8);
+    assert!(ai.penalty_bytes() * 3 <= full);
+    ai.set_field_precision(FieldPrecision::F32);
+    assert_eq!(ai.penalty_bytes(), full);
+}
+
+#[test]
+fn test_quantized_agent_learns_like_full_precision() {
+    let run = |precision: FieldPrecision| {
+        let mut ai = Singularity::new(32, vec![8]);
+        ai.set_field_precision(precision);
+        for i in 0..60 {
+            let state = i % 32;
+            ai.select_actions(state);
+            ai.learn(if i % 4 == 0 { 1.5 } else { -0.5 });
+        }
+        ai.set_penalty(5, 2, 3.0);
+        (ai.get_penalties(5), ai.get_penalties(7), ai.mwso.gravity_stats(0.0))
+    };
+
+    let (full5, full7, full_gravity) = run(FieldPrecision::F32);
+    for precision in [FieldPrecision::F16, FieldPrecision::I8] {
+        let (p5, p7, gravity) = run(precision);
+        assert!((p5[2] - 3.0).abs() < 0.05, "{precision:?}: set_penalty must survive quantization");
+        for (a, b) in full7.iter().zip(&p7).chain(full5.iter().zip(&p5)) {
+            assert!((a - b).abs() < 0.5, "{precision:?}: {a} vs {b}");
+        }
+        assert!((gravity.mean - full_gravity.mean).abs() < 0.05, "{precision:?}");
+    }
+}
+
+#[test]
+fn test_gravity_precision_round_trip() {
+    let mut mwso = MWSO::new(64);
+    for g in &mut mwso.gravity_field { *g = 0.25; }
+    mwso.set_gravity_precision(FieldPrecision::I8);
+    assert!(mwso.gravity_field.is_empty());
+    mwso.erode_gravity(0, 0.1, 8);
+    assert!((mwso.gravity_for_action(0, 8) - 0.15).abs() < 0.01);
+    assert!((mwso.gravity(63) - 0.25).abs() < 0.01);
+
+    mwso.set_gravity_precision(FieldPrecision::F32);
+    assert_eq!(mwso.gravity_field.len(), 64);
+    assert!((mwso.gravity_field[63] - 0.25).abs() < 0.01);
+}
+
+#[test]
+fn test_checkpoint_and_save_keep_quantized_fields() {
+    let mut ai = Singularity::new(16, vec![4]);
+    ai.set_field_precision(FieldPrecision::F16);
+    for i in 0..20 {
+        ai.select_actions(i % 16);
+        ai.learn(-1.0);
+    }
+    let snapshot = ai.checkpoint();
+    let before = ai.get_penalties(3);
+    for _ in 0..10 {
+        ai.select_actions(3);
+        ai.learn(-2.0);
+    }
+    ai.restore(&snapshot);
+    assert_eq!(ai.get_penalties(3), before);
+
+    let path = std::env::temp_dir().join("quantized_field_test.dsym");
+    let path = path.to_str().unwrap();
+    ai.save_to_file(path).unwrap();
+    let mut loaded = Singularity::new(16, vec![4]);
+    loaded.load_from_file(path).unwrap();
+    let _ = std::fs::remove_file(path);
+    let gravity = ai.mwso.gravity_values();
+    assert_eq!(loaded.mwso.gravity_field, gravity.to_vec());
+}
@@ -0,0 +1,79 @@
+use dark_singularity::core::knowledge::{Bootstrapper, Condition, RuleConflictPolicy};
+use dark_singularity::core::singularity::Singularity;
+
+const RETREAT: i32 = 0;
+
+#[test]
+fn test_range_rule_covers_every_action_in_range() {
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.add_range_rule(Condition::Id(RETREAT), 2..5, -0.5, 0);
+    bootstrapper.add_hamiltonian_rule(RETREAT, 3, 0.2);
+    assert_eq!(bootstrapper.rules.len(), 2);
+    assert!(bootstrapper.rules[0].targets(4) && !bootstrapper.rules[0].targets(5));
+
+    let field = bootstrapper.calculate_resonance_field(&[RETREAT], 6);
+    assert_eq!(field[0..2], [None, None]);
+    assert_eq!(field[2], Some(-0.5));
+    assert!((field[3].unwrap() + 0.3).abs() < 1e-6, "range rules combine with single-action rules");
+    assert_eq!(field[4], Some(-0.5));
+    assert_eq!(field[5], None);
+
+    // 行動数を超える部分は切り捨てる
+    let field = bootstrapper.calculate_resonance_field(&[RETREAT], 4);
+    assert_eq!(field.len(), 4);
+    assert!(bootstrapper.calculate_resonance_field(&[], 6).iter().all(Option::is_none));
+
+    bootstrapper.add_range_rule(Condition::Id(RETREAT), 3..3, 1.0, 0);
+    assert_eq!(bootstrapper.rules.len(), 2, "empty ranges are ignored");
+}
+
+#[test]
+fn test_range_rules_follow_conflict_policy() {
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.policy = RuleConflictPolicy::HighestPriority;
+    bootstrapper.add_range_rule(Condition::Id(RETREAT), 0..4, -1.0, 5);
+    bootstrapper.add_hamiltonian_rule(RETREAT, 1, 3.0);
+    let field = bootstrapper.calculate_resonance_field(&[RETREAT], 4);
+    assert_eq!(field, vec![Some(-1.0); 4]);
+}
+
+#[test]
+fn test_category_rule_prohibits_whole_category() {
+    let mut ai = Singularity::new(8, vec![3, 4]);
+    assert_eq!(ai.category_actions(1), Some(3..7));
+    assert_eq!(ai.category_actions(2), None);
+    assert!(!ai.add_category_rule(Condition::Id(RETREAT), 2, -1.0, 0));
+    assert!(ai.add_category_rule(Condition::Id(RETREAT), 1, -1.0, 0));
+    assert_eq!(ai.bootstrapper.rules.len(), 1);
+
+    let ranked = ai.simulate_rules(&[RETREAT], 5);
+    assert!(ranked.categories[1].iter().all(|&(_, score)| score <= -100.0));
+    assert!(ranked.categories[0].iter().all(|&(_, score)| score == 0.0));
+
+    // 意思決定の経路でも範囲内の行動だけが禁止される
+    ai.bootstrapper.add_range_rule(Condition::Id(RETREAT + 1), 3..6, -1.0, 0);
+    ai.set_active_conditions(&[RETREAT + 1]);
+    ai.select_actions(5);
+    let knowledge: Vec<f32> = ai.explain_last_decision().iter().map(|b| b.knowledge).collect();
+    assert_eq!(knowledge, vec![0.0, 0.0, 0.0, -100.0, -100.0, -100.0, 0.0]);
+}
+
+#[test]
+fn test_span_survives_json_and_dsym() {
+    let mut ai = Singularity::new(8, vec![3, 4]);
+    ai.add_category_rule(Condition::Not(Box::new(Condition::Id(RETREAT))), 0, 0.4, 1);
+    ai.bootstrapper.add_hamiltonian_rule(2, 1, 0.1);
+
+    let json = ai.bootstrapper.to_json().unwrap();
+    assert_eq!(Bootstrapper::from_json(&json).unwrap(), ai.bootstrapper);
+    assert!(Bootstrapper::from_json(r#"{"rules": [{"condition_id": 0, "target_action": 0, "span": 0, "strength": 1.0}]}"#).is_err());
+
+    let path = std::env::temp_dir().join("ds_range_rule_test.dsym");
+    let path = path.to_str().unwrap();
+    ai.save_to_file(path).unwrap();
+    let mut loaded = Singularity::new(8, vec![3, 4]);
+    loaded.load_from_file(path).unwrap();
+    assert_eq!(loaded.bootstrapper.rules, ai.bootstrapper.rules);
+    assert_eq!(loaded.bootstrapper.rules[0].span, 3);
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,80 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::wave::to_wave;
+
+fn set_pattern(mwso: &mut MWSO, seed: usize) -> Vec<f32> {
+    (0..mwso.dim).map(|i| {
+        let phase = ((i * 2654435761 + seed * 40503) % 6283) as f32 / 1000.0;
+        mwso.psi_real[i] = to_wave(phase.cos());
+        mwso.psi_imag[i] = to_wave(phase.sin());
+        phase
+    }).collect()
+}
+
+#[test]
+fn test_recall_reconstructs_imprinted_pattern() {
+    let mut mwso = MWSO::new(1024);
+    let (query_re, query_im) = mwso.state_query(3);
+    assert_eq!(mwso.recall(&query_re, &query_im).resonance, 0.0, "empty memory recalls nothing");
+
+    let phases = set_pattern(&mut mwso, 11);
+    mwso.imprint_qcel(3, 1.0);
+    let before = mwso.psi_real.clone();
+
+    let recall = mwso.recall(&query_re, &query_im);
+    assert_eq!(mwso.psi_real, before, "recall must not touch the wave");
+    assert!(recall.resonance > 0.9, "resonance {}", recall.resonance);
+    let overlap: f32 = phases.iter().enumerate()
+        .map(|(i, p)| recall.pattern_re[i] * p.cos() + recall.pattern_im[i] * p.sin())
+        .sum::<f32>() / (recall.energy(0..1024).sqrt() * (1024f32).sqrt());
+    assert!(overlap > 0.9, "recalled pattern must match the imprinted one: {}", overlap);
+}
+
+#[test]
+fn test_recall_filters_antiphase_keys() {
+    let mut mwso = MWSO::new(1024);
+    set_pattern(&mut mwso, 11);
+    mwso.imprint_qcel(3, 1.0);
+
+    // 状態 1 の鍵は状態 3 の鍵とほぼ逆位相
+    let (re, im) = mwso.state_query(3);
+    let matched = mwso.recall(&re, &im);
+    let (re, im) = mwso.state_query(1);
+    let opposed = mwso.recall(&re, &im);
+    assert!(opposed.resonance < -0.5, "resonance {}", opposed.resonance);
+    assert!(opposed.energy(0..1024) < matched.energy(0..1024) * 0.5);
+}
+
+#[test]
+fn test_recall_state_reports_past_actions() {
+    let mut ai = Singularity::new(16, vec![4]);
+    let fresh = ai.recall_state(2);
+    assert_eq!(fresh.actions.len(), 4);
+    assert_eq!(fresh.best_action(), None);
+
+    for _ in 0..12 {
+        let action = ai.select_actions(2)[0] as usize;
+        ai.learn(if action == 1 { 1.0 } else { -0.5 });
+    }
+    let recall = ai.recall_state(2);
+    assert_eq!(recall.actions.iter().map(|a| a.1).sum::<u32>(), 12);
+    assert!(recall.wave.resonance.is_finite());
+    assert_eq!(recall.wave.pattern_re.len(), ai.mwso.dim);
+    if recall.actions[1].1 > 0 {
+        assert_eq!(recall.best_action(), Some(1));
+    }
+    assert_eq!(ai.recall_state(9).best_action(), None);
+}
+
+#[test]
+fn test_recall_state_sharded() {
+    let mut ai = Singularity::new(8, vec![20]);
+    for _ in 0..5 {
+        ai.select_actions(3);
+        ai.learn(1.0);
+    }
+    let recall = ai.recall_state(3);
+    assert_eq!(recall.actions.len(), 20);
+    assert!(recall.best_action().is_some());
+    assert!(recall.wave.resonance.is_finite());
+}
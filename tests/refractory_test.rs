@@ -0,0 +1,61 @@
+use dark_singularity::core::node::{Node, NODE_AGGRESSION, NODE_FEAR};
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_spike_opens_a_suppression_window() {
+    let mut node = Node::new(1.0);
+    node.refractory_period = 3;
+    node.update(1.0, 0.0, 0.0, &[]);
+    assert_eq!(node.state, 1.0);
+    assert_eq!(node.refractory_left, 3);
+
+    // 不応期の間は入力に応答しない
+    for left in (0..3).rev() {
+        node.update(0.0, 0.0, 0.0, &[]);
+        assert_eq!(node.state, 1.0);
+        assert_eq!(node.refractory_left, left);
+    }
+    node.update(0.0, 0.0, 0.0, &[]);
+    assert!(node.state < 1.0);
+    assert_eq!(node.refractory_left, 0, "falling below the threshold is not a spike");
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let mut node = Node::new(1.0);
+    node.update(1.0, 0.0, 0.0, &[]);
+    node.update(0.0, 0.0, 0.0, &[]);
+    assert_eq!(node.refractory_left, 0);
+    assert!(node.state < 1.0);
+}
+
+fn dominance_flips(ai: &mut Singularity) -> usize {
+    let aggression = ai.node_index(NODE_AGGRESSION).unwrap();
+    let fear = ai.node_index(NODE_FEAR).unwrap();
+    let mut flips = 0;
+    let mut last = None;
+    for step in 0..40 {
+        // 報酬が交互に振れると、攻撃性と恐怖への入力も交互に入れ替わる
+        let mut inputs = [0.0; 4];
+        inputs[if step % 2 == 0 { aggression } else { fear }] = 1.0;
+        ai.update_all_nodes(&inputs, 0.0);
+        let dominant = ai.nodes[aggression].state > ai.nodes[fear].state;
+        if last.is_some_and(|d| d != dominant) { flips += 1; }
+        last = Some(dominant);
+    }
+    flips
+}
+
+#[test]
+fn test_refractory_damps_emotional_oscillation() {
+    let mut free = Singularity::new(8, vec![4]);
+    let mut damped = Singularity::new(8, vec![4]);
+    damped.set_refractory(4, 0.8);
+    assert!(damped.nodes.iter().all(|n| n.refractory_period == 4));
+    let (free_flips, damped_flips) = (dominance_flips(&mut free), dominance_flips(&mut damped));
+    assert!(damped_flips < free_flips, "{damped_flips} vs {free_flips}");
+
+    assert_eq!(damped.add_node("greed", 0.3).map(|i| damped.nodes[i].refractory_period), Some(4));
+    damped.set_refractory(0, 0.8);
+    assert!(damped.nodes.iter().all(|n| n.refractory_left == 0));
+}
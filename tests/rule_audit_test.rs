@@ -0,0 +1,67 @@
+use dark_singularity::core::knowledge::{Bootstrapper, RuleActivation, RuleConflictPolicy};
+use dark_singularity::core::singularity::Singularity;
+
+fn activation(rule: usize, action: usize, contribution: f32) -> RuleActivation {
+    RuleActivation { rule, action, contribution }
+}
+
+#[test]
+fn test_activations_sum_to_resonance_field() {
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.add_hamiltonian_rule(0, 1, 0.5);
+    bootstrapper.add_hamiltonian_rule(1, 1, -2.0);
+    bootstrapper.add_always_rule(2, 0.3);
+    bootstrapper.add_hamiltonian_rule(7, 0, 1.0);
+    let levels = [(0, 1.0), (1, 0.5)];
+    let mut out = Vec::new();
+    for policy in [RuleConflictPolicy::Sum, RuleConflictPolicy::HighestPriority, RuleConflictPolicy::MaxMagnitude] {
+        bootstrapper.policy = policy;
+        bootstrapper.rule_activations_into(&levels, 4, &mut out);
+        let field = bootstrapper.calculate_graded_resonance_field(&levels, 4);
+        for (action, value) in field.iter().enumerate() {
+            let parts: Vec<f32> = out.iter().filter(|a| a.action == action).map(|a| a.contribution).collect();
+            assert_eq!(value.is_some(), !parts.is_empty(), "{policy:?} action {action}");
+            assert!((value.unwrap_or(0.0) - parts.iter().sum::<f32>()).abs() < 1e-6, "{policy:?} action {action}");
+        }
+    }
+    bootstrapper.policy = RuleConflictPolicy::MaxMagnitude;
+    bootstrapper.rule_activations_into(&levels, 4, &mut out);
+    assert_eq!(out, vec![activation(1, 1, -1.0), activation(2, 2, 0.3)], "the weaker rule is overridden");
+}
+
+#[test]
+fn test_last_rule_activations_reports_each_decision() {
+    let mut ai = Singularity::new(8, vec![4]);
+    ai.bootstrapper.add_penalty_rule(0, 2, 1.0);
+    ai.bootstrapper.add_hamiltonian_rule(5, 1, 0.4);
+    assert!(ai.last_rule_activations().is_empty());
+
+    ai.set_active_conditions(&[0]);
+    ai.select_actions(3);
+    assert_eq!(ai.last_rule_activations(), &[activation(0, 2, -1.0)]);
+    ai.select_actions(5);
+    assert_eq!(ai.last_rule_activations(), &[activation(0, 2, -1.0), activation(1, 1, 0.4)], "state rules are audited too");
+
+    ai.set_active_conditions(&[]);
+    ai.select_actions(3);
+    assert!(ai.last_rule_activations().is_empty(), "a pack that never fires leaves no trail");
+    assert_eq!(ai.rule_audit.len(), 3);
+    assert_eq!(ai.rule_audit[1].state_idx, 5);
+
+    // 仮想評価は監査記録に残らない
+    ai.evaluate_actions(5);
+    assert_eq!(ai.rule_audit.len(), 3);
+}
+
+#[test]
+fn test_audit_is_a_bounded_ring() {
+    let mut ai = Singularity::new(8, vec![4]);
+    ai.bootstrapper.add_always_rule(0, 0.2);
+    for step in 0..100 {
+        ai.select_actions(step % 8);
+        ai.learn(0.0);
+    }
+    assert_eq!(ai.rule_audit.len(), 64);
+    assert_eq!(ai.rule_audit.back().unwrap().state_idx, 99 % 8);
+    assert!(ai.rule_audit.iter().all(|audit| audit.activations == vec![activation(0, 0, 0.2)]));
+}
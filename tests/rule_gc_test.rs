@@ -0,0 +1,41 @@
+use dark_singularity::core::singularity::{Experience, Singularity};
+
+fn replay(ai: &mut Singularity, state_idx: usize, action: usize, reward: f32) {
+    ai.history.push_back(Experience { state_idx, actions: vec![action] });
+    ai.learn(reward);
+}
+
+#[test]
+fn test_cap_evicts_least_recently_used() {
+    let mut ai = Singularity::new(20, vec![3]);
+    ai.config.learned_rule_cap = 3;
+    ai.config.crystallize_threshold = 0;
+
+    replay(&mut ai, 0, 0, 2.0);
+    replay(&mut ai, 1, 0, 2.0);
+    replay(&mut ai, 2, 0, 2.0);
+    replay(&mut ai, 0, 0, 2.0); // 状態0のルールを最近使用にする
+    replay(&mut ai, 3, 0, 2.0);
+
+    assert_eq!(ai.learned_rules.len(), 3);
+    let states: Vec<usize> = ai.learned_rules.iter().map(|r| r.state).collect();
+    assert!(!states.contains(&1), "Least recently reinforced rule should be evicted: {:?}", states);
+    assert!(states.contains(&0));
+}
+
+#[test]
+fn test_periodic_pruning_of_stale_weak_rules() {
+    let mut ai = Singularity::new(20, vec![3]);
+    ai.config.rule_prune_interval = 10;
+    ai.config.rule_stale_after = 5;
+    ai.config.crystallize_threshold = 0;
+
+    replay(&mut ai, 5, 1, 2.0); // 1回だけ（弱い）
+    replay(&mut ai, 6, 1, 2.0);
+    replay(&mut ai, 6, 1, 2.0); // 2回（最低回数を満たす）
+    for _ in 0..7 { replay(&mut ai, 7, 2, 0.0); }
+
+    assert_eq!(ai.rule_clock, 10);
+    assert!(!ai.learned_rules.iter().any(|r| r.state == 5), "Stale single-hit rule should be pruned");
+    assert!(ai.learned_rules.iter().any(|r| r.state == 6));
+}
@@ -0,0 +1,68 @@
+use dark_singularity::core::knowledge::{Bootstrapper, Condition, HamiltonianRule};
+use dark_singularity::core::singularity::{Experience, Singularity};
+
+fn linear(b: &Bootstrapper, condition_id: i32, action: usize) -> Option<&HamiltonianRule> {
+    b.rules.iter().find(|r| r.condition.is_none() && r.condition_id == condition_id && r.targets(action))
+}
+
+#[test]
+fn test_find_rule_matches_linear_scan() {
+    let mut b = Bootstrapper::new();
+    for i in 0..500 {
+        b.add_hamiltonian_rule(i % 50, (i * 7) as usize % 13, i as f32);
+    }
+    b.add_range_rule(Condition::Id(3), 0..13, -2.0, 0);
+    b.add_composite_rule(Condition::Not(Box::new(Condition::Id(1))), 4, 1.0);
+    b.add_hamiltonian_rule(3, 12, 9.0);
+    for condition in -1..52 {
+        for action in 0..14 {
+            assert_eq!(b.find_rule(condition, action), linear(&b, condition, action), "({condition}, {action})");
+        }
+    }
+}
+
+#[test]
+fn test_direct_mutation_falls_back_to_scan() {
+    let mut b = Bootstrapper::new();
+    b.add_hamiltonian_rule(1, 1, 0.5);
+    // 公開フィールドを直接書き換えても結果は正しい
+    b.rules.insert(0, HamiltonianRule { strength: 0.9, ..b.rules[0].clone() });
+    assert_eq!(b.find_rule(1, 1).unwrap().strength, 0.9);
+    b.rules.clear();
+    assert!(b.find_rule(1, 1).is_none());
+    b.add_hamiltonian_rule(2, 0, 0.1);
+    assert_eq!(b.find_rule(2, 0).unwrap().strength, 0.1, "the next insertion reindexes");
+
+    b.rules[0].condition_id = 5;
+    assert!(b.find_rule(2, 0).is_none(), "a replaced entry is not trusted");
+    b.reindex();
+    assert!(b.find_rule(5, 0).is_some());
+
+    let restored = Bootstrapper::from_json(&b.to_json().unwrap()).unwrap();
+    assert_eq!(restored.find_rule(5, 0).unwrap().strength, 0.1);
+}
+
+#[test]
+fn test_learned_rules_stay_consistent_through_eviction_and_demotion() {
+    let mut ai = Singularity::new(32, vec![4]);
+    ai.config.learned_rule_cap = 8;
+    ai.config.crystallize_threshold = 3;
+    for step in 0..60 {
+        let state = step % 11;
+        let action = step % 4;
+        ai.history.push_back(Experience { state_idx: state, actions: vec![action] });
+        ai.learn(if step % 5 == 0 { -1.0 } else { 2.0 });
+        assert!(ai.learned_rules.len() <= 8);
+    }
+    // 知識層のみの順位は learned_rules の内容と一致する
+    for rule in ai.learned_rules.clone() {
+        let ranked = ai.simulate_rules(&[], rule.state);
+        let score = ranked.categories[0].iter().find(|&&(a, _)| a == rule.action).unwrap().1;
+        assert!(score >= (rule.count as f32).min(5.0) - 1e-6, "{rule:?} -> {score}");
+    }
+
+    // 直接書き換えても読み出しは線形探索に戻る
+    ai.learned_rules.clear();
+    let ranked = ai.simulate_rules(&[], 0);
+    assert!(ranked.categories[0].iter().all(|&(_, score)| score <= 0.0 || ai.bootstrapper.find_rule(0, 0).is_some()));
+}
@@ -0,0 +1,92 @@
+use dark_singularity::core::knowledge::{Bootstrapper, RuleLifecycle};
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_decay_shrinks_strength_every_tick() {
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.lifecycle.auto_decay = 0.5;
+    bootstrapper.add_expiring_rule(0, 1, 2.0);
+    bootstrapper.add_hamiltonian_rule(0, 2, 2.0);
+    assert_eq!(bootstrapper.advance(1, &[]), 0);
+    assert_eq!(bootstrapper.rules[0].strength, 1.0);
+    assert_eq!(bootstrapper.rules[1].strength, 2.0, "hand-registered rules keep their strength");
+    bootstrapper.advance(2, &[]);
+    assert_eq!(bootstrapper.rules[0].strength, 0.25);
+    assert_eq!(bootstrapper.rules[0].age, 3);
+}
+
+#[test]
+fn test_ttl_uses_and_min_strength_expire_rules() {
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.lifecycle = RuleLifecycle { ttl: Some(3), ..RuleLifecycle::default() };
+    bootstrapper.add_expiring_rule(0, 0, 1.0);
+    bootstrapper.add_hamiltonian_rule(0, 1, 1.0);
+    assert_eq!(bootstrapper.advance(3, &[]), 0);
+    assert_eq!(bootstrapper.advance(1, &[]), 1);
+    assert_eq!(bootstrapper.rules.len(), 1);
+    assert!(!bootstrapper.rules[0].expires);
+
+    let mut by_use = Bootstrapper::new();
+    by_use.lifecycle = RuleLifecycle { max_uses: Some(2), ..RuleLifecycle::default() };
+    by_use.add_expiring_rule(5, 0, 1.0);
+    by_use.advance(10, &[(4, 1.0)]);
+    assert_eq!(by_use.rules[0].uses, 0, "only ticks where the rule fires count as uses");
+    by_use.advance(1, &[(5, 1.0)]);
+    assert_eq!(by_use.rules.len(), 1);
+    assert_eq!(by_use.advance(1, &[(5, 0.3)]), 1);
+
+    let mut weak = Bootstrapper::new();
+    weak.lifecycle = RuleLifecycle { auto_decay: 0.5, min_strength: 0.2, ..RuleLifecycle::default() };
+    weak.add_expiring_rule(0, 0, 1.0);
+    weak.advance(2, &[]);
+    assert_eq!(weak.rules.len(), 1);
+    assert_eq!(weak.advance(1, &[]), 1);
+}
+
+#[test]
+fn test_expert_rules_fade_unless_demonstrated_again() {
+    let mut ai = Singularity::new(8, vec![4]);
+    ai.bootstrapper.lifecycle = RuleLifecycle { auto_decay: 0.9, ttl: Some(5), ..RuleLifecycle::default() };
+    ai.bootstrapper.add_hamiltonian_rule(2, 3, 1.0);
+    ai.observe_expert(6, &[1], 1.0);
+    let rule = ai.bootstrapper.rules.iter().find(|r| r.condition_id == 6).unwrap();
+    assert!(rule.expires && rule.decay == 0.9);
+
+    for _ in 0..4 {
+        ai.select_actions(0);
+        ai.learn(0.0);
+    }
+    let rule = ai.bootstrapper.rules.iter().find(|r| r.condition_id == 6).unwrap();
+    assert!((rule.strength - 0.5 * 0.9f32.powi(4)).abs() < 1e-6);
+
+    // 再びデモされると寿命が延びる
+    ai.observe_expert(6, &[1], 1.0);
+    for _ in 0..5 {
+        ai.select_actions(0);
+        ai.learn(0.0);
+    }
+    assert!(ai.bootstrapper.rules.iter().any(|r| r.condition_id == 6));
+    ai.select_actions(0);
+    ai.learn(0.0);
+    assert!(!ai.bootstrapper.rules.iter().any(|r| r.condition_id == 6), "an obsolete demonstration expires");
+    assert!(ai.bootstrapper.rules.iter().any(|r| r.condition_id == 2), "designer rules stay");
+}
+
+#[test]
+fn test_lifecycle_state_survives_save_and_load() {
+    let path = std::env::temp_dir().join("ds_rule_lifecycle_test.dsym");
+    let path = path.to_str().unwrap();
+    let mut ai = Singularity::new(8, vec![4]);
+    ai.bootstrapper.lifecycle.auto_decay = 0.8;
+    ai.observe_expert(3, &[2], 1.0);
+    ai.select_actions(0);
+    ai.learn(0.0);
+    ai.save_to_file(path).unwrap();
+
+    let mut loaded = Singularity::new(8, vec![4]);
+    loaded.bootstrapper.lifecycle.ttl = Some(1);
+    loaded.load_from_file(path).unwrap();
+    assert_eq!(loaded.bootstrapper.rules, ai.bootstrapper.rules);
+    assert_eq!(loaded.bootstrapper.lifecycle.ttl, Some(1), "the lifecycle is a runtime setting");
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,82 @@
+use dark_singularity::core::knowledge::{Bootstrapper, Condition, RuleConflictPolicy};
+use dark_singularity::core::singularity::Singularity;
+
+const ALLY_IN_LINE: i32 = 0;
+const ENEMY_VISIBLE: i32 = 1;
+const FIRE: usize = 0;
+
+/// 味方が射線上にいるときの「撃つな」と、それを上回る数の弱い「撃て」
+fn doctrine() -> Bootstrapper {
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.add_prioritized_rule(Condition::Id(ALLY_IN_LINE), FIRE, -1.0, 10);
+    for _ in 0..3 {
+        bootstrapper.add_hamiltonian_rule(ENEMY_VISIBLE, FIRE, 0.6);
+    }
+    bootstrapper.add_hamiltonian_rule(ENEMY_VISIBLE, 1, 0.2);
+    bootstrapper
+}
+
+#[test]
+fn test_default_policy_sums() {
+    let bootstrapper = doctrine();
+    assert_eq!(bootstrapper.policy, RuleConflictPolicy::Sum);
+    let field = bootstrapper.calculate_resonance_field(&[ALLY_IN_LINE, ENEMY_VISIBLE], 3);
+    assert!((field[FIRE].unwrap() - 0.8).abs() < 1e-6, "the safety rule is outvoted");
+    assert_eq!(field[2], None);
+}
+
+#[test]
+fn test_highest_priority_wins() {
+    let mut bootstrapper = doctrine();
+    bootstrapper.policy = RuleConflictPolicy::HighestPriority;
+    let field = bootstrapper.calculate_resonance_field(&[ALLY_IN_LINE, ENEMY_VISIBLE], 3);
+    assert_eq!(field[FIRE], Some(-1.0));
+    assert_eq!(field[1], Some(0.2), "actions without a conflict are unaffected");
+
+    // 安全ルールが発動していなければ、同じ優先度どうしは足し合わせる
+    let field = bootstrapper.calculate_resonance_field(&[ENEMY_VISIBLE], 3);
+    assert!((field[FIRE].unwrap() - 1.8).abs() < 1e-6);
+
+    // 優先度の低いルールが先に登録されていても後から上書きされる
+    let mut reversed = Bootstrapper::new();
+    reversed.policy = RuleConflictPolicy::HighestPriority;
+    reversed.add_hamiltonian_rule(ENEMY_VISIBLE, FIRE, 5.0);
+    reversed.add_prioritized_rule(Condition::Id(ALLY_IN_LINE), FIRE, -0.5, 1);
+    assert_eq!(reversed.calculate_resonance_field(&[ALLY_IN_LINE, ENEMY_VISIBLE], 1), vec![Some(-0.5)]);
+}
+
+#[test]
+fn test_max_magnitude_keeps_strongest() {
+    let mut bootstrapper = doctrine();
+    bootstrapper.policy = RuleConflictPolicy::MaxMagnitude;
+    let field = bootstrapper.calculate_resonance_field(&[ALLY_IN_LINE, ENEMY_VISIBLE], 3);
+    assert_eq!(field[FIRE], Some(-1.0));
+    let field = bootstrapper.calculate_resonance_field(&[ENEMY_VISIBLE], 3);
+    assert_eq!(field[FIRE], Some(0.6));
+}
+
+#[test]
+fn test_prioritized_id_rule_is_found_by_condition_id() {
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.add_prioritized_rule(Condition::Id(4), 2, 1.0, 3);
+    bootstrapper.add_prioritized_rule(Condition::Not(Box::new(Condition::Id(4))), 2, 1.0, 3);
+    assert_eq!(bootstrapper.rules[0].condition_id, 4);
+    assert!(bootstrapper.rules[0].condition.is_none());
+    assert!(bootstrapper.rules[1].condition.is_some());
+    assert!(bootstrapper.rules.iter().all(|r| r.priority == 3));
+
+    for id in 0..3 {
+        assert!(RuleConflictPolicy::from_id(id).is_some());
+    }
+    assert_eq!(RuleConflictPolicy::from_id(3), None);
+}
+
+#[test]
+fn test_policy_applies_to_singularity_ranking() {
+    let mut ai = Singularity::new(8, vec![3]);
+    ai.bootstrapper = doctrine();
+    let conditions = [ALLY_IN_LINE, ENEMY_VISIBLE];
+    assert_eq!(ai.simulate_rules(&conditions, 7).best(0), Some(FIRE));
+    ai.bootstrapper.policy = RuleConflictPolicy::HighestPriority;
+    assert_ne!(ai.simulate_rules(&conditions, 7).best(0), Some(FIRE));
+}
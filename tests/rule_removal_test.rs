@@ -0,0 +1,70 @@
+use dark_singularity::core::knowledge::{Bootstrapper, Condition, RuleConflictPolicy};
+use dark_singularity::core::singularity::Singularity;
+
+const CAPTURE_FLAG: i32 = 0;
+const DEFEND_BASE: i32 = 1;
+
+#[test]
+fn test_remove_rule_retracts_matching_rules_only() {
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.add_hamiltonian_rule(CAPTURE_FLAG, 2, 0.5);
+    bootstrapper.add_hamiltonian_rule(CAPTURE_FLAG, 2, 0.3);
+    bootstrapper.add_hamiltonian_rule(CAPTURE_FLAG, 3, 0.5);
+    bootstrapper.add_hamiltonian_rule(DEFEND_BASE, 2, 0.5);
+    let composite = Condition::All(vec![Condition::Id(CAPTURE_FLAG), Condition::Id(DEFEND_BASE)]);
+    bootstrapper.add_composite_rule(composite.clone(), 2, -1.0);
+
+    assert_eq!(bootstrapper.remove_rule(&Condition::Id(CAPTURE_FLAG), 2), 2);
+    assert_eq!(bootstrapper.remove_rule(&Condition::Id(CAPTURE_FLAG), 2), 0);
+    assert!(bootstrapper.find_rule(CAPTURE_FLAG, 2).is_none());
+    assert!(bootstrapper.find_rule(CAPTURE_FLAG, 3).is_some());
+    assert!(bootstrapper.find_rule(DEFEND_BASE, 2).is_some());
+    assert_eq!(bootstrapper.rules.len(), 3);
+
+    assert_eq!(bootstrapper.remove_rule(&composite, 2), 1);
+    assert!(bootstrapper.rules.iter().all(|r| r.condition.is_none()));
+    assert_eq!(bootstrapper.calculate_resonance_field(&[CAPTURE_FLAG, DEFEND_BASE], 4), vec![None, None, Some(0.5), Some(0.5)]);
+}
+
+#[test]
+fn test_range_rules_are_addressed_by_first_action() {
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.add_range_rule(Condition::Id(CAPTURE_FLAG), 1..4, -1.0, 0);
+    assert_eq!(bootstrapper.remove_rule(&Condition::Id(CAPTURE_FLAG), 2), 0);
+    assert_eq!(bootstrapper.update_strength(&Condition::Id(CAPTURE_FLAG), 1, -0.2), 1);
+    assert_eq!(bootstrapper.find_rule(CAPTURE_FLAG, 3).unwrap().strength, -0.2);
+    assert_eq!(bootstrapper.remove_rule(&Condition::Id(CAPTURE_FLAG), 1), 1);
+    assert!(bootstrapper.find_rule(CAPTURE_FLAG, 3).is_none());
+}
+
+#[test]
+fn test_update_strength_and_clear() {
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.policy = RuleConflictPolicy::MaxMagnitude;
+    bootstrapper.add_hamiltonian_rule(DEFEND_BASE, 0, 0.2);
+    assert_eq!(bootstrapper.update_strength(&Condition::Id(DEFEND_BASE), 0, 0.9), 1);
+    assert_eq!(bootstrapper.update_strength(&Condition::Id(DEFEND_BASE), 1, 0.9), 0);
+    assert_eq!(bootstrapper.calculate_resonance_field(&[DEFEND_BASE], 2), vec![Some(0.9), None]);
+
+    bootstrapper.clear();
+    assert!(bootstrapper.rules.is_empty());
+    assert!(bootstrapper.find_rule(DEFEND_BASE, 0).is_none());
+    assert_eq!(bootstrapper.policy, RuleConflictPolicy::MaxMagnitude, "settings survive a clear");
+    bootstrapper.add_hamiltonian_rule(DEFEND_BASE, 1, 0.4);
+    assert_eq!(bootstrapper.find_rule(DEFEND_BASE, 1).unwrap().strength, 0.4);
+}
+
+#[test]
+fn test_objective_change_mid_session() {
+    let mut ai = Singularity::new(8, vec![4]);
+    ai.bootstrapper.add_penalty_rule(CAPTURE_FLAG, 1, 1.0);
+    ai.set_active_conditions(&[CAPTURE_FLAG]);
+    ai.select_actions(5);
+    assert_eq!(ai.explain_last_decision()[1].knowledge, -100.0);
+
+    // 目標が変わったので禁止を撤回する
+    ai.bootstrapper.remove_rule(&Condition::Id(CAPTURE_FLAG), 1);
+    ai.select_actions(5);
+    assert_eq!(ai.explain_last_decision()[1].knowledge, 0.0);
+    assert!(ai.last_rule_activations().is_empty());
+}
@@ -0,0 +1,24 @@
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_simulate_rules_is_deterministic() {
+    let mut ai = Singularity::new(10, vec![4, 3]);
+    ai.bootstrapper.add_hamiltonian_rule(1, 2, 0.6);
+    ai.bootstrapper.add_hamiltonian_rule(1, 5, 0.3);
+    ai.bootstrapper.add_penalty_rule(1, 3, 1.0);
+    ai.bootstrapper.add_hamiltonian_rule(7, 0, 0.8); // 状態7に紐づくルール
+
+    let ranked = ai.simulate_rules(&[1], 4);
+    assert_eq!(ranked.best(0), Some(2));
+    assert_eq!(ranked.best(1), Some(1)); // 全体インデックス5 = カテゴリー1のローカル1
+    assert_eq!(ranked.categories[0].last().unwrap().0, 3, "Penalized action ranks last");
+    assert_eq!(ranked, ai.simulate_rules(&[1], 4));
+
+    // 条件が無効なら状態ルールのみが効く
+    let ranked = ai.simulate_rules(&[], 7);
+    assert_eq!(ranked.best(0), Some(0));
+
+    // シミュレーションは脳の状態を変えない
+    assert!(ai.history.is_empty());
+    assert!(ai.active_conditions.is_empty());
+}
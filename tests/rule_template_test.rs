@@ -0,0 +1,84 @@
+use dark_singularity::core::knowledge::{ActionExpr, Bootstrapper, Condition, EXPR_CONST, EXPR_DIV, EXPR_MOD, EXPR_STATE};
+use dark_singularity::core::singularity::Singularity;
+
+const LAW: i32 = 0;
+
+/// (状態 / 2) % 4
+fn half_state() -> ActionExpr {
+    ActionExpr::Mod(
+        Box::new(ActionExpr::Div(Box::new(ActionExpr::State), Box::new(ActionExpr::Const(2)))),
+        Box::new(ActionExpr::Const(4)),
+    )
+}
+
+#[test]
+fn test_expression_evaluation_and_encoding() {
+    assert_eq!(half_state().evaluate(9), Some(0));
+    assert_eq!(half_state().evaluate(7), Some(3));
+    let codes = half_state().encode_prefix();
+    assert_eq!(codes, vec![EXPR_MOD, EXPR_DIV, EXPR_STATE, EXPR_CONST, 2, EXPR_CONST, 4]);
+    assert_eq!(ActionExpr::decode_prefix(&codes), Some(half_state()));
+    assert_eq!(ActionExpr::decode_prefix(&codes[..6]), None);
+    assert_eq!(ActionExpr::decode_prefix(&[EXPR_STATE, EXPR_STATE]), None, "trailing codes are rejected");
+    assert_eq!(ActionExpr::decode_prefix(&[42]), None);
+
+    let shifted = ActionExpr::Sub(Box::new(ActionExpr::State), Box::new(ActionExpr::Const(3)));
+    assert_eq!(shifted.evaluate(1), Some(-2));
+    assert_eq!(ActionExpr::Div(Box::new(ActionExpr::State), Box::new(ActionExpr::Const(0))).evaluate(5), None);
+    assert_eq!(ActionExpr::Mul(Box::new(ActionExpr::Const(i64::MAX)), Box::new(ActionExpr::Const(2))).evaluate(0), None);
+}
+
+#[test]
+fn test_template_steers_every_state_with_one_rule() {
+    let mut bootstrapper = Bootstrapper::new();
+    bootstrapper.add_template(Condition::Id(LAW), half_state(), 0.5);
+    let template = &bootstrapper.templates[0];
+    assert_eq!(template.target(5, 4), Some(2));
+    assert_eq!(template.target(5, 2), None, "targets outside the action range steer nothing");
+    assert_eq!(bootstrapper.template_strength(&[(LAW, 1.0)], 5, 2), 0.5);
+    assert_eq!(bootstrapper.template_strength(&[(LAW, 0.4)], 5, 2), 0.2);
+    assert_eq!(bootstrapper.template_strength(&[], 5, 2), 0.0);
+    assert_eq!(bootstrapper.template_strength(&[(LAW, 1.0)], 5, 1), 0.0);
+
+    let mut ai = Singularity::new(16, vec![4]);
+    ai.bootstrapper = bootstrapper;
+    for state in 0..16 {
+        assert_eq!(ai.simulate_rules(&[LAW], state).best(0), Some((state / 2) % 4));
+    }
+    assert!(ai.simulate_rules(&[], 3).categories[0].iter().all(|&(_, score)| score == 0.0));
+
+    ai.set_active_conditions(&[LAW]);
+    ai.select_actions(6);
+    let knowledge: Vec<f32> = ai.explain_last_decision().iter().map(|b| b.knowledge).collect();
+    assert_eq!(knowledge, vec![0.0, 0.0, 0.0, 2.5]);
+}
+
+#[test]
+fn test_templates_persist_in_json_and_dsym() {
+    let mut ai = Singularity::new(16, vec![4]);
+    // 常に真の条件は前置記法では空の All になる
+    ai.bootstrapper.add_template(Condition::All(vec![]), half_state(), 0.3);
+    ai.bootstrapper.add_template(Condition::Not(Box::new(Condition::Id(LAW))), ActionExpr::Const(1), -0.2);
+    ai.bootstrapper.add_hamiltonian_rule(LAW, 0, 0.1);
+
+    let json = ai.bootstrapper.to_json().unwrap();
+    assert_eq!(Bootstrapper::from_json(&json).unwrap(), ai.bootstrapper);
+    let path = std::env::temp_dir().join("ds_rule_template_test.json");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, &json).unwrap();
+    let mut other = Singularity::new(16, vec![4]);
+    assert_eq!(other.load_knowledge(path).unwrap(), 3);
+    assert_eq!(other.bootstrapper.templates, ai.bootstrapper.templates);
+    let _ = std::fs::remove_file(path);
+
+    let path = std::env::temp_dir().join("ds_rule_template_test.dsym");
+    let path = path.to_str().unwrap();
+    ai.save_to_file(path).unwrap();
+    let mut loaded = Singularity::new(16, vec![4]);
+    loaded.load_from_file(path).unwrap();
+    assert_eq!(loaded.bootstrapper, ai.bootstrapper);
+    let _ = std::fs::remove_file(path);
+
+    ai.bootstrapper.clear();
+    assert!(ai.bootstrapper.templates.is_empty());
+}
@@ -1,6 +1,7 @@
 use dark_singularity::core::mwso::MWSO;
 use dark_singularity::core::mwso::ShardedMWSO;
 use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::wave::to_wave;
 
 /// 最適化された SNR 計算
 fn calculate_interference_snr_optimized(mwso: &MWSO, patterns: &Vec<(Vec<f32>, Vec<f32>)>, target_idx: usize, total_energy_sq: f32) -> f32 {
@@ -103,8 +104,8 @@ fn benchmark_memory_capacity_scaling() {
 
                     let pattern_id = shard_idx * 100000 + shard_n[shard_idx];
                     let (re, im) = generate_random_phase_pattern(shard_dim, pattern_id);
-                    sharded.shards[shard_idx].psi_real = re.clone();
-                    sharded.shards[shard_idx].psi_imag = im.clone();
+                    sharded.shards[shard_idx].psi_real = re.iter().copied().map(to_wave).collect();
+                    sharded.shards[shard_idx].psi_imag = im.iter().copied().map(to_wave).collect();
                     sharded.shards[shard_idx].imprint_qcel(shard_n[shard_idx], 1.0);
                     shard_patterns[shard_idx].push((re, im));
 
@@ -175,8 +176,8 @@ fn benchmark_memory_capacity_scaling() {
             loop {
                 let next_n = total_n + 1;
                 let (re, im) = generate_random_phase_pattern(dim, next_n);
-                mwso.psi_real = re.clone();
-                mwso.psi_imag = im.clone();
+                mwso.psi_real = re.iter().copied().map(to_wave).collect();
+                mwso.psi_imag = im.iter().copied().map(to_wave).collect();
                 mwso.imprint_qcel(total_n, 1.0);
                 patterns.push((re, im));
 
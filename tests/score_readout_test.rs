@@ -0,0 +1,58 @@
+use dark_singularity::core::mwso::{MWSO, ShardedMWSO};
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_scores_into_matches_allocating_readout() {
+    let mut mwso = MWSO::new(256);
+    mwso.inject_state(3, 1.0, &[]);
+    mwso.step_core(0.1, 0.0, 0.5, 0.5, &[]);
+    let mut penalty = vec![0.0; 256];
+    penalty[40] = 2.0;
+
+    let expected = mwso.get_action_scores(16, 10, 0.0, &penalty);
+    let mut buf = [f32::NAN; 10];
+    mwso.get_action_scores_into(16, &penalty, &mut buf);
+    assert_eq!(buf.to_vec(), expected);
+    for (i, &s) in expected.iter().enumerate() {
+        assert_eq!(mwso.get_action_score(i, 10, &[]), mwso.get_action_scores(0, 10, 0.0, &[])[i]);
+        assert!(s.is_finite());
+    }
+}
+
+#[test]
+fn test_sharded_scores_into_writes_every_shard_range() {
+    let mut sharded = ShardedMWSO::new(20);
+    sharded.inject_state(5, 1.0, 0.5, &[]);
+    let expected = sharded.get_action_scores(&[]);
+    let mut buf = vec![f32::NAN; 20];
+    sharded.get_action_scores_into(&[], &mut buf);
+    assert_eq!(buf, expected);
+    for (i, &s) in expected.iter().enumerate() {
+        assert_eq!(sharded.get_action_score(i, &[]), s);
+    }
+}
+
+#[test]
+fn test_singularity_readout_subtracts_fatigue() {
+    for sizes in [vec![12], vec![20]] {
+        let mut ai = Singularity::new(16, sizes);
+        for i in 0..10 {
+            ai.select_actions(i % 16);
+            ai.learn(-1.0);
+        }
+        let raw = ai.get_raw_scores(ai.action_size);
+        let mut buf = vec![0.0; ai.action_size + 4];
+        assert_eq!(ai.action_scores_into(&mut buf), ai.action_size);
+        assert!(buf[ai.action_size..].iter().all(|&s| s == 0.0), "only action_size entries are written");
+        for i in 0..ai.action_size {
+            let expected = raw[i] - ai.fatigue_map[i] * 2.0;
+            assert!((buf[i] - expected).abs() < 1e-6);
+            assert!((ai.action_score(i) - expected).abs() < 1e-6);
+        }
+        assert_eq!(ai.action_score(ai.action_size), 0.0);
+
+        let mut short = [0.0; 3];
+        assert_eq!(ai.action_scores_into(&mut short), 3);
+        assert_eq!(short.to_vec(), buf[..3].to_vec());
+    }
+}
@@ -0,0 +1,108 @@
+use dark_singularity::core::selfplay::{cross_pollinate, Arena, Environment, StepOutcome};
+use dark_singularity::core::singularity::Singularity;
+
+/// 三目並べ（プレイヤー 0 = X, 1 = O）
+struct TicTacToe {
+    cells: [u8; 9],
+    turn: usize,
+}
+
+impl TicTacToe {
+    fn new() -> Self {
+        Self { cells: [0; 9], turn: 0 }
+    }
+
+    fn winner(&self) -> Option<usize> {
+        const LINES: [[usize; 3]; 8] = [
+            [0, 1, 2], [3, 4, 5], [6, 7, 8],
+            [0, 3, 6], [1, 4, 7], [2, 5, 8],
+            [0, 4, 8], [2, 4, 6],
+        ];
+        LINES.iter()
+            .find(|l| self.cells[l[0]] != 0 && self.cells[l[0]] == self.cells[l[1]] && self.cells[l[0]] == self.cells[l[2]])
+            .map(|l| self.cells[l[0]] as usize - 1)
+    }
+}
+
+impl Environment for TicTacToe {
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn current_player(&self) -> usize {
+        self.turn
+    }
+
+    fn state_index(&self, player: usize) -> usize {
+        let mut idx = 0;
+        let mut p = 1;
+        for &c in &self.cells {
+            let val = match c {
+                0 => 0,
+                c if c as usize - 1 == player => 1,
+                _ => 2,
+            };
+            idx += val * p;
+            p *= 3;
+        }
+        idx
+    }
+
+    fn active_conditions(&self, _player: usize) -> Vec<i32> {
+        (0..9).filter(|&i| self.cells[i] != 0).map(|i| i as i32).collect()
+    }
+
+    fn step(&mut self, actions: &[i32]) -> StepOutcome {
+        let cell = actions[0] as usize;
+        if self.cells[cell] != 0 { return StepOutcome::Invalid; }
+        self.cells[cell] = self.turn as u8 + 1;
+        if let Some(w) = self.winner() { return StepOutcome::Win(w); }
+        if self.cells.iter().all(|&c| c != 0) { return StepOutcome::Draw; }
+        self.turn = 1 - self.turn;
+        StepOutcome::Continue
+    }
+}
+
+#[test]
+fn test_arena_accounts_for_every_match() {
+    let mut x = Singularity::new(19683, vec![9]);
+    let mut o = Singularity::new(19683, vec![9]);
+    let arena = Arena::default();
+    let report = arena.run(&mut TicTacToe::new(), [&mut x, &mut o], 30);
+
+    assert_eq!(report.matches, 30);
+    assert_eq!(report.wins[0] + report.wins[1] + report.draws, 30);
+    assert!(report.invalid[0] <= report.wins[1]);
+    assert!(report.invalid[1] <= report.wins[0]);
+    let total = report.win_rate(0) + report.win_rate(1) + report.draw_rate();
+    assert!((total - 1.0).abs() < 1e-5);
+}
+
+struct Endless;
+
+impl Environment for Endless {
+    fn reset(&mut self) {}
+    fn current_player(&self) -> usize { 0 }
+    fn state_index(&self, _player: usize) -> usize { 0 }
+    fn step(&mut self, _actions: &[i32]) -> StepOutcome { StepOutcome::Continue }
+}
+
+#[test]
+fn test_turn_limit_ends_in_draw() {
+    let mut a = Singularity::new(4, vec![2]);
+    let mut b = Singularity::new(4, vec![2]);
+    let arena = Arena { max_turns: 5, ..Arena::default() };
+    let report = arena.run(&mut Endless, [&mut a, &mut b], 2);
+    assert_eq!(report.draws, 2);
+}
+
+#[test]
+fn test_cross_pollination_mixes_memory_waves() {
+    let mut a = Singularity::new(4, vec![2]);
+    let mut b = Singularity::new(4, vec![2]);
+    a.mwso.q_memory_re[0] = 1.0;
+    b.mwso.q_memory_re[0] = 0.0;
+    cross_pollinate(&mut a, &mut b, 0.25);
+    assert!((a.mwso.q_memory_re[0] - 0.75).abs() < 1e-9);
+    assert!((b.mwso.q_memory_re[0] - 0.25).abs() < 1e-9);
+}
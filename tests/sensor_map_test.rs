@@ -0,0 +1,47 @@
+use dark_singularity::core::node::{NODE_AGGRESSION, NODE_FEAR, NODE_TACTICAL};
+use dark_singularity::core::sensor::SensorMap;
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_hash_matches_java_string_hash_code() {
+    assert_eq!(SensorMap::hash_name(""), 0);
+    assert_eq!(SensorMap::hash_name("hello"), 99162322);
+    // 桁あふれは Java と同じく 32bit で折り返す
+    assert_eq!(SensorMap::hash_name("damage_taken"), -262489097);
+}
+
+#[test]
+fn test_bindings_build_node_inputs() {
+    let mut sensors = SensorMap::new();
+    sensors.bind("damage_taken", NODE_FEAR, 0.5, 0.0);
+    sensors.bind("allies_nearby", NODE_FEAR, -0.2, 0.1);
+    sensors.bind("allies_nearby", NODE_AGGRESSION, 0.3, 0.0);
+    sensors.bind("resource_rate", "greed", 1.0, 0.0);
+    let index = |name: &str| [NODE_AGGRESSION, NODE_FEAR].iter().position(|&n| n == name);
+
+    let inputs = sensors.inputs(&[("damage_taken", 1.0), ("allies_nearby", 2.0), ("weather", 5.0), ("resource_rate", 3.0)], 2, index);
+    assert!((inputs[0] - 0.6).abs() < 1e-6);
+    assert!((inputs[1] - 0.2).abs() < 1e-6);
+    assert_eq!(sensors.inputs(&[], 2, index), vec![0.0, 0.0], "offsets apply only to signals that arrive");
+
+    assert_eq!(sensors.unbind("allies_nearby"), 2);
+    assert_eq!(sensors.inputs(&[("allies_nearby", 2.0)], 2, index), vec![0.0, 0.0]);
+}
+
+#[test]
+fn test_feed_sensors_updates_nodes_by_name() {
+    let mut ai = Singularity::new(8, vec![4]);
+    ai.sensors.bind("damage_taken", NODE_FEAR, 1.0, 0.0);
+    ai.sensors.bind("resource_rate", NODE_TACTICAL, 0.5, 0.2);
+    ai.feed_sensors(&[("damage_taken", 0.8)], 0.0);
+    assert!(ai.node_state(NODE_FEAR) > 0.0);
+    assert_eq!(ai.node_state(NODE_TACTICAL), 0.0);
+    assert_eq!(ai.node_state(NODE_AGGRESSION), 0.0);
+
+    // ノードを足しても名前で結ばれているので対応はずれない
+    ai.add_node("greed", 1.0);
+    ai.sensors.bind("resource_rate", "greed", 1.0, 0.0);
+    ai.feed_sensors_hashed(&[(SensorMap::hash_name("resource_rate"), 0.4)], 0.0);
+    assert!(ai.node_state("greed") > 0.3);
+    assert!(ai.node_state(NODE_TACTICAL) > 0.0);
+}
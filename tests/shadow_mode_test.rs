@@ -0,0 +1,53 @@
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_shadow_logs_without_executing() {
+    let path = "test_shadow_candidate.dsym";
+    let mut candidate = Singularity::new(6, vec![3]);
+    for i in 0..10 {
+        candidate.select_actions(i % 6);
+        candidate.learn(1.0);
+    }
+    candidate.save_to_file(path).unwrap();
+
+    let mut live = Singularity::new(6, vec![3]);
+    live.load_shadow_from_file(path, 8).unwrap();
+    std::fs::remove_file(path).ok();
+
+    for i in 0..8 {
+        live.select_actions(i % 6);
+        let reward = if live.last_actions[0] == 0 { 1.0 } else { -0.5 };
+        live.learn(reward);
+    }
+
+    let report = live.shadow_report().expect("Shadow attached");
+    assert_eq!(report.decisions, 8);
+    assert!(report.complete);
+    assert!(report.agreements <= 8);
+    assert!((report.agreement_rate - report.agreements as f32 / 8.0).abs() < 1e-6);
+    assert!(report.estimated_decisions >= report.agreements);
+
+    let shadow = live.shadow.as_ref().unwrap();
+    assert_eq!(shadow.log.len(), 8);
+    assert!(shadow.log.iter().all(|d| d.reward.is_some()));
+    // 候補モデルは入力を受けるが学習しない（履歴が消化されずに残る）
+    assert_eq!(shadow.candidate.history.len(), 8);
+
+    // 仮想評価はシャドウの意思決定として数えない
+    live.evaluate_actions(1);
+    assert_eq!(live.shadow_report().unwrap().decisions, 8);
+}
+
+#[test]
+fn test_promote_and_mismatch() {
+    let mut live = Singularity::new(6, vec![3]);
+    assert!(!live.attach_shadow(Singularity::new(6, vec![4]), 5), "Category layout must match");
+
+    let mut candidate = Singularity::new(6, vec![3]);
+    candidate.system_temperature = 0.123;
+    assert!(live.attach_shadow(candidate, 5));
+    assert!(live.promote_shadow());
+    assert_eq!(live.system_temperature, 0.123);
+    assert!(live.shadow.is_none());
+    assert!(!live.promote_shadow());
+}
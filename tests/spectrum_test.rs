@@ -0,0 +1,68 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::spectrum::Spectrum;
+use std::f32::consts::PI;
+
+#[test]
+fn test_default_spectrum_is_quadratic_ramp() {
+    let mwso = MWSO::new(64);
+    assert_eq!(mwso.frequencies, Spectrum::Quadratic.frequencies(64));
+    assert_eq!(MWSO::with_spectrum(64, Spectrum::Quadratic).frequencies, mwso.frequencies);
+    assert!((mwso.frequencies[32] - 0.25 * 2.0 * PI).abs() < 1e-6);
+}
+
+#[test]
+fn test_presets_shape_the_dispersion_relation() {
+    let linear = Spectrum::Linear.frequencies(100);
+    assert!((linear[50] - PI).abs() < 1e-5);
+
+    let stack = Spectrum::HarmonicStack { harmonics: 4 }.frequencies(10);
+    let f0 = PI / 2.0;
+    assert_eq!(stack[0], f0);
+    assert_eq!(stack[3], 4.0 * f0);
+    assert_eq!(stack[4], f0);
+
+    let band = Spectrum::BandLimited { low: 0.25, high: 0.5 }.frequencies(100);
+    assert!(band.iter().all(|&f| (0.5 * PI..=PI).contains(&f)));
+    assert_eq!(band[0], 0.5 * PI);
+
+    // 逆転した帯域は下端に潰れる
+    let inverted = Spectrum::BandLimited { low: 0.6, high: 0.2 }.frequencies(8);
+    assert!(inverted.iter().all(|&f| (f - 1.2 * PI).abs() < 1e-5));
+}
+
+#[test]
+fn test_from_id() {
+    assert_eq!(Spectrum::from_id(0, 0.0, 0.0), Some(Spectrum::Linear));
+    assert_eq!(Spectrum::from_id(2, 3.0, 0.0), Some(Spectrum::HarmonicStack { harmonics: 3 }));
+    assert_eq!(Spectrum::from_id(3, 0.1, 0.2), Some(Spectrum::BandLimited { low: 0.1, high: 0.2 }));
+    assert_eq!(Spectrum::from_id(9, 0.0, 0.0), None);
+}
+
+#[test]
+fn test_custom_frequencies_constructor() {
+    let custom: Vec<f32> = (0..32).map(|i| (i % 3) as f32).collect();
+    let mut mwso = MWSO::with_frequencies(&custom);
+    assert_eq!(mwso.dim, 32);
+    assert_eq!(mwso.frequencies, custom);
+    mwso.step_core(0.1, 0.0, 0.5, 0.5, &[]);
+    assert!(mwso.psi_real.iter().all(|v| v.is_finite()));
+}
+
+#[test]
+fn test_singularity_applies_spectrum_to_every_shard() {
+    let mut ai = Singularity::new(8, vec![20]);
+    ai.set_spectrum(Spectrum::Linear);
+    let sharded = ai.sharded_mwso.as_ref().unwrap();
+    assert!(sharded.shards.iter().all(|s| s.frequencies == Spectrum::Linear.frequencies(s.dim)));
+
+    let dim = sharded.shards[0].dim;
+    assert!(!ai.set_frequencies(&[1.0; 3]));
+    assert!(ai.set_frequencies(&vec![0.5; dim]));
+    assert!(ai.sharded_mwso.as_ref().unwrap().shards.iter().all(|s| s.frequencies.iter().all(|&f| f == 0.5)));
+
+    let mut single = Singularity::new(8, vec![4]);
+    let dim = single.mwso.dim;
+    assert!(single.set_frequencies(&vec![1.0; dim]));
+    assert!(!single.set_frequencies(&vec![f32::NAN; dim]));
+}
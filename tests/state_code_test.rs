@@ -0,0 +1,125 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::state_code::{STATE_CODE_BINS, StateCodeTable, StateCoding};
+
+/// 状態 0..states のコード同士の |内積| の平均と最大（単位ベクトルとして）
+fn overlaps(coding: StateCoding, dim: usize, states: usize) -> (f32, f32) {
+    let mut mwso = MWSO::new(dim);
+    mwso.set_state_coding(coding);
+    let codes: Vec<Vec<(usize, f32)>> = (0..states).map(|s| mwso.state_code(s)).collect();
+    let (mut sum, mut max, mut pairs) = (0.0f32, 0.0f32, 0usize);
+    for a in 0..states {
+        for b in a + 1..states {
+            let (mut re, mut im) = (0.0f32, 0.0f32);
+            for &(bin_a, phase_a) in &codes[a] {
+                for &(bin_b, phase_b) in &codes[b] {
+                    if bin_a == bin_b {
+                        re += (phase_b - phase_a).cos();
+                        im += (phase_b - phase_a).sin();
+                    }
+                }
+            }
+            let overlap = (re * re + im * im).sqrt() / STATE_CODE_BINS as f32;
+            sum += overlap;
+            max = max.max(overlap);
+            pairs += 1;
+        }
+    }
+    (sum / pairs as f32, max)
+}
+
+#[test]
+fn test_random_codes_collide_less_than_prime_stride() {
+    let (prime_mean, prime_max) = overlaps(StateCoding::PrimeStride, 1024, 400);
+    let (random_mean, random_max) = overlaps(StateCoding::RandomPhase, 1024, 400);
+    let (ortho_mean, ortho_max) = overlaps(StateCoding::Orthogonal, 1024, 400);
+    // 素数ストライドでは一部の状態の組が 16 ビンのうち多くを同じ配置で共有する
+    assert!(prime_max >= 0.3, "prime max {}", prime_max);
+    assert!(random_max < prime_max, "random {} prime {}", random_max, prime_max);
+    assert!(ortho_max < random_max, "ortho {} random {}", ortho_max, random_max);
+    assert!(ortho_mean < random_mean && random_mean < prime_mean, "{} {} {}", prime_mean, random_mean, ortho_mean);
+}
+
+#[test]
+fn test_codes_are_deterministic_and_order_independent() {
+    for coding in [StateCoding::RandomPhase, StateCoding::Orthogonal] {
+        let mut forward = StateCodeTable::new(coding, 1024).unwrap();
+        let mut backward = StateCodeTable::new(coding, 1024).unwrap();
+        let _ = backward.code(50);
+        for s in 0..50 {
+            assert_eq!(forward.code(s), backward.code(s));
+        }
+        let (bins, _) = forward.code(7);
+        let mut sorted = bins.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(sorted.len(), STATE_CODE_BINS, "bins in a code must be distinct");
+        assert!(bins.iter().all(|&b| b < 1024));
+    }
+    assert!(StateCodeTable::new(StateCoding::PrimeStride, 1024).is_none());
+}
+
+#[test]
+fn test_prime_stride_default_matches_legacy_injection() {
+    let mut a = MWSO::new(1024);
+    let mut b = MWSO::new(1024);
+    b.set_state_coding(StateCoding::PrimeStride);
+    a.inject_state(123, 1.0, &[]);
+    b.inject_state(123, 1.0, &[]);
+    assert_eq!(a.psi_real, b.psi_real);
+    assert_eq!(a.psi_imag, b.psi_imag);
+
+    // 変化したビンは state_code が返すビンと一致する
+    let fresh = MWSO::new(1024);
+    let touched: Vec<usize> = (0..1024).filter(|&i| a.psi_real[i] != fresh.psi_real[i]).collect();
+    let mut expected: Vec<usize> = a.state_code(123).into_iter().map(|(bin, _)| bin).collect();
+    expected.sort_unstable();
+    assert_eq!(touched, expected);
+}
+
+#[test]
+fn test_coded_injection_touches_code_bins() {
+    let mut mwso = MWSO::new(1024);
+    mwso.set_state_coding(StateCoding::Orthogonal);
+    let fresh = MWSO::new(1024);
+    mwso.inject_state(42, 1.0, &[]);
+    let mut expected: Vec<usize> = mwso.state_code(42).into_iter().map(|(bin, _)| bin).collect();
+    expected.sort_unstable();
+    let touched: Vec<usize> = (0..1024).filter(|&i| mwso.psi_real[i] != fresh.psi_real[i]).collect();
+    assert_eq!(touched, expected);
+    assert_eq!(StateCoding::from_id(2), Some(StateCoding::Orthogonal));
+    assert_eq!(StateCoding::from_id(3), None);
+}
+
+#[test]
+fn test_singularity_learns_with_orthogonal_codes() {
+    for categories in [vec![4, 3], vec![20, 20]] {
+        let mut ai = Singularity::new(64, categories);
+        ai.set_state_coding(StateCoding::Orthogonal);
+        assert_eq!(ai.mwso.state_coding(), StateCoding::Orthogonal);
+        for i in 0..60 {
+            ai.select_actions(i % 64);
+            ai.learn(if i % 2 == 0 { 1.0 } else { -0.5 });
+        }
+        assert!(ai.wave_norm().is_finite());
+    }
+}
+
+#[test]
+fn test_state_coding_survives_save_and_load() {
+    let mut ai = Singularity::new(64, vec![4, 3]);
+    ai.set_state_coding(StateCoding::Orthogonal);
+    for i in 0..20 {
+        ai.select_actions(i % 64);
+        ai.learn(1.0);
+    }
+    let path = std::env::temp_dir().join(format!("ds_state_coding_{}.dsym", std::process::id()));
+    ai.save_to_file(path.to_str().unwrap()).unwrap();
+
+    // 既定の素数ストライドで作ったモデルへ読み込んでも、保存時のコードへ戻る
+    let mut loaded = Singularity::new(64, vec![4, 3]);
+    loaded.load_from_file(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(loaded.mwso.state_coding(), StateCoding::Orthogonal);
+    assert_eq!(loaded.mwso.state_code(17), ai.mwso.state_code(17));
+}
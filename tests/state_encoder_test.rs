@@ -0,0 +1,44 @@
+use dark_singularity::core::encoder::StateEncoder;
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_hashed_encoding_is_deterministic_and_in_range() {
+    let encoder = StateEncoder::hashed(1024);
+    let ids = [0u64, 1, 19_682, u64::MAX, 0xDEAD_BEEF_CAFE_F00D];
+    for &id in &ids {
+        let a = encoder.encode_id(id);
+        assert_eq!(a, encoder.encode_id(id));
+        assert!(a.iter().all(|&(idx, _)| idx < 1024));
+        let total: f32 = a.iter().map(|&(_, w)| w).sum();
+        assert!((total - 1.0).abs() < 1e-5);
+    }
+    assert_ne!(encoder.encode_id(1), encoder.encode_id(2));
+}
+
+#[test]
+fn test_feature_contributions_merge_per_bucket() {
+    let encoder = StateEncoder::Hashed { buckets: 1, probes: 3 };
+    assert_eq!(encoder.encode_features(&[(7, 0.5), (9, 2.0)]), vec![(0, 1.0)]);
+
+    let dense = StateEncoder::Dense;
+    assert_eq!(dense.encode_features(&[(2, 1.0), (5, 3.0), (6, 0.0)]), vec![(2, 0.25), (5, 0.75)]);
+}
+
+#[test]
+fn test_hashed_singularity_learns_on_huge_ids() {
+    let mut ai = Singularity::new_hashed(vec![4]);
+    assert_eq!(ai.state_size, ai.mwso.dim);
+
+    // 巨大な状態IDでも選択・学習が破綻しない
+    let state_id = 0x0123_4567_89AB_CDEF_u64;
+    for _ in 0..30 {
+        let action = ai.select_actions_id(state_id)[0];
+        ai.learn(if action == 2 { 1.0 } else { -0.5 });
+    }
+    let hits = (0..20).filter(|_| {
+        let a = ai.select_actions_id(state_id)[0];
+        ai.learn(if a == 2 { 1.0 } else { -0.5 });
+        a == 2
+    }).count();
+    assert!(hits >= 10, "Hashed state should still learn its best action ({} / 20)", hits);
+}
@@ -0,0 +1,37 @@
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_counts_and_latencies_are_recorded() {
+    let mut ai = Singularity::new(16, vec![4, 3]);
+    for i in 0..20 {
+        ai.select_actions(i % 16);
+        ai.learn(if i % 2 == 0 { 1.0 } else { -0.5 });
+    }
+    let path = std::env::temp_dir().join("ds_stats_test.dsym");
+    ai.save_to_file(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    let stats = ai.stats();
+    assert_eq!(stats.select_actions.count, 20);
+    assert_eq!(stats.learn.count, 20);
+    assert_eq!(stats.save_to_file.count, 1);
+    for summary in [stats.select_actions, stats.learn] {
+        assert!(summary.min <= summary.avg && summary.avg <= summary.max);
+        assert!(summary.min <= summary.p99 && summary.p99 <= summary.max);
+    }
+}
+
+#[test]
+fn test_histories_follow_learn_calls() {
+    let mut ai = Singularity::new(16, vec![4]);
+    for i in 0..300 {
+        ai.select_actions(i % 16);
+        ai.learn(-1.0);
+    }
+    let stats = ai.stats();
+    // 履歴は直近分だけ保持される
+    assert_eq!(stats.wave_norm.len(), 256);
+    assert_eq!(stats.temperature.len(), 256);
+    assert_eq!(*stats.temperature.last().unwrap(), ai.system_temperature);
+    assert!((stats.wave_norm.last().unwrap() - ai.wave_norm()).abs() < 1e-6);
+}
@@ -0,0 +1,69 @@
+use dark_singularity::core::singularity::Singularity;
+use std::fs;
+
+#[test]
+fn test_load_reports_progress() {
+    let path = "streaming_progress_test.dsym";
+    let mut sing = Singularity::new(32, vec![8]);
+    sing.system_temperature = 0.7;
+    sing.mwso.psi_real[3] = 0.42;
+    sing.save_to_file(path).expect("Failed to save");
+
+    let mut loaded = Singularity::new(32, vec![8]);
+    let mut reports: Vec<(u64, u64)> = Vec::new();
+    loaded
+        .load_from_file_with_progress(path, &mut |done, total| reports.push((done, total)))
+        .expect("Failed to load");
+
+    let total = fs::metadata(path).unwrap().len();
+    assert!(reports.len() > 1, "Progress should be reported per section");
+    assert!(reports.windows(2).all(|w| w[0].0 <= w[1].0));
+    assert_eq!(reports.last().unwrap(), &(total, total));
+    assert!((loaded.system_temperature - 0.7).abs() < 1e-6);
+    assert!((loaded.mwso.psi_real[3] - 0.42).abs() < 1e-6);
+
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn test_load_legacy_v14_without_section_tags() {
+    let path = "streaming_legacy_v14_test.dsym";
+    let action_size = 4;
+    let sing = Singularity::new(16, vec![action_size]);
+
+    // v14: セクションタグのない旧フォーマット
+    let mut buf = Vec::new();
+    buf.extend_from_slice(b"DSYM");
+    buf.extend_from_slice(&14u32.to_le_bytes());
+    buf.extend_from_slice(&16u32.to_le_bytes());
+    buf.extend_from_slice(&0.9f32.to_le_bytes()); // temperature
+    buf.extend_from_slice(&0u32.to_le_bytes()); // locked
+    for v in [0.3f32, 0.0, 1.0, 1.0, 1.0, 0.1] { buf.extend_from_slice(&v.to_le_bytes()); }
+    for _ in 0..action_size * 2 { buf.extend_from_slice(&0.0f32.to_le_bytes()); }
+    for _ in 0..sing.mwso.dim { buf.extend_from_slice(&0.0f32.to_le_bytes()); }
+    for _ in 0..4 { buf.extend_from_slice(&0u32.to_le_bytes()); } // history, cats, nodes, rules
+    buf.extend_from_slice(&0u32.to_le_bytes()); // dim mismatch -> wave skipped
+    buf.extend_from_slice(&0u32.to_le_bytes()); // theta length
+    fs::write(path, &buf).unwrap();
+
+    let mut loaded = Singularity::new(16, vec![action_size]);
+    loaded.load_from_file(path).expect("Legacy file should load");
+    assert!((loaded.system_temperature - 0.9).abs() < 1e-6);
+    assert!((loaded.adrenaline - 0.3).abs() < 1e-6);
+
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn test_truncated_file_is_an_error() {
+    let path = "streaming_truncated_test.dsym";
+    let sing = Singularity::new(16, vec![4]);
+    sing.save_to_file(path).unwrap();
+    let bytes = fs::read(path).unwrap();
+    fs::write(path, &bytes[..bytes.len() / 2]).unwrap();
+
+    let mut loaded = Singularity::new(16, vec![4]);
+    assert!(loaded.load_from_file(path).is_err());
+
+    let _ = fs::remove_file(path);
+}
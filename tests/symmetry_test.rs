@@ -0,0 +1,106 @@
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::symmetry::{SymmetryAnalyzer, SymmetryKind, SymmetryProposal};
+
+#[test]
+fn test_analyzer_groups_equivalent_states() {
+    let mut analyzer = SymmetryAnalyzer::new(3);
+    // 状態 0, 2, 5 は同じ行動-結果統計、状態 1 は逆、状態 3 はやや似ている
+    for &s in &[0usize, 2, 5] {
+        for _ in 0..3 {
+            analyzer.record(s, 0, 1.0);
+            analyzer.record(s, 1, -1.0);
+        }
+    }
+    for _ in 0..3 {
+        analyzer.record(1, 0, -1.0);
+        analyzer.record(1, 1, 1.0);
+        analyzer.record(3, 0, 0.8);
+        analyzer.record(3, 1, -1.0);
+    }
+
+    let proposals = analyzer.propose();
+    let merge = proposals.iter().find(|p| p.kind == SymmetryKind::Merge).expect("Merge proposal");
+    assert_eq!(merge.states, vec![0, 2, 5]);
+    assert!(proposals.iter().all(|p| !p.states.contains(&1)));
+    assert!(proposals.iter().any(|p| p.kind == SymmetryKind::Wormhole && p.states.contains(&3)));
+}
+
+#[test]
+fn test_merges_do_not_chain_beyond_tolerance() {
+    let mut analyzer = SymmetryAnalyzer::new(2);
+    // 隣り合う状態どうしは 0.03 しか違わないが、両端は 0.06 離れている（統合の許容値は 0.05）
+    for (s, reward) in [(0usize, 1.0f32), (1, 1.06), (2, 1.12)] {
+        for _ in 0..4 {
+            analyzer.record(s, 0, reward);
+            analyzer.record(s, 1, -1.0);
+        }
+    }
+
+    let proposals = analyzer.propose();
+    let merges: Vec<&SymmetryProposal> = proposals.iter().filter(|p| p.kind == SymmetryKind::Merge).collect();
+    assert_eq!(merges.len(), 1);
+    assert_eq!(merges[0].states, vec![0, 1]);
+    assert!(merges[0].distance < analyzer.merge_tolerance);
+    assert!(proposals.iter().any(|p| p.kind == SymmetryKind::Wormhole && p.states.contains(&2)));
+}
+
+#[test]
+fn test_apply_merge_aliases_states() {
+    let mut ai = Singularity::new(8, vec![2]);
+    for _ in 0..6 {
+        for &s in &[1usize, 4] {
+            ai.select_actions(s);
+            ai.learn(0.5);
+        }
+    }
+    assert!(ai.symmetry.stats.contains_key(&1), "learn should feed the experience statistics");
+
+    // 学習由来の統計を、結果が確定した合成ログで置き換える
+    ai.symmetry.stats.clear();
+    for &s in &[1usize, 4] {
+        for _ in 0..4 {
+            ai.symmetry.record(s, 0, 1.0);
+            ai.symmetry.record(s, 1, -1.0);
+        }
+    }
+
+    let proposals = ai.propose_symmetries();
+    let merge = proposals.iter().find(|p| p.kind == SymmetryKind::Merge).expect("Merge proposal");
+    assert_eq!(merge.states, vec![1, 4]);
+    assert!(ai.state_aliases.is_empty(), "Proposals are not applied until approved");
+
+    ai.apply_symmetry(merge, 0.1);
+    assert_eq!(ai.canonical_state(4), 1);
+    ai.select_actions(4);
+    assert_eq!(ai.last_state_idx, 1);
+
+    let wormholes_before = ai.mwso.entanglements.len();
+    let manual = SymmetryProposal { kind: SymmetryKind::Wormhole, states: vec![2, 6], distance: 0.1 };
+    ai.apply_symmetry(&manual, 0.1);
+    // 状態が注入されるビン（状態コード）どうしが双方向に結ばれる
+    let (code_2, code_6) = (ai.mwso.state_code(2), ai.mwso.state_code(6));
+    let linked = code_2.iter().zip(&code_6).filter(|(a, b)| a.0 != b.0).count();
+    assert!(linked > 0);
+    assert_eq!(ai.mwso.entanglements.len(), wormholes_before + linked * 2);
+    for (&(a, _), &(b, _)) in code_2.iter().zip(&code_6).filter(|(a, b)| a.0 != b.0) {
+        assert!(ai.mwso.entanglements.iter().any(|&(f, t, _)| f == a && t == b));
+        assert!(ai.mwso.entanglements.iter().any(|&(f, t, _)| f == b && t == a));
+    }
+}
+
+#[test]
+fn test_merges_survive_save_and_load() {
+    let mut ai = Singularity::new(8, vec![2]);
+    let merge = SymmetryProposal { kind: SymmetryKind::Merge, states: vec![1, 4, 6], distance: 0.0 };
+    ai.apply_symmetry(&merge, 0.1);
+    let path = std::env::temp_dir().join(format!("ds_symmetry_aliases_{}.dsym", std::process::id()));
+    ai.save_to_file(path.to_str().unwrap()).unwrap();
+
+    let mut loaded = Singularity::new(8, vec![2]);
+    loaded.load_from_file(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(loaded.state_aliases, ai.state_aliases);
+    assert_eq!(loaded.canonical_state(6), 1);
+    loaded.select_actions(4);
+    assert_eq!(loaded.last_state_idx, 1);
+}
@@ -0,0 +1,45 @@
+use dark_singularity::core::node::{NODE_FEAR, NODE_REFLEX};
+use dark_singularity::core::singularity::Singularity;
+
+fn synapses(ai: &Singularity) -> Vec<Vec<(usize, f32)>> {
+    ai.nodes.iter().map(|n| n.synapses.iter().map(|s| (s.target_id, s.weight)).collect()).collect()
+}
+
+#[test]
+fn test_shaped_topology_survives_reload() {
+    let path = std::env::temp_dir().join("ds_synapse_persistence_test.dsym");
+    let path = path.to_str().unwrap();
+
+    let mut ai = Singularity::new(8, vec![4]);
+    ai.config.plasticity_rate = 0.05;
+    ai.set_neuron_state(ai.node_index(NODE_FEAR).unwrap(), 0.8);
+    ai.reshape_topology();
+    ai.update_all_nodes(&[1.0, 1.0, 0.0, 0.0], 0.0);
+    let fear = ai.node_index(NODE_FEAR).unwrap();
+    let reflex = ai.node_index(NODE_REFLEX).unwrap();
+    assert!(ai.nodes[fear].synapses.iter().any(|s| s.target_id == reflex));
+    ai.save_to_file(path).unwrap();
+
+    let mut loaded = Singularity::new(8, vec![4]);
+    loaded.load_from_file(path).unwrap();
+    assert_eq!(synapses(&loaded), synapses(&ai));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_reload_replaces_existing_synapses() {
+    let path = std::env::temp_dir().join("ds_synapse_replace_test.dsym");
+    let path = path.to_str().unwrap();
+
+    let ai = Singularity::new(8, vec![4]);
+    ai.save_to_file(path).unwrap();
+
+    // 保存時に結合が無ければ、読み込み側の結合も消える
+    let mut loaded = Singularity::new(8, vec![4]);
+    loaded.set_neuron_state(1, 1.0);
+    loaded.reshape_topology();
+    assert!(loaded.nodes.iter().any(|n| !n.synapses.is_empty()));
+    loaded.load_from_file(path).unwrap();
+    assert!(loaded.nodes.iter().all(|n| n.synapses.is_empty()));
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,39 @@
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_trace_is_recorded_with_neutral_defaults() {
+    let mut ai = Singularity::new(5, vec![3]);
+    ai.select_actions(0);
+    ai.learn(0.5);
+    let trace = *ai.temperature_trace.back().expect("Controller should record a trace");
+    assert_eq!(trace.result, ai.system_temperature);
+    assert!((trace.ceiling - 2.0).abs() < 1e-6);
+    assert!((trace.floor - 0.01).abs() < 1e-6, "Morale above 1.0 keeps the base floor");
+}
+
+#[test]
+fn test_intervention_caps_temperature() {
+    let mut ai = Singularity::new(5, vec![3]);
+    ai.set_intervention_level(1.0);
+    for _ in 0..20 {
+        ai.select_actions(1);
+        ai.learn(-2.0);
+    }
+    let trace = *ai.temperature_trace.back().unwrap();
+    assert!((trace.ceiling - 1.0).abs() < 1e-6);
+    assert!(ai.system_temperature <= 1.0 + 1e-6);
+    assert!(trace.frustration_heat > 0.0, "Repeated failures should add frustration heat");
+    assert!(trace.floor > 0.01, "Low morale should raise the floor");
+}
+
+#[test]
+fn test_locked_temperature_is_untouched() {
+    let mut ai = Singularity::new(5, vec![3]);
+    ai.temperature_locked = true;
+    ai.system_temperature = 1.7;
+    ai.set_intervention_level(1.0);
+    ai.select_actions(0);
+    ai.learn(-1.0);
+    assert_eq!(ai.system_temperature, 1.7);
+    assert!(ai.temperature_trace.is_empty());
+}
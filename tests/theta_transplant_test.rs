@@ -0,0 +1,62 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::wave::{Wave, from_wave, to_wave};
+
+fn trained(states: usize) -> Singularity {
+    let mut ai = Singularity::new(states, vec![4]);
+    for i in 0..60 {
+        let a = ai.select_actions(i % states)[0];
+        ai.learn(if a as usize == i % 4 { 1.0 } else { -0.5 });
+    }
+    ai
+}
+
+#[test]
+fn test_export_import_roundtrip() {
+    let teacher = trained(8);
+    let theta = teacher.export_theta();
+    assert_eq!(theta.len(), teacher.mwso.dim * 2);
+
+    let mut student = Singularity::new(8, vec![4]);
+    assert_ne!(student.export_theta(), theta);
+    assert!(student.import_theta(&theta));
+    assert_eq!(student.export_theta(), theta);
+    // 書き出しは f32 なので、f64 モードでは f32 に丸めた値が移る
+    let rounded: Vec<Wave> = teacher.mwso.theta.iter().map(|&t| to_wave(from_wave(t))).collect();
+    assert_eq!(student.mwso.theta, rounded);
+}
+
+#[test]
+fn test_import_resamples_other_dimensions() {
+    let mut small = MWSO::new(128);
+    for (i, t) in small.theta.iter_mut().enumerate() { *t = if i < 128 { 0.5 } else { -0.25 }; }
+    let mut large = MWSO::new(256);
+    assert!(large.import_theta(&small.export_theta()));
+    assert_eq!(large.theta.len(), 512);
+    assert!(large.theta[..256].iter().all(|&t| (t - 0.5).abs() < 1e-6));
+    assert!(large.theta[256..].iter().all(|&t| (t + 0.25).abs() < 1e-6));
+}
+
+#[test]
+fn test_import_rejects_malformed_theta() {
+    let mut mwso = MWSO::new(64);
+    let before = mwso.theta.clone();
+    assert!(!mwso.import_theta(&[]));
+    assert!(!mwso.import_theta(&[0.1, 0.2, 0.3]));
+    assert!(!mwso.import_theta(&[0.1, f32::NAN]));
+    assert_eq!(mwso.theta, before);
+}
+
+#[test]
+fn test_sharded_theta_is_split_per_shard() {
+    let mut ai = Singularity::new(8, vec![20]);
+    let shard_count = ai.sharded_mwso.as_ref().unwrap().shards.len();
+    let theta = ai.export_theta();
+    let shard_len = theta.len() / shard_count;
+    let modified: Vec<f32> = (0..theta.len()).map(|i| (i / shard_len) as f32 * 0.1).collect();
+    assert!(ai.import_theta(&modified));
+    for (k, shard) in ai.sharded_mwso.as_ref().unwrap().shards.iter().enumerate() {
+        assert!(shard.export_theta().iter().all(|&t| (t - k as f32 * 0.1).abs() < 1e-6));
+    }
+    assert!(!ai.import_theta(&modified[..modified.len() - 1]));
+}
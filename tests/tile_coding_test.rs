@@ -0,0 +1,55 @@
+use dark_singularity::core::encoder::{StateEncoder, TileCoder};
+use dark_singularity::core::singularity::Singularity;
+
+fn shared_tiles(coder: &TileCoder, a: &[f32], b: &[f32]) -> usize {
+    let ta = coder.active_tiles(a);
+    let tb = coder.active_tiles(b);
+    ta.iter().zip(&tb).filter(|(x, y)| x == y).count()
+}
+
+#[test]
+fn test_nearby_inputs_share_more_tiles() {
+    let coder = TileCoder::new(vec![(0.0, 100.0), (0.0, 1.0)], 8, 8);
+    let base = [40.0, 0.5];
+    assert_eq!(coder.active_tiles(&base).len(), 8);
+    assert_eq!(shared_tiles(&coder, &base, &base), 8);
+
+    let near = shared_tiles(&coder, &base, &[42.0, 0.52]);
+    let far = shared_tiles(&coder, &base, &[90.0, 0.1]);
+    assert!(near > far, "near={} far={}", near, far);
+    assert!(near >= 4);
+    assert_eq!(far, 0);
+}
+
+#[test]
+fn test_out_of_range_inputs_are_clamped() {
+    let coder = TileCoder::new(vec![(0.0, 1.0)], 4, 4);
+    assert_eq!(coder.active_tiles(&[-5.0]), coder.active_tiles(&[0.0]));
+    assert_eq!(coder.active_tiles(&[7.0]), coder.active_tiles(&[1.0]));
+
+    let weights = coder.encode(&[0.3], &StateEncoder::hashed(256));
+    let total: f32 = weights.iter().map(|&(_, w)| w).sum();
+    assert!((total - 1.0).abs() < 1e-5);
+    assert!(weights.iter().all(|&(idx, _)| idx < 256));
+}
+
+#[test]
+fn test_continuous_selection_generalizes_to_similar_inputs() {
+    let mut ai = Singularity::new(1024, vec![3]);
+    assert!(ai.select_actions_continuous(&[0.5]).is_none());
+    ai.set_tile_coder(TileCoder::new(vec![(0.0, 100.0)], 10, 8));
+
+    // 距離 20 付近では行動1が正解
+    for i in 0..40 {
+        let d = 18.0 + (i % 5) as f32;
+        let a = ai.select_actions_continuous(&[d]).unwrap()[0];
+        ai.learn(if a == 1 { 1.0 } else { -0.5 });
+    }
+    // 未学習だが近い入力でも行動1が選ばれやすい
+    let hits = (0..20).filter(|_| {
+        let a = ai.select_actions_continuous(&[23.5]).unwrap()[0];
+        ai.learn(0.0);
+        a == 1
+    }).count();
+    assert!(hits >= 10, "Similar input should reuse learned preference ({} / 20)", hits);
+}
@@ -0,0 +1,66 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+
+fn evolved(dim: usize) -> MWSO {
+    let mut mwso = MWSO::new(dim);
+    for s in 0..4 {
+        mwso.inject_state(s * 5, 1.0, &[]);
+        mwso.step_core(0.1, 0.0, 0.5, 0.5, &[]);
+    }
+    mwso
+}
+
+fn argmax(scores: &[f32]) -> usize {
+    scores.iter().enumerate().max_by(|a, b| a.1.total_cmp(b.1)).unwrap().0
+}
+
+#[test]
+fn test_candidates_are_exact_and_others_rank_below() {
+    let mut mwso = evolved(1024);
+    let exact = mwso.get_action_scores(0, 256, 0.0, &[]);
+    mwso.score_top_k = 16;
+    let partial = mwso.get_action_scores(0, 256, 0.0, &[]);
+
+    let candidates: Vec<usize> = (0..256).filter(|&i| partial[i] == exact[i]).collect();
+    assert!(candidates.len() >= 16, "top-k candidates keep their exact score");
+    let floor = candidates.iter().map(|&i| partial[i]).fold(f32::INFINITY, f32::min);
+    for i in (0..256).filter(|i| !candidates.contains(i)) {
+        assert!(partial[i] < floor);
+    }
+    assert_eq!(argmax(&partial), argmax(&exact), "the best action survives the coarse pass");
+}
+
+#[test]
+fn test_all_but_one_exact_under_penalty() {
+    let mut mwso = evolved(512);
+    let mut penalty = vec![0.0; 512];
+    for p in penalty.iter_mut().skip(64).take(64) { *p = 1.0; }
+    let exact = mwso.get_action_scores(0, 64, 0.0, &penalty);
+    mwso.score_top_k = 63;
+    let partial = mwso.get_action_scores(0, 64, 0.0, &penalty);
+    // 63 件は正確、残る 1 件は推定の最下位
+    assert_eq!(partial.iter().zip(&exact).filter(|(p, e)| p == e).count(), 63);
+    assert!(partial.iter().all(|s| s.is_finite()));
+}
+
+#[test]
+fn test_top_k_disabled_or_covering_all_is_exact() {
+    let mut mwso = evolved(256);
+    let exact = mwso.get_action_scores(0, 8, 0.0, &[]);
+    mwso.score_top_k = 8;
+    assert_eq!(mwso.get_action_scores(0, 8, 0.0, &[]), exact);
+    mwso.score_top_k = 0;
+    assert_eq!(mwso.get_action_scores(0, 8, 0.0, &[]), exact);
+}
+
+#[test]
+fn test_singularity_selects_with_top_k() {
+    let mut ai = Singularity::new(16, vec![200]);
+    ai.set_score_top_k(8);
+    assert!(ai.sharded_mwso.as_ref().unwrap().shards.iter().all(|s| s.score_top_k == 8));
+    for i in 0..30 {
+        let actions = ai.select_actions(i % 16);
+        assert!((actions[0] as usize) < 200);
+        ai.learn(if actions[0] % 2 == 0 { 1.0 } else { -0.5 });
+    }
+}
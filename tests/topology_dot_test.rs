@@ -0,0 +1,38 @@
+use dark_singularity::core::node::{NodeSpec, NODE_FEAR, NODE_REFLEX};
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_dot_lists_nodes_with_activations() {
+    let mut ai = Singularity::new(8, vec![4]);
+    ai.set_neuron_state(ai.node_index(NODE_FEAR).unwrap(), 0.25);
+    let dot = ai.export_topology_dot();
+    assert!(dot.starts_with("digraph topology {"));
+    assert!(dot.ends_with('}'));
+    assert!(dot.contains("n1 [label=\"fear\\n0.250\""), "{dot}");
+    assert!(!dot.contains("->"), "a fresh instance has no synapses");
+}
+
+#[test]
+fn test_dot_edges_follow_synapses() {
+    let mut ai = Singularity::new(8, vec![4]);
+    let fear = ai.node_index(NODE_FEAR).unwrap();
+    let reflex = ai.node_index(NODE_REFLEX).unwrap();
+    ai.set_neuron_state(fear, 0.5);
+    ai.reshape_topology();
+    let edges = ai.nodes.iter().map(|n| n.synapses.len()).sum::<usize>();
+    let dot = ai.export_topology_dot();
+    assert_eq!(dot.matches("->").count(), edges);
+    // fear のシナプスは reflex から入力を受ける
+    assert!(dot.contains(&format!("n{reflex} -> n{fear} [label=\"1.000\", color=black")), "{dot}");
+
+    ai.nodes[fear].synapses[0].weight = -0.5;
+    assert!(ai.export_topology_dot().contains("label=\"-0.500\", color=blue"));
+}
+
+#[test]
+fn test_dot_escapes_and_names_unnamed_nodes() {
+    let ai = Singularity::with_nodes(8, vec![4], &[NodeSpec::new("", 0.3), NodeSpec::new("say \"hi\"", 0.3)]);
+    let dot = ai.export_topology_dot();
+    assert!(dot.contains("n0 [label=\"node0\\n"));
+    assert!(dot.contains("n1 [label=\"say \\\"hi\\\"\\n"), "{dot}");
+}
@@ -0,0 +1,71 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::wave::from_wave;
+use dark_singularity::core::wave_delta::{WaveDeltaStream, decode_delta};
+use dark_singularity::core::wave_view::WaveFrame;
+
+fn max_error(frame: &WaveFrame, mwso: &MWSO) -> f32 {
+    frame.psi_real.iter().zip(&mwso.psi_real).chain(frame.psi_imag.iter().zip(&mwso.psi_imag))
+        .map(|(&a, &b)| from_wave((a - b).abs()))
+        .fold(0.0, f32::max)
+}
+
+#[test]
+fn test_stream_tracks_wave_without_drift() {
+    let mut mwso = MWSO::new(4096);
+    let mut stream = WaveDeltaStream::default();
+    let mut receiver = WaveFrame::default();
+    let full_size = 4096 * 2 * 4;
+    for t in 0..200 {
+        if t % 20 == 0 { mwso.inject_state(t / 20, 1.0, &[]); }
+        mwso.step_core(0.1, 0.0, 0.5, 0.5, &[]);
+        let bytes = stream.next(&mwso.psi_real, &mwso.psi_imag);
+        if t > 0 { assert!(bytes.len() <= full_size / 3, "frame {} is {} bytes", t, bytes.len()); }
+        receiver = decode_delta(&receiver, &bytes).expect("decodable");
+        assert_eq!(receiver.step, t as u64 + 1);
+        assert_eq!(&receiver, stream.reference());
+    }
+    let amplitude = mwso.psi_real.iter().map(|v| from_wave(v.abs())).fold(0.0, f32::max);
+    assert!(max_error(&receiver, &mwso) < amplitude * 0.05, "error {}", max_error(&receiver, &mwso));
+}
+
+#[test]
+fn test_unchanged_wave_encodes_to_header_only() {
+    let mwso = MWSO::new(1024);
+    let mut prev = WaveFrame { step: 7, psi_real: mwso.psi_real.clone(), psi_imag: mwso.psi_imag.clone(), theta: Vec::new() };
+    let bytes = mwso.encode_delta(&prev);
+    assert!(bytes.len() < 40, "{} bytes", bytes.len());
+    prev = decode_delta(&prev, &bytes).unwrap();
+    assert_eq!(prev.step, 8);
+    assert_eq!(prev.psi_real, mwso.psi_real);
+}
+
+#[test]
+fn test_sparse_change_is_small_and_exact_in_place() {
+    let mut mwso = MWSO::new(1024);
+    let prev = WaveFrame { step: 0, psi_real: mwso.psi_real.clone(), psi_imag: mwso.psi_imag.clone(), theta: Vec::new() };
+    mwso.psi_real[10] += 0.5;
+    mwso.psi_imag[900] -= 0.25;
+    let bytes = mwso.encode_delta(&prev);
+    assert!(bytes.len() < 50, "{} bytes", bytes.len());
+
+    let mut replica = MWSO::new(1024);
+    assert!(replica.apply_delta(&bytes));
+    assert!(max_error(&WaveFrame { step: 0, psi_real: replica.psi_real.clone(), psi_imag: replica.psi_imag.clone(), theta: Vec::new() }, &mwso) < 1e-6);
+}
+
+#[test]
+fn test_dimension_change_sends_keyframe_and_rejects_garbage() {
+    let small = MWSO::new(64);
+    let mut replica = MWSO::new(128);
+    let keyframe = small.encode_delta(&WaveFrame::default());
+    assert!(!replica.apply_delta(&keyframe), "keyframe for another dimension");
+    let before = replica.psi_real.clone();
+    assert!(!replica.apply_delta(b"WDLT\x01garbage"));
+    assert!(!replica.apply_delta(&keyframe[..keyframe.len() - 1]));
+    assert_eq!(replica.psi_real, before);
+
+    let mut same = MWSO::new(64);
+    same.psi_real.iter_mut().for_each(|v| *v = 5.0);
+    assert!(same.apply_delta(&keyframe));
+    assert!(max_error(&WaveFrame { step: 0, psi_real: same.psi_real.clone(), psi_imag: same.psi_imag.clone(), theta: Vec::new() }, &small) < 1e-4);
+}
@@ -0,0 +1,59 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::wave::to_wave;
+
+#[test]
+fn test_fresh_wave_diagnostics() {
+    let mwso = MWSO::new(1024);
+    let diag = mwso.diagnostics();
+    // psi_real = 0.01 で一様
+    assert!((diag.norm - (1024.0f32 * 0.0001).sqrt()).abs() < 1e-4);
+    assert!(diag.spectral_entropy > 0.999);
+    assert_eq!(diag.memory_resonance, 0.0);
+    assert_eq!(diag.gravity_saturation, 0.0);
+    assert_eq!(diag.entanglement_count, mwso.entanglements.len());
+}
+
+#[test]
+fn test_dominant_bin_and_saturation() {
+    let mut mwso = MWSO::new(1024);
+    mwso.psi_real[300] = to_wave(5.0);
+    for g in &mut mwso.gravity_field[..256] { *g = 1.0; }
+    let diag = mwso.diagnostics();
+    assert_eq!(diag.dominant_bin, 300);
+    assert_eq!(diag.dominant_frequency, mwso.frequencies[300]);
+    assert!(diag.spectral_entropy < 0.9);
+    assert!((diag.gravity_saturation - 0.25).abs() < 1e-6);
+}
+
+#[test]
+fn test_memory_resonance_matches_imprinted_wave() {
+    let mut mwso = MWSO::new(1024);
+    for i in 0..1024 {
+        mwso.q_memory_re[i] = (i as f64 * 0.37).cos();
+        mwso.q_memory_im[i] = (i as f64 * 0.37).sin();
+    }
+    // 位相が揃った波と記憶は完全に重なる（全体の位相差は問わない）
+    for i in 0..1024 {
+        mwso.psi_real[i] = to_wave((i as f32 * 0.37 + 1.0).cos() * 2.0);
+        mwso.psi_imag[i] = to_wave((i as f32 * 0.37 + 1.0).sin() * 2.0);
+    }
+    assert!((mwso.diagnostics().memory_resonance - 1.0).abs() < 1e-3);
+}
+
+#[test]
+fn test_diagnostics_after_training_and_snapshot() {
+    let mut ai = Singularity::new(16, vec![4, 3]);
+    for i in 0..40 {
+        ai.select_actions(i % 16);
+        ai.learn(if i % 2 == 0 { 1.5 } else { -0.3 });
+    }
+    let diag = ai.mwso.diagnostics();
+    assert!((diag.norm - ai.wave_norm()).abs() < 1e-3 * diag.norm.max(1.0));
+    assert!((0.0..=1.0).contains(&diag.memory_resonance));
+    assert!(diag.dominant_bin < ai.mwso.dim);
+
+    let path = std::env::temp_dir().join("ds_wave_diagnostics_test.png");
+    assert!(ai.generate_visual_snapshot(path.to_str().unwrap()));
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,38 @@
+use dark_singularity::core::singularity::Singularity;
+use dark_singularity::core::wave::{Wave, from_wave, to_wave};
+
+#[test]
+fn test_wave_precision_follows_feature() {
+    let expected = if cfg!(feature = "f64-wave") { 8 } else { 4 };
+    assert_eq!(std::mem::size_of::<Wave>(), expected);
+}
+
+#[test]
+fn test_long_run_stays_finite_and_normalized() {
+    let mut ai = Singularity::new(32, vec![4, 3]);
+    for i in 0..300 {
+        ai.select_actions(i * 7 % 32);
+        ai.learn(if i % 3 == 0 { 1.5 } else { -0.7 });
+    }
+    assert!(ai.mwso.psi_real.iter().chain(&ai.mwso.psi_imag).chain(&ai.mwso.theta).all(|v| v.is_finite()));
+    let norm = ai.wave_norm();
+    assert!(norm > 0.5 && norm < 3.0, "Wave norm drifted to {}", norm);
+}
+
+#[test]
+fn test_checkpoint_stores_wave_as_f32() {
+    let mut ai = Singularity::new(16, vec![4]);
+    for i in 0..20 {
+        ai.select_actions(i % 16);
+        ai.learn(if i % 2 == 0 { 1.0 } else { -0.5 });
+    }
+    let path = std::env::temp_dir().join("ds_wave_precision_test.dsym");
+    ai.save_to_file(path.to_str().unwrap()).unwrap();
+    let mut loaded = Singularity::new(16, vec![4]);
+    loaded.load_from_file(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    // ファイル形式は精度に依らず f32 なので、f64 モードでは f32 に丸めた値が戻る
+    let rounded: Vec<Wave> = ai.mwso.psi_real.iter().map(|&v| to_wave(from_wave(v))).collect();
+    assert_eq!(loaded.mwso.psi_real, rounded);
+}
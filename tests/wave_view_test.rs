@@ -0,0 +1,113 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[test]
+fn test_view_publishes_after_each_step() {
+    let mut mwso = MWSO::new(64);
+    let view = mwso.wave_view();
+    let first = view.load();
+    assert_eq!(first.step, 1, "opening a view publishes the current wave");
+    assert_eq!(first.psi_real, mwso.psi_real);
+
+    mwso.step_core(0.1, 0.0, 0.5, 0.5, &[]);
+    let second = view.load();
+    assert_eq!(second.step, 2);
+    assert_eq!(second.psi_real, mwso.psi_real);
+    assert_eq!(second.theta, mwso.theta);
+
+    // 読み手が持っているフレームは以後の更新で書き換わらない
+    let held = second.psi_real.clone();
+    for _ in 0..3 { mwso.step_core(0.1, 0.0, 0.5, 0.5, &[]); }
+    assert_eq!(second.psi_real, held);
+    assert_eq!(view.load().step, 5);
+}
+
+#[test]
+fn test_publishing_stops_when_all_views_are_dropped() {
+    let mut mwso = MWSO::new(64);
+    let view = mwso.wave_view();
+    let copy = view.clone();
+    for _ in 0..2 { mwso.step_core(0.1, 0.0, 0.5, 0.5, &[]); }
+    drop(view);
+    mwso.step_core(0.1, 0.0, 0.5, 0.5, &[]);
+    assert_eq!(copy.load().step, 4, "a remaining clone keeps publishing alive");
+
+    drop(copy);
+    for _ in 0..3 { mwso.step_core(0.1, 0.0, 0.5, 0.5, &[]); }
+    // 読み手がいない間は写しを作らず、新しいハンドルで公開を再開する
+    let reopened = mwso.wave_view();
+    assert_eq!(reopened.load().step, 5);
+    assert_eq!(reopened.load().psi_real, mwso.psi_real);
+}
+
+#[test]
+fn test_cloned_operator_does_not_publish_to_original_view() {
+    let mut mwso = MWSO::new(32);
+    let view = mwso.wave_view();
+    let mut copy = mwso.clone();
+    copy.step_core(0.1, 0.0, 0.5, 0.5, &[]);
+    assert_eq!(view.load().step, 1);
+}
+
+#[test]
+fn test_concurrent_reader_sees_consistent_frames() {
+    let mut ai = Singularity::new(16, vec![8]);
+    let view = ai.wave_view();
+    let dim = ai.mwso.dim;
+    let done = Arc::new(AtomicBool::new(false));
+
+    let reader = {
+        let done = Arc::clone(&done);
+        std::thread::spawn(move || {
+            let (mut reads, mut last_step) = (0u64, 0u64);
+            while !done.load(Ordering::Relaxed) || reads == 0 {
+                let frame = view.load();
+                assert_eq!(frame.dim(), dim);
+                assert_eq!(frame.psi_imag.len(), dim);
+                assert!(frame.step >= last_step, "frames must not go back in time");
+                last_step = frame.step;
+                reads += 1;
+            }
+            last_step
+        })
+    };
+
+    for i in 0..50 {
+        ai.select_actions(i % 16);
+        ai.learn(if i % 3 == 0 { 1.0 } else { -0.2 });
+    }
+    done.store(true, Ordering::Relaxed);
+    let last_step = reader.join().unwrap();
+    assert!(last_step >= 1);
+}
+
+#[test]
+fn test_sharded_view_tracks_every_shard() {
+    let mut ai = Singularity::new(16, vec![40]);
+    let view = ai.wave_view();
+    for i in 0..5 {
+        ai.select_actions(i);
+        ai.learn(1.0);
+    }
+    let frame = view.load();
+    let sharded = ai.sharded_mwso.as_ref().expect("40 actions are sharded");
+    assert!(frame.step > 1, "shard steps must publish");
+    let psi_real: Vec<_> = sharded.shards.iter().flat_map(|s| s.psi_real.iter().copied()).collect();
+    let theta: Vec<_> = sharded.shards.iter().flat_map(|s| s.theta.iter().copied()).collect();
+    assert_eq!(frame.psi_real, psi_real);
+    assert_eq!(frame.theta, theta);
+}
+
+#[test]
+fn test_frames_survive_operator_drop() {
+    let mut mwso = MWSO::new(32);
+    let view = mwso.wave_view();
+    let held = view.load();
+    for _ in 0..4 { mwso.step_core(0.1, 0.0, 0.5, 0.5, &[]); }
+    let latest = mwso.psi_real.clone();
+    drop(mwso);
+    assert_eq!(held.step, 1);
+    assert_eq!(view.load().psi_real, latest);
+}
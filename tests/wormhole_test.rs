@@ -0,0 +1,72 @@
+use dark_singularity::core::mwso::MWSO;
+use dark_singularity::core::singularity::Singularity;
+
+#[test]
+fn test_add_list_and_remove() {
+    let mut mwso = MWSO::new(64);
+    mwso.entanglements.clear();
+    mwso.add_wormhole(1, 2, 0.5);
+    mwso.add_wormhole(3, 4, 0.2);
+    // 同じ組は増えずに強度が上書きされる
+    mwso.add_wormhole(1, 2, 0.7);
+    assert_eq!(mwso.list_wormholes(), &[(1, 2, 0.7), (3, 4, 0.2)]);
+
+    assert!(mwso.remove_wormhole(1, 2));
+    assert!(!mwso.remove_wormhole(1, 2));
+    assert_eq!(mwso.list_wormholes(), &[(3, 4, 0.2)]);
+}
+
+#[test]
+fn test_decay_prunes_weak_links() {
+    let mut mwso = MWSO::new(64);
+    mwso.entanglements.clear();
+    mwso.add_wormhole(1, 2, 1.0);
+    mwso.add_wormhole(3, 4, 0.015);
+    assert_eq!(mwso.decay_wormholes(0.5), 1);
+    assert_eq!(mwso.list_wormholes(), &[(1, 2, 0.5)]);
+}
+
+#[test]
+fn test_cap_evicts_weakest_links() {
+    let mut mwso = MWSO::new(64);
+    mwso.entanglements.clear();
+    mwso.set_wormhole_cap(3);
+    for (i, strength) in [0.4, 0.1, 0.9, 0.3, 0.2].into_iter().enumerate() {
+        mwso.add_wormhole(i, i + 10, strength);
+    }
+    assert_eq!(mwso.list_wormholes(), &[(0, 10, 0.4), (2, 12, 0.9), (3, 13, 0.3)]);
+
+    mwso.set_wormhole_cap(1);
+    assert_eq!(mwso.list_wormholes(), &[(2, 12, 0.9)]);
+}
+
+#[test]
+fn test_singularity_wrappers() {
+    let mut ai = Singularity::new(8, vec![4]);
+    let initial = ai.list_wormholes().len();
+    ai.add_wormhole(0, 2, 0.5);
+    assert_eq!(ai.list_wormholes().len(), initial + 1);
+    assert!(ai.remove_wormhole(0, 2));
+    assert_eq!(ai.list_wormholes().len(), initial);
+
+    // 初期の弱い結合 (0.05) は減衰で消える
+    assert_eq!(ai.decay_wormholes(0.1), initial);
+    assert!(ai.list_wormholes().is_empty());
+}
+
+#[test]
+fn test_wormholes_survive_reload() {
+    let mut ai = Singularity::new(8, vec![4]);
+    ai.add_wormhole(0, 3, 0.8);
+    ai.set_wormhole_cap(500);
+    let path = std::env::temp_dir().join("ds_wormhole_test.dsym");
+    ai.save_to_file(path.to_str().unwrap()).unwrap();
+
+    let mut loaded = Singularity::new(8, vec![4]);
+    loaded.mwso.entanglements.clear();
+    loaded.load_from_file(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(loaded.list_wormholes(), ai.list_wormholes());
+    assert_eq!(loaded.mwso.wormhole_cap, 500);
+}